@@ -1,4 +1,7 @@
-//! LogLine Parser/Serializer stub implementation.
+//! LogLine Parser/Serializer: grammar-driven span encoding.
+//!
+//! Spans are parsed by a real LALR grammar (`grammar.lalrpop`, compiled at
+//! build time by lalrpop) rather than hand-rolled line splitting.
 pub mod ast;
 pub mod parser;
 pub mod serializer;
@@ -1,31 +1,182 @@
-use crate::ast::{LogLineSpan, LogLineValue};
+//! Grammar-driven LogLine parser.
+//!
+//! The grammar itself lives in `grammar.lalrpop` and is compiled by `build.rs`
+//! via lalrpop into a real LALR parser; this module just adapts lalrpop's
+//! generic error type into a `ParseError` with byte offset, line/column, and
+//! the expected-token set, so callers (including the NAPI layer) get a
+//! structured failure instead of a panic.
+use crate::ast::LogLineSpan;
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+lalrpop_util::lalrpop_mod!(
+    #[allow(clippy::all)]
+    grammar
+);
+
+/// A LogLine parse failure, precise enough for a caller to render a pointer
+/// into the offending line or re-surface it as a JS exception.
+#[derive(Debug, Error, PartialEq)]
 pub enum ParseError {
     #[error("empty input")]
     Empty,
-    #[error("invalid header line")]
-    InvalidHeader,
+    #[error("unexpected token {token:?} at line {line}, column {column} (byte {offset}); expected one of {expected:?}")]
+    UnexpectedToken {
+        offset: usize,
+        line: usize,
+        column: usize,
+        token: String,
+        expected: Vec<String>,
+    },
+    #[error("unexpected end of input; expected one of {expected:?}")]
+    UnexpectedEof { expected: Vec<String> },
+    #[error("invalid token at line {line}, column {column} (byte {offset})")]
+    InvalidToken { offset: usize, line: usize, column: usize },
+}
+
+fn line_col(input: &str, offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+    for ch in input[..offset].chars() {
+        if ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
 }
 
-/// Minimal parser: expects first line as `TYPE: name` and key/value pairs until `END`.
+/// Parse a LogLine span. Malformed input returns a structured [`ParseError`]
+/// rather than panicking.
 pub fn parse_logline(input: &str) -> Result<LogLineSpan, ParseError> {
-    let mut lines = input.lines().map(str::trim).filter(|l| !l.is_empty());
-    let header = lines.next().ok_or(ParseError::Empty)?;
-    let mut parts = header.splitn(2, ':');
-    let r#type = parts.next().ok_or(ParseError::InvalidHeader)?.to_lowercase();
-    let name = parts.next().map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
-
-    let mut params = Vec::new();
-    for line in lines {
-        if line.eq_ignore_ascii_case("END") {
-            break;
+    if input.trim().is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    grammar::SpanParser::new().parse(input).map_err(|e| convert_error(input, e))
+}
+
+fn convert_error(input: &str, err: lalrpop_util::ParseError<usize, grammar::Token<'_>, &str>) -> ParseError {
+    use lalrpop_util::ParseError::*;
+    match err {
+        InvalidToken { location } => {
+            let (line, column) = line_col(input, location);
+            ParseError::InvalidToken { offset: location, line, column }
+        }
+        UnrecognizedEof { expected, .. } => ParseError::UnexpectedEof { expected },
+        UnrecognizedToken { token: (start, tok, _end), expected } => {
+            let (line, column) = line_col(input, start);
+            ParseError::UnexpectedToken { offset: start, line, column, token: tok.1.to_string(), expected }
         }
-        if let Some((k, v)) = line.split_once(':') {
-            params.push((k.trim().to_lowercase(), LogLineValue::Str(v.trim().to_string())));
+        ExtraToken { token: (start, tok, _end) } => {
+            let (line, column) = line_col(input, start);
+            ParseError::UnexpectedToken { offset: start, line, column, token: tok.1.to_string(), expected: vec![] }
         }
+        User { error } => ParseError::UnexpectedToken {
+            offset: 0,
+            line: 1,
+            column: 1,
+            token: error.to_string(),
+            expected: vec![],
+        },
+    }
+}
+
+/// Strip the surrounding quotes from a `STRING` token and resolve its escapes.
+pub(crate) fn unescape_string(raw: &str) -> String {
+    let inner = &raw[1..raw.len() - 1];
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                Some(other) => out.push(other),
+                None => {}
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::LogLineValue;
+    use crate::serializer::serialize_logline;
+
+    #[test]
+    fn parses_simple_span() {
+        let input = "TASK: build_feature\n  OWNER: \"alice\"\n  PRIORITY: 3\n  URGENT: true\nEND";
+        let span = parse_logline(input).unwrap();
+        assert_eq!(span.r#type, "task");
+        assert_eq!(span.name.as_deref(), Some("build_feature"));
+        assert_eq!(span.params[0], ("owner".to_string(), LogLineValue::Str("alice".to_string())));
+        assert_eq!(span.params[1], ("priority".to_string(), LogLineValue::Num(3.0)));
+        assert_eq!(span.params[2], ("urgent".to_string(), LogLineValue::Bool(true)));
+    }
+
+    #[test]
+    fn parses_nested_list() {
+        let input = "TASK: t\n  TAGS: [\"a\", \"b\", 1]\nEND";
+        let span = parse_logline(input).unwrap();
+        assert_eq!(
+            span.params[0].1,
+            LogLineValue::List(vec![
+                LogLineValue::Str("a".to_string()),
+                LogLineValue::Str("b".to_string()),
+                LogLineValue::Num(1.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_escaped_string() {
+        let input = "TASK: t\n  NOTE: \"line one\\nline two\"\nEND";
+        let span = parse_logline(input).unwrap();
+        assert_eq!(span.params[0].1, LogLineValue::Str("line one\nline two".to_string()));
     }
 
-    Ok(LogLineSpan { r#type, name, params })
+    #[test]
+    fn round_trips_canonical_input() {
+        let canonical = serialize_logline(&LogLineSpan {
+            r#type: "task".to_string(),
+            name: Some("build_feature".to_string()),
+            params: vec![
+                ("owner".to_string(), LogLineValue::Str("alice".to_string())),
+                ("priority".to_string(), LogLineValue::Num(3.0)),
+                (
+                    "tags".to_string(),
+                    LogLineValue::List(vec![LogLineValue::Str("a".to_string()), LogLineValue::Bool(true)]),
+                ),
+            ],
+        });
+
+        let parsed = parse_logline(&canonical).unwrap();
+        assert_eq!(serialize_logline(&parsed), canonical);
+    }
+
+    #[test]
+    fn empty_input_is_rejected() {
+        assert_eq!(parse_logline(""), Err(ParseError::Empty));
+        assert_eq!(parse_logline("   \n  "), Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn missing_end_reports_unexpected_eof() {
+        let err = parse_logline("TASK: t\n  OWNER: \"alice\"").unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedEof { .. }));
+    }
+
+    #[test]
+    fn malformed_header_reports_unexpected_token() {
+        let err = parse_logline("123: t\nEND").unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedToken { .. }));
+    }
 }
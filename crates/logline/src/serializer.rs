@@ -22,7 +22,7 @@ pub fn serialize_logline(span: &LogLineSpan) -> String {
 
 fn render_value(v: &LogLineValue) -> String {
     match v {
-        LogLineValue::Str(s) => s.clone(),
+        LogLineValue::Str(s) => quote_string(s),
         LogLineValue::Num(n) => n.to_string(),
         LogLineValue::Bool(b) => b.to_string(),
         LogLineValue::List(items) => {
@@ -31,3 +31,20 @@ fn render_value(v: &LogLineValue) -> String {
         }
     }
 }
+
+/// Quote and escape a string so it round-trips through the grammar's `STRING` token.
+fn quote_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -1,11 +1,17 @@
 //! API Handlers
+use std::sync::Arc;
+
 use axum::{
-    extract::Path,
-    http::StatusCode,
+    extract::{Path, State},
+    http::{header, StatusCode},
     Json,
 };
+use serde::Deserialize;
 use serde_json::{json, Value};
-use tdln_core::data_model::{InputPack, CompiledArtifact};
+use tdln_core::data_model::{Citation, Evidence, InputPack, CompiledArtifact};
+use tdln_core::merkle;
+
+use crate::policy_state::PolicyState;
 
 pub async fn compile(
     Json(payload): Json<InputPack>,
@@ -21,9 +27,20 @@ pub async fn compile(
     )
 }
 
-pub async fn verify(Json(payload): Json<Value>) -> (StatusCode, Json<Value>) {
-    // TODO: Verificar proof
-    (StatusCode::OK, Json(json!({ "ok": true })))
+pub async fn verify(Json(payload): Json<CompiledArtifact>) -> (StatusCode, Json<Value>) {
+    let ok = merkle::verify_all(&payload.citations, payload.proof.oracle.as_ref());
+
+    if ok {
+        (StatusCode::OK, Json(json!({ "ok": true })))
+    } else {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "ok": false,
+                "error": "one or more citations failed Merkle verification against the declared oracle root",
+            })),
+        )
+    }
 }
 
 pub async fn get_artifact(
@@ -42,10 +59,53 @@ pub async fn list_grammars() -> (StatusCode, Json<Value>) {
     )
 }
 
-pub async fn seal_truthpack(Json(payload): Json<Value>) -> (StatusCode, Json<Value>) {
-    (StatusCode::OK, Json(json!({ "merkle_root": "0x..." })))
+#[derive(Debug, Deserialize)]
+pub struct SealTruthpackRequest {
+    pub oracle_id: String,
+    #[serde(default)]
+    pub citations: Vec<Citation>,
+    #[serde(default)]
+    pub evidence: Vec<Evidence>,
+}
+
+pub async fn seal_truthpack(Json(mut payload): Json<SealTruthpackRequest>) -> (StatusCode, Json<Value>) {
+    let oracle = merkle::seal(payload.oracle_id, &mut payload.citations, &payload.evidence);
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "oracle": oracle,
+            "citations": payload.citations,
+        })),
+    )
 }
 
 pub async fn health() -> (StatusCode, Json<Value>) {
     (StatusCode::OK, Json(json!({ "status": "ok", "version": "1.0.0" })))
+}
+
+/// Prometheus scrape endpoint: text-exposition-format encoding of
+/// `policy.metrics().registry()` (see [`tdln_policy::PolicyMetrics`]).
+pub async fn metrics(State(policy): State<Arc<PolicyState>>) -> (StatusCode, [(header::HeaderName, &'static str); 1], String) {
+    match crate::metrics::encode(policy.metrics().registry()) {
+        Ok(body) => (StatusCode::OK, [(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            e.to_string(),
+        ),
+    }
+}
+
+/// Control endpoint: reload the active policy from its backing adapter and
+/// atomically swap it in. The same reload [`crate::run`] also triggers on
+/// SIGHUP, exposed here for deployments that can't send signals.
+pub async fn reload_policy(State(policy): State<Arc<PolicyState>>) -> (StatusCode, Json<Value>) {
+    match policy.reload() {
+        Ok(reloaded) => (StatusCode::OK, Json(json!({ "ok": true, "id": reloaded.id }))),
+        Err(e) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({ "ok": false, "error": e.to_string() })),
+        ),
+    }
 }
\ No newline at end of file
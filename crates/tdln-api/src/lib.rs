@@ -2,6 +2,9 @@
 pub mod handlers;
 pub mod middleware;
 pub mod metrics;
+pub mod policy_state;
+
+use std::sync::Arc;
 
 use axum::{
     Router,
@@ -12,23 +15,72 @@ use axum::{
 use serde_json::json;
 use tower_http::trace::TraceLayer;
 
-pub async fn create_app() -> Router {
+use policy_state::PolicyState;
+
+/// Build the initial [`PolicyState`]: loaded from `TDLN_POLICY_PATH` if set
+/// (so a malformed file fails at startup), otherwise the built-in mechanic
+/// mode policy with no backing file.
+fn initial_policy_state() -> PolicyState {
+    match std::env::var("TDLN_POLICY_PATH") {
+        Ok(path) => PolicyState::load(path).expect("failed to load TDLN_POLICY_PATH"),
+        Err(_) => PolicyState::in_memory(tdln_policy::PolicySet::mechanic()),
+    }
+}
+
+fn build_router(policy: Arc<PolicyState>) -> Router {
     Router::new()
         .route("/v1/compile", post(handlers::compile))
         .route("/v1/verify", post(handlers::verify))
         .route("/v1/artifacts/:hash", get(handlers::get_artifact))
         .route("/v1/registry/grammars", get(handlers::list_grammars))
         .route("/v1/truthpack/seal", post(handlers::seal_truthpack))
+        .route("/v1/policy/reload", post(handlers::reload_policy))
         .route("/v1/health", get(handlers::health))
+        .route("/metrics", get(handlers::metrics))
         .layer(TraceLayer::new_for_http())
+        .with_state(policy)
+}
+
+pub async fn create_app() -> Router {
+    build_router(Arc::new(initial_policy_state()))
 }
 
+/// Reload the policy whenever the process receives SIGHUP, same effect as
+/// a `POST /v1/policy/reload`. No-op on non-Unix targets.
+#[cfg(unix)]
+fn spawn_sighup_reload(policy: Arc<PolicyState>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tokio::spawn(async move {
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!("failed to install SIGHUP handler: {e}");
+                return;
+            }
+        };
+        while hangup.recv().await.is_some() {
+            match policy.reload() {
+                Ok(reloaded) => tracing::info!("policy reloaded on SIGHUP: {}", reloaded.id),
+                Err(e) => tracing::warn!("policy reload on SIGHUP failed: {e}"),
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn spawn_sighup_reload(_policy: Arc<PolicyState>) {}
+
 pub async fn run(addr: &str) {
-    let app = create_app().await;
+    let policy = Arc::new(initial_policy_state());
+    spawn_sighup_reload(Arc::clone(&policy));
+
+    let app = build_router(policy);
+
     let listener = tokio::net::TcpListener::bind(addr)
         .await
         .expect("Failed to bind");
-    
+
     tracing::info!("TDLN API listening on {}", addr);
     axum::serve(listener, app)
         .await
@@ -1,4 +1,9 @@
-//! Minimal prometheus registry so `/metrics` can be added later.
+//! Prometheus encoding for the `/metrics` route (see [`crate::handlers::metrics`]).
+//!
+//! The registry itself lives on [`crate::policy_state::PolicyState`] as a
+//! [`tdln_policy::PolicyMetrics`], shared with the [`PolicySet`](tdln_policy::PolicySet)
+//! evaluations it serves, so this module only turns a gathered [`Registry`]
+//! into the text exposition format Prometheus scrapes.
 use prometheus::{Encoder, TextEncoder, Registry};
 
 pub fn registry() -> Registry {
@@ -0,0 +1,73 @@
+//! Hot-reloadable policy state for the API server.
+//!
+//! Wraps a [`tdln_policy::PolicySet`] behind an `Arc` so request handlers
+//! can cheaply clone the currently-active policy, while a reload swaps in
+//! a freshly loaded one atomically via [`PolicyState::reload`] -- triggered
+//! either by a SIGHUP (see [`crate::run`]) or by the `/v1/policy/reload`
+//! control endpoint (see [`crate::handlers::reload_policy`]).
+use std::sync::{Arc, RwLock};
+
+use tdln_policy::{AdapterError, FileAdapter, InMemoryAdapter, PolicyAdapter, PolicyMetrics, PolicySet};
+
+/// The API server's live policy, reloadable from its backing
+/// [`PolicyAdapter`] without restarting the process.
+pub struct PolicyState {
+    adapter: Box<dyn PolicyAdapter + Send + Sync>,
+    current: RwLock<Arc<PolicySet>>,
+    /// Prometheus counters/histograms for evaluations served by this state,
+    /// scraped via `GET /metrics` (see [`crate::handlers::metrics`]).
+    /// Injectable through [`PolicyState::with_metrics`] so tests can supply
+    /// their own registry instead of asserting against a process-global one.
+    metrics: PolicyMetrics,
+}
+
+impl PolicyState {
+    /// Load the initial policy from `path` up front, so a malformed file
+    /// fails at startup rather than on the first reload.
+    pub fn load(path: impl Into<std::path::PathBuf>) -> Result<Self, AdapterError> {
+        Self::from_adapter(FileAdapter::new(path))
+    }
+
+    /// No backing file configured -- serve `policy` and make `reload` a
+    /// no-op (it reloads the same in-memory value) until something else
+    /// calls [`PolicyAdapter::save`] on the same adapter.
+    pub fn in_memory(policy: PolicySet) -> Self {
+        Self::from_adapter(InMemoryAdapter::new(policy)).expect("an in-memory adapter always loads")
+    }
+
+    /// Replace this state's [`PolicyMetrics`] (and so its registry), e.g.
+    /// with one built via `PolicyMetrics::with_registry` so a test can
+    /// gather from a registry it also owns.
+    pub fn with_metrics(mut self, metrics: PolicyMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    fn from_adapter(adapter: impl PolicyAdapter + Send + Sync + 'static) -> Result<Self, AdapterError> {
+        let policy = adapter.load()?;
+        Ok(Self {
+            adapter: Box::new(adapter),
+            current: RwLock::new(Arc::new(policy)),
+            metrics: PolicyMetrics::new(),
+        })
+    }
+
+    /// The currently active policy.
+    pub fn current(&self) -> Arc<PolicySet> {
+        Arc::clone(&self.current.read().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
+
+    /// This state's policy-decision metrics, for handlers to record
+    /// evaluations against and for `GET /metrics` to encode.
+    pub fn metrics(&self) -> &PolicyMetrics {
+        &self.metrics
+    }
+
+    /// Reload from the backing adapter and atomically swap it in. Leaves
+    /// the previous policy in place if the reload fails.
+    pub fn reload(&self) -> Result<Arc<PolicySet>, AdapterError> {
+        let policy = Arc::new(self.adapter.load()?);
+        *self.current.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::clone(&policy);
+        Ok(policy)
+    }
+}
@@ -0,0 +1,78 @@
+//! Pluggable result cache for [`crate::runner::PipelineRunner`].
+//!
+//! A deterministic stage run on an input it has already seen produces the
+//! same output by definition, so re-running it is wasted work on pipelines
+//! that reprocess mostly-unchanged inputs. [`StageCache`] lets a caller wire
+//! in whatever backend fits -- an in-memory LRU, Redis, a content-addressed
+//! blob store -- without the runner needing to know which.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// A content-addressed cache of stage outputs, keyed by stage ID and the
+/// blake3 hash of the stage's input bytes.
+pub trait StageCache: Send + Sync {
+    /// Look up a previously cached output for `stage_id` run on input
+    /// hashing to `in_hash`.
+    fn get(&self, stage_id: &str, in_hash: &str) -> Option<Vec<u8>>;
+
+    /// Record `output` as the result of running `stage_id` on input hashing
+    /// to `in_hash`.
+    fn put(&self, stage_id: &str, in_hash: &str, output: Vec<u8>);
+}
+
+/// An unbounded in-process [`StageCache`] backed by a `HashMap`. Good enough
+/// for a single pipeline run or a short-lived worker process; callers that
+/// need eviction or cross-process sharing should implement [`StageCache`]
+/// against their own store instead.
+#[derive(Default)]
+pub struct InMemoryStageCache {
+    entries: Mutex<HashMap<(String, String), Vec<u8>>>,
+}
+
+impl InMemoryStageCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StageCache for InMemoryStageCache {
+    fn get(&self, stage_id: &str, in_hash: &str) -> Option<Vec<u8>> {
+        self.entries
+            .lock()
+            .expect("stage cache mutex poisoned")
+            .get(&(stage_id.to_string(), in_hash.to_string()))
+            .cloned()
+    }
+
+    fn put(&self, stage_id: &str, in_hash: &str, output: Vec<u8>) {
+        self.entries
+            .lock()
+            .expect("stage cache mutex poisoned")
+            .insert((stage_id.to_string(), in_hash.to_string()), output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn miss_then_hit_after_put() {
+        let cache = InMemoryStageCache::new();
+        assert_eq!(cache.get("parse.v1", "blake3:abc"), None);
+
+        cache.put("parse.v1", "blake3:abc", vec![1, 2, 3]);
+        assert_eq!(cache.get("parse.v1", "blake3:abc"), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn distinct_stage_ids_do_not_collide() {
+        let cache = InMemoryStageCache::new();
+        cache.put("parse.v1", "blake3:abc", vec![1]);
+        cache.put("render.v1", "blake3:abc", vec![2]);
+
+        assert_eq!(cache.get("parse.v1", "blake3:abc"), Some(vec![1]));
+        assert_eq!(cache.get("render.v1", "blake3:abc"), Some(vec![2]));
+    }
+}
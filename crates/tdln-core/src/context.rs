@@ -1,6 +1,8 @@
 //! Execution Context: Estado compartilhado durante pipeline
+use crate::determinism::ReplayTrace;
 use std::collections::HashMap;
 use serde_json::Value;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone)]
 pub struct ExecutionContext {
@@ -12,6 +14,11 @@ pub struct ExecutionContext {
     pub no_truth_no_output: bool,
     pub determinism_seed: Option<String>,
     pub metadata: HashMap<String, Value>,
+    /// When set, each stage executed through [`crate::runner::PipelineRunner`]
+    /// appends a `(stage_id, blake3(input), blake3(output))` entry here,
+    /// building up a [`ReplayTrace`] that [`crate::determinism::replay`] can
+    /// later verify against.
+    recorder: Option<Arc<Mutex<ReplayTrace>>>,
 }
 
 impl ExecutionContext {
@@ -25,6 +32,31 @@ impl ExecutionContext {
             no_truth_no_output: false,
             determinism_seed: None,
             metadata: HashMap::new(),
+            recorder: None,
         }
     }
+
+    /// Enable recording mode: subsequent pipeline runs using this context
+    /// capture a replayable trace instead of discarding per-stage hashes.
+    pub fn recording(mut self) -> Self {
+        self.recorder = Some(Arc::new(Mutex::new(ReplayTrace::default())));
+        self
+    }
+
+    /// Whether this context is currently recording a trace.
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    pub(crate) fn record_stage(&self, stage_id: &str, input: &[u8], output: &[u8]) {
+        if let Some(recorder) = &self.recorder {
+            recorder.lock().expect("replay trace mutex poisoned").push(stage_id, input, output);
+        }
+    }
+
+    /// Snapshot the trace recorded so far. Returns `None` if recording mode
+    /// was never enabled.
+    pub fn trace(&self) -> Option<ReplayTrace> {
+        self.recorder.as_ref().map(|r| r.lock().expect("replay trace mutex poisoned").clone())
+    }
 }
\ No newline at end of file
@@ -64,6 +64,11 @@ pub struct StageProof {
     pub deterministic: bool,
     pub latency_ms: u64,
     pub verdict: Option<String>,
+    /// Whether this stage was skipped in favor of a [`crate::cache::StageCache`]
+    /// hit on `in_hash`. `in_hash`/`out_hash` are still the real hashes either
+    /// way, so the proof chain verifies identically whether or not the stage
+    /// actually ran.
+    pub cached: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +85,13 @@ pub struct Citation {
     pub loc: String,
     pub quote: String,
     pub hash: String,
+    /// Merkle inclusion proof against the sealing `OracleRef::merkle_root`:
+    /// ordered sibling hashes from leaf to root, each paired with whether
+    /// the sibling sits to the left (`true`) or right (`false`) of this
+    /// citation's path. Filled by [`crate::merkle::seal`], checked by
+    /// [`crate::merkle::verify_citation`].
+    #[serde(default)]
+    pub proof: Vec<(bool, String)>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
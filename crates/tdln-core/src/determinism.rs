@@ -0,0 +1,231 @@
+//! Determinism verification and replay for `Stage` execution.
+//!
+//! `Stage::deterministic()` and `StageError::Determinism` describe the
+//! contract but don't enforce it by themselves. [`verify_determinism`] runs a
+//! stage N times on the same input and fails loudly if the outputs diverge.
+//! [`ExecutionContext::recording`] captures a [`ReplayTrace`] of hashed
+//! input/output pairs per stage as a pipeline runs, and [`replay`] re-executes
+//! that trace to confirm the recorded hashes still reproduce.
+use crate::context::ExecutionContext;
+use crate::stage::{Stage, StageError};
+
+/// Run `stage` on `input` `runs` times and confirm every run hashes to the
+/// same output. Returns `Err(StageError::Determinism(..))`, naming the
+/// diverging run indices, if any output differs from the first.
+pub fn verify_determinism(
+    stage: &dyn Stage,
+    input: &[u8],
+    ctx: &ExecutionContext,
+    runs: usize,
+) -> Result<(), StageError> {
+    if runs < 2 {
+        return Ok(());
+    }
+
+    let mut baseline: Option<String> = None;
+    let mut divergent = Vec::new();
+
+    for run in 0..runs {
+        let output = stage.run(input, ctx)?;
+        let hash = hash_bytes(&output);
+        match &baseline {
+            None => baseline = Some(hash),
+            Some(first) if *first != hash => divergent.push(run),
+            Some(_) => {}
+        }
+    }
+
+    if divergent.is_empty() {
+        Ok(())
+    } else {
+        Err(StageError::Determinism(format!(
+            "stage '{}' produced different output on run(s) {:?} out of {} runs",
+            stage.id(),
+            divergent,
+            runs
+        )))
+    }
+}
+
+/// One recorded stage execution: the stage's id and the blake3 hashes of its
+/// input and output, sufficient to replay and cross-check without storing
+/// the payloads themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayEntry {
+    pub stage_id: String,
+    pub in_hash: String,
+    pub out_hash: String,
+}
+
+/// An ordered sequence of [`ReplayEntry`] captured while a context was in
+/// recording mode.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayTrace {
+    pub entries: Vec<ReplayEntry>,
+}
+
+impl ReplayTrace {
+    pub fn push(&mut self, stage_id: &str, input: &[u8], output: &[u8]) {
+        self.entries.push(ReplayEntry {
+            stage_id: stage_id.to_string(),
+            in_hash: hash_bytes(input),
+            out_hash: hash_bytes(output),
+        });
+    }
+}
+
+/// Re-run the pipeline chain described by `trace`, starting from
+/// `original_input`, chaining each stage's output into the next exactly as
+/// [`crate::runner::PipelineRunner::run`] does, and assert every recorded
+/// input/output hash still reproduces. `stages` is looked up by id, so it
+/// may be a superset of (or differently ordered from) the traced stages.
+pub fn replay(
+    trace: &ReplayTrace,
+    stages: &[&dyn Stage],
+    original_input: &[u8],
+    ctx: &ExecutionContext,
+) -> Result<(), StageError> {
+    let mut current = original_input.to_vec();
+
+    for (index, entry) in trace.entries.iter().enumerate() {
+        let stage = stages.iter().find(|s| s.id() == entry.stage_id).ok_or_else(|| {
+            StageError::Determinism(format!(
+                "replay entry {} references unknown stage '{}'",
+                index, entry.stage_id
+            ))
+        })?;
+
+        let in_hash = hash_bytes(&current);
+        if in_hash != entry.in_hash {
+            return Err(StageError::Determinism(format!(
+                "replay step {} ('{}'): input hashes to {} but trace recorded {}",
+                index, entry.stage_id, in_hash, entry.in_hash
+            )));
+        }
+
+        let output = stage.run(&current, ctx)?;
+        let out_hash = hash_bytes(&output);
+        if out_hash != entry.out_hash {
+            return Err(StageError::Determinism(format!(
+                "replay step {} ('{}'): output hashes to {} but trace recorded {}",
+                index, entry.stage_id, out_hash, entry.out_hash
+            )));
+        }
+
+        current = output;
+    }
+
+    Ok(())
+}
+
+fn hash_bytes(data: &[u8]) -> String {
+    format!("blake3:{}", blake3::hash(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct FlakyStage {
+        calls: AtomicU32,
+        flip_after: u32,
+    }
+
+    impl Stage for FlakyStage {
+        fn id(&self) -> &'static str {
+            "test.flaky.v1"
+        }
+        fn in_schema(&self) -> &'static [u8] {
+            b"{}"
+        }
+        fn out_schema(&self) -> &'static [u8] {
+            b"{}"
+        }
+        fn run(&self, input: &[u8], _ctx: &ExecutionContext) -> Result<Vec<u8>, StageError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call >= self.flip_after {
+                Ok(b"mutated".to_vec())
+            } else {
+                Ok(input.to_vec())
+            }
+        }
+    }
+
+    struct StableStage;
+
+    impl Stage for StableStage {
+        fn id(&self) -> &'static str {
+            "test.stable.v1"
+        }
+        fn in_schema(&self) -> &'static [u8] {
+            b"{}"
+        }
+        fn out_schema(&self) -> &'static [u8] {
+            b"{}"
+        }
+        fn run(&self, input: &[u8], _ctx: &ExecutionContext) -> Result<Vec<u8>, StageError> {
+            Ok(input.to_vec())
+        }
+    }
+
+    fn test_ctx() -> ExecutionContext {
+        ExecutionContext::new("tenant".to_string(), "dev".to_string())
+    }
+
+    #[test]
+    fn stable_stage_passes_determinism_check() {
+        let stage = StableStage;
+        let ctx = test_ctx();
+        assert!(verify_determinism(&stage, b"hello", &ctx, 5).is_ok());
+    }
+
+    #[test]
+    fn flaky_stage_reports_diverging_runs() {
+        let stage = FlakyStage { calls: AtomicU32::new(0), flip_after: 2 };
+        let ctx = test_ctx();
+        let err = verify_determinism(&stage, b"hello", &ctx, 4).unwrap_err();
+        match err {
+            StageError::Determinism(msg) => {
+                assert!(msg.contains("test.flaky.v1"));
+                assert!(msg.contains("[2, 3]"));
+            }
+            other => panic!("expected Determinism error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn replay_matches_recorded_hashes() {
+        let stage = StableStage;
+        let ctx = test_ctx();
+        let mut trace = ReplayTrace::default();
+        let output = stage.run(b"hello", &ctx).unwrap();
+        trace.push(stage.id(), b"hello", &output);
+
+        let stages: Vec<&dyn Stage> = vec![&stage];
+        assert!(replay(&trace, &stages, b"hello", &ctx).is_ok());
+    }
+
+    #[test]
+    fn replay_rejects_divergent_output() {
+        let stage = FlakyStage { calls: AtomicU32::new(0), flip_after: 0 };
+        let ctx = test_ctx();
+        let mut trace = ReplayTrace::default();
+        trace.push(stage.id(), b"hello", b"hello");
+
+        let stages: Vec<&dyn Stage> = vec![&stage];
+        let err = replay(&trace, &stages, b"hello", &ctx).unwrap_err();
+        assert!(matches!(err, StageError::Determinism(_)));
+    }
+
+    #[test]
+    fn replay_reports_unknown_stage() {
+        let ctx = test_ctx();
+        let mut trace = ReplayTrace::default();
+        trace.push("missing.stage.v1", b"hello", b"hello");
+
+        let stages: Vec<&dyn Stage> = vec![];
+        let err = replay(&trace, &stages, b"hello", &ctx).unwrap_err();
+        assert!(matches!(err, StageError::Determinism(_)));
+    }
+}
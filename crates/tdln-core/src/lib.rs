@@ -5,14 +5,22 @@
 pub mod stage;
 pub mod runner;
 pub mod data_model;
+pub mod determinism;
 pub mod error;
 pub mod context;
+pub mod cache;
+pub mod merkle;
+pub mod provenance;
 
 pub use stage::{Stage, StageError};
 pub use runner::PipelineRunner;
 pub use data_model::{InputPack, CompiledArtifact, Proof, Evidence};
+pub use determinism::{replay, verify_determinism, ReplayEntry, ReplayTrace};
 pub use context::ExecutionContext;
 pub use error::TdlnError;
+pub use cache::{InMemoryStageCache, StageCache};
+pub use merkle::{seal as seal_truthpack, verify_all as verify_citations, verify_citation};
+pub use provenance::{ContentHash, MerkleRoot, Provenance, ProvenanceCitation};
 
 /// Versão do motor TDLN
 pub const TDLN_VERSION: &str = "1.0.0";
\ No newline at end of file
@@ -0,0 +1,276 @@
+//! Merkle tree over `Evidence`/`Citation` leaves.
+//!
+//! [`seal`] fills `OracleRef::merkle_root` at truthpack sealing time and
+//! stamps an inclusion proof onto each [`Citation`]; [`verify_citation`] and
+//! [`verify_all`] recompute the root from a citation's stored proof and
+//! compare it against the declared [`OracleRef`].
+//!
+//! Domain separation: leaves hash as `SHA-256(0x00 || canonical_bytes)`,
+//! internal nodes as `SHA-256(0x01 || left || right)`. Tagging the two hash
+//! kinds keeps a leaf from ever being replayed as an internal node (or vice
+//! versa) during verification. Leaves are sorted by hash before the tree is
+//! built, so the root only depends on which `Citation`/`Evidence` entries
+//! exist, not the order they were recorded in; a level with an odd number of
+//! nodes duplicates its last node to pair it with itself.
+
+use sha2::{Digest, Sha256};
+
+use crate::data_model::{Citation, Evidence, OracleRef};
+
+const LEAF_TAG: u8 = 0x00;
+const NODE_TAG: u8 = 0x01;
+
+/// Fill `oracle.merkle_root` (and each citation's `proof`) from a leaf set
+/// built over `citations` and `evidence`.
+pub fn seal(oracle_id: impl Into<String>, citations: &mut [Citation], evidence: &[Evidence]) -> OracleRef {
+    let mut leaves: Vec<[u8; 32]> = citations
+        .iter()
+        .map(|c| leaf_hash(&citation_canonical_bytes(c)))
+        .chain(evidence.iter().map(|e| leaf_hash(&evidence_canonical_bytes(e))))
+        .collect();
+    leaves.sort();
+
+    for citation in citations.iter_mut() {
+        let hash = leaf_hash(&citation_canonical_bytes(citation));
+        let index = leaves
+            .binary_search(&hash)
+            .expect("citation leaf is always present in the leaf set it was hashed into");
+        citation.proof = inclusion_path(&leaves, index);
+    }
+
+    OracleRef {
+        id: oracle_id.into(),
+        merkle_root: format!("sha256:{}", encode_hex(&merkle_root_of(&leaves))),
+    }
+}
+
+/// Verify that `citation` is included under `oracle.merkle_root`, by
+/// recomputing the root from its leaf hash and stored `proof`.
+pub fn verify_citation(citation: &Citation, oracle: &OracleRef) -> bool {
+    let Some(expected_hex) = oracle.merkle_root.strip_prefix("sha256:") else {
+        return false;
+    };
+
+    let mut current = leaf_hash(&citation_canonical_bytes(citation));
+    for (is_left, sibling_hex) in &citation.proof {
+        let Some(sibling) = decode_hex(sibling_hex) else {
+            return false;
+        };
+        current = if *is_left {
+            node_hash(&sibling, &current)
+        } else {
+            node_hash(&current, &sibling)
+        };
+    }
+
+    encode_hex(&current) == expected_hex
+}
+
+/// Verify every citation against the declared oracle root. An artifact with
+/// no oracle has nothing to tamper-check, so it verifies only if it also
+/// carries no citations to (falsely) vouch for.
+pub fn verify_all(citations: &[Citation], oracle: Option<&OracleRef>) -> bool {
+    match oracle {
+        Some(oracle) => citations.iter().all(|c| verify_citation(c, oracle)),
+        None => citations.is_empty(),
+    }
+}
+
+fn leaf_hash(canonical_bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_TAG]);
+    hasher.update(canonical_bytes);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_TAG]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn merkle_root_of(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return leaf_hash(b"");
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let mut i = 0;
+        while i < level.len() {
+            let left = level[i];
+            let right = if i + 1 < level.len() { level[i + 1] } else { level[i] };
+            next.push(node_hash(&left, &right));
+            i += 2;
+        }
+        level = next;
+    }
+    level[0]
+}
+
+/// Ordered sibling hashes from leaf to root for `leaves[index]`, each tagged
+/// with whether the sibling sits to the left (`true`) or right (`false`) of
+/// the path -- the same shape stored in `Citation::proof`.
+fn inclusion_path(leaves: &[[u8; 32]], mut index: usize) -> Vec<(bool, String)> {
+    let mut path = Vec::new();
+    let mut level = leaves.to_vec();
+
+    while level.len() > 1 {
+        let pair_index = if index % 2 == 0 { index + 1 } else { index - 1 };
+        let sibling = if pair_index < level.len() { level[pair_index] } else { level[index] };
+        path.push((index % 2 == 1, encode_hex(&sibling)));
+
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let mut i = 0;
+        while i < level.len() {
+            let left = level[i];
+            let right = if i + 1 < level.len() { level[i + 1] } else { level[i] };
+            next.push(node_hash(&left, &right));
+            i += 2;
+        }
+        level = next;
+        index /= 2;
+    }
+
+    path
+}
+
+fn citation_canonical_bytes(citation: &Citation) -> Vec<u8> {
+    canonical_json(&serde_json::json!({
+        "source": citation.source,
+        "loc": citation.loc,
+        "quote": citation.quote,
+    }))
+    .into_bytes()
+}
+
+fn evidence_canonical_bytes(evidence: &Evidence) -> Vec<u8> {
+    canonical_json(&serde_json::json!({
+        "field": evidence.field,
+        "value": evidence.value,
+        "source_id": evidence.source_id,
+        "confidence": evidence.confidence,
+    }))
+    .into_bytes()
+}
+
+/// Render `value` as JSON with object keys sorted and no insignificant
+/// whitespace, so the same logical leaf hashes identically across engines.
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let entries: Vec<String> = keys
+                .into_iter()
+                .map(|k| format!("{}:{}", serde_json::to_string(k).unwrap(), canonical_json(&map[k])))
+                .collect();
+            format!("{{{}}}", entries.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            let entries: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", entries.join(","))
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(s: &str) -> Option<[u8; 32]> {
+    if s.len() != 64 {
+        return None;
+    }
+    let mut out = [0u8; 32];
+    for (i, chunk) in s.as_bytes().chunks(2).enumerate() {
+        let byte_str = std::str::from_utf8(chunk).ok()?;
+        out[i] = u8::from_str_radix(byte_str, 16).ok()?;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn citation(source: &str, loc: &str, quote: &str) -> Citation {
+        Citation {
+            source: source.to_string(),
+            loc: loc.to_string(),
+            quote: quote.to_string(),
+            hash: String::new(),
+            proof: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn seal_fills_oracle_root_and_citation_proofs() {
+        let mut citations = vec![
+            citation("doc-a", "p1", "quote a"),
+            citation("doc-b", "p2", "quote b"),
+            citation("doc-c", "p3", "quote c"),
+        ];
+
+        let oracle = seal("contract-oracle", &mut citations, &[]);
+
+        assert!(oracle.merkle_root.starts_with("sha256:"));
+        assert!(citations.iter().all(|c| !c.proof.is_empty()));
+    }
+
+    #[test]
+    fn sealed_citations_verify_against_the_oracle_root() {
+        let mut citations = vec![
+            citation("doc-a", "p1", "quote a"),
+            citation("doc-b", "p2", "quote b"),
+        ];
+        let evidence = vec![Evidence {
+            field: "status".to_string(),
+            value: "ok".to_string(),
+            source_id: "doc-a".to_string(),
+            confidence: 0.9,
+        }];
+
+        let oracle = seal("contract-oracle", &mut citations, &evidence);
+
+        assert!(verify_all(&citations, Some(&oracle)));
+    }
+
+    #[test]
+    fn tampered_citation_fails_verification() {
+        let mut citations = vec![
+            citation("doc-a", "p1", "quote a"),
+            citation("doc-b", "p2", "quote b"),
+        ];
+        let oracle = seal("contract-oracle", &mut citations, &[]);
+
+        citations[0].quote = "a different quote entirely".to_string();
+        assert!(!verify_citation(&citations[0], &oracle));
+    }
+
+    #[test]
+    fn root_is_independent_of_recording_order() {
+        let mut forward = vec![citation("doc-a", "p1", "quote a"), citation("doc-b", "p2", "quote b")];
+        let mut reversed = vec![citation("doc-b", "p2", "quote b"), citation("doc-a", "p1", "quote a")];
+
+        let oracle_forward = seal("oracle", &mut forward, &[]);
+        let oracle_reversed = seal("oracle", &mut reversed, &[]);
+
+        assert_eq!(oracle_forward.merkle_root, oracle_reversed.merkle_root);
+    }
+
+    #[test]
+    fn artifact_with_no_oracle_and_no_citations_verifies() {
+        assert!(verify_all(&[], None));
+    }
+
+    #[test]
+    fn artifact_with_citations_but_no_oracle_fails() {
+        let citations = vec![citation("doc-a", "p1", "quote a")];
+        assert!(!verify_all(&citations, None));
+    }
+}
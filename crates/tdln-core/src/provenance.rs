@@ -0,0 +1,242 @@
+//! Shared provenance primitives for every pack-shaped type that commits to
+//! its content with a Merkle root -- today that's `tdln_truthpack::TruthPack`
+//! (legal/jurisdiction citations) and `tdln_in::prover::TruthPack`
+//! (grammar-translation evidence). Before this module existed the two hashed
+//! with different shapes (a flat `blake3` hash over a whole serialized blob
+//! vs. a real binary tree keyed by a private leaf/combine pair) and exposed
+//! no common way to ask "is this pack's root valid" or "what does it cite",
+//! so a consumer holding both couldn't treat them uniformly. [`Provenance`]
+//! gives both a shared `merkle_root()`/`verify()`/`citations()` surface over
+//! the same [`ContentHash`] primitive.
+//!
+//! Domain separation from [`crate::merkle`]: that module seals
+//! `data_model::Citation`/`Evidence` with SHA-256 and is unrelated to the two
+//! `TruthPack` types this module unifies, which already use blake3. This
+//! module keeps blake3 rather than switching `merkle`'s callers over, so
+//! existing sealed oracles stay verifiable unchanged.
+
+use serde::{Deserialize, Serialize};
+
+/// A blake3 content hash, hex-encoded with a `"blake3:"` prefix so it's
+/// self-describing if a second hash algorithm is ever mixed in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ContentHash(String);
+
+impl ContentHash {
+    /// Hash `bytes` directly -- used for leaves and for combining two
+    /// child hashes (pass their concatenated bytes).
+    pub fn of(bytes: &[u8]) -> Self {
+        Self(format!("blake3:{}", blake3::hash(bytes).to_hex()))
+    }
+
+    /// Parse a previously-formatted `"blake3:<hex>"` string, e.g. one loaded
+    /// back out of a `TruthPack`'s stored `merkle_root` field.
+    pub fn parse(s: &str) -> Option<Self> {
+        let hex = s.strip_prefix("blake3:")?;
+        if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        Some(Self(s.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Combine two child hashes into their parent node's hash.
+    pub fn combine(left: &ContentHash, right: &ContentHash) -> ContentHash {
+        let mut buf = Vec::with_capacity(left.0.len() + right.0.len());
+        buf.extend_from_slice(left.0.as_bytes());
+        buf.extend_from_slice(right.0.as_bytes());
+        ContentHash::of(&buf)
+    }
+}
+
+impl std::fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The root of a pack's Merkle tree -- a [`ContentHash`] under a distinct
+/// name so a root and a leaf hash can't be accidentally swapped at a call
+/// site that expects one or the other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MerkleRoot(ContentHash);
+
+impl MerkleRoot {
+    pub fn new(hash: ContentHash) -> Self {
+        Self(hash)
+    }
+
+    /// Build a root over `leaves` by pairwise-combining levels bottom-up,
+    /// duplicating the last node of an odd-sized level to pair it with
+    /// itself.
+    pub fn from_leaves(leaves: Vec<ContentHash>) -> Self {
+        if leaves.is_empty() {
+            return Self(ContentHash::of(b""));
+        }
+
+        let mut level = leaves;
+        while level.len() > 1 {
+            let mut next = Vec::with_capacity(level.len().div_ceil(2));
+            for pair in level.chunks(2) {
+                let left = &pair[0];
+                let right = pair.get(1).unwrap_or(left);
+                next.push(ContentHash::combine(left, right));
+            }
+            level = next;
+        }
+        Self(level.into_iter().next().expect("non-empty leaves always reduce to one root"))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl std::fmt::Display for MerkleRoot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Which side of a Merkle proof step a sibling hash sits on, relative to
+/// the node being proven -- mirrors the pairing [`MerkleRoot::from_leaves`]
+/// itself uses, so [`MerkleRoot::verify`] folds each step the same way the
+/// tree was built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl MerkleRoot {
+    /// Build an inclusion proof for the leaf at `index` among `leaves`: the
+    /// ordered sibling hashes from leaf to root, each tagged with which
+    /// side of the pair it sits on. `leaves` must be the same list, in the
+    /// same order, [`MerkleRoot::from_leaves`] built the root from.
+    pub fn prove(leaves: &[ContentHash], index: usize) -> Vec<(ContentHash, Side)> {
+        let mut proof = Vec::new();
+        let mut level = leaves.to_vec();
+        let mut idx = index;
+
+        while level.len() > 1 {
+            let pair_index = if idx % 2 == 0 { idx + 1 } else { idx - 1 };
+            let sibling = level.get(pair_index).cloned().unwrap_or_else(|| level[idx].clone());
+            let side = if idx % 2 == 0 { Side::Right } else { Side::Left };
+            proof.push((sibling, side));
+
+            let mut next = Vec::with_capacity(level.len().div_ceil(2));
+            for pair in level.chunks(2) {
+                let left = &pair[0];
+                let right = pair.get(1).unwrap_or(left);
+                next.push(ContentHash::combine(left, right));
+            }
+            level = next;
+            idx /= 2;
+        }
+
+        proof
+    }
+
+    /// Verify that `leaf` is included under `root`, given an inclusion
+    /// proof produced by [`MerkleRoot::prove`] -- recomputes the path from
+    /// leaf to root and compares against `root` directly, so it doesn't
+    /// need the full leaf list the proof was built from.
+    pub fn verify(leaf: &ContentHash, proof: &[(ContentHash, Side)], root: &MerkleRoot) -> bool {
+        let mut current = leaf.clone();
+        for (sibling, side) in proof {
+            current = match side {
+                Side::Left => ContentHash::combine(sibling, &current),
+                Side::Right => ContentHash::combine(&current, sibling),
+            };
+        }
+        current == root.0
+    }
+}
+
+/// A single provenance citation: a quoted excerpt anchored to a source, in
+/// the common shape both `TruthPack` types can report via
+/// [`Provenance::citations`] regardless of their own internal citation type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvenanceCitation {
+    pub source_id: String,
+    pub location: String,
+    pub quote: String,
+}
+
+/// A pack that commits to its content with a Merkle root over `blake3`
+/// leaves, and can be asked whether that root actually matches its content.
+pub trait Provenance {
+    /// This pack's committed root.
+    fn merkle_root(&self) -> MerkleRoot;
+
+    /// Whether `merkle_root()` actually matches the pack's current content.
+    fn verify(&self) -> bool;
+
+    /// Every citation this pack vouches for, in the shared shape.
+    fn citations(&self) -> Vec<ProvenanceCitation>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_hash_parse_round_trips_a_formatted_hash() {
+        let hash = ContentHash::of(b"hello");
+        let parsed = ContentHash::parse(hash.as_str()).unwrap();
+        assert_eq!(hash, parsed);
+    }
+
+    #[test]
+    fn content_hash_parse_rejects_the_wrong_prefix() {
+        assert!(ContentHash::parse("sha256:deadbeef").is_none());
+    }
+
+    #[test]
+    fn merkle_root_is_independent_of_unrelated_state() {
+        let a = ContentHash::of(b"one");
+        let b = ContentHash::of(b"two");
+        let root1 = MerkleRoot::from_leaves(vec![a.clone(), b.clone()]);
+        let root2 = MerkleRoot::from_leaves(vec![a, b]);
+        assert_eq!(root1, root2);
+    }
+
+    #[test]
+    fn merkle_root_changes_when_a_leaf_changes() {
+        let root1 = MerkleRoot::from_leaves(vec![ContentHash::of(b"one"), ContentHash::of(b"two")]);
+        let root2 = MerkleRoot::from_leaves(vec![ContentHash::of(b"one"), ContentHash::of(b"three")]);
+        assert_ne!(root1, root2);
+    }
+
+    #[test]
+    fn empty_leaves_produce_a_stable_root() {
+        let root1 = MerkleRoot::from_leaves(vec![]);
+        let root2 = MerkleRoot::from_leaves(vec![]);
+        assert_eq!(root1, root2);
+    }
+
+    #[test]
+    fn prove_and_verify_round_trip_for_every_leaf() {
+        let leaves: Vec<ContentHash> =
+            ["one", "two", "three"].iter().map(|s| ContentHash::of(s.as_bytes())).collect();
+        let root = MerkleRoot::from_leaves(leaves.clone());
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = MerkleRoot::prove(&leaves, index);
+            assert!(MerkleRoot::verify(leaf, &proof, &root));
+        }
+    }
+
+    #[test]
+    fn verify_rejects_a_proof_against_the_wrong_leaf() {
+        let leaves: Vec<ContentHash> =
+            ["one", "two", "three", "four"].iter().map(|s| ContentHash::of(s.as_bytes())).collect();
+        let root = MerkleRoot::from_leaves(leaves.clone());
+
+        let proof = MerkleRoot::prove(&leaves, 0);
+        assert!(!MerkleRoot::verify(&ContentHash::of(b"not-one"), &proof, &root));
+    }
+}
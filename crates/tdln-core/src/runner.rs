@@ -1,13 +1,16 @@
 //! Pipeline Runner: Encadeia estágios com validação e coleta de métricas
+use crate::cache::StageCache;
 use crate::stage::Stage;
 use crate::data_model::{CompiledArtifact, Proof, StageProof, QualityProof};
 use crate::context::ExecutionContext;
 use blake3;
+use std::sync::Arc;
 use std::time::Instant;
 
 pub struct PipelineRunner {
     stages: Vec<Box<dyn Stage>>,
     pipeline_id: String,
+    cache: Option<Arc<dyn StageCache>>,
 }
 
 impl PipelineRunner {
@@ -18,7 +21,14 @@ impl PipelineRunner {
             .collect::<Vec<_>>()
             .join("→");
 
-        Self { stages, pipeline_id }
+        Self { stages, pipeline_id, cache: None }
+    }
+
+    /// Wire in a [`StageCache`] so deterministic stages re-run on
+    /// previously-seen input can be skipped in favor of the cached output.
+    pub fn with_cache(mut self, cache: Arc<dyn StageCache>) -> Self {
+        self.cache = Some(cache);
+        self
     }
 
     pub async fn run(
@@ -32,21 +42,45 @@ impl PipelineRunner {
         for stage in &self.stages {
             let start = Instant::now();
             let in_hash = self.hash_bytes(&current);
+            let deterministic = stage.deterministic();
+
+            let cached_hit = if deterministic {
+                self.cache.as_ref().and_then(|c| c.get(stage.id(), &in_hash))
+            } else {
+                None
+            };
+
+            let (result, cached) = match cached_hit {
+                Some(result) => (result, true),
+                None => {
+                    let result = stage.run(&current, ctx).map_err(|e| {
+                        crate::error::TdlnError::ParseError(e.to_string())
+                    })?;
+
+                    if deterministic {
+                        if let Some(cache) = &self.cache {
+                            cache.put(stage.id(), &in_hash, result.clone());
+                        }
+                    }
 
-            let result = stage.run(&current, ctx).map_err(|e| {
-                crate::error::TdlnError::ParseError(e.to_string())
-            })?;
+                    (result, false)
+                }
+            };
 
             let out_hash = self.hash_bytes(&result);
             let latency_ms = start.elapsed().as_millis() as u64;
+            let verdict = stage.verdict(&result);
+
+            ctx.record_stage(stage.id(), &current, &result);
 
             proofs.push(StageProof {
                 id: stage.id().to_string(),
                 in_hash,
                 out_hash,
-                deterministic: stage.deterministic(),
+                deterministic,
                 latency_ms,
-                verdict: None,
+                verdict,
+                cached,
             });
 
             current = result;
@@ -62,4 +96,86 @@ impl PipelineRunner {
     pub fn pipeline_id(&self) -> &str {
         &self.pipeline_id
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::InMemoryStageCache;
+    use crate::stage::StageError;
+
+    struct CountingStage {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl Stage for CountingStage {
+        fn id(&self) -> &'static str {
+            "count.v1"
+        }
+
+        fn in_schema(&self) -> &'static [u8] {
+            b"{}"
+        }
+
+        fn out_schema(&self) -> &'static [u8] {
+            b"{}"
+        }
+
+        fn run(&self, input: &[u8], _ctx: &ExecutionContext) -> Result<Vec<u8>, StageError> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(input.to_vec())
+        }
+    }
+
+    /// `PipelineRunner::run` is `async` for future stages that may need it,
+    /// but every `Stage::run` today is synchronous, so its generated future
+    /// always resolves on the first poll -- no real executor needed to
+    /// drive it in a test.
+    fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+        use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+        fn noop(_: *const ()) {}
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        let raw = RawWaker::new(std::ptr::null(), &VTABLE);
+        let waker = unsafe { Waker::from_raw(raw) };
+        let mut cx = Context::from_waker(&waker);
+        let mut fut = Box::pin(fut);
+
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(val) => val,
+            Poll::Pending => panic!("block_on: future did not complete synchronously"),
+        }
+    }
+
+    #[test]
+    fn cache_hit_skips_stage_execution_but_keeps_hashes() {
+        let runner = PipelineRunner::new(vec![Box::new(CountingStage {
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        })])
+        .with_cache(Arc::new(InMemoryStageCache::new()));
+        let ctx = ExecutionContext::new("t".to_string(), "dev".to_string());
+
+        let (_, first) = block_on(runner.run(b"same input", &ctx)).unwrap();
+        assert!(!first[0].cached);
+
+        let (_, second) = block_on(runner.run(b"same input", &ctx)).unwrap();
+        assert!(second[0].cached);
+        assert_eq!(second[0].in_hash, first[0].in_hash);
+        assert_eq!(second[0].out_hash, first[0].out_hash);
+    }
+
+    #[test]
+    fn no_cache_configured_never_marks_a_hit() {
+        let runner = PipelineRunner::new(vec![Box::new(CountingStage {
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        })]);
+        let ctx = ExecutionContext::new("t".to_string(), "dev".to_string());
+
+        let (_, proofs) = block_on(runner.run(b"input", &ctx)).unwrap();
+        assert!(!proofs[0].cached);
+    }
 }
\ No newline at end of file
@@ -18,6 +18,15 @@ pub trait Stage: Send + Sync {
         true
     }
 
+    /// Verdict this stage contributes to its `StageProof`, derived from its
+    /// own `run` output (e.g. `"OK"`/`"WARN"`/`"BLOCK"`). Most stages don't
+    /// gate a decision and so have nothing to report; a stage like
+    /// `PolicyGateStage` that does overrides this to read the verdict back
+    /// out of the output it just produced.
+    fn verdict(&self, _output: &[u8]) -> Option<String> {
+        None
+    }
+
     /// Executa o estágio
     fn run(
         &self,
@@ -0,0 +1,239 @@
+//! Compiles every grammar under `grammars/*.yaml` into a static Rust module
+//! of combinator `Segment` trees, written to `OUT_DIR/compiled_grammars.rs`
+//! and pulled in by `src/compiled.rs` via `include!`. This moves YAML
+//! parsing and pattern compilation to build time so the generated grammars
+//! need no file I/O and no YAML parsing at runtime; the one runtime cost
+//! left is building each grammar's prefilter `RegexSet` (via
+//! `build_pattern_set`) from the generated literals.
+//!
+//! Reuses the crate's own `combinator`/`grammar` modules (rather than
+//! re-implementing pattern compilation here) by including them under a
+//! separate `#[path]` -- this binary is a standalone build-time program, so
+//! `crate::` inside those files resolves to this module tree, not the
+//! library being built.
+//!
+//! This build script only ever calls [`grammar::CompiledGrammar::from_yaml`]
+//! and reads the result back out through the renderer functions below, so
+//! most of each included file's own items (parser internals, the `load`
+//! convenience constructor, etc.) look unused from here even though they're
+//! very much used by the real library build -- hence the blanket allow
+//! rather than sprinkling per-item allows through files that aren't "owned"
+//! by this binary.
+#![allow(dead_code)]
+
+#[path = "src/combinator.rs"]
+mod combinator;
+#[path = "src/grammar.rs"]
+mod grammar;
+
+use grammar::{AbstainConfig, CompiledGrammar, CompiledPattern, CompiledRule, Constraints, ParamSpec, SlotPattern, SlotType};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let grammars_dir = manifest_dir.join("..").join("..").join("grammars");
+    println!("cargo:rerun-if-changed={}", grammars_dir.display());
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&grammars_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| matches!(path.extension().and_then(|e| e.to_str()), Some("yaml") | Some("yml")))
+        .collect();
+    paths.sort();
+
+    let mut source = String::from("// @generated by build.rs for compiled intent grammars -- do not edit.\n\n");
+    let mut ids = Vec::new();
+
+    for path in &paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        let id = path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let yaml = fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("failed to read grammar {}: {e}", path.display()));
+        let compiled = CompiledGrammar::from_yaml(&yaml)
+            .unwrap_or_else(|e| panic!("failed to compile grammar {}: {e}", path.display()));
+
+        let fn_name = format!("__compiled_{}", id.replace(['-', '.'], "_"));
+        source.push_str(&render_grammar_fn(&fn_name, &compiled));
+        ids.push((id, fn_name));
+    }
+
+    source.push_str("/// Every grammar ID this binary was built with, paired with the\n");
+    source.push_str("/// constructor `build.rs` generated for it.\n");
+    source.push_str("pub(crate) fn compiled_registry() -> Vec<(&'static str, fn() -> crate::grammar::CompiledGrammar)> {\n");
+    source.push_str("    vec![\n");
+    for (id, fn_name) in &ids {
+        source.push_str(&format!("        ({id:?}, {fn_name} as fn() -> crate::grammar::CompiledGrammar),\n"));
+    }
+    source.push_str("    ]\n}\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("compiled_grammars.rs"), source).unwrap();
+}
+
+fn render_grammar_fn(fn_name: &str, grammar: &CompiledGrammar) -> String {
+    let mut rules = String::new();
+    for rule in &grammar.rules {
+        rules.push_str(&render_rule(rule));
+    }
+
+    let slot_types = render_map(
+        grammar.slot_types.iter(),
+        "            ",
+        |name, slot_type| format!("{name:?}.to_string(), {}", render_slot_type(slot_type)),
+    );
+
+    format!(
+        "fn {fn_name}() -> crate::grammar::CompiledGrammar {{\n    let rules = vec![\n{rules}    ];\n    crate::grammar::CompiledGrammar {{\n        literal_index: crate::grammar::build_literal_index(&rules),\n        pattern_set: crate::grammar::build_pattern_set(&rules).expect(\"pattern set should compile for a grammar this build already validated\"),\n        rules,\n        slot_types: {slot_types},\n        abstain_config: {},\n    }}\n}}\n\n",
+        render_abstain_config(&grammar.abstain_config),
+    )
+}
+
+fn render_rule(rule: &CompiledRule) -> String {
+    let patterns: String = rule.patterns.iter().map(render_pattern).collect();
+    let params = render_map(
+        rule.params.iter(),
+        "                ",
+        |name, spec| format!("{name:?}.to_string(), {}", render_param_spec(spec)),
+    );
+
+    format!(
+        "            crate::grammar::CompiledRule {{\n                name: {:?}.to_string(),\n                description: {:?}.to_string(),\n                patterns: vec![\n{patterns}                ],\n                params: {params},\n                mode: {:?}.to_string(),\n                constraints: {},\n                read_only: {:?},\n            }},\n",
+        rule.name,
+        rule.description,
+        rule.mode,
+        render_option_constraints(&rule.constraints),
+        rule.read_only,
+    )
+}
+
+fn render_pattern(pattern: &CompiledPattern) -> String {
+    let segments: String = pattern.pattern.segments.iter().map(render_segment).collect();
+    let slot_names: String = pattern
+        .slot_names
+        .iter()
+        .map(|n| format!("{n:?}.to_string(), "))
+        .collect();
+    let literal_atoms: String = pattern
+        .literal_atoms
+        .iter()
+        .map(|a| format!("{a:?}.to_string(), "))
+        .collect();
+
+    format!(
+        "                    crate::grammar::CompiledPattern {{\n                        original: {:?}.to_string(),\n                        pattern: crate::combinator::Pattern::new(vec![{segments}]),\n                        slot_names: vec![{slot_names}],\n                        specificity: {},\n                        literal_atoms: vec![{literal_atoms}],\n                    }},\n",
+        pattern.original, pattern.specificity,
+    )
+}
+
+fn render_segment(segment: &combinator::Segment) -> String {
+    match segment {
+        combinator::Segment::Literal(word) => format!("crate::combinator::Segment::Literal({word:?}.to_string()), "),
+        combinator::Segment::Slot { name, required, type_patterns } => {
+            let type_patterns: String = type_patterns.iter().map(|p| format!("{p:?}.to_string(), ")).collect();
+            format!(
+                "crate::combinator::Segment::Slot {{ name: {name:?}.to_string(), required: {required:?}, type_patterns: vec![{type_patterns}] }}, "
+            )
+        }
+    }
+}
+
+fn render_param_spec(spec: &ParamSpec) -> String {
+    format!(
+        "crate::grammar::ParamSpec {{ r#type: {:?}.to_string(), required: {:?}, default: {} }}",
+        spec.r#type,
+        spec.required,
+        render_option_string(&spec.default),
+    )
+}
+
+fn render_option_constraints(constraints: &Option<Constraints>) -> String {
+    match constraints {
+        None => "None".to_string(),
+        Some(c) => format!(
+            "Some(crate::grammar::Constraints {{ max_files: {:?}, max_lines: {:?}, must_pass_tests: {:?}, requires_confirmation: {:?} }})",
+            c.max_files, c.max_lines, c.must_pass_tests, c.requires_confirmation,
+        ),
+    }
+}
+
+fn render_slot_type(slot_type: &SlotType) -> String {
+    let patterns: String = slot_type
+        .patterns
+        .iter()
+        .map(render_slot_pattern)
+        .collect();
+    format!(
+        "crate::grammar::SlotType {{ description: {:?}.to_string(), patterns: vec![{patterns}] }}",
+        slot_type.description,
+    )
+}
+
+fn render_slot_pattern(pattern: &SlotPattern) -> String {
+    format!(
+        "crate::grammar::SlotPattern {{ pattern: {:?}.to_string(), r#type: {:?}.to_string() }}, ",
+        pattern.pattern, pattern.r#type,
+    )
+}
+
+fn render_abstain_config(config: &Option<AbstainConfig>) -> String {
+    match config {
+        None => "None".to_string(),
+        Some(c) => {
+            let triggers = render_string_map(&c.triggers);
+            let templates = render_string_map(&c.clarification_templates);
+            format!(
+                "Some(crate::grammar::AbstainConfig {{ description: {:?}.to_string(), triggers: {triggers}, clarification_templates: {templates} }})",
+                c.description,
+            )
+        }
+    }
+}
+
+fn render_string_map(map: &std::collections::HashMap<String, String>) -> String {
+    render_map(map.iter(), "                ", |k, v| {
+        format!("{k:?}.to_string(), {v:?}.to_string()")
+    })
+}
+
+/// Render a `HashMap::from_iter`-equivalent literal for a `.insert`-built
+/// map, skipping the `let mut m = ...` scaffolding entirely when `entries`
+/// is empty -- emitting that for an always-empty map would trip
+/// `unused_mut`/`unused_variables` under `-D warnings`.
+fn render_map<'a, K, V>(
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+    indent: &str,
+    render_entry: impl Fn(&K, &V) -> String,
+) -> String
+where
+    K: 'a,
+    V: 'a,
+{
+    let inserts: Vec<String> = entries
+        .map(|(k, v)| format!("{indent}    m.insert({});\n", render_entry(k, v)))
+        .collect();
+
+    if inserts.is_empty() {
+        return "std::collections::HashMap::new()".to_string();
+    }
+
+    format!(
+        "{{\n{indent}    let mut m = std::collections::HashMap::new();\n{}{indent}    m\n{indent}}}",
+        inserts.concat(),
+    )
+}
+
+fn render_option_string(value: &Option<String>) -> String {
+    match value {
+        None => "None".to_string(),
+        Some(s) => format!("Some({s:?}.to_string())"),
+    }
+}
@@ -0,0 +1,67 @@
+//! CLI entry point for the grammar conformance harness (see
+//! [`tdln_in::conformance`]). Prints a per-rule coverage table and exits
+//! non-zero if any rule's match rate falls below `--threshold`, so it can
+//! gate CI the same way a test suite runner would.
+//!
+//! ```text
+//! conformance <corpus.jsonl> <grammar.yaml> [--threshold 0.8]
+//! ```
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    let mut positional = Vec::new();
+    let mut threshold = 0.8;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--threshold" => {
+                i += 1;
+                threshold = match args.get(i).and_then(|v| v.parse::<f64>().ok()) {
+                    Some(t) => t,
+                    None => {
+                        eprintln!("--threshold requires a numeric argument");
+                        return ExitCode::FAILURE;
+                    }
+                };
+            }
+            other => positional.push(other.to_string()),
+        }
+        i += 1;
+    }
+
+    let (corpus_path, grammar_path) = match (positional.first(), positional.get(1)) {
+        (Some(c), Some(g)) => (c, g),
+        _ => {
+            eprintln!("usage: conformance <corpus.jsonl> <grammar.yaml> [--threshold 0.8]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let report = match tdln_in::conformance::run(corpus_path, grammar_path) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("conformance run failed: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    print!("{}", report.render_table());
+
+    let failing = report.rules_below(threshold);
+    if failing.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!(
+            "\n{} rule(s) below the {:.0}% match threshold:",
+            failing.len(),
+            threshold * 100.0
+        );
+        for rule in failing {
+            eprintln!("  {} ({:.1}% match rate, {} cases)", rule.rule, rule.match_rate() * 100.0, rule.total);
+        }
+        ExitCode::FAILURE
+    }
+}
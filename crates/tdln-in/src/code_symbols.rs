@@ -0,0 +1,136 @@
+//! Tree-sitter-backed symbol extraction for code-grounded entities.
+//!
+//! Given a file on disk, parses it with the tree-sitter grammar selected by
+//! its extension and queries out the symbols it actually defines (functions,
+//! classes/structs/types). [`crate::entities`] uses this to promote a
+//! text-only guess like "the UserService class" to a verified, span-backed
+//! reference instead of trusting a PascalCase regex.
+use std::collections::HashMap;
+use std::path::Path;
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Language, Parser, Query, QueryCursor};
+
+/// A symbol definition found by parsing a source file, with its byte span
+/// within that file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefinedSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub start_byte: usize,
+    pub end_byte: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Type,
+}
+
+struct LanguageSpec {
+    language: fn() -> Language,
+    function_query: &'static str,
+    type_query: &'static str,
+}
+
+fn spec_for_extension(ext: &str) -> Option<LanguageSpec> {
+    match ext {
+        "rs" => Some(LanguageSpec {
+            language: || tree_sitter_rust::LANGUAGE.into(),
+            function_query: "(function_item name: (identifier) @name)",
+            type_query: "[(struct_item name: (type_identifier) @name) (enum_item name: (type_identifier) @name) (trait_item name: (type_identifier) @name)]",
+        }),
+        "py" => Some(LanguageSpec {
+            language: || tree_sitter_python::LANGUAGE.into(),
+            function_query: "(function_definition name: (identifier) @name)",
+            type_query: "(class_definition name: (identifier) @name)",
+        }),
+        "go" => Some(LanguageSpec {
+            language: || tree_sitter_go::LANGUAGE.into(),
+            function_query: "(function_declaration name: (identifier) @name)",
+            type_query: "(type_spec name: (type_identifier) @name)",
+        }),
+        "ts" | "tsx" | "js" | "jsx" => Some(LanguageSpec {
+            language: || tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            function_query: "(function_declaration name: (identifier) @name)",
+            type_query: "[(class_declaration name: (type_identifier) @name) (interface_declaration name: (type_identifier) @name)]",
+        }),
+        _ => None,
+    }
+}
+
+/// Parse `source` as the language implied by `extension` and return every
+/// function and type (class/struct/enum/interface) it defines. Returns
+/// `None` if there's no registered grammar for `extension`.
+pub fn extract_defined_symbols(extension: &str, source: &str) -> Option<Vec<DefinedSymbol>> {
+    let spec = spec_for_extension(extension)?;
+    let language = (spec.language)();
+
+    let mut parser = Parser::new();
+    parser.set_language(&language).ok()?;
+    let tree = parser.parse(source, None)?;
+
+    let mut symbols = Vec::new();
+    run_query(&language, spec.function_query, &tree, source, SymbolKind::Function, &mut symbols);
+    run_query(&language, spec.type_query, &tree, source, SymbolKind::Type, &mut symbols);
+    Some(symbols)
+}
+
+fn run_query(
+    language: &Language,
+    query_src: &str,
+    tree: &tree_sitter::Tree,
+    source: &str,
+    kind: SymbolKind,
+    out: &mut Vec<DefinedSymbol>,
+) {
+    let Ok(query) = Query::new(language, query_src) else { return };
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(&query, tree.root_node(), source.as_bytes());
+    while let Some(m) = matches.next() {
+        for capture in m.captures {
+            let node = capture.node;
+            out.push(DefinedSymbol {
+                name: source[node.byte_range()].to_string(),
+                kind,
+                start_byte: node.start_byte(),
+                end_byte: node.end_byte(),
+            });
+        }
+    }
+}
+
+/// Load `path` from disk and extract its defined symbols, keyed by name for
+/// fast lookup against text-only entity guesses. Returns `None` if the file
+/// can't be read or its extension has no registered grammar.
+pub fn defined_symbols_in_file(path: &Path) -> Option<HashMap<String, DefinedSymbol>> {
+    let ext = path.extension()?.to_str()?;
+    let source = std::fs::read_to_string(path).ok()?;
+    let symbols = extract_defined_symbols(ext, &source)?;
+    Some(symbols.into_iter().map(|s| (s.name.clone(), s)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_rust_function_and_struct() {
+        let src = "fn handle_request() {}\nstruct UserService { id: u32 }\n";
+        let symbols = extract_defined_symbols("rs", src).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "handle_request" && s.kind == SymbolKind::Function));
+        assert!(symbols.iter().any(|s| s.name == "UserService" && s.kind == SymbolKind::Type));
+    }
+
+    #[test]
+    fn extracts_python_function_and_class() {
+        let src = "def validate_token():\n    pass\n\nclass UserService:\n    pass\n";
+        let symbols = extract_defined_symbols("py", src).unwrap();
+        assert!(symbols.iter().any(|s| s.name == "validate_token" && s.kind == SymbolKind::Function));
+        assert!(symbols.iter().any(|s| s.name == "UserService" && s.kind == SymbolKind::Type));
+    }
+
+    #[test]
+    fn unknown_extension_returns_none() {
+        assert!(extract_defined_symbols("xyz", "whatever").is_none());
+    }
+}
@@ -0,0 +1,411 @@
+//! Parser-combinator engine for compiled intent patterns.
+//!
+//! Patterns used to compile straight to a single non-greedy regex per
+//! pattern string, which gave a binary "did it match" with no insight into
+//! *why* a near-match failed. This module compiles a pattern into a small
+//! tree of [`Segment`]s (sequence / optional / slot / literal) and drives it
+//! with a tokenwise parser that, on failure, records the set of things it
+//! [`Expectation`]s at the token position where parsing got stuck -- e.g. it
+//! consumed "rename old.ts" and then expected the literal "to". A rule's
+//! patterns are tried as alternatives (`alt`); within a pattern, segments run
+//! in `seq`; a segment whose slot is not `required` behaves like `optional`
+//! and is allowed to consume nothing without failing the parse.
+//!
+//! A slot's span is still found by the boundary-consumption rule above, but
+//! if [`crate::grammar::compile_pattern`] attached `type_patterns` (from the
+//! grammar's declared [`crate::grammar::SlotType`]), the captured text must
+//! also match one of them or the slot is treated as not found -- see
+//! [`matches_any_type`]. This catches a captured span that's the right
+//! *shape* of text but the wrong *kind* (a `{destination:file_path}` slot
+//! that swallowed a bare word with no extension, say).
+
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// One piece of a compiled pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// A literal keyword that must appear verbatim (case-insensitive).
+    Literal(String),
+    /// A named slot that greedily consumes tokens up to the next literal.
+    Slot {
+        name: String,
+        required: bool,
+        /// Regex sources the captured value must match at least one of,
+        /// drawn from the slot's declared [`crate::grammar::SlotType`].
+        /// Empty means untyped -- any span is accepted, same as before
+        /// slot types were wired in.
+        type_patterns: Vec<String>,
+    },
+}
+
+/// Something the parser was looking for at the point it gave up.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expectation {
+    /// A literal keyword was expected next.
+    Literal(String),
+    /// A slot value was expected next.
+    Slot(String),
+    /// Nothing more -- the parser expected the input to have ended.
+    EndOfInput,
+}
+
+impl std::fmt::Display for Expectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expectation::Literal(word) => write!(f, "'{}'", word),
+            Expectation::Slot(name) => write!(f, "a {} value", name),
+            Expectation::EndOfInput => write!(f, "nothing else"),
+        }
+    }
+}
+
+/// A captured slot value and the token span it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotCapture {
+    pub value: String,
+    pub start_token: usize,
+    pub end_token: usize,
+}
+
+/// The outcome of driving a [`Pattern`] over a token sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseOutcome {
+    /// The whole sequence consumed the input; `missing_optional` lists the
+    /// optional slots that matched nothing so the caller can score the
+    /// confidence of a partial-but-successful parse.
+    Matched {
+        slots: HashMap<String, SlotCapture>,
+        missing_optional: Vec<String>,
+    },
+    /// Parsing failed at `token_position`; `expected` is every segment kind
+    /// that would have let it continue from there.
+    Failed {
+        token_position: usize,
+        expected: Vec<Expectation>,
+    },
+}
+
+/// A compiled pattern: a `seq` of literal/slot segments tried as one unit.
+/// A [`super::grammar::CompiledRule`] holds several of these and tries them
+/// in order -- the `alt` over a rule's patterns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub segments: Vec<Segment>,
+}
+
+impl Pattern {
+    pub fn new(segments: Vec<Segment>) -> Self {
+        Pattern { segments }
+    }
+
+    /// Run the sequence combinator over `tokens`.
+    pub fn parse(&self, tokens: &[&str]) -> ParseOutcome {
+        let mut pos = 0usize;
+        let mut slots = HashMap::new();
+        let mut missing_optional = Vec::new();
+
+        let mut i = 0;
+        while i < self.segments.len() {
+            match &self.segments[i] {
+                Segment::Literal(word) => match literal(word, tokens, pos) {
+                    Ok(next_pos) => {
+                        pos = next_pos;
+                        i += 1;
+                    }
+                    Err(expected) => {
+                        return ParseOutcome::Failed {
+                            token_position: pos,
+                            expected: vec![expected],
+                        }
+                    }
+                },
+                Segment::Slot { name, required, type_patterns } => {
+                    let next_literal = self.segments[i + 1..].iter().find_map(|s| match s {
+                        Segment::Literal(word) => Some(word.as_str()),
+                        _ => None,
+                    });
+
+                    match slot(name, *required, next_literal, type_patterns, tokens, pos) {
+                        SlotOutcome::Consumed { capture, next_pos } => {
+                            slots.insert(name.clone(), capture);
+                            pos = next_pos;
+                        }
+                        SlotOutcome::SkippedOptional => {
+                            missing_optional.push(name.clone());
+                        }
+                        SlotOutcome::Failed(expected) => {
+                            return ParseOutcome::Failed {
+                                token_position: pos,
+                                expected: vec![expected],
+                            }
+                        }
+                    }
+                    i += 1;
+                }
+            }
+        }
+
+        if pos == tokens.len() {
+            ParseOutcome::Matched {
+                slots,
+                missing_optional,
+            }
+        } else {
+            ParseOutcome::Failed {
+                token_position: pos,
+                expected: vec![Expectation::EndOfInput],
+            }
+        }
+    }
+}
+
+/// The `literal` combinator: consume one token if it case-insensitively
+/// equals `word`, otherwise report what was expected.
+fn literal(word: &str, tokens: &[&str], pos: usize) -> Result<usize, Expectation> {
+    match tokens.get(pos) {
+        Some(token) if token.eq_ignore_ascii_case(word) => Ok(pos + 1),
+        _ => Err(Expectation::Literal(word.to_string())),
+    }
+}
+
+enum SlotOutcome {
+    Consumed { capture: SlotCapture, next_pos: usize },
+    SkippedOptional,
+    Failed(Expectation),
+}
+
+/// The `slot` combinator: lazily consume tokens from `pos` up to (but not
+/// including) the next occurrence of `boundary_literal`, or to the end of
+/// input if this is the trailing segment. An `optional` slot that finds no
+/// room to consume resolves via `SkippedOptional` instead of failing the
+/// whole parse -- this is what lets a partial parse with only optional
+/// slots missing still resolve to a `Match`.
+///
+/// When `boundary_literal` never occurs in the remaining tokens at all, the
+/// connector itself is what's missing, not the slot value -- so rather than
+/// failing here, this takes a best-effort single token and lets the
+/// following `literal` combinator report the connector it actually wanted
+/// (e.g. "matched 'rename old.ts', expected 'to'" instead of a confusing
+/// complaint about the `source` slot that *did* have a value).
+fn slot(
+    name: &str,
+    required: bool,
+    boundary_literal: Option<&str>,
+    type_patterns: &[String],
+    tokens: &[&str],
+    pos: usize,
+) -> SlotOutcome {
+    let end = match boundary_literal {
+        Some(word) => (pos..tokens.len()).find(|&p| tokens[p].eq_ignore_ascii_case(word)),
+        None => Some(tokens.len()),
+    };
+
+    match end {
+        Some(end) if end > pos => {
+            let value = tokens[pos..end].join(" ");
+            if matches_any_type(&value, type_patterns) {
+                SlotOutcome::Consumed {
+                    capture: SlotCapture { value, start_token: pos, end_token: end },
+                    next_pos: end,
+                }
+            } else if required {
+                SlotOutcome::Failed(Expectation::Slot(name.to_string()))
+            } else {
+                SlotOutcome::SkippedOptional
+            }
+        }
+        None if boundary_literal.is_some() && pos < tokens.len() => {
+            let value = tokens[pos].to_string();
+            if matches_any_type(&value, type_patterns) {
+                SlotOutcome::Consumed {
+                    capture: SlotCapture { value, start_token: pos, end_token: pos + 1 },
+                    next_pos: pos + 1,
+                }
+            } else if required {
+                SlotOutcome::Failed(Expectation::Slot(name.to_string()))
+            } else {
+                SlotOutcome::SkippedOptional
+            }
+        }
+        _ if required => SlotOutcome::Failed(Expectation::Slot(name.to_string())),
+        _ => SlotOutcome::SkippedOptional,
+    }
+}
+
+/// Whether `value` matches at least one of `type_patterns` -- an empty list
+/// means the slot is untyped, so any span is accepted (the pre-slot-typing
+/// behavior). Invalid regex sources never match rather than panicking:
+/// [`cached_type_regex`] caches `None` for them.
+fn matches_any_type(value: &str, type_patterns: &[String]) -> bool {
+    type_patterns.is_empty()
+        || type_patterns
+            .iter()
+            .any(|pattern| cached_type_regex(pattern).as_ref().as_ref().is_some_and(|re| re.is_match(value)))
+}
+
+lazy_static! {
+    static ref TYPE_REGEX_CACHE: Mutex<HashMap<String, Arc<Option<regex::Regex>>>> = Mutex::new(HashMap::new());
+}
+
+/// Compile (and cache) a [`crate::grammar::SlotPattern`] regex source,
+/// mirroring `tdln_policy::expr`'s `cached_regex` convention for the same
+/// "compile once, reuse by pattern string" pattern.
+fn cached_type_regex(pattern: &str) -> Arc<Option<regex::Regex>> {
+    let mut cache = TYPE_REGEX_CACHE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(re) = cache.get(pattern) {
+        return Arc::clone(re);
+    }
+    let compiled = Arc::new(regex::Regex::new(pattern).ok());
+    cache.insert(pattern.to_string(), Arc::clone(&compiled));
+    compiled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(segments: Vec<Segment>) -> Pattern {
+        Pattern::new(segments)
+    }
+
+    fn toks(text: &str) -> Vec<&str> {
+        text.split_whitespace().collect()
+    }
+
+    fn slot_seg(name: &str, required: bool) -> Segment {
+        Segment::Slot { name: name.to_string(), required, type_patterns: Vec::new() }
+    }
+
+    #[test]
+    fn literal_then_slot_matches() {
+        let p = pattern(vec![
+            Segment::Literal("fix".to_string()),
+            slot_seg("target", true),
+        ]);
+        match p.parse(&toks("fix src/auth.ts")) {
+            ParseOutcome::Matched { slots, missing_optional } => {
+                assert_eq!(slots["target"].value, "src/auth.ts");
+                assert!(missing_optional.is_empty());
+            }
+            other => panic!("expected match, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn two_slots_bounded_by_middle_literal() {
+        let p = pattern(vec![
+            Segment::Literal("rename".to_string()),
+            slot_seg("source", true),
+            Segment::Literal("to".to_string()),
+            slot_seg("destination", true),
+        ]);
+        match p.parse(&toks("rename old.ts to new.ts")) {
+            ParseOutcome::Matched { slots, .. } => {
+                assert_eq!(slots["source"].value, "old.ts");
+                assert_eq!(slots["destination"].value, "new.ts");
+            }
+            other => panic!("expected match, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn missing_connector_literal_reports_expected() {
+        let p = pattern(vec![
+            Segment::Literal("rename".to_string()),
+            slot_seg("source", true),
+            Segment::Literal("to".to_string()),
+            slot_seg("destination", true),
+        ]);
+        match p.parse(&toks("rename old.ts new.ts")) {
+            ParseOutcome::Failed {
+                token_position,
+                expected,
+            } => {
+                // "rename old.ts" consumed, then it wanted the literal "to"
+                assert_eq!(token_position, 2);
+                assert_eq!(expected, vec![Expectation::Literal("to".to_string())]);
+            }
+            other => panic!("expected failure, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn missing_optional_trailing_slot_still_matches() {
+        let p = pattern(vec![
+            Segment::Literal("refactor".to_string()),
+            slot_seg("target", false),
+        ]);
+        match p.parse(&toks("refactor")) {
+            ParseOutcome::Matched {
+                slots,
+                missing_optional,
+            } => {
+                assert!(slots.is_empty());
+                assert_eq!(missing_optional, vec!["target".to_string()]);
+            }
+            other => panic!("expected match, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn required_slot_rejects_a_value_of_the_wrong_type() {
+        let p = pattern(vec![
+            Segment::Literal("fix".to_string()),
+            Segment::Slot {
+                name: "target".to_string(),
+                required: true,
+                type_patterns: vec![r"^\S+\.\w+$".to_string()],
+            },
+        ]);
+        match p.parse(&toks("fix everything")) {
+            ParseOutcome::Failed { expected, .. } => {
+                assert_eq!(expected, vec![Expectation::Slot("target".to_string())]);
+            }
+            other => panic!("expected failure, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn required_slot_accepts_a_value_matching_its_type() {
+        let p = pattern(vec![
+            Segment::Literal("fix".to_string()),
+            Segment::Slot {
+                name: "target".to_string(),
+                required: true,
+                type_patterns: vec![r"^\S+\.\w+$".to_string()],
+            },
+        ]);
+        match p.parse(&toks("fix src/auth.ts")) {
+            ParseOutcome::Matched { slots, .. } => {
+                assert_eq!(slots["target"].value, "src/auth.ts");
+            }
+            other => panic!("expected match, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn optional_slot_of_the_wrong_type_is_skipped_leaving_the_token_unconsumed() {
+        let p = pattern(vec![
+            Segment::Literal("refactor".to_string()),
+            Segment::Slot {
+                name: "target".to_string(),
+                required: false,
+                type_patterns: vec![r"^\S+\.\w+$".to_string()],
+            },
+        ]);
+        // "everything" doesn't look like a file path, so the optional slot
+        // skips it instead of consuming it -- leaving it unconsumed fails
+        // the overall parse rather than the slot itself.
+        match p.parse(&toks("refactor everything")) {
+            ParseOutcome::Failed {
+                expected,
+                ..
+            } => {
+                assert_eq!(expected, vec![Expectation::EndOfInput]);
+            }
+            other => panic!("expected failure, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Zero-parse access to grammars embedded at build time.
+//!
+//! `translate`/`TranslateRequest::grammar_path` re-reads and re-parses a
+//! YAML grammar file on every call -- fine for dynamic/user-supplied
+//! grammars, but wasteful for the fixed set of grammars this binary ships
+//! with, especially behind the NAPI bindings where `translate` is on the
+//! hot path. `build.rs` compiles every file under `grammars/*.yaml` into a
+//! combinator [`Segment`](crate::combinator::Segment) tree baked straight
+//! into Rust source (`OUT_DIR/compiled_grammars.rs`, pulled in below via
+//! `include!`); each grammar is built from that generated code once and
+//! cached, so repeat calls pay only a hash-map lookup -- no filesystem
+//! access or YAML parsing at runtime. The first build of each grammar still
+//! compiles its pattern `RegexSet`.
+
+include!(concat!(env!("OUT_DIR"), "/compiled_grammars.rs"));
+
+use crate::grammar::CompiledGrammar;
+use crate::{translate_compound, TranslateError, TranslateResult};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+static REGISTRY: OnceLock<HashMap<&'static str, CompiledGrammar>> = OnceLock::new();
+
+fn registry() -> &'static HashMap<&'static str, CompiledGrammar> {
+    REGISTRY.get_or_init(|| {
+        compiled_registry()
+            .into_iter()
+            .map(|(id, build)| (id, build()))
+            .collect()
+    })
+}
+
+/// Every grammar ID embedded in this binary at build time, in the order
+/// `build.rs` discovered the files in `grammars/`.
+pub fn available_grammars() -> Vec<&'static str> {
+    compiled_registry().into_iter().map(|(id, _)| id).collect()
+}
+
+/// Translate `text` against a grammar embedded at build time, looked up by
+/// `grammar_id` (see [`available_grammars`]). Unlike [`crate::translate`],
+/// this never touches the filesystem or re-parses YAML: the grammar is
+/// compiled once on first use and cached for the lifetime of the process.
+pub fn translate_with_compiled(grammar_id: &str, text: &str) -> Result<TranslateResult, TranslateError> {
+    let grammar = registry()
+        .get(grammar_id)
+        .ok_or_else(|| TranslateError::GrammarError(format!("unknown compiled grammar: {grammar_id}")))?;
+
+    Ok(translate_compound(text, grammar, grammar_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_grammar_id_is_a_grammar_error() {
+        let err = translate_with_compiled("does-not-exist", "fix src/auth.ts").unwrap_err();
+        assert!(matches!(err, TranslateError::GrammarError(_)));
+    }
+
+    #[test]
+    fn available_grammars_matches_registry_keys() {
+        let ids = available_grammars();
+        assert_eq!(ids.len(), registry().len());
+        for id in ids {
+            assert!(registry().contains_key(id));
+        }
+    }
+}
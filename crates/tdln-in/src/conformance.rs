@@ -0,0 +1,285 @@
+//! Grammar conformance harness.
+//!
+//! The round-trip tests in `tests/roundtrip_tests.rs` hard-code one example
+//! per intent and, for an abstained case, just `eprintln!` a warning ("may
+//! need grammar expansion") that no one is watching. This module is a
+//! test262-style suite runner instead: [`run`] loads a corpus of labeled
+//! `{input, expected_intent, expected_mode, expected_slots}` cases, runs
+//! each through [`crate::translate`], and folds the results into a
+//! [`ConformanceReport`] with per-rule match/abstain rates, slot-extraction
+//! accuracy, and a confidence histogram -- a measurable regression gate
+//! instead of an easy-to-ignore log line.
+
+use crate::{translate, TranslateRequest, Verdict};
+use logline::LogLineValue;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// One labeled example in a conformance corpus.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConformanceCase {
+    /// The natural-language input to translate.
+    pub input: String,
+    /// The rule name the input is expected to match.
+    pub expected_intent: String,
+    /// The mode (mechanic/genius) the matched rule is expected to report.
+    #[serde(default)]
+    pub expected_mode: Option<String>,
+    /// Slot name -> expected extracted value, checked only for slots this
+    /// case cares about (missing slots aren't penalized).
+    #[serde(default)]
+    pub expected_slots: HashMap<String, String>,
+}
+
+/// Match/abstain/slot-accuracy tally for a single expected intent.
+#[derive(Debug, Clone)]
+pub struct RuleCoverage {
+    pub rule: String,
+    pub total: usize,
+    pub matched: usize,
+    pub abstained: usize,
+    pub correct_intent: usize,
+    pub correct_mode: usize,
+    pub slot_fields_total: usize,
+    pub slot_fields_correct: usize,
+}
+
+impl RuleCoverage {
+    fn new(rule: &str) -> Self {
+        RuleCoverage {
+            rule: rule.to_string(),
+            total: 0,
+            matched: 0,
+            abstained: 0,
+            correct_intent: 0,
+            correct_mode: 0,
+            slot_fields_total: 0,
+            slot_fields_correct: 0,
+        }
+    }
+
+    /// Fraction of cases that matched *and* named this rule.
+    pub fn match_rate(&self) -> f64 {
+        ratio(self.correct_intent, self.total)
+    }
+
+    pub fn abstain_rate(&self) -> f64 {
+        ratio(self.abstained, self.total)
+    }
+
+    /// Fraction of checked slot fields (across every case) whose extracted
+    /// value matched the corpus's expectation. `1.0` if no case for this
+    /// rule declared any `expected_slots`.
+    pub fn slot_accuracy(&self) -> f64 {
+        if self.slot_fields_total == 0 {
+            1.0
+        } else {
+            ratio(self.slot_fields_correct, self.slot_fields_total)
+        }
+    }
+}
+
+fn ratio(n: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        n as f64 / total as f64
+    }
+}
+
+/// Compliance report for a full corpus run.
+#[derive(Debug, Clone)]
+pub struct ConformanceReport {
+    /// One entry per distinct `expected_intent` seen, in first-seen order.
+    pub per_rule: Vec<RuleCoverage>,
+    /// `confidence_histogram[i]` counts matches whose confidence fell in
+    /// `[i / 10.0, (i + 1) / 10.0)` (the last bucket is inclusive of 1.0).
+    pub confidence_histogram: [usize; 10],
+    pub total_cases: usize,
+}
+
+impl ConformanceReport {
+    /// Fraction of all cases that matched their expected intent.
+    pub fn overall_match_rate(&self) -> f64 {
+        let correct: usize = self.per_rule.iter().map(|r| r.correct_intent).sum();
+        ratio(correct, self.total_cases)
+    }
+
+    /// Every rule whose [`RuleCoverage::match_rate`] is below `threshold`,
+    /// in report order -- what a CI gate should fail on.
+    pub fn rules_below(&self, threshold: f64) -> Vec<&RuleCoverage> {
+        self.per_rule.iter().filter(|r| r.match_rate() < threshold).collect()
+    }
+
+    /// Render a plain-text table: one row per rule plus a confidence
+    /// histogram, suitable for printing from a CLI.
+    pub fn render_table(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!(
+            "{:<20} {:>6} {:>10} {:>10} {:>10} {:>10}\n",
+            "rule", "cases", "match%", "abstain%", "mode%", "slot%"
+        ));
+        for rule in &self.per_rule {
+            out.push_str(&format!(
+                "{:<20} {:>6} {:>9.1}% {:>9.1}% {:>9.1}% {:>9.1}%\n",
+                rule.rule,
+                rule.total,
+                rule.match_rate() * 100.0,
+                rule.abstain_rate() * 100.0,
+                ratio(rule.correct_mode, rule.total) * 100.0,
+                rule.slot_accuracy() * 100.0,
+            ));
+        }
+        out.push_str(&format!(
+            "\noverall match rate: {:.1}% ({} cases)\n",
+            self.overall_match_rate() * 100.0,
+            self.total_cases
+        ));
+        out.push_str("confidence histogram:\n");
+        for (i, count) in self.confidence_histogram.iter().enumerate() {
+            out.push_str(&format!("  [{:.1}, {:.1}): {}\n", i as f64 / 10.0, (i + 1) as f64 / 10.0, count));
+        }
+        out
+    }
+}
+
+/// Load a corpus from a JSON-lines file (one [`ConformanceCase`] per line,
+/// blank lines skipped).
+pub fn load_corpus(corpus_path: &str) -> Result<Vec<ConformanceCase>, String> {
+    let content = std::fs::read_to_string(corpus_path)
+        .map_err(|e| format!("failed to read corpus {corpus_path}: {e}"))?;
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str::<ConformanceCase>(line).map_err(|e| format!("invalid corpus line {line:?}: {e}")))
+        .collect()
+}
+
+/// Run every case in `corpus_path` through [`translate`] against
+/// `grammar_path` and fold the results into a [`ConformanceReport`].
+pub fn run(corpus_path: &str, grammar_path: &str) -> Result<ConformanceReport, String> {
+    let cases = load_corpus(corpus_path)?;
+    let mut per_rule: Vec<RuleCoverage> = Vec::new();
+    let mut confidence_histogram = [0usize; 10];
+
+    for case in &cases {
+        let coverage = match per_rule.iter_mut().find(|r| r.rule == case.expected_intent) {
+            Some(existing) => existing,
+            None => {
+                per_rule.push(RuleCoverage::new(&case.expected_intent));
+                per_rule.last_mut().unwrap()
+            }
+        };
+        coverage.total += 1;
+
+        let result = translate(TranslateRequest {
+            text: case.input.clone(),
+            grammar_path: Some(grammar_path.to_string()),
+        })
+        .map_err(|e| format!("translate failed for {:?}: {e}", case.input))?;
+
+        let bucket = ((result.confidence * 10.0) as usize).min(9);
+        if result.verdict == Verdict::Match {
+            confidence_histogram[bucket] += 1;
+        }
+
+        match result.verdict {
+            Verdict::Abstain => coverage.abstained += 1,
+            Verdict::Match => {
+                coverage.matched += 1;
+                let span = result.span.expect("Match verdict always carries a span");
+
+                if span.name.as_deref() == Some(case.expected_intent.as_str()) {
+                    coverage.correct_intent += 1;
+                }
+                if let Some(expected_mode) = &case.expected_mode {
+                    if result.mode.as_deref() == Some(expected_mode.as_str()) {
+                        coverage.correct_mode += 1;
+                    }
+                }
+                for (slot_name, expected_value) in &case.expected_slots {
+                    coverage.slot_fields_total += 1;
+                    let actual = span.params.iter().find_map(|(name, value)| {
+                        if name == slot_name {
+                            if let LogLineValue::Str(s) = value {
+                                return Some(s.as_str());
+                            }
+                        }
+                        None
+                    });
+                    if actual == Some(expected_value.as_str()) {
+                        coverage.slot_fields_correct += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(ConformanceReport {
+        per_rule,
+        confidence_histogram,
+        total_cases: cases.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grammar_path() -> String {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let workspace_root = std::path::Path::new(&manifest_dir).parent().unwrap().parent().unwrap();
+        workspace_root.join("grammars/coding-intents.yaml").to_string_lossy().to_string()
+    }
+
+    /// Write `cases` (one JSON object per line) to a scratch file and
+    /// return its path.
+    fn write_corpus(name: &str, cases: &[&str]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("tdln_in_conformance_{}_{}.jsonl", name, std::process::id()));
+        std::fs::write(&path, cases.join("\n")).unwrap();
+        path
+    }
+
+    #[test]
+    fn reports_full_coverage_for_clean_matches() {
+        let corpus = write_corpus("clean", &[
+            r#"{"input": "fix the bug in src/auth.ts", "expected_intent": "bug_fix", "expected_mode": "mechanic", "expected_slots": {"target": "src/auth.ts"}}"#,
+            r#"{"input": "add dark mode support", "expected_intent": "feature", "expected_mode": "genius"}"#,
+        ]);
+
+        let report = run(corpus.to_str().unwrap(), &grammar_path()).unwrap();
+        std::fs::remove_file(&corpus).ok();
+
+        assert_eq!(report.total_cases, 2);
+        assert_eq!(report.overall_match_rate(), 1.0);
+        assert!(report.rules_below(1.0).is_empty());
+
+        let bug_fix = report.per_rule.iter().find(|r| r.rule == "bug_fix").unwrap();
+        assert_eq!(bug_fix.slot_accuracy(), 1.0);
+    }
+
+    #[test]
+    fn flags_a_rule_whose_corpus_case_abstains() {
+        let corpus = write_corpus("abstain", &[r#"{"input": "zzz", "expected_intent": "bug_fix"}"#]);
+
+        let report = run(corpus.to_str().unwrap(), &grammar_path()).unwrap();
+        std::fs::remove_file(&corpus).ok();
+
+        let bug_fix = report.per_rule.iter().find(|r| r.rule == "bug_fix").unwrap();
+        assert_eq!(bug_fix.abstained, 1);
+        assert_eq!(bug_fix.match_rate(), 0.0);
+        assert_eq!(report.rules_below(0.5).len(), 1);
+    }
+
+    #[test]
+    fn render_table_includes_every_rule_and_a_histogram() {
+        let corpus = write_corpus("table", &[r#"{"input": "fix the bug", "expected_intent": "bug_fix"}"#]);
+        let report = run(corpus.to_str().unwrap(), &grammar_path()).unwrap();
+        std::fs::remove_file(&corpus).ok();
+
+        let table = report.render_table();
+        assert!(table.contains("bug_fix"));
+        assert!(table.contains("confidence histogram"));
+    }
+}
@@ -5,10 +5,20 @@
 //! - Symbol names (functions, classes, variables)
 //! - Code references
 //! - Natural language descriptions
+//!
+//! Plain [`extract_entities`] only ever guesses from the text itself, so a
+//! `FunctionName`/`ClassName` entity is never more than a low-confidence
+//! regex match (any PascalCase word looks like a class). When a `FilePath`
+//! entity resolves to a real file, [`extract_entities_grounded`] parses that
+//! file with [`crate::code_symbols`] and promotes any mentioned symbol that
+//! matches a real definition to high confidence with a source span.
 
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashSet;
+use std::path::Path;
+
+use crate::code_symbols;
 
 lazy_static! {
     /// File path pattern
@@ -59,13 +69,30 @@ lazy_static! {
     };
 }
 
+/// How sure we are that a `FunctionName`/`ClassName` guess is a real symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confidence {
+    /// Only matched the text-level regex; could be a false positive.
+    Low,
+    /// Verified against a real definition parsed out of the referenced file.
+    High,
+}
+
+/// A byte range within a file, pointing at the node that defines a symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceSpan {
+    pub file: String,
+    pub start_byte: usize,
+    pub end_byte: usize,
+}
+
 /// An extracted entity
 #[derive(Debug, Clone, PartialEq)]
 pub enum Entity {
     FilePath(String),
     SymbolRef(String),
-    FunctionName(String),
-    ClassName(String),
+    FunctionName { name: String, confidence: Confidence, span: Option<SourceSpan> },
+    ClassName { name: String, confidence: Confidence, span: Option<SourceSpan> },
     LineNumber(u32),
     Identifier(String),
 }
@@ -91,15 +118,15 @@ pub fn extract_entities(text: &str) -> Vec<Entity> {
     for cap in FUNCTION_CALL.captures_iter(text) {
         let name = cap.get(1).unwrap().as_str();
         if !is_common_word(name) {
-            entities.push(Entity::FunctionName(name.to_string()));
+            entities.push(Entity::FunctionName { name: name.to_string(), confidence: Confidence::Low, span: None });
         }
     }
-    
+
     // Extract class names
     for cap in CLASS_NAME.captures_iter(text) {
         let name = cap.get(1).unwrap().as_str();
         if !is_common_word(name) && name.len() > 2 {
-            entities.push(Entity::ClassName(name.to_string()));
+            entities.push(Entity::ClassName { name: name.to_string(), confidence: Confidence::Low, span: None });
         }
     }
     
@@ -113,6 +140,61 @@ pub fn extract_entities(text: &str) -> Vec<Entity> {
     entities
 }
 
+/// Extract entities, then promote any `FunctionName`/`ClassName` guess that
+/// matches a real definition found in a mentioned `FilePath` to high
+/// confidence with a source span. `workspace_root` anchors relative file
+/// paths; paths that don't resolve to a file with a registered grammar are
+/// left as low-confidence text guesses.
+pub fn extract_entities_grounded(text: &str, workspace_root: &Path) -> Vec<Entity> {
+    let mut entities = extract_entities(text);
+
+    let defined: Vec<_> = entities
+        .iter()
+        .filter_map(|e| match e {
+            Entity::FilePath(p) => Some(workspace_root.join(p)),
+            _ => None,
+        })
+        .filter_map(|path| code_symbols::defined_symbols_in_file(&path).map(|symbols| (path, symbols)))
+        .collect();
+
+    for entity in &mut entities {
+        let (target_name, is_function) = match entity {
+            Entity::FunctionName { name, .. } => (name.clone(), true),
+            Entity::ClassName { name, .. } => (name.clone(), false),
+            _ => continue,
+        };
+
+        for (path, symbols) in &defined {
+            if let Some(symbol) = symbols.get(&target_name) {
+                let matches_kind = match symbol.kind {
+                    code_symbols::SymbolKind::Function => is_function,
+                    code_symbols::SymbolKind::Type => !is_function,
+                };
+                if !matches_kind {
+                    continue;
+                }
+
+                let span = SourceSpan {
+                    file: path.display().to_string(),
+                    start_byte: symbol.start_byte,
+                    end_byte: symbol.end_byte,
+                };
+                match entity {
+                    Entity::FunctionName { confidence, span: entity_span, .. }
+                    | Entity::ClassName { confidence, span: entity_span, .. } => {
+                        *confidence = Confidence::High;
+                        *entity_span = Some(span);
+                    }
+                    _ => unreachable!(),
+                }
+                break;
+            }
+        }
+    }
+
+    entities
+}
+
 /// Extract just file paths from text
 pub fn extract_file_paths(text: &str) -> Vec<String> {
     extract_entities(text)
@@ -129,7 +211,9 @@ pub fn extract_symbols(text: &str) -> Vec<String> {
     extract_entities(text)
         .into_iter()
         .filter_map(|e| match e {
-            Entity::SymbolRef(s) | Entity::FunctionName(s) | Entity::ClassName(s) => Some(s),
+            Entity::SymbolRef(s) => Some(s),
+            Entity::FunctionName { name, .. } => Some(name),
+            Entity::ClassName { name, .. } => Some(name),
             _ => None,
         })
         .collect()
@@ -190,7 +274,11 @@ mod tests {
     #[test]
     fn test_class_name_extraction() {
         let entities = extract_entities("the UserService class is broken");
-        assert!(entities.contains(&Entity::ClassName("UserService".to_string())));
+        assert!(entities.contains(&Entity::ClassName {
+            name: "UserService".to_string(),
+            confidence: Confidence::Low,
+            span: None,
+        }));
     }
 
     #[test]
@@ -198,4 +286,41 @@ mod tests {
         let entities = extract_entities("error on line 42");
         assert!(entities.contains(&Entity::LineNumber(42)));
     }
+
+    #[test]
+    fn test_grounded_extraction_promotes_verified_class() {
+        let dir = std::env::temp_dir().join(format!("tdln_entities_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("user_service.rs"), "struct UserService { id: u32 }\n").unwrap();
+
+        let entities = extract_entities_grounded("the UserService class in user_service.rs is broken", &dir);
+        let class = entities
+            .iter()
+            .find(|e| matches!(e, Entity::ClassName { name, .. } if name == "UserService"))
+            .unwrap();
+        match class {
+            Entity::ClassName { confidence, span, .. } => {
+                assert_eq!(*confidence, Confidence::High);
+                assert!(span.is_some());
+            }
+            _ => unreachable!(),
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_grounded_extraction_leaves_unverified_guess_low_confidence() {
+        let dir = std::env::temp_dir().join(format!("tdln_entities_test_empty_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let entities = extract_entities_grounded("the UserService class is broken", &dir);
+        let class = entities
+            .iter()
+            .find(|e| matches!(e, Entity::ClassName { name, .. } if name == "UserService"))
+            .unwrap();
+        assert!(matches!(class, Entity::ClassName { confidence: Confidence::Low, span: None, .. }));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -0,0 +1,177 @@
+//! Levenshtein-based fuzzy matching for the abstain path.
+//!
+//! `match_text` only ever tries exact regex patterns, so a single typo
+//! ("refacor", "ad drk mode") sends otherwise-clear commands straight to
+//! abstain with a generic suggestion list. This module compares the user's
+//! tokens against the literal (non-slot) keywords drawn from every loaded
+//! pattern, using edit distance capped at `max(len/3, 1)` so obviously
+//! unrelated words are rejected cheaply. [`autocorrect_text`] lets
+//! [`crate::matcher::match_text`] retry a near-miss command before giving
+//! up; [`find_fuzzy_matches`] ranks the closest intents for the abstain
+//! clarification when even the corrected text doesn't match.
+use crate::grammar::CompiledGrammar;
+
+/// Edit distance threshold used throughout: roughly a third of the word's
+/// length, with a floor of 1 so single-character words still tolerate one typo.
+pub fn distance_threshold(word: &str) -> usize {
+    (word.chars().count() / 3).max(1)
+}
+
+/// Classic Levenshtein distance, but bails out as soon as the running
+/// minimum for a row exceeds `max_distance` — callers only need to know
+/// whether a candidate is within threshold, not its exact distance beyond it.
+pub fn bounded_levenshtein(a: &str, b: &str, max_distance: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.len().abs_diff(b.len()) > max_distance {
+        return None;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut row = vec![0usize; b.len() + 1];
+        row[0] = i;
+        let mut row_min = row[0];
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            row[j] = (prev[j] + 1).min(row[j - 1] + 1).min(prev[j - 1] + cost);
+            row_min = row_min.min(row[j]);
+        }
+        if row_min > max_distance {
+            return None;
+        }
+        prev = row;
+    }
+
+    let distance = prev[b.len()];
+    if distance <= max_distance {
+        Some(distance)
+    } else {
+        None
+    }
+}
+
+/// A candidate keyword close to one of the user's input tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    pub input_token: String,
+    pub keyword: String,
+    pub rule_name: String,
+    pub distance: usize,
+    pub pattern_confidence: f64,
+}
+
+/// Compare every token in `text` against the literal keywords of every
+/// pattern in `grammar`, keeping candidates within [`distance_threshold`].
+/// Ranked by `(distance asc, pattern confidence desc)`, deduplicated to the
+/// single best candidate per rule, truncated to `top_n`.
+pub fn find_fuzzy_matches(text: &str, grammar: &CompiledGrammar, top_n: usize) -> Vec<FuzzyMatch> {
+    let tokens: Vec<String> = text.split_whitespace().map(|t| t.to_lowercase()).collect();
+    let mut candidates = Vec::new();
+
+    for rule in &grammar.rules {
+        for pattern in &rule.patterns {
+            for word in pattern.original.split_whitespace().filter(|w| !w.starts_with('{')) {
+                let word_lower = word.to_lowercase();
+                for token in &tokens {
+                    if token == &word_lower {
+                        continue; // exact match isn't a "near miss"
+                    }
+                    let threshold = distance_threshold(token).max(distance_threshold(&word_lower));
+                    if let Some(distance) = bounded_levenshtein(token, &word_lower, threshold) {
+                        candidates.push(FuzzyMatch {
+                            input_token: token.clone(),
+                            keyword: word.to_string(),
+                            rule_name: rule.name.clone(),
+                            distance,
+                            pattern_confidence: pattern.specificity as f64,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        a.distance
+            .cmp(&b.distance)
+            .then_with(|| b.pattern_confidence.partial_cmp(&a.pattern_confidence).unwrap_or(std::cmp::Ordering::Equal))
+    });
+
+    let mut seen_rules = std::collections::HashSet::new();
+    candidates.retain(|c| seen_rules.insert(c.rule_name.clone()));
+    candidates.truncate(top_n);
+    candidates
+}
+
+/// Rewrite `text`, replacing any token that's a near-miss of a grammar
+/// keyword (and not an exact match of anything already) with that keyword.
+/// Returns `None` if no token needed correcting. The returned `usize` is the
+/// total edit distance spent correcting, used to discount match confidence.
+pub fn autocorrect_text(text: &str, grammar: &CompiledGrammar) -> Option<(String, usize)> {
+    let vocabulary: Vec<&str> = grammar
+        .rules
+        .iter()
+        .flat_map(|r| &r.patterns)
+        .flat_map(|p| p.original.split_whitespace())
+        .filter(|w| !w.starts_with('{'))
+        .collect();
+
+    let mut corrected_tokens = Vec::new();
+    let mut total_distance = 0usize;
+    let mut corrected_any = false;
+
+    for token in text.split_whitespace() {
+        if vocabulary.iter().any(|w| w.eq_ignore_ascii_case(token)) {
+            corrected_tokens.push(token.to_string());
+            continue;
+        }
+
+        let threshold = distance_threshold(token);
+        let mut best: Option<(&str, usize)> = None;
+        for word in &vocabulary {
+            if let Some(distance) = bounded_levenshtein(&token.to_lowercase(), &word.to_lowercase(), threshold) {
+                if best.map(|(_, d)| distance < d).unwrap_or(true) {
+                    best = Some((word, distance));
+                }
+            }
+        }
+
+        match best {
+            Some((word, distance)) => {
+                corrected_tokens.push(word.to_string());
+                total_distance += distance;
+                corrected_any = true;
+            }
+            None => corrected_tokens.push(token.to_string()),
+        }
+    }
+
+    if corrected_any {
+        Some((corrected_tokens.join(" "), total_distance))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_levenshtein_finds_close_words() {
+        assert_eq!(bounded_levenshtein("refacor", "refactor", 3), Some(1));
+    }
+
+    #[test]
+    fn bounded_levenshtein_bails_on_distant_words() {
+        assert_eq!(bounded_levenshtein("refacor", "delete", 2), None);
+    }
+
+    #[test]
+    fn distance_threshold_has_floor_of_one() {
+        assert_eq!(distance_threshold("ad"), 1);
+        assert_eq!(distance_threshold("refactor"), 2);
+    }
+}
@@ -9,6 +9,8 @@
 use serde::Deserialize;
 use std::collections::HashMap;
 
+use crate::combinator::{Pattern, Segment};
+
 /// Top-level grammar file structure
 #[derive(Debug, Clone, Deserialize)]
 pub struct GrammarFile {
@@ -91,6 +93,32 @@ pub struct CompiledGrammar {
     pub rules: Vec<CompiledRule>,
     pub slot_types: HashMap<String, SlotType>,
     pub abstain_config: Option<AbstainConfig>,
+    /// Inverted index from a literal atom to every rule index that requires
+    /// it in at least one pattern -- see [`CompiledGrammar::candidate_rules`].
+    /// `pub(crate)` rather than private so `build.rs`'s generated
+    /// constructors (emitted into `crate::compiled`, a sibling module) can
+    /// set it directly instead of recomputing it from scratch at startup.
+    pub(crate) literal_index: HashMap<String, Vec<usize>>,
+    /// Every pattern's source compiled into one [`regex::RegexSet`] for
+    /// [`CompiledGrammar::match_input`]'s single-pass scan -- see
+    /// [`PatternSet`]. `pub(crate)` for the same reason as `literal_index`.
+    pub(crate) pattern_set: PatternSet,
+}
+
+/// The `RegexSet`-backed alternative to driving one pattern's combinator
+/// [`Pattern`] at a time: every compiled pattern's source is translated to
+/// a regex (see [`pattern_regex_source`]) up front, and
+/// [`CompiledGrammar::match_input`] runs [`regex::RegexSet::matches`] once
+/// over all of them before falling back to the individual compiled
+/// [`regex::Regex`]es -- at `set_idx` -- to pull out named captures, rather
+/// than looping over every pattern's own `is_match` call.
+#[derive(Debug, Clone)]
+pub(crate) struct PatternSet {
+    regex_set: regex::RegexSet,
+    compiled: Vec<regex::Regex>,
+    /// Parallel to `compiled`: which rule/pattern each `RegexSet` member
+    /// index owns.
+    index: Vec<(usize, usize)>,
 }
 
 /// A compiled rule with regex patterns
@@ -105,14 +133,18 @@ pub struct CompiledRule {
     pub read_only: bool,
 }
 
-/// A compiled pattern with extracted slot names
+/// A compiled pattern, parsed into a sequence of combinator [`Segment`]s.
 #[derive(Debug, Clone)]
 pub struct CompiledPattern {
     pub original: String,
-    pub regex: regex::Regex,
+    pub pattern: Pattern,
     pub slot_names: Vec<String>,
     /// Specificity score (more literal chars = higher)
     pub specificity: usize,
+    /// Lowercased literal words (the non-`{slot}` tokens), used by
+    /// [`CompiledGrammar::candidate_rules`] to decide whether this pattern
+    /// could possibly match before anything actually tries to parse it.
+    pub literal_atoms: Vec<String>,
 }
 
 impl CompiledGrammar {
@@ -133,7 +165,7 @@ impl CompiledGrammar {
         for rule in file.rules {
             let compiled_patterns: Vec<CompiledPattern> = rule.patterns
                 .iter()
-                .filter_map(|p| compile_pattern(p).ok())
+                .filter_map(|p| compile_pattern(p, &rule.params, &file.slots).ok())
                 .collect();
             
             if !compiled_patterns.is_empty() {
@@ -155,66 +187,307 @@ impl CompiledGrammar {
             let b_spec: usize = b.patterns.iter().map(|p| p.specificity).sum::<usize>() / b.patterns.len().max(1);
             b_spec.cmp(&a_spec)
         });
-        
+
+        let literal_index = build_literal_index(&rules);
+        let pattern_set = build_pattern_set(&rules)?;
+
         Ok(CompiledGrammar {
             rules,
             slot_types: file.slots,
             abstain_config: file.abstain,
+            literal_index,
+            pattern_set,
         })
     }
+
+    /// Single-pass alternative to [`crate::matcher::match_all`]: rather than
+    /// replaying every [`CompiledGrammar::candidate_rules`] hit through the
+    /// combinator engine one rule at a time, scan every pattern's
+    /// [`PatternSet`] regex at once via `RegexSet::matches`, then only run
+    /// the individual regexes for the patterns that actually hit to pull out
+    /// named captures. Like `match_all`, returns every pattern that matched
+    /// rather than committing to one winner, so ambiguity between rules can
+    /// be detected directly instead of inferred from confidence scores.
+    pub fn match_input(&self, text: &str) -> Vec<crate::matcher::MatchResult> {
+        self.pattern_set
+            .regex_set
+            .matches(text)
+            .into_iter()
+            .filter_map(|set_idx| {
+                let (rule_idx, pattern_idx) = self.pattern_set.index[set_idx];
+                let rule = &self.rules[rule_idx];
+                let pattern = rule.patterns.get(pattern_idx)?;
+                let captures = self.pattern_set.compiled[set_idx].captures(text)?;
+
+                let slots: HashMap<String, String> = pattern
+                    .slot_names
+                    .iter()
+                    .filter_map(|name| captures.name(name).map(|m| (name.clone(), m.as_str().to_string())))
+                    .collect();
+
+                Some(crate::matcher::MatchResult::Match(crate::matcher::build_intent_match(
+                    rule,
+                    pattern,
+                    &slots,
+                    0.0,
+                    Vec::new(),
+                )))
+            })
+            .collect()
+    }
+
+    /// Rule indices that could possibly match `tokens`, decided purely from
+    /// literal atoms rather than running any pattern's parser: a rule is a
+    /// candidate if at least one of its patterns either has no literal atoms
+    /// at all (an all-slot pattern, always a candidate) or has every one of
+    /// its literal atoms present somewhere in `tokens`.
+    ///
+    /// This doesn't sit on [`crate::matcher::match_text`]'s main parse
+    /// path -- that drives one compiled LALR table over every rule in a
+    /// single pass (see [`crate::lalr::compile`]). Its caller is
+    /// [`crate::matcher::match_all`], which `match_text` runs after an
+    /// accepted parse to look for rival rules, so only the prefiltered
+    /// candidates are replayed through the combinator engine.
+    pub fn candidate_rules(&self, tokens: &[&str]) -> Vec<usize> {
+        let present: std::collections::HashSet<String> =
+            tokens.iter().map(|t| t.to_lowercase()).collect();
+
+        let mut candidates: Vec<usize> = Vec::new();
+        for (idx, rule) in self.rules.iter().enumerate() {
+            let is_candidate = rule.patterns.iter().any(|p| {
+                p.literal_atoms.is_empty() || p.literal_atoms.iter().all(|atom| present.contains(atom))
+            });
+            if is_candidate {
+                candidates.push(idx);
+            }
+        }
+        candidates
+    }
+
+    /// Rule indices indexed by a single literal atom, e.g. every rule that
+    /// requires the word "rename" in at least one pattern. Exposed for
+    /// callers that want to reason about one atom at a time instead of
+    /// calling [`CompiledGrammar::candidate_rules`] per full input.
+    pub fn rules_requiring(&self, atom: &str) -> &[usize] {
+        self.literal_index.get(atom).map(Vec::as_slice).unwrap_or(&[])
+    }
 }
 
-/// Compile a pattern string with {slot} placeholders into a regex
-fn compile_pattern(pattern: &str) -> Result<CompiledPattern, String> {
-    let mut regex_str = String::from("^");
-    let mut slot_names = Vec::new();
-    let mut specificity = 0;
-    
-    let mut chars = pattern.chars().peekable();
-    
-    while let Some(c) = chars.next() {
-        if c == '{' {
-            // Extract slot name
-            let mut slot_name = String::new();
-            while let Some(&next) = chars.peek() {
-                if next == '}' {
-                    chars.next();
-                    break;
-                }
-                slot_name.push(chars.next().unwrap());
+/// Build the atom -> rule-index inverted index used by
+/// [`CompiledGrammar::candidate_rules`]/[`CompiledGrammar::rules_requiring`].
+pub(crate) fn build_literal_index(rules: &[CompiledRule]) -> HashMap<String, Vec<usize>> {
+    let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+    for (rule_idx, rule) in rules.iter().enumerate() {
+        for atom in rule.patterns.iter().flat_map(|p| &p.literal_atoms) {
+            let entry = index.entry(atom.clone()).or_default();
+            if entry.last() != Some(&rule_idx) {
+                entry.push(rule_idx);
             }
-            
-            slot_names.push(slot_name.clone());
-            
-            // Create a capturing group for the slot
-            // Use non-greedy matching for better results
-            regex_str.push_str(&format!("(?P<{}>.*?)", slot_name));
-        } else {
-            // Escape regex special characters and count literal chars
-            if c.is_alphanumeric() || c == ' ' {
-                specificity += 1;
+        }
+    }
+    index
+}
+
+/// Build the [`PatternSet`] [`CompiledGrammar::match_input`] scans: every
+/// pattern's segments translated to a regex source via
+/// [`pattern_regex_source`], compiled both individually and as one
+/// [`regex::RegexSet`]. A pattern whose translated source fails to compile
+/// (shouldn't happen for anything [`compile_pattern`] itself accepted, but
+/// regex translation is necessarily approximate) is dropped rather than
+/// erroring the whole grammar load, same as an unresolved slot type. A
+/// failure to combine the surviving sources into one `RegexSet` *is*
+/// propagated, though -- unlike a single bad pattern, that would silently
+/// disable `match_input` for the entire grammar with nothing to show for it.
+pub(crate) fn build_pattern_set(rules: &[CompiledRule]) -> Result<PatternSet, String> {
+    let mut sources = Vec::new();
+    let mut compiled = Vec::new();
+    let mut index = Vec::new();
+
+    for (rule_idx, rule) in rules.iter().enumerate() {
+        for (pattern_idx, pattern) in rule.patterns.iter().enumerate() {
+            let source = pattern_regex_source(&pattern.pattern.segments);
+            if let Ok(re) = regex::Regex::new(&source) {
+                sources.push(source);
+                compiled.push(re);
+                index.push((rule_idx, pattern_idx));
             }
-            
-            if "\\^$.|?*+()[]{}".contains(c) {
-                regex_str.push('\\');
+        }
+    }
+
+    let regex_set = regex::RegexSet::new(&sources)
+        .map_err(|e| format!("failed to build pattern RegexSet: {e}"))?;
+
+    Ok(PatternSet { regex_set, compiled, index })
+}
+
+/// Translate a pattern's combinator [`Segment`]s into a single anchored
+/// regex source, approximating what [`Pattern::parse`] accepts: each
+/// literal becomes a case-insensitive literal (matching
+/// [`crate::combinator`]'s `eq_ignore_ascii_case` comparison) and each slot
+/// becomes a named capture group (typed slots become an alternation of
+/// their `type_patterns`, anchors stripped since they're now embedded
+/// mid-pattern rather than matching a whole captured span on their own).
+///
+/// An optional slot folds its separator into its own `(?:...)?` group
+/// rather than leaving a bare `\s+` next to it, so skipping it doesn't
+/// leave either a demanded space that was never there or two neighbors
+/// glued together with none at all:
+///   - An interior or trailing optional slot (something precedes it) folds
+///     in its *leading* separator: `A(?:\s+SLOT)?` / `A(?:\s+SLOT)?\s+B` --
+///     if skipped, `A`'s normal trailing space (independently owned by
+///     whatever mandatory segment follows) is all that's left between its
+///     neighbors, exactly as if the slot and one adjacent space had been
+///     deleted together.
+///   - A leading optional slot (nothing precedes it) has no leading
+///     separator to fold in, so it folds in its *trailing* one instead:
+///     `(?:SLOT\s+)?B` -- and `B` (or whichever mandatory segment follows)
+///     must skip the leading separator it would otherwise own, since the
+///     slot already accounted for it.
+/// This is still an approximation: two adjacent optional slots would each
+/// try to own the separator between them and double it up. Real grammars
+/// don't do that, so it's left unhandled.
+fn pattern_regex_source(segments: &[Segment]) -> String {
+    let mut source = String::from("(?i)^");
+    let last = segments.len().saturating_sub(1);
+    // Whether the previous segment was a leading (position-0) optional slot
+    // that already folded in the separator this segment would otherwise own.
+    let mut prev_was_leading_optional = false;
+
+    for (i, segment) in segments.iter().enumerate() {
+        let skip_own_separator = i == 0 || prev_was_leading_optional;
+
+        match segment {
+            Segment::Literal(word) => {
+                if !skip_own_separator {
+                    source.push_str(r"\s+");
+                }
+                source.push_str(&regex::escape(word));
+            }
+            Segment::Slot { name, required, type_patterns } => {
+                let inner = if type_patterns.is_empty() {
+                    ".+?".to_string()
+                } else {
+                    type_patterns.iter().map(|p| strip_anchors(p)).collect::<Vec<_>>().join("|")
+                };
+                let group = format!("(?P<{name}>{inner})");
+
+                if *required {
+                    if !skip_own_separator {
+                        source.push_str(r"\s+");
+                    }
+                    source.push_str(&group);
+                } else if i == 0 {
+                    source.push_str(&if i < last {
+                        format!(r"(?:{group}\s+)?")
+                    } else {
+                        format!("{group}?")
+                    });
+                } else {
+                    source.push_str(&format!(r"(?:\s+{group})?"));
+                }
             }
-            regex_str.push(c);
         }
+
+        prev_was_leading_optional =
+            i == 0 && matches!(segment, Segment::Slot { required: false, .. });
+    }
+
+    source.push('$');
+    source
+}
+
+/// Strip a leading `^`/trailing `$` a [`SlotType`] pattern may carry (e.g.
+/// the anchored regex [`crate::normalizer::glob_to_regex`] produces) --
+/// embedded as an alternative mid-pattern in [`pattern_regex_source`],
+/// those anchors would assert against the whole haystack rather than the
+/// captured span, rejecting everything.
+fn strip_anchors(pattern: &str) -> &str {
+    let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+    pattern.strip_suffix('$').unwrap_or(pattern)
+}
+
+/// Resolve one [`SlotPattern`] to the regex source [`crate::combinator::matches_any_type`]
+/// should match a captured slot value against. A `glob` pattern's `pattern`
+/// field is a shell-style glob (e.g. `src/**/*.ts`), translated via
+/// [`crate::normalizer::glob_to_regex`]; every other type is already a
+/// regex source and passes through unchanged. An unparseable glob is
+/// dropped rather than erroring the whole grammar load, same as an
+/// unresolved slot type name leaving a slot untyped.
+fn slot_pattern_regex_source(slot_pattern: &SlotPattern) -> Option<String> {
+    if slot_pattern.r#type == "glob" {
+        crate::normalizer::glob_to_regex(&slot_pattern.pattern)
+            .ok()
+            .map(|re| re.as_str().to_string())
+    } else {
+        Some(slot_pattern.pattern.clone())
+    }
+}
+
+/// Compile a pattern string with `{slot}` placeholders into a sequence of
+/// combinator [`Segment`]s. A `{slot}` becomes optional when `params` marks
+/// it `required: false`; unknown slots (absent from `params`) default to
+/// required, matching the old regex engine's behavior of always capturing.
+///
+/// A slot may also pin its type inline as `{name:type}`, overriding the
+/// type `params` declares for `name` -- this lets one pattern narrow a
+/// param to a more specific [`SlotType`] than its rule-wide default. The
+/// resolved type name is looked up in `slot_types` and its `patterns` are
+/// attached to the segment so [`crate::combinator::matches_any_type`] can
+/// reject a captured span of the wrong kind; an unresolved type name (not
+/// declared in `slot_types`) leaves the slot untyped rather than erroring,
+/// since an unknown type is the same "no constraint" case as no type at all.
+fn compile_pattern(
+    pattern: &str,
+    params: &HashMap<String, ParamSpec>,
+    slot_types: &HashMap<String, SlotType>,
+) -> Result<CompiledPattern, String> {
+    let mut segments = Vec::new();
+    let mut slot_names = Vec::new();
+    let mut literal_atoms = Vec::new();
+    let mut specificity = 0;
+
+    for word in pattern.split_whitespace() {
+        if let Some(inner) = word.strip_prefix('{').and_then(|w| w.strip_suffix('}')) {
+            let (name, inline_type) = match inner.split_once(':') {
+                Some((name, r#type)) => (name, Some(r#type)),
+                None => (inner, None),
+            };
+            let required = params.get(name).map(|p| p.required).unwrap_or(true);
+            let type_name = inline_type.or_else(|| params.get(name).map(|p| p.r#type.as_str()));
+            let type_patterns: Vec<String> = type_name
+                .and_then(|t| slot_types.get(t))
+                .map(|slot_type| {
+                    slot_type
+                        .patterns
+                        .iter()
+                        .filter_map(|p| slot_pattern_regex_source(p))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            slot_names.push(name.to_string());
+            segments.push(Segment::Slot {
+                name: name.to_string(),
+                required,
+                type_patterns,
+            });
+        } else {
+            specificity += word.chars().filter(|c| c.is_alphanumeric()).count();
+            literal_atoms.push(word.to_lowercase());
+            segments.push(Segment::Literal(word.to_string()));
+        }
+    }
+
+    if segments.is_empty() {
+        return Err("pattern has no tokens".to_string());
     }
-    
-    regex_str.push('$');
-    
-    // Make the regex case-insensitive
-    let regex = regex::RegexBuilder::new(&regex_str)
-        .case_insensitive(true)
-        .build()
-        .map_err(|e| format!("Invalid pattern regex: {}", e))?;
-    
+
     Ok(CompiledPattern {
         original: pattern.to_string(),
-        regex,
+        pattern: Pattern::new(segments),
         slot_names,
         specificity,
+        literal_atoms,
     })
 }
 
@@ -241,18 +514,254 @@ mod tests {
 
     #[test]
     fn test_pattern_compilation() {
-        let pattern = compile_pattern("fix {target}").unwrap();
+        let pattern = compile_pattern("fix {target}", &HashMap::new(), &HashMap::new()).unwrap();
         assert_eq!(pattern.slot_names, vec!["target"]);
-        assert!(pattern.regex.is_match("fix the bug"));
+        let tokens: Vec<&str> = "fix the bug".split_whitespace().collect();
+        assert!(matches!(
+            pattern.pattern.parse(&tokens),
+            crate::combinator::ParseOutcome::Matched { .. }
+        ));
     }
 
     #[test]
     fn test_multiple_slots() {
-        let pattern = compile_pattern("rename {source} to {destination}").unwrap();
+        let pattern = compile_pattern("rename {source} to {destination}", &HashMap::new(), &HashMap::new()).unwrap();
         assert_eq!(pattern.slot_names, vec!["source", "destination"]);
-        
-        let caps = pattern.regex.captures("rename foo to bar").unwrap();
-        assert_eq!(caps.name("source").unwrap().as_str(), "foo");
-        assert_eq!(caps.name("destination").unwrap().as_str(), "bar");
+
+        let tokens: Vec<&str> = "rename foo to bar".split_whitespace().collect();
+        match pattern.pattern.parse(&tokens) {
+            crate::combinator::ParseOutcome::Matched { slots, .. } => {
+                assert_eq!(slots["source"].value, "foo");
+                assert_eq!(slots["destination"].value, "bar");
+            }
+            other => panic!("expected match, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_connector_reports_expected_literal() {
+        let pattern = compile_pattern("rename {source} to {destination}", &HashMap::new(), &HashMap::new()).unwrap();
+        let tokens: Vec<&str> = "rename foo bar".split_whitespace().collect();
+        match pattern.pattern.parse(&tokens) {
+            crate::combinator::ParseOutcome::Failed { expected, .. } => {
+                assert_eq!(
+                    expected,
+                    vec![crate::combinator::Expectation::Literal("to".to_string())]
+                );
+            }
+            other => panic!("expected failure, got {other:?}"),
+        }
+    }
+
+    fn file_path_slot_types() -> HashMap<String, SlotType> {
+        let mut types = HashMap::new();
+        types.insert(
+            "file_path".to_string(),
+            SlotType {
+                description: "A path to a source file".to_string(),
+                patterns: vec![SlotPattern {
+                    pattern: r"^\S+\.\w+$".to_string(),
+                    r#type: "file_path".to_string(),
+                }],
+            },
+        );
+        types
+    }
+
+    #[test]
+    fn compile_pattern_attaches_type_patterns_from_a_param_default() {
+        let mut params = HashMap::new();
+        params.insert(
+            "target".to_string(),
+            ParamSpec { r#type: "file_path".to_string(), required: true, default: None },
+        );
+        let pattern = compile_pattern("fix {target}", &params, &file_path_slot_types()).unwrap();
+
+        match &pattern.pattern.segments[1] {
+            Segment::Slot { type_patterns, .. } => {
+                assert_eq!(type_patterns, &vec![r"^\S+\.\w+$".to_string()]);
+            }
+            other => panic!("expected a slot segment, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn compile_pattern_inline_type_overrides_the_param_default() {
+        let mut params = HashMap::new();
+        params.insert(
+            "target".to_string(),
+            ParamSpec { r#type: "string".to_string(), required: true, default: None },
+        );
+        let pattern = compile_pattern("fix {target:file_path}", &params, &file_path_slot_types()).unwrap();
+
+        assert_eq!(pattern.slot_names, vec!["target"]);
+        match &pattern.pattern.segments[1] {
+            Segment::Slot { type_patterns, .. } => {
+                assert_eq!(type_patterns, &vec![r"^\S+\.\w+$".to_string()]);
+            }
+            other => panic!("expected a slot segment, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn compile_pattern_translates_a_glob_slot_type_to_an_anchored_regex() {
+        let mut types = HashMap::new();
+        types.insert(
+            "glob_target".to_string(),
+            SlotType {
+                description: "A glob matching one or more source files".to_string(),
+                patterns: vec![SlotPattern { pattern: "src/**/*.ts".to_string(), r#type: "glob".to_string() }],
+            },
+        );
+        let mut params = HashMap::new();
+        params.insert(
+            "target".to_string(),
+            ParamSpec { r#type: "glob_target".to_string(), required: true, default: None },
+        );
+        let pattern = compile_pattern("refactor {target}", &params, &types).unwrap();
+
+        match &pattern.pattern.segments[1] {
+            Segment::Slot { type_patterns, .. } => {
+                assert_eq!(type_patterns.len(), 1);
+                let re = regex::Regex::new(&type_patterns[0]).unwrap();
+                assert!(re.is_match("src/a/auth.ts"));
+                assert!(!re.is_match("lib/auth.ts"));
+            }
+            other => panic!("expected a slot segment, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn compile_pattern_unresolved_type_name_leaves_the_slot_untyped() {
+        let pattern = compile_pattern("fix {target:nonexistent}", &HashMap::new(), &HashMap::new()).unwrap();
+        match &pattern.pattern.segments[1] {
+            Segment::Slot { type_patterns, .. } => assert!(type_patterns.is_empty()),
+            other => panic!("expected a slot segment, got {other:?}"),
+        }
+    }
+
+    fn candidate_grammar() -> CompiledGrammar {
+        CompiledGrammar::from_yaml(
+            r#"
+version: "1.0"
+rules:
+  - name: bug_fix
+    description: Fix bugs
+    patterns:
+      - "fix {target}"
+    mode: mechanic
+  - name: file_rename
+    description: Rename a file
+    patterns:
+      - "rename {source} to {destination}"
+    mode: mechanic
+  - name: anything
+    description: All-slot fallback
+    patterns:
+      - "{whatever}"
+    mode: mechanic
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn candidate_rules_requires_every_literal_atom_present() {
+        let grammar = candidate_grammar();
+        let tokens: Vec<&str> = "rename old.ts to new.ts".split_whitespace().collect();
+        let candidates = grammar.candidate_rules(&tokens);
+
+        let names: Vec<&str> = candidates.iter().map(|&idx| grammar.rules[idx].name.as_str()).collect();
+        assert!(names.contains(&"file_rename"));
+        assert!(names.contains(&"anything")); // all-slot pattern is always a candidate
+        assert!(!names.contains(&"bug_fix")); // missing "fix"
+    }
+
+    #[test]
+    fn rules_requiring_looks_up_a_single_atom() {
+        let grammar = candidate_grammar();
+        let rule_idx = grammar.rules.iter().position(|r| r.name == "file_rename").unwrap();
+        assert_eq!(grammar.rules_requiring("rename"), &[rule_idx]);
+        assert!(grammar.rules_requiring("nonexistent").is_empty());
+    }
+
+    #[test]
+    fn match_input_extracts_slots_via_the_regex_set_single_pass() {
+        let grammar = candidate_grammar();
+        let matches = grammar.match_input("rename old.ts to new.ts");
+
+        let rename_match = matches
+            .iter()
+            .find_map(|m| match m {
+                crate::matcher::MatchResult::Match(im) if im.rule == "file_rename" => Some(im),
+                _ => None,
+            })
+            .expect("expected file_rename to match");
+        assert_eq!(rename_match.slots["source"].value, "old.ts");
+        assert_eq!(rename_match.slots["destination"].value, "new.ts");
+    }
+
+    #[test]
+    fn match_input_handles_a_leading_optional_slot_present_or_omitted() {
+        let grammar = CompiledGrammar::from_yaml(
+            r#"
+version: "1.0"
+rules:
+  - name: commit
+    description: Make a commit, optionally with an urgency marker
+    patterns:
+      - "{urgency} commit"
+    params:
+      urgency:
+        type: string
+        required: false
+    mode: mechanic
+"#,
+        )
+        .unwrap();
+
+        let omitted = grammar.match_input("commit");
+        match omitted.as_slice() {
+            [crate::matcher::MatchResult::Match(im)] => assert!(!im.slots.contains_key("urgency")),
+            other => panic!("expected exactly one match with no urgency slot, got {other:?}"),
+        }
+
+        let present = grammar.match_input("urgent commit");
+        match present.as_slice() {
+            [crate::matcher::MatchResult::Match(im)] => assert_eq!(im.slots["urgency"].value, "urgent"),
+            other => panic!("expected exactly one match with an urgency slot, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn match_input_finds_every_pattern_that_matches_not_just_one_winner() {
+        let grammar = CompiledGrammar::from_yaml(
+            r#"
+version: "1.0"
+rules:
+  - name: list_files
+    description: List files
+    patterns:
+      - "list {target}"
+    mode: mechanic
+  - name: ls_alias
+    description: Alias for listing files
+    patterns:
+      - "list {target}"
+    mode: mechanic
+"#,
+        )
+        .unwrap();
+
+        let matches = grammar.match_input("list src");
+        let rule_names: Vec<&str> = matches
+            .iter()
+            .map(|m| match m {
+                crate::matcher::MatchResult::Match(im) => im.rule.as_str(),
+                crate::matcher::MatchResult::Abstain(_) => "",
+            })
+            .collect();
+        assert!(rule_names.contains(&"list_files"));
+        assert!(rule_names.contains(&"ls_alias"));
     }
 }
@@ -0,0 +1,831 @@
+//! SLR(1) grammar compilation for intent patterns.
+//!
+//! [`crate::matcher`] used to try each rule's patterns independently and
+//! pick the highest-confidence match after the fact -- workable for flat
+//! keyword patterns, but unable to express "rename X to Y then commit" as a
+//! single grammar, and it disambiguated inputs that look like two rules at
+//! once (mentioning both "fix" and "add") with a runtime score rather than
+//! with the grammar itself. This module instead compiles every rule's
+//! patterns into one LR(0) item-set automaton with SLR(1) (FOLLOW-set)
+//! reduce lookahead -- the simplest member of the LALR family of table
+//! constructions, and sufficient here because none of the generated
+//! productions are nullable (see [`build_productions`]) -- and
+//! [`parse`] drives it with a standard shift-reduce table walk, so the
+//! grammar's own structure decides which rule a token stream belongs to
+//! instead of a separate per-rule trial-and-score pass.
+//!
+//! Each optional slot in a rule's pattern expands into two alternative
+//! productions (with and without it); each slot itself compiles to a
+//! small left-recursive `Slot -> Value | Slot Value` nonterminal, so a
+//! slot can span any number of tokens and the automaton reduces it the
+//! moment the lookahead token is something only the *rest* of the pattern
+//! could start with.
+//!
+//! Shift/reduce and reduce/reduce conflicts are resolved in favor of the
+//! higher-[`CompiledPattern::specificity`] production (ties keep the
+//! existing choice), and every resolved conflict is recorded in a
+//! [`Conflict`] for the caller to surface as match provenance.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::combinator::Segment;
+use crate::grammar::CompiledGrammar;
+
+/// A grammar terminal. `Keyword` only exists for literal words that appear
+/// in the compiled grammar; every other token lexes as `Value` and feeds a
+/// `Slot` production.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Terminal {
+    Keyword(String),
+    Value,
+    EndOfInput,
+}
+
+/// A grammar symbol: either a terminal or the name of a nonterminal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Symbol {
+    Terminal(Terminal),
+    NonTerminal(String),
+}
+
+/// One alternative (RHS) of a nonterminal. `rule_name`/`pattern_index`
+/// trace a rule production back to the grammar rule/pattern it implements;
+/// `slot_order` names the `Slot` occurrences in `rhs`, in order, so a
+/// reduction can re-attach slot values by name.
+#[derive(Debug, Clone)]
+pub struct Production {
+    pub lhs: String,
+    pub rhs: Vec<Symbol>,
+    pub rule_name: String,
+    pub pattern_index: usize,
+    pub specificity: usize,
+    pub slot_order: Vec<String>,
+}
+
+/// Augmented start nonterminal (`S' -> Start`), used only to define the
+/// automaton's accept condition.
+const AUGMENTED_START: &str = "S'";
+/// Top nonterminal every rule's nonterminal is reachable from.
+const START: &str = "Start";
+/// The recursive nonterminal every `Slot` segment compiles to.
+const SLOT: &str = "Slot";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Action {
+    Shift(usize),
+    Reduce(usize),
+    Accept,
+}
+
+/// A shift/reduce or reduce/reduce conflict resolved at table-build time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub state: usize,
+    pub terminal: Terminal,
+    pub chosen: String,
+    pub discarded: String,
+}
+
+/// A compiled SLR(1) table: the productions it was built from plus the
+/// ACTION/GOTO entries of its LR(0) automaton.
+pub struct ParseTable {
+    pub productions: Vec<Production>,
+    keywords: HashSet<String>,
+    action: HashMap<(usize, Terminal), Action>,
+    goto: HashMap<(usize, String), usize>,
+    start_state: usize,
+    /// Item set per state, kept around (rather than discarded after
+    /// [`compile`] builds the tables) so a rejected parse can report which
+    /// rule it had gotten partway through -- see [`ParseTable::in_progress_rule_at`].
+    item_sets: Vec<ItemSet>,
+    /// Human-readable description of every conflict resolved at a given
+    /// `(state, terminal)`, so [`parse`] can report exactly which conflicts
+    /// its particular path through the table actually relied on.
+    conflict_lookup: HashMap<(usize, Terminal), String>,
+}
+
+impl ParseTable {
+    /// Human-readable description of every terminal this state has an
+    /// action for, for an error message at a rejected parse.
+    fn expected_at(&self, state: usize) -> Vec<String> {
+        let mut expected: Vec<String> = self
+            .action
+            .keys()
+            .filter(|(s, _)| *s == state)
+            .map(|(_, terminal)| describe_terminal(terminal))
+            .collect();
+        expected.sort();
+        expected.dedup();
+        expected
+    }
+
+    /// The rule/pattern a rejected parse had made the most progress through,
+    /// found by scanning `state`'s items for the one with the furthest
+    /// advanced dot (ties keep the first found) -- used to give a "matched X,
+    /// then expected Y" clarification instead of a generic rule list.
+    fn in_progress_rule_at(&self, state: usize) -> Option<(String, usize)> {
+        self.item_sets[state]
+            .iter()
+            .filter(|(prod_idx, dot)| *dot > 0 && !self.productions[*prod_idx].rule_name.is_empty())
+            .max_by_key(|(_, dot)| *dot)
+            .map(|(prod_idx, _)| {
+                let prod = &self.productions[*prod_idx];
+                (prod.rule_name.clone(), prod.pattern_index)
+            })
+    }
+}
+
+fn describe_terminal(terminal: &Terminal) -> String {
+    match terminal {
+        Terminal::Keyword(word) => format!("'{word}'"),
+        Terminal::Value => "a value".to_string(),
+        Terminal::EndOfInput => "nothing else".to_string(),
+    }
+}
+
+fn nt_rule(name: &str) -> String {
+    format!("Rule_{name}")
+}
+
+/// Expand a pattern's segments into every present/absent combination of its
+/// optional slots, returning each combination's RHS symbols paired with the
+/// names (in order) of the `Slot` occurrences it actually contains.
+fn expand_segments(segments: &[Segment]) -> Vec<(Vec<Symbol>, Vec<String>)> {
+    let mut variants: Vec<(Vec<Symbol>, Vec<String>)> = vec![(Vec::new(), Vec::new())];
+
+    for segment in segments {
+        match segment {
+            Segment::Literal(word) => {
+                for (rhs, _) in variants.iter_mut() {
+                    rhs.push(Symbol::Terminal(Terminal::Keyword(word.to_lowercase())));
+                }
+            }
+            Segment::Slot { name, required, .. } => {
+                let mut with_slot = variants.clone();
+                for (rhs, names) in with_slot.iter_mut() {
+                    rhs.push(Symbol::NonTerminal(SLOT.to_string()));
+                    names.push(name.clone());
+                }
+                if *required {
+                    variants = with_slot;
+                } else {
+                    variants.extend(with_slot);
+                }
+            }
+        }
+    }
+
+    variants
+}
+
+/// Build every production of the grammar: `Start -> Rule_x` for each rule,
+/// `Rule_x -> ...` for each of the rule's (slot-expanded) patterns, and the
+/// shared `Slot -> Value | Slot Value` pair.
+pub fn build_productions(grammar: &CompiledGrammar) -> Vec<Production> {
+    let mut productions = Vec::new();
+
+    for rule in &grammar.rules {
+        productions.push(Production {
+            lhs: START.to_string(),
+            rhs: vec![Symbol::NonTerminal(nt_rule(&rule.name))],
+            rule_name: rule.name.clone(),
+            pattern_index: usize::MAX,
+            specificity: 0,
+            slot_order: Vec::new(),
+        });
+    }
+
+    for rule in &grammar.rules {
+        for (pattern_index, pattern) in rule.patterns.iter().enumerate() {
+            for (rhs, slot_order) in expand_segments(&pattern.pattern.segments) {
+                productions.push(Production {
+                    lhs: nt_rule(&rule.name),
+                    rhs,
+                    rule_name: rule.name.clone(),
+                    pattern_index,
+                    specificity: pattern.specificity,
+                    slot_order,
+                });
+            }
+        }
+    }
+
+    productions.push(Production {
+        lhs: SLOT.to_string(),
+        rhs: vec![Symbol::Terminal(Terminal::Value)],
+        rule_name: String::new(),
+        pattern_index: usize::MAX,
+        specificity: 0,
+        slot_order: Vec::new(),
+    });
+    productions.push(Production {
+        lhs: SLOT.to_string(),
+        rhs: vec![Symbol::NonTerminal(SLOT.to_string()), Symbol::Terminal(Terminal::Value)],
+        rule_name: String::new(),
+        pattern_index: usize::MAX,
+        specificity: 0,
+        slot_order: Vec::new(),
+    });
+
+    productions
+}
+
+type Item = (usize, usize);
+type ItemSet = BTreeSet<Item>;
+
+fn closure(mut items: ItemSet, productions: &[Production]) -> ItemSet {
+    loop {
+        let mut added = false;
+        for (prod_idx, dot) in items.clone() {
+            if let Some(Symbol::NonTerminal(nt)) = productions[prod_idx].rhs.get(dot) {
+                for (i, p) in productions.iter().enumerate() {
+                    if &p.lhs == nt && items.insert((i, 0)) {
+                        added = true;
+                    }
+                }
+            }
+        }
+        if !added {
+            return items;
+        }
+    }
+}
+
+fn goto(items: &ItemSet, symbol: &Symbol, productions: &[Production]) -> ItemSet {
+    let moved: ItemSet = items
+        .iter()
+        .filter(|(prod_idx, dot)| productions[*prod_idx].rhs.get(*dot) == Some(symbol))
+        .map(|(prod_idx, dot)| (*prod_idx, dot + 1))
+        .collect();
+    closure(moved, productions)
+}
+
+/// FIRST(nonterminal): since no production here is empty, this is just the
+/// (transitive) set of terminals that can start it -- no nullable-prefix
+/// propagation is needed.
+fn compute_first(productions: &[Production]) -> HashMap<String, HashSet<Terminal>> {
+    let mut first: HashMap<String, HashSet<Terminal>> = productions
+        .iter()
+        .map(|p| (p.lhs.clone(), HashSet::new()))
+        .collect();
+
+    loop {
+        let mut changed = false;
+        for prod in productions {
+            let additions: Vec<Terminal> = match &prod.rhs[0] {
+                Symbol::Terminal(t) => vec![t.clone()],
+                Symbol::NonTerminal(nt) => first.get(nt).cloned().unwrap_or_default().into_iter().collect(),
+            };
+            let entry = first.get_mut(&prod.lhs).unwrap();
+            for t in additions {
+                if entry.insert(t) {
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return first;
+        }
+    }
+}
+
+/// FOLLOW(nonterminal), used as the SLR(1) reduce lookahead. Like FIRST,
+/// this skips nullable-prefix handling since no production here is empty.
+fn compute_follow(productions: &[Production]) -> HashMap<String, HashSet<Terminal>> {
+    let first = compute_first(productions);
+    let mut follow: HashMap<String, HashSet<Terminal>> =
+        productions.iter().map(|p| (p.lhs.clone(), HashSet::new())).collect();
+    follow.entry(AUGMENTED_START.to_string()).or_default().insert(Terminal::EndOfInput);
+
+    loop {
+        let mut changed = false;
+        for prod in productions {
+            for (i, symbol) in prod.rhs.iter().enumerate() {
+                let Symbol::NonTerminal(b) = symbol else { continue };
+                let additions: Vec<Terminal> = match prod.rhs.get(i + 1) {
+                    Some(Symbol::Terminal(t)) => vec![t.clone()],
+                    Some(Symbol::NonTerminal(next)) => first.get(next).cloned().unwrap_or_default().into_iter().collect(),
+                    None => follow.get(&prod.lhs).cloned().unwrap_or_default().into_iter().collect(),
+                };
+                let entry = follow.get_mut(b).unwrap();
+                for t in additions {
+                    if entry.insert(t) {
+                        changed = true;
+                    }
+                }
+            }
+        }
+        if !changed {
+            return follow;
+        }
+    }
+}
+
+fn action_priority(action: &Action, productions: &[Production]) -> usize {
+    match action {
+        // A longer structural match beats stopping early to reduce, mirroring
+        // the greedy-to-boundary behavior of the combinator engine this
+        // replaces; shift/shift conflicts can't occur since GOTO is a
+        // function of (state, symbol).
+        Action::Shift(_) | Action::Accept => usize::MAX,
+        Action::Reduce(idx) => productions[*idx].specificity,
+    }
+}
+
+fn describe_action(action: &Action, productions: &[Production]) -> String {
+    match action {
+        Action::Shift(state) => format!("shift to state {state}"),
+        Action::Reduce(idx) => {
+            let p = &productions[*idx];
+            format!("reduce {} (pattern #{})", p.lhs, p.pattern_index)
+        }
+        Action::Accept => "accept".to_string(),
+    }
+}
+
+fn set_action(
+    action: &mut HashMap<(usize, Terminal), Action>,
+    conflicts: &mut Vec<Conflict>,
+    conflict_lookup: &mut HashMap<(usize, Terminal), String>,
+    productions: &[Production],
+    state: usize,
+    terminal: Terminal,
+    new_action: Action,
+) {
+    match action.get(&(state, terminal.clone())) {
+        None => {
+            action.insert((state, terminal), new_action);
+        }
+        Some(existing) if *existing == new_action => {}
+        Some(existing) => {
+            let (chosen, discarded) = if action_priority(&new_action, productions) > action_priority(existing, productions) {
+                (new_action.clone(), existing.clone())
+            } else {
+                (existing.clone(), new_action.clone())
+            };
+            let chosen_desc = describe_action(&chosen, productions);
+            let discarded_desc = describe_action(&discarded, productions);
+            conflict_lookup.insert(
+                (state, terminal.clone()),
+                format!("chose to {chosen_desc} over {discarded_desc}"),
+            );
+            conflicts.push(Conflict {
+                state,
+                terminal: terminal.clone(),
+                chosen: chosen_desc,
+                discarded: discarded_desc,
+            });
+            action.insert((state, terminal), chosen);
+        }
+    }
+}
+
+/// Compile a grammar's rules into an SLR(1) parse table, along with every
+/// shift/reduce or reduce/reduce conflict that had to be resolved to build
+/// it (empty for an unambiguous grammar).
+pub fn compile(grammar: &CompiledGrammar) -> (ParseTable, Vec<Conflict>) {
+    let mut productions = vec![Production {
+        lhs: AUGMENTED_START.to_string(),
+        rhs: vec![Symbol::NonTerminal(START.to_string())],
+        rule_name: String::new(),
+        pattern_index: usize::MAX,
+        specificity: 0,
+        slot_order: Vec::new(),
+    }];
+    productions.extend(build_productions(grammar));
+
+    let keywords: HashSet<String> = productions
+        .iter()
+        .flat_map(|p| p.rhs.iter())
+        .filter_map(|s| match s {
+            Symbol::Terminal(Terminal::Keyword(w)) => Some(w.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let follow = compute_follow(&productions);
+
+    let start_items = closure(BTreeSet::from([(0usize, 0usize)]), &productions);
+    let mut states: Vec<ItemSet> = vec![start_items];
+    let mut action: HashMap<(usize, Terminal), Action> = HashMap::new();
+    let mut goto_table: HashMap<(usize, String), usize> = HashMap::new();
+    let mut conflicts = Vec::new();
+    let mut conflict_lookup: HashMap<(usize, Terminal), String> = HashMap::new();
+
+    let mut frontier = vec![0usize];
+    while let Some(state_idx) = frontier.pop() {
+        let items = states[state_idx].clone();
+
+        let mut symbols: BTreeSet<Symbol> = BTreeSet::new();
+        for (prod_idx, dot) in &items {
+            if let Some(sym) = productions[*prod_idx].rhs.get(*dot) {
+                symbols.insert(sym.clone());
+            }
+        }
+
+        for sym in symbols {
+            let target_items = goto(&items, &sym, &productions);
+            if target_items.is_empty() {
+                continue;
+            }
+            let target_idx = match states.iter().position(|s| *s == target_items) {
+                Some(pos) => pos,
+                None => {
+                    states.push(target_items);
+                    frontier.push(states.len() - 1);
+                    states.len() - 1
+                }
+            };
+
+            match sym {
+                Symbol::Terminal(terminal) => {
+                    set_action(&mut action, &mut conflicts, &mut conflict_lookup, &productions, state_idx, terminal, Action::Shift(target_idx));
+                }
+                Symbol::NonTerminal(nt) => {
+                    goto_table.insert((state_idx, nt), target_idx);
+                }
+            }
+        }
+
+        for (prod_idx, dot) in &items {
+            let prod = &productions[*prod_idx];
+            if *dot != prod.rhs.len() {
+                continue;
+            }
+            if *prod_idx == 0 {
+                set_action(&mut action, &mut conflicts, &mut conflict_lookup, &productions, state_idx, Terminal::EndOfInput, Action::Accept);
+            } else {
+                for terminal in follow.get(&prod.lhs).cloned().unwrap_or_default() {
+                    set_action(&mut action, &mut conflicts, &mut conflict_lookup, &productions, state_idx, terminal, Action::Reduce(*prod_idx));
+                }
+            }
+        }
+    }
+
+    (
+        ParseTable {
+            productions,
+            keywords,
+            action,
+            goto: goto_table,
+            start_state: 0,
+            item_sets: states,
+            conflict_lookup,
+        },
+        conflicts,
+    )
+}
+
+/// Value assembled on the parser's value stack as productions reduce.
+#[derive(Debug, Clone)]
+enum StackValue {
+    Token(String),
+    Slot(Vec<String>),
+    Rule {
+        rule_name: String,
+        pattern_index: usize,
+        slots: HashMap<String, String>,
+    },
+}
+
+fn reduce(prod: &Production, mut popped: Vec<StackValue>) -> StackValue {
+    if prod.lhs == SLOT {
+        if prod.rhs.len() == 1 {
+            match popped.pop() {
+                Some(StackValue::Token(tok)) => StackValue::Slot(vec![tok]),
+                other => unreachable!("Slot -> Value must reduce a token, got {other:?}"),
+            }
+        } else {
+            let tok = match popped.pop() {
+                Some(StackValue::Token(tok)) => tok,
+                other => unreachable!("Slot -> Slot Value must reduce a trailing token, got {other:?}"),
+            };
+            let mut tokens = match popped.pop() {
+                Some(StackValue::Slot(tokens)) => tokens,
+                other => unreachable!("Slot -> Slot Value must reduce a leading Slot, got {other:?}"),
+            };
+            tokens.push(tok);
+            StackValue::Slot(tokens)
+        }
+    } else if prod.lhs == START {
+        popped.pop().expect("Start -> Rule_x must reduce exactly one value")
+    } else {
+        let mut slot_names = prod.slot_order.iter();
+        let mut slots = HashMap::new();
+        for (symbol, value) in prod.rhs.iter().zip(popped.into_iter()) {
+            if let Symbol::NonTerminal(nt) = symbol {
+                if nt == SLOT {
+                    if let (StackValue::Slot(tokens), Some(name)) = (value, slot_names.next()) {
+                        slots.insert(name.clone(), tokens.join(" "));
+                    }
+                }
+            }
+        }
+        StackValue::Rule {
+            rule_name: prod.rule_name.clone(),
+            pattern_index: prod.pattern_index,
+            slots,
+        }
+    }
+}
+
+/// The outcome of driving [`parse`] over a token sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseOutcome {
+    Accepted {
+        rule_name: String,
+        pattern_index: usize,
+        slots: HashMap<String, String>,
+        /// Descriptions of every conflict this particular parse path relied
+        /// on having been resolved at table-build time (see [`Conflict`]).
+        /// Empty for an unambiguous grammar.
+        conflicts_resolved: Vec<String>,
+    },
+    Rejected {
+        token_position: usize,
+        expected: Vec<String>,
+        /// The rule/pattern the parse had gotten furthest into before
+        /// failing, if any token was consumed -- see
+        /// [`ParseTable::in_progress_rule_at`].
+        best_rule: Option<(String, usize)>,
+    },
+}
+
+/// Drive the table's automaton over `tokens` with the standard shift-reduce
+/// loop: a token lexes as `Keyword` only if the grammar has that literal
+/// *and* the current state has an action for it, so a token that happens to
+/// spell a keyword elsewhere in the grammar can still flow into a `Slot` at
+/// a state expecting a value.
+pub fn parse(table: &ParseTable, tokens: &[&str]) -> ParseOutcome {
+    let mut state_stack = vec![table.start_state];
+    let mut value_stack: Vec<StackValue> = Vec::new();
+    let mut pos = 0usize;
+    let mut conflicts_resolved = Vec::new();
+
+    loop {
+        let state = *state_stack.last().unwrap();
+        let (terminal, token_text) = if pos < tokens.len() {
+            let token = tokens[pos];
+            let lower = token.to_lowercase();
+            if table.keywords.contains(&lower) && table.action.contains_key(&(state, Terminal::Keyword(lower.clone()))) {
+                (Terminal::Keyword(lower), token.to_string())
+            } else {
+                (Terminal::Value, token.to_string())
+            }
+        } else {
+            (Terminal::EndOfInput, String::new())
+        };
+
+        if let Some(description) = table.conflict_lookup.get(&(state, terminal.clone())) {
+            conflicts_resolved.push(description.clone());
+        }
+
+        match table.action.get(&(state, terminal)) {
+            Some(Action::Shift(next)) => {
+                state_stack.push(*next);
+                value_stack.push(StackValue::Token(token_text));
+                pos += 1;
+            }
+            Some(Action::Reduce(prod_idx)) => {
+                let prod = &table.productions[*prod_idx];
+                let mut popped = Vec::with_capacity(prod.rhs.len());
+                for _ in 0..prod.rhs.len() {
+                    state_stack.pop();
+                    popped.push(value_stack.pop().unwrap());
+                }
+                popped.reverse();
+
+                let reduced = reduce(prod, popped);
+
+                let goto_state = *state_stack.last().unwrap();
+                let next_state = *table
+                    .goto
+                    .get(&(goto_state, prod.lhs.clone()))
+                    .expect("every reduction target must have a GOTO entry");
+                state_stack.push(next_state);
+                value_stack.push(reduced);
+            }
+            Some(Action::Accept) => {
+                return match value_stack.pop() {
+                    Some(StackValue::Rule { rule_name, pattern_index, slots }) => {
+                        ParseOutcome::Accepted { rule_name, pattern_index, slots, conflicts_resolved }
+                    }
+                    other => unreachable!("accept must reduce to a Rule value, got {other:?}"),
+                };
+            }
+            None => {
+                return ParseOutcome::Rejected {
+                    token_position: pos,
+                    expected: table.expected_at(state),
+                    best_rule: table.in_progress_rule_at(state),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grammar(yaml: &str) -> CompiledGrammar {
+        CompiledGrammar::from_yaml(yaml).unwrap()
+    }
+
+    fn toks(text: &str) -> Vec<&str> {
+        text.split_whitespace().collect()
+    }
+
+    #[test]
+    fn accepts_simple_pattern_and_extracts_slot() {
+        let g = grammar(
+            r#"
+version: "1.0"
+rules:
+  - name: bug_fix
+    description: Fix bugs
+    patterns:
+      - "fix {target}"
+    params:
+      target:
+        type: file_or_symbol
+    mode: mechanic
+"#,
+        );
+        let (table, conflicts) = compile(&g);
+        assert!(conflicts.is_empty());
+
+        match parse(&table, &toks("fix src/auth.ts")) {
+            ParseOutcome::Accepted { rule_name, slots, .. } => {
+                assert_eq!(rule_name, "bug_fix");
+                assert_eq!(slots["target"], "src/auth.ts");
+            }
+            other => panic!("expected accept, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn disambiguates_rules_sharing_no_prefix_without_scoring() {
+        let g = grammar(
+            r#"
+version: "1.0"
+rules:
+  - name: bug_fix
+    description: Fix bugs
+    patterns:
+      - "fix {target}"
+    params:
+      target:
+        type: file_or_symbol
+    mode: mechanic
+  - name: feature
+    description: Add features
+    patterns:
+      - "add {feature}"
+    params:
+      feature:
+        type: string
+    mode: genius
+"#,
+        );
+        let (table, _) = compile(&g);
+
+        match parse(&table, &toks("add dark mode and fix src/auth.ts")) {
+            ParseOutcome::Accepted { rule_name, slots, .. } => {
+                assert_eq!(rule_name, "feature");
+                // The Slot greedily consumes everything up to end of input
+                // since nothing in this tiny grammar bounds it.
+                assert_eq!(slots["feature"], "dark mode and fix src/auth.ts");
+            }
+            other => panic!("expected accept, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn handles_recursive_rename_then_pattern() {
+        let g = grammar(
+            r#"
+version: "1.0"
+rules:
+  - name: rename_then_commit
+    description: Rename a file then commit
+    patterns:
+      - "rename {source} to {destination} then commit"
+    params:
+      source:
+        type: file_or_symbol
+      destination:
+        type: file_or_symbol
+    mode: mechanic
+"#,
+        );
+        let (table, conflicts) = compile(&g);
+        assert!(conflicts.is_empty());
+
+        match parse(&table, &toks("rename old.ts to new.ts then commit")) {
+            ParseOutcome::Accepted { rule_name, slots, .. } => {
+                assert_eq!(rule_name, "rename_then_commit");
+                assert_eq!(slots["source"], "old.ts");
+                assert_eq!(slots["destination"], "new.ts");
+            }
+            other => panic!("expected accept, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn optional_slot_pattern_matches_with_and_without_it() {
+        let g = grammar(
+            r#"
+version: "1.0"
+rules:
+  - name: refactor
+    description: Refactor code
+    patterns:
+      - "refactor {target}"
+    params:
+      target:
+        type: file_or_symbol
+        required: false
+    mode: genius
+"#,
+        );
+        let (table, _) = compile(&g);
+
+        match parse(&table, &toks("refactor")) {
+            ParseOutcome::Accepted { slots, .. } => assert!(slots.is_empty()),
+            other => panic!("expected accept, got {other:?}"),
+        }
+        match parse(&table, &toks("refactor src/auth.ts")) {
+            ParseOutcome::Accepted { slots, .. } => assert_eq!(slots["target"], "src/auth.ts"),
+            other => panic!("expected accept, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_missing_connector_with_expected_keyword() {
+        let g = grammar(
+            r#"
+version: "1.0"
+rules:
+  - name: file_rename
+    description: Rename a file
+    patterns:
+      - "rename {source} to {destination}"
+    params:
+      source:
+        type: file_or_symbol
+      destination:
+        type: file_or_symbol
+    mode: mechanic
+"#,
+        );
+        let (table, _) = compile(&g);
+
+        match parse(&table, &toks("rename old.ts new.ts")) {
+            ParseOutcome::Rejected { token_position, expected, best_rule } => {
+                // The greedy source Slot absorbs "new.ts" too, so the parse
+                // fails at end of input wanting "to" or more of the source.
+                assert_eq!(token_position, 3);
+                assert_eq!(expected, vec!["'to'".to_string(), "a value".to_string()]);
+                assert_eq!(best_rule, Some(("file_rename".to_string(), 0)));
+            }
+            other => panic!("expected reject, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn keyword_continuation_wins_over_bare_slot_value() {
+        let g = grammar(
+            r#"
+version: "1.0"
+rules:
+  - name: bug_fix
+    description: Fix bugs
+    patterns:
+      - "fix {target}"
+    params:
+      target:
+        type: file_or_symbol
+    mode: mechanic
+  - name: bug_fix_verbose
+    description: Fix bugs, verbose phrasing
+    patterns:
+      - "fix the bug in {target}"
+    params:
+      target:
+        type: file_or_symbol
+        required: false
+    mode: mechanic
+"#,
+        );
+        let (table, _) = compile(&g);
+
+        match parse(&table, &toks("fix the bug in src/auth.ts")) {
+            ParseOutcome::Accepted { rule_name, slots, .. } => {
+                assert_eq!(rule_name, "bug_fix_verbose");
+                assert_eq!(slots["target"], "src/auth.ts");
+            }
+            other => panic!("expected accept, got {other:?}"),
+        }
+    }
+}
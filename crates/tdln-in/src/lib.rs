@@ -20,11 +20,20 @@
 //! }
 //! ```
 
+pub mod combinator;
+pub mod compiled;
+pub mod conformance;
 pub mod grammar;
+pub mod lalr;
 pub mod normalizer;
 pub mod matcher;
 pub mod entities;
+pub mod code_symbols;
+pub mod fuzzy;
 pub mod prover;
+pub mod segmentation;
+
+pub use compiled::{available_grammars, translate_with_compiled};
 
 use logline::{LogLineSpan, LogLineValue};
 use matcher::{MatchResult, IntentMatch, AbstainResult};
@@ -60,23 +69,49 @@ pub enum Verdict {
 /// Result of a translation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranslateResult {
-    /// The verdict
+    /// The verdict for `spans[0]`, kept at the top level for callers that
+    /// only handle a single intent per input.
+    pub verdict: Verdict,
+    /// The translated LogLine span (if Match) for `spans[0]`.
+    pub span: Option<LogLineSpan>,
+    /// Confidence score (0.0 to 1.0) for `spans[0]`.
+    pub confidence: f64,
+    /// Suggested mode (mechanic/genius) for `spans[0]`.
+    pub mode: Option<String>,
+    /// Constraints for the operation for `spans[0]`.
+    pub constraints: Option<ConstraintsJson>,
+    /// Reason for abstaining (if Abstain) for `spans[0]`.
+    pub abstain_reason: Option<String>,
+    /// Clarification message (if Abstain) for `spans[0]`.
+    pub clarification: Option<String>,
+    /// Suggestions for the user (if Abstain) for `spans[0]`.
+    pub suggestions: Option<Vec<String>>,
+    /// A combined TruthPack whose `merkle_root` commits to the ordered
+    /// `merkle_root`s of every span in `spans` (see
+    /// [`prover::combine_merkle_roots`]). For a single-clause input this
+    /// degenerates to that one span's own TruthPack content.
+    pub truth_pack: Option<TruthPackJson>,
+    /// Every clause of the input, matched (or abstained on) independently
+    /// and in order. A non-compound input still produces exactly one
+    /// element here, equal to the top-level fields above.
+    pub spans: Vec<Span>,
+}
+
+/// One resolved sub-command within a (possibly compound) translation, in
+/// the order its clause appeared in the input. See [`segmentation`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Span {
+    /// The clause text this span was matched against, after cross-reference
+    /// resolution (e.g. "add tests for it" -> "add tests for src/auth.ts").
+    pub clause: String,
     pub verdict: Verdict,
-    /// The translated LogLine span (if Match)
     pub span: Option<LogLineSpan>,
-    /// Confidence score (0.0 to 1.0)
     pub confidence: f64,
-    /// Suggested mode (mechanic/genius)
     pub mode: Option<String>,
-    /// Constraints for the operation
     pub constraints: Option<ConstraintsJson>,
-    /// Reason for abstaining (if Abstain)
     pub abstain_reason: Option<String>,
-    /// Clarification message (if Abstain)
     pub clarification: Option<String>,
-    /// Suggestions for the user (if Abstain)
     pub suggestions: Option<Vec<String>>,
-    /// TruthPack for provenance (if Match)
     pub truth_pack: Option<TruthPackJson>,
 }
 
@@ -115,17 +150,86 @@ pub fn translate(request: TranslateRequest) -> Result<TranslateResult, Translate
     let grammar_path = request.grammar_path
         .as_deref()
         .unwrap_or(DEFAULT_GRAMMAR_PATH);
-    
+
     // Load grammar
     let grammar = grammar::CompiledGrammar::load(grammar_path)
         .map_err(TranslateError::GrammarError)?;
-    
+
+    Ok(translate_compound(&request.text, &grammar, grammar_path))
+}
+
+/// Segment `text` into clauses, translate each independently against an
+/// already-compiled grammar, and fold the results into one [`TranslateResult`]:
+/// `spans` carries every clause in order, while the top-level fields mirror
+/// `spans[0]` for callers that only handle a single intent. Shared by
+/// [`translate`] (path-based, re-parses on every call) and
+/// [`translate_with_compiled`] (build-time-embedded, zero parse cost).
+pub(crate) fn translate_compound(text: &str, grammar: &grammar::CompiledGrammar, grammar_path: &str) -> TranslateResult {
+    let mut clauses = segmentation::segment(text);
+    segmentation::resolve_cross_references(&mut clauses);
+
+    let spans: Vec<Span> = clauses
+        .iter()
+        .map(|clause| translate_against(&clause.text, grammar, grammar_path))
+        .collect();
+
+    let truth_pack = combine_truth_packs(text, &spans);
+
+    let first = spans
+        .first()
+        .expect("segmentation::segment always yields at least one clause");
+
+    TranslateResult {
+        verdict: first.verdict,
+        span: first.span.clone(),
+        confidence: first.confidence,
+        mode: first.mode.clone(),
+        constraints: first.constraints.clone(),
+        abstain_reason: first.abstain_reason.clone(),
+        clarification: first.clarification.clone(),
+        suggestions: first.suggestions.clone(),
+        truth_pack,
+        spans,
+    }
+}
+
+/// Combine the per-span TruthPacks into one whose `merkle_root` commits to
+/// the ordered sub-span roots (see [`prover::combine_merkle_roots`]). `None`
+/// if every span abstained and so none produced a TruthPack of its own.
+fn combine_truth_packs(original_text: &str, spans: &[Span]) -> Option<TruthPackJson> {
+    let roots: Vec<String> = spans
+        .iter()
+        .filter_map(|s| s.truth_pack.as_ref().map(|t| t.merkle_root.clone()))
+        .collect();
+    if roots.is_empty() {
+        return None;
+    }
+
+    let matched_rule = spans
+        .iter()
+        .filter_map(|s| s.truth_pack.as_ref().map(|t| t.matched_rule.clone()))
+        .collect::<Vec<_>>()
+        .join(" + ");
+    let confidence = spans.iter().map(|s| s.confidence).sum::<f64>() / spans.len() as f64;
+
+    Some(TruthPackJson {
+        input_hash: prover::hash_string(original_text),
+        matched_rule,
+        confidence,
+        merkle_root: prover::combine_merkle_roots(&roots),
+    })
+}
+
+/// Run the normalize/match/build-span pipeline for a single clause against
+/// an already-compiled grammar.
+fn translate_against(text: &str, grammar: &grammar::CompiledGrammar, grammar_path: &str) -> Span {
     // Normalize input
-    let normalized = normalizer::normalize(&request.text);
-    
+    let normalized = normalizer::normalize(text);
+
     // Check for vague input
-    if normalizer::is_too_vague(&request.text) {
-        return Ok(TranslateResult {
+    if normalizer::is_too_vague(text) {
+        return Span {
+            clause: text.to_string(),
             verdict: Verdict::Abstain,
             span: None,
             confidence: 0.0,
@@ -141,21 +245,22 @@ pub fn translate(request: TranslateRequest) -> Result<TranslateResult, Translate
                 "explain [code]".to_string(),
             ]),
             truth_pack: None,
-        });
+        };
     }
-    
+
     // Match against grammar
-    let match_result = matcher::match_text(&normalized, &grammar);
-    
+    let match_result = matcher::match_text(&normalized, grammar);
+
     match match_result {
         MatchResult::Match(intent) => {
             // Build LogLine span
-            let span = build_logline_span(&intent, &request.text);
-            
+            let span = build_logline_span(&intent, text);
+
             // Build TruthPack
-            let truth_pack = build_truth_pack(&intent, &request.text, grammar_path);
-            
-            Ok(TranslateResult {
+            let truth_pack = build_truth_pack(&intent, text, grammar_path);
+
+            Span {
+                clause: text.to_string(),
                 verdict: Verdict::Match,
                 span: Some(span),
                 confidence: intent.confidence,
@@ -175,10 +280,11 @@ pub fn translate(request: TranslateRequest) -> Result<TranslateResult, Translate
                     confidence: truth_pack.confidence,
                     merkle_root: truth_pack.merkle_root,
                 }),
-            })
+            }
         }
         MatchResult::Abstain(abstain) => {
-            Ok(TranslateResult {
+            Span {
+                clause: text.to_string(),
                 verdict: Verdict::Abstain,
                 span: None,
                 confidence: 0.0,
@@ -188,7 +294,7 @@ pub fn translate(request: TranslateRequest) -> Result<TranslateResult, Translate
                 clarification: Some(abstain.clarification),
                 suggestions: Some(abstain.suggestions),
                 truth_pack: None,
-            })
+            }
         }
     }
 }
@@ -238,6 +344,7 @@ fn build_truth_pack(intent: &IntentMatch, original_text: &str, grammar_path: &st
         &intent.matched_pattern,
         slots,
         intent.confidence,
+        intent.conflicts.clone(),
     )
 }
 
@@ -312,4 +419,73 @@ rules:
             assert_eq!(r.abstain_reason, Some("too_vague".to_string()));
         }
     }
+
+    fn compound_test_grammar() -> grammar::CompiledGrammar {
+        grammar::CompiledGrammar::from_yaml(r#"
+version: "1.0"
+rules:
+  - name: bug_fix
+    description: Fix bugs
+    patterns:
+      - "fix the bug in {target}"
+    params:
+      target:
+        type: file_or_symbol
+    mode: mechanic
+  - name: add_tests
+    description: Add tests
+    patterns:
+      - "add tests for {target}"
+    params:
+      target:
+        type: file_or_symbol
+    mode: mechanic
+"#).unwrap()
+    }
+
+    #[test]
+    fn test_compound_command_yields_one_span_per_clause() {
+        let grammar = compound_test_grammar();
+        let result = translate_compound(
+            "fix the bug in src/auth.ts and add tests for it",
+            &grammar,
+            "grammars/coding-intents.yaml",
+        );
+
+        assert_eq!(result.spans.len(), 2);
+        assert_eq!(result.spans[0].clause, "fix the bug in src/auth.ts");
+        assert_eq!(result.spans[1].clause, "add tests for src/auth.ts");
+        assert_eq!(result.spans[0].verdict, Verdict::Match);
+        assert_eq!(result.spans[1].verdict, Verdict::Match);
+
+        // Top-level fields mirror the first span for single-intent callers.
+        assert_eq!(result.verdict, result.spans[0].verdict);
+        assert_eq!(result.confidence, result.spans[0].confidence);
+    }
+
+    #[test]
+    fn test_compound_command_combined_truth_pack_commits_to_order() {
+        let grammar = compound_test_grammar();
+        let forward = translate_compound(
+            "fix the bug in src/auth.ts and add tests for it",
+            &grammar,
+            "grammars/coding-intents.yaml",
+        );
+        let swapped = translate_compound(
+            "add tests for src/auth.ts and fix the bug in src/auth.ts",
+            &grammar,
+            "grammars/coding-intents.yaml",
+        );
+
+        let forward_root = forward.truth_pack.as_ref().unwrap().merkle_root.clone();
+        let swapped_root = swapped.truth_pack.as_ref().unwrap().merkle_root.clone();
+        assert_ne!(forward_root, swapped_root);
+    }
+
+    #[test]
+    fn test_single_clause_input_still_has_exactly_one_span() {
+        let grammar = compound_test_grammar();
+        let result = translate_compound("fix the bug in src/auth.ts", &grammar, "grammars/coding-intents.yaml");
+        assert_eq!(result.spans.len(), 1);
+    }
 }
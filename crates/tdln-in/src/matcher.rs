@@ -1,9 +1,22 @@
 //! Intent matching for TDLN-IN.
 //!
-//! Matches normalized text against compiled grammar patterns,
-//! extracting slots and computing confidence scores.
+//! Matches normalized text against a compiled grammar by compiling every
+//! rule's patterns into one LALR (see [`crate::lalr`]) table and driving it
+//! over the whitespace-tokenized input, extracting slots and computing
+//! confidence scores from the production that accepted. Since the table
+//! silently resolves any ambiguity between rules to one winner,
+//! [`match_text`] also replays every candidate rule independently via
+//! [`match_all`] and abstains instead of returning a confident match when
+//! another rule scores within striking distance of the winner.
+//!
+//! [`crate::grammar::CompiledGrammar::match_input`] is a third, independent
+//! way to the same "every rule that fired" view as [`match_all`], built on
+//! a [`regex::RegexSet`] single-pass scan instead of replaying the
+//! combinator engine per candidate rule.
 
-use crate::grammar::{CompiledGrammar, CompiledRule, CompiledPattern, Constraints};
+use crate::fuzzy;
+use crate::grammar::{CompiledGrammar, CompiledPattern, CompiledRule, Constraints};
+use crate::lalr::{self, ParseOutcome};
 use std::collections::HashMap;
 
 /// Result of matching text against grammar
@@ -34,6 +47,11 @@ pub struct IntentMatch {
     pub read_only: bool,
     /// The pattern that matched
     pub matched_pattern: String,
+    /// Descriptions of any shift/reduce or reduce/reduce conflicts the
+    /// grammar's table had to resolve to reach this parse, for provenance
+    /// in the resulting [`crate::prover::TruthPack`] -- see
+    /// [`lalr::Conflict`].
+    pub conflicts: Vec<String>,
 }
 
 /// An extracted slot value with type information
@@ -50,79 +68,218 @@ pub struct AbstainResult {
     pub reason: String,
     pub clarification: String,
     pub suggestions: Vec<String>,
+    /// What the parser was looking for at the point it gave up, rendered as
+    /// human-readable strings (e.g. `"'to'"`, `"a value"`).
+    /// Empty unless `reason` is `"incomplete_match"`.
+    pub expected: Vec<String>,
+}
+
+/// The furthest the grammar's table got into a rule before rejecting --
+/// used to report a precise "matched X, then expected Y" clarification
+/// instead of a generic rule list when the input is clearly attempting a
+/// known intent.
+struct PartialFailure {
+    rule_name: String,
+    pattern: String,
+    matched_prefix: String,
+    token_position: usize,
+    expected: Vec<String>,
 }
 
-/// Match text against a compiled grammar
+/// Match text against a compiled grammar.
+///
+/// Compiles `grammar`'s rules into one LALR table (see [`lalr::compile`])
+/// and drives it over the whitespace-tokenized input in a single
+/// shift-reduce pass, so ambiguity between rules that share a prefix (or
+/// both mention each other's keywords) is resolved by the grammar's own
+/// conflict-resolution rule rather than by scoring every rule's best
+/// independent attempt after the fact.
 pub fn match_text(text: &str, grammar: &CompiledGrammar) -> MatchResult {
-    let mut best_match: Option<(IntentMatch, f64)> = None;
-    
-    // Try each rule
-    for rule in &grammar.rules {
-        if let Some((pattern_match, confidence)) = try_match_rule(text, rule) {
-            // Keep the best match (highest confidence)
-            if best_match.is_none() || confidence > best_match.as_ref().unwrap().1 {
-                best_match = Some((pattern_match, confidence));
-            }
+    let (table, _conflicts) = lalr::compile(grammar);
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let outcome = lalr::parse(&table, &tokens);
+
+    if let Some(intent_match) = accept(&outcome, grammar, 0.0) {
+        if let Some(abstain) = ambiguous_rivals(text, grammar, &intent_match) {
+            return MatchResult::Abstain(abstain);
         }
+        return MatchResult::Match(intent_match);
     }
-    
-    match best_match {
-        Some((intent_match, _)) => MatchResult::Match(intent_match),
-        None => MatchResult::Abstain(generate_abstain_result(text, grammar)),
+
+    // No exact pattern matched. Before abstaining, try correcting likely
+    // typos against the grammar's own keyword vocabulary and re-matching;
+    // a near-miss command should still produce a (discounted) Match.
+    if let Some((corrected, distance)) = fuzzy::autocorrect_text(text, grammar) {
+        let corrected_tokens: Vec<&str> = corrected.split_whitespace().collect();
+        let discount = 0.05 * distance as f64;
+        if let Some(intent_match) = accept(&lalr::parse(&table, &corrected_tokens), grammar, discount) {
+            return MatchResult::Match(intent_match);
+        }
+    }
+
+    let failures = match outcome {
+        ParseOutcome::Rejected { token_position, expected, best_rule } => {
+            partial_failure(grammar, &tokens, token_position, expected, best_rule)
+                .into_iter()
+                .collect()
+        }
+        ParseOutcome::Accepted { .. } => Vec::new(),
+    };
+
+    MatchResult::Abstain(generate_abstain_result(text, grammar, failures))
+}
+
+/// Turn an `Accepted` outcome into an [`IntentMatch`], looking the matched
+/// production back up in `grammar` for the rule/pattern metadata the table
+/// itself doesn't carry (description, mode, constraints...). `discount` is
+/// subtracted from the computed confidence (used for a fuzzy-corrected
+/// match, which should never out-rank an exact one).
+fn accept(outcome: &ParseOutcome, grammar: &CompiledGrammar, discount: f64) -> Option<IntentMatch> {
+    let ParseOutcome::Accepted { rule_name, pattern_index, slots: captures, conflicts_resolved } = outcome else {
+        return None;
+    };
+
+    let rule = grammar.rules.iter().find(|r| &r.name == rule_name)?;
+    let pattern = rule.patterns.get(*pattern_index)?;
+
+    Some(build_intent_match(rule, pattern, captures, discount, conflicts_resolved.clone()))
+}
+
+/// Shared scoring logic between [`accept`] (the LALR table's chosen
+/// winner), [`match_all`] (every rule independently replayed through the
+/// combinator engine), and [`crate::grammar::CompiledGrammar::match_input`]
+/// (every pattern scanned at once via `RegexSet`) -- so confidence can't
+/// drift between the three and [`ambiguous_rivals`]'s comparison is
+/// apples-to-apples. `pub(crate)` rather than private so `grammar.rs` can
+/// build an [`IntentMatch`] from its own regex captures without
+/// duplicating this scoring.
+pub(crate) fn build_intent_match(
+    rule: &CompiledRule,
+    pattern: &CompiledPattern,
+    captures: &HashMap<String, String>,
+    discount: f64,
+    conflicts: Vec<String>,
+) -> IntentMatch {
+    let mut slots = HashMap::new();
+    let mut slot_confidence_sum = 0.0;
+    for (slot_name, value) in captures {
+        let value = value.trim().to_string();
+        let slot_confidence = calculate_slot_confidence(&value, slot_name);
+        slot_confidence_sum += slot_confidence;
+        slots.insert(slot_name.clone(), SlotValue {
+            value,
+            slot_type: get_slot_type(slot_name),
+            confidence: slot_confidence,
+        });
+    }
+
+    let pattern_specificity = pattern.specificity as f64 / 50.0; // Normalize to ~1.0
+    let slot_count = captures.len();
+    let avg_slot_confidence = if slot_count > 0 {
+        slot_confidence_sum / slot_count as f64
+    } else {
+        1.0
+    };
+
+    let missing_optional = rule.params
+        .iter()
+        .filter(|(name, spec)| !spec.required && !captures.contains_key(*name))
+        .count();
+
+    let mut confidence = (pattern_specificity.min(1.0) * 0.6 + avg_slot_confidence * 0.4).min(1.0);
+    // A pattern that resolved with some of its optional slots unfilled is a
+    // weaker match than one that filled every slot.
+    confidence *= 0.97f64.powi(missing_optional as i32);
+    confidence = (confidence - discount).max(0.1);
+
+    IntentMatch {
+        rule: rule.name.clone(),
+        description: rule.description.clone(),
+        slots,
+        confidence,
+        mode: rule.mode.clone(),
+        constraints: rule.constraints.clone(),
+        read_only: rule.read_only,
+        matched_pattern: pattern.original.clone(),
+        conflicts,
     }
 }
 
-/// Try to match text against a single rule
-fn try_match_rule(text: &str, rule: &CompiledRule) -> Option<(IntentMatch, f64)> {
-    for pattern in &rule.patterns {
-        if let Some(captures) = pattern.regex.captures(text) {
-            let mut slots = HashMap::new();
-            let mut slot_confidence_sum = 0.0;
-            
-            // Extract slot values
-            for slot_name in &pattern.slot_names {
-                if let Some(m) = captures.name(slot_name) {
-                    let value = m.as_str().trim().to_string();
-                    let slot_confidence = calculate_slot_confidence(&value, slot_name);
-                    
-                    slots.insert(slot_name.clone(), SlotValue {
-                        value,
-                        slot_type: get_slot_type(slot_name),
-                        confidence: slot_confidence,
-                    });
-                    
-                    slot_confidence_sum += slot_confidence;
-                }
+/// Try every rule [`CompiledGrammar::candidate_rules`] says could possibly
+/// match `text`, replaying each one's patterns directly through the
+/// combinator engine in a single pass over the prefiltered candidates --
+/// rather than the shared LALR table, which by construction commits to one
+/// winner and never reports the rules it didn't pick. Used to detect
+/// genuine cross-rule ambiguity (two *different* intents both describing
+/// the same input), as opposed to the in-grammar shift/reduce conflicts
+/// [`lalr::compile`] already resolves on its own.
+pub fn match_all(text: &str, grammar: &CompiledGrammar) -> Vec<IntentMatch> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let mut matches = Vec::new();
+
+    for &rule_idx in &grammar.candidate_rules(&tokens) {
+        let rule = &grammar.rules[rule_idx];
+        for pattern in &rule.patterns {
+            if let crate::combinator::ParseOutcome::Matched { slots, .. } = pattern.pattern.parse(&tokens) {
+                let captures: HashMap<String, String> =
+                    slots.into_iter().map(|(name, capture)| (name, capture.value)).collect();
+                matches.push(build_intent_match(rule, pattern, &captures, 0.0, Vec::new()));
+                break; // this rule already has a hit; its other patterns are alternatives, not additional votes
             }
-            
-            // Calculate overall confidence
-            let pattern_specificity = pattern.specificity as f64 / 50.0; // Normalize to ~1.0
-            let slot_count = pattern.slot_names.len();
-            let avg_slot_confidence = if slot_count > 0 {
-                slot_confidence_sum / slot_count as f64
-            } else {
-                1.0
-            };
-            
-            let confidence = (pattern_specificity.min(1.0) * 0.6 + avg_slot_confidence * 0.4).min(1.0);
-            
-            return Some((
-                IntentMatch {
-                    rule: rule.name.clone(),
-                    description: rule.description.clone(),
-                    slots,
-                    confidence,
-                    mode: rule.mode.clone(),
-                    constraints: rule.constraints.clone(),
-                    read_only: rule.read_only,
-                    matched_pattern: pattern.original.clone(),
-                },
-                confidence,
-            ));
         }
     }
-    
-    None
+
+    matches
+}
+
+/// If [`match_all`] turns up another rule that matches `text` at a
+/// confidence within `0.05` of the LALR table's chosen `winner`, that's a
+/// real ambiguity the table's conflict resolution papered over silently --
+/// surface it as an abstain instead of a confident (and arbitrarily-picked)
+/// [`MatchResult::Match`].
+fn ambiguous_rivals(text: &str, grammar: &CompiledGrammar, winner: &IntentMatch) -> Option<AbstainResult> {
+    let mut rivals: Vec<IntentMatch> = match_all(text, grammar)
+        .into_iter()
+        .filter(|m| m.rule != winner.rule && (m.confidence - winner.confidence).abs() < 0.05)
+        .collect();
+
+    if rivals.is_empty() {
+        return None;
+    }
+
+    rivals.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+    let mut names = vec![winner.rule.clone()];
+    names.extend(rivals.into_iter().map(|m| m.rule));
+
+    Some(AbstainResult {
+        reason: "ambiguous".to_string(),
+        clarification: format!("'{}' could mean different things. Did you want to: {}?", text, names.join(", ")),
+        suggestions: names,
+        expected: Vec::new(),
+    })
+}
+
+/// Build the single [`PartialFailure`] a rejected parse reports, naming the
+/// rule/pattern it had gotten furthest into (if it consumed any tokens at
+/// all).
+fn partial_failure(
+    grammar: &CompiledGrammar,
+    tokens: &[&str],
+    token_position: usize,
+    expected: Vec<String>,
+    best_rule: Option<(String, usize)>,
+) -> Option<PartialFailure> {
+    let (rule_name, pattern_index) = best_rule?;
+    let rule = grammar.rules.iter().find(|r| r.name == rule_name)?;
+    let pattern = rule.patterns.get(pattern_index)?;
+
+    Some(PartialFailure {
+        rule_name,
+        pattern: pattern.original.clone(),
+        matched_prefix: tokens[..token_position.min(tokens.len())].join(" "),
+        token_position,
+        expected,
+    })
 }
 
 /// Calculate confidence for a slot value
@@ -168,9 +325,9 @@ fn get_slot_type(slot_name: &str) -> String {
 }
 
 /// Generate an abstain result when no match is found
-fn generate_abstain_result(text: &str, grammar: &CompiledGrammar) -> AbstainResult {
+fn generate_abstain_result(text: &str, grammar: &CompiledGrammar, failures: Vec<PartialFailure>) -> AbstainResult {
     let word_count = text.split_whitespace().count();
-    
+
     // Check for too vague input
     if word_count <= 1 {
         return AbstainResult {
@@ -185,31 +342,75 @@ fn generate_abstain_result(text: &str, grammar: &CompiledGrammar) -> AbstainResu
                 "explain [code]".to_string(),
                 "refactor [target]".to_string(),
             ],
+            expected: Vec::new(),
         };
     }
-    
+
+    // If some pattern got partway through the input before failing, that's
+    // a stronger signal than a fuzzy keyword match -- the user is clearly
+    // attempting a known intent and just tripped on a missing connector or
+    // slot, so name exactly what was expected and where.
+    if let Some(best) = failures
+        .iter()
+        .filter(|f| f.token_position > 0)
+        .max_by_key(|f| f.token_position)
+    {
+        let expected = best.expected.clone();
+        return AbstainResult {
+            reason: "incomplete_match".to_string(),
+            clarification: format!(
+                "I matched a '{}' intent up to \"{}\" but then expected {}.",
+                best.rule_name,
+                best.matched_prefix,
+                expected.join(" or ")
+            ),
+            suggestions: vec![best.pattern.clone()],
+            expected,
+        };
+    }
+
     // Find closest matching rules for suggestions
     let suggestions: Vec<String> = grammar.rules
         .iter()
         .take(4)
         .map(|r| format!("{}: {}", r.name, r.patterns.first().map(|p| &p.original).unwrap_or(&String::new())))
         .collect();
-    
-    // Check for ambiguous input (could match multiple intents)
-    let partial_matches: Vec<&str> = grammar.rules
+
+    // If a token in the input is a near-miss (typo) of a keyword from a
+    // grammar pattern, lead with that instead of the generic rule list.
+    let fuzzy_matches = fuzzy::find_fuzzy_matches(text, grammar, 4);
+    if let Some(top) = fuzzy_matches.first() {
+        let examples: Vec<String> = fuzzy_matches
+            .iter()
+            .filter_map(|m| grammar.rules.iter().find(|r| r.name == m.rule_name))
+            .map(|r| format!("{}: {}", r.name, r.patterns.first().map(|p| p.original.clone()).unwrap_or_default()))
+            .collect();
+
+        return AbstainResult {
+            reason: "near_miss".to_string(),
+            clarification: format!("I don't recognize '{}'. Did you mean to *{}*?", top.input_token, top.keyword),
+            suggestions: examples,
+            expected: Vec::new(),
+        };
+    }
+
+    // Check for ambiguous input (could match multiple intents). Rather than
+    // re-scanning every rule's patterns for a substring hit, look each
+    // input token up in the grammar's literal-atom index directly.
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let mut matched_rule_indices: Vec<usize> = Vec::new();
+    for token in &tokens {
+        for &idx in grammar.rules_requiring(&token.to_lowercase()) {
+            if !matched_rule_indices.contains(&idx) {
+                matched_rule_indices.push(idx);
+            }
+        }
+    }
+    let partial_matches: Vec<&str> = matched_rule_indices
         .iter()
-        .filter(|r| {
-            r.patterns.iter().any(|p| {
-                // Check if any words from the pattern appear in the text
-                p.original
-                    .split_whitespace()
-                    .filter(|w| !w.starts_with('{'))
-                    .any(|word| text.to_lowercase().contains(&word.to_lowercase()))
-            })
-        })
-        .map(|r| r.name.as_str())
+        .map(|&idx| grammar.rules[idx].name.as_str())
         .collect();
-    
+
     if partial_matches.len() > 1 {
         return AbstainResult {
             reason: "ambiguous".to_string(),
@@ -219,9 +420,10 @@ fn generate_abstain_result(text: &str, grammar: &CompiledGrammar) -> AbstainResu
                 partial_matches.join(", ")
             ),
             suggestions: partial_matches.iter().map(|s| s.to_string()).collect(),
+            expected: Vec::new(),
         };
     }
-    
+
     AbstainResult {
         reason: "no_match".to_string(),
         clarification: format!(
@@ -229,6 +431,7 @@ fn generate_abstain_result(text: &str, grammar: &CompiledGrammar) -> AbstainResu
             text
         ),
         suggestions,
+        expected: Vec::new(),
     }
 }
 
@@ -261,6 +464,27 @@ rules:
         type: string
         required: true
     mode: genius
+  - name: refactor
+    description: Refactor code
+    patterns:
+      - "refactor {target}"
+    params:
+      target:
+        type: file_or_symbol
+        required: false
+    mode: genius
+  - name: file_rename
+    description: Rename a file
+    patterns:
+      - "rename {source} to {destination}"
+    params:
+      source:
+        type: file_or_symbol
+        required: true
+      destination:
+        type: file_or_symbol
+        required: true
+    mode: mechanic
 "#).unwrap()
     }
 
@@ -295,11 +519,122 @@ rules:
     fn test_abstain_vague() {
         let grammar = test_grammar();
         let result = match_text("x", &grammar);
-        
+
         if let MatchResult::Abstain(a) = result {
             assert_eq!(a.reason, "too_vague");
         } else {
             panic!("Expected abstain");
         }
     }
+
+    #[test]
+    fn test_typo_still_matches_with_discounted_confidence() {
+        let grammar = test_grammar();
+        let clean = match_text("refactor src/auth.ts", &grammar);
+        let typo = match_text("refacor src/auth.ts", &grammar);
+
+        match (clean, typo) {
+            (MatchResult::Match(clean), MatchResult::Match(typo)) => {
+                assert_eq!(clean.rule, "refactor");
+                assert_eq!(typo.rule, "refactor");
+                assert!(typo.confidence < clean.confidence);
+            }
+            other => panic!("expected both to match: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_abstain_suggests_near_miss_keyword() {
+        let grammar = test_grammar();
+        // Leading word breaks the anchored pattern match even after
+        // autocorrecting "refacor" -> "refactor", so this should abstain
+        // rather than match -- but the clarification should still name the
+        // near-miss keyword.
+        let result = match_text("somehow refacor this widget", &grammar);
+
+        if let MatchResult::Abstain(a) = result {
+            assert_eq!(a.reason, "near_miss");
+            assert!(a.clarification.contains("refactor"));
+        } else {
+            panic!("Expected abstain with near-miss suggestion");
+        }
+    }
+
+    #[test]
+    fn test_rename_extracts_source_and_destination() {
+        let grammar = test_grammar();
+        let result = match_text("rename old.ts to new.ts", &grammar);
+
+        match result {
+            MatchResult::Match(m) => {
+                assert_eq!(m.rule, "file_rename");
+                assert_eq!(m.slots["source"].value, "old.ts");
+                assert_eq!(m.slots["destination"].value, "new.ts");
+            }
+            other => panic!("expected match, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_abstain_names_expected_connector_on_incomplete_match() {
+        let grammar = test_grammar();
+        // Matches "rename old.ts new.ts" against `file_rename` (the greedy
+        // source slot takes both values), then at end of input the parser
+        // wants the literal "to" or more of the source -- the clarification
+        // should say exactly that instead of falling back to a generic rule
+        // list.
+        let result = match_text("rename old.ts new.ts", &grammar);
+
+        if let MatchResult::Abstain(a) = result {
+            assert_eq!(a.reason, "incomplete_match");
+            assert!(a.clarification.contains("file_rename"));
+            assert!(a.clarification.contains("rename old.ts"));
+            assert_eq!(a.expected, vec!["'to'".to_string(), "a value".to_string()]);
+        } else {
+            panic!("expected abstain with incomplete-match clarification, got {result:?}");
+        }
+    }
+
+    fn duplicate_rule_grammar() -> CompiledGrammar {
+        CompiledGrammar::from_yaml(
+            r#"
+version: "1.0"
+rules:
+  - name: list_files
+    description: List files
+    patterns:
+      - "list {target}"
+    mode: mechanic
+  - name: ls_alias
+    description: Alias for listing files
+    patterns:
+      - "list {target}"
+    mode: mechanic
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn match_all_finds_every_rule_that_fires() {
+        let grammar = duplicate_rule_grammar();
+        let matches = match_all("list src", &grammar);
+        let rule_names: Vec<&str> = matches.iter().map(|m| m.rule.as_str()).collect();
+        assert!(rule_names.contains(&"list_files"));
+        assert!(rule_names.contains(&"ls_alias"));
+    }
+
+    #[test]
+    fn two_equally_specific_rules_abstain_as_ambiguous_instead_of_picking_one() {
+        let grammar = duplicate_rule_grammar();
+        let result = match_text("list src", &grammar);
+
+        if let MatchResult::Abstain(a) = result {
+            assert_eq!(a.reason, "ambiguous");
+            assert!(a.clarification.contains("list_files"));
+            assert!(a.clarification.contains("ls_alias"));
+        } else {
+            panic!("expected an ambiguous abstain, got {result:?}");
+        }
+    }
 }
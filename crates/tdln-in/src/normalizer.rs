@@ -10,6 +10,7 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
+use std::sync::Mutex;
 
 lazy_static! {
     /// Common contractions and their expansions
@@ -49,30 +50,32 @@ lazy_static! {
         m
     };
 
-    /// Common coding-related typos
-    static ref TYPO_CORRECTIONS: HashMap<&'static str, &'static str> = {
-        let mut m = HashMap::new();
-        m.insert("refactor", "refactor");
-        m.insert("refacor", "refactor");
-        m.insert("fucntion", "function");
-        m.insert("funciton", "function");
-        m.insert("funtion", "function");
-        m.insert("implment", "implement");
-        m.insert("impelment", "implement");
-        m.insert("anaylze", "analyze");
-        m.insert("analzye", "analyze");
-        m.insert("expain", "explain");
-        m.insert("expalin", "explain");
-        m.insert("reveiw", "review");
-        m.insert("reivew", "review");
-        m
-    };
+    /// Default coding vocabulary the typo corrector matches tokens against,
+    /// indexed in a [`BkTree`] for nearest-neighbor lookup by edit distance.
+    /// Extend it at runtime with [`extend_vocabulary`].
+    static ref VOCABULARY: Mutex<BkTree> = Mutex::new(BkTree::from_words(&[
+        "fix", "bug", "bugs", "add", "remove", "delete", "rename", "refactor",
+        "implement", "function", "method", "class", "module", "variable",
+        "file", "feature", "test", "tests", "analyze", "explain", "review",
+        "document", "optimize", "debug", "deploy", "build", "compile",
+        "format", "lint", "merge", "branch", "commit", "push", "pull",
+        "update", "create", "design", "security", "performance", "code", "check",
+    ]));
 
     /// Multiple whitespace pattern
     static ref MULTI_SPACE: Regex = Regex::new(r"\s+").unwrap();
-    
+
     /// File path pattern (to preserve)
     static ref FILE_PATH: Regex = Regex::new(r"[a-zA-Z0-9_/.-]+\.[a-zA-Z]+").unwrap();
+
+    /// A path-like token containing at least one glob wildcard (`*` or `?`),
+    /// used by [`extract_globs`].
+    static ref GLOB_TOKEN: Regex = Regex::new(r"[a-zA-Z0-9_/.*?-]*[*?][a-zA-Z0-9_/.*?-]*").unwrap();
+
+    /// Regex-special bytes [`glob_to_regex`] escapes literally -- everything
+    /// except `*`/`?`, which get their own glob translation.
+    static ref GLOB_REGEX_SPECIAL: std::collections::HashSet<char> =
+        ['.', '+', '(', ')', '[', ']', '{', '}', '^', '$', '|', '\\'].into_iter().collect();
 }
 
 /// Normalize text for pattern matching
@@ -90,11 +93,9 @@ pub fn normalize(text: &str) -> String {
         result = result.replace(contraction, expansion);
     }
     
-    // Fix common typos
-    for (typo, correction) in TYPO_CORRECTIONS.iter() {
-        result = result.replace(typo, correction);
-    }
-    
+    // Fix common typos, token by token, instead of a fixed substring table
+    result = correct_typos(&result);
+
     // Normalize whitespace
     result = MULTI_SPACE.replace_all(&result, " ").to_string();
     
@@ -110,6 +111,160 @@ pub fn normalize(text: &str) -> String {
     result
 }
 
+/// Maximum Damerau-Levenshtein distance a token may be from a vocabulary
+/// word and still be corrected to it.
+const MAX_TYPO_DISTANCE: usize = 2;
+
+/// Token-level fuzzy typo correction: each whitespace-separated token not
+/// already in [`VOCABULARY`] and not looking like a file path (contains a
+/// digit or a `/`) is replaced by the closest vocabulary word within
+/// [`MAX_TYPO_DISTANCE`] edits, if one exists. Tokens shorter than 4
+/// characters are left alone even so -- at [`MAX_TYPO_DISTANCE`] = 2, a
+/// 3-letter word is within range of nearly every vocabulary entry, which
+/// makes "closest match" meaningless noise rather than a real correction.
+fn correct_typos(text: &str) -> String {
+    let vocab = VOCABULARY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    text.split_whitespace()
+        .map(|token| {
+            if token.chars().count() < 4
+                || token.contains('/')
+                || token.chars().any(|c| c.is_ascii_digit())
+                || vocab.contains(token)
+            {
+                token.to_string()
+            } else {
+                vocab
+                    .closest(token, MAX_TYPO_DISTANCE)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| token.to_string())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Add words to the default coding vocabulary the typo corrector matches
+/// against, e.g. project-specific jargon -- extends the default list
+/// rather than replacing it.
+pub fn extend_vocabulary(words: &[&str]) {
+    let mut vocab = VOCABULARY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    for word in words {
+        vocab.insert(&word.to_lowercase());
+    }
+}
+
+/// A BK-tree keyed on Damerau-Levenshtein distance: each node's children are
+/// indexed by their distance to that node, so a nearest-neighbor query only
+/// has to descend into children whose indexed distance could possibly fall
+/// within the query's distance threshold (see [`BkTree::candidates_within`]).
+struct BkTree {
+    root: Option<Box<BkNode>>,
+}
+
+struct BkNode {
+    word: String,
+    children: HashMap<usize, Box<BkNode>>,
+}
+
+impl BkTree {
+    fn from_words(words: &[&str]) -> Self {
+        let mut tree = BkTree { root: None };
+        for word in words {
+            tree.insert(word);
+        }
+        tree
+    }
+
+    fn insert(&mut self, word: &str) {
+        match &mut self.root {
+            None => self.root = Some(Box::new(BkNode { word: word.to_string(), children: HashMap::new() })),
+            Some(root) => insert_node(root, word),
+        }
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.candidates_within(word, 0).iter().any(|&(w, d)| d == 0 && w == word)
+    }
+
+    /// The lexicographically smallest vocabulary word at the minimum
+    /// distance within `k` of `query`, kept deterministic when several
+    /// words tie for closest.
+    fn closest(&self, query: &str, k: usize) -> Option<&str> {
+        let candidates = self.candidates_within(query, k);
+        let min_distance = candidates.iter().map(|&(_, d)| d).min()?;
+        candidates.into_iter().filter(|&(_, d)| d == min_distance).map(|(w, _)| w).min()
+    }
+
+    /// Every vocabulary word within edit distance `k` of `query`, as
+    /// `(word, distance)` pairs -- the standard BK-tree range search:
+    /// compute the query's distance to a node, collect it if within `k`,
+    /// then only recurse into children whose indexed distance lies in
+    /// `[d - k, d + k]`.
+    fn candidates_within<'a>(&'a self, query: &str, k: usize) -> Vec<(&'a str, usize)> {
+        let mut results = Vec::new();
+        if let Some(root) = &self.root {
+            search_node(root, query, k, &mut results);
+        }
+        results
+    }
+}
+
+fn insert_node(node: &mut BkNode, word: &str) {
+    let distance = damerau_levenshtein(&node.word, word);
+    if distance == 0 {
+        return;
+    }
+    match node.children.get_mut(&distance) {
+        Some(child) => insert_node(child, word),
+        None => {
+            node.children.insert(distance, Box::new(BkNode { word: word.to_string(), children: HashMap::new() }));
+        }
+    }
+}
+
+fn search_node<'a>(node: &'a BkNode, query: &str, k: usize, results: &mut Vec<(&'a str, usize)>) {
+    let distance = damerau_levenshtein(&node.word, query);
+    if distance <= k {
+        results.push((node.word.as_str(), distance));
+    }
+    let lo = distance.saturating_sub(k);
+    let hi = distance + k;
+    for (&edge, child) in &node.children {
+        if edge >= lo && edge <= hi {
+            search_node(child, query, k, results);
+        }
+    }
+}
+
+/// Damerau-Levenshtein distance (optimal string alignment variant): like
+/// Levenshtein, but an adjacent transposition also counts as a single edit
+/// instead of two substitutions.
+fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (la, lb) = (a.len(), b.len());
+
+    let mut d = vec![vec![0usize; lb + 1]; la + 1];
+    for (i, row) in d.iter_mut().enumerate().take(la + 1) {
+        row[0] = i;
+    }
+    for j in 0..=lb {
+        d[0][j] = j;
+    }
+
+    for i in 1..=la {
+        for j in 1..=lb {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[la][lb]
+}
+
 /// Extract potential file paths from text
 pub fn extract_file_paths(text: &str) -> Vec<String> {
     FILE_PATH
@@ -118,6 +273,50 @@ pub fn extract_file_paths(text: &str) -> Vec<String> {
         .collect()
 }
 
+/// Extract shell-style glob tokens (anything containing `*` or `?`) from
+/// text, alongside the plain-path extraction [`extract_file_paths`] does.
+pub fn extract_globs(text: &str) -> Vec<String> {
+    GLOB_TOKEN
+        .find_iter(text)
+        .map(|m| m.as_str().to_string())
+        .collect()
+}
+
+/// Translate a shell-style glob into an anchored [`Regex`], using the
+/// ordered-replacement technique from Mercurial's filepattern code: `**/`
+/// is replaced before `*` (otherwise `*` alone would consume it first),
+/// then `*`, then `?`, and every other regex-special byte is escaped so it
+/// matches itself literally. The result is anchored at the start and ends
+/// in a `(?:/|$)` suffix so a glob like `src/**/*.ts` matches a full path
+/// under `src/` without also matching an unrelated longer path that merely
+/// starts the same way.
+pub fn glob_to_regex(pattern: &str) -> Result<Regex, String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut out = String::from("^");
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i..].starts_with(&['*', '*', '/']) {
+            out.push_str("(?:.*/)?");
+            i += 3;
+        } else if chars[i] == '*' {
+            out.push_str("[^/]*");
+            i += 1;
+        } else if chars[i] == '?' {
+            out.push_str("[^/]");
+            i += 1;
+        } else if GLOB_REGEX_SPECIAL.contains(&chars[i]) {
+            out.push('\\');
+            out.push(chars[i]);
+            i += 1;
+        } else {
+            out.push(chars[i]);
+            i += 1;
+        }
+    }
+    out.push_str("(?:/|$)");
+    Regex::new(&out).map_err(|e| format!("failed to compile translated glob regex: {e}"))
+}
+
 /// Check if text is too vague (single word or very short)
 pub fn is_too_vague(text: &str) -> bool {
     let normalized = normalize(text);
@@ -151,6 +350,39 @@ mod tests {
         assert_eq!(normalize("it's broken"), "it is broken");
     }
 
+    #[test]
+    fn test_typo_correction_fixes_a_transposed_pair() {
+        assert_eq!(normalize("fix the fucntion"), "fix the function");
+        assert_eq!(normalize("reivew this"), "review this");
+    }
+
+    #[test]
+    fn test_typo_correction_ignores_tokens_that_look_like_file_paths() {
+        // "auht.ts" is close to no vocabulary word but contains no digit or
+        // slash, so it's still a correction candidate... a genuine path is
+        // what must survive untouched.
+        assert_eq!(normalize("check src/auth.ts"), "check src/auth.ts");
+    }
+
+    #[test]
+    fn test_typo_correction_ignores_tokens_with_digits() {
+        assert_eq!(normalize("check file1"), "check file1");
+    }
+
+    #[test]
+    fn test_extend_vocabulary_lets_an_unknown_word_survive_uncorrected() {
+        assert_eq!(normalize("check the loglne"), "check the loglne");
+        extend_vocabulary(&["loglne"]);
+        assert_eq!(normalize("check the loglne"), "check the loglne");
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_counts_a_transposition_as_one_edit() {
+        assert_eq!(damerau_levenshtein("function", "fucntion"), 1);
+        assert_eq!(damerau_levenshtein("function", "function"), 0);
+        assert_eq!(damerau_levenshtein("kitten", "sitting"), 3);
+    }
+
     #[test]
     fn test_file_path_extraction() {
         let paths = extract_file_paths("fix the bug in src/auth.ts and lib/utils.js");
@@ -164,4 +396,38 @@ mod tests {
         assert!(!is_too_vague("fix the bug"));
         assert!(!is_too_vague("help")); // Valid single command
     }
+
+    #[test]
+    fn test_extract_globs() {
+        let globs = extract_globs("refactor all files in src/**/*.ts and lib/*.js");
+        assert_eq!(globs, vec!["src/**/*.ts", "lib/*.js"]);
+    }
+
+    #[test]
+    fn test_extract_globs_ignores_plain_paths() {
+        assert!(extract_globs("fix the bug in src/auth.ts").is_empty());
+    }
+
+    #[test]
+    fn test_glob_to_regex_matches_a_recursive_wildcard() {
+        let re = glob_to_regex("src/**/*.ts").unwrap();
+        assert!(re.is_match("src/a/b/c.ts"));
+        assert!(re.is_match("src/c.ts"));
+        assert!(!re.is_match("lib/c.ts"));
+        assert!(!re.is_match("src/c.js"));
+    }
+
+    #[test]
+    fn test_glob_to_regex_single_star_does_not_cross_a_path_separator() {
+        let re = glob_to_regex("src/*.ts").unwrap();
+        assert!(re.is_match("src/auth.ts"));
+        assert!(!re.is_match("src/nested/auth.ts"));
+    }
+
+    #[test]
+    fn test_glob_to_regex_escapes_literal_regex_metacharacters() {
+        let re = glob_to_regex("file(1).ts").unwrap();
+        assert!(re.is_match("file(1).ts"));
+        assert!(!re.is_match("fileX1X.ts"));
+    }
 }
@@ -8,6 +8,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use tdln_core::provenance::{ContentHash, MerkleRoot, Provenance, ProvenanceCitation};
 
 /// A TruthPack containing provenance information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +29,11 @@ pub struct TruthPack {
     pub timestamp: u64,
     /// Merkle root of all evidence
     pub merkle_root: String,
+    /// Human-readable descriptions of any grammar-table shift/reduce or
+    /// reduce/reduce conflicts this match's parse relied on having been
+    /// resolved at compile time (see [`crate::lalr::Conflict`]). Empty for
+    /// an unambiguous grammar.
+    pub conflicts: Vec<String>,
 }
 
 /// Evidence for a single slot extraction
@@ -52,6 +58,7 @@ impl TruthPack {
         matched_pattern: &str,
         slots: HashMap<String, (String, usize, usize, f64)>,
         confidence: f64,
+        conflicts: Vec<String>,
     ) -> Self {
         let input_hash = hash_string(input);
         let grammar_hash = hash_string(grammar_path);
@@ -85,6 +92,7 @@ impl TruthPack {
             confidence,
             timestamp,
             merkle_root,
+            conflicts,
         }
     }
     
@@ -97,7 +105,36 @@ impl TruthPack {
         );
         computed_root == self.merkle_root
     }
-    
+
+    /// Build an [`InclusionProof`] that `slot_name` was part of this
+    /// TruthPack's evidence, without revealing any other slot's value.
+    /// `None` if no slot by that name was extracted.
+    pub fn prove_slot(&self, slot_name: &str) -> Option<InclusionProof> {
+        let leaves = leaves(&self.input_hash, &self.grammar_hash, &self.slot_evidence);
+        let target_key = format!("slot:{slot_name}");
+        let mut index = leaves.iter().position(|leaf| leaf.key == target_key)?;
+
+        let leaf_hash = leaves[index].hash;
+        let levels = merkle_levels(leaves.into_iter().map(|leaf| leaf.hash).collect());
+
+        let mut siblings = Vec::new();
+        for level in &levels[..levels.len() - 1] {
+            let is_left_child = index % 2 == 0;
+            let sibling_index = if is_left_child {
+                if index + 1 < level.len() { index + 1 } else { index }
+            } else {
+                index - 1
+            };
+            siblings.push((is_left_child, encode_hex(&level[sibling_index])));
+            index /= 2;
+        }
+
+        Some(InclusionProof {
+            leaf_hash: encode_hex(&leaf_hash),
+            siblings,
+        })
+    }
+
     /// Get a summary suitable for logging
     pub fn summary(&self) -> String {
         format!(
@@ -110,14 +147,110 @@ impl TruthPack {
     }
 }
 
-/// Simple hash function (in production, use blake3)
-fn hash_string(s: &str) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    
-    let mut hasher = DefaultHasher::new();
-    s.hash(&mut hasher);
-    format!("hash:{:016x}", hasher.finish())
+impl Provenance for TruthPack {
+    fn merkle_root(&self) -> MerkleRoot {
+        MerkleRoot::new(
+            ContentHash::parse(&self.merkle_root)
+                .expect("TruthPack::merkle_root is always formatted by compute_merkle_root"),
+        )
+    }
+
+    fn verify(&self) -> bool {
+        TruthPack::verify(self)
+    }
+
+    /// Each extracted slot reported as a citation anchored to the rule that
+    /// matched it, so a grammar-translation pack can be audited the same way
+    /// as a jurisdiction pack's quoted sources.
+    fn citations(&self) -> Vec<ProvenanceCitation> {
+        let mut names: Vec<&String> = self.slot_evidence.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| ProvenanceCitation {
+                source_id: format!("slot:{name}"),
+                location: self.matched_rule.clone(),
+                quote: self.slot_evidence[name].value.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Hash a string with blake3, matching the other `TruthPack` type in
+/// `tdln_truthpack` so roots from either crate are actually cryptographic
+/// commitments rather than a `DefaultHasher` fingerprint.
+pub(crate) fn hash_string(s: &str) -> String {
+    format!("blake3:{}", blake3::hash(s.as_bytes()).to_hex())
+}
+
+/// One named leaf of a [`TruthPack`]'s Merkle tree, before hashing.
+/// `key` is only used to make leaf order deterministic (`HashMap` iteration
+/// order is not) -- it plays no role in the committed hash itself.
+struct Leaf {
+    key: String,
+    hash: [u8; 32],
+}
+
+/// Produce this `TruthPack`'s leaves -- `input_hash`, `grammar_hash`, and
+/// one `blake3("{name}:{value}:{start}:{end}")` per slot -- sorted by key
+/// so the tree (and any proof built from it) is stable across runs
+/// regardless of `slot_evidence`'s `HashMap` iteration order.
+fn leaves(input_hash: &str, grammar_hash: &str, slot_evidence: &HashMap<String, SlotEvidence>) -> Vec<Leaf> {
+    let mut leaves = vec![
+        Leaf {
+            key: "input_hash".to_string(),
+            hash: *blake3::hash(input_hash.as_bytes()).as_bytes(),
+        },
+        Leaf {
+            key: "grammar_hash".to_string(),
+            hash: *blake3::hash(grammar_hash.as_bytes()).as_bytes(),
+        },
+    ];
+
+    for (name, evidence) in slot_evidence {
+        let preimage = format!("{}:{}:{}:{}", name, evidence.value, evidence.start, evidence.end);
+        leaves.push(Leaf {
+            key: format!("slot:{name}"),
+            hash: *blake3::hash(preimage.as_bytes()).as_bytes(),
+        });
+    }
+
+    leaves.sort_by(|a, b| a.key.cmp(&b.key));
+    leaves
+}
+
+/// `blake3(left || right)`, the pairwise-hashing step shared by building a
+/// root and folding an [`InclusionProof`].
+fn combine(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(left);
+    buf[32..].copy_from_slice(right);
+    *blake3::hash(&buf).as_bytes()
+}
+
+/// Every level of the Merkle tree built from `leaf_hashes`, leaves first
+/// and the single root last. An odd-sized level promotes its last node by
+/// duplicating it as its own sibling, the same convention Bitcoin's Merkle
+/// trees use.
+fn merkle_levels(leaf_hashes: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![if leaf_hashes.is_empty() {
+        vec![*blake3::hash(b"").as_bytes()]
+    } else {
+        leaf_hashes
+    }];
+
+    while levels.last().unwrap().len() > 1 {
+        let level = levels.last().unwrap();
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        for pair in level.chunks(2) {
+            let left = pair[0];
+            let right = pair.get(1).copied().unwrap_or(left);
+            next.push(combine(&left, &right));
+        }
+        levels.push(next);
+    }
+
+    levels
 }
 
 /// Compute a Merkle root from evidence
@@ -126,19 +259,67 @@ fn compute_merkle_root(
     grammar_hash: &str,
     slot_evidence: &HashMap<String, SlotEvidence>,
 ) -> String {
-    let mut leaves: Vec<String> = vec![
-        input_hash.to_string(),
-        grammar_hash.to_string(),
-    ];
-    
-    // Add slot evidence hashes
-    for (name, evidence) in slot_evidence {
-        leaves.push(hash_string(&format!("{}:{}", name, evidence.value)));
+    let leaf_hashes = leaves(input_hash, grammar_hash, slot_evidence).into_iter().map(|l| l.hash).collect();
+    let levels = merkle_levels(leaf_hashes);
+    format!("blake3:{}", encode_hex(levels.last().unwrap()[0].as_slice()))
+}
+
+/// A proof that a single named leaf is included in a [`TruthPack`]'s Merkle
+/// tree without revealing any other leaf's value: the leaf's own hash plus
+/// the ordered sibling hashes needed to fold back up to the root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InclusionProof {
+    /// Hex-encoded hash of the proven leaf.
+    pub leaf_hash: String,
+    /// One entry per tree level from the leaf up to the root: the sibling's
+    /// hex-encoded hash, and whether the proven node is that level's left
+    /// child (so folding combines `node || sibling`) or right child
+    /// (`sibling || node`).
+    pub siblings: Vec<(bool, String)>,
+}
+
+/// Fold `proof` up to a root hash, returning whether it matches `root`
+/// (e.g. [`TruthPack::merkle_root`]).
+pub fn verify_slot(proof: &InclusionProof, root: &str) -> bool {
+    let Some(mut current) = decode_hex_32(&proof.leaf_hash) else {
+        return false;
+    };
+
+    for (is_left_child, sibling_hex) in &proof.siblings {
+        let Some(sibling) = decode_hex_32(sibling_hex) else {
+            return false;
+        };
+        current = if *is_left_child {
+            combine(&current, &sibling)
+        } else {
+            combine(&sibling, &current)
+        };
     }
-    
-    // Build Merkle tree (simplified - just hash all leaves together)
-    let combined = leaves.join("|");
-    hash_string(&combined)
+
+    format!("blake3:{}", encode_hex(&current)) == root
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex_32(s: &str) -> Option<[u8; 32]> {
+    if s.len() != 64 {
+        return None;
+    }
+    let bytes: Option<Vec<u8>> = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect();
+    bytes?.try_into().ok()
+}
+
+/// Combine the merkle roots of an ordered list of sub-translations (e.g. one
+/// per clause of a compound command) into a single root that commits to
+/// both their content and their order -- swapping two clauses changes the
+/// root even if the set of clauses is identical.
+pub(crate) fn combine_merkle_roots(ordered_roots: &[String]) -> String {
+    hash_string(&ordered_roots.join("|"))
 }
 
 /// Generate a proof string for a translation
@@ -167,6 +348,7 @@ mod tests {
             "fix the {target} bug",
             slots,
             0.85,
+            Vec::new(),
         );
         
         assert_eq!(pack.matched_rule, "bug_fix");
@@ -182,8 +364,74 @@ mod tests {
             "{test}",
             HashMap::new(),
             1.0,
+            Vec::new(),
         );
         
         assert!(pack.verify());
     }
+
+    #[test]
+    fn test_combine_merkle_roots_is_order_sensitive() {
+        let a = hash_string("a");
+        let b = hash_string("b");
+        assert_ne!(combine_merkle_roots(&[a.clone(), b.clone()]), combine_merkle_roots(&[b, a]));
+    }
+
+    fn sample_pack() -> TruthPack {
+        let mut slots = HashMap::new();
+        slots.insert("target".to_string(), ("src/auth.ts".to_string(), 8, 19, 0.9));
+        slots.insert("action".to_string(), ("fix".to_string(), 0, 3, 0.95));
+
+        TruthPack::new(
+            "fix the src/auth.ts bug",
+            "grammars/coding-intents.yaml",
+            "bug_fix",
+            "fix the {target} bug",
+            slots,
+            0.85,
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn test_prove_slot_verifies_against_the_root() {
+        let pack = sample_pack();
+        let proof = pack.prove_slot("target").unwrap();
+        assert!(verify_slot(&proof, &pack.merkle_root));
+    }
+
+    #[test]
+    fn test_prove_slot_returns_none_for_an_unknown_slot() {
+        let pack = sample_pack();
+        assert!(pack.prove_slot("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejects_a_tampered_leaf() {
+        let pack = sample_pack();
+        let mut proof = pack.prove_slot("action").unwrap();
+        proof.leaf_hash = hash_string("tampered")[7..].to_string();
+        assert!(!verify_slot(&proof, &pack.merkle_root));
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejects_the_wrong_root() {
+        let pack = sample_pack();
+        let proof = pack.prove_slot("target").unwrap();
+        assert!(!verify_slot(&proof, "blake3:not-the-real-root"));
+    }
+
+    #[test]
+    fn test_provenance_merkle_root_matches_the_stored_field() {
+        let pack = sample_pack();
+        assert_eq!(Provenance::merkle_root(&pack).to_string(), pack.merkle_root);
+    }
+
+    #[test]
+    fn test_provenance_citations_cover_every_extracted_slot() {
+        let pack = sample_pack();
+        let citations = Provenance::citations(&pack);
+        assert_eq!(citations.len(), pack.slot_evidence.len());
+        assert!(citations.iter().any(|c| c.source_id == "slot:target" && c.quote == "src/auth.ts"));
+    }
 }
@@ -0,0 +1,117 @@
+//! Clause segmentation for compound commands.
+//!
+//! `translate` used to assume one intent per input, so "fix the bug in
+//! src/auth.ts and add tests for it" either matched the first clause as one
+//! giant malformed pattern or abstained on the whole thing. This module
+//! splits the input on coordinating conjunctions into an ordered list of
+//! [`Clause`]s so the matcher can be run on each one independently, and
+//! resolves pronoun references ("it", "that", "this") in later clauses
+//! against entities mentioned earlier.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::entities;
+
+lazy_static! {
+    /// Conjunctions/punctuation that separate chained actions. Longer
+    /// alternatives are listed first since the `regex` crate prefers
+    /// earlier alternatives at the same starting position ("and then"
+    /// must win over "then" and "and" both matching inside it).
+    static ref CLAUSE_BOUNDARY: Regex = Regex::new(
+        r"(?i)\s*(?:;|\.(?:\s|$)|\b(?:and then|and also|then|and)\b)\s*"
+    ).unwrap();
+
+    /// Pronouns that can corefer to an entity from an earlier clause.
+    static ref PRONOUN: Regex = Regex::new(r"(?i)\b(?:it|that|this)\b").unwrap();
+}
+
+/// One clause of a (possibly compound) command, in the order it appeared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clause {
+    pub text: String,
+}
+
+/// Split `text` into clauses on coordinating conjunctions and clause-ending
+/// punctuation. A non-compound input still yields exactly one `Clause`
+/// equal to the (trimmed) whole input, so callers never need a separate
+/// "wasn't compound" path.
+pub fn segment(text: &str) -> Vec<Clause> {
+    let trimmed = text.trim();
+    let clauses: Vec<Clause> = CLAUSE_BOUNDARY
+        .split(trimmed)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| Clause { text: s.to_string() })
+        .collect();
+
+    if clauses.is_empty() {
+        vec![Clause { text: trimmed.to_string() }]
+    } else {
+        clauses
+    }
+}
+
+/// Resolve pronoun cross-references in place: whenever a clause mentions
+/// "it"/"that"/"this" and an earlier clause named a file, substitute that
+/// file path before matching. Tracks the most recently mentioned file across
+/// *all* clauses seen so far (not just the immediately preceding one), so
+/// "fix src/auth.ts, add a test, and document it" still resolves "it" back
+/// to `src/auth.ts`.
+pub fn resolve_cross_references(clauses: &mut [Clause]) {
+    let mut last_file: Option<String> = None;
+
+    for clause in clauses.iter_mut() {
+        if let Some(file) = &last_file {
+            if PRONOUN.is_match(&clause.text) {
+                clause.text = PRONOUN.replace_all(&clause.text, file.as_str()).to_string();
+            }
+        }
+
+        if let Some(path) = entities::extract_file_paths(&clause.text).into_iter().next() {
+            last_file = Some(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_and() {
+        let clauses = segment("fix the bug in src/auth.ts and add tests for it");
+        assert_eq!(clauses.len(), 2);
+        assert_eq!(clauses[0].text, "fix the bug in src/auth.ts");
+        assert_eq!(clauses[1].text, "add tests for it");
+    }
+
+    #[test]
+    fn single_clause_input_is_one_clause() {
+        let clauses = segment("fix the bug in src/auth.ts");
+        assert_eq!(clauses, vec![Clause { text: "fix the bug in src/auth.ts".to_string() }]);
+    }
+
+    #[test]
+    fn splits_on_semicolon_and_then() {
+        let clauses = segment("fix src/auth.ts; then refactor lib/utils.js and then add tests");
+        assert_eq!(clauses.len(), 3);
+        assert_eq!(clauses[0].text, "fix src/auth.ts");
+        assert_eq!(clauses[1].text, "refactor lib/utils.js");
+        assert_eq!(clauses[2].text, "add tests");
+    }
+
+    #[test]
+    fn resolves_it_to_earlier_file() {
+        let mut clauses = segment("fix the bug in src/auth.ts and add tests for it");
+        resolve_cross_references(&mut clauses);
+        assert_eq!(clauses[1].text, "add tests for src/auth.ts");
+    }
+
+    #[test]
+    fn leaves_clause_without_pronoun_unchanged() {
+        let mut clauses = segment("fix src/auth.ts and refactor lib/utils.js");
+        resolve_cross_references(&mut clauses);
+        assert_eq!(clauses[1].text, "refactor lib/utils.js");
+    }
+}
@@ -100,22 +100,186 @@ impl CitationSet {
         }
     }
 
-    /// Generate a Merkle root of all citations
+    /// Generate a cryptographic Merkle root over all citations.
+    ///
+    /// Leaves are hashed in citation order as `blake3(source_path ‖ canonical-json(value))`;
+    /// internal nodes are `blake3(left ‖ right)`, duplicating the last node on odd levels.
     pub fn merkle_root(&self) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        
-        let mut hasher = DefaultHasher::new();
-        
-        for citation in &self.citations {
-            citation.source_path.hash(&mut hasher);
-            citation.value.to_string().hash(&mut hasher);
+        let leaves: Vec<[u8; 32]> = self.citations.iter().map(leaf_hash).collect();
+        format!("merkle:{}", encode_hex(&merkle_root_of(&leaves)))
+    }
+
+    /// Build an inclusion proof for the citation with the given ID.
+    ///
+    /// The proof contains the ordered sibling hashes from leaf to root, each tagged
+    /// with whether the sibling sits on the left or right of the path.
+    pub fn inclusion_proof(&self, id: &str) -> Option<MerkleProof> {
+        let index: usize = id.strip_prefix("cite:")?.parse().ok()?;
+        if index >= self.citations.len() {
+            return None;
         }
-        
-        format!("merkle:{:016x}", hasher.finish())
+
+        let leaves: Vec<[u8; 32]> = self.citations.iter().map(leaf_hash).collect();
+        let siblings = collect_siblings(&leaves, index);
+
+        Some(MerkleProof {
+            leaf_index: index,
+            siblings,
+        })
+    }
+}
+
+/// An inclusion proof that a single citation belongs to a `CitationSet`'s Merkle root,
+/// without revealing any of the other citations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Index of the proven leaf among the citations used to build the tree.
+    pub leaf_index: usize,
+    /// Sibling hashes from leaf to root, each paired with its side relative to the path.
+    pub siblings: Vec<MerkleSibling>,
+}
+
+/// A single sibling hash in a Merkle inclusion proof.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleSibling {
+    /// Hex-encoded sibling hash.
+    pub hash: String,
+    /// `true` if the sibling is the left node, `false` if it is the right node.
+    pub is_left: bool,
+}
+
+/// Verify that `leaf_path`/`leaf_value` is included under `root`, given an inclusion proof.
+///
+/// Recomputes the leaf hash and folds in each sibling up to the root, comparing the
+/// result against the `merkle:<hex>` root produced by [`CitationSet::merkle_root`].
+pub fn verify_proof(leaf_path: &str, leaf_value: &Value, proof: &MerkleProof, root: &str) -> bool {
+    let Some(expected_hex) = root.strip_prefix("merkle:") else {
+        return false;
+    };
+
+    let mut current = leaf_hash_raw(leaf_path, leaf_value);
+    for sibling in &proof.siblings {
+        let Some(sibling_hash) = decode_hex(&sibling.hash) else {
+            return false;
+        };
+
+        current = if sibling.is_left {
+            hash_pair(&sibling_hash, &current)
+        } else {
+            hash_pair(&current, &sibling_hash)
+        };
+    }
+
+    encode_hex(&current) == expected_hex
+}
+
+/// Encode bytes as lowercase hex, matching the `blake3:<hex>` convention used elsewhere.
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a 32-byte hex string, rejecting anything of the wrong length or alphabet.
+fn decode_hex(s: &str) -> Option<[u8; 32]> {
+    if s.len() != 64 {
+        return None;
+    }
+    let mut out = [0u8; 32];
+    for (i, chunk) in s.as_bytes().chunks(2).enumerate() {
+        let byte_str = std::str::from_utf8(chunk).ok()?;
+        out[i] = u8::from_str_radix(byte_str, 16).ok()?;
+    }
+    Some(out)
+}
+
+fn leaf_hash(citation: &Citation) -> [u8; 32] {
+    leaf_hash_raw(&citation.source_path, &citation.value)
+}
+
+fn leaf_hash_raw(source_path: &str, value: &Value) -> [u8; 32] {
+    let canonical = canonicalize_json(value);
+    let mut input = Vec::with_capacity(source_path.len() + canonical.len());
+    input.extend_from_slice(source_path.as_bytes());
+    input.extend_from_slice(canonical.as_bytes());
+    *blake3::hash(&input).as_bytes()
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut input = Vec::with_capacity(64);
+    input.extend_from_slice(left);
+    input.extend_from_slice(right);
+    *blake3::hash(&input).as_bytes()
+}
+
+/// Render `value` as JSON with keys sorted at every level and no insignificant
+/// whitespace, so the same logical value hashes identically across machines.
+fn canonicalize_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let entries: Vec<String> = keys
+                .into_iter()
+                .map(|k| format!("{}:{}", serde_json::to_string(k).unwrap(), canonicalize_json(&map[k])))
+                .collect();
+            format!("{{{}}}", entries.join(","))
+        }
+        Value::Array(items) => {
+            let entries: Vec<String> = items.iter().map(canonicalize_json).collect();
+            format!("[{}]", entries.join(","))
+        }
+        _ => value.to_string(),
     }
 }
 
+fn merkle_root_of(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return *blake3::hash(b"").as_bytes();
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let mut i = 0;
+        while i < level.len() {
+            let left = level[i];
+            let right = if i + 1 < level.len() { level[i + 1] } else { level[i] };
+            next.push(hash_pair(&left, &right));
+            i += 2;
+        }
+        level = next;
+    }
+    level[0]
+}
+
+/// Walk the tree for `target_index`, collecting sibling hashes from leaf to root.
+fn collect_siblings(leaves: &[[u8; 32]], target_index: usize) -> Vec<MerkleSibling> {
+    let mut siblings = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut index = target_index;
+
+    while level.len() > 1 {
+        let pair_index = if index.is_multiple_of(2) { index + 1 } else { index - 1 };
+        let sibling_value = if pair_index < level.len() { level[pair_index] } else { level[index] };
+        siblings.push(MerkleSibling {
+            hash: encode_hex(&sibling_value),
+            is_left: index % 2 == 1,
+        });
+
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let mut i = 0;
+        while i < level.len() {
+            let left = level[i];
+            let right = if i + 1 < level.len() { level[i + 1] } else { level[i] };
+            next.push(hash_pair(&left, &right));
+            i += 2;
+        }
+        level = next;
+        index /= 2;
+    }
+
+    siblings
+}
+
 /// Extract citations from data during rendering
 pub fn extract_citations(data: &Value, prefix: &str) -> CitationSet {
     let mut citations = CitationSet::new();
@@ -226,8 +390,46 @@ mod tests {
         let mut citations = CitationSet::new();
         citations.add("a", json!("1"));
         citations.add("b", json!("2"));
-        
+
         let root = citations.merkle_root();
         assert!(root.starts_with("merkle:"));
     }
+
+    #[test]
+    fn test_merkle_root_reproducible() {
+        let mut citations = CitationSet::new();
+        citations.add("a", json!({"z": 1, "a": 2}));
+        citations.add("b", json!("2"));
+
+        let mut same_values_different_order = CitationSet::new();
+        same_values_different_order.add("a", json!({"a": 2, "z": 1}));
+        same_values_different_order.add("b", json!("2"));
+
+        assert_eq!(citations.merkle_root(), same_values_different_order.merkle_root());
+    }
+
+    #[test]
+    fn test_inclusion_proof_verifies() {
+        let mut citations = CitationSet::new();
+        citations.add("a", json!("1"));
+        citations.add("b", json!("2"));
+        citations.add("c", json!("3"));
+
+        let root = citations.merkle_root();
+        for citation in citations.all() {
+            let proof = citations.inclusion_proof(&citation.id).unwrap();
+            assert!(verify_proof(&citation.source_path, &citation.value, &proof, &root));
+        }
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejects_wrong_value() {
+        let mut citations = CitationSet::new();
+        citations.add("a", json!("1"));
+        citations.add("b", json!("2"));
+
+        let root = citations.merkle_root();
+        let proof = citations.inclusion_proof("cite:0").unwrap();
+        assert!(!verify_proof("a", &json!("not-1"), &proof, &root));
+    }
 }
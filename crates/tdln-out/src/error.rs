@@ -0,0 +1,75 @@
+//! Crate-wide error types for TDLN-OUT.
+//!
+//! Render failures used to collapse into a single formatted `String`, so a
+//! caller couldn't tell a template parse error from a missing field from a
+//! helper crash without string-matching the message. [`TemplateRenderError`]
+//! keeps that distinction (and the underlying Handlebars cause) through to
+//! [`TdlnError`], the error type the crate's public render entry points
+//! return.
+
+use thiserror::Error;
+
+/// Why a [`crate::renderer::TemplateRenderer`] render call failed.
+#[derive(Error, Debug)]
+pub enum TemplateRenderError {
+    /// The template (or a partial/inline it references) failed to parse.
+    #[error("template parse error: {0}")]
+    Parse(#[from] handlebars::TemplateError),
+
+    /// A referenced field was absent and the renderer was built with
+    /// `strict: true`, so Handlebars raised instead of rendering empty --
+    /// see [`crate::renderer::TemplateRenderer::new`].
+    #[error("undefined reference: {0}")]
+    MissingField(String),
+
+    /// A helper, decorator, or Rhai script call failed during render.
+    #[error("helper failed: {0}")]
+    Helper(String),
+
+    /// Any other render failure Handlebars reports that doesn't fit the
+    /// categories above.
+    #[error("render failed: {0}")]
+    Render(String),
+
+    /// A template includes itself, directly or transitively, via
+    /// `{{> name}}` -- detected and rejected up front so a misconfigured
+    /// `TemplatesFile` fails the render instead of recursing until the
+    /// stack overflows. The string is the cycle, e.g. `"a -> b -> a"`.
+    #[error("cyclic include: {0}")]
+    CyclicInclude(String),
+
+    /// A template called a helper that isn't registered with the
+    /// [`crate::renderer::TemplateRenderer`] -- neither a built-in, a
+    /// [`crate::templates::TemplatesFile::scripts`] entry, nor one added via
+    /// [`crate::renderer::TemplateRenderer::register_helper`]. Previously
+    /// this fell into the generic [`TemplateRenderError::Render`] bucket,
+    /// indistinguishable from any other render failure.
+    #[error("unknown helper: {0}")]
+    UnknownHelper(String),
+}
+
+impl From<handlebars::RenderError> for TemplateRenderError {
+    fn from(e: handlebars::RenderError) -> Self {
+        let desc = e.desc.clone();
+        if desc.contains("not found in strict mode") || desc.contains("missing in strict mode") {
+            TemplateRenderError::MissingField(desc)
+        } else if desc.starts_with("script helper") || desc.starts_with("script decorator") {
+            TemplateRenderError::Helper(desc)
+        } else if desc.contains("Helper not defined") {
+            TemplateRenderError::UnknownHelper(desc)
+        } else {
+            TemplateRenderError::Render(e.to_string())
+        }
+    }
+}
+
+/// Crate-wide error type returned by TDLN-OUT's public render entry points.
+#[derive(Error, Debug)]
+pub enum TdlnError {
+    #[error("Template load failed: {0}")]
+    Template(String),
+    #[error("Render failed: {0}")]
+    Render(#[from] TemplateRenderError),
+    #[error("Validation failed: {0}")]
+    Validation(String),
+}
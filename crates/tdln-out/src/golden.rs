@@ -0,0 +1,288 @@
+//! Golden-test runner for TDLN-OUT templates.
+//!
+//! A [`crate::templates::Template`] with both an `example` input and an
+//! expected `output` is already a golden fixture sitting unused in the
+//! templates file -- [`verify_templates`] renders every such template
+//! against its own `example` and compares the result to `output`, so a
+//! regression in `response-templates.yaml` shows up as a failing test
+//! instead of drifting unnoticed. [`GoldenReport::to_junit_xml`] emits the
+//! result as JUnit XML so it drops straight into CI dashboards.
+
+use crate::error::TdlnError;
+use crate::renderer::TemplateRenderer;
+use crate::templates::TemplatesFile;
+
+/// Outcome of verifying a single template against its `example`/`output`.
+#[derive(Debug, Clone)]
+pub enum CaseOutcome {
+    /// Rendered output matched the expected `output` exactly.
+    Passed,
+    /// Rendered output differed from `output` -- carries a unified diff.
+    Failed(String),
+    /// Rendering the template itself errored, rather than producing the
+    /// wrong output. Reported as a test failure, not an abort of the run.
+    Errored(String),
+    /// The template has no `example`, no `output`, or neither -- nothing to
+    /// compare it against.
+    Ignored,
+}
+
+/// Result of verifying one template -- one `<testcase>` in the JUnit report.
+#[derive(Debug, Clone)]
+pub struct GoldenCase {
+    pub name: String,
+    pub outcome: CaseOutcome,
+}
+
+impl GoldenCase {
+    /// Whether this case counts as a JUnit `<failure>`.
+    pub fn is_failure(&self) -> bool {
+        matches!(self.outcome, CaseOutcome::Failed(_) | CaseOutcome::Errored(_))
+    }
+}
+
+/// Result of verifying every template in one [`TemplatesFile`] -- one
+/// `<testsuite>` in the JUnit report.
+#[derive(Debug, Clone)]
+pub struct GoldenReport {
+    pub suite_name: String,
+    pub cases: Vec<GoldenCase>,
+}
+
+impl GoldenReport {
+    /// Number of cases that failed or errored.
+    pub fn failures(&self) -> usize {
+        self.cases.iter().filter(|c| c.is_failure()).count()
+    }
+
+    /// Number of cases with no `example`/`output` to compare.
+    pub fn ignored(&self) -> usize {
+        self.cases.iter().filter(|c| matches!(c.outcome, CaseOutcome::Ignored)).count()
+    }
+
+    /// Whether every non-ignored case passed.
+    pub fn all_passed(&self) -> bool {
+        self.failures() == 0
+    }
+
+    /// Render this report as a JUnit XML document: a `<testsuites>` root
+    /// wrapping one `<testsuite>` (this report), one `<testcase>` per
+    /// template. A failing case's `<failure>` body is the unified diff of
+    /// expected vs actual output (or the render error, for
+    /// [`CaseOutcome::Errored`]); an ignored case is reported `<skipped/>`.
+    pub fn to_junit_xml(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<testsuites>\n");
+        out.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">\n",
+            xml_escape(&self.suite_name),
+            self.cases.len(),
+            self.failures(),
+            self.ignored(),
+        ));
+
+        for case in &self.cases {
+            match &case.outcome {
+                CaseOutcome::Passed => {
+                    out.push_str(&format!("    <testcase name=\"{}\"/>\n", xml_escape(&case.name)));
+                }
+                CaseOutcome::Ignored => {
+                    out.push_str(&format!(
+                        "    <testcase name=\"{}\"><skipped/></testcase>\n",
+                        xml_escape(&case.name)
+                    ));
+                }
+                CaseOutcome::Failed(diff) => {
+                    out.push_str(&format!(
+                        "    <testcase name=\"{}\">\n      <failure message=\"output mismatch\">{}</failure>\n    </testcase>\n",
+                        xml_escape(&case.name),
+                        xml_escape(diff),
+                    ));
+                }
+                CaseOutcome::Errored(message) => {
+                    out.push_str(&format!(
+                        "    <testcase name=\"{}\">\n      <failure message=\"render error\">{}</failure>\n    </testcase>\n",
+                        xml_escape(&case.name),
+                        xml_escape(message),
+                    ));
+                }
+            }
+        }
+
+        out.push_str("  </testsuite>\n");
+        out.push_str("</testsuites>\n");
+        out
+    }
+}
+
+/// Load the `TemplatesFile` at `path` and verify every template against its
+/// own `example`/`output` -- see [`verify`] for the per-template rules.
+pub fn verify_templates(path: &str) -> Result<GoldenReport, TdlnError> {
+    let templates = TemplatesFile::load(path).map_err(TdlnError::Template)?;
+    Ok(verify(&templates, path))
+}
+
+/// Verify every template in an already-loaded [`TemplatesFile`], with
+/// `suite_name` identifying it in the report (typically the path it was
+/// loaded from). A template with both `example` and `output` is rendered
+/// and compared; one missing either is [`CaseOutcome::Ignored`]; a render
+/// error is [`CaseOutcome::Errored`] rather than aborting the remaining
+/// templates.
+pub fn verify(templates: &TemplatesFile, suite_name: &str) -> GoldenReport {
+    let renderer = TemplateRenderer::new(templates.clone(), false);
+
+    let mut names: Vec<&String> = templates.templates.keys().collect();
+    names.sort();
+
+    let cases = names
+        .into_iter()
+        .map(|name| {
+            let template = &templates.templates[name];
+            let outcome = match (&template.example, &template.output) {
+                (Some(example), Some(expected)) => match renderer.render(name, example) {
+                    Ok(actual) if &actual == expected => CaseOutcome::Passed,
+                    Ok(actual) => CaseOutcome::Failed(unified_diff(expected, &actual)),
+                    Err(e) => CaseOutcome::Errored(e.to_string()),
+                },
+                _ => CaseOutcome::Ignored,
+            };
+            GoldenCase { name: name.clone(), outcome }
+        })
+        .collect();
+
+    GoldenReport { suite_name: suite_name.to_string(), cases }
+}
+
+/// A unified diff of `expected` vs `actual`, line by line -- enough to spot
+/// a golden-test regression without pulling in a diff library for what's
+/// usually a one- or two-line template output.
+fn unified_diff(expected: &str, actual: &str) -> String {
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+
+    let mut out = String::new();
+    out.push_str("--- expected\n");
+    out.push_str("+++ actual\n");
+    out.push_str(&format!(
+        "@@ -1,{} +1,{} @@\n",
+        expected_lines.len().max(1),
+        actual_lines.len().max(1),
+    ));
+    for line in &expected_lines {
+        out.push('-');
+        out.push_str(line);
+        out.push('\n');
+    }
+    for line in &actual_lines {
+        out.push('+');
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Escape the five XML special characters so diff/error text can't break
+/// out of its enclosing element.
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn templates_with(example: Option<serde_json::Value>, output: Option<&str>) -> TemplatesFile {
+        let mut templates = TemplatesFile::from_yaml(
+            r#"
+version: "1.0"
+templates:
+  greeting:
+    description: Simple greeting
+    template: "Hello, {{name}}!"
+"#,
+        )
+        .unwrap();
+
+        let template = templates.templates.get_mut("greeting").unwrap();
+        template.example = example;
+        template.output = output.map(String::from);
+        templates
+    }
+
+    #[test]
+    fn test_passing_case() {
+        let templates = templates_with(Some(json!({ "name": "World" })), Some("Hello, World!"));
+        let report = verify(&templates, "suite");
+        assert_eq!(report.cases.len(), 1);
+        assert!(matches!(report.cases[0].outcome, CaseOutcome::Passed));
+        assert!(report.all_passed());
+    }
+
+    #[test]
+    fn test_failing_case_carries_a_diff() {
+        let templates = templates_with(Some(json!({ "name": "World" })), Some("Hello, Mars!"));
+        let report = verify(&templates, "suite");
+        match &report.cases[0].outcome {
+            CaseOutcome::Failed(diff) => {
+                assert!(diff.contains("-Hello, Mars!"));
+                assert!(diff.contains("+Hello, World!"));
+            }
+            other => panic!("expected Failed, got {other:?}"),
+        }
+        assert_eq!(report.failures(), 1);
+        assert!(!report.all_passed());
+    }
+
+    #[test]
+    fn test_missing_example_or_output_is_ignored() {
+        let templates = templates_with(None, Some("Hello, World!"));
+        let report = verify(&templates, "suite");
+        assert!(matches!(report.cases[0].outcome, CaseOutcome::Ignored));
+        assert_eq!(report.ignored(), 1);
+        assert_eq!(report.failures(), 0);
+    }
+
+    #[test]
+    fn test_render_error_is_a_failure_not_an_abort() {
+        let mut templates = templates_with(Some(json!({ "name": "World" })), Some("Hello, World!"));
+        templates.templates.insert(
+            "broken".to_string(),
+            crate::templates::Template {
+                description: "Calls an unregistered helper".to_string(),
+                template: "{{not_a_real_helper name}}".to_string(),
+                example: Some(json!({ "name": "World" })),
+                output: Some("anything".to_string()),
+                include: None,
+            },
+        );
+
+        let report = verify(&templates, "suite");
+        assert_eq!(report.cases.len(), 2);
+
+        let broken = report.cases.iter().find(|c| c.name == "broken").unwrap();
+        assert!(matches!(broken.outcome, CaseOutcome::Errored(_)));
+
+        let greeting = report.cases.iter().find(|c| c.name == "greeting").unwrap();
+        assert!(matches!(greeting.outcome, CaseOutcome::Passed));
+
+        assert_eq!(report.failures(), 1);
+    }
+
+    #[test]
+    fn test_junit_xml_shape() {
+        let templates = templates_with(Some(json!({ "name": "World" })), Some("Hello, World!"));
+        let xml = verify(&templates, "response-templates.yaml").to_junit_xml();
+
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<testsuites>"));
+        assert!(xml.contains("<testsuite name=\"response-templates.yaml\" tests=\"1\" failures=\"0\" skipped=\"0\">"));
+        assert!(xml.contains("<testcase name=\"greeting\"/>"));
+        assert!(xml.trim_end().ends_with("</testsuites>"));
+    }
+}
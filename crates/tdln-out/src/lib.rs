@@ -27,12 +27,14 @@
 pub mod templates;
 pub mod renderer;
 pub mod citations;
+pub mod error;
+pub mod golden;
 
 use citations::{CitationSet, ValidationResult, extract_citations, validate_output};
+pub use error::TdlnError;
 use renderer::TemplateRenderer;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use thiserror::Error;
 
 /// Request to render structured data to natural language
 #[derive(Debug, Clone, Deserialize)]
@@ -58,40 +60,28 @@ pub struct RenderResult {
     pub validation: ValidationResult,
 }
 
-/// Errors that can occur during rendering
-#[derive(Debug, Error)]
-pub enum RenderError {
-    #[error("Template load failed: {0}")]
-    Template(String),
-    #[error("Render failed: {0}")]
-    Render(String),
-    #[error("Validation failed: {0}")]
-    Validation(String),
-}
-
 /// Default templates path
 const DEFAULT_TEMPLATES_PATH: &str = "grammars/response-templates.yaml";
 
 /// Render structured data to natural language
-pub fn render_to_nl(request: RenderRequest) -> Result<RenderResult, RenderError> {
+pub fn render_to_nl(request: RenderRequest) -> Result<RenderResult, TdlnError> {
     let templates_path = request.templates_path
         .as_deref()
         .unwrap_or(DEFAULT_TEMPLATES_PATH);
-    
+
     // Load templates
     let renderer = TemplateRenderer::load(templates_path)
-        .map_err(RenderError::Template)?;
-    
+        .map_err(TdlnError::Template)?;
+
     // Extract citations from source data
     let citations = extract_citations(&request.data, "");
-    
+
     // Render the template
-    let output = renderer.render(&request.template_name, &request.data)
-        .map_err(RenderError::Render)?;
-    
+    let output = renderer.render(&request.template_name, &request.data)?;
+
     // Validate output
     let validation = validate_output(&output, &request.data, &citations);
-    
+
     Ok(RenderResult {
         output,
         template_used: request.template_name,
@@ -100,8 +90,57 @@ pub fn render_to_nl(request: RenderRequest) -> Result<RenderResult, RenderError>
     })
 }
 
+/// Result of a [`render_to_writer`] call: the same provenance/validation
+/// metadata [`RenderResult`] carries, minus `output` -- the rendered text
+/// was streamed directly into the caller's writer instead of buffered and
+/// returned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamedRenderResult {
+    /// Template that was used
+    pub template_used: String,
+    /// Citations for provenance
+    pub citations: CitationSet,
+    /// Validation result
+    pub validation: ValidationResult,
+}
+
+/// Render structured data to natural language, writing incrementally into
+/// `writer` instead of materializing the full output as a `String` --
+/// see [`renderer::TemplateRenderer::render_to_writer`]. Citations are
+/// extracted from `request.data` as before, and output validation still
+/// sees the complete rendered text (teed through `writer` as it's
+/// produced), so the returned metadata
+/// matches [`render_to_nl`]'s one-for-one, minus `output` itself.
+pub fn render_to_writer(
+    request: RenderRequest,
+    writer: &mut dyn std::io::Write,
+) -> Result<StreamedRenderResult, TdlnError> {
+    let templates_path = request.templates_path
+        .as_deref()
+        .unwrap_or(DEFAULT_TEMPLATES_PATH);
+
+    // Load templates
+    let renderer = TemplateRenderer::load(templates_path)
+        .map_err(TdlnError::Template)?;
+
+    // Extract citations from source data
+    let citations = extract_citations(&request.data, "");
+
+    // Render the template straight into `writer`
+    let output = renderer.render_to_writer(&request.template_name, &request.data, writer)?;
+
+    // Validate output
+    let validation = validate_output(&output, &request.data, &citations);
+
+    Ok(StreamedRenderResult {
+        template_used: request.template_name,
+        citations,
+        validation,
+    })
+}
+
 /// Render with an inline template string
-pub fn render_string(template: &str, data: &Value) -> Result<String, RenderError> {
+pub fn render_string(template: &str, data: &Value) -> Result<String, TdlnError> {
     let templates = templates::TemplatesFile::from_yaml(&format!(
         r#"version: "1.0"
 templates:
@@ -110,19 +149,18 @@ templates:
     template: "{}"
 "#,
         template.replace('"', "\\\"")
-    )).map_err(RenderError::Template)?;
-    
-    let renderer = TemplateRenderer::new(templates);
-    renderer.render("inline", data).map_err(RenderError::Render)
+    )).map_err(TdlnError::Template)?;
+
+    let renderer = TemplateRenderer::new(templates, false);
+    Ok(renderer.render("inline", data)?)
 }
 
 /// Legacy render function for backwards compatibility
-pub fn render(data: &Value, template_path: &str) -> Result<String, RenderError> {
+pub fn render(data: &Value, template_path: &str) -> Result<String, TdlnError> {
     let templates = templates::load(template_path)
-        .map_err(RenderError::Template)?;
-    
-    renderer::render_template(&templates, data)
-        .map_err(RenderError::Render)
+        .map_err(TdlnError::Template)?;
+
+    Ok(renderer::render_template(&templates, data)?)
 }
 
 /// Quick render helper for common response types
@@ -163,12 +201,59 @@ pub mod quick {
             "question": question,
             "suggestions": suggestions,
         });
-        
+
         render_string(
             "{{question}}\n\nTry:\n{{#each suggestions}}- {{this}}\n{{/each}}",
             &data
         ).unwrap_or_else(|_| question.to_string())
     }
+
+    /// Render a policy-blocked audit entry as a message listing each
+    /// violation with its remediation (if any) and who can authorize an
+    /// override. The message comes from an inline template string; no named
+    /// template for it is defined in a templates file.
+    pub fn policy_blocked(entry: &tdln_policy::AuditEntry) -> String {
+        let violations: Vec<Value> = entry.violations.iter()
+            .map(|v| json!({
+                "description": v.description,
+                "remediation": v.remediation,
+            }))
+            .collect();
+
+        let data = json!({
+            "operation": entry.operation,
+            "risk_level": format!("{:?}", entry.risk_level),
+            "risk_score": entry.risk_score,
+            "violations": violations,
+            "override_paths": override_paths(entry.risk_level),
+        });
+
+        render_string(
+            "✗ Blocked: {{operation}} (risk: {{risk_level}}, score: {{risk_score}})\n\
+             {{#each violations}}- {{this.description}}{{#if this.remediation}}\n    remediation: {{this.remediation}}{{/if}}\n{{/each}}\
+             {{#if override_paths}}\nOverride path:\n{{#each override_paths}}- {{this.role}} ({{this.types}})\n{{/each}}{{/if}}",
+            &data
+        ).unwrap_or_else(|_| format!("Blocked: {}", entry.operation))
+    }
+
+    /// Roles whose [`tdln_policy::OverridePermissions`] cover `risk_level`,
+    /// paired with the override types each is allowed to use -- the
+    /// "who can authorize, which `OverrideType` applies" half of a
+    /// `policy_blocked` message.
+    fn override_paths(risk_level: tdln_policy::RiskLevel) -> Vec<Value> {
+        use tdln_policy::OverridePermissions;
+
+        [("reviewer", OverridePermissions::reviewer()), ("admin", OverridePermissions::admin())]
+            .into_iter()
+            .filter(|(_, permissions)| permissions.max_risk_level >= risk_level)
+            .map(|(role, permissions)| {
+                let types: Vec<String> = permissions.allowed_types.iter()
+                    .map(|t| t.to_string())
+                    .collect();
+                json!({ "role": role, "types": types.join(", ") })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -182,6 +267,39 @@ mod tests {
         assert_eq!(result, "Hello, World!");
     }
 
+    #[test]
+    fn test_render_to_writer_streams_and_matches_render_to_nl_metadata() {
+        let path = std::env::temp_dir().join(format!(
+            "tdln_out_render_to_writer_test_{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(&path, r#"
+version: "1.0"
+templates:
+  greeting:
+    description: Simple greeting
+    template: "Hello, {{name}}!"
+"#).unwrap();
+
+        let request = RenderRequest {
+            template_name: "greeting".to_string(),
+            data: json!({ "name": "World" }),
+            templates_path: Some(path.to_string_lossy().into_owned()),
+        };
+
+        let mut buf = Vec::new();
+        let streamed = render_to_writer(request.clone(), &mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "Hello, World!");
+
+        let buffered = render_to_nl(request).unwrap();
+        assert_eq!(buffered.output, "Hello, World!");
+        assert_eq!(streamed.template_used, buffered.template_used);
+        assert_eq!(streamed.validation.valid, buffered.validation.valid);
+        assert_eq!(streamed.citations.all().len(), buffered.citations.all().len());
+
+        std::fs::remove_file(&path).ok();
+    }
+
     #[test]
     fn test_quick_success() {
         let result = quick::job_success("Fixed the bug", None, None);
@@ -195,4 +313,20 @@ mod tests {
         assert!(result.contains("Failed"));
         assert!(result.contains("AssertionError"));
     }
+
+    #[test]
+    fn test_quick_policy_blocked() {
+        use tdln_policy::{AuditEntry, OperationMetrics, PolicySet, RuleContext};
+
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("feature").with_files(20).with_lines(500);
+        let metrics = OperationMetrics::new().with_files(20, vec![]).with_lines(500);
+        let evaluation = policy.evaluate(&context, &metrics);
+        let entry = AuditEntry::from_evaluation(&evaluation, "op_1");
+
+        let result = quick::policy_blocked(&entry);
+        assert!(result.contains("Blocked: op_1"));
+        assert!(result.contains("Override path"));
+        assert!(result.contains("reviewer") || result.contains("admin"));
+    }
 }
@@ -2,73 +2,376 @@
 //!
 //! Uses Handlebars for template rendering with custom helpers:
 //! - percent: Format number as percentage
+//! - round: Round a number to N decimal places (default 0)
+//! - fixed: Format a number with exactly N decimal places (default 2)
+//! - pluralize: Pick a singular/plural word form by count
 //! - truncate: Truncate string to max length
+//! - regex_replace: Substitute regex matches in a string, pattern compiled once and cached
 //! - eq: Equality comparison for conditionals
 //! - join: Join array with separator
+//! - default: Fall back to a default when a value is missing
+//! - plus one `ScriptHelper` per entry in [`TemplatesFile::scripts`], a
+//!   Rhai expression compiled at load time so template authors can add
+//!   their own conditional/arithmetic helpers without a recompile, and any
+//!   closure registered via [`TemplateRenderer::register_helper`].
+//!
+//! A template calling a helper that isn't registered under any of the
+//! above fails with [`crate::error::TemplateRenderError::UnknownHelper`]
+//! rather than the generic [`crate::error::TemplateRenderError::Render`],
+//! so a typo in a template or a [`TemplatesFile::helpers`] entry with no
+//! backing registration is distinguishable from any other render failure.
+//!
+//! Every entry of [`TemplatesFile::templates`] and [`TemplatesFile::partials`]
+//! is registered with Handlebars, so templates can compose via
+//! `{{> score_badge}}` the same way they'd include any other registered
+//! template -- context defaults to the includer's own, or an explicit
+//! sub-path can be passed (`{{> files_block changes}}`), both native
+//! Handlebars syntax. [`Template::include`] is shorthand for the same thing:
+//! setting it to a name is equivalent to prefixing the template body with
+//! `{{> name}}`. Templates may also define their own ad-hoc `{{#*inline
+//! "x"}}...{{/inline}}` partials -- that's native Handlebars syntax too and
+//! needs no registration here. `render`/`render_string` walk the static
+//! `{{> name}}` reference graph before handing off to Handlebars, so a
+//! template that includes itself (directly or transitively) fails with
+//! [`crate::error::TemplateRenderError::CyclicInclude`] instead of
+//! recursing until the stack overflows.
+//!
+//! [`TemplatesFile::decorators`] registers Rhai-scripted Handlebars
+//! decorators (`{{*name}}`), which run before helpers and can merge
+//! precomputed fields into the render context; [`TemplateRenderer::register_decorator`]
+//! does the same for a hand-written Rust decorator.
+//!
+//! `render`/`render_string` return [`crate::error::TemplateRenderError`]
+//! rather than a formatted string, so a caller can distinguish a template
+//! parse error from a helper failure from a missing field. The latter is
+//! only raised when the renderer was built with `strict: true` (see
+//! [`TemplateRenderer::new`]) -- otherwise Handlebars renders undefined
+//! references as empty, same as before.
 
 use handlebars::{
-    Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext, RenderError,
-    Renderable,
+    Context, Decorator, DecoratorDef, Handlebars, Helper, HelperDef, HelperResult, Output,
+    RenderContext, RenderError, Renderable,
 };
+use once_cell::sync::Lazy;
+use rhai::{Engine, Scope, AST};
 use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
-use crate::templates::TemplatesFile;
+use crate::error::TemplateRenderError;
+use crate::templates::{Template, TemplatesFile};
 
 /// Compiled renderer with registered helpers
 pub struct TemplateRenderer<'a> {
-    handlebars: Handlebars<'a>,
-    templates: TemplatesFile,
+    handlebars: RefCell<Handlebars<'a>>,
+    templates: RefCell<TemplatesFile>,
+    /// Whether undefined `{{field}}` references are a hard render error
+    /// rather than empty output -- see [`TemplateRenderer::new`]. Kept so
+    /// dev-mode reloads rebuild the registry with the same mode.
+    strict: bool,
+    /// Set by [`TemplateRenderer::load_dev`]; `None` keeps the hot render
+    /// path a single field read with no filesystem access, so production
+    /// rendering pays nothing for this feature.
+    dev: Option<DevSource>,
+}
+
+/// Tracks the on-disk templates file backing a dev-mode renderer so each
+/// render can cheaply notice an edit and reload.
+struct DevSource {
+    path: PathBuf,
+    last_modified: RefCell<Option<SystemTime>>,
 }
 
 impl<'a> TemplateRenderer<'a> {
-    /// Create a new renderer from a templates file
-    pub fn new(templates: TemplatesFile) -> Self {
-        let mut handlebars = Handlebars::new();
-        
-        // Configure Handlebars
-        handlebars.set_strict_mode(false);
-        
-        // Register custom helpers
-        handlebars.register_helper("percent", Box::new(PercentHelper));
-        handlebars.register_helper("truncate", Box::new(TruncateHelper));
-        handlebars.register_helper("eq", Box::new(EqHelper));
-        handlebars.register_helper("join", Box::new(JoinHelper));
-        handlebars.register_helper("default", Box::new(DefaultHelper));
-        
-        // Register all templates
-        for (name, template) in &templates.templates {
-            let _ = handlebars.register_template_string(name, &template.template);
+    /// Create a new renderer from a templates file. When `strict` is
+    /// `true`, a `{{field}}` reference that resolves to nothing is a hard
+    /// [`TemplateRenderError::MissingField`] instead of silently rendering
+    /// empty -- turn it on wherever a blank TDLN-OUT artifact would be
+    /// worse than failing the pipeline loudly.
+    pub fn new(templates: TemplatesFile, strict: bool) -> Self {
+        let handlebars = build_registry(&templates, strict);
+        TemplateRenderer {
+            handlebars: RefCell::new(handlebars),
+            templates: RefCell::new(templates),
+            strict,
+            dev: None,
         }
-        
-        TemplateRenderer { handlebars, templates }
     }
 
     /// Load from a file path
     pub fn load(path: &str) -> Result<Self, String> {
         let templates = TemplatesFile::load(path)?;
-        Ok(Self::new(templates))
+        Ok(Self::new(templates, false))
+    }
+
+    /// Load a directory tree of templates instead of a single YAML file --
+    /// see [`TemplatesFile::load_dir`] for how names are namespaced.
+    pub fn load_dir(root: &str) -> Result<Self, String> {
+        let templates = TemplatesFile::load_dir(root)?;
+        Ok(Self::new(templates, false))
+    }
+
+    /// Load from a file path in dev mode: every `render`/`render_string`
+    /// call first stats `path`, and if its mtime has moved since the last
+    /// check, reparses the `TemplatesFile`, rebuilds the Handlebars
+    /// registry and re-registers all helpers before rendering. This
+    /// mirrors the "reload from file when dev mode enabled" pattern used
+    /// elsewhere in this workspace for watching grammar/fixture edits, so
+    /// editing a TDLN-OUT template takes effect on the next render instead
+    /// of requiring a process restart.
+    pub fn load_dev(path: &str) -> Result<Self, String> {
+        let mut renderer = Self::load(path)?;
+        renderer.dev = Some(DevSource {
+            path: PathBuf::from(path),
+            last_modified: RefCell::new(file_mtime(path)),
+        });
+        Ok(renderer)
+    }
+
+    /// Reload the templates file if dev mode is enabled and its mtime has
+    /// changed since the last check. No-op (and no filesystem access) when
+    /// dev mode is off.
+    fn reload_if_stale(&self) {
+        let Some(dev) = &self.dev else { return };
+
+        let current = file_mtime(&dev.path);
+        if current == *dev.last_modified.borrow() {
+            return;
+        }
+        *dev.last_modified.borrow_mut() = current;
+
+        if let Ok(templates) = TemplatesFile::load(dev.path.to_string_lossy().as_ref()) {
+            *self.handlebars.borrow_mut() = build_registry(&templates, self.strict);
+            *self.templates.borrow_mut() = templates;
+        }
     }
 
     /// Render a named template with data
-    pub fn render(&self, template_name: &str, data: &Value) -> Result<String, String> {
-        self.handlebars
-            .render(template_name, data)
-            .map_err(|e| format!("Render error: {}", e))
+    pub fn render(&self, template_name: &str, data: &Value) -> Result<String, TemplateRenderError> {
+        self.reload_if_stale();
+        check_for_cycles(&self.templates.borrow(), template_name)?;
+        Ok(self.handlebars.borrow().render(template_name, data)?)
     }
 
     /// Render a template string directly (not from file)
-    pub fn render_string(&self, template: &str, data: &Value) -> Result<String, String> {
-        self.handlebars
-            .render_template(template, data)
-            .map_err(|e| format!("Render error: {}", e))
+    pub fn render_string(&self, template: &str, data: &Value) -> Result<String, TemplateRenderError> {
+        self.reload_if_stale();
+        for referenced in partial_refs(template) {
+            check_for_cycles(&self.templates.borrow(), referenced)?;
+        }
+        Ok(self.handlebars.borrow().render_template(template, data)?)
+    }
+
+    /// Render a named template straight into `writer` as Handlebars
+    /// produces it, instead of materializing the full output as a `String`
+    /// first -- the difference matters for a large `job_complete_success`
+    /// render (big file lists, many review comments) going to a socket or
+    /// file. The rendered text is still returned, teed through `writer` as
+    /// it's written rather than copied from it afterward, since callers
+    /// like [`crate::render_to_writer`] need the complete text for citation
+    /// validation.
+    pub fn render_to_writer(
+        &self,
+        template_name: &str,
+        data: &Value,
+        writer: &mut dyn std::io::Write,
+    ) -> Result<String, TemplateRenderError> {
+        self.reload_if_stale();
+        check_for_cycles(&self.templates.borrow(), template_name)?;
+
+        let mut tee = TeeWriter { sink: writer, buffer: Vec::new() };
+        self.handlebars.borrow().render_to_write(template_name, data, &mut tee)?;
+        Ok(String::from_utf8_lossy(&tee.buffer).into_owned())
     }
 
-    /// List available template names
-    pub fn list_templates(&self) -> Vec<&str> {
-        self.templates.list_templates()
+    /// List available template names. Owned `String`s rather than `&str`
+    /// because dev-mode reloads can swap `self.templates` out from under a
+    /// borrow at any time.
+    pub fn list_templates(&self) -> Vec<String> {
+        self.templates.borrow().templates.keys().cloned().collect()
+    }
+
+    /// Register a Rust decorator under `name`, invoked in a template as
+    /// `{{*name}}`. Decorators run before helpers and can mutate the
+    /// render context or define local helpers, so they're strictly more
+    /// powerful than a helper when a template needs to derive a value
+    /// once and reference it repeatedly -- see [`TemplatesFile::decorators`]
+    /// for the declarative, Rhai-scripted equivalent.
+    pub fn register_decorator(
+        &mut self,
+        name: &str,
+        decorator: Box<dyn DecoratorDef + Send + Sync + 'a>,
+    ) {
+        self.handlebars.get_mut().register_decorator(name, decorator);
+    }
+
+    /// Register a Rust helper under `name`, invoked in a template as
+    /// `{{name args...}}`. Lets a downstream crate extend the built-in set
+    /// (`percent`, `round`, `fixed`, `pluralize`, `truncate`,
+    /// `regex_replace`, `eq`, `join`, `default`) without forking this one --
+    /// see [`TemplatesFile::scripts`] for the declarative, Rhai-scripted
+    /// equivalent.
+    pub fn register_helper(&mut self, name: &str, helper: Box<dyn HelperDef + Send + Sync + 'a>) {
+        self.handlebars.get_mut().register_helper(name, helper);
     }
 }
 
+/// Build a fresh Handlebars registry from `templates`: the nine built-in
+/// helpers, one [`ScriptHelper`] per Rhai entry in
+/// [`TemplatesFile::scripts`], all named templates, and all
+/// [`TemplatesFile::partials`]. Used both for initial construction and for
+/// dev-mode reloads so the two paths can't drift apart.
+fn build_registry<'a>(templates: &TemplatesFile, strict: bool) -> Handlebars<'a> {
+    let mut handlebars = Handlebars::new();
+
+    // Configure Handlebars
+    handlebars.set_strict_mode(strict);
+
+    // Register custom helpers
+    handlebars.register_helper("percent", Box::new(PercentHelper));
+    handlebars.register_helper("round", Box::new(RoundHelper));
+    handlebars.register_helper("fixed", Box::new(FixedHelper));
+    handlebars.register_helper("pluralize", Box::new(PluralizeHelper));
+    handlebars.register_helper("truncate", Box::new(TruncateHelper));
+    handlebars.register_helper("regex_replace", Box::new(RegexReplaceHelper));
+    handlebars.register_helper("eq", Box::new(EqHelper));
+    handlebars.register_helper("join", Box::new(JoinHelper));
+    handlebars.register_helper("default", Box::new(DefaultHelper));
+
+    // Register Rhai-scripted helpers defined in the templates file.
+    // Scripts that fail to compile are skipped, matching the
+    // best-effort registration of templates below.
+    let engine = Arc::new(Engine::new());
+    for (name, source) in &templates.scripts {
+        if let Ok(ast) = engine.compile(source) {
+            handlebars.register_helper(
+                name,
+                Box::new(ScriptHelper { engine: Arc::clone(&engine), ast }),
+            );
+        }
+    }
+
+    // Register all templates. `Template::include`, if set, is spliced in as
+    // a leading `{{> name}}` so authors can compose without writing the
+    // Handlebars partial syntax by hand -- see `effective_template_source`.
+    for (name, template) in &templates.templates {
+        let _ = handlebars.register_template_string(name, &effective_template_source(template));
+    }
+
+    // Register standalone partial fragments. Handlebars treats a
+    // registered template and a registered partial identically, so this
+    // is the same call as above -- it's split out only so `partials:` in
+    // the YAML can name fragments that aren't meant to be rendered as a
+    // top-level template in their own right.
+    for (name, partial) in &templates.partials {
+        let _ = handlebars.register_partial(name, partial);
+    }
+
+    // Register Rhai-scripted decorators defined in the templates file.
+    // Scripts that fail to compile are skipped, matching the
+    // best-effort registration above.
+    for (name, source) in &templates.decorators {
+        if let Ok(ast) = engine.compile(source) {
+            handlebars.register_decorator(
+                name,
+                Box::new(ScriptDecorator { engine: Arc::clone(&engine), ast }),
+            );
+        }
+    }
+
+    handlebars
+}
+
+/// Forwards every write to `sink` while also accumulating it in `buffer`,
+/// so [`TemplateRenderer::render_to_writer`] can stream Handlebars' output
+/// straight into the caller's writer and still hand back the complete
+/// text in one pass, without a separate buffer-then-copy step.
+struct TeeWriter<'w> {
+    sink: &'w mut dyn std::io::Write,
+    buffer: Vec<u8>,
+}
+
+impl std::io::Write for TeeWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        self.sink.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.sink.flush()
+    }
+}
+
+/// Stat `path` for its mtime, treating any error (missing file,
+/// unsupported platform) as "unknown" rather than failing the render.
+fn file_mtime(path: impl AsRef<std::path::Path>) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// `template`'s Handlebars source, with `{{> <include>}}` spliced in front
+/// if [`Template::include`] is set -- equivalent to an author writing that
+/// partial reference directly in `template:`.
+fn effective_template_source(template: &Template) -> String {
+    match &template.include {
+        Some(name) => format!("{{{{> {name}}}}}{}", template.template),
+        None => template.template.clone(),
+    }
+}
+
+/// Names referenced via `{{> name ...}}` in `body`. A best-effort scan
+/// (not a full Handlebars parse) -- good enough to build the static
+/// dependency graph [`check_for_cycles`] walks; a dynamic partial name
+/// (e.g. `{{> (lookup ..)}}`) isn't followed, the same way it can't be
+/// resolved ahead of render time either.
+fn partial_refs(body: &str) -> Vec<&str> {
+    let mut refs = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("{{>") {
+        let after = &rest[start + 3..];
+        let trimmed = after.trim_start();
+        let end = trimmed.find(|c: char| c.is_whitespace() || c == '}').unwrap_or(trimmed.len());
+        if end != 0 {
+            refs.push(&trimmed[..end]);
+        }
+        rest = after;
+    }
+    refs
+}
+
+/// Walk the static `{{> name}}` dependency graph starting at `start`,
+/// failing with [`TemplateRenderError::CyclicInclude`] the moment a name
+/// already on the current path is reached again -- before Handlebars would
+/// recurse into it at render time and overflow the stack.
+fn check_for_cycles(templates: &TemplatesFile, start: &str) -> Result<(), TemplateRenderError> {
+    fn visit(templates: &TemplatesFile, name: &str, path: &mut Vec<String>) -> Result<(), TemplateRenderError> {
+        if let Some(pos) = path.iter().position(|n| n == name) {
+            let mut cycle = path[pos..].to_vec();
+            cycle.push(name.to_string());
+            return Err(TemplateRenderError::CyclicInclude(cycle.join(" -> ")));
+        }
+
+        let body = if let Some(template) = templates.templates.get(name) {
+            effective_template_source(template)
+        } else if let Some(partial) = templates.partials.get(name) {
+            partial.clone()
+        } else {
+            return Ok(());
+        };
+
+        path.push(name.to_string());
+        for referenced in partial_refs(&body) {
+            visit(templates, referenced, path)?;
+        }
+        path.pop();
+        Ok(())
+    }
+
+    visit(templates, start, &mut Vec::new())
+}
+
 // ============================================================================
 // Custom Helpers
 // ============================================================================
@@ -95,6 +398,77 @@ impl HelperDef for PercentHelper {
     }
 }
 
+/// Round a number to `decimals` decimal places (second param, default 0),
+/// writing it as a plain number -- `{{round 3.14159 2}}` -> `3.14`,
+/// `{{round 2.6}}` -> `3`.
+struct RoundHelper;
+
+impl HelperDef for RoundHelper {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        _r: &'reg Handlebars<'reg>,
+        _ctx: &'rc Context,
+        _rc: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let value = h.param(0).and_then(|v| v.value().as_f64()).unwrap_or(0.0);
+        let decimals = h.param(1).and_then(|v| v.value().as_u64()).unwrap_or(0) as i32;
+
+        let factor = 10f64.powi(decimals);
+        out.write(&format!("{}", (value * factor).round() / factor))?;
+        Ok(())
+    }
+}
+
+/// Format a number with exactly `decimals` decimal places (second param,
+/// default 2), zero-padded -- `{{fixed 3.1}}` -> `3.10`.
+struct FixedHelper;
+
+impl HelperDef for FixedHelper {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        _r: &'reg Handlebars<'reg>,
+        _ctx: &'rc Context,
+        _rc: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let value = h.param(0).and_then(|v| v.value().as_f64()).unwrap_or(0.0);
+        let decimals = h.param(1).and_then(|v| v.value().as_u64()).unwrap_or(2) as usize;
+
+        out.write(&format!("{:.*}", decimals, value))?;
+        Ok(())
+    }
+}
+
+/// Pick a word form by count: `{{pluralize count "file"}}` writes `"file"`
+/// when `count == 1`, otherwise the plural -- either an explicit third
+/// param or `singular` with an `s` appended.
+struct PluralizeHelper;
+
+impl HelperDef for PluralizeHelper {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        _r: &'reg Handlebars<'reg>,
+        _ctx: &'rc Context,
+        _rc: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let count = h.param(0).and_then(|v| v.value().as_f64()).unwrap_or(0.0);
+        let singular = h.param(1).and_then(|v| v.value().as_str()).unwrap_or("");
+        let plural = h.param(2).and_then(|v| v.value().as_str()).map(String::from);
+
+        if count == 1.0 {
+            out.write(singular)?;
+        } else {
+            out.write(&plural.unwrap_or_else(|| format!("{singular}s")))?;
+        }
+        Ok(())
+    }
+}
+
 /// Truncate a string to max length with ellipsis
 struct TruncateHelper;
 
@@ -219,17 +593,153 @@ impl HelperDef for DefaultHelper {
     }
 }
 
+/// Substitute regex matches in a string: `{{regex_replace value pattern
+/// replacement}}`. Patterns are compiled once per distinct string and
+/// cached in [`REGEX_CACHE`] rather than recompiled on every call.
+struct RegexReplaceHelper;
+
+impl HelperDef for RegexReplaceHelper {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        _r: &'reg Handlebars<'reg>,
+        _ctx: &'rc Context,
+        _rc: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let value = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+        let pattern = h.param(1).and_then(|v| v.value().as_str()).unwrap_or("");
+        let replacement = h.param(2).and_then(|v| v.value().as_str()).unwrap_or("");
+
+        match cached_regex(pattern).as_ref() {
+            Some(re) => out.write(&re.replace_all(value, replacement))?,
+            None => out.write(value)?,
+        }
+        Ok(())
+    }
+}
+
+/// Regexes passed to [`RegexReplaceHelper`] are compiled once per distinct
+/// pattern and cached here, rather than recompiled on every helper call --
+/// the same tradeoff `tdln-policy`'s expression DSL makes for its own
+/// regex cache. `None` caches a pattern that failed to compile, so a bad
+/// pattern reached at render time still costs one compile attempt, not
+/// one per call.
+static REGEX_CACHE: Lazy<Mutex<HashMap<String, Arc<Option<regex::Regex>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn cached_regex(pattern: &str) -> Arc<Option<regex::Regex>> {
+    let mut cache = REGEX_CACHE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(re) = cache.get(pattern) {
+        return Arc::clone(re);
+    }
+    let compiled = Arc::new(regex::Regex::new(pattern).ok());
+    cache.insert(pattern.to_string(), Arc::clone(&compiled));
+    compiled
+}
+
+/// A helper backed by a Rhai script from [`TemplatesFile::scripts`]. The
+/// helper's positional params are exposed to the script as a `params`
+/// array (plus `value` bound to the first param, for the common
+/// single-argument case) and the script's return value is written out
+/// via its `to_string()` form.
+struct ScriptHelper {
+    engine: Arc<Engine>,
+    ast: AST,
+}
+
+impl HelperDef for ScriptHelper {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        _r: &'reg Handlebars<'reg>,
+        _ctx: &'rc Context,
+        _rc: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        let params: Vec<Value> = h.params().iter().map(|p| p.value().clone()).collect();
+
+        let mut scope = Scope::new();
+        scope.push(
+            "params",
+            rhai::serde::to_dynamic(&params)
+                .map_err(|e| RenderError::new(format!("script helper params: {e}")))?,
+        );
+        if let Some(first) = params.first() {
+            scope.push(
+                "value",
+                rhai::serde::to_dynamic(first)
+                    .map_err(|e| RenderError::new(format!("script helper value: {e}")))?,
+            );
+        }
+
+        let result: rhai::Dynamic = self
+            .engine
+            .eval_ast_with_scope(&mut scope, &self.ast)
+            .map_err(|e| RenderError::new(format!("script helper eval: {e}")))?;
+
+        out.write(&result.to_string())?;
+        Ok(())
+    }
+}
+
+/// A decorator backed by a Rhai script from [`TemplatesFile::decorators`].
+/// The script sees the current render context's data as `ctx` and must
+/// return an object; that object's keys are merged into the context
+/// (overwriting on conflict) before the rest of the template renders, so
+/// e.g. `{{*totals}}` can precompute a `summary` object once instead of
+/// every `{{summary.pass_rate}}` reference recomputing it.
+struct ScriptDecorator {
+    engine: Arc<Engine>,
+    ast: AST,
+}
+
+impl DecoratorDef for ScriptDecorator {
+    fn call<'reg: 'rc, 'rc>(
+        &'reg self,
+        _d: &Decorator<'reg, 'rc>,
+        _r: &'reg Handlebars<'reg>,
+        ctx: &'rc Context,
+        rc: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<(), RenderError> {
+        let Value::Object(mut base) = ctx.data().clone() else {
+            return Err(RenderError::new("script decorator requires an object context"));
+        };
+
+        let mut scope = Scope::new();
+        scope.push(
+            "ctx",
+            rhai::serde::to_dynamic(ctx.data())
+                .map_err(|e| RenderError::new(format!("script decorator ctx: {e}")))?,
+        );
+
+        let result: rhai::Dynamic = self
+            .engine
+            .eval_ast_with_scope(&mut scope, &self.ast)
+            .map_err(|e| RenderError::new(format!("script decorator eval: {e}")))?;
+        let derived: Value = rhai::serde::from_dynamic(&result)
+            .map_err(|e| RenderError::new(format!("script decorator result: {e}")))?;
+        let Value::Object(derived) = derived else {
+            return Err(RenderError::new("script decorator must return an object"));
+        };
+
+        base.extend(derived);
+        rc.set_context(Context::wraps(base)?);
+        Ok(())
+    }
+}
+
 /// Legacy render function for backwards compatibility
-pub fn render_template(template: &TemplatesFile, data: &Value) -> Result<String, String> {
-    let renderer = TemplateRenderer::new(template.clone());
-    
+pub fn render_template(template: &TemplatesFile, data: &Value) -> Result<String, TemplateRenderError> {
+    let renderer = TemplateRenderer::new(template.clone(), false);
+
     // Try to render "default" template
     if template.templates.contains_key("default") {
         renderer.render("default", data)
     } else if let Some(first_name) = template.templates.keys().next() {
         renderer.render(first_name, data)
     } else {
-        Err("No templates found".to_string())
+        Err(TemplateRenderError::Render("No templates found".to_string()))
     }
 }
 
@@ -254,24 +764,430 @@ templates:
 "#).unwrap()
     }
 
+    #[test]
+    fn test_non_strict_renders_missing_field_as_empty() {
+        let renderer = TemplateRenderer::new(test_templates(), false);
+        let result = renderer.render("greeting", &json!({})).unwrap();
+        assert_eq!(result, "Hello, !");
+    }
+
+    #[test]
+    fn test_strict_mode_errors_on_missing_field() {
+        let renderer = TemplateRenderer::new(test_templates(), true);
+        let err = renderer.render("greeting", &json!({})).unwrap_err();
+        assert!(matches!(err, TemplateRenderError::MissingField(_)));
+    }
+
+    #[test]
+    fn test_script_helper_failure_is_structured() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  divide:
+    description: Divides by a field that doesn't exist, to force a Rhai error
+    template: "{{divide value}}"
+scripts:
+  divide: "value / missing_var"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let err = renderer.render("divide", &json!({ "value": 10 })).unwrap_err();
+        assert!(matches!(err, TemplateRenderError::Helper(_)));
+    }
+
     #[test]
     fn test_simple_render() {
-        let renderer = TemplateRenderer::new(test_templates());
+        let renderer = TemplateRenderer::new(test_templates(), false);
         let result = renderer.render("greeting", &json!({ "name": "World" })).unwrap();
         assert_eq!(result, "Hello, World!");
     }
 
     #[test]
     fn test_percent_helper() {
-        let renderer = TemplateRenderer::new(test_templates());
+        let renderer = TemplateRenderer::new(test_templates(), false);
         let result = renderer.render("score", &json!({ "score": 0.85 })).unwrap();
         assert_eq!(result, "Score: 85%");
     }
 
+    #[test]
+    fn test_round_helper() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  rounded:
+    description: Round to 2 decimal places
+    template: "{{round value 2}}"
+  rounded_default:
+    description: Round with the default of 0 decimal places
+    template: "{{round value}}"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let result = renderer.render("rounded", &json!({ "value": 3.14159 })).unwrap();
+        assert_eq!(result, "3.14");
+        let result = renderer.render("rounded_default", &json!({ "value": 2.6 })).unwrap();
+        assert_eq!(result, "3");
+    }
+
+    #[test]
+    fn test_fixed_helper() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  price:
+    description: Format with exactly 2 decimal places
+    template: "${{fixed value}}"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let result = renderer.render("price", &json!({ "value": 3.1 })).unwrap();
+        assert_eq!(result, "$3.10");
+    }
+
+    #[test]
+    fn test_pluralize_helper() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  count:
+    description: Pluralize a count with a default plural form
+    template: "{{count}} {{pluralize count \"file\"}}"
+  count_explicit:
+    description: Pluralize a count with an explicit irregular plural
+    template: "{{count}} {{pluralize count \"box\" \"boxes\"}}"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        assert_eq!(renderer.render("count", &json!({ "count": 1 })).unwrap(), "1 file");
+        assert_eq!(renderer.render("count", &json!({ "count": 3 })).unwrap(), "3 files");
+        assert_eq!(
+            renderer.render("count_explicit", &json!({ "count": 2 })).unwrap(),
+            "2 boxes"
+        );
+    }
+
+    #[test]
+    fn test_regex_replace_helper() {
+        let templates = TemplatesFile::from_yaml(r##"
+version: "1.0"
+templates:
+  redacted:
+    description: Redact digits from a string
+    template: "{{regex_replace value \"[0-9]+\" \"#\"}}"
+"##).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let result = renderer.render("redacted", &json!({ "value": "order 12345 shipped" })).unwrap();
+        assert_eq!(result, "order # shipped");
+    }
+
+    #[test]
+    fn test_register_helper_rust_fn() {
+        struct ShoutHelper;
+        impl HelperDef for ShoutHelper {
+            fn call<'reg: 'rc, 'rc>(
+                &self,
+                h: &Helper<'reg, 'rc>,
+                _r: &'reg Handlebars<'reg>,
+                _ctx: &'rc Context,
+                _rc: &mut RenderContext<'reg, 'rc>,
+                out: &mut dyn Output,
+            ) -> HelperResult {
+                let text = h.param(0).and_then(|v| v.value().as_str()).unwrap_or("");
+                out.write(&text.to_uppercase())?;
+                Ok(())
+            }
+        }
+
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  greeting:
+    description: Simple greeting
+    template: "{{shout name}}"
+"#).unwrap();
+
+        let mut renderer = TemplateRenderer::new(templates, false);
+        renderer.register_helper("shout", Box::new(ShoutHelper));
+        let result = renderer.render("greeting", &json!({ "name": "world" })).unwrap();
+        assert_eq!(result, "WORLD");
+    }
+
+    #[test]
+    fn test_unknown_helper_is_a_typed_error() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  broken:
+    description: Calls a helper that was never registered
+    template: "{{not_a_real_helper name}}"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let err = renderer.render("broken", &json!({ "name": "hello" })).unwrap_err();
+        assert!(matches!(err, TemplateRenderError::UnknownHelper(_)));
+    }
+
     #[test]
     fn test_join_helper() {
-        let renderer = TemplateRenderer::new(test_templates());
+        let renderer = TemplateRenderer::new(test_templates(), false);
         let result = renderer.render("list", &json!({ "items": ["a", "b", "c"] })).unwrap();
         assert_eq!(result, "Items: a, b, c");
     }
+
+    #[test]
+    fn test_partial_composition() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  verdict:
+    description: Verdict with an embedded score badge
+    template: "Verdict: {{> score_badge}}"
+partials:
+  score_badge: "[{{percent score}}]"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let result = renderer.render("verdict", &json!({ "score": 0.5 })).unwrap();
+        assert_eq!(result, "Verdict: [50%]");
+    }
+
+    #[test]
+    fn test_inline_partial() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  verdict:
+    description: Verdict with an inline partial
+    template: "{{#*inline \"badge\"}}<{{score}}>{{/inline}}{{> badge}}"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let result = renderer.render("verdict", &json!({ "score": 42 })).unwrap();
+        assert_eq!(result, "<42>");
+    }
+
+    #[test]
+    fn test_named_template_composes_another_via_partial_syntax() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  files_block:
+    description: Bulleted list of changed files, rendered relative to its own context
+    template: "{{#each files}}- {{this}}\n{{/each}}"
+  job_complete_success:
+    description: Job completion message passing a sub-path context to files_block
+    template: "Done! {{summary}}\n{{> files_block changes}}"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let data = json!({ "summary": "Fixed the bug", "changes": { "files": ["a.rs", "b.rs"] } });
+        let result = renderer.render("job_complete_success", &data).unwrap();
+        assert_eq!(result, "Done! Fixed the bug\n- a.rs\n- b.rs\n");
+    }
+
+    #[test]
+    fn test_include_key_splices_a_leading_partial_reference() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  score_badge:
+    description: Score badge fragment
+    template: "[{{percent score}}]"
+  verdict:
+    description: Verdict using the include shorthand instead of {{> score_badge}}
+    template: " is the verdict"
+    include: score_badge
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let result = renderer.render("verdict", &json!({ "score": 0.5 })).unwrap();
+        assert_eq!(result, "[50%] is the verdict");
+    }
+
+    #[test]
+    fn test_direct_self_include_is_rejected_as_cyclic() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  looping:
+    description: Includes itself
+    template: "{{> looping}}"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let err = renderer.render("looping", &json!({})).unwrap_err();
+        assert!(matches!(err, TemplateRenderError::CyclicInclude(_)));
+    }
+
+    #[test]
+    fn test_transitive_cycle_through_a_partial_is_rejected() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  a:
+    description: Includes b
+    template: "{{> b}}"
+  b:
+    description: Includes the back_to_a partial
+    template: "{{> back_to_a}}"
+partials:
+  back_to_a: "{{> a}}"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let err = renderer.render("a", &json!({})).unwrap_err();
+        match err {
+            TemplateRenderError::CyclicInclude(cycle) => {
+                assert_eq!(cycle, "a -> b -> back_to_a -> a");
+            }
+            other => panic!("expected CyclicInclude, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_script_decorator() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  report:
+    description: Pass-rate summary computed by a decorator
+    template: "{{*totals}}{{summary.pass_rate}}"
+decorators:
+  totals: |
+    let proofs = ctx.proofs;
+    let passed = 0;
+    for p in proofs { if p.pass { passed += 1; } }
+    #{ summary: #{ pass_rate: passed.to_float() / proofs.len().to_float() } }
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let data = json!({
+            "proofs": [{ "pass": true }, { "pass": true }, { "pass": false }, { "pass": true }]
+        });
+        let result = renderer.render("report", &data).unwrap();
+        assert_eq!(result, "0.75");
+    }
+
+    #[test]
+    fn test_register_decorator_rust_fn() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  greeting:
+    description: Simple greeting
+    template: "{{*shout}}{{name}}"
+"#).unwrap();
+
+        let mut renderer = TemplateRenderer::new(templates, false);
+        renderer.register_decorator(
+            "shout",
+            Box::new(
+                |_: &handlebars::Decorator<'_, '_>,
+                 _: &Handlebars<'_>,
+                 ctx: &Context,
+                 rc: &mut RenderContext<'_, '_>|
+                 -> Result<(), RenderError> {
+                    let Value::Object(mut base) = ctx.data().clone() else {
+                        return Ok(());
+                    };
+                    base.insert("name".to_string(), json!("WORLD"));
+                    rc.set_context(Context::wraps(base)?);
+                    Ok(())
+                },
+            ),
+        );
+
+        let result = renderer.render("greeting", &json!({ "name": "world" })).unwrap();
+        assert_eq!(result, "WORLD");
+    }
+
+    #[test]
+    fn test_script_helper() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  shout:
+    description: Shout a value
+    template: "{{shout name}}"
+scripts:
+  shout: "value.to_upper() + \"!\""
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        let result = renderer.render("shout", &json!({ "name": "hello" })).unwrap();
+        assert_eq!(result, "HELLO!");
+    }
+
+    #[test]
+    fn test_load_dir_renders_namespaced_templates() {
+        let root = std::env::temp_dir().join(format!(
+            "tdln_out_load_dir_renderer_test_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(root.join("out/verdict")).unwrap();
+        std::fs::write(root.join("out/verdict/fail.hbs"), "Failed: {{reason}}").unwrap();
+
+        let renderer = TemplateRenderer::load_dir(root.to_str().unwrap()).unwrap();
+        assert_eq!(renderer.list_templates(), vec!["out/verdict/fail".to_string()]);
+        let result = renderer
+            .render("out/verdict/fail", &json!({ "reason": "timeout" }))
+            .unwrap();
+        assert_eq!(result, "Failed: timeout");
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_load_dev_reloads_on_mtime_change() {
+        let path = std::env::temp_dir().join(format!(
+            "tdln_out_load_dev_test_{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(&path, r#"
+version: "1.0"
+templates:
+  greeting:
+    description: Simple greeting
+    template: "Hello, {{name}}!"
+"#).unwrap();
+
+        let renderer = TemplateRenderer::load_dev(path.to_str().unwrap()).unwrap();
+        let first = renderer.render("greeting", &json!({ "name": "World" })).unwrap();
+        assert_eq!(first, "Hello, World!");
+
+        // Force the mtime forward so the next render sees a change even if
+        // the rewrite lands within the filesystem's mtime granularity.
+        let bumped = SystemTime::now() + std::time::Duration::from_secs(2);
+        std::fs::write(&path, r#"
+version: "1.0"
+templates:
+  greeting:
+    description: Simple greeting
+    template: "Hi, {{name}}!"
+"#).unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        file.set_modified(bumped).unwrap();
+
+        let second = renderer.render("greeting", &json!({ "name": "World" })).unwrap();
+        assert_eq!(second, "Hi, World!");
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_script_helper_with_invalid_script_is_skipped() {
+        let templates = TemplatesFile::from_yaml(r#"
+version: "1.0"
+templates:
+  broken:
+    description: Uses an unregistered helper
+    template: "{{broken name}}"
+scripts:
+  broken: "this is not ) valid rhai ("
+"#).unwrap();
+
+        let renderer = TemplateRenderer::new(templates, false);
+        assert!(renderer.render("broken", &json!({ "name": "hello" })).is_err());
+    }
 }
@@ -7,6 +7,8 @@
 
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::path::Path;
+use walkdir::WalkDir;
 
 /// Top-level templates file structure
 #[derive(Debug, Clone, Deserialize)]
@@ -17,6 +19,30 @@ pub struct TemplatesFile {
     pub filters: HashMap<String, FilterDef>,
     #[serde(default)]
     pub helpers: HashMap<String, HelperDef>,
+    /// Rhai expression helpers, keyed by the name they're registered under
+    /// with Handlebars. Lets conditional/arithmetic formatting logic for
+    /// TDLN-OUT ship with the template file instead of requiring a
+    /// recompile of this crate -- see [`crate::renderer::TemplateRenderer::new`].
+    #[serde(default)]
+    pub scripts: HashMap<String, String>,
+    /// Reusable Handlebars partial fragments, keyed by the name they're
+    /// registered under -- e.g. a `score_badge` fragment a `verdict`
+    /// template includes via `{{> score_badge}}`. Kept separate from
+    /// `templates` so a partial meant only for composition doesn't also
+    /// show up as a top-level template authors can render directly -- see
+    /// [`crate::renderer::TemplateRenderer::new`].
+    #[serde(default)]
+    pub partials: HashMap<String, String>,
+    /// Rhai scripts run as Handlebars decorators before rendering, keyed by
+    /// the name they're registered under (invoked as `{{*name}}`). Each
+    /// script sees the current render context as `ctx` and returns an
+    /// object whose keys are merged into that context -- e.g. a `totals`
+    /// decorator that pre-aggregates an array of stage proofs into a
+    /// `summary` object downstream `{{summary.pass_rate}}` references can
+    /// read, computed once per render instead of per reference -- see
+    /// [`crate::renderer::TemplateRenderer::new`].
+    #[serde(default)]
+    pub decorators: HashMap<String, String>,
 }
 
 /// A single template definition
@@ -28,6 +54,15 @@ pub struct Template {
     pub example: Option<serde_json::Value>,
     #[serde(default)]
     pub output: Option<String>,
+    /// Shorthand for starting this template's body with `{{> <include>}}`,
+    /// so an author composing mostly-shared fragments doesn't have to spell
+    /// out the Handlebars partial syntax by hand -- equivalent to writing
+    /// `template: "{{> <include>}}..."` directly. Resolved against the same
+    /// `templates` map as an inline `{{> name}}` reference (see
+    /// [`crate::renderer::build_registry`]), so it can name either another
+    /// top-level template or a [`TemplatesFile::partials`] fragment.
+    #[serde(default)]
+    pub include: Option<String>,
 }
 
 /// Filter definition
@@ -75,18 +110,74 @@ impl TemplatesFile {
                 template: legacy.template,
                 example: None,
                 output: None,
+                include: None,
             });
             return Ok(TemplatesFile {
                 version: "1.0".to_string(),
                 templates,
                 filters: HashMap::new(),
                 helpers: HashMap::new(),
+                scripts: HashMap::new(),
+                partials: HashMap::new(),
+                decorators: HashMap::new(),
             });
         }
         
         Err("Failed to parse templates YAML".to_string())
     }
 
+    /// Load a directory tree of templates instead of a single YAML file.
+    /// Every `.hbs` file becomes a template whose content is the raw
+    /// Handlebars source (with an empty `description`); every
+    /// `.yaml`/`.yml` file is parsed as a single [`Template`] definition.
+    /// Either way the template's name is derived from its path relative to
+    /// `root` with the extension stripped and components joined with `/`
+    /// -- so `out/verdict/fail.hbs` becomes the template `out/verdict/fail`
+    /// on every platform, Windows included. Lets a large TDLN-OUT template
+    /// set live as files on disk instead of one giant YAML.
+    pub fn load_dir(root: &str) -> Result<Self, String> {
+        let root_path = Path::new(root);
+        let mut file = TemplatesFile {
+            version: "1.0".to_string(),
+            templates: HashMap::new(),
+            filters: HashMap::new(),
+            helpers: HashMap::new(),
+            scripts: HashMap::new(),
+            partials: HashMap::new(),
+            decorators: HashMap::new(),
+        };
+
+        for entry in WalkDir::new(root_path) {
+            let entry = entry.map_err(|e| format!("Failed to walk {}: {}", root, e))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let path = entry.path();
+            let template = match path.extension().and_then(|e| e.to_str()) {
+                Some("hbs") => Template {
+                    description: String::new(),
+                    template: std::fs::read_to_string(path)
+                        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?,
+                    example: None,
+                    output: None,
+                    include: None,
+                },
+                Some("yaml") | Some("yml") => {
+                    let content = std::fs::read_to_string(path)
+                        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                    serde_yaml::from_str(&content)
+                        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?
+                }
+                _ => continue,
+            };
+
+            file.templates.insert(namespaced_name(root_path, path)?, template);
+        }
+
+        Ok(file)
+    }
+
     /// Get a template by name
     pub fn get(&self, name: &str) -> Option<&Template> {
         self.templates.get(name)
@@ -103,6 +194,23 @@ pub fn load(path: &str) -> Result<TemplatesFile, String> {
     TemplatesFile::load(path)
 }
 
+/// Derive a [`TemplatesFile::load_dir`] template name from `path`'s
+/// location relative to `root`: strip the extension and join path
+/// components with `/`, so the name is identical whether loaded on
+/// Windows or Unix.
+fn namespaced_name(root: &Path, path: &Path) -> Result<String, String> {
+    let relative = path
+        .strip_prefix(root)
+        .map_err(|e| format!("{} is not under {}: {}", path.display(), root.display(), e))?
+        .with_extension("");
+
+    Ok(relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,8 +237,104 @@ templates:
         let yaml = r#"
 template: "Hello {{name}}!"
 "#;
-        
+
         let file = TemplatesFile::from_yaml(yaml).unwrap();
         assert!(file.templates.contains_key("default"));
     }
+
+    #[test]
+    fn test_scripts_section() {
+        let yaml = r#"
+version: "1.0"
+templates:
+  job_complete:
+    description: Job completion message
+    template: "Done! {{summary}}"
+scripts:
+  shout:
+    description: dummy
+    scripts: ""
+"#;
+
+        // scripts is keyed by name -> rhai source, not a nested object, so
+        // the malformed entry above should be rejected rather than
+        // silently accepted.
+        assert!(TemplatesFile::from_yaml(yaml).is_err());
+
+        let yaml = r#"
+version: "1.0"
+templates:
+  job_complete:
+    description: Job completion message
+    template: "Done! {{summary}}"
+scripts:
+  shout: "params[0].to_upper()"
+"#;
+
+        let file = TemplatesFile::from_yaml(yaml).unwrap();
+        assert_eq!(file.scripts.get("shout").unwrap(), "params[0].to_upper()");
+    }
+
+    #[test]
+    fn test_partials_section() {
+        let yaml = r#"
+version: "1.0"
+templates:
+  verdict:
+    description: Verdict with an embedded score badge
+    template: "Verdict: {{> score_badge}}"
+partials:
+  score_badge: "[{{percent score}}]"
+"#;
+
+        let file = TemplatesFile::from_yaml(yaml).unwrap();
+        assert_eq!(file.partials.get("score_badge").unwrap(), "[{{percent score}}]");
+    }
+
+    #[test]
+    fn test_decorators_section() {
+        let yaml = r##"
+version: "1.0"
+templates:
+  report:
+    description: Pass-rate summary
+    template: "{{*totals}}{{summary.pass_rate}}"
+decorators:
+  totals: "#{ summary: #{ pass_rate: 1.0 } }"
+"##;
+
+        let file = TemplatesFile::from_yaml(yaml).unwrap();
+        assert_eq!(
+            file.decorators.get("totals").unwrap(),
+            "#{ summary: #{ pass_rate: 1.0 } }"
+        );
+    }
+
+    #[test]
+    fn test_load_dir_namespaces_by_path() {
+        let root = std::env::temp_dir().join(format!(
+            "tdln_out_load_dir_test_{}_{}",
+            std::process::id(),
+            "chunk3_5"
+        ));
+        std::fs::create_dir_all(root.join("out/verdict")).unwrap();
+
+        std::fs::write(root.join("out/verdict/fail.hbs"), "Failed: {{reason}}").unwrap();
+        std::fs::write(
+            root.join("out/greeting.yaml"),
+            "description: Greeting\ntemplate: \"Hello, {{name}}!\"\n",
+        ).unwrap();
+
+        let file = TemplatesFile::load_dir(root.to_str().unwrap()).unwrap();
+        assert_eq!(
+            file.templates.get("out/verdict/fail").unwrap().template,
+            "Failed: {{reason}}"
+        );
+        assert_eq!(
+            file.templates.get("out/greeting").unwrap().template,
+            "Hello, {{name}}!"
+        );
+
+        std::fs::remove_dir_all(&root).ok();
+    }
 }
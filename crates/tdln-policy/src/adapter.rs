@@ -0,0 +1,195 @@
+//! Loading/persisting a [`PolicySet`] to external storage, so a deployment
+//! can update policy without recompiling or restarting -- the same
+//! motivation as [`crate::config`]'s layered constraint documents, but for
+//! a whole [`PolicySet`] (including its compiled `rules`) rather than just
+//! `Constraints`.
+//!
+//! [`FileAdapter`] round-trips a [`PolicySet`] through JSON or TOML on
+//! disk, dispatching on extension the same way
+//! [`crate::config::ConstraintsDocument::load`] does. [`InMemoryAdapter`]
+//! holds one behind a `Mutex`, for tests and for deployments that push
+//! policy updates through some other channel (e.g. a control endpoint)
+//! instead of a file.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::policy_set::PolicySet;
+
+/// A source a [`PolicySet`] can be loaded from and saved back to. Callers
+/// that want hot-reload (e.g. on SIGHUP, or a control endpoint) call
+/// [`Self::load`] and atomically swap the result into an `Arc<PolicySet>`.
+pub trait PolicyAdapter {
+    fn load(&self) -> Result<PolicySet, AdapterError>;
+    fn save(&self, policy: &PolicySet) -> Result<(), AdapterError>;
+}
+
+/// Reads/writes a [`PolicySet`] as a JSON or TOML file on disk, dispatching
+/// on the file extension the same way
+/// [`crate::config::ConstraintsDocument::load`] does: `.json` is JSON,
+/// anything else (including `.toml`) is TOML.
+#[derive(Debug, Clone)]
+pub struct FileAdapter {
+    path: PathBuf,
+}
+
+impl FileAdapter {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn is_json(&self) -> bool {
+        self.path.extension().and_then(|e| e.to_str()) == Some("json")
+    }
+}
+
+impl PolicyAdapter for FileAdapter {
+    fn load(&self) -> Result<PolicySet, AdapterError> {
+        let src = std::fs::read_to_string(&self.path)
+            .map_err(|e| AdapterError::Io(self.path.display().to_string(), e.to_string()))?;
+        let policy: PolicySet = if self.is_json() {
+            serde_json::from_str(&src).map_err(|e| AdapterError::Parse(e.to_string()))?
+        } else {
+            toml::from_str(&src).map_err(|e| AdapterError::Parse(e.to_string()))?
+        };
+        validate(&policy)?;
+        Ok(policy)
+    }
+
+    fn save(&self, policy: &PolicySet) -> Result<(), AdapterError> {
+        validate(policy)?;
+        let rendered = if self.is_json() {
+            serde_json::to_string_pretty(policy).map_err(|e| AdapterError::Parse(e.to_string()))?
+        } else {
+            toml::to_string_pretty(policy).map_err(|e| AdapterError::Parse(e.to_string()))?
+        };
+        std::fs::write(&self.path, rendered)
+            .map_err(|e| AdapterError::Io(self.path.display().to_string(), e.to_string()))
+    }
+}
+
+/// Holds a [`PolicySet`] behind a `Mutex`, for tests or for deployments
+/// that update policy through some channel other than a file.
+#[derive(Debug, Default)]
+pub struct InMemoryAdapter {
+    policy: Mutex<Option<PolicySet>>,
+}
+
+impl InMemoryAdapter {
+    pub fn new(policy: PolicySet) -> Self {
+        Self { policy: Mutex::new(Some(policy)) }
+    }
+
+    /// An adapter with nothing stored yet -- [`Self::load`] errors until a
+    /// [`Self::save`] populates it.
+    pub fn empty() -> Self {
+        Self { policy: Mutex::new(None) }
+    }
+}
+
+impl PolicyAdapter for InMemoryAdapter {
+    fn load(&self) -> Result<PolicySet, AdapterError> {
+        self.policy
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+            .ok_or_else(|| AdapterError::Schema("no policy set has been stored yet".to_string()))
+    }
+
+    fn save(&self, policy: &PolicySet) -> Result<(), AdapterError> {
+        validate(policy)?;
+        *self.policy.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(policy.clone());
+        Ok(())
+    }
+}
+
+/// Reject a [`PolicySet`] whose `rules` don't satisfy the invariants the
+/// rest of the crate assumes -- today, that every rule id is unique, since
+/// `Violation::rule_id` is how callers identify which rule fired.
+fn validate(policy: &PolicySet) -> Result<(), AdapterError> {
+    let mut seen = std::collections::HashSet::new();
+    for rule in &policy.rules {
+        if !seen.insert(rule.id.as_str()) {
+            return Err(AdapterError::Schema(format!("duplicate rule id '{}'", rule.id)));
+        }
+    }
+    Ok(())
+}
+
+/// Error loading, saving, or validating a [`PolicySet`] through a
+/// [`PolicyAdapter`].
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AdapterError {
+    #[error("failed to read policy set file '{0}': {1}")]
+    Io(String, String),
+    #[error("failed to parse policy set: {0}")]
+    Parse(String),
+    #[error("policy set failed schema validation: {0}")]
+    Schema(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::PolicyRule;
+
+    fn sample_policy() -> PolicySet {
+        let mut policy = PolicySet::new("sample@1.0", "Sample Policy");
+        policy.rules.push(PolicyRule::new("r1", "Rule One"));
+        policy
+    }
+
+    #[test]
+    fn file_adapter_round_trips_through_json() {
+        let path = std::env::temp_dir().join(format!("tdln_policy_adapter_{}.json", std::process::id()));
+        let adapter = FileAdapter::new(&path);
+        adapter.save(&sample_policy()).unwrap();
+
+        let loaded = adapter.load().unwrap();
+        assert_eq!(loaded.id, "sample@1.0");
+        assert_eq!(loaded.rules.len(), 1);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn file_adapter_round_trips_through_toml() {
+        let path = std::env::temp_dir().join(format!("tdln_policy_adapter_{}.toml", std::process::id()));
+        let adapter = FileAdapter::new(&path);
+        adapter.save(&sample_policy()).unwrap();
+
+        let loaded = adapter.load().unwrap();
+        assert_eq!(loaded.id, "sample@1.0");
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn file_adapter_load_reports_io_error_for_a_missing_file() {
+        let path = std::env::temp_dir().join("tdln_policy_adapter_does_not_exist.json");
+        let err = FileAdapter::new(&path).load().unwrap_err();
+        assert!(matches!(err, AdapterError::Io(_, _)));
+    }
+
+    #[test]
+    fn in_memory_adapter_round_trips() {
+        let adapter = InMemoryAdapter::new(sample_policy());
+        let loaded = adapter.load().unwrap();
+        assert_eq!(loaded.id, "sample@1.0");
+    }
+
+    #[test]
+    fn in_memory_adapter_errors_before_anything_is_saved() {
+        let adapter = InMemoryAdapter::empty();
+        assert!(matches!(adapter.load(), Err(AdapterError::Schema(_))));
+    }
+
+    #[test]
+    fn duplicate_rule_ids_are_rejected_as_a_schema_error() {
+        let mut policy = sample_policy();
+        policy.rules.push(PolicyRule::new("r1", "Rule One Again"));
+
+        let adapter = InMemoryAdapter::empty();
+        assert!(matches!(adapter.save(&policy), Err(AdapterError::Schema(_))));
+    }
+}
@@ -1,12 +1,25 @@
 //! Audit trail generation
 //!
-//! Logs all policy decisions for compliance and debugging.
+//! Logs all policy decisions for compliance and debugging. Entries are
+//! hash-chained (`prev_hash`/`entry_hash`) so the log is tamper-evident:
+//! altering or deleting a logged entry breaks every hash after it, and
+//! [`AuditLog::verify_integrity`] walks the chain to prove it wasn't.
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::metrics::PolicyMetrics;
 use crate::risk::RiskLevel;
 use crate::policy_set::FullEvaluation;
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Conventional all-zero sentinel for "nothing came before this" -- the
+/// `prev_hash` a verifier should expect before the first entry in a chain
+/// that was never trimmed. Real entries use `None` internally; this is
+/// only for external systems anchoring [`AuditLog::head_hash`] that want a
+/// fixed starting value rather than special-casing an empty log.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
 /// An audit log entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEntry {
@@ -41,14 +54,21 @@ pub struct AuditEntry {
     /// Job ID if applicable
     #[serde(skip_serializing_if = "Option::is_none")]
     pub job_id: Option<String>,
-    
+
+    /// What produced this operation -- a repo, a file, a config path --
+    /// so entries from many inputs can be rolled up per-source by
+    /// [`AuditReport`] instead of only as one flat log.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
     /// Additional context
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<serde_json::Value>,
     
-    /// Violation details
+    /// Violation details, each with its suggested remediation (if the
+    /// originating [`crate::verdict::Violation`] carried one).
     #[serde(default)]
-    pub violations: Vec<String>,
+    pub violations: Vec<ViolationRecord>,
     
     /// Whether an override was used
     #[serde(default)]
@@ -57,6 +77,17 @@ pub struct AuditEntry {
     /// Override details if applicable
     #[serde(skip_serializing_if = "Option::is_none")]
     pub override_details: Option<OverrideRecord>,
+
+    /// Hash of the previous entry in the chain, or `None` for the first
+    /// entry (after trimming, that is the [`AuditEventType::Checkpoint`]).
+    /// Filled by [`AuditLog::log`] -- never set this by hand.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+
+    /// `sha256(canonical_json(self sans hash fields) || prev_hash)`. Filled
+    /// by [`AuditLog::log`]; left empty until then.
+    #[serde(default)]
+    pub entry_hash: String,
 }
 
 impl AuditEntry {
@@ -76,16 +107,22 @@ impl AuditEntry {
             risk_score: evaluation.risk_assessment.score,
             actor: None,
             job_id: None,
+            source: None,
             context: None,
             violations: evaluation.all_violations()
                 .iter()
-                .map(|v| v.description.clone())
+                .map(|v| ViolationRecord {
+                    description: v.description.clone(),
+                    remediation: v.remediation.clone(),
+                })
                 .collect(),
             override_used: false,
             override_details: None,
+            prev_hash: None,
+            entry_hash: String::new(),
         }
     }
-    
+
     /// Set the actor
     pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
         self.actor = Some(actor.into());
@@ -97,7 +134,13 @@ impl AuditEntry {
         self.job_id = Some(job_id.into());
         self
     }
-    
+
+    /// Set the source (repo/file/config) that produced this operation
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
     /// Add context
     pub fn with_context(mut self, context: serde_json::Value) -> Self {
         self.context = Some(context);
@@ -112,6 +155,18 @@ impl AuditEntry {
     }
 }
 
+/// A violation carried by an [`AuditEntry`], trimmed down from
+/// [`crate::verdict::Violation`] to the two fields an operator reading the
+/// audit trail actually needs: what went wrong, and how to fix it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViolationRecord {
+    /// What went wrong
+    pub description: String,
+    /// Suggested corrective action, if the originating violation carried one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+}
+
 /// Type of audit event
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -126,6 +181,11 @@ pub enum AuditEventType {
     ConfigChange,
     /// Policy update
     PolicyUpdate,
+    /// Synthetic entry standing in for a trimmed prefix of the log (see
+    /// [`AuditLog::with_trimming`]): its `entry_hash` is not recomputed
+    /// from its own content but carries forward the hash of the last
+    /// entry that was dropped, so the retained tail still chains.
+    Checkpoint,
 }
 
 /// Record of an override
@@ -142,6 +202,11 @@ pub struct OverrideRecord {
     pub expires_at: Option<u64>,
     /// Violations that were overridden
     pub overridden_violations: Vec<String>,
+    /// The role whose permission ceiling authorized this override, if it
+    /// was granted via [`crate::override_system::OverrideManager::resolve_role_permissions`]
+    /// rather than a direct requester/profile match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorizing_role: Option<String>,
 }
 
 /// Type of override
@@ -169,10 +234,25 @@ impl std::fmt::Display for OverrideType {
     }
 }
 
-/// Audit log collector
+/// Audit log collector. Append-only and hash-chained: entries are never
+/// edited in place, and [`AuditLog::log`] is the only way new ones are
+/// added.
 pub struct AuditLog {
     entries: Vec<AuditEntry>,
     max_entries: usize,
+    /// Whether exceeding `max_entries` actually drops the oldest entries.
+    /// Trimming a hash-chained log loses provable history, so it's gated
+    /// behind this flag rather than happening unconditionally the way the
+    /// old unchained `max_entries` cap did; `with_max_entries` still turns
+    /// it on, preserving that behavior for existing callers.
+    trim_enabled: bool,
+    /// `entry_hash` of the most recently logged entry, or `None` before
+    /// anything has been logged.
+    last_hash: Option<String>,
+    /// If installed via [`AuditLog::with_metrics`], incremented/set
+    /// alongside every [`AuditLog::log_evaluation`] so operators can
+    /// scrape live policy-decision counts instead of grepping the log.
+    metrics: Option<PolicyMetrics>,
 }
 
 impl AuditLog {
@@ -181,28 +261,136 @@ impl AuditLog {
         Self {
             entries: Vec::new(),
             max_entries: 10000,
+            trim_enabled: true,
+            last_hash: None,
+            metrics: None,
         }
     }
-    
-    /// Create with a custom max size
+
+    /// Create with a custom max size. Trimming is enabled, matching the
+    /// cap this constructor has always implied; call `with_trimming(false)`
+    /// to keep the bound for bookkeeping while retaining every entry.
     pub fn with_max_entries(max: usize) -> Self {
         Self {
             entries: Vec::new(),
             max_entries: max,
+            trim_enabled: true,
+            last_hash: None,
+            metrics: None,
         }
     }
-    
+
+    /// Enable or disable trimming once `max_entries` is exceeded.
+    pub fn with_trimming(mut self, enabled: bool) -> Self {
+        self.trim_enabled = enabled;
+        self
+    }
+
+    /// Install a [`PolicyMetrics`] to update alongside every
+    /// [`AuditLog::log_evaluation`]: incrementing `tdln_policy_verdicts_total`
+    /// and observing `tdln_policy_risk_score`, then resetting
+    /// `tdln_policy_block_rate` from the log's own [`AuditLog::stats`].
+    pub fn with_metrics(mut self, metrics: PolicyMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Conventional all-zero genesis sentinel external systems can anchor
+    /// alongside [`AuditLog::head_hash`] (see [`GENESIS_HASH`]).
+    pub fn genesis_hash(&self) -> &'static str {
+        GENESIS_HASH
+    }
+
+    /// Hash of the most recently logged entry (or [`GENESIS_HASH`] if the
+    /// log is empty), for external systems to anchor.
+    pub fn head_hash(&self) -> &str {
+        self.last_hash.as_deref().unwrap_or(GENESIS_HASH)
+    }
+
+    /// Walk the chain from the first entry, recomputing each `entry_hash`
+    /// and checking it against the stored value and the next entry's
+    /// `prev_hash`. Returns the index of the first broken link, if any.
+    pub fn verify_integrity(&self) -> Result<(), usize> {
+        let mut expected_prev: Option<String> = None;
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            if entry.prev_hash != expected_prev {
+                return Err(i);
+            }
+
+            if entry.event_type == AuditEventType::Checkpoint {
+                // A checkpoint's hash stands in for a dropped prefix and
+                // isn't recomputable from its own content -- trust it and
+                // carry it forward as the next entry's expected prev_hash.
+                expected_prev = Some(entry.entry_hash.clone());
+                continue;
+            }
+
+            if compute_entry_hash(entry) != entry.entry_hash {
+                return Err(i);
+            }
+
+            expected_prev = Some(entry.entry_hash.clone());
+        }
+
+        Ok(())
+    }
+
     /// Log an entry
-    pub fn log(&mut self, entry: AuditEntry) {
+    pub fn log(&mut self, mut entry: AuditEntry) {
+        entry.prev_hash = self.last_hash.clone();
+        entry.entry_hash = compute_entry_hash(&entry);
+        self.last_hash = Some(entry.entry_hash.clone());
         self.entries.push(entry);
-        
-        // Trim if over limit
-        if self.entries.len() > self.max_entries {
-            let drain_count = self.entries.len() - self.max_entries;
-            self.entries.drain(0..drain_count);
+
+        if self.trim_enabled && self.entries.len() > self.max_entries {
+            self.checkpoint_trim();
         }
     }
-    
+
+    /// Drop the oldest entries down to `max_entries`, replacing them with
+    /// a single [`AuditEventType::Checkpoint`] entry that carries forward
+    /// the hash of the last entry dropped, so `verify_integrity` still
+    /// succeeds on the retained tail.
+    fn checkpoint_trim(&mut self) {
+        let overflow = self.entries.len() - self.max_entries;
+        // Drop one extra entry to make room for the checkpoint that
+        // replaces it, so the log settles back at exactly `max_entries`.
+        let drain_count = overflow + 1;
+        if drain_count > self.entries.len() {
+            return;
+        }
+
+        let dropped_through = self.entries[drain_count - 1].entry_hash.clone();
+        self.entries.drain(0..drain_count);
+
+        self.entries.insert(
+            0,
+            AuditEntry {
+                id: generate_audit_id(),
+                timestamp: current_timestamp(),
+                event_type: AuditEventType::Checkpoint,
+                policy_id: String::new(),
+                operation: "checkpoint".to_string(),
+                verdict: String::new(),
+                risk_level: RiskLevel::Low,
+                risk_score: 0,
+                actor: None,
+                job_id: None,
+                source: None,
+                context: Some(serde_json::json!({
+                    "dropped_entries": drain_count,
+                    "dropped_through": dropped_through,
+                })),
+                violations: Vec::new(),
+                override_used: false,
+                override_details: None,
+                prev_hash: None,
+                entry_hash: dropped_through,
+            },
+        );
+    }
+
     /// Log an evaluation
     pub fn log_evaluation(
         &mut self,
@@ -212,6 +400,12 @@ impl AuditLog {
         let entry = AuditEntry::from_evaluation(evaluation, operation);
         let id = entry.id.clone();
         self.log(entry);
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_evaluation(evaluation.final_verdict.severity(), evaluation.risk_assessment.score);
+            metrics.set_block_rate(self.stats().block_rate);
+        }
+
         id
     }
     
@@ -255,9 +449,14 @@ impl AuditLog {
             .collect()
     }
     
-    /// Clear all entries
+    /// Clear all entries and reset the hash chain -- the next [`Self::log`]
+    /// call starts a fresh genesis (`prev_hash: None`) rather than chaining
+    /// onto the hash of an entry that no longer exists, which would make
+    /// [`Self::verify_integrity`] report the first post-clear entry as
+    /// broken.
     pub fn clear(&mut self) {
         self.entries.clear();
+        self.last_hash = None;
     }
     
     /// Export to JSON
@@ -272,7 +471,83 @@ impl AuditLog {
             .collect::<Vec<_>>()
             .join("\n")
     }
-    
+
+    /// Export blocked/warned entries as a SARIF 2.1.0 run, so policy
+    /// decisions surface in the same viewers as static-analysis findings.
+    /// Each distinct `policy_id`+violation pair becomes a
+    /// `reportingDescriptor` under `tool.driver.rules`; every violating
+    /// `AuditEntry` contributes one `result` per violation it carries.
+    pub fn to_sarif(&self) -> Result<String, serde_json::Error> {
+        let mut seen_rules = std::collections::HashSet::new();
+        let mut rules = Vec::new();
+        let mut results = Vec::new();
+
+        for entry in &self.entries {
+            let level = match entry.verdict.as_str() {
+                "Block" => "error",
+                "Warn" => "warning",
+                _ => continue,
+            };
+
+            for violation in &entry.violations {
+                if seen_rules.insert((entry.policy_id.clone(), violation.description.clone())) {
+                    rules.push(SarifRule {
+                        id: entry.policy_id.clone(),
+                        short_description: SarifText { text: entry.operation.clone() },
+                    });
+                }
+
+                let mut partial_fingerprints = HashMap::new();
+                partial_fingerprints.insert(
+                    "auditEntry/v1".to_string(),
+                    format!("{}:{}", entry.id, entry.policy_id),
+                );
+
+                let message = match &violation.remediation {
+                    Some(remediation) => format!("{} (remediation: {})", violation.description, remediation),
+                    None => violation.description.clone(),
+                };
+
+                results.push(SarifResult {
+                    rule_id: entry.policy_id.clone(),
+                    level: level.to_string(),
+                    message: SarifText { text: message },
+                    partial_fingerprints,
+                    properties: SarifProperties {
+                        risk_score: entry.risk_score,
+                        risk_level: entry.risk_level,
+                        actor: entry.actor.clone(),
+                        job_id: entry.job_id.clone(),
+                        override_used: entry.override_used,
+                    },
+                });
+            }
+        }
+
+        let sarif = SarifLog {
+            schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json".to_string(),
+            version: "2.1.0".to_string(),
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "atomic-agents-policy".to_string(),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        };
+
+        serde_json::to_string_pretty(&sarif)
+    }
+
+
+    /// Group entries by `source` into an [`AuditReport`] with a per-source
+    /// and overall pass/fail rollup.
+    pub fn report(&self) -> AuditReport {
+        AuditReport::from_entries(&self.entries)
+    }
+
     /// Get statistics
     pub fn stats(&self) -> AuditStats {
         let total = self.entries.len();
@@ -300,6 +575,52 @@ impl AuditLog {
             override_rate: if blocked > 0 { overridden as f64 / blocked as f64 } else { 0.0 },
         }
     }
+
+    /// Fraction of `actor`'s entries since `since_ms` that used an
+    /// override. `0.0` if the actor has no entries in the window, so
+    /// callers can threshold-compare without special-casing "no history".
+    pub fn override_rate_for_actor(&self, actor: &str, since_ms: u64) -> f64 {
+        let entries: Vec<&AuditEntry> = self.entries.iter()
+            .filter(|e| e.event_type != AuditEventType::Checkpoint)
+            .filter(|e| e.actor.as_deref() == Some(actor) && e.timestamp >= since_ms)
+            .collect();
+
+        if entries.is_empty() {
+            return 0.0;
+        }
+
+        let overridden = entries.iter().filter(|e| e.override_used).count();
+        overridden as f64 / entries.len() as f64
+    }
+
+    /// Number of `Block` verdicts recorded for `policy_id` since `since_ms`.
+    pub fn block_count_for_policy(&self, policy_id: &str, since_ms: u64) -> u32 {
+        self.entries.iter()
+            .filter(|e| e.policy_id == policy_id && e.timestamp >= since_ms && e.verdict == "Block")
+            .count() as u32
+    }
+
+    /// Entries for `actor` within the last `window_ms` milliseconds, most
+    /// recent activity first being the caller's concern -- returned in
+    /// log order like every other `entries_*` accessor.
+    pub fn recent_entries_for_actor(&self, actor: &str, window_ms: u64) -> Vec<&AuditEntry> {
+        let since = current_timestamp().saturating_sub(window_ms);
+        self.entries.iter()
+            .filter(|e| e.actor.as_deref() == Some(actor) && e.timestamp >= since)
+            .collect()
+    }
+
+    /// Roll the windowed history for `actor`/`policy_id` up into one
+    /// [`AuditSignals`], the shape `PolicySet::evaluate_with_signals`
+    /// expects.
+    pub fn signals_for_actor(&self, actor: &str, policy_id: &str, window_ms: u64) -> AuditSignals {
+        let since = current_timestamp().saturating_sub(window_ms);
+        AuditSignals {
+            override_rate: self.override_rate_for_actor(actor, since),
+            block_count: self.block_count_for_policy(policy_id, since),
+            recent_entry_count: self.recent_entries_for_actor(actor, window_ms).len(),
+        }
+    }
 }
 
 impl Default for AuditLog {
@@ -320,6 +641,197 @@ pub struct AuditStats {
     pub override_rate: f64,
 }
 
+/// Windowed aggregates derived from the audit trail, so a stateless
+/// [`crate::policy_set::PolicySet::evaluate`] can be handed a summary of
+/// recent history instead of the raw log. Built by
+/// [`AuditLog::signals_for_actor`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuditSignals {
+    /// Fraction (0.0-1.0) of the actor's recent entries that used an override.
+    pub override_rate: f64,
+    /// Number of recent `Block` verdicts recorded for the policy.
+    pub block_count: u32,
+    /// Total entries the actor has in the lookback window.
+    pub recent_entry_count: usize,
+}
+
+/// Entries grouped by [`AuditEntry::source`] with a per-source and overall
+/// pass/fail rollup, the way policy tools combine findings from many
+/// input files into one actionable report instead of a flat log. Built
+/// by [`AuditLog::report`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub status: ReportStatus,
+    pub sources: Vec<SourceReport>,
+}
+
+/// Pass/fail status of a [`SourceReport`] or the overall [`AuditReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportStatus {
+    Pass,
+    Fail,
+}
+
+impl std::fmt::Display for ReportStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ReportStatus::Pass => write!(f, "Pass"),
+            ReportStatus::Fail => write!(f, "Fail"),
+        }
+    }
+}
+
+/// Rollup of every entry sharing one [`AuditEntry::source`] (entries with
+/// no source recorded are grouped under `"unspecified"`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceReport {
+    pub source: String,
+    pub status: ReportStatus,
+    pub blocked: usize,
+    pub overridden: usize,
+    /// Distinct violation descriptions raised by this source's entries.
+    pub violating_rules: Vec<String>,
+}
+
+#[derive(Default)]
+struct SourceReportBuilder {
+    blocked: usize,
+    overridden: usize,
+    violating_rules: Vec<String>,
+}
+
+impl AuditReport {
+    /// Build a report from a log's entries, grouping by `source` and
+    /// skipping [`AuditEventType::Checkpoint`] entries (they don't
+    /// represent an operation, just a trimmed prefix).
+    pub fn from_entries(entries: &[AuditEntry]) -> Self {
+        let mut by_source: std::collections::BTreeMap<String, SourceReportBuilder> = Default::default();
+
+        for entry in entries {
+            if entry.event_type == AuditEventType::Checkpoint {
+                continue;
+            }
+
+            let key = entry.source.clone().unwrap_or_else(|| "unspecified".to_string());
+            let builder = by_source.entry(key).or_default();
+
+            if entry.verdict == "Block" {
+                builder.blocked += 1;
+            }
+            if entry.override_used {
+                builder.overridden += 1;
+            }
+            for violation in &entry.violations {
+                if !builder.violating_rules.contains(&violation.description) {
+                    builder.violating_rules.push(violation.description.clone());
+                }
+            }
+        }
+
+        let sources: Vec<SourceReport> = by_source
+            .into_iter()
+            .map(|(source, b)| SourceReport {
+                status: if b.blocked > 0 { ReportStatus::Fail } else { ReportStatus::Pass },
+                source,
+                blocked: b.blocked,
+                overridden: b.overridden,
+                violating_rules: b.violating_rules,
+            })
+            .collect();
+
+        let status = if sources.iter().any(|s| s.status == ReportStatus::Fail) {
+            ReportStatus::Fail
+        } else {
+            ReportStatus::Pass
+        };
+
+        Self { status, sources }
+    }
+
+    /// Export to JSON
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Render a compact table: one row per source plus the overall status.
+    pub fn render_table(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!(
+            "{:<24} {:<6} {:>8} {:>10}\n",
+            "source", "status", "blocked", "overridden"
+        ));
+        for s in &self.sources {
+            out.push_str(&format!(
+                "{:<24} {:<6} {:>8} {:>10}\n",
+                s.source, s.status, s.blocked, s.overridden
+            ));
+        }
+        out.push_str(&format!("\noverall: {}\n", self.status));
+        out
+    }
+}
+
+/// Minimal SARIF 2.1.0 log: one `run` carrying the rules and results
+/// [`AuditLog::to_sarif`] builds from blocked/warned entries.
+#[derive(Debug, Clone, Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: String,
+    version: String,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifDriver {
+    name: String,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifRule {
+    id: String,
+    #[serde(rename = "shortDescription")]
+    short_description: SarifText,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifText {
+    text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    level: String,
+    message: SarifText,
+    #[serde(rename = "partialFingerprints")]
+    partial_fingerprints: HashMap<String, String>,
+    properties: SarifProperties,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SarifProperties {
+    risk_score: u32,
+    risk_level: RiskLevel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    job_id: Option<String>,
+    override_used: bool,
+}
+
 fn generate_audit_id() -> String {
     use std::sync::atomic::{AtomicU64, Ordering};
     static COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -336,12 +848,56 @@ fn current_timestamp() -> u64 {
         .unwrap_or(0)
 }
 
+/// `sha256(canonical_json(entry sans hash fields) || prev_hash)`. `entry`'s
+/// own `prev_hash`/`entry_hash` fields are ignored (whatever they're
+/// currently set to), so this can be called both while building a new
+/// entry and while re-verifying one already in the log.
+fn compute_entry_hash(entry: &AuditEntry) -> String {
+    let mut value = serde_json::to_value(entry).expect("AuditEntry always serializes");
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove("prev_hash");
+        obj.remove("entry_hash");
+    }
+    let canonical = canonical_json(&value);
+
+    let mut hasher = Sha256::new();
+    hasher.update(canonical.as_bytes());
+    hasher.update(entry.prev_hash.as_deref().unwrap_or("").as_bytes());
+    encode_hex(&hasher.finalize())
+}
+
+/// Deterministic JSON rendering with object keys sorted, so semantically
+/// identical entries hash the same regardless of field order.
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let entries: Vec<String> = keys
+                .into_iter()
+                .map(|k| format!("{}:{}", serde_json::to_string(k).unwrap(), canonical_json(&map[k])))
+                .collect();
+            format!("{{{}}}", entries.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            let entries: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", entries.join(","))
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::policy_set::PolicySet;
     use crate::rule::RuleContext;
     use crate::constraints::OperationMetrics;
+    use crate::verdict::VerdictSeverity;
 
     #[test]
     fn test_audit_log() {
@@ -409,5 +965,257 @@ mod tests {
         // Should only keep last 5
         assert_eq!(log.entries().len(), 5);
     }
+
+    #[test]
+    fn chain_verifies_for_an_untouched_log() {
+        let mut log = AuditLog::new();
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("test");
+        let metrics = OperationMetrics::new();
+
+        for i in 0..5 {
+            let evaluation = policy.evaluate(&context, &metrics);
+            log.log_evaluation(&evaluation, format!("op{}", i));
+        }
+
+        assert_eq!(log.verify_integrity(), Ok(()));
+        assert_eq!(log.head_hash(), log.entries().last().unwrap().entry_hash);
+    }
+
+    #[test]
+    fn tampering_with_an_entry_is_detected_at_its_index() {
+        let mut log = AuditLog::new();
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("test");
+        let metrics = OperationMetrics::new();
+
+        for i in 0..3 {
+            let evaluation = policy.evaluate(&context, &metrics);
+            log.log_evaluation(&evaluation, format!("op{}", i));
+        }
+
+        log.entries[1].operation = "tampered".to_string();
+
+        assert_eq!(log.verify_integrity(), Err(1));
+    }
+
+    #[test]
+    fn with_trimming_false_keeps_every_entry_past_max_entries() {
+        let mut log = AuditLog::with_max_entries(5).with_trimming(false);
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("test");
+        let metrics = OperationMetrics::new();
+
+        for _ in 0..10 {
+            let evaluation = policy.evaluate(&context, &metrics);
+            log.log_evaluation(&evaluation, "op");
+        }
+
+        assert_eq!(log.entries().len(), 10);
+        assert_eq!(log.verify_integrity(), Ok(()));
+    }
+
+    #[test]
+    fn trimmed_log_still_verifies_via_its_checkpoint_entry() {
+        let mut log = AuditLog::with_max_entries(5);
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("test");
+        let metrics = OperationMetrics::new();
+
+        for i in 0..10 {
+            let evaluation = policy.evaluate(&context, &metrics);
+            log.log_evaluation(&evaluation, format!("op{}", i));
+        }
+
+        assert_eq!(log.entries()[0].event_type, AuditEventType::Checkpoint);
+        assert_eq!(log.verify_integrity(), Ok(()));
+    }
+
+    #[test]
+    fn to_sarif_emits_a_result_per_violation_and_dedupes_rules() {
+        let mut log = AuditLog::new();
+        let policy = PolicySet::mechanic();
+
+        let good_context = RuleContext::new("bug_fix").with_files(2);
+        let good_metrics = OperationMetrics::new().with_files(2, vec![]).with_tests(true);
+        log.log_evaluation(&policy.evaluate(&good_context, &good_metrics), "op_ok");
+
+        let bad_context = RuleContext::new("feature").with_files(20);
+        let bad_metrics = OperationMetrics::new().with_files(20, vec![]);
+        let eval = policy.evaluate(&bad_context, &bad_metrics);
+        let violation_count = eval.all_violations().len();
+        log.log_evaluation(&eval, "op_bad");
+
+        let sarif_text = log.to_sarif().unwrap();
+        let sarif: serde_json::Value = serde_json::from_str(&sarif_text).unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), violation_count);
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["ruleId"], "mechanic@1.0");
+
+        let rules = sarif["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), violation_count);
+    }
+
+    #[test]
+    fn report_rolls_up_pass_fail_per_source_and_overall() {
+        let mut log = AuditLog::new();
+        let policy = PolicySet::mechanic();
+
+        let good_context = RuleContext::new("bug_fix").with_files(2);
+        let good_metrics = OperationMetrics::new().with_files(2, vec![]).with_tests(true);
+        let good_entry = AuditEntry::from_evaluation(&policy.evaluate(&good_context, &good_metrics), "op_ok")
+            .with_source("repo-a");
+        log.log(good_entry);
+
+        let bad_context = RuleContext::new("feature").with_files(20);
+        let bad_metrics = OperationMetrics::new().with_files(20, vec![]);
+        let bad_entry = AuditEntry::from_evaluation(&policy.evaluate(&bad_context, &bad_metrics), "op_bad")
+            .with_source("repo-b");
+        log.log(bad_entry);
+
+        let report = log.report();
+
+        assert_eq!(report.status, ReportStatus::Fail);
+        assert_eq!(report.sources.len(), 2);
+
+        let repo_a = report.sources.iter().find(|s| s.source == "repo-a").unwrap();
+        assert_eq!(repo_a.status, ReportStatus::Pass);
+        assert_eq!(repo_a.blocked, 0);
+
+        let repo_b = report.sources.iter().find(|s| s.source == "repo-b").unwrap();
+        assert_eq!(repo_b.status, ReportStatus::Fail);
+        assert_eq!(repo_b.blocked, 1);
+        assert!(!repo_b.violating_rules.is_empty());
+
+        let table = report.render_table();
+        assert!(table.contains("repo-a"));
+        assert!(table.contains("repo-b"));
+        assert!(table.contains("overall: Fail"));
+    }
+
+    #[test]
+    fn report_groups_entries_with_no_source_as_unspecified() {
+        let mut log = AuditLog::new();
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("bug_fix").with_files(2);
+        let metrics = OperationMetrics::new().with_files(2, vec![]).with_tests(true);
+        log.log_evaluation(&policy.evaluate(&context, &metrics), "op");
+
+        let report = log.report();
+
+        assert_eq!(report.sources.len(), 1);
+        assert_eq!(report.sources[0].source, "unspecified");
+    }
+
+    #[test]
+    fn override_rate_for_actor_counts_only_that_actors_recent_entries() {
+        let mut log = AuditLog::new();
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("bug_fix").with_files(2);
+        let metrics = OperationMetrics::new().with_files(2, vec![]).with_tests(true);
+        let evaluation = policy.evaluate(&context, &metrics);
+
+        log.log(AuditEntry::from_evaluation(&evaluation, "op_1").with_actor("alice"));
+        log.log(AuditEntry::from_evaluation(&evaluation, "op_2").with_actor("alice").with_override(
+            OverrideRecord {
+                override_type: OverrideType::ManualApproval,
+                authorized_by: "lead".to_string(),
+                reason: "urgent".to_string(),
+                expires_at: None,
+                overridden_violations: vec![],
+                authorizing_role: None,
+            },
+        ));
+        log.log(AuditEntry::from_evaluation(&evaluation, "op_3").with_actor("bob"));
+
+        assert_eq!(log.override_rate_for_actor("alice", 0), 0.5);
+        assert_eq!(log.override_rate_for_actor("carol", 0), 0.0);
+    }
+
+    #[test]
+    fn block_count_for_policy_only_counts_block_verdicts() {
+        let mut log = AuditLog::new();
+        let policy = PolicySet::mechanic();
+        let blocked_context = RuleContext::new("feature").with_files(20).with_lines(500);
+        let blocked_metrics = OperationMetrics::new().with_files(20, vec![]).with_lines(500);
+        let allowed_context = RuleContext::new("bug_fix").with_files(2);
+        let allowed_metrics = OperationMetrics::new().with_files(2, vec![]).with_tests(true);
+
+        log.log_evaluation(&policy.evaluate(&blocked_context, &blocked_metrics), "op_1");
+        log.log_evaluation(&policy.evaluate(&blocked_context, &blocked_metrics), "op_2");
+        log.log_evaluation(&policy.evaluate(&allowed_context, &allowed_metrics), "op_3");
+
+        assert_eq!(log.block_count_for_policy(&policy.id, 0), 2);
+        assert_eq!(log.block_count_for_policy("other@1.0", 0), 0);
+    }
+
+    #[test]
+    fn signals_for_actor_rolls_up_override_rate_and_block_count() {
+        let mut log = AuditLog::new();
+        let policy = PolicySet::mechanic();
+        let blocked_context = RuleContext::new("feature").with_files(20).with_lines(500);
+        let blocked_metrics = OperationMetrics::new().with_files(20, vec![]).with_lines(500);
+        let blocked_eval = policy.evaluate(&blocked_context, &blocked_metrics);
+
+        log.log(AuditEntry::from_evaluation(&blocked_eval, "op_1").with_actor("alice"));
+        log.log(AuditEntry::from_evaluation(&blocked_eval, "op_2").with_actor("alice").with_override(
+            OverrideRecord {
+                override_type: OverrideType::ManualApproval,
+                authorized_by: "lead".to_string(),
+                reason: "urgent".to_string(),
+                expires_at: None,
+                overridden_violations: vec![],
+                authorizing_role: None,
+            },
+        ));
+
+        let signals = log.signals_for_actor("alice", &policy.id, 3_600_000);
+
+        assert_eq!(signals.override_rate, 0.5);
+        assert_eq!(signals.block_count, 2);
+        assert_eq!(signals.recent_entry_count, 2);
+    }
+
+    #[test]
+    fn log_evaluation_updates_installed_metrics() {
+        let metrics = PolicyMetrics::new();
+        let mut log = AuditLog::new().with_metrics(metrics.clone());
+        let policy = PolicySet::mechanic();
+
+        let good_context = RuleContext::new("bug_fix").with_files(2);
+        let good_metrics = OperationMetrics::new().with_files(2, vec![]).with_tests(true);
+        log.log_evaluation(&policy.evaluate(&good_context, &good_metrics), "op1");
+
+        let bad_context = RuleContext::new("feature").with_files(20);
+        let bad_metrics = OperationMetrics::new().with_files(20, vec![]);
+        log.log_evaluation(&policy.evaluate(&bad_context, &bad_metrics), "op2");
+
+        let total: f64 = [VerdictSeverity::Allow, VerdictSeverity::Warn, VerdictSeverity::Block]
+            .into_iter()
+            .map(|v| metrics.verdict_count(v))
+            .sum();
+        assert_eq!(total, 2.0);
+        assert_eq!(metrics.verdict_count(VerdictSeverity::Block), log.stats().blocked as f64);
+    }
+
+    #[test]
+    fn clear_resets_the_hash_chain_so_the_next_entry_starts_a_fresh_genesis() {
+        let mut log = AuditLog::new();
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("bug_fix").with_files(2);
+        let metrics = OperationMetrics::new().with_files(2, vec![]).with_tests(true);
+        log.log_evaluation(&policy.evaluate(&context, &metrics), "op1");
+
+        log.clear();
+        assert_eq!(log.head_hash(), log.genesis_hash());
+
+        log.log_evaluation(&policy.evaluate(&context, &metrics), "op2");
+
+        assert_eq!(log.entries()[0].prev_hash, None);
+        assert_eq!(log.verify_integrity(), Ok(()));
+    }
 }
 
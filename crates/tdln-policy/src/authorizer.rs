@@ -0,0 +1,254 @@
+//! Authorization gate: who may run or approve an operation.
+//!
+//! [`RiskAssessment`] scores *what* risk an operation carries; it says
+//! nothing about *who* is allowed to run or approve it. `Authorizer` closes
+//! that gap: it maps a [`Principal`]'s role to a [`Grant`] -- an overall risk
+//! ceiling plus optional per-[`RiskCategory`] ceilings -- and checks an
+//! assessment's factors against it.
+
+use std::collections::HashMap;
+
+use crate::risk::{RiskAssessment, RiskCategory, RiskLevel};
+
+/// A caller attempting to run or approve an operation.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    /// Identity of the caller (e.g. username or account id).
+    pub id: String,
+    /// Role the caller is acting in, looked up in an [`Authorizer`]'s grants.
+    pub role: String,
+}
+
+impl Principal {
+    /// Create a new principal.
+    pub fn new(id: impl Into<String>, role: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            role: role.into(),
+        }
+    }
+}
+
+/// What a role is permitted to do.
+///
+/// `max_risk_level` is the ceiling for any [`RiskCategory`] not named in
+/// `category_limits`; an entry in `category_limits` overrides it for that
+/// one category, letting a role be trusted broadly but restricted on a
+/// specific kind of factor (e.g. a `junior` role capped at `Medium` overall
+/// but only `Low` for `Destructive` factors).
+#[derive(Debug, Clone)]
+pub struct Grant {
+    /// Risk ceiling for categories not listed in `category_limits`.
+    pub max_risk_level: RiskLevel,
+    /// Per-category risk ceilings that override `max_risk_level`.
+    pub category_limits: HashMap<RiskCategory, RiskLevel>,
+}
+
+impl Grant {
+    /// A grant with no category-specific overrides.
+    pub fn up_to(max_risk_level: RiskLevel) -> Self {
+        Self {
+            max_risk_level,
+            category_limits: HashMap::new(),
+        }
+    }
+
+    /// Restrict a single category to a lower (or higher) ceiling than
+    /// `max_risk_level`.
+    pub fn limit(mut self, category: RiskCategory, max_risk_level: RiskLevel) -> Self {
+        self.category_limits.insert(category, max_risk_level);
+        self
+    }
+
+    fn ceiling_for(&self, category: RiskCategory) -> RiskLevel {
+        self.category_limits
+            .get(&category)
+            .copied()
+            .unwrap_or(self.max_risk_level)
+    }
+}
+
+impl Default for Grant {
+    /// No role registered, no category overrides: nothing above `Low`.
+    fn default() -> Self {
+        Self::up_to(RiskLevel::Low)
+    }
+}
+
+/// Why an [`Authorizer`] refused to let a principal proceed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenyReason {
+    role: String,
+    level: RiskLevel,
+    category: Option<RiskCategory>,
+}
+
+impl std::fmt::Display for DenyReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.category {
+            Some(category) => write!(
+                f,
+                "role={} may not perform {:?} operations above {}",
+                self.role, category, self.level
+            ),
+            None => write!(
+                f,
+                "role={} may not perform operations above {}",
+                self.role, self.level
+            ),
+        }
+    }
+}
+
+/// A role -> [`Grant`] policy, checked against a [`RiskAssessment`].
+#[derive(Debug, Clone, Default)]
+pub struct Authorizer {
+    grants: HashMap<String, Grant>,
+    default_grant: Grant,
+}
+
+impl Authorizer {
+    /// Create an authorizer with no registered roles -- every role falls
+    /// back to `default_grant` (itself [`Grant::default`] unless overridden
+    /// with [`Self::with_default_grant`]).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the [`Grant`] a role is held to.
+    pub fn grant(mut self, role: impl Into<String>, grant: Grant) -> Self {
+        self.grants.insert(role.into(), grant);
+        self
+    }
+
+    /// Override the grant used for roles with no explicit registration.
+    pub fn with_default_grant(mut self, grant: Grant) -> Self {
+        self.default_grant = grant;
+        self
+    }
+
+    /// Check whether `principal` may proceed with an operation that scored
+    /// `assessment`. Denies on the first factor whose category exceeds the
+    /// principal's ceiling for that category; if every factor is within
+    /// bounds but the overall level still isn't, denies on the level alone.
+    pub fn enforce(
+        &self,
+        principal: &Principal,
+        assessment: &RiskAssessment,
+    ) -> Result<(), DenyReason> {
+        let grant = self
+            .grants
+            .get(&principal.role)
+            .unwrap_or(&self.default_grant);
+
+        for factor in &assessment.factors {
+            let ceiling = grant.ceiling_for(factor.category);
+            if assessment.level > ceiling {
+                return Err(DenyReason {
+                    role: principal.role.clone(),
+                    level: ceiling,
+                    category: Some(factor.category),
+                });
+            }
+        }
+
+        if assessment.level > grant.max_risk_level {
+            return Err(DenyReason {
+                role: principal.role.clone(),
+                level: grant.max_risk_level,
+                category: None,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::{calculate_risk, RiskFactor, RiskInput};
+
+    fn production_assessment() -> RiskAssessment {
+        let input = RiskInput::new("feature").with_files(3).production();
+        calculate_risk(&input)
+    }
+
+    #[test]
+    fn unregistered_role_is_capped_at_low() {
+        let authorizer = Authorizer::new();
+        let principal = Principal::new("alice", "junior");
+        let assessment = RiskAssessment::new(vec![RiskFactor::new(
+            "refactor",
+            40,
+            "moderate change",
+            RiskCategory::Scope,
+        )]);
+
+        let err = authorizer.enforce(&principal, &assessment).unwrap_err();
+        assert_eq!(err.category, Some(RiskCategory::Scope));
+        assert!(err.to_string().contains("role=junior"));
+    }
+
+    #[test]
+    fn role_with_overall_ceiling_passes_within_bounds() {
+        let authorizer = Authorizer::new().grant("senior-engineer", Grant::up_to(RiskLevel::High));
+        let principal = Principal::new("bob", "senior-engineer");
+        let assessment = RiskAssessment::new(vec![RiskFactor::new(
+            "refactor",
+            40,
+            "moderate change",
+            RiskCategory::Scope,
+        )]);
+
+        assert!(authorizer.enforce(&principal, &assessment).is_ok());
+    }
+
+    #[test]
+    fn category_limit_overrides_overall_ceiling() {
+        // A junior may do up to High risk in general, but Destructive work
+        // is capped at Medium regardless.
+        let authorizer = Authorizer::new().grant(
+            "junior",
+            Grant::up_to(RiskLevel::High).limit(RiskCategory::Destructive, RiskLevel::Medium),
+        );
+        let principal = Principal::new("carol", "junior");
+
+        let assessment = RiskAssessment::new(vec![RiskFactor::new(
+            "destructive",
+            70,
+            "deletes files",
+            RiskCategory::Destructive,
+        )]);
+        assert_eq!(assessment.level, RiskLevel::High);
+
+        let err = authorizer.enforce(&principal, &assessment).unwrap_err();
+        assert_eq!(err.level, RiskLevel::Medium);
+        assert_eq!(err.category, Some(RiskCategory::Destructive));
+        assert_eq!(
+            err.to_string(),
+            "role=junior may not perform Destructive operations above MEDIUM"
+        );
+    }
+
+    #[test]
+    fn only_release_engineer_may_proceed_when_production_is_targeted() {
+        let authorizer = Authorizer::new()
+            .with_default_grant(
+                Grant::up_to(RiskLevel::High).limit(RiskCategory::Environment, RiskLevel::Low),
+            )
+            .grant("release-engineer", Grant::up_to(RiskLevel::Critical));
+
+        let assessment = production_assessment();
+        assert!(assessment
+            .factors
+            .iter()
+            .any(|f| f.category == RiskCategory::Environment));
+
+        let other = Principal::new("dan", "engineer");
+        assert!(authorizer.enforce(&other, &assessment).is_err());
+
+        let release_engineer = Principal::new("erin", "release-engineer");
+        assert!(authorizer.enforce(&release_engineer, &assessment).is_ok());
+    }
+}
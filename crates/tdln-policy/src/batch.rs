@@ -0,0 +1,216 @@
+//! Rolling up many [`RiskAssessment`]s into one combined report.
+//!
+//! A multi-step plan runs `RiskCalculator::calculate` once per operation, so
+//! callers end up with one assessment per step and no single place to ask
+//! "how risky is the plan as a whole, and why". `BatchAssessment` ingests a
+//! labeled collection of assessments and produces that combined view.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::risk::{RiskAssessment, RiskCategory, RiskLevel};
+
+/// One assessment in a batch, keyed by the id of the operation it scored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchMember {
+    /// Identifier of the operation this assessment belongs to (e.g. a step
+    /// index or a file path).
+    pub operation_id: String,
+    /// The assessment itself.
+    pub assessment: RiskAssessment,
+}
+
+impl BatchMember {
+    /// Create a new batch member.
+    pub fn new(operation_id: impl Into<String>, assessment: RiskAssessment) -> Self {
+        Self {
+            operation_id: operation_id.into(),
+            assessment,
+        }
+    }
+}
+
+/// A single factor rolled up into a batch report, tagged with which
+/// operation it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFactor {
+    /// The operation this factor was contributed by.
+    pub operation_id: String,
+    /// Name of the factor (see [`crate::risk::RiskFactor::name`]).
+    pub name: String,
+    /// Impact on risk score.
+    pub impact: u32,
+    /// Category of this factor.
+    pub category: RiskCategory,
+}
+
+/// Combined view over many [`RiskAssessment`]s.
+///
+/// The overall level is driven by the single highest-scoring member, not a
+/// sum across members -- a plan with one `Critical` step and nine `Low`
+/// steps is `Critical`, not an averaged-down `Medium`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchAssessment {
+    /// Overall risk level, equal to the level of `worst_operation_id`.
+    pub level: RiskLevel,
+    /// Operation id of the single highest-scoring member.
+    pub worst_operation_id: String,
+    /// Score of `worst_operation_id`.
+    pub worst_score: u32,
+    /// Deduplicated union of every member's recommendations, in first-seen
+    /// order across members.
+    pub recommendations: Vec<String>,
+    /// Sum of impact per [`RiskCategory`] across every member.
+    pub category_subtotals: HashMap<RiskCategory, u32>,
+    /// Per-operation summary, keyed by operation id, for serialization.
+    pub members: HashMap<String, RiskAssessment>,
+}
+
+impl BatchAssessment {
+    /// Combine a labeled collection of assessments into one batch report.
+    ///
+    /// Returns `None` if `members` is empty -- there is no meaningful
+    /// "worst" member of an empty batch.
+    pub fn new(members: Vec<BatchMember>) -> Option<Self> {
+        let worst = members.iter().max_by_key(|m| m.assessment.score)?;
+        let level = worst.assessment.level;
+        let worst_operation_id = worst.operation_id.clone();
+        let worst_score = worst.assessment.score;
+
+        let mut recommendations = Vec::new();
+        let mut category_subtotals: HashMap<RiskCategory, u32> = HashMap::new();
+        let mut by_id = HashMap::new();
+
+        for member in members {
+            for rec in &member.assessment.recommendations {
+                if !recommendations.contains(rec) {
+                    recommendations.push(rec.clone());
+                }
+            }
+            for factor in &member.assessment.factors {
+                *category_subtotals.entry(factor.category).or_insert(0) += factor.impact;
+            }
+            by_id.insert(member.operation_id, member.assessment);
+        }
+
+        Some(Self {
+            level,
+            worst_operation_id,
+            worst_score,
+            recommendations,
+            category_subtotals,
+            members: by_id,
+        })
+    }
+
+    /// The top `n` contributing factors across the whole batch, sorted by
+    /// impact descending, each tagged with the operation id it came from.
+    pub fn worst_offenders(&self, n: usize) -> Vec<BatchFactor> {
+        let mut factors: Vec<BatchFactor> = self
+            .members
+            .iter()
+            .flat_map(|(operation_id, assessment)| {
+                assessment.factors.iter().map(move |f| BatchFactor {
+                    operation_id: operation_id.clone(),
+                    name: f.name.clone(),
+                    impact: f.impact,
+                    category: f.category,
+                })
+            })
+            .collect();
+
+        factors.sort_by(|a, b| b.impact.cmp(&a.impact));
+        factors.truncate(n);
+        factors
+    }
+
+    /// Number of operations rolled up into this batch.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Whether this batch has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::{calculate_risk, RiskInput};
+
+    fn member(operation_id: &str, input: RiskInput) -> BatchMember {
+        BatchMember::new(operation_id, calculate_risk(&input))
+    }
+
+    #[test]
+    fn empty_batch_has_no_assessment() {
+        assert!(BatchAssessment::new(vec![]).is_none());
+    }
+
+    #[test]
+    fn overall_level_follows_the_worst_member_not_a_sum() {
+        let members = vec![
+            member("step_1", RiskInput::new("analyze").with_files(1)),
+            member("step_2", RiskInput::new("analyze").with_files(1)),
+            member(
+                "step_3",
+                RiskInput::new("file_delete")
+                    .with_files(25)
+                    .with_lines(1000)
+                    .destructive()
+                    .production()
+                    .with_affected_paths(["migrations/0001_init.sql"]),
+            ),
+        ];
+
+        let batch = BatchAssessment::new(members).unwrap();
+        assert_eq!(batch.level, RiskLevel::Critical);
+        assert_eq!(batch.worst_operation_id, "step_3");
+        assert_eq!(batch.len(), 3);
+    }
+
+    #[test]
+    fn recommendations_are_deduplicated_across_members() {
+        let members = vec![
+            member("a", RiskInput::new("feature").with_files(15).with_lines(300)),
+            member("b", RiskInput::new("feature").with_files(15).with_lines(300)),
+        ];
+
+        let batch = BatchAssessment::new(members).unwrap();
+        let unique: std::collections::HashSet<_> = batch.recommendations.iter().collect();
+        assert_eq!(unique.len(), batch.recommendations.len());
+    }
+
+    #[test]
+    fn category_subtotals_sum_across_members() {
+        let members = vec![
+            member("a", RiskInput::new("analyze").destructive()),
+            member("b", RiskInput::new("analyze").destructive()),
+        ];
+
+        let batch = BatchAssessment::new(members).unwrap();
+        assert_eq!(
+            batch.category_subtotals.get(&RiskCategory::Destructive),
+            Some(&40)
+        );
+    }
+
+    #[test]
+    fn worst_offenders_are_sorted_by_impact_and_tagged_with_operation_id() {
+        let members = vec![
+            member(
+                "a",
+                RiskInput::new("file_delete").with_files(25).destructive(),
+            ),
+            member("b", RiskInput::new("analyze").with_files(1)),
+        ];
+
+        let batch = BatchAssessment::new(members).unwrap();
+        let top = batch.worst_offenders(1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].operation_id, "a");
+    }
+}
@@ -0,0 +1,193 @@
+//! Layered constraint configuration: named profiles plus environment overrides.
+//!
+//! [`Constraints::mechanic_mode`] and [`Constraints::genius_mode`] are
+//! hardcoded presets, so real deployments that want their own profiles (or
+//! to tweak a profile per environment -- local vs. staging vs. production)
+//! need a recompile. A [`ConstraintsDocument`] holds a `[profiles.<name>]`
+//! table of base [`Constraints`] plus an `[env.<name>]` table of overlays;
+//! [`Constraints::from_profile`] resolves a profile's effective constraints
+//! by folding its environment overlay on top via [`Constraints::merge`],
+//! the same precedence rule `merge` already uses everywhere else. The
+//! camelCase serde names already on `Constraints` (`maxFiles`,
+//! `mustPassTests`, `forbiddenPatterns`, ...) are reused as-is, so the same
+//! keys work whether the document is written as TOML or JSON -- the two
+//! built-in modes become just two profiles a deployment can ship in such a
+//! file instead of recompiling to change.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::constraints::Constraints;
+
+/// A loaded configuration document: named base profiles plus named
+/// environment overlays.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ConstraintsDocument {
+    /// Named base constraint sets, e.g. `mechanic`, `genius`, or anything a
+    /// deployment defines.
+    #[serde(default)]
+    pub profiles: HashMap<String, Constraints>,
+    /// Named overlays, folded on top of a profile via [`Constraints::merge`].
+    #[serde(default)]
+    pub env: HashMap<String, Constraints>,
+}
+
+impl ConstraintsDocument {
+    /// Parse a document from source text. `is_json` selects JSON; otherwise
+    /// the text is parsed as TOML.
+    pub fn parse(src: &str, is_json: bool) -> Result<Self, ConfigError> {
+        if is_json {
+            serde_json::from_str(src).map_err(|e| ConfigError::Parse(e.to_string()))
+        } else {
+            toml::from_str(src).map_err(|e| ConfigError::Parse(e.to_string()))
+        }
+    }
+
+    /// Read and parse a document from disk, dispatching on the file
+    /// extension: `.json` is parsed as JSON, anything else (including
+    /// `.toml`) as TOML.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let src = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::Io(path.display().to_string(), e.to_string()))?;
+        let is_json = path.extension().and_then(|e| e.to_str()) == Some("json");
+        Self::parse(&src, is_json)
+    }
+
+    /// Resolve `profile`'s effective constraints: the named base profile
+    /// with `env`'s overlay (if `env` is given and a matching overlay
+    /// exists) folded on top via [`Constraints::merge`]. A requested `env`
+    /// with no matching overlay is not an error -- the base profile is
+    /// returned unchanged.
+    pub fn resolve(&self, profile: &str, env: Option<&str>) -> Result<Constraints, ConfigError> {
+        let base = self
+            .profiles
+            .get(profile)
+            .cloned()
+            .ok_or_else(|| ConfigError::ProfileNotFound(profile.to_string()))?;
+
+        Ok(match env.and_then(|name| self.env.get(name)) {
+            Some(overlay) => base.merge(overlay),
+            None => base,
+        })
+    }
+}
+
+/// Error loading or resolving a constraint configuration document.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConfigError {
+    #[error("failed to parse constraints config: {0}")]
+    Parse(String),
+    #[error("failed to read constraints config file '{0}': {1}")]
+    Io(String, String),
+    #[error("profile '{0}' not found in constraints config")]
+    ProfileNotFound(String),
+}
+
+impl Constraints {
+    /// Load `path`, resolve `profile` (folding `env`'s overlay on top, if
+    /// given), and return the effective constraint set. Equivalent to
+    /// `ConstraintsDocument::load(path)?.resolve(profile, env)`.
+    pub fn from_profile(
+        path: &Path,
+        profile: &str,
+        env: Option<&str>,
+    ) -> Result<Self, ConfigError> {
+        ConstraintsDocument::load(path)?.resolve(profile, env)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML_DOC: &str = r#"
+[profiles.mechanic]
+maxFiles = 5
+maxLines = 200
+mustPassTests = true
+forbiddenPatterns = ["*.env*", "*secrets*"]
+
+[profiles.genius]
+maxSteps = 100
+mustPassTests = true
+
+[env.production]
+maxFiles = 2
+requiresConfirmation = true
+
+[env.staging]
+maxFiles = 20
+"#;
+
+    #[test]
+    fn resolve_without_env_returns_the_base_profile() {
+        let doc = ConstraintsDocument::parse(TOML_DOC, false).unwrap();
+        let resolved = doc.resolve("mechanic", None).unwrap();
+
+        assert_eq!(resolved.max_files, Some(5));
+        assert_eq!(resolved.max_lines, Some(200));
+        assert_eq!(resolved.must_pass_tests, Some(true));
+    }
+
+    #[test]
+    fn resolve_with_env_folds_the_overlay_on_top() {
+        let doc = ConstraintsDocument::parse(TOML_DOC, false).unwrap();
+        let resolved = doc.resolve("mechanic", Some("production")).unwrap();
+
+        // Overlay wins on fields it sets...
+        assert_eq!(resolved.max_files, Some(2));
+        assert_eq!(resolved.requires_confirmation, Some(true));
+        // ...but fields it doesn't set still come from the base profile.
+        assert_eq!(resolved.max_lines, Some(200));
+        assert_eq!(resolved.must_pass_tests, Some(true));
+    }
+
+    #[test]
+    fn resolve_with_unknown_env_returns_the_base_profile_unchanged() {
+        let doc = ConstraintsDocument::parse(TOML_DOC, false).unwrap();
+        let resolved = doc.resolve("genius", Some("nonexistent")).unwrap();
+
+        assert_eq!(resolved.max_steps, Some(100));
+    }
+
+    #[test]
+    fn resolve_with_unknown_profile_is_an_error() {
+        let doc = ConstraintsDocument::parse(TOML_DOC, false).unwrap();
+        assert!(matches!(
+            doc.resolve("nonexistent", None),
+            Err(ConfigError::ProfileNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn json_documents_use_the_same_camel_case_keys() {
+        let json_doc = r#"{
+            "profiles": {
+                "mechanic": { "maxFiles": 5, "mustPassTests": true }
+            },
+            "env": {
+                "staging": { "maxFiles": 20 }
+            }
+        }"#;
+
+        let doc = ConstraintsDocument::parse(json_doc, true).unwrap();
+        let resolved = doc.resolve("mechanic", Some("staging")).unwrap();
+
+        assert_eq!(resolved.max_files, Some(20));
+        assert_eq!(resolved.must_pass_tests, Some(true));
+    }
+
+    #[test]
+    fn from_profile_loads_and_resolves_from_a_file_on_disk() {
+        let path = std::env::temp_dir()
+            .join(format!("tdln_policy_config_{}.toml", std::process::id()));
+        std::fs::write(&path, TOML_DOC).unwrap();
+
+        let resolved = Constraints::from_profile(&path, "mechanic", Some("staging")).unwrap();
+        assert_eq!(resolved.max_files, Some(20));
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -3,6 +3,7 @@
 //! Validates operation metrics against defined constraints.
 
 use serde::{Deserialize, Serialize};
+use crate::runner::RunSummary;
 use crate::verdict::{Verdict, Violation, ViolationSeverity};
 
 /// Constraints for an operation
@@ -51,7 +52,15 @@ pub struct Constraints {
     /// Forbidden file patterns (glob)
     #[serde(rename = "forbiddenPatterns", skip_serializing_if = "Option::is_none")]
     pub forbidden_patterns: Option<Vec<String>>,
-    
+
+    /// If set, every affected file must match at least one of these glob
+    /// patterns (see [`glob_match`]); anything else is a violation. Unlike
+    /// `forbidden_patterns` this is an allow-list, so an empty affected-file
+    /// set trivially passes and an unmatched file fails even if it matches
+    /// no `forbidden_patterns` entry.
+    #[serde(rename = "allowedPaths", skip_serializing_if = "Option::is_none")]
+    pub allowed_paths: Option<Vec<String>>,
+
     /// Required reviewers count
     #[serde(rename = "requiredReviewers", skip_serializing_if = "Option::is_none")]
     pub required_reviewers: Option<u32>,
@@ -82,6 +91,7 @@ impl Constraints {
                 "*password*".to_string(),
                 ".env".to_string(),
             ]),
+            allowed_paths: None,
             required_reviewers: None,
         }
     }
@@ -103,6 +113,7 @@ impl Constraints {
                 "*.env".to_string(),
                 "*secrets*".to_string(),
             ]),
+            allowed_paths: None,
             required_reviewers: Some(1),
         }
     }
@@ -120,6 +131,7 @@ impl Constraints {
         if other.allow_production.is_some() { self.allow_production = other.allow_production; }
         if other.allowed_operations.is_some() { self.allowed_operations = other.allowed_operations.clone(); }
         if other.forbidden_patterns.is_some() { self.forbidden_patterns = other.forbidden_patterns.clone(); }
+        if other.allowed_paths.is_some() { self.allowed_paths = other.allowed_paths.clone(); }
         if other.required_reviewers.is_some() { self.required_reviewers = other.required_reviewers; }
         self
     }
@@ -140,6 +152,11 @@ pub struct OperationMetrics {
     pub operation_type: String,
     pub affected_files: Vec<String>,
     pub reviewer_count: u32,
+    /// Per-unit results from a [`crate::runner::TestRunner`] run, if tests
+    /// were actually executed rather than just reported pass/fail.
+    pub test_results: Option<RunSummary>,
+    /// Per-unit results from a [`crate::runner::TestRunner`] lint run.
+    pub lint_results: Option<RunSummary>,
 }
 
 impl OperationMetrics {
@@ -182,7 +199,22 @@ impl OperationMetrics {
         self.lint_passed = Some(passed);
         self
     }
-    
+
+    /// Attach per-unit test results (e.g. from [`crate::runner::TestRunner`])
+    /// so [`generate_remediation`] can point at the first failure.
+    pub fn with_test_results(mut self, results: RunSummary) -> Self {
+        self.tests_passed = Some(results.all_passed());
+        self.test_results = Some(results);
+        self
+    }
+
+    /// Attach per-unit lint results, analogous to [`Self::with_test_results`].
+    pub fn with_lint_results(mut self, results: RunSummary) -> Self {
+        self.lint_passed = Some(results.all_passed());
+        self.lint_results = Some(results);
+        self
+    }
+
     pub fn confirmed(mut self) -> Self {
         self.has_confirmation = true;
         self
@@ -359,6 +391,20 @@ pub fn validate_constraints(
         }
     }
     
+    // Check allowed paths
+    if let Some(allowed) = &constraints.allowed_paths {
+        for file in &metrics.affected_files {
+            if !allowed.iter().any(|pattern| matches_pattern(file, pattern)) {
+                violations.push(Violation::new(
+                    "path_not_allowed",
+                    "Path Not Allowed",
+                    format!("File '{}' matches none of the allowed path patterns: {:?}", file, allowed),
+                ).with_severity(ViolationSeverity::Critical)
+                 .with_location(file.clone()));
+            }
+        }
+    }
+
     // Check reviewers
     if let Some(required) = constraints.required_reviewers {
         if metrics.reviewer_count < required {
@@ -381,7 +427,7 @@ pub fn validate_constraints(
             Verdict::warn("Passed with warnings", warnings)
         }
     } else {
-        let remediation = generate_remediation(&violations, constraints);
+        let remediation = generate_remediation(&violations, constraints, metrics);
         Verdict::block_with_remediation(
             "Constraint violations detected",
             violations,
@@ -390,23 +436,188 @@ pub fn validate_constraints(
     }
 }
 
-/// Simple glob pattern matching
-fn matches_pattern(path: &str, pattern: &str) -> bool {
-    if pattern.starts_with('*') && pattern.ends_with('*') {
-        let middle = &pattern[1..pattern.len()-1];
-        path.contains(middle)
-    } else if pattern.starts_with('*') {
-        path.ends_with(&pattern[1..])
-    } else if pattern.ends_with('*') {
-        path.starts_with(&pattern[..pattern.len()-1])
-    } else {
-        path == pattern
+/// Match `path` against `pattern`. A `pattern` with no `/` is anchored to
+/// the whole path *or* to any path component onward (so `*secrets*` still
+/// matches `config/secrets.yaml`, the way the old prefix/suffix heuristic
+/// did); a pattern containing `/` is matched against the full path via
+/// [`glob_match`], so `src/**/*.key` and `**/node_modules/**` work as real
+/// globs instead of silently degrading to a substring check.
+pub(crate) fn matches_pattern(path: &str, pattern: &str) -> bool {
+    if pattern.contains('/') {
+        return glob_match(pattern, path);
+    }
+
+    if glob_match(pattern, path) {
+        return true;
+    }
+
+    path.match_indices('/').any(|(i, _)| glob_match(pattern, &path[i + 1..]))
+}
+
+/// A single token of a compiled glob pattern.
+#[derive(Debug, Clone)]
+enum GlobToken {
+    /// A literal character.
+    Literal(char),
+    /// `?` -- exactly one character, never a path separator.
+    AnyChar,
+    /// `*` -- any run of characters, stopping at a path separator.
+    Star,
+    /// `**` -- any run of characters, including path separators.
+    DoubleStar,
+    /// `[...]` / `[!...]` -- one character against a set of ranges.
+    Class { negate: bool, ranges: Vec<(char, char)> },
+}
+
+fn tokenize_glob(pattern: &str) -> Vec<GlobToken> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' => {
+                if i + 1 < chars.len() && chars[i + 1] == '*' {
+                    tokens.push(GlobToken::DoubleStar);
+                    i += 2;
+                } else {
+                    tokens.push(GlobToken::Star);
+                    i += 1;
+                }
+            }
+            '?' => {
+                tokens.push(GlobToken::AnyChar);
+                i += 1;
+            }
+            '[' => {
+                let mut j = i + 1;
+                let negate = j < chars.len() && (chars[j] == '!' || chars[j] == '^');
+                if negate {
+                    j += 1;
+                }
+                let start = j;
+                while j < chars.len() && chars[j] != ']' {
+                    j += 1;
+                }
+                if j < chars.len() && j > start {
+                    tokens.push(GlobToken::Class {
+                        negate,
+                        ranges: parse_class_ranges(&chars[start..j]),
+                    });
+                    i = j + 1;
+                } else {
+                    // Unterminated or empty class -- treat `[` literally.
+                    tokens.push(GlobToken::Literal('['));
+                    i += 1;
+                }
+            }
+            c => {
+                tokens.push(GlobToken::Literal(c));
+                i += 1;
+            }
+        }
     }
+
+    tokens
 }
 
-fn generate_remediation(violations: &[Violation], constraints: &Constraints) -> Vec<String> {
+fn parse_class_ranges(body: &[char]) -> Vec<(char, char)> {
+    let mut ranges = Vec::new();
+    let mut i = 0;
+
+    while i < body.len() {
+        if i + 2 < body.len() && body[i + 1] == '-' {
+            ranges.push((body[i], body[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((body[i], body[i]));
+            i += 1;
+        }
+    }
+
+    ranges
+}
+
+/// Match a glob `pattern` against `text`. Supports `*` (any run of
+/// characters except `/`), `**` (any run of characters, including `/`),
+/// `?` (any single non-`/` character), and `[...]`/`[!...]` character
+/// classes. Implemented as a two-pointer backtracking scan (the classic
+/// wildcard-matching technique) rather than a regex dependency: `*`/`**`
+/// tokens are recorded as a backtrack point, and a later mismatch rewinds
+/// to the most recent one and has it consume one more character.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let tokens = tokenize_glob(pattern);
+    let txt: Vec<char> = text.chars().collect();
+
+    let mut pi = 0usize;
+    let mut ti = 0usize;
+    let mut star_pi: Option<usize> = None;
+    let mut star_ti = 0usize;
+
+    while ti < txt.len() {
+        if pi < tokens.len() {
+            match &tokens[pi] {
+                GlobToken::Star | GlobToken::DoubleStar => {
+                    star_pi = Some(pi);
+                    star_ti = ti;
+                    pi += 1;
+                    continue;
+                }
+                GlobToken::Literal(c) => {
+                    if txt[ti] == *c {
+                        pi += 1;
+                        ti += 1;
+                        continue;
+                    }
+                }
+                GlobToken::AnyChar => {
+                    if txt[ti] != '/' {
+                        pi += 1;
+                        ti += 1;
+                        continue;
+                    }
+                }
+                GlobToken::Class { negate, ranges } => {
+                    let in_set = ranges.iter().any(|&(lo, hi)| txt[ti] >= lo && txt[ti] <= hi);
+                    if in_set != *negate {
+                        pi += 1;
+                        ti += 1;
+                        continue;
+                    }
+                }
+            }
+        }
+
+        // No match at the current token (or pattern exhausted): rewind to
+        // the last star and have it swallow one more character.
+        match star_pi {
+            Some(sp) => {
+                let is_double = matches!(tokens[sp], GlobToken::DoubleStar);
+                if !is_double && txt[star_ti] == '/' {
+                    return false;
+                }
+                star_ti += 1;
+                ti = star_ti;
+                pi = sp + 1;
+            }
+            None => return false,
+        }
+    }
+
+    while pi < tokens.len() && matches!(tokens[pi], GlobToken::Star | GlobToken::DoubleStar) {
+        pi += 1;
+    }
+
+    pi == tokens.len()
+}
+
+fn generate_remediation(
+    violations: &[Violation],
+    constraints: &Constraints,
+    metrics: &OperationMetrics,
+) -> Vec<String> {
     let mut steps = Vec::new();
-    
+
     for violation in violations {
         match violation.rule_id.as_str() {
             "max_files_exceeded" => {
@@ -426,10 +637,16 @@ fn generate_remediation(violations: &[Violation], constraints: &Constraints) ->
                 steps.push("Reduce prompt size or use a more efficient approach".to_string());
             }
             "tests_failed" => {
-                steps.push("Fix failing tests before proceeding".to_string());
+                steps.push(remediation_for_failure(
+                    &metrics.test_results,
+                    "Fix failing tests before proceeding",
+                ));
             }
             "lint_failed" => {
-                steps.push("Fix lint errors before proceeding".to_string());
+                steps.push(remediation_for_failure(
+                    &metrics.lint_results,
+                    "Fix lint errors before proceeding",
+                ));
             }
             "confirmation_required" => {
                 steps.push("Request human approval for this operation".to_string());
@@ -451,6 +668,22 @@ fn generate_remediation(violations: &[Violation], constraints: &Constraints) ->
     steps
 }
 
+/// Point at the specific failing unit from a [`RunSummary`], if one ran;
+/// otherwise fall back to the generic `default` message.
+fn remediation_for_failure(results: &Option<RunSummary>, default: &str) -> String {
+    match results.as_ref() {
+        Some(r) if r.first_failure.is_some() => {
+            let failure = r.first_failure.as_ref().unwrap();
+            let snippet: String = failure.output.chars().take(200).collect();
+            format!(
+                "Fix the failure in '{}' ({} of {} units failed): {}",
+                failure.unit, r.failed, r.total, snippet.trim()
+            )
+        }
+        _ => default.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -531,6 +764,50 @@ mod tests {
         assert!(!matches_pattern("config.yaml", "*secrets*"));
     }
 
+    #[test]
+    fn test_double_star_crosses_path_separators() {
+        assert!(matches_pattern("src/auth/tokens.key", "src/**/*.key"));
+        assert!(matches_pattern("a/b/c/node_modules/pkg/index.js", "**/node_modules/**"));
+        assert!(!matches_pattern("src/tokens.yaml", "src/**/*.key"));
+    }
+
+    #[test]
+    fn test_single_star_does_not_cross_path_separators() {
+        assert!(!matches_pattern("src/auth/tokens.key", "src/*.key"));
+        assert!(matches_pattern("src/tokens.key", "src/*.key"));
+    }
+
+    #[test]
+    fn test_question_mark_matches_exactly_one_character() {
+        assert!(matches_pattern("config/password1.txt", "config/password?.*"));
+        assert!(!matches_pattern("config/password12.txt", "config/password?.*"));
+    }
+
+    #[test]
+    fn test_character_class_matches_with_range_and_negation() {
+        assert!(matches_pattern("config/Password.yaml", "config/[pP]assword.*"));
+        assert!(matches_pattern("config/password.yaml", "config/[pP]assword.*"));
+        assert!(!matches_pattern("config/xassword.yaml", "config/[pP]assword.*"));
+        assert!(matches_pattern("config/secretA.txt", "config/secret[!0-9].*"));
+        assert!(!matches_pattern("config/secret1.txt", "config/secret[!0-9].*"));
+    }
+
+    #[test]
+    fn test_allowed_paths_blocks_files_outside_the_allow_list() {
+        let constraints = Constraints {
+            allowed_paths: Some(vec!["src/**/*.rs".to_string(), "docs/*.md".to_string()]),
+            ..Default::default()
+        };
+
+        let passing = OperationMetrics::new()
+            .with_files(2, vec!["src/pkg/lib.rs".to_string(), "docs/readme.md".to_string()]);
+        assert!(validate_constraints(&constraints, &passing).is_allowed());
+
+        let failing = OperationMetrics::new()
+            .with_files(1, vec!["scripts/deploy.sh".to_string()]);
+        assert!(validate_constraints(&constraints, &failing).is_blocked());
+    }
+
     #[test]
     fn test_constraints_merge() {
         let base = Constraints::mechanic_mode();
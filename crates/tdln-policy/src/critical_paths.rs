@@ -0,0 +1,177 @@
+//! Sensitive-path catalog for risk scoring.
+//!
+//! `RiskInput::affects_critical_files` used to be a single opaque bool the
+//! caller had to compute themselves, collapsing "touches a migration" and
+//! "touches a CI workflow" into one flat penalty with no explanation of
+//! *which* file or *why* it mattered. `CriticalPathRule` replaces that with
+//! an ordered catalog of glob/regex patterns, each carrying its own impact
+//! and reason, so [`crate::risk::RiskCalculator::calculate`] can emit one
+//! explainable [`crate::risk::RiskFactor`] per matched pattern instead of a
+//! single generic one.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// How a [`CriticalPathRule`]'s `pattern` is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternKind {
+    /// A shell-style glob: `*` matches within a path segment, `**` matches
+    /// across zero or more segments.
+    Glob,
+    /// A regular expression, matched anywhere in the path.
+    Regex,
+}
+
+fn default_pattern_kind() -> PatternKind {
+    PatternKind::Glob
+}
+
+/// One sensitive-path rule: a pattern and the score it contributes when a
+/// changed path matches it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CriticalPathRule {
+    /// Short identifier, used as the [`crate::risk::RiskFactor`] name.
+    pub name: String,
+    /// The glob or regex pattern to match affected paths against.
+    pub pattern: String,
+    /// How `pattern` should be interpreted.
+    #[serde(default = "default_pattern_kind")]
+    pub kind: PatternKind,
+    /// Impact on risk score when this rule matches.
+    pub impact: u32,
+    /// Human-readable reason this pattern is considered sensitive, e.g.
+    /// "schema change" or "secrets".
+    pub reason: String,
+}
+
+impl CriticalPathRule {
+    /// Create a glob-matched rule.
+    pub fn glob(
+        name: impl Into<String>,
+        pattern: impl Into<String>,
+        impact: u32,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            pattern: pattern.into(),
+            kind: PatternKind::Glob,
+            impact,
+            reason: reason.into(),
+        }
+    }
+
+    /// Create a regex-matched rule.
+    pub fn regex(
+        name: impl Into<String>,
+        pattern: impl Into<String>,
+        impact: u32,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            pattern: pattern.into(),
+            kind: PatternKind::Regex,
+            impact,
+            reason: reason.into(),
+        }
+    }
+
+    /// Whether `path` matches this rule's pattern.
+    pub fn matches(&self, path: &str) -> bool {
+        let source = match self.kind {
+            PatternKind::Glob => glob_to_regex(&self.pattern),
+            PatternKind::Regex => self.pattern.clone(),
+        };
+        Regex::new(&source)
+            .map(|re| re.is_match(path))
+            .unwrap_or(false)
+    }
+}
+
+/// Translate a shell-style glob into an anchored regex source string.
+///
+/// `**/` matches zero or more whole path segments, a bare `**` matches the
+/// rest of the path, a lone `*` matches within one segment, `?` matches any
+/// single character, and every other character is matched literally.
+fn glob_to_regex(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut out = String::from("^");
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '*' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            if chars.get(i) == Some(&'/') {
+                out.push_str("(?:.*/)?");
+                i += 1;
+            } else {
+                out.push_str(".*");
+            }
+        } else if chars[i] == '*' {
+            out.push_str("[^/]*");
+            i += 1;
+        } else if chars[i] == '?' {
+            out.push('.');
+            i += 1;
+        } else {
+            let c = chars[i];
+            if "\\.+()[]{}|^$".contains(c) {
+                out.push('\\');
+            }
+            out.push(c);
+            i += 1;
+        }
+    }
+    out.push('$');
+    out
+}
+
+/// The built-in sensitive-path catalog, kept as the fallback for any
+/// [`crate::risk::RiskCalculator`] that doesn't override
+/// `critical_path_rules` via policy document.
+pub(crate) fn default_critical_path_rules() -> Vec<CriticalPathRule> {
+    vec![
+        CriticalPathRule::glob("migrations", "**/migrations/**", 25, "schema change"),
+        CriticalPathRule::glob("ci_config", ".github/workflows/*", 20, "CI config"),
+        CriticalPathRule::glob("secrets", "**/*.env", 30, "secrets"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrations_glob_matches_with_and_without_leading_dirs() {
+        let rule = CriticalPathRule::glob("migrations", "**/migrations/**", 25, "schema change");
+        assert!(rule.matches("migrations/0001_init.sql"));
+        assert!(rule.matches("apps/api/migrations/0002_add_index.sql"));
+        assert!(!rule.matches("src/migrations_helper.rs"));
+    }
+
+    #[test]
+    fn ci_config_glob_only_matches_one_segment_deep() {
+        let rule = CriticalPathRule::glob("ci_config", ".github/workflows/*", 20, "CI config");
+        assert!(rule.matches(".github/workflows/deploy.yml"));
+        assert!(!rule.matches(".github/workflows/nested/deploy.yml"));
+        assert!(!rule.matches("other/.github/workflows/deploy.yml"));
+    }
+
+    #[test]
+    fn env_glob_matches_at_any_depth() {
+        let rule = CriticalPathRule::glob("secrets", "**/*.env", 30, "secrets");
+        assert!(rule.matches(".env"));
+        assert!(rule.matches("config/.env"));
+        assert!(rule.matches("a/b/c/.env"));
+        assert!(!rule.matches("config/.env.example"));
+    }
+
+    #[test]
+    fn regex_rule_matches_anywhere_in_path() {
+        let rule = CriticalPathRule::regex("secret_key", r"secret[-_]key", 30, "secret key file");
+        assert!(rule.matches("config/secret_key.yaml"));
+        assert!(rule.matches("src/secret-key.rs"));
+        assert!(!rule.matches("src/main.rs"));
+    }
+}
@@ -0,0 +1,771 @@
+//! Embedded boolean expression language for [`crate::rule::RuleCondition::Expr`].
+//!
+//! Replaces the old `Custom { predicate: fn(&RuleContext) -> bool }` escape
+//! hatch, which couldn't round-trip through JSON, with a small expression
+//! language over [`RuleContext`] fields: `&&`, `||`, `!`, comparisons (`<`,
+//! `<=`, `==`, `!=`, `>`, `>=`), an `in` membership test against a list
+//! literal, a `contains` substring test, a `matches` regex test, and
+//! parentheses, e.g.:
+//!
+//! ```text
+//! risk_level >= 2 && file_count > 10
+//! operation_type in ["deploy", "release"] && !has_confirmation
+//! operation_type contains "rewrite" && !tests_passed
+//! ```
+//!
+//! On top of the infix `matches` operator, a small library of function-call
+//! forms covers path-list matching and string normalization:
+//!
+//! ```text
+//! glob(files, "infra/**")
+//! matches(regex_replace(operation_type, "_v[0-9]+$", ""), "^deploy$")
+//! any(files, item matches "\.secret$")
+//! all(files, glob(item, "src/**.rs"))
+//! ```
+//!
+//! `matches(field, pattern)` and `glob(field, pattern)` are function-call
+//! aliases for the `matches` and `glob` comparisons and can take a
+//! `regex_replace(...)` in place of a bare field name; `any`/`all` quantify
+//! a predicate over a list field (`files`/`affected_paths`), binding the
+//! current item to the pseudo-field `item` inside it. Regexes (from
+//! `matches` and `regex_replace`) are compiled once and cached by pattern
+//! (see [`cached_regex`]); an invalid pattern is rejected when a
+//! [`crate::rule_dsl`] clause compiles it, not on every evaluation.
+//!
+//! [`parse`] compiles a source string into an [`Expr`] tree of `And` / `Or`
+//! / `Not` / `Compare(value, op, literal)` / `Quantifier` nodes;
+//! [`Expr::eval`] then checks that tree against a [`RuleContext`]. Fields:
+//! `risk_level` (compared as its ordinal, `Low` = 0 through `Critical` = 3),
+//! `file_count`, `line_count` (numbers), `operation_type`, `mode` (strings,
+//! compared case-insensitively), `is_destructive`, `targets_production`,
+//! `tests_passed`, `lint_passed`, `has_confirmation`,
+//! `affects_critical_files` (booleans; the `Option<bool>` fields read as
+//! `false` when unset), `files`/`affected_paths` (a string list, usable
+//! with `glob`/`any`/`all`). As with `tdln_quality::rules`'s regex
+//! conditions, `RuleCondition::Expr` re-parses its source each time the
+//! condition is checked rather than caching the [`Expr`] tree, keeping it a
+//! plain serializable string.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+
+use crate::rule::RuleContext;
+
+/// A compiled boolean expression, ready to evaluate against a [`RuleContext`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(ValueExpr, CompareOp, Literal),
+    /// `any(list_field, predicate)` / `all(list_field, predicate)` --
+    /// `predicate` is evaluated once per item of `list_field`, with the
+    /// item bound to the pseudo-field `item`.
+    Quantifier {
+        all: bool,
+        list_field: String,
+        predicate: Box<Expr>,
+    },
+}
+
+/// The left-hand side of a [`Expr::Compare`]: either a bare field name or a
+/// `regex_replace(...)` transform applied to one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueExpr {
+    Field(String),
+    RegexReplace {
+        inner: Box<ValueExpr>,
+        pattern: String,
+        replacement: String,
+    },
+}
+
+/// Comparison operator in a [`Expr::Compare`] node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    /// Field's value is a member of a [`Literal::List`].
+    In,
+    /// Field's string value contains the literal string as a substring
+    /// (case-insensitive, matching [`CompareOp::Eq`]'s string semantics).
+    Contains,
+    /// Field's string value matches the literal string as a regex.
+    /// An invalid pattern doesn't match anything, rather than panicking --
+    /// same "never panics on bad input" convention as an unknown field.
+    Matches,
+    /// Field's string value (or, for a list field, any of its items)
+    /// matches the literal as a shell-style glob, via
+    /// [`crate::constraints::matches_pattern`].
+    Glob,
+}
+
+/// A literal value on the right-hand side of a [`Expr::Compare`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    List(Vec<Literal>),
+}
+
+/// Error parsing a [`Expr`] source string.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{0}")]
+pub struct ExprError(pub String);
+
+/// Parse a single boolean expression, e.g. `risk_level >= 2 && !is_destructive`.
+pub fn parse(source: &str) -> Result<Expr, ExprError> {
+    let tokens = tokenize(source).map_err(ExprError)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or().map_err(ExprError)?;
+    if parser.pos != parser.tokens.len() {
+        return Err(ExprError(format!(
+            "unexpected trailing input at token {}",
+            parser.pos
+        )));
+    }
+    Ok(expr)
+}
+
+impl Expr {
+    /// Evaluate this expression against `context`.
+    pub fn eval(&self, context: &RuleContext) -> bool {
+        self.eval_with(context, None)
+    }
+
+    /// Evaluate this expression against `context`, with `item` (if any)
+    /// bound to the pseudo-field `item` for a [`Expr::Quantifier`]'s
+    /// predicate.
+    fn eval_with(&self, context: &RuleContext, item: Option<&str>) -> bool {
+        match self {
+            Expr::And(l, r) => l.eval_with(context, item) && r.eval_with(context, item),
+            Expr::Or(l, r) => l.eval_with(context, item) || r.eval_with(context, item),
+            Expr::Not(inner) => !inner.eval_with(context, item),
+            Expr::Compare(value, op, literal) => {
+                compare(*op, &resolve_value(context, item, value), literal)
+            }
+            Expr::Quantifier { all, list_field, predicate } => {
+                let FieldValue::List(items) = field_value(context, list_field) else {
+                    return false;
+                };
+                if *all {
+                    items.iter().all(|it| predicate.eval_with(context, Some(it)))
+                } else {
+                    items.iter().any(|it| predicate.eval_with(context, Some(it)))
+                }
+            }
+        }
+    }
+}
+
+/// A field's resolved runtime value, compared against a [`Literal`].
+#[derive(Debug, Clone, PartialEq)]
+enum FieldValue {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    List(Vec<String>),
+}
+
+fn field_value(context: &RuleContext, name: &str) -> FieldValue {
+    match name {
+        "operation_type" => FieldValue::Str(context.operation_type.clone()),
+        "mode" => FieldValue::Str(context.mode.clone()),
+        "risk_level" => FieldValue::Num(context.risk_level as i32 as f64),
+        "file_count" => FieldValue::Num(context.file_count as f64),
+        "line_count" => FieldValue::Num(context.line_count as f64),
+        "is_destructive" => FieldValue::Bool(context.is_destructive),
+        "targets_production" => FieldValue::Bool(context.targets_production),
+        "tests_passed" => FieldValue::Bool(context.tests_passed == Some(true)),
+        "lint_passed" => FieldValue::Bool(context.lint_passed == Some(true)),
+        "has_confirmation" => FieldValue::Bool(context.has_confirmation),
+        "affects_critical_files" => FieldValue::Bool(context.affects_critical_files),
+        "files" | "affected_paths" => FieldValue::List(context.affected_paths.clone()),
+        // Unknown fields resolve to an inert value rather than panicking --
+        // a condition referencing a typo'd field just never matches.
+        _ => FieldValue::Bool(false),
+    }
+}
+
+/// Resolve a [`ValueExpr`] against `context`, with `item` bound to the
+/// pseudo-field `item` inside a quantifier's predicate.
+fn resolve_value(context: &RuleContext, item: Option<&str>, value: &ValueExpr) -> FieldValue {
+    match value {
+        ValueExpr::Field(name) if name == "item" => {
+            FieldValue::Str(item.unwrap_or_default().to_string())
+        }
+        ValueExpr::Field(name) => field_value(context, name),
+        ValueExpr::RegexReplace { inner, pattern, replacement } => {
+            match resolve_value(context, item, inner) {
+                FieldValue::Str(s) => FieldValue::Str(regex_replace(&s, pattern, replacement)),
+                other => other,
+            }
+        }
+    }
+}
+
+/// Regexes are compiled once per distinct pattern and cached here, rather
+/// than recompiled on every [`Expr::eval`] call. `None` caches a pattern
+/// that failed to compile, so a bad pattern reached at evaluation time
+/// (rather than rejected at rule-load time by [`crate::rule_dsl`]) still
+/// costs one compile attempt, not one per call.
+static REGEX_CACHE: Lazy<Mutex<HashMap<String, Arc<Option<regex::Regex>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn cached_regex(pattern: &str) -> Arc<Option<regex::Regex>> {
+    let mut cache = REGEX_CACHE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(re) = cache.get(pattern) {
+        return Arc::clone(re);
+    }
+    let compiled = Arc::new(regex::Regex::new(pattern).ok());
+    cache.insert(pattern.to_string(), Arc::clone(&compiled));
+    compiled
+}
+
+/// Replace all matches of `pattern` in `input` with `replacement`, via the
+/// same cached regex [`CompareOp::Matches`] uses. An invalid pattern leaves
+/// `input` unchanged, same "never panics on bad input" convention as an
+/// unknown field.
+fn regex_replace(input: &str, pattern: &str, replacement: &str) -> String {
+    match cached_regex(pattern).as_ref() {
+        Some(re) => re.replace_all(input, replacement).into_owned(),
+        None => input.to_string(),
+    }
+}
+
+fn compare(op: CompareOp, left: &FieldValue, literal: &Literal) -> bool {
+    if op == CompareOp::In {
+        let Literal::List(items) = literal else {
+            return false;
+        };
+        return items.iter().any(|item| compare(CompareOp::Eq, left, item));
+    }
+    if op == CompareOp::Contains {
+        let (FieldValue::Str(a), Literal::Str(b)) = (left, literal) else {
+            return false;
+        };
+        return a.to_lowercase().contains(&b.to_lowercase());
+    }
+    if op == CompareOp::Matches {
+        let (FieldValue::Str(a), Literal::Str(pattern)) = (left, literal) else {
+            return false;
+        };
+        return cached_regex(pattern).as_ref().as_ref().map(|re| re.is_match(a)).unwrap_or(false);
+    }
+    if op == CompareOp::Glob {
+        let Literal::Str(pattern) = literal else {
+            return false;
+        };
+        return match left {
+            FieldValue::Str(s) => crate::constraints::matches_pattern(s, pattern),
+            FieldValue::List(items) => items.iter().any(|s| crate::constraints::matches_pattern(s, pattern)),
+            _ => false,
+        };
+    }
+
+    match (left, literal) {
+        (FieldValue::Str(a), Literal::Str(b)) => match op {
+            CompareOp::Eq => a.eq_ignore_ascii_case(b),
+            CompareOp::Ne => !a.eq_ignore_ascii_case(b),
+            _ => false,
+        },
+        (FieldValue::Num(a), Literal::Num(b)) => match op {
+            CompareOp::Eq => a == b,
+            CompareOp::Ne => a != b,
+            CompareOp::Gt => a > b,
+            CompareOp::Lt => a < b,
+            CompareOp::Ge => a >= b,
+            CompareOp::Le => a <= b,
+            CompareOp::In | CompareOp::Contains | CompareOp::Matches | CompareOp::Glob => false,
+        },
+        (FieldValue::Bool(a), Literal::Bool(b)) => match op {
+            CompareOp::Eq => a == b,
+            CompareOp::Ne => a != b,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// === Tokenizer / parser ===
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    AndAnd,
+    OrOr,
+    Bang,
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '"' {
+            let mut s = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                s.push(chars[i]);
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err("unterminated string literal".to_string());
+            }
+            i += 1;
+            tokens.push(Token::Str(s));
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let n = text
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number literal '{text}'"))?;
+            tokens.push(Token::Num(n));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push(Token::Ident(word));
+        } else {
+            match c {
+                '(' => {
+                    tokens.push(Token::LParen);
+                    i += 1;
+                }
+                ')' => {
+                    tokens.push(Token::RParen);
+                    i += 1;
+                }
+                '[' => {
+                    tokens.push(Token::LBracket);
+                    i += 1;
+                }
+                ']' => {
+                    tokens.push(Token::RBracket);
+                    i += 1;
+                }
+                ',' => {
+                    tokens.push(Token::Comma);
+                    i += 1;
+                }
+                '&' if chars.get(i + 1) == Some(&'&') => {
+                    tokens.push(Token::AndAnd);
+                    i += 2;
+                }
+                '|' if chars.get(i + 1) == Some(&'|') => {
+                    tokens.push(Token::OrOr);
+                    i += 2;
+                }
+                '=' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Eq);
+                    i += 2;
+                }
+                '!' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Ne);
+                    i += 2;
+                }
+                '!' => {
+                    tokens.push(Token::Bang);
+                    i += 1;
+                }
+                '>' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Ge);
+                    i += 2;
+                }
+                '>' => {
+                    tokens.push(Token::Gt);
+                    i += 1;
+                }
+                '<' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Le);
+                    i += 2;
+                }
+                '<' => {
+                    tokens.push(Token::Lt);
+                    i += 1;
+                }
+                other => return Err(format!("unexpected character '{other}'")),
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_and()?;
+        while self.peek() == Some(&Token::OrOr) {
+            self.bump();
+            let rhs = self.parse_and()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_unary()?;
+        while self.peek() == Some(&Token::AndAnd) {
+            self.bump();
+            let rhs = self.parse_unary()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Bang) {
+            self.bump();
+            let inner = self.parse_unary()?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::LParen) {
+            self.bump();
+            let inner = self.parse_or()?;
+            match self.bump() {
+                Some(Token::RParen) => return Ok(inner),
+                _ => return Err("expected closing ')'".to_string()),
+            }
+        }
+
+        if let Some(Token::Ident(name)) = self.peek().cloned() {
+            let followed_by_call = self.tokens.get(self.pos + 1) == Some(&Token::LParen);
+
+            if matches!(name.as_str(), "matches" | "glob") && followed_by_call {
+                self.bump();
+                self.bump();
+                let value = self.parse_value()?;
+                self.expect(Token::Comma)?;
+                let pattern = self.parse_string()?;
+                self.expect(Token::RParen)?;
+                let op = if name == "matches" { CompareOp::Matches } else { CompareOp::Glob };
+                return Ok(Expr::Compare(value, op, Literal::Str(pattern)));
+            }
+
+            if matches!(name.as_str(), "any" | "all") && followed_by_call {
+                self.bump();
+                self.bump();
+                let list_field = match self.bump() {
+                    Some(Token::Ident(f)) => f,
+                    other => return Err(format!("expected a list field name, got {other:?}")),
+                };
+                self.expect(Token::Comma)?;
+                let predicate = self.parse_or()?;
+                self.expect(Token::RParen)?;
+                return Ok(Expr::Quantifier {
+                    all: name == "all",
+                    list_field,
+                    predicate: Box::new(predicate),
+                });
+            }
+        }
+
+        let value = self.parse_value()?;
+
+        let op = match self.bump() {
+            Some(Token::Eq) => CompareOp::Eq,
+            Some(Token::Ne) => CompareOp::Ne,
+            Some(Token::Gt) => CompareOp::Gt,
+            Some(Token::Lt) => CompareOp::Lt,
+            Some(Token::Ge) => CompareOp::Ge,
+            Some(Token::Le) => CompareOp::Le,
+            Some(Token::Ident(word)) if word == "in" => CompareOp::In,
+            Some(Token::Ident(word)) if word == "contains" => CompareOp::Contains,
+            Some(Token::Ident(word)) if word == "matches" => CompareOp::Matches,
+            other => return Err(format!("expected a comparison operator, got {other:?}")),
+        };
+
+        let literal = self.parse_literal()?;
+        Ok(Expr::Compare(value, op, literal))
+    }
+
+    /// Parse a bare field name, or a `regex_replace(value, "pattern",
+    /// "replacement")` transform applied to one.
+    fn parse_value(&mut self) -> Result<ValueExpr, String> {
+        match self.bump() {
+            Some(Token::Ident(name)) if name == "regex_replace" && self.peek() == Some(&Token::LParen) => {
+                self.bump();
+                let inner = self.parse_value()?;
+                self.expect(Token::Comma)?;
+                let pattern = self.parse_string()?;
+                self.expect(Token::Comma)?;
+                let replacement = self.parse_string()?;
+                self.expect(Token::RParen)?;
+                Ok(ValueExpr::RegexReplace {
+                    inner: Box::new(inner),
+                    pattern,
+                    replacement,
+                })
+            }
+            Some(Token::Ident(name)) => Ok(ValueExpr::Field(name)),
+            other => Err(format!("expected a field name or regex_replace(...), got {other:?}")),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        match self.bump() {
+            Some(Token::Str(s)) => Ok(s),
+            other => Err(format!("expected a string literal, got {other:?}")),
+        }
+    }
+
+    fn expect(&mut self, tok: Token) -> Result<(), String> {
+        if self.bump().as_ref() == Some(&tok) {
+            Ok(())
+        } else {
+            Err(format!("expected {tok:?}"))
+        }
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, String> {
+        match self.bump() {
+            Some(Token::Str(s)) => Ok(Literal::Str(s)),
+            Some(Token::Num(n)) => Ok(Literal::Num(n)),
+            Some(Token::Ident(word)) if word == "true" => Ok(Literal::Bool(true)),
+            Some(Token::Ident(word)) if word == "false" => Ok(Literal::Bool(false)),
+            Some(Token::LBracket) => {
+                let mut items = Vec::new();
+                if self.peek() != Some(&Token::RBracket) {
+                    loop {
+                        items.push(self.parse_literal()?);
+                        if self.peek() == Some(&Token::Comma) {
+                            self.bump();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                match self.bump() {
+                    Some(Token::RBracket) => Ok(Literal::List(items)),
+                    other => Err(format!("expected closing ']', got {other:?}")),
+                }
+            }
+            other => Err(format!("expected a literal, got {other:?}")),
+        }
+    }
+}
+
+/// Walk a parsed expression and reject any regex pattern (from `matches` or
+/// `regex_replace`) that fails to compile, so [`crate::rule_dsl`] can
+/// surface a bad pattern when a clause is compiled instead of on every
+/// evaluation.
+pub(crate) fn validate_patterns(expr: &Expr) -> Result<(), String> {
+    match expr {
+        Expr::And(l, r) | Expr::Or(l, r) => {
+            validate_patterns(l)?;
+            validate_patterns(r)
+        }
+        Expr::Not(inner) => validate_patterns(inner),
+        Expr::Compare(value, op, literal) => {
+            validate_value_patterns(value)?;
+            if *op == CompareOp::Matches {
+                if let Literal::Str(pattern) = literal {
+                    regex::Regex::new(pattern)
+                        .map_err(|e| format!("invalid regex pattern '{pattern}': {e}"))?;
+                }
+            }
+            Ok(())
+        }
+        Expr::Quantifier { predicate, .. } => validate_patterns(predicate),
+    }
+}
+
+fn validate_value_patterns(value: &ValueExpr) -> Result<(), String> {
+    if let ValueExpr::RegexReplace { inner, pattern, .. } = value {
+        regex::Regex::new(pattern).map_err(|e| format!("invalid regex pattern '{pattern}': {e}"))?;
+        validate_value_patterns(inner)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::RiskLevel;
+
+    #[test]
+    fn evaluates_a_numeric_comparison() {
+        let expr = parse("file_count > 5").unwrap();
+        assert!(expr.eval(&RuleContext::new("bug_fix").with_files(10)));
+        assert!(!expr.eval(&RuleContext::new("bug_fix").with_files(1)));
+    }
+
+    #[test]
+    fn compares_risk_level_as_an_ordinal() {
+        let expr = parse("risk_level >= 2").unwrap();
+        assert!(expr.eval(&RuleContext::new("deploy").with_risk(RiskLevel::High)));
+        assert!(!expr.eval(&RuleContext::new("deploy").with_risk(RiskLevel::Low)));
+    }
+
+    #[test]
+    fn string_comparisons_are_case_insensitive() {
+        let expr = parse(r#"operation_type == "Deploy""#).unwrap();
+        assert!(expr.eval(&RuleContext::new("deploy")));
+        assert!(expr.eval(&RuleContext::new("DEPLOY")));
+        assert!(!expr.eval(&RuleContext::new("rollback")));
+    }
+
+    #[test]
+    fn supports_in_membership() {
+        let expr = parse(r#"operation_type in ["deploy", "release"]"#).unwrap();
+        assert!(expr.eval(&RuleContext::new("release")));
+        assert!(!expr.eval(&RuleContext::new("bug_fix")));
+    }
+
+    #[test]
+    fn supports_and_or_not_and_parentheses() {
+        let expr =
+            parse("!has_confirmation && (file_count > 1 || is_destructive)").unwrap();
+        let needs_review = RuleContext::new("refactor").with_files(3);
+        let confirmed = RuleContext::new("refactor").with_files(3).confirmed();
+        assert!(expr.eval(&needs_review));
+        assert!(!expr.eval(&confirmed));
+    }
+
+    #[test]
+    fn rejects_malformed_syntax() {
+        assert!(parse("file_count >").is_err());
+        assert!(parse("file_count > 1 &&").is_err());
+    }
+
+    #[test]
+    fn supports_contains_as_a_case_insensitive_substring_test() {
+        let expr = parse(r#"operation_type contains "rewrite""#).unwrap();
+        assert!(expr.eval(&RuleContext::new("full_rewrite")));
+        assert!(expr.eval(&RuleContext::new("REWRITE_module")));
+        assert!(!expr.eval(&RuleContext::new("bug_fix")));
+    }
+
+    #[test]
+    fn supports_matches_as_a_regex_test() {
+        let expr = parse(r#"operation_type matches "^deploy_v[0-9]+$""#).unwrap();
+        assert!(expr.eval(&RuleContext::new("deploy_v2")));
+        assert!(!expr.eval(&RuleContext::new("deploy_vx")));
+    }
+
+    #[test]
+    fn an_invalid_matches_pattern_never_matches_instead_of_panicking() {
+        let expr = parse(r#"operation_type matches "(""#).unwrap();
+        assert!(!expr.eval(&RuleContext::new("deploy")));
+    }
+
+    #[test]
+    fn matches_function_call_is_equivalent_to_the_infix_operator() {
+        let expr = parse(r#"matches(operation_type, "^deploy_v[0-9]+$")"#).unwrap();
+        assert!(expr.eval(&RuleContext::new("deploy_v2")));
+        assert!(!expr.eval(&RuleContext::new("deploy_vx")));
+    }
+
+    #[test]
+    fn glob_matches_a_single_path_field() {
+        let expr = parse(r#"glob(operation_type, "deploy_*")"#).unwrap();
+        assert!(expr.eval(&RuleContext::new("deploy_prod")));
+        assert!(!expr.eval(&RuleContext::new("rollback")));
+    }
+
+    #[test]
+    fn glob_matches_any_item_of_the_files_list() {
+        let expr = parse(r#"glob(files, "infra/**")"#).unwrap();
+        let touches_infra = RuleContext::new("deploy").with_affected_paths(["infra/network.tf"]);
+        let elsewhere = RuleContext::new("deploy").with_affected_paths(["src/main.rs"]);
+        assert!(expr.eval(&touches_infra));
+        assert!(!expr.eval(&elsewhere));
+    }
+
+    #[test]
+    fn regex_replace_normalizes_a_field_before_comparison() {
+        let expr = parse(r#"regex_replace(operation_type, "_v[0-9]+$", "") == "deploy""#).unwrap();
+        assert!(expr.eval(&RuleContext::new("deploy_v12")));
+        assert!(!expr.eval(&RuleContext::new("rollback_v2")));
+    }
+
+    #[test]
+    fn any_quantifier_matches_if_one_item_satisfies_the_predicate() {
+        let expr = parse(r#"any(files, item matches "\.secret$")"#).unwrap();
+        let leaks = RuleContext::new("deploy").with_affected_paths(["README.md", "keys/.secret"]);
+        let clean = RuleContext::new("deploy").with_affected_paths(["README.md", "src/main.rs"]);
+        assert!(expr.eval(&leaks));
+        assert!(!expr.eval(&clean));
+    }
+
+    #[test]
+    fn all_quantifier_requires_every_item_to_satisfy_the_predicate() {
+        let expr = parse(r#"all(files, glob(item, "src/**"))"#).unwrap();
+        let all_in_src = RuleContext::new("refactor").with_affected_paths(["src/a.rs", "src/b.rs"]);
+        let mixed = RuleContext::new("refactor").with_affected_paths(["src/a.rs", "infra/net.tf"]);
+        assert!(expr.eval(&all_in_src));
+        assert!(!expr.eval(&mixed));
+    }
+
+    #[test]
+    fn all_quantifier_over_an_empty_list_is_vacuously_true() {
+        let expr = parse(r#"all(files, item matches "never")"#).unwrap();
+        assert!(expr.eval(&RuleContext::new("deploy")));
+    }
+
+    #[test]
+    fn validate_patterns_rejects_an_invalid_regex_in_matches() {
+        let expr = parse(r#"operation_type matches "(""#).unwrap();
+        assert!(validate_patterns(&expr).is_err());
+    }
+
+    #[test]
+    fn validate_patterns_rejects_an_invalid_regex_in_regex_replace() {
+        let expr = parse(r#"regex_replace(operation_type, "(", "") == "deploy""#).unwrap();
+        assert!(validate_patterns(&expr).is_err());
+    }
+
+    #[test]
+    fn validate_patterns_accepts_well_formed_patterns() {
+        let expr = parse(r#"any(files, item matches "\.secret$")"#).unwrap();
+        assert!(validate_patterns(&expr).is_ok());
+    }
+}
@@ -89,13 +89,29 @@
 //! println!("Block rate: {:.1}%", stats.block_rate * 100.0);
 //! ```
 
+pub mod adapter;
 pub mod audit;
+pub mod authorizer;
+pub mod batch;
+pub mod config;
 pub mod constraints;
+pub mod critical_paths;
+pub mod expr;
+pub mod metrics;
+pub mod override_policy;
+pub mod override_profile;
 pub mod override_system;
+pub mod override_token;
+pub mod policy_dsl;
 pub mod policy_set;
 pub mod risk;
+pub mod risk_policy;
 pub mod rule;
+pub mod rule_dsl;
+pub mod rule_set;
+pub mod runner;
 pub mod verdict;
+pub mod watch;
 
 // Make override available under a different name to avoid Rust keyword
 pub use override_system as policy_override;
@@ -107,27 +123,82 @@ pub use verdict::{Verdict, Violation, ViolationSeverity, VerdictSeverity};
 pub use risk::{
     RiskLevel, RiskAssessment, RiskFactor, RiskCategory,
     RiskCalculator, RiskInput, calculate_risk,
+    Approval, ApproverRole, ApprovalPolicy,
 };
 
+// Risk policy-as-code
+pub use risk_policy::{CompiledRiskRule, RiskPolicyError};
+
+// Sensitive-path catalog
+pub use critical_paths::{CriticalPathRule, PatternKind};
+
+// Prometheus metrics for policy decisions
+pub use metrics::PolicyMetrics;
+
+// Batch risk rollup
+pub use batch::{BatchAssessment, BatchFactor, BatchMember};
+
+// Authorization gate
+pub use authorizer::{Authorizer, DenyReason, Grant, Principal};
+
 // Constraints
 pub use constraints::{Constraints, OperationMetrics, validate_constraints};
 
+// Layered constraint configuration
+pub use config::{ConfigError, ConstraintsDocument};
+
+// Test/lint execution
+pub use runner::{RunnerConfig, RunnerError, RunSummary, TestRunner, UnitFailure};
+
+// Continuous constraint re-validation
+pub use watch::{watch_constraints, WatchError, DEFAULT_DEBOUNCE as WATCH_DEFAULT_DEBOUNCE};
+
 // Rules
-pub use rule::{PolicyRule, RuleCondition, RuleContext, RuleSeverity, default_rules};
+pub use rule::{Actor, PolicyRule, RuleCondition, RuleContext, RuleMode, RuleSeverity, RoleManager, default_rules};
+pub use rule::{ConditionResult, PolicyReport, RuleReport, SeverityCounts, evaluate_all_detailed};
+pub use rule_set::{RuleSet, RuleSetError, DEFAULT_CACHE_TTL};
+pub use expr::{CompareOp, Expr, ExprError, Literal};
 
 // Policy sets
-pub use policy_set::{PolicySet, PolicyEvaluation, FullEvaluation, PolicyGate};
+pub use policy_set::{
+    PolicySet, PolicyEvaluation, FullEvaluation, PolicyGate, CombinedReport, OperationStatus,
+    RuleRollup, OperationViolation,
+};
+
+// Policy DSL
+pub use policy_dsl::{CompiledClause, PolicyDslError};
+
+// Declarative rule DSL (compiles directly into PolicyRule)
+pub use rule_dsl::RuleDslError;
+
+// Loading/persisting a PolicySet to external storage
+pub use adapter::{AdapterError, FileAdapter, InMemoryAdapter, PolicyAdapter};
 
 // Audit
 pub use audit::{
-    AuditLog, AuditEntry, AuditEventType, AuditStats,
+    AuditLog, AuditEntry, AuditEventType, AuditStats, AuditSignals,
+    AuditReport, ReportStatus, SourceReport, ViolationRecord,
     OverrideRecord, OverrideType,
 };
 
 // Override system
 pub use override_system::{
-    OverrideManager, OverrideRequest, OverrideResult,
-    OverridePermissions, Exemption, OverrideStats,
+    OverrideManager, OverrideOutcome, OverridePrompter, OverrideRequest, OverrideResult,
+    OverridePermissions, Exemption, OverrideStats, PromptContext, PromptResponse,
+};
+
+// Declarative override authorization policy
+pub use override_policy::{
+    derive_facts, parse_policy, FactSet, OverrideDenial, OverridePolicy, OverridePolicyError,
+};
+
+// Signed, attenuable override tokens
+pub use override_token::{OverrideToken, OverrideTokenError, TokenBlock, TokenScope};
+
+// Layered override profiles
+pub use override_profile::{
+    parse_filter, resolve_permissions, FilterExpr, MatchRule, OverrideProfile, OverrideProfileError,
+    PermissionsFragment, ResolvedPermissions, ResolvedSources, Source,
 };
 
 /// Quick policy check for an operation
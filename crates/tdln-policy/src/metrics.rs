@@ -0,0 +1,183 @@
+//! Prometheus metrics for live policy-decision observability.
+//!
+//! [`AuditLog::log_evaluation`](crate::audit::AuditLog::log_evaluation) and
+//! [`OverrideManager::request_override`](crate::override_system::OverrideManager::request_override)
+//! increment a [`PolicyMetrics`] when one is installed via `with_metrics`,
+//! so operators can scrape how often the gate blocks operations instead of
+//! grepping the audit log. The registry is injectable
+//! ([`PolicyMetrics::with_registry`]) so tests -- and embedders like
+//! `tdln_api`, which exposes it over `/metrics` -- can assert on or merge
+//! in the emitted series rather than being stuck with a process-global one.
+
+use prometheus::{
+    Counter, CounterVec, Gauge, Histogram, HistogramOpts, Opts, Registry,
+};
+
+use crate::audit::OverrideType;
+use crate::verdict::VerdictSeverity;
+
+/// Counters and gauges for one policy-decision pipeline. Cheap to clone
+/// (every field is an `Arc` internally, per the `prometheus` crate), so it
+/// can be shared across an `AuditLog`, an `OverrideManager`, and an API
+/// server's `/metrics` route without wrapping it in another `Arc`.
+#[derive(Clone)]
+pub struct PolicyMetrics {
+    registry: Registry,
+    verdicts_total: CounterVec,
+    risk_score: Histogram,
+    overrides_total: CounterVec,
+    block_rate: Gauge,
+}
+
+impl PolicyMetrics {
+    /// Register a fresh set of metrics into a new [`Registry`].
+    pub fn new() -> Self {
+        Self::with_registry(Registry::new()).expect("registering into a fresh registry never collides")
+    }
+
+    /// Register a fresh set of metrics into `registry`, so an embedder can
+    /// share one registry across multiple subsystems (or, in tests, gather
+    /// only what this call registered). Fails if `registry` already has a
+    /// metric under one of these names.
+    pub fn with_registry(registry: Registry) -> Result<Self, prometheus::Error> {
+        let verdicts_total = CounterVec::new(
+            Opts::new("tdln_policy_verdicts_total", "Policy verdicts, labeled by outcome"),
+            &["verdict"],
+        )?;
+        let risk_score = Histogram::with_opts(HistogramOpts::new(
+            "tdln_policy_risk_score",
+            "Distribution of RiskAssessment::score across evaluated operations",
+        ).buckets(vec![5.0, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0]))?;
+        let overrides_total = CounterVec::new(
+            Opts::new("tdln_policy_overrides_total", "Granted overrides, labeled by override type"),
+            &["override_type"],
+        )?;
+        let block_rate = Gauge::new(
+            "tdln_policy_block_rate",
+            "Rolling fraction of logged evaluations with a Block verdict (see AuditStats::block_rate)",
+        )?;
+
+        registry.register(Box::new(verdicts_total.clone()))?;
+        registry.register(Box::new(risk_score.clone()))?;
+        registry.register(Box::new(overrides_total.clone()))?;
+        registry.register(Box::new(block_rate.clone()))?;
+
+        Ok(Self { registry, verdicts_total, risk_score, overrides_total, block_rate })
+    }
+
+    /// The underlying [`Registry`], for an embedder to `gather()` or pass
+    /// to an encoder (e.g. `tdln_api::metrics::encode`).
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Record one evaluation's verdict and risk score. Called by
+    /// [`crate::audit::AuditLog::log_evaluation`] when a `PolicyMetrics` is
+    /// installed.
+    pub fn record_evaluation(&self, verdict: VerdictSeverity, risk_score: u32) {
+        self.verdicts_total.with_label_values(&[verdict_label(verdict)]).inc();
+        self.risk_score.observe(risk_score as f64);
+    }
+
+    /// Record one granted override. Called by
+    /// [`crate::override_system::OverrideManager::request_override`] when a
+    /// `PolicyMetrics` is installed.
+    pub fn record_override(&self, override_type: OverrideType) {
+        self.overrides_total.with_label_values(&[override_type_label(override_type)]).inc();
+    }
+
+    /// Set the rolling block-rate gauge, typically from
+    /// `AuditLog::stats().block_rate`.
+    pub fn set_block_rate(&self, rate: f64) {
+        self.block_rate.set(rate);
+    }
+
+    /// Total verdicts recorded under `verdict` so far, for test assertions.
+    pub fn verdict_count(&self, verdict: VerdictSeverity) -> f64 {
+        self.verdicts_total.with_label_values(&[verdict_label(verdict)]).get()
+    }
+
+    /// Total overrides recorded under `override_type` so far, for test
+    /// assertions.
+    pub fn override_count(&self, override_type: OverrideType) -> f64 {
+        self.overrides_total.with_label_values(&[override_type_label(override_type)]).get()
+    }
+}
+
+impl Default for PolicyMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn verdict_label(verdict: VerdictSeverity) -> &'static str {
+    match verdict {
+        VerdictSeverity::Allow => "allow",
+        VerdictSeverity::Warn => "warn",
+        VerdictSeverity::Block => "block",
+    }
+}
+
+fn override_type_label(override_type: OverrideType) -> &'static str {
+    match override_type {
+        OverrideType::ManualApproval => "manual_approval",
+        OverrideType::Exemption => "exemption",
+        OverrideType::Emergency => "emergency",
+        OverrideType::Waiver => "waiver",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_a_verdict_and_its_risk_score() {
+        let metrics = PolicyMetrics::new();
+        metrics.record_evaluation(VerdictSeverity::Block, 85);
+        assert_eq!(metrics.verdict_count(VerdictSeverity::Block), 1.0);
+        assert_eq!(metrics.verdict_count(VerdictSeverity::Allow), 0.0);
+        let families = metrics.registry().gather();
+        let histogram = families
+            .iter()
+            .find(|f| f.get_name() == "tdln_policy_risk_score")
+            .expect("risk score histogram is registered");
+        assert_eq!(histogram.get_metric()[0].get_histogram().get_sample_sum(), 85.0);
+    }
+
+    #[test]
+    fn records_an_override_by_type() {
+        let metrics = PolicyMetrics::new();
+        metrics.record_override(OverrideType::Emergency);
+        metrics.record_override(OverrideType::Emergency);
+        metrics.record_override(OverrideType::Waiver);
+        assert_eq!(metrics.override_count(OverrideType::Emergency), 2.0);
+        assert_eq!(metrics.override_count(OverrideType::Waiver), 1.0);
+    }
+
+    #[test]
+    fn block_rate_gauge_reflects_the_last_value_set() {
+        let metrics = PolicyMetrics::new();
+        metrics.set_block_rate(0.25);
+        let families = metrics.registry.gather();
+        let gauge = families
+            .iter()
+            .find(|f| f.get_name() == "tdln_policy_block_rate")
+            .expect("block rate gauge is registered");
+        assert_eq!(gauge.get_metric()[0].get_gauge().get_value(), 0.25);
+    }
+
+    #[test]
+    fn shares_an_injected_registry_with_another_collector() {
+        let registry = Registry::new();
+        let external = Counter::new("other_subsystem_total", "unrelated counter").unwrap();
+        registry.register(Box::new(external.clone())).unwrap();
+
+        let metrics = PolicyMetrics::with_registry(registry).unwrap();
+        metrics.record_evaluation(VerdictSeverity::Allow, 5);
+
+        let families = metrics.registry().gather();
+        assert!(families.iter().any(|f| f.get_name() == "other_subsystem_total"));
+        assert!(families.iter().any(|f| f.get_name() == "tdln_policy_verdicts_total"));
+    }
+}
@@ -0,0 +1,524 @@
+//! Declarative, Datalog-style policy language for override authorization.
+//!
+//! [`OverrideManager::request_override`] used to hardcode its authorization
+//! logic (authorized-list lookup, allowed-type check, max-risk check,
+//! critical-violation check) directly in Rust, so a deployment couldn't
+//! change who may override what without recompiling. This module adds a
+//! small rule engine instead: a [`FullEvaluation`] and [`OverrideRequest`]
+//! are turned into a [`FactSet`] of ground facts --
+//!
+//! ```text
+//! risk(High)
+//! violation("max_files_exceeded")
+//! requester("admin@x")
+//! override_type(Emergency)
+//! ```
+//!
+//! -- and an [`OverridePolicy`], parsed from a small clause language with
+//! [`parse_policy`], is evaluated against it:
+//!
+//! ```text
+//! allow if requester("admin@x")
+//! allow if override_type(Emergency) and risk(High)
+//! deny if violation("no_rollback_plan")
+//! check if not risk(Critical) or override_type(Emergency)
+//! ```
+//!
+//! Evaluation collects every matching `allow`/`deny`/`check` clause; a
+//! request is granted only if at least one `allow` fires, no `deny` fires,
+//! and every `check` holds (an empty `check` set holds vacuously). This
+//! mirrors [`crate::policy_dsl`]'s clause-per-line design, but clauses
+//! match *facts* (predicate/argument pairs) instead of [`RuleContext`]
+//! fields, since override authorization reasons about a whole evaluation
+//! plus who's asking, not a single operation's metrics.
+//!
+//! [`OverrideManager::with_policy`] installs a custom [`OverridePolicy`];
+//! without one, [`OverrideManager::request_override`] keeps its original
+//! hardcoded checks unchanged, so existing deployments see no behavior
+//! change until they opt in.
+
+use crate::policy_set::FullEvaluation;
+use crate::risk::RiskLevel;
+use crate::audit::OverrideType;
+use crate::override_system::OverrideRequest;
+
+/// Evaluating a clause condition walks one [`Expr`] node per step; a policy
+/// with a pathological number of clauses or deeply nested `and`/`or`/`not`
+/// expressions is rejected rather than left to run unbounded.
+const MAX_EVAL_STEPS: usize = 10_000;
+
+/// A [`FactSet`] derived from an evaluation is capped at this many facts
+/// (mostly driven by violation count) -- a runaway number of violations
+/// shouldn't turn override evaluation into an O(n) scan with no ceiling.
+const MAX_FACTS: usize = 1_000;
+
+/// Error parsing an override policy document, or bounding its evaluation.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum OverridePolicyError {
+    #[error("line {line}: {message}")]
+    Parse { line: usize, message: String },
+    #[error("policy evaluation exceeded the iteration limit ({0} steps)")]
+    IterationLimitExceeded(usize),
+    #[error("fact derivation exceeded the limit ({0} facts)")]
+    FactLimitExceeded(usize),
+}
+
+/// A set of ground facts, each a predicate applied to one argument, e.g.
+/// `risk(High)` is stored as the pair `("risk", "High")`.
+#[derive(Debug, Clone, Default)]
+pub struct FactSet {
+    facts: Vec<(String, String)>,
+}
+
+impl FactSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assert `predicate(arg)`. Returns an error once [`MAX_FACTS`] would be
+    /// exceeded, so a request with an unbounded number of violations can't
+    /// make evaluation unbounded either.
+    pub fn assert(
+        &mut self,
+        predicate: impl Into<String>,
+        arg: impl Into<String>,
+    ) -> Result<(), OverridePolicyError> {
+        if self.facts.len() >= MAX_FACTS {
+            return Err(OverridePolicyError::FactLimitExceeded(MAX_FACTS));
+        }
+        self.facts.push((predicate.into(), arg.into()));
+        Ok(())
+    }
+
+    fn contains(&self, predicate: &str, arg: &str) -> bool {
+        self.facts.iter().any(|(p, a)| p == predicate && a == arg)
+    }
+
+    pub fn len(&self) -> usize {
+        self.facts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.facts.is_empty()
+    }
+}
+
+/// Derive the standard fact set for an override request: the evaluation's
+/// risk level, every violation's rule id, the requester, and the requested
+/// override type.
+pub fn derive_facts(
+    evaluation: &FullEvaluation,
+    request: &OverrideRequest,
+) -> Result<FactSet, OverridePolicyError> {
+    let mut facts = FactSet::new();
+    facts.assert("risk", format!("{:?}", evaluation.risk_assessment.level))?;
+    for violation in evaluation.all_violations() {
+        facts.assert("violation", violation.rule_id.clone())?;
+    }
+    facts.assert("requester", request.requester.clone())?;
+    facts.assert("override_type", format!("{:?}", request.override_type))?;
+    Ok(facts)
+}
+
+/// A compiled override policy: `allow`/`deny`/`check` clauses, each a
+/// boolean condition over a [`FactSet`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OverridePolicy {
+    allow: Vec<Expr>,
+    deny: Vec<Expr>,
+    check: Vec<Expr>,
+}
+
+/// Why an [`OverridePolicy::evaluate`] call denied a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverrideDenial {
+    /// No `allow` clause fired.
+    NoAllowMatched,
+    /// A `deny` clause fired.
+    DenyMatched,
+    /// A `check` clause did not hold.
+    CheckFailed,
+}
+
+impl OverridePolicy {
+    /// Evaluate this policy against `facts`: granted iff at least one
+    /// `allow` clause holds, no `deny` clause holds, and every `check`
+    /// clause holds.
+    pub fn evaluate(&self, facts: &FactSet) -> Result<Result<(), OverrideDenial>, OverridePolicyError> {
+        let mut steps = 0usize;
+
+        let mut allowed = false;
+        for expr in &self.allow {
+            if expr.eval(facts, &mut steps)? {
+                allowed = true;
+            }
+        }
+        if !allowed {
+            return Ok(Err(OverrideDenial::NoAllowMatched));
+        }
+
+        for expr in &self.deny {
+            if expr.eval(facts, &mut steps)? {
+                return Ok(Err(OverrideDenial::DenyMatched));
+            }
+        }
+
+        for expr in &self.check {
+            if !expr.eval(facts, &mut steps)? {
+                return Ok(Err(OverrideDenial::CheckFailed));
+            }
+        }
+
+        Ok(Ok(()))
+    }
+}
+
+/// Parse a full override policy document: one clause per non-blank,
+/// non-comment line, each `allow if <expr>`, `deny if <expr>`, or
+/// `check if <expr>`.
+pub fn parse_policy(source: &str) -> Result<OverridePolicy, OverridePolicyError> {
+    let mut policy = OverridePolicy::default();
+
+    for (i, line) in source.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let line_no = i + 1;
+        parse_clause(trimmed, &mut policy).map_err(|message| OverridePolicyError::Parse {
+            line: line_no,
+            message,
+        })?;
+    }
+
+    Ok(policy)
+}
+
+fn parse_clause(line: &str, policy: &mut OverridePolicy) -> Result<(), String> {
+    let tokens = tokenize(line)?;
+    let mut parser = Parser { tokens, pos: 0 };
+
+    let keyword = parser.expect_keyword()?;
+    parser.expect_if()?;
+    let expr = parser.parse_or()?;
+
+    if parser.pos != parser.tokens.len() {
+        return Err(format!("unexpected trailing input at token {}", parser.pos));
+    }
+
+    match keyword.as_str() {
+        "allow" => policy.allow.push(expr),
+        "deny" => policy.deny.push(expr),
+        "check" => policy.check.push(expr),
+        _ => unreachable!("expect_keyword only returns allow/deny/check"),
+    }
+    Ok(())
+}
+
+// === Expression language: facts combined with and/or/not/parens ===
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Fact(String, String),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, facts: &FactSet, steps: &mut usize) -> Result<bool, OverridePolicyError> {
+        *steps += 1;
+        if *steps > MAX_EVAL_STEPS {
+            return Err(OverridePolicyError::IterationLimitExceeded(MAX_EVAL_STEPS));
+        }
+        Ok(match self {
+            Expr::Fact(predicate, arg) => facts.contains(predicate, arg),
+            Expr::Not(inner) => !inner.eval(facts, steps)?,
+            Expr::And(l, r) => l.eval(facts, steps)? && r.eval(facts, steps)?,
+            Expr::Or(l, r) => l.eval(facts, steps)? || r.eval(facts, steps)?,
+        })
+    }
+}
+
+// === Tokenizer / parser ===
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '"' {
+            let mut s = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                s.push(chars[i]);
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err("unterminated string literal".to_string());
+            }
+            i += 1;
+            tokens.push(Token::Str(s));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            match c {
+                '(' => {
+                    tokens.push(Token::LParen);
+                    i += 1;
+                }
+                ')' => {
+                    tokens.push(Token::RParen);
+                    i += 1;
+                }
+                ',' => {
+                    tokens.push(Token::Comma);
+                    i += 1;
+                }
+                other => return Err(format!("unexpected character '{other}'")),
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn expect_keyword(&mut self) -> Result<String, String> {
+        match self.bump() {
+            Some(Token::Ident(word)) if matches!(word.as_str(), "allow" | "deny" | "check") => Ok(word),
+            other => Err(format!("expected 'allow', 'deny', or 'check', got {other:?}")),
+        }
+    }
+
+    fn expect_if(&mut self) -> Result<(), String> {
+        match self.bump() {
+            Some(Token::Ident(word)) if word == "if" => Ok(()),
+            other => Err(format!("expected 'if', got {other:?}")),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_and()?;
+        while matches!(self.peek(), Some(Token::Ident(w)) if w == "or") {
+            self.bump();
+            let rhs = self.parse_and()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_unary()?;
+        while matches!(self.peek(), Some(Token::Ident(w)) if w == "and") {
+            self.bump();
+            let rhs = self.parse_unary()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if matches!(self.peek(), Some(Token::Ident(w)) if w == "not") {
+            self.bump();
+            let inner = self.parse_unary()?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.bump() {
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                match self.bump() {
+                    Some(Token::RParen) => Ok(inner),
+                    other => Err(format!("expected closing ')', got {other:?}")),
+                }
+            }
+            Some(Token::Ident(name)) => {
+                match self.bump() {
+                    Some(Token::LParen) => {}
+                    other => return Err(format!("expected '(' after predicate '{name}', got {other:?}")),
+                }
+                let arg = match self.bump() {
+                    Some(Token::Ident(word)) => word,
+                    Some(Token::Str(s)) => s,
+                    other => return Err(format!("expected a fact argument, got {other:?}")),
+                };
+                match self.bump() {
+                    Some(Token::RParen) => {}
+                    other => return Err(format!("expected closing ')', got {other:?}")),
+                }
+                Ok(Expr::Fact(name, arg))
+            }
+            other => Err(format!("expected a fact or '(', got {other:?}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy_set::PolicySet;
+    use crate::rule::RuleContext;
+    use crate::constraints::OperationMetrics;
+
+    fn evaluation() -> FullEvaluation {
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("feature").with_files(20).with_lines(500);
+        let metrics = OperationMetrics::new().with_files(20, vec![]).with_lines(500);
+        policy.evaluate(&context, &metrics)
+    }
+
+    #[test]
+    fn derives_risk_requester_and_violation_facts() {
+        let request = OverrideRequest::new("admin@x", "urgent").with_type(OverrideType::Emergency);
+        let facts = derive_facts(&evaluation(), &request).unwrap();
+
+        assert!(facts.contains("requester", "admin@x"));
+        assert!(facts.contains("override_type", "Emergency"));
+        assert!(!facts.is_empty());
+    }
+
+    #[test]
+    fn grants_when_an_allow_clause_matches_and_nothing_denies() {
+        let policy = parse_policy(r#"allow if requester("admin@x")"#).unwrap();
+        let mut facts = FactSet::new();
+        facts.assert("requester", "admin@x").unwrap();
+
+        assert_eq!(policy.evaluate(&facts).unwrap(), Ok(()));
+    }
+
+    #[test]
+    fn denies_when_no_allow_clause_matches() {
+        let policy = parse_policy(r#"allow if requester("admin@x")"#).unwrap();
+        let mut facts = FactSet::new();
+        facts.assert("requester", "someone-else").unwrap();
+
+        assert_eq!(policy.evaluate(&facts).unwrap(), Err(OverrideDenial::NoAllowMatched));
+    }
+
+    #[test]
+    fn a_matching_deny_clause_wins_over_an_allow() {
+        let policy = parse_policy(
+            "allow if requester(\"admin@x\")\ndeny if violation(\"no_rollback_plan\")\n",
+        )
+        .unwrap();
+        let mut facts = FactSet::new();
+        facts.assert("requester", "admin@x").unwrap();
+        facts.assert("violation", "no_rollback_plan").unwrap();
+
+        assert_eq!(policy.evaluate(&facts).unwrap(), Err(OverrideDenial::DenyMatched));
+    }
+
+    #[test]
+    fn every_check_clause_must_hold() {
+        let policy = parse_policy(
+            "allow if requester(\"admin@x\")\ncheck if override_type(Emergency)\n",
+        )
+        .unwrap();
+
+        let mut granted_facts = FactSet::new();
+        granted_facts.assert("requester", "admin@x").unwrap();
+        granted_facts.assert("override_type", "Emergency").unwrap();
+        assert_eq!(policy.evaluate(&granted_facts).unwrap(), Ok(()));
+
+        let mut denied_facts = FactSet::new();
+        denied_facts.assert("requester", "admin@x").unwrap();
+        denied_facts.assert("override_type", "ManualApproval").unwrap();
+        assert_eq!(policy.evaluate(&denied_facts).unwrap(), Err(OverrideDenial::CheckFailed));
+    }
+
+    #[test]
+    fn supports_and_or_not_and_parentheses() {
+        let policy = parse_policy(
+            r#"allow if risk(High) and (override_type(Emergency) or not violation("no_rollback_plan"))"#,
+        )
+        .unwrap();
+
+        let mut facts = FactSet::new();
+        facts.assert("risk", "High").unwrap();
+        assert_eq!(policy.evaluate(&facts).unwrap(), Ok(()));
+    }
+
+    #[test]
+    fn reports_the_offending_line_number_on_syntax_errors() {
+        let err = parse_policy("allow if requester(\"ok\")\ndeny if (").unwrap_err();
+        assert!(matches!(err, OverridePolicyError::Parse { line: 2, .. }));
+    }
+
+    #[test]
+    fn rejects_an_unknown_keyword() {
+        let OverridePolicyError::Parse { message, .. } =
+            parse_policy(r#"maybe if requester("x")"#).unwrap_err()
+        else {
+            panic!("expected a parse error");
+        };
+        assert!(message.contains("expected 'allow', 'deny', or 'check'"));
+    }
+
+    #[test]
+    fn fact_set_assert_enforces_the_fact_limit() {
+        let mut facts = FactSet::new();
+        for i in 0..MAX_FACTS {
+            facts.assert("violation", format!("rule_{i}")).unwrap();
+        }
+        assert!(matches!(
+            facts.assert("violation", "one_too_many"),
+            Err(OverridePolicyError::FactLimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn evaluate_enforces_the_iteration_limit() {
+        // Build an expression deep enough to blow the step budget: a chain
+        // of `not not not ... requester("x")`.
+        let mut source = String::from("allow if ");
+        for _ in 0..MAX_EVAL_STEPS + 10 {
+            source.push_str("not ");
+        }
+        source.push_str("requester(\"x\")\n");
+
+        let policy = parse_policy(&source).unwrap();
+        let mut facts = FactSet::new();
+        facts.assert("requester", "x").unwrap();
+
+        assert!(matches!(
+            policy.evaluate(&facts),
+            Err(OverridePolicyError::IterationLimitExceeded(_))
+        ));
+    }
+}
@@ -0,0 +1,616 @@
+//! Layered override profiles, resolved like nextest's per-test override
+//! stack: an ordered list of [`OverrideProfile`]s, each holding
+//! [`MatchRule`]s keyed by a filter expression over the requester,
+//! operation, policy, and risk level. [`resolve_permissions`] walks the
+//! profiles top-to-bottom and, independently for each
+//! [`OverridePermissions`] field, takes the value from the first rule that
+//! matches -- recording which profile and rule supplied it as a
+//! [`Source`] so callers can explain *why* a limit applied.
+//!
+//! # Filter expression syntax
+//!
+//! ```text
+//! operation ~= "deploy*" and risk <= High
+//! requester == "admin@example.com" or policy ~= "mechanic*"
+//! not (risk == Critical)
+//! ```
+//!
+//! Fields: `requester`, `operation`, `policy` (string, compared with `==`
+//! for an exact match or `~=` for a glob with trailing `*`), and `risk`
+//! (a [`RiskLevel`] variant name, compared with `==`, `<=`, `>=`, `<`, or
+//! `>`). Combine with `and`, `or`, `not`, and parentheses.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::audit::OverrideType;
+use crate::policy_set::FullEvaluation;
+use crate::risk::RiskLevel;
+use crate::override_system::OverridePermissions;
+
+/// Errors parsing a filter expression.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum OverrideProfileError {
+    #[error("invalid filter expression: {0}")]
+    Parse(String),
+}
+
+/// Context a [`FilterExpr`] is matched against.
+struct MatchContext<'a> {
+    requester: &'a str,
+    operation: &'a str,
+    policy: &'a str,
+    risk: RiskLevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RiskCmp {
+    Eq,
+    Le,
+    Ge,
+    Lt,
+    Gt,
+}
+
+/// A parsed filter expression, as produced by [`parse_filter`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    Requester(String),
+    RequesterGlob(String),
+    Operation(String),
+    OperationGlob(String),
+    Policy(String),
+    PolicyGlob(String),
+    Risk(RiskCmp, RiskLevel),
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+impl FilterExpr {
+    fn matches(&self, ctx: &MatchContext) -> bool {
+        match self {
+            FilterExpr::Requester(v) => ctx.requester == v,
+            FilterExpr::RequesterGlob(p) => glob_match(p, ctx.requester),
+            FilterExpr::Operation(v) => ctx.operation == v,
+            FilterExpr::OperationGlob(p) => glob_match(p, ctx.operation),
+            FilterExpr::Policy(v) => ctx.policy == v,
+            FilterExpr::PolicyGlob(p) => glob_match(p, ctx.policy),
+            FilterExpr::Risk(cmp, level) => match cmp {
+                RiskCmp::Eq => ctx.risk == *level,
+                RiskCmp::Le => ctx.risk <= *level,
+                RiskCmp::Ge => ctx.risk >= *level,
+                RiskCmp::Lt => ctx.risk < *level,
+                RiskCmp::Gt => ctx.risk > *level,
+            },
+            FilterExpr::Not(e) => !e.matches(ctx),
+            FilterExpr::And(a, b) => a.matches(ctx) && b.matches(ctx),
+            FilterExpr::Or(a, b) => a.matches(ctx) || b.matches(ctx),
+        }
+    }
+}
+
+/// `true` if `pattern` equals `text` exactly, or `pattern` ends with `*`
+/// and `text` starts with everything before it -- same suffix-wildcard
+/// semantics as [`crate::override_system::Exemption::matches_operation`].
+fn glob_match(pattern: &str, text: &str) -> bool {
+    if let Some(prefix) = pattern.strip_suffix('*') {
+        text.starts_with(prefix)
+    } else {
+        text == pattern
+    }
+}
+
+/// Parse a filter expression. See the [module docs](self) for syntax.
+pub fn parse_filter(source: &str) -> Result<FilterExpr, OverrideProfileError> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(OverrideProfileError::Parse(format!(
+            "unexpected trailing input near token {}",
+            parser.pos
+        )));
+    }
+    Ok(expr)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Op(String),
+    LParen,
+    RParen,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, OverrideProfileError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if c == '"' {
+            let start = i + 1;
+            let mut j = start;
+            while j < chars.len() && chars[j] != '"' {
+                j += 1;
+            }
+            if j >= chars.len() {
+                return Err(OverrideProfileError::Parse("unterminated string literal".to_string()));
+            }
+            tokens.push(Token::Str(chars[start..j].iter().collect()));
+            i = j + 1;
+        } else if "=<>~".contains(c) {
+            let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            match two.as_str() {
+                "==" | "<=" | ">=" | "~=" => {
+                    tokens.push(Token::Op(two));
+                    i += 2;
+                }
+                _ if c == '<' || c == '>' => {
+                    tokens.push(Token::Op(c.to_string()));
+                    i += 1;
+                }
+                _ => {
+                    return Err(OverrideProfileError::Parse(format!("unexpected character '{}'", c)));
+                }
+            }
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            let mut j = i;
+            while j < chars.len() && (chars[j].is_alphanumeric() || chars[j] == '_') {
+                j += 1;
+            }
+            tokens.push(Token::Ident(chars[start..j].iter().collect()));
+            i = j;
+        } else {
+            return Err(OverrideProfileError::Parse(format!("unexpected character '{}'", c)));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn is_ident(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(w)) if w.eq_ignore_ascii_case(word))
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, OverrideProfileError> {
+        let mut lhs = self.parse_and()?;
+        while self.is_ident("or") {
+            self.pos += 1;
+            let rhs = self.parse_and()?;
+            lhs = FilterExpr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, OverrideProfileError> {
+        let mut lhs = self.parse_unary()?;
+        while self.is_ident("and") {
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            lhs = FilterExpr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, OverrideProfileError> {
+        if self.is_ident("not") {
+            self.pos += 1;
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<FilterExpr, OverrideProfileError> {
+        match self.peek() {
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let expr = self.parse_or()?;
+                match self.peek() {
+                    Some(Token::RParen) => {
+                        self.pos += 1;
+                        Ok(expr)
+                    }
+                    _ => Err(OverrideProfileError::Parse("expected ')'".to_string())),
+                }
+            }
+            Some(Token::Ident(field)) => {
+                let field = field.clone();
+                self.pos += 1;
+                let op = match self.peek() {
+                    Some(Token::Op(op)) => op.clone(),
+                    _ => return Err(OverrideProfileError::Parse(format!("expected an operator after '{}'", field))),
+                };
+                self.pos += 1;
+
+                match field.as_str() {
+                    "requester" | "operation" | "policy" => {
+                        let value = match self.peek() {
+                            Some(Token::Str(s)) => s.clone(),
+                            _ => return Err(OverrideProfileError::Parse("expected a quoted string value".to_string())),
+                        };
+                        self.pos += 1;
+                        build_string_comparison(&field, &op, value)
+                    }
+                    "risk" => {
+                        let level = match self.peek() {
+                            Some(Token::Ident(level)) => parse_risk_level(level)?,
+                            _ => return Err(OverrideProfileError::Parse("expected a risk level".to_string())),
+                        };
+                        self.pos += 1;
+                        let cmp = match op.as_str() {
+                            "==" => RiskCmp::Eq,
+                            "<=" => RiskCmp::Le,
+                            ">=" => RiskCmp::Ge,
+                            "<" => RiskCmp::Lt,
+                            ">" => RiskCmp::Gt,
+                            other => return Err(OverrideProfileError::Parse(format!("'{}' is not a valid risk operator", other))),
+                        };
+                        Ok(FilterExpr::Risk(cmp, level))
+                    }
+                    other => Err(OverrideProfileError::Parse(format!("unknown field '{}'", other))),
+                }
+            }
+            Some(other) => Err(OverrideProfileError::Parse(format!("unexpected token {:?}", other))),
+            None => Err(OverrideProfileError::Parse("unexpected end of expression".to_string())),
+        }
+    }
+}
+
+fn build_string_comparison(field: &str, op: &str, value: String) -> Result<FilterExpr, OverrideProfileError> {
+    match (field, op) {
+        ("requester", "==") => Ok(FilterExpr::Requester(value)),
+        ("requester", "~=") => Ok(FilterExpr::RequesterGlob(value)),
+        ("operation", "==") => Ok(FilterExpr::Operation(value)),
+        ("operation", "~=") => Ok(FilterExpr::OperationGlob(value)),
+        ("policy", "==") => Ok(FilterExpr::Policy(value)),
+        ("policy", "~=") => Ok(FilterExpr::PolicyGlob(value)),
+        (field, op) => Err(OverrideProfileError::Parse(format!(
+            "'{}' does not support the '{}' operator",
+            field, op
+        ))),
+    }
+}
+
+fn parse_risk_level(name: &str) -> Result<RiskLevel, OverrideProfileError> {
+    match name {
+        "Low" => Ok(RiskLevel::Low),
+        "Medium" => Ok(RiskLevel::Medium),
+        "High" => Ok(RiskLevel::High),
+        "Critical" => Ok(RiskLevel::Critical),
+        other => Err(OverrideProfileError::Parse(format!("'{}' is not a risk level", other))),
+    }
+}
+
+/// Which profile and rule supplied a resolved permission field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Source {
+    pub profile: String,
+    pub rule_index: usize,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "profile '{}' rule #{}", self.profile, self.rule_index)
+    }
+}
+
+/// The provenance of each field in a [`ResolvedPermissions`]. `None` means
+/// no rule in any profile specified that field, so the
+/// [`OverridePermissions::default`] value was used instead.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedSources {
+    pub allowed_types: Option<Source>,
+    pub max_risk_level: Option<Source>,
+    pub max_violations: Option<Source>,
+    pub allow_emergency: Option<Source>,
+}
+
+/// An [`OverridePermissions`], resolved from one or more [`OverrideProfile`]s,
+/// together with the [`Source`] of each field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedPermissions {
+    pub allowed_types: Vec<OverrideType>,
+    pub max_risk_level: RiskLevel,
+    pub max_violations: Option<usize>,
+    pub allow_emergency: bool,
+    pub sources: ResolvedSources,
+}
+
+/// A fragment of [`OverridePermissions`] a [`MatchRule`] contributes. Any
+/// field left `None` falls through to the next matching rule, or to
+/// [`OverridePermissions::default`] if no rule ever specifies it.
+///
+/// `max_violations` is doubly-optional: the outer `Option` is whether this
+/// rule specifies the field at all, the inner is the field's own
+/// "unlimited" value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionsFragment {
+    pub allowed_types: Option<Vec<OverrideType>>,
+    pub max_risk_level: Option<RiskLevel>,
+    pub max_violations: Option<Option<usize>>,
+    pub allow_emergency: Option<bool>,
+}
+
+impl PermissionsFragment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allowed_types(mut self, types: Vec<OverrideType>) -> Self {
+        self.allowed_types = Some(types);
+        self
+    }
+
+    pub fn max_risk_level(mut self, level: RiskLevel) -> Self {
+        self.max_risk_level = Some(level);
+        self
+    }
+
+    pub fn max_violations(mut self, max: Option<usize>) -> Self {
+        self.max_violations = Some(max);
+        self
+    }
+
+    pub fn allow_emergency(mut self, allow: bool) -> Self {
+        self.allow_emergency = Some(allow);
+        self
+    }
+}
+
+/// One filter-matched permissions fragment within an [`OverrideProfile`].
+#[derive(Debug, Clone)]
+pub struct MatchRule {
+    pub filter: FilterExpr,
+    pub permissions: PermissionsFragment,
+}
+
+impl MatchRule {
+    /// Parse `filter` and pair it with `permissions`.
+    pub fn parse(filter: &str, permissions: PermissionsFragment) -> Result<Self, OverrideProfileError> {
+        Ok(Self {
+            filter: parse_filter(filter)?,
+            permissions,
+        })
+    }
+}
+
+/// An ordered list of [`MatchRule`]s. Profiles themselves are ordered by
+/// the caller (e.g. [`crate::override_system::OverrideManager`]'s profile
+/// list) -- higher-priority profiles come first.
+#[derive(Debug, Clone)]
+pub struct OverrideProfile {
+    pub name: String,
+    pub rules: Vec<MatchRule>,
+}
+
+impl OverrideProfile {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            rules: Vec::new(),
+        }
+    }
+
+    /// Parse `filter` and append a rule mapping it to `permissions`.
+    pub fn with_rule(mut self, filter: &str, permissions: PermissionsFragment) -> Result<Self, OverrideProfileError> {
+        self.rules.push(MatchRule::parse(filter, permissions)?);
+        Ok(self)
+    }
+}
+
+/// Walk `profiles` top-to-bottom and, independently for each
+/// [`OverridePermissions`] field, take the value from the first rule (in
+/// profile order, then rule order within a profile) whose filter matches
+/// `requester`/`operation`/`evaluation`. Returns `None` if no rule in any
+/// profile matches at all -- i.e. this requester is not authorized by any
+/// profile.
+pub fn resolve_permissions(
+    profiles: &[OverrideProfile],
+    requester: &str,
+    evaluation: &FullEvaluation,
+    operation: &str,
+) -> Option<ResolvedPermissions> {
+    let ctx = MatchContext {
+        requester,
+        operation,
+        policy: &evaluation.policy_id,
+        risk: evaluation.risk_assessment.level,
+    };
+
+    let mut allowed_types: Option<(Vec<OverrideType>, Source)> = None;
+    let mut max_risk_level: Option<(RiskLevel, Source)> = None;
+    let mut max_violations: Option<(Option<usize>, Source)> = None;
+    let mut allow_emergency: Option<(bool, Source)> = None;
+
+    for profile in profiles {
+        for (rule_index, rule) in profile.rules.iter().enumerate() {
+            if !rule.filter.matches(&ctx) {
+                continue;
+            }
+            let source = || Source {
+                profile: profile.name.clone(),
+                rule_index,
+            };
+
+            if allowed_types.is_none() {
+                if let Some(v) = &rule.permissions.allowed_types {
+                    allowed_types = Some((v.clone(), source()));
+                }
+            }
+            if max_risk_level.is_none() {
+                if let Some(v) = rule.permissions.max_risk_level {
+                    max_risk_level = Some((v, source()));
+                }
+            }
+            if max_violations.is_none() {
+                if let Some(v) = rule.permissions.max_violations {
+                    max_violations = Some((v, source()));
+                }
+            }
+            if allow_emergency.is_none() {
+                if let Some(v) = rule.permissions.allow_emergency {
+                    allow_emergency = Some((v, source()));
+                }
+            }
+        }
+    }
+
+    if allowed_types.is_none() && max_risk_level.is_none() && max_violations.is_none() && allow_emergency.is_none() {
+        return None;
+    }
+
+    let defaults = OverridePermissions::default();
+    Some(ResolvedPermissions {
+        allowed_types: allowed_types.as_ref().map(|(v, _)| v.clone()).unwrap_or(defaults.allowed_types),
+        max_risk_level: max_risk_level.as_ref().map(|(v, _)| *v).unwrap_or(defaults.max_risk_level),
+        max_violations: max_violations.as_ref().map(|(v, _)| *v).unwrap_or(defaults.max_violations),
+        allow_emergency: allow_emergency.as_ref().map(|(v, _)| *v).unwrap_or(defaults.allow_emergency),
+        sources: ResolvedSources {
+            allowed_types: allowed_types.map(|(_, s)| s),
+            max_risk_level: max_risk_level.map(|(_, s)| s),
+            max_violations: max_violations.map(|(_, s)| s),
+            allow_emergency: allow_emergency.map(|(_, s)| s),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy_set::PolicySet;
+    use crate::rule::RuleContext;
+    use crate::constraints::OperationMetrics;
+
+    fn evaluation_with_risk(files: usize, lines: usize) -> FullEvaluation {
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("feature").with_files(files).with_lines(lines);
+        let metrics = OperationMetrics::new().with_files(files, vec![]).with_lines(lines);
+        policy.evaluate(&context, &metrics)
+    }
+
+    #[test]
+    fn parses_and_matches_a_compound_expression() {
+        let expr = parse_filter(r#"operation ~= "deploy*" and risk <= High"#).unwrap();
+        let ctx = MatchContext {
+            requester: "a",
+            operation: "deploy-staging",
+            policy: "mechanic@1.0",
+            risk: RiskLevel::Medium,
+        };
+        assert!(expr.matches(&ctx));
+
+        let ctx = MatchContext {
+            requester: "a",
+            operation: "build",
+            policy: "mechanic@1.0",
+            risk: RiskLevel::Medium,
+        };
+        assert!(!expr.matches(&ctx));
+    }
+
+    #[test]
+    fn not_and_parens_negate_correctly() {
+        let expr = parse_filter(r#"not (risk == Critical)"#).unwrap();
+        let ctx = MatchContext {
+            requester: "a",
+            operation: "op",
+            policy: "p",
+            risk: RiskLevel::Low,
+        };
+        assert!(expr.matches(&ctx));
+    }
+
+    #[test]
+    fn unknown_field_is_rejected() {
+        assert!(parse_filter(r#"bogus == "x""#).is_err());
+    }
+
+    #[test]
+    fn resolve_permissions_takes_the_first_matching_rule_per_field() {
+        let top = OverrideProfile::new("on-call")
+            .with_rule(
+                r#"requester == "oncall@example.com""#,
+                PermissionsFragment::new().max_risk_level(RiskLevel::Critical),
+            )
+            .unwrap();
+        let fallback = OverrideProfile::new("default")
+            .with_rule(
+                "risk <= Critical",
+                PermissionsFragment::new()
+                    .max_risk_level(RiskLevel::Low)
+                    .allow_emergency(false),
+            )
+            .unwrap();
+
+        let evaluation = evaluation_with_risk(20, 500);
+        let resolved = resolve_permissions(
+            &[top, fallback],
+            "oncall@example.com",
+            &evaluation,
+            "deploy",
+        )
+        .unwrap();
+
+        assert_eq!(resolved.max_risk_level, RiskLevel::Critical);
+        assert_eq!(resolved.sources.max_risk_level.unwrap().profile, "on-call");
+        // allow_emergency only appears in `fallback`
+        assert!(!resolved.allow_emergency);
+        assert_eq!(resolved.sources.allow_emergency.unwrap().profile, "default");
+    }
+
+    #[test]
+    fn resolve_permissions_returns_none_when_nothing_matches() {
+        let profile = OverrideProfile::new("admins")
+            .with_rule(
+                r#"requester == "admin@example.com""#,
+                PermissionsFragment::new().max_risk_level(RiskLevel::Critical),
+            )
+            .unwrap();
+
+        let evaluation = evaluation_with_risk(20, 500);
+        let resolved = resolve_permissions(&[profile], "stranger@example.com", &evaluation, "deploy");
+
+        assert!(resolved.is_none());
+    }
+
+    #[test]
+    fn unmatched_fields_fall_back_to_permission_defaults() {
+        let profile = OverrideProfile::new("minimal")
+            .with_rule(
+                r#"requester == "admin@example.com""#,
+                PermissionsFragment::new().allow_emergency(true),
+            )
+            .unwrap();
+
+        let evaluation = evaluation_with_risk(20, 500);
+        let resolved = resolve_permissions(&[profile], "admin@example.com", &evaluation, "deploy").unwrap();
+
+        assert_eq!(resolved.max_risk_level, OverridePermissions::default().max_risk_level);
+        assert!(resolved.sources.max_risk_level.is_none());
+    }
+}
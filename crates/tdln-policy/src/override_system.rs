@@ -2,12 +2,21 @@
 //!
 //! Allows authorized users to bypass policy blocks in exceptional circumstances.
 
+use ed25519_dalek::SigningKey;
+use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
 use crate::verdict::Verdict;
 use crate::risk::RiskLevel;
 use crate::audit::{OverrideRecord, OverrideType};
+use crate::metrics::PolicyMetrics;
+use crate::override_policy::{derive_facts, OverrideDenial, OverridePolicy};
+use crate::override_profile::{
+    resolve_permissions, FilterExpr, MatchRule, OverrideProfile, PermissionsFragment, ResolvedSources,
+};
+use crate::override_token::{OverrideToken, TokenScope};
 use crate::policy_set::FullEvaluation;
-use std::collections::HashMap;
+use crate::rule::RoleManager;
+use std::collections::{HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Override request
@@ -25,6 +34,17 @@ pub struct OverrideRequest {
     /// Specific violations to override (empty = all)
     #[serde(default)]
     pub violations: Vec<String>,
+    /// Operation name this override applies to, matched against
+    /// `operation`/`operation ~=` filters in [`OverrideProfile`] rules.
+    #[serde(default)]
+    pub operation: String,
+    /// Roles the requester currently holds, consulted by
+    /// [`OverrideManager::resolve_role_permissions`] when no
+    /// `requester`/`operation` profile rule matches. Expanded through
+    /// [`OverrideManager::role_manager`] before matching, same as
+    /// [`crate::rule::RuleContext::effective_subjects`].
+    #[serde(default)]
+    pub roles: Vec<String>,
     /// Additional context
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<serde_json::Value>,
@@ -39,92 +59,379 @@ impl OverrideRequest {
             override_type: OverrideType::ManualApproval,
             duration_ms: None,
             violations: Vec::new(),
+            operation: String::new(),
+            roles: Vec::new(),
             context: None,
         }
     }
-    
+
+    /// Set the roles the requester currently holds.
+    pub fn with_roles(mut self, roles: Vec<String>) -> Self {
+        self.roles = roles;
+        self
+    }
+
     /// Set the override type
     pub fn with_type(mut self, t: OverrideType) -> Self {
         self.override_type = t;
         self
     }
-    
+
     /// Set duration
     pub fn with_duration(mut self, ms: u64) -> Self {
         self.duration_ms = Some(ms);
         self
     }
-    
+
     /// Limit to specific violations
     pub fn for_violations(mut self, violations: Vec<String>) -> Self {
         self.violations = violations;
         self
     }
+
+    /// Set the operation name, matched against [`OverrideProfile`] rules'
+    /// `operation` filters.
+    pub fn for_operation(mut self, operation: impl Into<String>) -> Self {
+        self.operation = operation.into();
+        self
+    }
 }
 
 /// Result of an override request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OverrideResult {
-    /// Whether the override was granted
+    /// Whether the override was granted (durably or once). `false` for
+    /// both `Denied` and a still-pending `Prompt` outcome.
     pub granted: bool,
+    /// Quadri-state outcome; see [`OverrideOutcome`].
+    pub outcome: OverrideOutcome,
     /// Override record if granted
     pub record: Option<OverrideRecord>,
     /// Reason if denied
     pub denial_reason: Option<String>,
     /// Updated verdict
     pub new_verdict: Option<Verdict>,
+    /// Signed, attenuable token for this grant. `None` on denial. On a
+    /// grant produced by [`OverrideManager::verify_token`] re-checking a
+    /// caller-supplied token, this is that token, not a newly issued one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<OverrideToken>,
+    /// Provenance of the [`ResolvedPermissions`] that decided this
+    /// request, when it was resolved from [`OverrideManager`]'s layered
+    /// profiles -- lets audit logs explain *why* a given limit applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_sources: Option<ResolvedSources>,
 }
 
 impl OverrideResult {
-    /// Create a granted result
+    /// Create a durably granted result with no token.
     pub fn granted(record: OverrideRecord, new_verdict: Verdict) -> Self {
         Self {
             granted: true,
+            outcome: OverrideOutcome::Granted,
             record: Some(record),
             denial_reason: None,
             new_verdict: Some(new_verdict),
+            token: None,
+            permission_sources: None,
         }
     }
-    
+
+    /// Create a durably granted result carrying a signed override token.
+    pub fn granted_with_token(record: OverrideRecord, new_verdict: Verdict, token: OverrideToken) -> Self {
+        Self {
+            granted: true,
+            outcome: OverrideOutcome::Granted,
+            record: Some(record),
+            denial_reason: None,
+            new_verdict: Some(new_verdict),
+            token: Some(token),
+            permission_sources: None,
+        }
+    }
+
+    /// Create a one-shot granted result (no exemption persisted) carrying
+    /// a signed override token.
+    pub fn granted_once_with_token(record: OverrideRecord, new_verdict: Verdict, token: OverrideToken) -> Self {
+        Self {
+            granted: true,
+            outcome: OverrideOutcome::GrantedOnce,
+            record: Some(record),
+            denial_reason: None,
+            new_verdict: Some(new_verdict),
+            token: Some(token),
+            permission_sources: None,
+        }
+    }
+
+    /// Create a result for a prompt that couldn't resolve a decision.
+    pub fn prompt_pending() -> Self {
+        Self {
+            granted: false,
+            outcome: OverrideOutcome::Prompt,
+            record: None,
+            denial_reason: Some("Awaiting interactive prompt response".to_string()),
+            new_verdict: None,
+            token: None,
+            permission_sources: None,
+        }
+    }
+
     /// Create a denied result
     pub fn denied(reason: impl Into<String>) -> Self {
         Self {
             granted: false,
+            outcome: OverrideOutcome::Denied,
             record: None,
             denial_reason: Some(reason.into()),
             new_verdict: None,
+            token: None,
+            permission_sources: None,
         }
     }
+
+    /// Attach the [`ResolvedPermissions`] provenance that decided this
+    /// request.
+    pub fn with_permission_sources(mut self, sources: ResolvedSources) -> Self {
+        self.permission_sources = Some(sources);
+        self
+    }
+}
+
+/// A requester's answer to a runtime [`OverridePrompter`] prompt, modeled
+/// on Deno's permission prompter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PromptResponse {
+    /// Grant with the durable [`OverrideOutcome::Granted`] outcome. No
+    /// overrider entry or exemption is persisted, so a later request from
+    /// the same requester is prompted again.
+    Allow,
+    /// Grant this single request only; no exemption is persisted.
+    AllowOnce,
+    /// Deny this request.
+    Deny,
+    /// Deny this request, and auto-deny this requester/policy pair from
+    /// now on without prompting again.
+    DenyAndRemember,
+}
+
+/// Context an [`OverridePrompter`] is shown when a requester is not
+/// statically authorized but the risk level falls inside
+/// [`OverrideManager`]'s promptable band.
+pub struct PromptContext<'a> {
+    pub requester: &'a str,
+    pub override_type: OverrideType,
+    pub risk_level: RiskLevel,
+    pub violations: &'a [String],
+}
+
+/// Pluggable runtime approval callback for override requests that aren't
+/// pre-authorized. `None` means the callback could not resolve a decision
+/// (e.g. no interactive surface is wired up), leaving the request pending.
+pub trait OverridePrompter {
+    fn prompt(&self, ctx: &PromptContext) -> Option<PromptResponse>;
+}
+
+/// Default prompter: never resolves a decision. Since
+/// [`OverrideManager::promptable_range`] is `None` by default too, no
+/// request reaches this prompter unless [`OverrideManager::set_promptable_range`]
+/// has been called, so this preserves the manager's original
+/// authorized-list-only behavior out of the box.
+struct NoopPrompter;
+
+impl OverridePrompter for NoopPrompter {
+    fn prompt(&self, _ctx: &PromptContext) -> Option<PromptResponse> {
+        None
+    }
+}
+
+/// Quadri-state result of an override request, widening the plain
+/// `granted` boolean so callers can tell a durable grant apart from a
+/// one-shot grant and from a prompt that's still pending a decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverrideOutcome {
+    Granted,
+    GrantedOnce,
+    Prompt,
+    Denied,
 }
 
+/// Name of the implicit, always-top-priority profile [`OverrideManager::add_overrider`]
+/// lowers its entries into.
+const IMPLICIT_PROFILE_NAME: &str = "authorized_overriders";
+
 /// Override manager
 pub struct OverrideManager {
-    /// Authorized override users/roles
-    authorized_overriders: HashMap<String, OverridePermissions>,
+    /// Layered permission profiles, highest-priority first. `add_overrider`
+    /// lowers to a single implicit profile (named
+    /// [`IMPLICIT_PROFILE_NAME`]) always kept at index 0, so profiles
+    /// added via [`OverrideManager::add_profile`] are consulted after it.
+    profiles: Vec<OverrideProfile>,
     /// Active exemptions (policy_id -> exemption)
     exemptions: HashMap<String, Exemption>,
     /// Override history
     history: Vec<OverrideHistoryEntry>,
     /// Maximum history size
     max_history: usize,
+    /// Declarative allow/deny/check policy from [`crate::override_policy`].
+    /// When set, `request_override` grants/denies by evaluating this policy
+    /// against facts derived from the request and evaluation instead of the
+    /// hardcoded authorized-list/risk/critical-violation checks below.
+    policy: Option<OverridePolicy>,
+    /// Key this manager signs issued [`OverrideToken`]s with.
+    signing_key: SigningKey,
+    /// Revocation ids rejected by [`OverrideManager::verify_token`],
+    /// regardless of whether the token would otherwise still verify.
+    revoked: HashSet<String>,
+    /// Risk band (inclusive) within which an otherwise-unauthorized
+    /// requester is sent to `prompt_callback` instead of being denied
+    /// outright. `None` (the default) never prompts, preserving the
+    /// original authorized-list-only behavior.
+    promptable_range: Option<(RiskLevel, RiskLevel)>,
+    /// Runtime approval callback consulted for requesters in
+    /// `promptable_range`. Defaults to [`NoopPrompter`], which never
+    /// resolves a decision.
+    prompt_callback: Box<dyn OverridePrompter>,
+    /// `(requester, policy_id)` pairs permanently denied via a prior
+    /// [`PromptResponse::DenyAndRemember`].
+    denied_pairs: HashSet<(String, String)>,
+    /// Role-based permission ceilings, consulted by
+    /// [`OverrideManager::resolve_role_permissions`] when no profile rule
+    /// matches the requester directly.
+    role_permissions: HashMap<String, OverridePermissions>,
+    /// Role inheritance consulted by [`OverrideManager::resolve_role_permissions`]
+    /// to expand a request's roles transitively before matching, same BFS
+    /// closure [`crate::rule::RuleContext::effective_subjects`] uses.
+    /// `None` leaves roles unexpanded.
+    role_manager: Option<RoleManager>,
+    /// If installed via [`OverrideManager::with_metrics`], incremented
+    /// whenever [`OverrideManager::request_override`] grants an override.
+    metrics: Option<PolicyMetrics>,
 }
 
 impl OverrideManager {
-    /// Create a new override manager
+    /// Create a new override manager, generating a fresh signing key for
+    /// the tokens it issues.
     pub fn new() -> Self {
         Self {
-            authorized_overriders: HashMap::new(),
+            profiles: Vec::new(),
             exemptions: HashMap::new(),
             history: Vec::new(),
             max_history: 1000,
+            policy: None,
+            signing_key: SigningKey::generate(&mut OsRng),
+            revoked: HashSet::new(),
+            promptable_range: None,
+            prompt_callback: Box::new(NoopPrompter),
+            denied_pairs: HashSet::new(),
+            role_permissions: HashMap::new(),
+            role_manager: None,
+            metrics: None,
         }
     }
-    
-    /// Add an authorized overrider
+
+    /// Install a [`PolicyMetrics`] to increment `tdln_policy_overrides_total`
+    /// whenever [`Self::request_override`] grants an override.
+    pub fn with_metrics(mut self, metrics: PolicyMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Install the runtime approval callback consulted for requesters who
+    /// aren't in `authorized_overriders` but fall within
+    /// [`OverrideManager::set_promptable_range`].
+    pub fn set_prompt_callback(&mut self, callback: Box<dyn OverridePrompter>) {
+        self.prompt_callback = callback;
+    }
+
+    /// Set the inclusive risk band within which an unauthorized requester
+    /// is sent to the prompt callback instead of being denied outright.
+    pub fn set_promptable_range(&mut self, min: RiskLevel, max: RiskLevel) {
+        self.promptable_range = Some((min, max));
+    }
+
+    /// Install a declarative override policy, parsed with
+    /// [`crate::override_policy::parse_policy`]. Replaces the hardcoded
+    /// authorized-list/risk/critical-violation checks for every subsequent
+    /// [`OverrideManager::request_override`] call.
+    pub fn with_policy(mut self, policy: OverridePolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Add an authorized overrider: lowered to a rule in a single implicit,
+    /// always-top-priority profile (see [`IMPLICIT_PROFILE_NAME`]), keyed
+    /// on `requester == "<id>"`. A later call for the same `id` replaces
+    /// its earlier rule, matching the old map's overwrite-on-insert
+    /// semantics.
     pub fn add_overrider(&mut self, id: String, permissions: OverridePermissions) {
-        self.authorized_overriders.insert(id, permissions);
+        let fragment = PermissionsFragment::new()
+            .allowed_types(permissions.allowed_types)
+            .max_risk_level(permissions.max_risk_level)
+            .max_violations(permissions.max_violations)
+            .allow_emergency(permissions.allow_emergency);
+        let rule = MatchRule::parse(&format!("requester == \"{}\"", id), fragment)
+            .expect("a requester id always forms a valid filter expression");
+
+        if self.profiles.first().map(|p| p.name.as_str()) != Some(IMPLICIT_PROFILE_NAME) {
+            self.profiles.insert(0, OverrideProfile::new(IMPLICIT_PROFILE_NAME));
+        }
+        let implicit = &mut self.profiles[0];
+        implicit.rules.retain(|r| r.filter != FilterExpr::Requester(id.clone()));
+        implicit.rules.push(rule);
     }
-    
+
+    /// Add a layered permission profile, consulted after the implicit
+    /// `add_overrider` profile and after any previously added profile.
+    pub fn add_profile(&mut self, profile: OverrideProfile) {
+        self.profiles.push(profile);
+    }
+
+    /// Register `role`'s permission ceiling, consulted by
+    /// [`Self::resolve_role_permissions`]. A later call for the same role
+    /// replaces its earlier ceiling.
+    pub fn add_role_permissions(&mut self, role: impl Into<String>, permissions: OverridePermissions) {
+        self.role_permissions.insert(role.into(), permissions);
+    }
+
+    /// Install the role hierarchy [`Self::resolve_role_permissions`]
+    /// expands a request's roles through before matching. Without one,
+    /// only a request's own roles (not their inherited parents) match.
+    pub fn with_role_manager(mut self, role_manager: RoleManager) -> Self {
+        self.role_manager = Some(role_manager);
+        self
+    }
+
+    /// Among `roles` (expanded transitively through [`Self::role_manager`]
+    /// if one is installed), find the registered role with the highest
+    /// [`OverridePermissions::max_risk_level`] -- ties broken by
+    /// `allow_emergency`, then by role name, so the pick is deterministic.
+    /// Returns the winning role's name alongside its permissions, for
+    /// [`Self::request_override`] to record as the authorizing role.
+    fn resolve_role_permissions(&self, roles: &[String]) -> Option<(String, OverridePermissions)> {
+        let effective = self.expand_roles(roles);
+
+        effective
+            .into_iter()
+            .filter_map(|role| self.role_permissions.get(&role).cloned().map(|perms| (role, perms)))
+            .max_by(|(role_a, perms_a), (role_b, perms_b)| {
+                perms_a
+                    .max_risk_level
+                    .cmp(&perms_b.max_risk_level)
+                    .then(perms_a.allow_emergency.cmp(&perms_b.allow_emergency))
+                    .then(role_a.cmp(role_b))
+            })
+    }
+
+    /// Expand `roles` transitively through [`Self::role_manager`] if one is
+    /// installed, otherwise return them unchanged.
+    fn expand_roles(&self, roles: &[String]) -> Vec<String> {
+        match &self.role_manager {
+            Some(rm) => rm.expand(roles),
+            None => roles.to_vec(),
+        }
+    }
+
     /// Add an exemption
     pub fn add_exemption(&mut self, exemption: Exemption) {
         self.exemptions.insert(exemption.id.clone(), exemption);
@@ -136,41 +443,116 @@ impl OverrideManager {
         request: OverrideRequest,
         evaluation: &FullEvaluation,
     ) -> OverrideResult {
-        // Check if requester is authorized
-        let permissions = match self.authorized_overriders.get(&request.requester) {
-            Some(p) => p,
-            None => return OverrideResult::denied("Requester is not authorized for overrides"),
-        };
-        
-        // Check if override type is allowed
-        if !permissions.allowed_types.contains(&request.override_type) {
-            return OverrideResult::denied(format!(
-                "Requester is not authorized for {:?} overrides",
-                request.override_type
-            ));
-        }
-        
-        // Check risk level
-        if evaluation.risk_assessment.level > permissions.max_risk_level {
-            return OverrideResult::denied(format!(
-                "Risk level {} exceeds authorized maximum {}",
-                evaluation.risk_assessment.level,
-                permissions.max_risk_level
-            ));
-        }
-        
-        // Check for critical violations
-        let all_violations = evaluation.all_violations();
-        let has_critical = all_violations
-            .iter()
-            .any(|v| v.rule_id.contains("critical") || v.rule_id.contains("emergency"));
-        
-        if has_critical && request.override_type != OverrideType::Emergency {
-            return OverrideResult::denied(
-                "Critical violations require Emergency override type"
-            );
+        let mut once = false;
+        let mut permission_sources: Option<ResolvedSources> = None;
+        let mut authorizing_role: Option<String> = None;
+
+        if let Some(policy) = &self.policy {
+            if let Err(denial) = self.evaluate_policy(policy, &request, evaluation) {
+                return OverrideResult::denied(denial);
+            }
+        } else if self
+            .denied_pairs
+            .contains(&(request.requester.clone(), evaluation.policy_id.clone()))
+        {
+            return OverrideResult::denied("Requester was denied and remembered for this policy");
+        } else if let Some(resolved) =
+            resolve_permissions(&self.profiles, &request.requester, evaluation, &request.operation)
+        {
+            // Check if override type is allowed
+            if !resolved.allowed_types.contains(&request.override_type) {
+                return OverrideResult::denied(format!(
+                    "Requester is not authorized for {:?} overrides",
+                    request.override_type
+                ))
+                .with_permission_sources(resolved.sources);
+            }
+
+            // Check risk level
+            if evaluation.risk_assessment.level > resolved.max_risk_level {
+                let source = resolved
+                    .sources
+                    .max_risk_level
+                    .as_ref()
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "the default permission level".to_string());
+                return OverrideResult::denied(format!(
+                    "Risk level {} exceeds authorized maximum {} (from {})",
+                    evaluation.risk_assessment.level,
+                    resolved.max_risk_level,
+                    source,
+                ))
+                .with_permission_sources(resolved.sources);
+            }
+
+            // Check for critical violations
+            let all_violations = evaluation.all_violations();
+            let has_critical = all_violations
+                .iter()
+                .any(|v| v.rule_id.contains("critical") || v.rule_id.contains("emergency"));
+
+            if has_critical && request.override_type != OverrideType::Emergency {
+                return OverrideResult::denied(
+                    "Critical violations require Emergency override type"
+                )
+                .with_permission_sources(resolved.sources);
+            }
+
+            permission_sources = Some(resolved.sources);
+        } else if let Some((role, permissions)) = self.resolve_role_permissions(&request.roles) {
+            if !permissions.allowed_types.contains(&request.override_type) {
+                return OverrideResult::denied(format!(
+                    "Role '{role}' is not authorized for {:?} overrides",
+                    request.override_type
+                ));
+            }
+
+            if evaluation.risk_assessment.level > permissions.max_risk_level {
+                return OverrideResult::denied(format!(
+                    "Risk level {} exceeds role '{}'s authorized maximum {}",
+                    evaluation.risk_assessment.level, role, permissions.max_risk_level,
+                ));
+            }
+
+            let all_violations = evaluation.all_violations();
+            let has_critical = all_violations
+                .iter()
+                .any(|v| v.rule_id.contains("critical") || v.rule_id.contains("emergency"));
+
+            if has_critical && request.override_type != OverrideType::Emergency {
+                return OverrideResult::denied("Critical violations require Emergency override type");
+            }
+
+            authorizing_role = Some(role);
+        } else if let Some((min, max)) = self.promptable_range {
+            let risk = evaluation.risk_assessment.level;
+            if risk < min || risk > max {
+                return OverrideResult::denied("Requester is not authorized for overrides");
+            }
+
+            let ctx = PromptContext {
+                requester: &request.requester,
+                override_type: request.override_type,
+                risk_level: risk,
+                violations: &request.violations,
+            };
+            match self.prompt_callback.prompt(&ctx) {
+                Some(PromptResponse::Allow) => {}
+                Some(PromptResponse::AllowOnce) => once = true,
+                Some(PromptResponse::Deny) => {
+                    return OverrideResult::denied("Denied via interactive prompt");
+                }
+                Some(PromptResponse::DenyAndRemember) => {
+                    self.denied_pairs
+                        .insert((request.requester.clone(), evaluation.policy_id.clone()));
+                    return OverrideResult::denied("Denied via interactive prompt and remembered");
+                }
+                None => return OverrideResult::prompt_pending(),
+            }
+        } else {
+            return OverrideResult::denied("Requester is not authorized for overrides");
         }
-        
+
         // Grant the override
         let now = current_timestamp();
         let expires_at = request.duration_ms.map(|d| now + d);
@@ -190,8 +572,13 @@ impl OverrideManager {
             reason: request.reason.clone(),
             expires_at,
             overridden_violations: overridden_violations.clone(),
+            authorizing_role: authorizing_role.clone(),
         };
-        
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_override(request.override_type);
+        }
+
         // Record in history
         self.history.push(OverrideHistoryEntry {
             timestamp: now,
@@ -215,10 +602,121 @@ impl OverrideManager {
             request.override_type,
             request.reason
         ));
-        
-        OverrideResult::granted(record, new_verdict)
+
+        let revocation_id = format!("{}-{}", now, self.history.len());
+        let scope = TokenScope {
+            violations: Some(overridden_violations),
+            expires_at,
+            operation_patterns: None,
+        };
+        let token = OverrideToken::issue(
+            &self.signing_key,
+            revocation_id,
+            request.requester,
+            request.override_type,
+            request.reason,
+            scope,
+        );
+
+        let result = if once {
+            OverrideResult::granted_once_with_token(record, new_verdict, token)
+        } else {
+            OverrideResult::granted_with_token(record, new_verdict, token)
+        };
+
+        match permission_sources {
+            Some(sources) => result.with_permission_sources(sources),
+            None => result,
+        }
     }
-    
+
+    /// Invalidate every token carrying `revocation_id`, present or future --
+    /// [`OverrideManager::verify_token`] rejects any token whose id is in
+    /// this set regardless of signature or expiry.
+    pub fn revoke(&mut self, revocation_id: impl Into<String>) {
+        self.revoked.insert(revocation_id.into());
+    }
+
+    /// Re-check a caller-held [`OverrideToken`] against this manager's
+    /// revocation set, `now`, and `evaluation`'s current violations.
+    /// Rejects a token that is revoked, expired, has a broken signature
+    /// chain, was not issued by this manager's own signing key, or whose
+    /// effective scope does not cover every violation in
+    /// `evaluation.all_violations()`. The issuer check is what actually
+    /// ties a token back to this manager -- [`OverrideToken::effective_scope`]
+    /// only verifies that a chain is internally self-consistent, which a
+    /// self-issued attacker token satisfies just as well as a real one.
+    pub fn verify_token(&self, token: &OverrideToken, evaluation: &FullEvaluation, now: u64) -> OverrideResult {
+        if self.revoked.contains(&token.revocation_id) {
+            return OverrideResult::denied("Override token has been revoked");
+        }
+
+        if !token.issued_by(&self.signing_key.verifying_key()) {
+            return OverrideResult::denied("Override token was not issued by this manager");
+        }
+
+        let scope = match token.effective_scope() {
+            Ok(scope) => scope,
+            Err(e) => return OverrideResult::denied(format!("Override token failed verification: {}", e)),
+        };
+
+        if let Some(expires_at) = scope.expires_at {
+            if now > expires_at {
+                return OverrideResult::denied("Override token has expired");
+            }
+        }
+
+        let covered = evaluation.all_violations().iter().all(|v| match &scope.violations {
+            Some(allowed) => allowed.contains(&v.rule_id),
+            None => true,
+        });
+        if !covered {
+            return OverrideResult::denied("Override token does not cover all current violations");
+        }
+
+        let record = OverrideRecord {
+            override_type: token.override_type,
+            authorized_by: token.requester.clone(),
+            reason: token.reason.clone(),
+            expires_at: scope.expires_at,
+            overridden_violations: scope.violations.clone().unwrap_or_default(),
+            authorizing_role: None,
+        };
+        let new_verdict = Verdict::allow_with_message(format!(
+            "Overridden by {} ({}): {}",
+            token.requester, token.override_type, token.reason
+        ));
+
+        OverrideResult::granted_with_token(record, new_verdict, token.clone())
+    }
+
+    /// Derive facts from `request`/`evaluation` and evaluate `policy`
+    /// against them, returning `Ok(())` if granted or `Err(<denial reason>)`
+    /// otherwise -- a parse-time-valid policy can still fail to evaluate if
+    /// derivation hits [`crate::override_policy::OverridePolicyError`]'s
+    /// fact or iteration limits, which is surfaced the same way as any
+    /// other denial.
+    fn evaluate_policy(
+        &self,
+        policy: &OverridePolicy,
+        request: &OverrideRequest,
+        evaluation: &FullEvaluation,
+    ) -> Result<(), String> {
+        let facts = derive_facts(evaluation, request).map_err(|e| e.to_string())?;
+        match policy.evaluate(&facts).map_err(|e| e.to_string())? {
+            Ok(()) => Ok(()),
+            Err(OverrideDenial::NoAllowMatched) => {
+                Err("No policy 'allow' clause matched this request".to_string())
+            }
+            Err(OverrideDenial::DenyMatched) => {
+                Err("A policy 'deny' clause matched this request".to_string())
+            }
+            Err(OverrideDenial::CheckFailed) => {
+                Err("A policy 'check' clause did not hold for this request".to_string())
+            }
+        }
+    }
+
     /// Check for applicable exemptions
     pub fn check_exemptions(&self, policy_id: &str, operation: &str) -> Option<&Exemption> {
         let now = current_timestamp();
@@ -238,13 +736,55 @@ impl OverrideManager {
         operation: &str,
     ) -> Option<OverrideRecord> {
         let exemption = self.check_exemptions(&evaluation.policy_id, operation)?;
-        
+
+        Some(OverrideRecord {
+            override_type: OverrideType::Exemption,
+            authorized_by: exemption.created_by.clone(),
+            reason: exemption.reason.clone(),
+            expires_at: exemption.expires_at,
+            overridden_violations: exemption.exempt_violations.clone(),
+            authorizing_role: None,
+        })
+    }
+
+    /// Like [`Self::check_exemptions`], but also requires `roles` (expanded
+    /// transitively through [`Self::role_manager`]) to satisfy any
+    /// [`Exemption::required_role`] the matching exemption declares.
+    pub fn check_exemptions_for_roles(
+        &self,
+        policy_id: &str,
+        operation: &str,
+        roles: &[String],
+    ) -> Option<&Exemption> {
+        let now = current_timestamp();
+        let effective = self.expand_roles(roles);
+
+        self.exemptions.values().find(|e| {
+            e.policy_id == policy_id
+                && e.matches_operation(operation)
+                && !e.is_expired(now)
+                && e.matches_roles(&effective)
+        })
+    }
+
+    /// Role-aware counterpart to [`Self::apply_exemption`]: records the
+    /// matched exemption's [`Exemption::required_role`] as the granted
+    /// record's [`OverrideRecord::authorizing_role`], if any.
+    pub fn apply_exemption_for_roles(
+        &self,
+        evaluation: &FullEvaluation,
+        operation: &str,
+        roles: &[String],
+    ) -> Option<OverrideRecord> {
+        let exemption = self.check_exemptions_for_roles(&evaluation.policy_id, operation, roles)?;
+
         Some(OverrideRecord {
             override_type: OverrideType::Exemption,
             authorized_by: exemption.created_by.clone(),
             reason: exemption.reason.clone(),
             expires_at: exemption.expires_at,
             overridden_violations: exemption.exempt_violations.clone(),
+            authorizing_role: exemption.required_role.clone(),
         })
     }
     
@@ -351,6 +891,15 @@ pub struct Exemption {
     /// When it expires
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<u64>,
+    /// If set, only a requester holding this role (expanded transitively
+    /// through [`OverrideManager::role_manager`] the same way
+    /// [`OverrideManager::resolve_role_permissions`] does) can invoke this
+    /// exemption -- checked by
+    /// [`OverrideManager::check_exemptions_for_roles`]. `None` means the
+    /// exemption applies regardless of role, same as before this field
+    /// existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_role: Option<String>,
 }
 
 impl Exemption {
@@ -366,11 +915,20 @@ impl Exemption {
             }
         })
     }
-    
+
     /// Check if exemption is expired
     pub fn is_expired(&self, now: u64) -> bool {
         self.expires_at.map(|exp| now > exp).unwrap_or(false)
     }
+
+    /// Check if `roles` (already expanded) satisfies this exemption's
+    /// [`Self::required_role`], if any.
+    fn matches_roles(&self, roles: &[String]) -> bool {
+        match &self.required_role {
+            Some(required) => roles.iter().any(|r| r == required),
+            None => true,
+        }
+    }
 }
 
 /// History entry for an override
@@ -430,6 +988,180 @@ mod tests {
         
         assert!(!result.granted);
         assert!(result.denial_reason.is_some());
+        assert_eq!(result.outcome, OverrideOutcome::Denied);
+    }
+
+    struct ScriptedPrompter(PromptResponse);
+
+    impl OverridePrompter for ScriptedPrompter {
+        fn prompt(&self, _ctx: &PromptContext) -> Option<PromptResponse> {
+            Some(self.0)
+        }
+    }
+
+    #[test]
+    fn test_unauthorized_requester_outside_promptable_range_is_denied_without_prompting() {
+        let mut manager = OverrideManager::new();
+        manager.set_prompt_callback(Box::new(ScriptedPrompter(PromptResponse::Allow)));
+        manager.set_promptable_range(RiskLevel::Low, RiskLevel::Medium);
+
+        let evaluation = create_blocked_evaluation(); // risk level is Critical here
+        let request = OverrideRequest::new("unknown@example.com", "Test");
+        let result = manager.request_override(request, &evaluation);
+
+        assert_eq!(result.outcome, OverrideOutcome::Denied);
+    }
+
+    #[test]
+    fn test_prompt_allow_grants_durably() {
+        let mut manager = OverrideManager::new();
+        manager.set_prompt_callback(Box::new(ScriptedPrompter(PromptResponse::Allow)));
+        manager.set_promptable_range(RiskLevel::Low, RiskLevel::Critical);
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("unknown@example.com", "Test");
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(result.granted);
+        assert_eq!(result.outcome, OverrideOutcome::Granted);
+    }
+
+    #[test]
+    fn test_prompt_allow_once_grants_without_persisting() {
+        let mut manager = OverrideManager::new();
+        manager.set_prompt_callback(Box::new(ScriptedPrompter(PromptResponse::AllowOnce)));
+        manager.set_promptable_range(RiskLevel::Low, RiskLevel::Critical);
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("unknown@example.com", "Test");
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(result.granted);
+        assert_eq!(result.outcome, OverrideOutcome::GrantedOnce);
+        assert!(manager.profiles.is_empty());
+    }
+
+    #[test]
+    fn test_prompt_deny_denies_without_remembering() {
+        let mut manager = OverrideManager::new();
+        manager.set_prompt_callback(Box::new(ScriptedPrompter(PromptResponse::Deny)));
+        manager.set_promptable_range(RiskLevel::Low, RiskLevel::Critical);
+
+        let evaluation = create_blocked_evaluation();
+        let result = manager.request_override(
+            OverrideRequest::new("unknown@example.com", "Test"),
+            &evaluation,
+        );
+        assert_eq!(result.outcome, OverrideOutcome::Denied);
+
+        // A later request from the same requester is prompted again, not
+        // auto-denied, since `Deny` (unlike `DenyAndRemember`) isn't persisted.
+        manager.set_prompt_callback(Box::new(ScriptedPrompter(PromptResponse::Allow)));
+        let result = manager.request_override(
+            OverrideRequest::new("unknown@example.com", "Test"),
+            &evaluation,
+        );
+        assert!(result.granted);
+    }
+
+    #[test]
+    fn test_prompt_deny_and_remember_auto_denies_future_requests() {
+        let mut manager = OverrideManager::new();
+        manager.set_prompt_callback(Box::new(ScriptedPrompter(PromptResponse::DenyAndRemember)));
+        manager.set_promptable_range(RiskLevel::Low, RiskLevel::Critical);
+
+        let evaluation = create_blocked_evaluation();
+        let result = manager.request_override(
+            OverrideRequest::new("unknown@example.com", "Test"),
+            &evaluation,
+        );
+        assert_eq!(result.outcome, OverrideOutcome::Denied);
+
+        // Even if the callback would now allow it, the remembered denial wins.
+        manager.set_prompt_callback(Box::new(ScriptedPrompter(PromptResponse::Allow)));
+        let result = manager.request_override(
+            OverrideRequest::new("unknown@example.com", "Test"),
+            &evaluation,
+        );
+        assert_eq!(result.outcome, OverrideOutcome::Denied);
+    }
+
+    #[test]
+    fn test_noop_prompter_leaves_request_pending_in_promptable_range() {
+        let mut manager = OverrideManager::new();
+        manager.set_promptable_range(RiskLevel::Low, RiskLevel::Critical);
+
+        let evaluation = create_blocked_evaluation();
+        let result = manager.request_override(
+            OverrideRequest::new("unknown@example.com", "Test"),
+            &evaluation,
+        );
+
+        assert!(!result.granted);
+        assert_eq!(result.outcome, OverrideOutcome::Prompt);
+    }
+
+    #[test]
+    fn test_default_manager_preserves_original_authorized_list_behavior() {
+        // No promptable range configured: an unauthorized requester is
+        // denied outright, exactly as before this feature existed.
+        let mut manager = OverrideManager::new();
+        let evaluation = create_blocked_evaluation();
+        let result = manager.request_override(
+            OverrideRequest::new("unknown@example.com", "Test"),
+            &evaluation,
+        );
+
+        assert_eq!(result.outcome, OverrideOutcome::Denied);
+        assert!(result
+            .denial_reason
+            .unwrap()
+            .contains("not authorized for overrides"));
+    }
+
+    #[test]
+    fn test_override_with_declarative_policy_grants_on_matching_allow() {
+        let policy = crate::override_policy::parse_policy(
+            r#"allow if requester("admin@example.com")"#,
+        )
+        .unwrap();
+        let mut manager = OverrideManager::new().with_policy(policy);
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("admin@example.com", "Urgent fix needed");
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(result.granted);
+    }
+
+    #[test]
+    fn test_override_with_declarative_policy_denies_unmatched_requester() {
+        let policy = crate::override_policy::parse_policy(
+            r#"allow if requester("admin@example.com")"#,
+        )
+        .unwrap();
+        let mut manager = OverrideManager::new().with_policy(policy);
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("someone-else@example.com", "Test");
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(!result.granted);
+    }
+
+    #[test]
+    fn test_override_with_declarative_policy_deny_clause_wins() {
+        let policy = crate::override_policy::parse_policy(
+            "allow if requester(\"admin@example.com\")\ndeny if violation(\"max_files_exceeded\")\n",
+        )
+        .unwrap();
+        let mut manager = OverrideManager::new().with_policy(policy);
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("admin@example.com", "Test");
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(!result.granted);
     }
 
     #[test]
@@ -479,6 +1211,7 @@ mod tests {
             reason: "CI/CD needs more files".to_string(),
             created_by: "admin".to_string(),
             expires_at: None,
+            required_role: None,
         });
         
         let exemption = manager.check_exemptions("mechanic@1.0", "deploy-staging");
@@ -506,6 +1239,104 @@ mod tests {
         assert_eq!(manager.history().len(), 5);
     }
 
+    #[test]
+    fn test_override_grant_includes_a_verifiable_token() {
+        let mut manager = OverrideManager::new();
+        manager.add_overrider("admin@example.com".to_string(), OverridePermissions::admin());
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("admin@example.com", "Urgent fix needed");
+        let result = manager.request_override(request, &evaluation);
+
+        let token = result.token.expect("granted override should carry a token");
+        assert_eq!(token.requester, "admin@example.com");
+        assert!(token.effective_scope().is_ok());
+    }
+
+    #[test]
+    fn request_override_increments_installed_metrics_by_type() {
+        let metrics = PolicyMetrics::new();
+        let mut manager = OverrideManager::new().with_metrics(metrics.clone());
+        manager.add_overrider("admin@example.com".to_string(), OverridePermissions::admin());
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("admin@example.com", "Urgent fix needed")
+            .with_type(OverrideType::Emergency);
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(result.granted);
+        assert_eq!(metrics.override_count(OverrideType::Emergency), 1.0);
+        assert_eq!(metrics.override_count(OverrideType::Waiver), 0.0);
+    }
+
+    #[test]
+    fn test_verify_token_rejects_a_revoked_token() {
+        let mut manager = OverrideManager::new();
+        manager.add_overrider("admin@example.com".to_string(), OverridePermissions::admin());
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("admin@example.com", "Urgent fix needed");
+        let token = manager.request_override(request, &evaluation).token.unwrap();
+
+        manager.revoke(token.revocation_id.clone());
+        let result = manager.verify_token(&token, &evaluation, current_timestamp());
+
+        assert!(!result.granted);
+        assert!(result.denial_reason.unwrap().contains("revoked"));
+    }
+
+    #[test]
+    fn test_verify_token_rejects_an_expired_token() {
+        let mut manager = OverrideManager::new();
+        manager.add_overrider("admin@example.com".to_string(), OverridePermissions::admin());
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("admin@example.com", "Urgent fix").with_duration(1000);
+        let token = manager.request_override(request, &evaluation).token.unwrap();
+
+        let result = manager.verify_token(&token, &evaluation, current_timestamp() + 10_000);
+
+        assert!(!result.granted);
+        assert!(result.denial_reason.unwrap().contains("expired"));
+    }
+
+    #[test]
+    fn test_verify_token_rejects_a_token_out_of_scope_of_current_violations() {
+        let mut manager = OverrideManager::new();
+        manager.add_overrider("admin@example.com".to_string(), OverridePermissions::admin());
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("admin@example.com", "Urgent fix")
+            .for_violations(vec!["max_lines_exceeded".to_string()]);
+        let token = manager.request_override(request, &evaluation).token.unwrap();
+
+        let result = manager.verify_token(&token, &evaluation, current_timestamp());
+
+        assert!(!result.granted);
+        assert!(result.denial_reason.unwrap().contains("cover"));
+    }
+
+    #[test]
+    fn test_verify_token_rejects_a_self_issued_token_from_an_untrusted_key() {
+        let manager = OverrideManager::new();
+        let evaluation = create_blocked_evaluation();
+
+        let attacker_key = SigningKey::generate(&mut OsRng);
+        let forged = OverrideToken::issue(
+            &attacker_key,
+            "forged-rev",
+            "attacker@example.com",
+            OverrideType::Emergency,
+            "not actually authorized",
+            TokenScope::unrestricted(),
+        );
+
+        let result = manager.verify_token(&forged, &evaluation, current_timestamp());
+
+        assert!(!result.granted);
+        assert!(result.denial_reason.unwrap().contains("not issued by this manager"));
+    }
+
     #[test]
     fn test_exemption_expiry() {
         let exemption = Exemption {
@@ -516,10 +1347,188 @@ mod tests {
             reason: "Test".to_string(),
             created_by: "admin".to_string(),
             expires_at: Some(1000), // Expired
+            required_role: None,
         };
         
         assert!(exemption.is_expired(2000));
         assert!(!exemption.is_expired(500));
     }
+
+    #[test]
+    fn test_role_permissions_grant_an_override_and_record_the_authorizing_role() {
+        let mut manager = OverrideManager::new();
+        manager.add_role_permissions(
+            "release-manager",
+            OverridePermissions {
+                allowed_types: vec![OverrideType::ManualApproval],
+                max_risk_level: RiskLevel::Critical,
+                max_violations: None,
+                allow_emergency: false,
+            },
+        );
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("unknown@example.com", "Shipping a hotfix")
+            .with_roles(vec!["release-manager".to_string()]);
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(result.granted);
+        assert_eq!(
+            result.record.unwrap().authorizing_role,
+            Some("release-manager".to_string())
+        );
+    }
+
+    #[test]
+    fn test_role_permissions_are_resolved_through_transitive_inheritance() {
+        let mut manager = OverrideManager::new();
+        manager.add_role_permissions(
+            "engineer",
+            OverridePermissions {
+                allowed_types: vec![OverrideType::ManualApproval],
+                max_risk_level: RiskLevel::Critical,
+                max_violations: None,
+                allow_emergency: false,
+            },
+        );
+        let mut manager = manager.with_role_manager(
+            RoleManager::new().with_inheritance("junior-engineer", "engineer"),
+        );
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("unknown@example.com", "Inherited role")
+            .with_roles(vec!["junior-engineer".to_string()]);
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(result.granted);
+        assert_eq!(
+            result.record.unwrap().authorizing_role,
+            Some("engineer".to_string())
+        );
+    }
+
+    #[test]
+    fn test_role_permissions_pick_the_highest_privilege_role_deterministically() {
+        let mut manager = OverrideManager::new();
+        manager.add_role_permissions(
+            "reviewer",
+            OverridePermissions {
+                allowed_types: vec![OverrideType::ManualApproval],
+                max_risk_level: RiskLevel::Medium,
+                max_violations: None,
+                allow_emergency: false,
+            },
+        );
+        manager.add_role_permissions(
+            "release-manager",
+            OverridePermissions {
+                allowed_types: vec![OverrideType::ManualApproval],
+                max_risk_level: RiskLevel::Critical,
+                max_violations: None,
+                allow_emergency: false,
+            },
+        );
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("unknown@example.com", "Two roles held")
+            .with_roles(vec!["reviewer".to_string(), "release-manager".to_string()]);
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(result.granted);
+        assert_eq!(
+            result.record.unwrap().authorizing_role,
+            Some("release-manager".to_string())
+        );
+    }
+
+    #[test]
+    fn test_role_permissions_deny_an_override_type_the_role_is_not_authorized_for() {
+        let mut manager = OverrideManager::new();
+        manager.add_role_permissions(
+            "release-manager",
+            OverridePermissions {
+                allowed_types: vec![OverrideType::Waiver],
+                max_risk_level: RiskLevel::Critical,
+                max_violations: None,
+                allow_emergency: false,
+            },
+        );
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("unknown@example.com", "Wrong type")
+            .with_roles(vec!["release-manager".to_string()]);
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(!result.granted);
+        assert!(result.denial_reason.unwrap().contains("not authorized"));
+    }
+
+    #[test]
+    fn test_role_permissions_deny_when_risk_level_exceeds_the_roles_ceiling() {
+        let mut manager = OverrideManager::new();
+        manager.add_role_permissions(
+            "release-manager",
+            OverridePermissions {
+                allowed_types: vec![OverrideType::ManualApproval],
+                max_risk_level: RiskLevel::Low,
+                max_violations: None,
+                allow_emergency: false,
+            },
+        );
+
+        let evaluation = create_blocked_evaluation();
+        let request = OverrideRequest::new("unknown@example.com", "Too risky")
+            .with_roles(vec!["release-manager".to_string()]);
+        let result = manager.request_override(request, &evaluation);
+
+        assert!(!result.granted);
+        assert!(result.denial_reason.unwrap().contains("exceeds"));
+    }
+
+    #[test]
+    fn test_exemption_scoped_to_a_role_is_invisible_to_requesters_without_it() {
+        let mut manager = OverrideManager::new();
+        manager.add_exemption(Exemption {
+            id: "ex-role".to_string(),
+            policy_id: "mechanic@1.0".to_string(),
+            operation_patterns: vec!["*".to_string()],
+            exempt_violations: vec!["max_files_exceeded".to_string()],
+            reason: "Release window".to_string(),
+            created_by: "admin".to_string(),
+            expires_at: None,
+            required_role: Some("release-manager".to_string()),
+        });
+
+        let none = vec![];
+        let found = manager.check_exemptions_for_roles("mechanic@1.0", "deploy-staging", &none);
+        assert!(found.is_none());
+
+        let roles = vec!["release-manager".to_string()];
+        let found = manager.check_exemptions_for_roles("mechanic@1.0", "deploy-staging", &roles);
+        assert!(found.is_some());
+    }
+
+    #[test]
+    fn test_apply_exemption_for_roles_records_the_required_role_as_authorizing() {
+        let mut manager = OverrideManager::new();
+        manager.add_exemption(Exemption {
+            id: "ex-role".to_string(),
+            policy_id: "mechanic@1.0".to_string(),
+            operation_patterns: vec!["*".to_string()],
+            exempt_violations: vec!["max_files_exceeded".to_string()],
+            reason: "Release window".to_string(),
+            created_by: "admin".to_string(),
+            expires_at: None,
+            required_role: Some("release-manager".to_string()),
+        });
+
+        let evaluation = create_blocked_evaluation();
+        let roles = vec!["release-manager".to_string()];
+        let record = manager
+            .apply_exemption_for_roles(&evaluation, "deploy-staging", &roles)
+            .unwrap();
+
+        assert_eq!(record.authorizing_role, Some("release-manager".to_string()));
+    }
 }
 
@@ -0,0 +1,415 @@
+//! Signed, block-scoped override tokens.
+//!
+//! A granted override used to be just a plain [`crate::audit::OverrideRecord`]
+//! -- data with no way to verify who issued it, or to hand a caller a
+//! narrower grant than the one [`crate::override_system::OverrideManager`]
+//! holds internally. [`OverrideToken`] wraps a grant in a root
+//! [`TokenBlock`], signed Ed25519, the same block-scoped biscuit-style
+//! design `tdln_truthpack::delegation` uses for capability chains:
+//! [`OverrideToken::attenuate`] appends a further block that can only
+//! narrow the token's [`TokenScope`] -- fewer overridden violations, an
+//! earlier expiry, a narrower operation-pattern allowlist -- never widen
+//! it, and [`verify_chain`] replays every block's signature and folds them
+//! down to their intersection rather than trusting whichever block claims
+//! to be the tightest.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::audit::OverrideType;
+
+/// Errors verifying or attenuating an [`OverrideToken`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum OverrideTokenError {
+    #[error("attenuation would widen the token's scope instead of narrowing it")]
+    OverBroadAttenuation,
+    #[error("bad signature at block {0}: {1}")]
+    BadSignature(usize, String),
+    #[error("block {0} does not chain to its parent")]
+    BrokenChain(usize),
+    #[error("a token must have at least one (root) block")]
+    EmptyToken,
+}
+
+/// The narrowable scope an [`OverrideToken`] authorizes. `None` in any
+/// field means "unrestricted" for that dimension -- the widest a block can
+/// claim; every [`TokenBlock::scope`] is intersected with its predecessors'
+/// at verify time via [`TokenScope::intersect`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenScope {
+    /// Rule ids this token may override; `None` covers all violations.
+    #[serde(default)]
+    pub violations: Option<Vec<String>>,
+    /// Unix ms after which the token is no longer valid; `None` means no
+    /// expiry is set at this block.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    /// Operation name patterns (same `*`-suffix matching as
+    /// [`crate::override_system::Exemption::matches_operation`]) this token
+    /// applies to; `None` means unrestricted.
+    #[serde(default)]
+    pub operation_patterns: Option<Vec<String>>,
+}
+
+impl TokenScope {
+    /// The widest possible scope: no restriction on any dimension.
+    pub fn unrestricted() -> Self {
+        TokenScope {
+            violations: None,
+            expires_at: None,
+            operation_patterns: None,
+        }
+    }
+
+    /// `true` if `self` is equal to or strictly narrower than `parent` on
+    /// every dimension -- `self` restricting a dimension `parent` left
+    /// unrestricted is narrowing; `self` leaving a dimension unrestricted
+    /// that `parent` had already restricted would be widening.
+    fn attenuates(&self, parent: &TokenScope) -> bool {
+        subset_ok(&self.violations, &parent.violations)
+            && le_ok(self.expires_at, parent.expires_at)
+            && subset_ok(&self.operation_patterns, &parent.operation_patterns)
+    }
+
+    /// The tightest scope both `self` and `other` allow.
+    fn intersect(&self, other: &TokenScope) -> TokenScope {
+        TokenScope {
+            violations: intersect_opt_vec(&self.violations, &other.violations),
+            expires_at: min_opt(self.expires_at, other.expires_at),
+            operation_patterns: intersect_opt_vec(&self.operation_patterns, &other.operation_patterns),
+        }
+    }
+}
+
+fn subset_ok(child: &Option<Vec<String>>, parent: &Option<Vec<String>>) -> bool {
+    match (child, parent) {
+        (Some(child), Some(parent)) => child.iter().all(|v| parent.contains(v)),
+        (Some(_), None) => true,
+        (None, Some(_)) => false,
+        (None, None) => true,
+    }
+}
+
+fn le_ok(child: Option<u64>, parent: Option<u64>) -> bool {
+    match (child, parent) {
+        (Some(child), Some(parent)) => child <= parent,
+        (Some(_), None) => true,
+        (None, Some(_)) => false,
+        (None, None) => true,
+    }
+}
+
+fn intersect_opt_vec(a: &Option<Vec<String>>, b: &Option<Vec<String>>) -> Option<Vec<String>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.iter().filter(|v| b.contains(v)).cloned().collect()),
+        (Some(a), None) => Some(a.clone()),
+        (None, Some(b)) => Some(b.clone()),
+        (None, None) => None,
+    }
+}
+
+fn min_opt(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (Some(a), None) => Some(a),
+        (None, Some(b)) => Some(b),
+        (None, None) => None,
+    }
+}
+
+/// One signed block in an [`OverrideToken`]'s chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenBlock {
+    pub scope: TokenScope,
+    /// blake3 hex of the block before this one; `None` for the root block.
+    pub parent_cid: Option<String>,
+    /// Hex-encoded Ed25519 signature over `(scope, parent_cid)`.
+    pub signature: String,
+}
+
+impl TokenBlock {
+    fn signing_payload(scope: &TokenScope, parent_cid: Option<&str>) -> Vec<u8> {
+        let mut buf = serde_json::to_vec(scope).expect("TokenScope always serializes");
+        buf.extend_from_slice(parent_cid.unwrap_or("").as_bytes());
+        buf
+    }
+
+    /// Content address of this block: `blake3(signing payload ‖ signature)`.
+    pub fn cid(&self) -> String {
+        let mut buf = Self::signing_payload(&self.scope, self.parent_cid.as_deref());
+        buf.extend_from_slice(self.signature.as_bytes());
+        encode_hex(blake3::hash(&buf).as_bytes())
+    }
+}
+
+/// A signed, attenuable override grant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverrideToken {
+    /// Unique id; [`crate::override_system::OverrideManager::revoke`] adds
+    /// it to a revocation set to invalidate every block of this token.
+    pub revocation_id: String,
+    pub requester: String,
+    pub override_type: OverrideType,
+    pub reason: String,
+    /// Hex-encoded Ed25519 public key of the issuing manager.
+    pub issuer: String,
+    /// Root block first, most recent attenuation last.
+    pub blocks: Vec<TokenBlock>,
+}
+
+impl OverrideToken {
+    /// Issue a new token with a single root block, signed by `signing_key`.
+    pub fn issue(
+        signing_key: &SigningKey,
+        revocation_id: impl Into<String>,
+        requester: impl Into<String>,
+        override_type: OverrideType,
+        reason: impl Into<String>,
+        scope: TokenScope,
+    ) -> Self {
+        let payload = TokenBlock::signing_payload(&scope, None);
+        let signature = encode_hex(&signing_key.sign(&payload).to_bytes());
+
+        OverrideToken {
+            revocation_id: revocation_id.into(),
+            requester: requester.into(),
+            override_type,
+            reason: reason.into(),
+            issuer: encode_hex(signing_key.verifying_key().as_bytes()),
+            blocks: vec![TokenBlock {
+                scope,
+                parent_cid: None,
+                signature,
+            }],
+        }
+    }
+
+    /// Append a new block narrowing this token to `scope`, signed with the
+    /// same key that issued the root block. Errors if `scope` would widen
+    /// -- rather than narrow -- the token's current effective scope.
+    pub fn attenuate(&self, signing_key: &SigningKey, scope: TokenScope) -> Result<OverrideToken, OverrideTokenError> {
+        let current = self.effective_scope()?;
+        if !scope.attenuates(&current) {
+            return Err(OverrideTokenError::OverBroadAttenuation);
+        }
+
+        let parent = self.blocks.last().ok_or(OverrideTokenError::EmptyToken)?;
+        let parent_cid = parent.cid();
+        let payload = TokenBlock::signing_payload(&scope, Some(&parent_cid));
+        let signature = encode_hex(&signing_key.sign(&payload).to_bytes());
+
+        let mut token = self.clone();
+        token.blocks.push(TokenBlock {
+            scope,
+            parent_cid: Some(parent_cid),
+            signature,
+        });
+        Ok(token)
+    }
+
+    /// The scope this token currently authorizes: every block's signature
+    /// and chain link verified, then folded down to their intersection.
+    pub fn effective_scope(&self) -> Result<TokenScope, OverrideTokenError> {
+        verify_chain(self)
+    }
+
+    /// `true` if `self.issuer` is `trusted`'s hex encoding -- callers (e.g.
+    /// [`crate::override_system::OverrideManager::verify_token`]) must check
+    /// this against their own signing key before trusting
+    /// [`Self::effective_scope`]: a chain verifies fine against *any*
+    /// self-consistent issuer, including one an attacker generated
+    /// themselves, so this is the only thing standing between "well-formed
+    /// token" and "token this manager actually issued".
+    pub fn issued_by(&self, trusted: &VerifyingKey) -> bool {
+        self.issuer == encode_hex(trusted.as_bytes())
+    }
+}
+
+/// Verify every block in `token`'s chain -- signature, and (for non-root
+/// blocks) that `parent_cid` matches the previous block's content address
+/// -- then return the intersection of all blocks' scopes.
+fn verify_chain(token: &OverrideToken) -> Result<TokenScope, OverrideTokenError> {
+    let issuer_vk =
+        parse_verifying_key(&token.issuer).ok_or_else(|| OverrideTokenError::BadSignature(0, "malformed issuer key".to_string()))?;
+
+    let mut scope = TokenScope::unrestricted();
+    for (i, block) in token.blocks.iter().enumerate() {
+        if i == 0 {
+            if block.parent_cid.is_some() {
+                return Err(OverrideTokenError::BrokenChain(i));
+            }
+        } else {
+            let expected = token.blocks[i - 1].cid();
+            if block.parent_cid.as_deref() != Some(expected.as_str()) {
+                return Err(OverrideTokenError::BrokenChain(i));
+            }
+        }
+
+        let payload = TokenBlock::signing_payload(&block.scope, block.parent_cid.as_deref());
+        verify_signature(&issuer_vk, &payload, &block.signature)
+            .map_err(|e| OverrideTokenError::BadSignature(i, e))?;
+
+        scope = scope.intersect(&block.scope);
+    }
+
+    if token.blocks.is_empty() {
+        return Err(OverrideTokenError::EmptyToken);
+    }
+
+    Ok(scope)
+}
+
+fn parse_verifying_key(hex_key: &str) -> Option<VerifyingKey> {
+    VerifyingKey::from_bytes(&decode_hex_32(hex_key)?).ok()
+}
+
+fn verify_signature(key: &VerifyingKey, payload: &[u8], signature_hex: &str) -> Result<(), String> {
+    let bytes = decode_hex_64(signature_hex).ok_or_else(|| "malformed signature".to_string())?;
+    let signature = Signature::from_bytes(&bytes);
+    key.verify(payload, &signature).map_err(|e| e.to_string())
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex_32(s: &str) -> Option<[u8; 32]> {
+    decode_hex(s)?.try_into().ok()
+}
+
+fn decode_hex_64(s: &str) -> Option<[u8; 64]> {
+    decode_hex(s)?.try_into().ok()
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_from_seed(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn root_token(key: &SigningKey, scope: TokenScope) -> OverrideToken {
+        OverrideToken::issue(key, "rev-1", "admin@x", OverrideType::Emergency, "urgent fix", scope)
+    }
+
+    #[test]
+    fn a_freshly_issued_token_verifies_to_its_root_scope() {
+        let key = key_from_seed(1);
+        let scope = TokenScope {
+            violations: Some(vec!["max_files_exceeded".to_string()]),
+            expires_at: Some(1_000),
+            operation_patterns: None,
+        };
+        let token = root_token(&key, scope.clone());
+        assert_eq!(token.effective_scope().unwrap(), scope);
+    }
+
+    #[test]
+    fn attenuation_narrows_the_effective_scope() {
+        let key = key_from_seed(1);
+        let token = root_token(
+            &key,
+            TokenScope {
+                violations: Some(vec!["max_files_exceeded".to_string(), "max_lines_exceeded".to_string()]),
+                expires_at: Some(10_000),
+                operation_patterns: None,
+            },
+        );
+
+        let narrowed = token
+            .attenuate(
+                &key,
+                TokenScope {
+                    violations: Some(vec!["max_files_exceeded".to_string()]),
+                    expires_at: Some(5_000),
+                    operation_patterns: None,
+                },
+            )
+            .unwrap();
+
+        let effective = narrowed.effective_scope().unwrap();
+        assert_eq!(effective.violations, Some(vec!["max_files_exceeded".to_string()]));
+        assert_eq!(effective.expires_at, Some(5_000));
+    }
+
+    #[test]
+    fn attenuation_cannot_widen_violations() {
+        let key = key_from_seed(1);
+        let token = root_token(
+            &key,
+            TokenScope {
+                violations: Some(vec!["max_files_exceeded".to_string()]),
+                expires_at: None,
+                operation_patterns: None,
+            },
+        );
+
+        let widened = token.attenuate(
+            &key,
+            TokenScope {
+                violations: Some(vec!["max_files_exceeded".to_string(), "max_lines_exceeded".to_string()]),
+                expires_at: None,
+                operation_patterns: None,
+            },
+        );
+
+        assert_eq!(widened, Err(OverrideTokenError::OverBroadAttenuation));
+    }
+
+    #[test]
+    fn attenuation_cannot_widen_an_expiry() {
+        let key = key_from_seed(1);
+        let token = root_token(
+            &key,
+            TokenScope {
+                violations: None,
+                expires_at: Some(1_000),
+                operation_patterns: None,
+            },
+        );
+
+        let widened = token.attenuate(
+            &key,
+            TokenScope {
+                violations: None,
+                expires_at: Some(2_000),
+                operation_patterns: None,
+            },
+        );
+
+        assert_eq!(widened, Err(OverrideTokenError::OverBroadAttenuation));
+    }
+
+    #[test]
+    fn a_tampered_block_fails_signature_verification() {
+        let key = key_from_seed(1);
+        let mut token = root_token(&key, TokenScope::unrestricted());
+        token.blocks[0].signature = "00".repeat(64);
+
+        assert_eq!(token.effective_scope(), Err(OverrideTokenError::BadSignature(0, "signature error: Verification equation was not satisfied".to_string())));
+    }
+
+    #[test]
+    fn a_block_with_a_mismatched_parent_cid_breaks_the_chain() {
+        let key = key_from_seed(1);
+        let token = root_token(&key, TokenScope::unrestricted());
+        let mut attenuated = token
+            .attenuate(&key, TokenScope::unrestricted())
+            .unwrap();
+        attenuated.blocks[1].parent_cid = Some("00".repeat(32));
+
+        assert_eq!(attenuated.effective_scope(), Err(OverrideTokenError::BrokenChain(1)));
+    }
+}
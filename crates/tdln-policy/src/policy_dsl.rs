@@ -0,0 +1,503 @@
+//! Declarative, logic-based policy language for [`crate::policy_set::PolicySet`].
+//!
+//! `PolicySet` could previously only hold [`crate::rule::PolicyRule`]s
+//! constructed in Rust (`default_rules`, `with_rule`), so a tenant couldn't
+//! author or ship their own rules without recompiling. This module adds a
+//! small clause language, one clause per line, inspired by the clause/rule
+//! model in policy engines like Polar:
+//!
+//! ```text
+//! error "no_prod_destructive": is_destructive and targets_production
+//! warning "large_change": file_count > 20
+//! critical "tests_failing": tests_passed == false
+//! ```
+//!
+//! Each clause is `<severity> "<name>": <condition>`, where `severity` is
+//! one of `info` / `warning` / `error` / `critical` and `condition` is a
+//! boolean expression over [`crate::rule::RuleContext`] fields using `==`,
+//! `!=`, `>`, `<`, `>=`, `<=`, `and`, `or`, `not`, parentheses, and string/
+//! number/bool literals. A clause whose condition evaluates `true` describes
+//! a violation -- the mirror image of [`crate::rule::RuleCondition`], whose
+//! conditions describe what must hold to *pass*. Blank lines and lines
+//! starting with `#` are ignored.
+//!
+//! Fields exposed: `operation_type`, `mode` (strings), `file_count`,
+//! `line_count` (numbers), `is_destructive`, `targets_production`,
+//! `tests_passed`, `lint_passed`, `has_confirmation`, `affects_critical_files`
+//! (booleans; the `Option<bool>` fields read as `false` when unset).
+
+use crate::rule::{RuleContext, RuleSeverity};
+use crate::verdict::{Violation, ViolationSeverity};
+
+/// Error parsing a policy DSL document.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("line {line}: {message}")]
+pub struct PolicyDslError {
+    /// 1-based line number the error occurred on.
+    pub line: usize,
+    /// Description of what went wrong.
+    pub message: String,
+}
+
+/// One compiled clause, ready to evaluate against a [`RuleContext`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledClause {
+    /// Severity emitted when this clause's condition matches.
+    pub severity: RuleSeverity,
+    /// Clause name, used as both the violation's rule id and rule name.
+    pub name: String,
+    condition: Expr,
+}
+
+impl CompiledClause {
+    /// Evaluate this clause's condition against `context`, returning a
+    /// [`Violation`] if it holds.
+    pub fn evaluate(&self, context: &RuleContext) -> Option<Violation> {
+        if !self.condition.eval_bool(context) {
+            return None;
+        }
+
+        let severity = match self.severity {
+            RuleSeverity::Info => ViolationSeverity::Info,
+            RuleSeverity::Warning => ViolationSeverity::Warning,
+            RuleSeverity::Error => ViolationSeverity::Error,
+            RuleSeverity::Critical => ViolationSeverity::Critical,
+        };
+
+        Some(
+            Violation::new(
+                &self.name,
+                &self.name,
+                format!("Policy clause '{}' matched", self.name),
+            )
+            .with_severity(severity),
+        )
+    }
+}
+
+/// Parse a full policy document: one clause per non-blank, non-comment
+/// line.
+pub fn parse_source(source: &str) -> Result<Vec<CompiledClause>, PolicyDslError> {
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                None
+            } else {
+                Some((i + 1, trimmed))
+            }
+        })
+        .map(|(line_no, line)| {
+            parse_clause(line).map_err(|message| PolicyDslError {
+                line: line_no,
+                message,
+            })
+        })
+        .collect()
+}
+
+fn parse_clause(line: &str) -> Result<CompiledClause, String> {
+    let tokens = tokenize(line)?;
+    let mut parser = Parser { tokens, pos: 0 };
+
+    let severity = parser.expect_severity()?;
+    let name = parser.expect_name()?;
+    parser.expect_colon()?;
+    let condition = parser.parse_or()?;
+
+    if parser.pos != parser.tokens.len() {
+        return Err(format!("unexpected trailing input at token {}", parser.pos));
+    }
+
+    Ok(CompiledClause {
+        severity,
+        name,
+        condition,
+    })
+}
+
+// === Expression language ===
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    And,
+    Or,
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Lit(Value),
+    Field(String),
+    Not(Box<Expr>),
+    BinOp(BinOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, context: &RuleContext) -> Value {
+        match self {
+            Expr::Lit(v) => v.clone(),
+            Expr::Field(name) => field_value(context, name),
+            Expr::Not(inner) => Value::Bool(!truthy(&inner.eval(context))),
+            Expr::BinOp(BinOp::And, l, r) => {
+                Value::Bool(truthy(&l.eval(context)) && truthy(&r.eval(context)))
+            }
+            Expr::BinOp(BinOp::Or, l, r) => {
+                Value::Bool(truthy(&l.eval(context)) || truthy(&r.eval(context)))
+            }
+            Expr::BinOp(op, l, r) => Value::Bool(compare(*op, &l.eval(context), &r.eval(context))),
+        }
+    }
+
+    fn eval_bool(&self, context: &RuleContext) -> bool {
+        truthy(&self.eval(context))
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    matches!(value, Value::Bool(true))
+}
+
+fn field_value(context: &RuleContext, name: &str) -> Value {
+    match name {
+        "operation_type" => Value::Str(context.operation_type.clone()),
+        "mode" => Value::Str(context.mode.clone()),
+        "file_count" => Value::Num(context.file_count as f64),
+        "line_count" => Value::Num(context.line_count as f64),
+        "is_destructive" => Value::Bool(context.is_destructive),
+        "targets_production" => Value::Bool(context.targets_production),
+        "tests_passed" => Value::Bool(context.tests_passed == Some(true)),
+        "lint_passed" => Value::Bool(context.lint_passed == Some(true)),
+        "has_confirmation" => Value::Bool(context.has_confirmation),
+        "affects_critical_files" => Value::Bool(context.affects_critical_files),
+        // Unknown fields evaluate to an inert value rather than panicking --
+        // a condition referencing a typo'd field just never matches.
+        _ => Value::Bool(false),
+    }
+}
+
+fn compare(op: BinOp, left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Str(a), Value::Str(b)) => match op {
+            BinOp::Eq => a == b,
+            BinOp::Ne => a != b,
+            _ => false,
+        },
+        (Value::Num(a), Value::Num(b)) => match op {
+            BinOp::Eq => a == b,
+            BinOp::Ne => a != b,
+            BinOp::Gt => a > b,
+            BinOp::Lt => a < b,
+            BinOp::Ge => a >= b,
+            BinOp::Le => a <= b,
+            _ => false,
+        },
+        (Value::Bool(a), Value::Bool(b)) => match op {
+            BinOp::Eq => a == b,
+            BinOp::Ne => a != b,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// === Tokenizer / parser ===
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Colon,
+    And,
+    Or,
+    Not,
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    LParen,
+    RParen,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '"' {
+            let mut s = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                s.push(chars[i]);
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err("unterminated string literal".to_string());
+            }
+            i += 1;
+            tokens.push(Token::Str(s));
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let n = text
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number literal '{text}'"))?;
+            tokens.push(Token::Num(n));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push(match word.as_str() {
+                "and" => Token::And,
+                "or" => Token::Or,
+                "not" => Token::Not,
+                _ => Token::Ident(word),
+            });
+        } else {
+            match c {
+                ':' => {
+                    tokens.push(Token::Colon);
+                    i += 1;
+                }
+                '(' => {
+                    tokens.push(Token::LParen);
+                    i += 1;
+                }
+                ')' => {
+                    tokens.push(Token::RParen);
+                    i += 1;
+                }
+                '=' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Eq);
+                    i += 2;
+                }
+                '!' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Ne);
+                    i += 2;
+                }
+                '>' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Ge);
+                    i += 2;
+                }
+                '>' => {
+                    tokens.push(Token::Gt);
+                    i += 1;
+                }
+                '<' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Le);
+                    i += 2;
+                }
+                '<' => {
+                    tokens.push(Token::Lt);
+                    i += 1;
+                }
+                other => return Err(format!("unexpected character '{other}'")),
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn expect_severity(&mut self) -> Result<RuleSeverity, String> {
+        match self.bump() {
+            Some(Token::Ident(word)) => match word.as_str() {
+                "info" => Ok(RuleSeverity::Info),
+                "warning" | "warn" => Ok(RuleSeverity::Warning),
+                "error" => Ok(RuleSeverity::Error),
+                "critical" => Ok(RuleSeverity::Critical),
+                other => Err(format!("unknown severity '{other}'")),
+            },
+            other => Err(format!("expected a severity, got {other:?}")),
+        }
+    }
+
+    fn expect_name(&mut self) -> Result<String, String> {
+        match self.bump() {
+            Some(Token::Str(s)) => Ok(s),
+            other => Err(format!("expected a quoted clause name, got {other:?}")),
+        }
+    }
+
+    fn expect_colon(&mut self) -> Result<(), String> {
+        match self.bump() {
+            Some(Token::Colon) => Ok(()),
+            other => Err(format!("expected ':', got {other:?}")),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.bump();
+            let rhs = self.parse_and()?;
+            lhs = Expr::BinOp(BinOp::Or, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.bump();
+            let rhs = self.parse_unary()?;
+            lhs = Expr::BinOp(BinOp::And, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.bump();
+            let inner = self.parse_unary()?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        let lhs = self.parse_primary()?;
+        let op = match self.peek() {
+            Some(Token::Eq) => Some(BinOp::Eq),
+            Some(Token::Ne) => Some(BinOp::Ne),
+            Some(Token::Gt) => Some(BinOp::Gt),
+            Some(Token::Lt) => Some(BinOp::Lt),
+            Some(Token::Ge) => Some(BinOp::Ge),
+            Some(Token::Le) => Some(BinOp::Le),
+            _ => None,
+        };
+        let Some(op) = op else {
+            return Ok(lhs);
+        };
+        self.bump();
+        let rhs = self.parse_primary()?;
+        Ok(Expr::BinOp(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.bump() {
+            Some(Token::Str(s)) => Ok(Expr::Lit(Value::Str(s))),
+            Some(Token::Num(n)) => Ok(Expr::Lit(Value::Num(n))),
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true" => Ok(Expr::Lit(Value::Bool(true))),
+                "false" => Ok(Expr::Lit(Value::Bool(false))),
+                _ => Ok(Expr::Field(name)),
+            },
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                match self.bump() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err("expected closing ')'".to_string()),
+                }
+            }
+            other => Err(format!("expected an expression, got {other:?}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_evaluates_a_simple_clause() {
+        let clauses = parse_source(r#"error "no_prod_destructive": is_destructive and targets_production"#).unwrap();
+        assert_eq!(clauses.len(), 1);
+
+        let matching = RuleContext::new("deploy").destructive().production();
+        let safe = RuleContext::new("deploy").destructive();
+        assert!(clauses[0].evaluate(&matching).is_some());
+        assert!(clauses[0].evaluate(&safe).is_none());
+    }
+
+    #[test]
+    fn violation_carries_the_declared_severity() {
+        let clauses = parse_source(r#"critical "tests_failing": tests_passed == false"#).unwrap();
+        let context = RuleContext::new("deploy").tests(false);
+        let violation = clauses[0].evaluate(&context).unwrap();
+        assert_eq!(violation.severity, ViolationSeverity::Critical);
+        assert_eq!(violation.rule_id, "tests_failing");
+    }
+
+    #[test]
+    fn supports_multiple_clauses_comments_and_blank_lines() {
+        let source = r#"
+# mechanic mode limits
+error "too_many_files": file_count > 5
+warning "large_change": line_count > 200
+
+critical "prod_delete": is_destructive and targets_production
+"#;
+        let clauses = parse_source(source).unwrap();
+        assert_eq!(clauses.len(), 3);
+        assert_eq!(clauses[0].name, "too_many_files");
+        assert_eq!(clauses[1].name, "large_change");
+        assert_eq!(clauses[2].name, "prod_delete");
+    }
+
+    #[test]
+    fn supports_not_and_parentheses() {
+        let clauses =
+            parse_source(r#"warning "needs_review": not has_confirmation and (file_count > 1 or is_destructive)"#)
+                .unwrap();
+        let needs_review = RuleContext::new("refactor").with_files(3);
+        let confirmed = RuleContext::new("refactor").with_files(3).confirmed();
+        assert!(clauses[0].evaluate(&needs_review).is_some());
+        assert!(clauses[0].evaluate(&confirmed).is_none());
+    }
+
+    #[test]
+    fn reports_the_offending_line_number_on_syntax_errors() {
+        let err = parse_source("error \"ok\": file_count > 1\nwarning \"broken\": file_count >")
+            .unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn rejects_unknown_severity_keywords() {
+        let err = parse_source(r#"urgent "oops": is_destructive"#).unwrap_err();
+        assert!(err.message.contains("unknown severity"));
+    }
+}
@@ -2,10 +2,14 @@
 //!
 //! Allows grouping and evaluating multiple rules together.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use crate::verdict::{Verdict, Violation, VerdictSeverity};
-use crate::rule::{PolicyRule, RuleContext, default_rules};
+use crate::rule::{Actor, PolicyRule, RuleContext, default_rules};
 use crate::constraints::{Constraints, OperationMetrics, validate_constraints};
+use crate::policy_dsl::{self, PolicyDslError};
+use crate::rule_dsl::{self, RuleDslError};
 use crate::risk::{RiskCalculator, RiskInput, RiskAssessment};
 
 /// A set of policies to evaluate
@@ -27,17 +31,43 @@ pub struct PolicySet {
     #[serde(default)]
     pub rules: Vec<PolicyRule>,
     
+    /// Declarative policy DSL source, compiled into extra clauses evaluated
+    /// alongside `rules` in [`Self::evaluate_rules`]. See [`crate::policy_dsl`].
+    #[serde(default)]
+    pub dsl_source: Option<String>,
+
     /// Base constraints
     #[serde(default)]
     pub constraints: Constraints,
-    
+
+    /// Per-role constraint overrides, merged onto `constraints` (via
+    /// [`Constraints::merge`]) for operations whose [`RuleContext::actor`]
+    /// holds that role. Lets a more trusted role (e.g. `senior`) relax a
+    /// bound like `max_files` without duplicating the whole policy set.
+    #[serde(default)]
+    pub role_capabilities: HashMap<String, Constraints>,
+
     /// Whether to fail fast on first violation
     #[serde(default)]
     pub fail_fast: bool,
-    
+
     /// Tags for categorization
     #[serde(default)]
     pub tags: Vec<String>,
+
+    /// If set, [`Self::evaluate_with_signals`] blocks whenever the actor's
+    /// windowed [`crate::audit::AuditSignals::override_rate`] exceeds this
+    /// fraction -- catches override fatigue that a single stateless
+    /// evaluation can never see.
+    #[serde(default)]
+    pub override_rate_threshold: Option<f64>,
+
+    /// If set, [`Self::evaluate_with_signals`] escalates the risk score
+    /// whenever [`crate::audit::AuditSignals::block_count`] reaches this
+    /// many recent blocks for the policy -- catches repeated blocked
+    /// retries the stateless evaluation can never see.
+    #[serde(default)]
+    pub repeat_block_threshold: Option<u32>,
 }
 
 impl PolicySet {
@@ -49,46 +79,93 @@ impl PolicySet {
             description: String::new(),
             version: "1.0".to_string(),
             rules: Vec::new(),
+            dsl_source: None,
             constraints: Constraints::none(),
+            role_capabilities: HashMap::new(),
             fail_fast: false,
             tags: Vec::new(),
+            override_rate_threshold: None,
+            repeat_block_threshold: None,
         }
     }
-    
+
+    /// Create a policy set from declarative policy DSL source (see
+    /// [`crate::policy_dsl`]), validating it up front so a malformed
+    /// document fails at load time rather than at evaluation time.
+    pub fn from_policy_source(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        source: impl Into<String>,
+    ) -> Result<Self, PolicyDslError> {
+        let source = source.into();
+        policy_dsl::parse_source(&source)?;
+
+        let mut policy = Self::new(id, name);
+        policy.dsl_source = Some(source);
+        Ok(policy)
+    }
+
+    /// Create a policy set from the `<verdict> "<name>" when <condition>`
+    /// rule DSL (see [`crate::rule_dsl`]), compiling each clause directly
+    /// into `rules` rather than storing a side-channel source string --
+    /// operators author rules this way instead of constructing
+    /// [`PolicyRule`] values (or recompiling `rule::default_rules()`) by
+    /// hand. Validated up front, same as [`Self::from_policy_source`], so a
+    /// malformed document fails at load time.
+    pub fn from_source(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        source: impl Into<String>,
+    ) -> Result<Self, RuleDslError> {
+        let rules = rule_dsl::parse_source(&source.into())?;
+
+        let mut policy = Self::new(id, name);
+        policy.rules = rules;
+        Ok(policy)
+    }
+
     /// Create the default mechanic mode policy set
     pub fn mechanic() -> Self {
         let mut rules = default_rules();
         rules.retain(|r| r.tags.contains(&"mechanic".to_string()) || r.tags.contains(&"safety".to_string()) || r.tags.contains(&"quality".to_string()));
-        
+
         Self {
             id: "mechanic@1.0".to_string(),
             name: "Mechanic Mode Policy".to_string(),
             description: "Strict policy for automated operations with limited scope".to_string(),
             version: "1.0".to_string(),
             rules,
+            dsl_source: None,
             constraints: Constraints::mechanic_mode(),
+            role_capabilities: HashMap::new(),
             fail_fast: true,
             tags: vec!["mechanic".to_string(), "default".to_string()],
+            override_rate_threshold: None,
+            repeat_block_threshold: None,
         }
     }
-    
+
     /// Create the genius mode policy set
     pub fn genius() -> Self {
         let mut rules = default_rules();
         rules.retain(|r| r.tags.contains(&"genius".to_string()) || r.tags.contains(&"safety".to_string()));
-        
+
         Self {
             id: "genius@1.0".to_string(),
             name: "Genius Mode Policy".to_string(),
             description: "Relaxed policy for complex operations requiring human oversight".to_string(),
             version: "1.0".to_string(),
             rules,
+            dsl_source: None,
             constraints: Constraints::genius_mode(),
+            role_capabilities: HashMap::new(),
             fail_fast: false,
             tags: vec!["genius".to_string(), "default".to_string()],
+            override_rate_threshold: None,
+            repeat_block_threshold: None,
         }
     }
-    
+
     /// Get policy set by mode name
     pub fn for_mode(mode: &str) -> Self {
         match mode.to_lowercase().as_str() {
@@ -108,7 +185,43 @@ impl PolicySet {
         self.constraints = constraints;
         self
     }
-    
+
+    /// Grant `role` a capability override, merged onto the base constraints
+    /// for actors holding that role (see [`Self::effective_constraints`]).
+    pub fn with_role_capability(mut self, role: impl Into<String>, capability: Constraints) -> Self {
+        self.role_capabilities.insert(role.into(), capability);
+        self
+    }
+
+    /// Block operations once an actor's recent override rate (see
+    /// [`crate::audit::AuditSignals`]) exceeds `threshold`.
+    pub fn with_override_rate_threshold(mut self, threshold: f64) -> Self {
+        self.override_rate_threshold = Some(threshold);
+        self
+    }
+
+    /// Escalate the risk score once a policy has accumulated `threshold`
+    /// recent blocks (see [`crate::audit::AuditSignals`]).
+    pub fn with_repeat_block_threshold(mut self, threshold: u32) -> Self {
+        self.repeat_block_threshold = Some(threshold);
+        self
+    }
+
+    /// Constraints in effect for `actor`: the base `constraints`, relaxed by
+    /// merging in every `role_capabilities` entry the actor's roles unlock.
+    pub fn effective_constraints(&self, actor: Option<&Actor>) -> Constraints {
+        let mut constraints = self.constraints.clone();
+        if let Some(actor) = actor {
+            for role in &actor.roles {
+                if let Some(capability) = self.role_capabilities.get(role) {
+                    constraints = constraints.merge(capability);
+                }
+            }
+        }
+        constraints
+    }
+
+
     /// Set description
     pub fn with_description(mut self, desc: impl Into<String>) -> Self {
         self.description = desc.into();
@@ -128,7 +241,7 @@ impl PolicySet {
         
         for rule in &self.rules {
             if let Some(violation) = rule.evaluate(context) {
-                if rule.severity.is_blocking() {
+                if rule.severity.is_blocking() && violation.blocking {
                     violations.push(violation);
                     if self.fail_fast {
                         break;
@@ -138,7 +251,31 @@ impl PolicySet {
                 }
             }
         }
-        
+
+        // Declarative DSL clauses (see `crate::policy_dsl`), evaluated
+        // alongside the Rust-constructed rules above. `from_policy_source`
+        // validates `dsl_source` up front; a source that fails to parse here
+        // (e.g. one set directly on the public field or deserialized) just
+        // means the clauses don't fire rather than panicking.
+        if !(self.fail_fast && !violations.is_empty()) {
+            if let Some(source) = &self.dsl_source {
+                if let Ok(clauses) = policy_dsl::parse_source(source) {
+                    for clause in &clauses {
+                        if let Some(violation) = clause.evaluate(context) {
+                            if clause.severity.is_blocking() {
+                                violations.push(violation);
+                                if self.fail_fast {
+                                    break;
+                                }
+                            } else {
+                                warnings.push(violation.description.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
         let verdict = if violations.is_empty() {
             if warnings.is_empty() {
                 Verdict::allow()
@@ -172,7 +309,12 @@ impl PolicySet {
     
     /// Full evaluation (constraints + rules)
     pub fn evaluate(&self, context: &RuleContext, metrics: &OperationMetrics) -> FullEvaluation {
-        let constraint_eval = self.evaluate_constraints(metrics);
+        let effective_constraints = self.effective_constraints(context.actor.as_ref());
+        let constraint_eval = PolicyEvaluation {
+            policy_id: self.id.clone(),
+            policy_version: self.version.clone(),
+            verdict: validate_constraints(&effective_constraints, metrics),
+        };
         let rule_eval = self.evaluate_rules(context);
         
         // Calculate risk
@@ -182,7 +324,7 @@ impl PolicySet {
             line_count: context.line_count,
             is_destructive: context.is_destructive,
             targets_production: context.targets_production,
-            affects_critical_files: context.affects_critical_files,
+            affected_paths: context.affected_paths.clone(),
             tests_status: context.tests_passed,
         };
         let risk = RiskCalculator::default().calculate(&risk_input);
@@ -196,9 +338,58 @@ impl PolicySet {
             constraint_result: constraint_eval,
             rule_result: rule_eval,
             risk_assessment: risk,
+            principal: context.actor.clone(),
             final_verdict,
         }
     }
+
+    /// Like [`Self::evaluate`], but also folds in windowed history from the
+    /// audit trail (see [`crate::audit::AuditSignals`]). The pipeline stays
+    /// stateless per-operation -- the caller pulls `signals` from its own
+    /// `AuditLog` -- but a policy can now react to recent behavior via
+    /// [`Self::override_rate_threshold`] and [`Self::repeat_block_threshold`].
+    /// With `signals: None` this is identical to a plain `evaluate`.
+    pub fn evaluate_with_signals(
+        &self,
+        context: &RuleContext,
+        metrics: &OperationMetrics,
+        signals: Option<&crate::audit::AuditSignals>,
+    ) -> FullEvaluation {
+        let mut evaluation = self.evaluate(context, metrics);
+        let Some(signals) = signals else {
+            return evaluation;
+        };
+
+        if let Some(threshold) = self.override_rate_threshold {
+            if signals.override_rate > threshold {
+                let violation = Violation::new(
+                    "override_fatigue",
+                    "Override Fatigue",
+                    format!(
+                        "actor's override rate ({:.0}%) exceeds the {:.0}% threshold",
+                        signals.override_rate * 100.0,
+                        threshold * 100.0,
+                    ),
+                );
+                evaluation.final_verdict = evaluation
+                    .final_verdict
+                    .clone()
+                    .combine(Verdict::block("Override fatigue detected", vec![violation]));
+            }
+        }
+
+        if let Some(threshold) = self.repeat_block_threshold {
+            if signals.block_count >= threshold {
+                evaluation.risk_assessment.score = (evaluation.risk_assessment.score + 20).min(100);
+                evaluation.risk_assessment.explanation.push_str(&format!(
+                    " Escalated: blocked {} times recently.",
+                    signals.block_count
+                ));
+            }
+        }
+
+        evaluation
+    }
 }
 
 /// Result of a policy evaluation
@@ -225,6 +416,10 @@ pub struct FullEvaluation {
     pub rule_result: PolicyEvaluation,
     /// Risk assessment
     pub risk_assessment: RiskAssessment,
+    /// Who the decision was made for, echoed back from
+    /// [`RuleContext::actor`] so the effective roles are auditable
+    /// alongside the verdict (and, eventually, in the evaluation's `Proof`).
+    pub principal: Option<Actor>,
     /// Final combined verdict
     pub final_verdict: Verdict,
 }
@@ -250,25 +445,34 @@ impl FullEvaluation {
     
     /// Generate a summary message
     pub fn summary(&self) -> String {
+        let principal = self
+            .principal
+            .as_ref()
+            .map(|actor| format!(" [actor={} roles={:?}]", actor.id, actor.roles))
+            .unwrap_or_default();
+
         if self.is_blocked() {
             let count = self.all_violations().len();
             format!(
-                "BLOCKED: {} violation(s) - Risk: {} (score: {})",
+                "BLOCKED: {} violation(s) - Risk: {} (score: {}){}",
                 count,
                 self.risk_assessment.level,
-                self.risk_assessment.score
+                self.risk_assessment.score,
+                principal
             )
         } else if self.final_verdict.severity() == VerdictSeverity::Warn {
             format!(
-                "ALLOWED with warnings - Risk: {} (score: {})",
+                "ALLOWED with warnings - Risk: {} (score: {}){}",
                 self.risk_assessment.level,
-                self.risk_assessment.score
+                self.risk_assessment.score,
+                principal
             )
         } else {
             format!(
-                "ALLOWED - Risk: {} (score: {})",
+                "ALLOWED - Risk: {} (score: {}){}",
                 self.risk_assessment.level,
-                self.risk_assessment.score
+                self.risk_assessment.score,
+                principal
             )
         }
     }
@@ -301,6 +505,134 @@ impl PolicyGate {
     }
 }
 
+/// A single operation's standing within a [`CombinedReport`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationStatus {
+    /// The operation's label, as supplied to [`CombinedReport::from_evaluations`].
+    pub operation: String,
+    /// This operation's own verdict severity.
+    pub severity: VerdictSeverity,
+}
+
+/// One rule's combined standing across every operation in a
+/// [`CombinedReport`]: which operations it blocked vs. only warned on.
+/// Both lists are built from [`FullEvaluation::all_violations`], which
+/// holds only the violations of `Block` verdicts. Violations of
+/// Warning-severity or [`crate::rule::RuleMode::Audit`] rules reach a
+/// [`PolicyEvaluation`] only as warning strings without a rule id, so they
+/// are not counted in `warned_operations`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleRollup {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub blocked_operations: Vec<String>,
+    pub warned_operations: Vec<String>,
+}
+
+/// One violation tied to the operation it came from, for
+/// [`CombinedReport`]'s optional per-violation detail section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationViolation {
+    pub operation: String,
+    pub violation: Violation,
+}
+
+/// Aggregated view over many [`FullEvaluation`]s, for CI/batch callers that
+/// need one structured document over a whole changeset instead of reading
+/// each operation's evaluation separately. Per-rule and per-operation lists
+/// are ordered by `rule_id` then operation name (`rules`/`operations`
+/// themselves are already sorted that way), so serialized output stays
+/// diff-friendly across runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombinedReport {
+    /// The most severe [`FullEvaluation::final_verdict`] among all operations,
+    /// kept as-is (not merged) so its `reason`/`violations` stay meaningful.
+    pub overall_verdict: Verdict,
+    pub operations: Vec<OperationStatus>,
+    pub rules: Vec<RuleRollup>,
+    /// Present only when built via [`CombinedReport::from_evaluations_with_detail`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub violations: Option<Vec<OperationViolation>>,
+}
+
+impl CombinedReport {
+    /// Build the compact summary: per-rule and per-operation rollups, no
+    /// per-violation detail.
+    pub fn from_evaluations(evaluations: impl IntoIterator<Item = (String, FullEvaluation)>) -> Self {
+        Self::build(evaluations, false)
+    }
+
+    /// Like [`Self::from_evaluations`], but also populates `violations` with
+    /// every violation across every operation.
+    pub fn from_evaluations_with_detail(
+        evaluations: impl IntoIterator<Item = (String, FullEvaluation)>,
+    ) -> Self {
+        Self::build(evaluations, true)
+    }
+
+    fn build(
+        evaluations: impl IntoIterator<Item = (String, FullEvaluation)>,
+        include_detail: bool,
+    ) -> Self {
+        let mut operations = Vec::new();
+        let mut rules: HashMap<String, RuleRollup> = HashMap::new();
+        let mut violations = Vec::new();
+        let mut overall_verdict = Verdict::allow();
+
+        for (operation, evaluation) in evaluations {
+            operations.push(OperationStatus {
+                operation: operation.clone(),
+                severity: evaluation.final_verdict.severity(),
+            });
+
+            for violation in evaluation.all_violations() {
+                let rollup = rules.entry(violation.rule_id.clone()).or_insert_with(|| RuleRollup {
+                    rule_id: violation.rule_id.clone(),
+                    rule_name: violation.rule_name.clone(),
+                    blocked_operations: Vec::new(),
+                    warned_operations: Vec::new(),
+                });
+                if violation.blocking {
+                    rollup.blocked_operations.push(operation.clone());
+                } else {
+                    rollup.warned_operations.push(operation.clone());
+                }
+
+                if include_detail {
+                    violations.push(OperationViolation {
+                        operation: operation.clone(),
+                        violation: violation.clone(),
+                    });
+                }
+            }
+
+            if evaluation.final_verdict.severity() >= overall_verdict.severity() {
+                overall_verdict = evaluation.final_verdict;
+            }
+        }
+
+        operations.sort_by(|a, b| a.operation.cmp(&b.operation));
+
+        let mut rules: Vec<RuleRollup> = rules.into_values().collect();
+        rules.sort_by(|a, b| a.rule_id.cmp(&b.rule_id));
+        for rollup in &mut rules {
+            rollup.blocked_operations.sort();
+            rollup.warned_operations.sort();
+        }
+
+        violations.sort_by(|a: &OperationViolation, b: &OperationViolation| {
+            a.violation.rule_id.cmp(&b.violation.rule_id).then(a.operation.cmp(&b.operation))
+        });
+
+        Self {
+            overall_verdict,
+            operations,
+            rules,
+            violations: include_detail.then_some(violations),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -396,4 +728,207 @@ mod tests {
         assert!(summary.contains("ALLOWED"));
         assert!(summary.contains("Risk"));
     }
+
+    #[test]
+    fn test_dsl_clauses_block_alongside_rust_rules() {
+        let policy = PolicySet::from_policy_source(
+            "tenant@1.0",
+            "Tenant Policy",
+            r#"error "no_prod_destructive": is_destructive and targets_production"#,
+        )
+        .expect("valid policy source");
+
+        let context = RuleContext::new("deploy").destructive().production();
+        let result = policy.evaluate_rules(&context);
+        assert!(result.verdict.is_blocked());
+    }
+
+    #[test]
+    fn test_dsl_clauses_allow_when_condition_does_not_hold() {
+        let policy = PolicySet::from_policy_source(
+            "tenant@1.0",
+            "Tenant Policy",
+            r#"error "no_prod_destructive": is_destructive and targets_production"#,
+        )
+        .expect("valid policy source");
+
+        let context = RuleContext::new("deploy").destructive();
+        let result = policy.evaluate_rules(&context);
+        assert!(result.verdict.is_allowed());
+    }
+
+    #[test]
+    fn test_invalid_dsl_source_is_rejected_at_construction() {
+        let err = PolicySet::from_policy_source("bad@1.0", "Bad Policy", "error \"oops\": file_count >")
+            .unwrap_err();
+        assert_eq!(err.line, 1);
+    }
+
+    #[test]
+    fn test_rule_dsl_clauses_compile_into_rules_and_block() {
+        let policy = PolicySet::from_source(
+            "tenant@1.0",
+            "Tenant Policy",
+            r#"block "no_prod_destructive" when is_destructive && targets_production"#,
+        )
+        .expect("valid rule dsl source");
+
+        assert_eq!(policy.rules.len(), 1);
+        assert_eq!(policy.rules[0].id, "no_prod_destructive");
+
+        let context = RuleContext::new("deploy").destructive().production();
+        let result = policy.evaluate_rules(&context);
+        assert!(result.verdict.is_blocked());
+    }
+
+    #[test]
+    fn test_invalid_rule_dsl_source_is_rejected_at_construction() {
+        let err = PolicySet::from_source("bad@1.0", "Bad Policy", r#"block "oops" when nonexistent == 1"#)
+            .unwrap_err();
+        assert_eq!(err.line, 1);
+    }
+
+    #[test]
+    fn test_role_capability_relaxes_constraint_for_holder() {
+        let policy = PolicySet::new("scoped@1.0", "Scoped Policy")
+            .with_constraints(Constraints {
+                max_files: Some(5),
+                ..Default::default()
+            })
+            .with_role_capability(
+                "senior",
+                Constraints {
+                    max_files: Some(20),
+                    ..Default::default()
+                },
+            );
+
+        let metrics = OperationMetrics::new().with_files(10, vec![]);
+
+        let no_actor = RuleContext::new("feature").with_files(10);
+        assert!(!policy.evaluate(&no_actor, &metrics).is_allowed());
+
+        let senior = RuleContext::new("feature")
+            .with_files(10)
+            .with_actor(Actor::new("alice").with_role("senior"));
+        assert!(policy.evaluate(&senior, &metrics).is_allowed());
+    }
+
+    #[test]
+    fn test_full_evaluation_surfaces_principal_in_summary() {
+        let policy = PolicySet::mechanic();
+
+        let context = RuleContext::new("bug_fix")
+            .with_files(2)
+            .tests(true)
+            .lint(true)
+            .mode("mechanic")
+            .with_actor(Actor::new("bob").with_role("senior"));
+
+        let metrics = OperationMetrics::new()
+            .with_files(2, vec![])
+            .with_tests(true)
+            .with_lint(true);
+
+        let result = policy.evaluate(&context, &metrics);
+        assert_eq!(result.principal.as_ref().map(|a| a.id.as_str()), Some("bob"));
+        assert!(result.summary().contains("actor=bob"));
+        assert!(result.summary().contains("senior"));
+    }
+
+    #[test]
+    fn test_evaluate_with_signals_is_identical_to_evaluate_when_signals_is_none() {
+        let policy = PolicySet::mechanic();
+        let context = RuleContext::new("bug_fix").with_files(2).tests(true);
+        let metrics = OperationMetrics::new().with_files(2, vec![]).with_tests(true);
+
+        let plain = policy.evaluate(&context, &metrics);
+        let with_signals = policy.evaluate_with_signals(&context, &metrics, None);
+
+        assert_eq!(plain.final_verdict.is_allowed(), with_signals.final_verdict.is_allowed());
+        assert_eq!(plain.risk_assessment.score, with_signals.risk_assessment.score);
+    }
+
+    #[test]
+    fn test_override_rate_threshold_blocks_on_fatigue() {
+        use crate::audit::AuditSignals;
+
+        let policy = PolicySet::mechanic().with_override_rate_threshold(0.5);
+        let context = RuleContext::new("bug_fix").with_files(2).tests(true);
+        let metrics = OperationMetrics::new().with_files(2, vec![]).with_tests(true);
+
+        let fatigued = AuditSignals { override_rate: 0.75, block_count: 0, recent_entry_count: 4 };
+        let result = policy.evaluate_with_signals(&context, &metrics, Some(&fatigued));
+        assert!(result.final_verdict.is_blocked());
+
+        let calm = AuditSignals { override_rate: 0.1, block_count: 0, recent_entry_count: 4 };
+        let result = policy.evaluate_with_signals(&context, &metrics, Some(&calm));
+        assert!(result.final_verdict.is_allowed());
+    }
+
+    #[test]
+    fn test_repeat_block_threshold_escalates_risk_score() {
+        use crate::audit::AuditSignals;
+
+        let policy = PolicySet::mechanic().with_repeat_block_threshold(3);
+        let context = RuleContext::new("bug_fix").with_files(2).tests(true);
+        let metrics = OperationMetrics::new().with_files(2, vec![]).with_tests(true);
+
+        let baseline = policy.evaluate(&context, &metrics).risk_assessment.score;
+
+        let signals = AuditSignals { override_rate: 0.0, block_count: 3, recent_entry_count: 3 };
+        let escalated = policy.evaluate_with_signals(&context, &metrics, Some(&signals));
+
+        assert_eq!(escalated.risk_assessment.score, (baseline + 20).min(100));
+        assert!(escalated.risk_assessment.explanation.contains("Escalated"));
+    }
+
+    #[test]
+    fn test_combined_report_rolls_up_rules_and_operations_in_stable_order() {
+        let policy = PolicySet::mechanic();
+        let passing_context = RuleContext::new("bug_fix").with_files(2).with_lines(50).tests(true);
+        let passing_metrics = OperationMetrics::new()
+            .with_files(2, vec![])
+            .with_lines(50)
+            .with_tests(true)
+            .with_lint(true);
+        let failing_context = RuleContext::new("feature").with_files(20).with_lines(500);
+        let failing_metrics = OperationMetrics::new().with_files(20, vec![]).with_lines(500);
+
+        let evaluations = vec![
+            ("b.rs".to_string(), policy.evaluate(&failing_context, &failing_metrics)),
+            ("a.rs".to_string(), policy.evaluate(&passing_context, &passing_metrics)),
+        ];
+        let report = CombinedReport::from_evaluations(evaluations);
+
+        assert_eq!(report.operations.len(), 2);
+        assert_eq!(report.operations[0].operation, "a.rs");
+        assert_eq!(report.operations[1].operation, "b.rs");
+        assert_eq!(report.operations[0].severity, VerdictSeverity::Allow);
+        assert_eq!(report.operations[1].severity, VerdictSeverity::Block);
+
+        assert!(report.overall_verdict.is_blocked());
+        assert!(!report.rules.is_empty());
+        assert!(report.rules.windows(2).all(|w| w[0].rule_id <= w[1].rule_id));
+        assert!(report.rules.iter().any(|r| r.blocked_operations == vec!["b.rs".to_string()]));
+        assert!(report.violations.is_none());
+    }
+
+    #[test]
+    fn test_combined_report_with_detail_includes_every_violation() {
+        let policy = PolicySet::mechanic();
+        let failing_context = RuleContext::new("feature").with_files(20).with_lines(500);
+        let failing_metrics = OperationMetrics::new().with_files(20, vec![]).with_lines(500);
+        let evaluation = policy.evaluate(&failing_context, &failing_metrics);
+        let expected_violations = evaluation.all_violations().len();
+
+        let report = CombinedReport::from_evaluations_with_detail(vec![(
+            "b.rs".to_string(),
+            evaluation,
+        )]);
+
+        let violations = report.violations.expect("detail requested");
+        assert_eq!(violations.len(), expected_violations);
+        assert!(violations.iter().all(|v| v.operation == "b.rs"));
+    }
 }
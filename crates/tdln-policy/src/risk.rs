@@ -3,9 +3,13 @@
 //! Calculates risk levels based on operation characteristics.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::critical_paths::{self, CriticalPathRule};
+use crate::risk_policy::{self, CompiledRiskRule, RiskPolicyError};
 
 /// Risk level of an operation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RiskLevel {
     /// 0-30: Read-only, small changes
@@ -62,6 +66,111 @@ impl std::fmt::Display for RiskLevel {
     }
 }
 
+/// The minimum role an [`Approval`] must carry to count toward an
+/// [`ApprovalPolicy`]'s threshold. Ordered so a higher role also satisfies a
+/// lower one (a `Senior` approval counts for a policy that only asks for a
+/// `Reviewer`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApproverRole {
+    Reviewer,
+    Senior,
+}
+
+/// A single recorded approval from an identity, at a given role.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Approval {
+    /// Identity of the approver (e.g. username or account id).
+    pub approver: String,
+    /// Role the approver is acting in.
+    pub role: ApproverRole,
+}
+
+impl Approval {
+    /// Create a new approval.
+    pub fn new(approver: impl Into<String>, role: ApproverRole) -> Self {
+        Self {
+            approver: approver.into(),
+            role,
+        }
+    }
+}
+
+/// A k-of-n approval quorum: at least `required` distinct approvers, each
+/// holding at least `role`, must sign off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalPolicy {
+    /// Minimum number of distinct qualifying approvals (the "k" in k-of-n).
+    pub required: u32,
+    /// Minimum role an approval must carry to count toward `required`.
+    pub role: ApproverRole,
+}
+
+impl ApprovalPolicy {
+    /// A policy that is always satisfied -- no approvals needed.
+    pub fn none() -> Self {
+        Self {
+            required: 0,
+            role: ApproverRole::Reviewer,
+        }
+    }
+
+    /// Whether `approvals` meets this policy's quorum. Approvals below
+    /// `role`, and duplicate approvals from the same identity, don't count.
+    pub fn is_satisfied(&self, approvals: &[Approval]) -> bool {
+        if self.required == 0 {
+            return true;
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        let qualifying = approvals
+            .iter()
+            .filter(|a| a.role >= self.role)
+            .filter(|a| seen.insert(a.approver.as_str()))
+            .count();
+
+        qualifying as u32 >= self.required
+    }
+}
+
+/// The `(k, role)` approval quorum this crate used before it became
+/// calculator-configurable -- kept as the fallback for any
+/// [`RiskCalculator`] that doesn't override `approval_requirements`.
+pub(crate) fn default_approval_requirements() -> HashMap<RiskLevel, ApprovalPolicy> {
+    [
+        (
+            RiskLevel::Low,
+            ApprovalPolicy {
+                required: 0,
+                role: ApproverRole::Reviewer,
+            },
+        ),
+        (
+            RiskLevel::Medium,
+            ApprovalPolicy {
+                required: 1,
+                role: ApproverRole::Reviewer,
+            },
+        ),
+        (
+            RiskLevel::High,
+            ApprovalPolicy {
+                required: 1,
+                role: ApproverRole::Senior,
+            },
+        ),
+        (
+            RiskLevel::Critical,
+            ApprovalPolicy {
+                required: 2,
+                role: ApproverRole::Senior,
+            },
+        ),
+    ]
+    .into_iter()
+    .collect()
+}
+
 /// Risk assessment for an operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskAssessment {
@@ -75,25 +184,43 @@ pub struct RiskAssessment {
     pub explanation: String,
     /// Recommendations
     pub recommendations: Vec<String>,
+    /// Approval quorum required before this operation may proceed.
+    pub approval_policy: ApprovalPolicy,
 }
 
 impl RiskAssessment {
-    /// Create a new risk assessment
+    /// Create a new risk assessment, using the built-in
+    /// [`default_approval_requirements`] to derive its [`ApprovalPolicy`].
     pub fn new(factors: Vec<RiskFactor>) -> Self {
+        Self::with_approval_requirements(factors, &default_approval_requirements())
+    }
+
+    /// Create a new risk assessment whose [`ApprovalPolicy`] comes from a
+    /// caller-supplied `RiskLevel -> ApprovalPolicy` mapping, e.g.
+    /// [`RiskCalculator::approval_requirements`].
+    pub(crate) fn with_approval_requirements(
+        factors: Vec<RiskFactor>,
+        approval_requirements: &HashMap<RiskLevel, ApprovalPolicy>,
+    ) -> Self {
         let score: u32 = factors.iter().map(|f| f.impact).sum::<u32>().min(100);
         let level = RiskLevel::from_score(score);
         let explanation = Self::generate_explanation(&factors, level);
         let recommendations = Self::generate_recommendations(&factors, level);
-        
+        let approval_policy = approval_requirements
+            .get(&level)
+            .copied()
+            .unwrap_or_else(ApprovalPolicy::none);
+
         Self {
             score,
             level,
             factors,
             explanation,
             recommendations,
+            approval_policy,
         }
     }
-    
+
     /// Create a low-risk assessment
     pub fn low(message: impl Into<String>) -> Self {
         Self {
@@ -102,6 +229,7 @@ impl RiskAssessment {
             factors: vec![],
             explanation: message.into(),
             recommendations: vec![],
+            approval_policy: ApprovalPolicy::none(),
         }
     }
     
@@ -198,7 +326,7 @@ impl RiskFactor {
 }
 
 /// Category of risk factor
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RiskCategory {
     /// Related to operation type
@@ -215,6 +343,60 @@ pub enum RiskCategory {
     Compliance,
 }
 
+/// The operation weights `RiskCalculator::default()` used before they became
+/// policy-overridable -- kept as the fallback for any policy document that
+/// doesn't set `operation_weights`.
+pub(crate) fn default_operation_weights() -> std::collections::HashMap<String, u32> {
+    let mut operation_weights = std::collections::HashMap::new();
+    operation_weights.insert("analyze".to_string(), 0);
+    operation_weights.insert("review".to_string(), 0);
+    operation_weights.insert("explain".to_string(), 0);
+    operation_weights.insert("test".to_string(), 10);
+    operation_weights.insert("document".to_string(), 10);
+    operation_weights.insert("bug_fix".to_string(), 20);
+    operation_weights.insert("refactor".to_string(), 30);
+    operation_weights.insert("feature".to_string(), 40);
+    operation_weights.insert("file_rename".to_string(), 25);
+    operation_weights.insert("file_delete".to_string(), 60);
+    operation_weights.insert("file_create".to_string(), 15);
+    operation_weights
+}
+
+/// Fallback file count thresholds, see [`default_operation_weights`].
+pub(crate) fn default_file_thresholds() -> Vec<(usize, u32)> {
+    vec![
+        (1, 5),   // 1+ files: +5
+        (5, 15),  // 5+ files: +15
+        (10, 25), // 10+ files: +25
+        (20, 35), // 20+ files: +35
+    ]
+}
+
+/// Fallback line count thresholds, see [`default_operation_weights`].
+pub(crate) fn default_line_thresholds() -> Vec<(usize, u32)> {
+    vec![
+        (10, 5),   // 10+ lines: +5
+        (50, 10),  // 50+ lines: +10
+        (200, 20), // 200+ lines: +20
+        (500, 30), // 500+ lines: +30
+    ]
+}
+
+/// Fallback destructive penalty, see [`default_operation_weights`].
+pub(crate) fn default_destructive_penalty() -> u32 {
+    20
+}
+
+/// Fallback production penalty, see [`default_operation_weights`].
+pub(crate) fn default_production_penalty() -> u32 {
+    25
+}
+
+/// Fallback sensitive-path catalog, see [`crate::critical_paths`].
+pub(crate) fn default_critical_path_rules() -> Vec<CriticalPathRule> {
+    critical_paths::default_critical_path_rules()
+}
+
 /// Risk calculator with configurable weights
 #[derive(Debug, Clone)]
 pub struct RiskCalculator {
@@ -228,47 +410,69 @@ pub struct RiskCalculator {
     pub destructive_penalty: u32,
     /// Production target penalty
     pub production_penalty: u32,
-    /// Critical file penalty
-    pub critical_file_penalty: u32,
+    /// Sensitive-path catalog: glob/regex patterns checked against
+    /// [`RiskInput::affected_paths`], each with its own impact and reason.
+    pub critical_path_rules: Vec<CriticalPathRule>,
+    /// Additional rules loaded from a policy-as-code document (see
+    /// [`crate::risk_policy`]), evaluated after the fixed factors above.
+    /// Empty unless built via [`Self::from_policy_str`]/[`Self::from_policy_file`].
+    policy_rules: Vec<CompiledRiskRule>,
+    /// Approval quorum required per [`RiskLevel`], used to derive each
+    /// assessment's [`ApprovalPolicy`].
+    pub approval_requirements: HashMap<RiskLevel, ApprovalPolicy>,
 }
 
 impl Default for RiskCalculator {
     fn default() -> Self {
-        let mut operation_weights = std::collections::HashMap::new();
-        operation_weights.insert("analyze".to_string(), 0);
-        operation_weights.insert("review".to_string(), 0);
-        operation_weights.insert("explain".to_string(), 0);
-        operation_weights.insert("test".to_string(), 10);
-        operation_weights.insert("document".to_string(), 10);
-        operation_weights.insert("bug_fix".to_string(), 20);
-        operation_weights.insert("refactor".to_string(), 30);
-        operation_weights.insert("feature".to_string(), 40);
-        operation_weights.insert("file_rename".to_string(), 25);
-        operation_weights.insert("file_delete".to_string(), 60);
-        operation_weights.insert("file_create".to_string(), 15);
-        
         Self {
-            operation_weights,
-            file_thresholds: vec![
-                (1, 5),   // 1+ files: +5
-                (5, 15),  // 5+ files: +15
-                (10, 25), // 10+ files: +25
-                (20, 35), // 20+ files: +35
-            ],
-            line_thresholds: vec![
-                (10, 5),    // 10+ lines: +5
-                (50, 10),   // 50+ lines: +10
-                (200, 20),  // 200+ lines: +20
-                (500, 30),  // 500+ lines: +30
-            ],
-            destructive_penalty: 20,
-            production_penalty: 25,
-            critical_file_penalty: 15,
+            operation_weights: default_operation_weights(),
+            file_thresholds: default_file_thresholds(),
+            line_thresholds: default_line_thresholds(),
+            destructive_penalty: default_destructive_penalty(),
+            production_penalty: default_production_penalty(),
+            critical_path_rules: default_critical_path_rules(),
+            policy_rules: Vec::new(),
+            approval_requirements: default_approval_requirements(),
         }
     }
 }
 
 impl RiskCalculator {
+    /// Build a calculator from a YAML (or JSON) policy document, falling
+    /// back to [`Self::default`]'s weights/thresholds/penalties for any
+    /// field the document doesn't set. See [`crate::risk_policy`] for the
+    /// document format and guard expression language.
+    pub fn from_policy_str(policy: &str) -> Result<Self, RiskPolicyError> {
+        let document = risk_policy::parse_document(policy)?;
+        let policy_rules = document.compile_rules()?;
+        Ok(Self {
+            operation_weights: document.operation_weights,
+            file_thresholds: document.file_thresholds,
+            line_thresholds: document.line_thresholds,
+            destructive_penalty: document.destructive_penalty,
+            production_penalty: document.production_penalty,
+            critical_path_rules: document.critical_path_rules.clone(),
+            policy_rules,
+            approval_requirements: default_approval_requirements(),
+        })
+    }
+
+    /// Build a calculator from a policy document on disk.
+    pub fn from_policy_file(path: impl AsRef<std::path::Path>) -> Result<Self, RiskPolicyError> {
+        let document = risk_policy::load_document(path.as_ref())?;
+        let policy_rules = document.compile_rules()?;
+        Ok(Self {
+            operation_weights: document.operation_weights,
+            file_thresholds: document.file_thresholds,
+            line_thresholds: document.line_thresholds,
+            destructive_penalty: document.destructive_penalty,
+            production_penalty: document.production_penalty,
+            critical_path_rules: document.critical_path_rules.clone(),
+            policy_rules,
+            approval_requirements: default_approval_requirements(),
+        })
+    }
+
     /// Calculate risk for an operation
     pub fn calculate(&self, input: &RiskInput) -> RiskAssessment {
         let mut factors = Vec::new();
@@ -334,16 +538,28 @@ impl RiskCalculator {
             ).with_recommendation("Use staging environment first"));
         }
         
-        // Critical files
-        if input.affects_critical_files {
+        // Critical files: one factor per sensitive-path rule that matches at
+        // least one affected path, naming the matching files.
+        for rule in &self.critical_path_rules {
+            let matched: Vec<&str> = input
+                .affected_paths
+                .iter()
+                .map(String::as_str)
+                .filter(|path| rule.matches(path))
+                .collect();
+
+            if matched.is_empty() {
+                continue;
+            }
+
             factors.push(RiskFactor::new(
-                "critical_files",
-                self.critical_file_penalty,
-                "Affects critical system files",
+                rule.name.clone(),
+                rule.impact,
+                format!("{} ({}): {}", rule.reason, rule.pattern, matched.join(", ")),
                 RiskCategory::Compliance,
             ).with_recommendation("Extra review required for critical files"));
         }
-        
+
         // Tests not passed
         if input.tests_status == Some(false) {
             factors.push(RiskFactor::new(
@@ -353,8 +569,15 @@ impl RiskCalculator {
                 RiskCategory::Validation,
             ).with_recommendation("Fix failing tests before proceeding"));
         }
-        
-        RiskAssessment::new(factors)
+
+        // Policy-as-code rules, evaluated after the fixed factors above.
+        for rule in &self.policy_rules {
+            if let Some(factor) = rule.evaluate(input) {
+                factors.push(factor);
+            }
+        }
+
+        RiskAssessment::with_approval_requirements(factors, &self.approval_requirements)
     }
 }
 
@@ -366,7 +589,9 @@ pub struct RiskInput {
     pub line_count: usize,
     pub is_destructive: bool,
     pub targets_production: bool,
-    pub affects_critical_files: bool,
+    /// Paths affected by the operation, checked against
+    /// [`RiskCalculator::critical_path_rules`].
+    pub affected_paths: Vec<String>,
     pub tests_status: Option<bool>,
 }
 
@@ -398,11 +623,17 @@ impl RiskInput {
         self
     }
     
-    pub fn critical_files(mut self) -> Self {
-        self.affects_critical_files = true;
+    /// Record the paths this operation affects, for matching against
+    /// [`RiskCalculator::critical_path_rules`].
+    pub fn with_affected_paths<I, S>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.affected_paths = paths.into_iter().map(Into::into).collect();
         self
     }
-    
+
     pub fn tests_passed(mut self, passed: bool) -> Self {
         self.tests_status = Some(passed);
         self
@@ -455,7 +686,7 @@ mod tests {
             .with_lines(1000)
             .destructive()
             .production()
-            .critical_files();
+            .with_affected_paths(["migrations/0001_init.sql"]);
         let assessment = calculate_risk(&input);
         assert_eq!(assessment.level, RiskLevel::Critical);
         assert!(assessment.score >= 80);
@@ -477,5 +708,106 @@ mod tests {
         let assessment = calculate_risk(&input);
         assert!(!assessment.recommendations.is_empty());
     }
+
+    #[test]
+    fn test_approval_policy_by_risk_level() {
+        let input = RiskInput::new("analyze").with_files(1);
+        assert_eq!(calculate_risk(&input).approval_policy, ApprovalPolicy::none());
+
+        let input = RiskInput::new("file_delete")
+            .with_files(25)
+            .with_lines(1000)
+            .destructive()
+            .production()
+            .with_affected_paths(["migrations/0001_init.sql"]);
+        let assessment = calculate_risk(&input);
+        assert_eq!(assessment.level, RiskLevel::Critical);
+        assert_eq!(
+            assessment.approval_policy,
+            ApprovalPolicy {
+                required: 2,
+                role: ApproverRole::Senior,
+            }
+        );
+    }
+
+    #[test]
+    fn test_approval_policy_is_satisfied_requires_distinct_qualifying_approvers() {
+        let policy = ApprovalPolicy {
+            required: 2,
+            role: ApproverRole::Senior,
+        };
+
+        // One senior, one reviewer: the reviewer doesn't qualify.
+        let mixed = vec![
+            Approval::new("alice", ApproverRole::Senior),
+            Approval::new("bob", ApproverRole::Reviewer),
+        ];
+        assert!(!policy.is_satisfied(&mixed));
+
+        // Same senior approving twice doesn't count as two approvals.
+        let duplicate = vec![
+            Approval::new("alice", ApproverRole::Senior),
+            Approval::new("alice", ApproverRole::Senior),
+        ];
+        assert!(!policy.is_satisfied(&duplicate));
+
+        // Two distinct seniors satisfies it.
+        let enough = vec![
+            Approval::new("alice", ApproverRole::Senior),
+            Approval::new("carol", ApproverRole::Senior),
+        ];
+        assert!(policy.is_satisfied(&enough));
+    }
+
+    #[test]
+    fn test_approval_requirements_are_calculator_configurable() {
+        let mut calculator = RiskCalculator::default();
+        calculator
+            .approval_requirements
+            .insert(RiskLevel::Medium, ApprovalPolicy {
+                required: 3,
+                role: ApproverRole::Senior,
+            });
+
+        let input = RiskInput::new("refactor").with_files(3);
+        let assessment = calculator.calculate(&input);
+        assert_eq!(assessment.level, RiskLevel::Medium);
+        assert_eq!(assessment.approval_policy.required, 3);
+        assert_eq!(assessment.approval_policy.role, ApproverRole::Senior);
+    }
+
+    #[test]
+    fn test_critical_path_rules_emit_one_factor_per_matched_pattern() {
+        let input = RiskInput::new("refactor")
+            .with_affected_paths(["db/migrations/0007_add_index.sql", ".github/workflows/ci.yml"]);
+        let assessment = calculate_risk(&input);
+
+        let migrations = assessment
+            .factors
+            .iter()
+            .find(|f| f.name == "migrations")
+            .expect("migrations factor");
+        assert_eq!(migrations.impact, 25);
+        assert!(migrations.description.contains("db/migrations/0007_add_index.sql"));
+
+        let ci = assessment
+            .factors
+            .iter()
+            .find(|f| f.name == "ci_config")
+            .expect("ci_config factor");
+        assert_eq!(ci.impact, 20);
+        assert!(ci.description.contains(".github/workflows/ci.yml"));
+    }
+
+    #[test]
+    fn test_unmatched_affected_paths_contribute_no_critical_path_factor() {
+        let input = RiskInput::new("refactor").with_affected_paths(["src/lib.rs"]);
+        let assessment = calculate_risk(&input);
+        assert!(!assessment
+            .factors
+            .iter()
+            .any(|f| f.category == RiskCategory::Compliance));
+    }
 }
 
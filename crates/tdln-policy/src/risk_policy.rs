@@ -0,0 +1,606 @@
+//! Declarative policy-as-code for [`crate::risk::RiskCalculator`].
+//!
+//! `RiskCalculator::default()` hardcodes every operation weight, threshold,
+//! and penalty, so tuning risk posture meant recompiling. This module parses
+//! a YAML (or JSON, which is valid YAML) policy document into a calculator:
+//! the built-in weights/thresholds/penalties can be overridden wholesale,
+//! and on top of them a list of named rules can push extra [`RiskFactor`]s
+//! when a small guard expression over [`RiskInput`] fields matches, e.g.
+//!
+//! ```yaml
+//! rules:
+//!   - name: bulk_file_delete
+//!     category: destructive
+//!     when: 'operation_type == "file_delete" && file_count > 10'
+//!     impact: 40
+//!     description: Bulk deletion across many files
+//!     recommendation: Split into smaller, reviewable deletions
+//! ```
+//!
+//! Guards support `==`, `!=`, `>`, `<`, `>=`, `<=`, `&&`, `||`, `!`, string
+//! and numeric literals, the [`RiskInput`] field names below, and the named
+//! functions `regex_match(field, pattern)` / `starts_with(field, prefix)`.
+//!
+//! Fields exposed to guards: `operation_type` (string), `file_count`,
+//! `line_count` (numbers), `is_destructive`, `targets_production`,
+//! `affects_critical_files`, `tests_passed`, `tests_failed` (booleans).
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::critical_paths::CriticalPathRule;
+use crate::risk::{RiskCategory, RiskFactor, RiskInput};
+
+/// A rule compiled from a policy document, ready to evaluate against a
+/// [`RiskInput`] and (if its guard matches) push a [`RiskFactor`].
+#[derive(Debug, Clone)]
+pub struct CompiledRiskRule {
+    name: String,
+    category: RiskCategory,
+    guard: Expr,
+    impact: u32,
+    description: String,
+    recommendation: Option<String>,
+}
+
+impl CompiledRiskRule {
+    /// Evaluate this rule's guard against `input`, returning a [`RiskFactor`]
+    /// if it matches.
+    pub fn evaluate(&self, input: &RiskInput) -> Option<RiskFactor> {
+        if !self.guard.eval_bool(input) {
+            return None;
+        }
+
+        let description = if self.description.is_empty() {
+            format!("Policy rule '{}' matched", self.name)
+        } else {
+            self.description.clone()
+        };
+
+        let factor = RiskFactor::new(self.name.clone(), self.impact, description, self.category);
+        Some(match &self.recommendation {
+            Some(rec) => factor.with_recommendation(rec.clone()),
+            None => factor,
+        })
+    }
+}
+
+/// One `rules:` entry as deserialized straight from the policy document.
+#[derive(Debug, Clone, Deserialize)]
+struct RiskPolicyRule {
+    name: String,
+    category: RiskCategory,
+    #[serde(rename = "when")]
+    guard: String,
+    impact: u32,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    recommendation: Option<String>,
+}
+
+/// Top-level policy document. Any field left out keeps its built-in
+/// default, so a policy only needs to state what it wants to change.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct RiskPolicyDocument {
+    #[serde(default = "crate::risk::default_operation_weights")]
+    pub operation_weights: HashMap<String, u32>,
+    #[serde(default = "crate::risk::default_file_thresholds")]
+    pub file_thresholds: Vec<(usize, u32)>,
+    #[serde(default = "crate::risk::default_line_thresholds")]
+    pub line_thresholds: Vec<(usize, u32)>,
+    #[serde(default = "crate::risk::default_destructive_penalty")]
+    pub destructive_penalty: u32,
+    #[serde(default = "crate::risk::default_production_penalty")]
+    pub production_penalty: u32,
+    #[serde(default = "crate::risk::default_critical_path_rules")]
+    pub critical_path_rules: Vec<CriticalPathRule>,
+    #[serde(default)]
+    rules: Vec<RiskPolicyRule>,
+}
+
+impl RiskPolicyDocument {
+    /// Parse and compile, catching guard syntax errors up front rather than
+    /// at evaluation time.
+    pub(crate) fn compile_rules(&self) -> Result<Vec<CompiledRiskRule>, RiskPolicyError> {
+        self.rules
+            .iter()
+            .map(|r| {
+                let guard = parse_guard(&r.guard).map_err(|message| RiskPolicyError::Guard {
+                    rule: r.name.clone(),
+                    message,
+                })?;
+                Ok(CompiledRiskRule {
+                    name: r.name.clone(),
+                    category: r.category,
+                    guard,
+                    impact: r.impact,
+                    description: r.description.clone(),
+                    recommendation: r.recommendation.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Parse a policy document from a YAML (or JSON) string.
+pub(crate) fn parse_document(src: &str) -> Result<RiskPolicyDocument, RiskPolicyError> {
+    serde_yaml::from_str(src).map_err(|e| RiskPolicyError::Parse(e.to_string()))
+}
+
+/// Read and parse a policy document from disk.
+pub(crate) fn load_document(path: &Path) -> Result<RiskPolicyDocument, RiskPolicyError> {
+    let src = std::fs::read_to_string(path)
+        .map_err(|e| RiskPolicyError::Io(path.display().to_string(), e.to_string()))?;
+    parse_document(&src)
+}
+
+/// Error loading or parsing a risk policy document.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RiskPolicyError {
+    #[error("failed to parse risk policy: {0}")]
+    Parse(String),
+    #[error("failed to read risk policy file '{0}': {1}")]
+    Io(String, String),
+    #[error("rule '{rule}' has an invalid guard expression: {message}")]
+    Guard { rule: String, message: String },
+}
+
+// === Guard expression language ===
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    And,
+    Or,
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Lit(Value),
+    Field(String),
+    Call(String, Vec<Expr>),
+    Not(Box<Expr>),
+    BinOp(BinOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, input: &RiskInput) -> Value {
+        match self {
+            Expr::Lit(v) => v.clone(),
+            Expr::Field(name) => field_value(input, name),
+            Expr::Not(inner) => Value::Bool(!truthy(&inner.eval(input))),
+            Expr::BinOp(BinOp::And, l, r) => {
+                Value::Bool(truthy(&l.eval(input)) && truthy(&r.eval(input)))
+            }
+            Expr::BinOp(BinOp::Or, l, r) => {
+                Value::Bool(truthy(&l.eval(input)) || truthy(&r.eval(input)))
+            }
+            Expr::BinOp(op, l, r) => Value::Bool(compare(*op, &l.eval(input), &r.eval(input))),
+            Expr::Call(name, args) => Value::Bool(call(name, args, input)),
+        }
+    }
+
+    fn eval_bool(&self, input: &RiskInput) -> bool {
+        truthy(&self.eval(input))
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    matches!(value, Value::Bool(true))
+}
+
+fn field_value(input: &RiskInput, name: &str) -> Value {
+    match name {
+        "operation_type" => Value::Str(input.operation_type.clone()),
+        "file_count" => Value::Num(input.file_count as f64),
+        "line_count" => Value::Num(input.line_count as f64),
+        "is_destructive" => Value::Bool(input.is_destructive),
+        "targets_production" => Value::Bool(input.targets_production),
+        "affects_critical_files" => Value::Bool(!input.affected_paths.is_empty()),
+        "tests_passed" => Value::Bool(input.tests_status == Some(true)),
+        "tests_failed" => Value::Bool(input.tests_status == Some(false)),
+        // Unknown fields evaluate to an inert value rather than panicking --
+        // a guard referencing a typo'd field just never matches.
+        _ => Value::Bool(false),
+    }
+}
+
+fn compare(op: BinOp, left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Str(a), Value::Str(b)) => match op {
+            BinOp::Eq => a == b,
+            BinOp::Ne => a != b,
+            _ => false,
+        },
+        (Value::Num(a), Value::Num(b)) => match op {
+            BinOp::Eq => a == b,
+            BinOp::Ne => a != b,
+            BinOp::Gt => a > b,
+            BinOp::Lt => a < b,
+            BinOp::Ge => a >= b,
+            BinOp::Le => a <= b,
+            _ => false,
+        },
+        (Value::Bool(a), Value::Bool(b)) => match op {
+            BinOp::Eq => a == b,
+            BinOp::Ne => a != b,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn call(name: &str, args: &[Expr], input: &RiskInput) -> bool {
+    match name {
+        "regex_match" => {
+            let (Some(field), Some(pattern)) = (args.first(), args.get(1)) else {
+                return false;
+            };
+            let (Value::Str(haystack), Value::Str(pattern)) =
+                (field.eval(input), pattern.eval(input))
+            else {
+                return false;
+            };
+            regex::Regex::new(&pattern)
+                .map(|re| re.is_match(&haystack))
+                .unwrap_or(false)
+        }
+        "starts_with" => {
+            let (Some(field), Some(prefix)) = (args.first(), args.get(1)) else {
+                return false;
+            };
+            let (Value::Str(haystack), Value::Str(prefix)) =
+                (field.eval(input), prefix.eval(input))
+            else {
+                return false;
+            };
+            haystack.starts_with(&prefix)
+        }
+        _ => false,
+    }
+}
+
+/// Parse a guard expression, e.g. `operation_type == "file_delete" &&
+/// file_count > 10`, into an [`Expr`] tree.
+fn parse_guard(src: &str) -> Result<Expr, String> {
+    let tokens = tokenize(src)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(format!("unexpected trailing input at token {}", parser.pos));
+    }
+    Ok(expr)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    And,
+    Or,
+    Not,
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let quote = c;
+            let mut s = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != quote {
+                s.push(chars[i]);
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err("unterminated string literal".to_string());
+            }
+            i += 1;
+            tokens.push(Token::Str(s));
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let n = text
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number literal '{text}'"))?;
+            tokens.push(Token::Num(n));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            match c {
+                '(' => {
+                    tokens.push(Token::LParen);
+                    i += 1;
+                }
+                ')' => {
+                    tokens.push(Token::RParen);
+                    i += 1;
+                }
+                ',' => {
+                    tokens.push(Token::Comma);
+                    i += 1;
+                }
+                '!' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Ne);
+                    i += 2;
+                }
+                '!' => {
+                    tokens.push(Token::Not);
+                    i += 1;
+                }
+                '=' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Eq);
+                    i += 2;
+                }
+                '>' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Ge);
+                    i += 2;
+                }
+                '>' => {
+                    tokens.push(Token::Gt);
+                    i += 1;
+                }
+                '<' if chars.get(i + 1) == Some(&'=') => {
+                    tokens.push(Token::Le);
+                    i += 2;
+                }
+                '<' => {
+                    tokens.push(Token::Lt);
+                    i += 1;
+                }
+                '&' if chars.get(i + 1) == Some(&'&') => {
+                    tokens.push(Token::And);
+                    i += 2;
+                }
+                '|' if chars.get(i + 1) == Some(&'|') => {
+                    tokens.push(Token::Or);
+                    i += 2;
+                }
+                other => return Err(format!("unexpected character '{other}'")),
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.bump();
+            let rhs = self.parse_and()?;
+            lhs = Expr::BinOp(BinOp::Or, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.bump();
+            let rhs = self.parse_unary()?;
+            lhs = Expr::BinOp(BinOp::And, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.bump();
+            let inner = self.parse_unary()?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        let lhs = self.parse_term()?;
+        let op = match self.peek() {
+            Some(Token::Eq) => Some(BinOp::Eq),
+            Some(Token::Ne) => Some(BinOp::Ne),
+            Some(Token::Gt) => Some(BinOp::Gt),
+            Some(Token::Lt) => Some(BinOp::Lt),
+            Some(Token::Ge) => Some(BinOp::Ge),
+            Some(Token::Le) => Some(BinOp::Le),
+            _ => None,
+        };
+        let Some(op) = op else {
+            return Ok(lhs);
+        };
+        self.bump();
+        let rhs = self.parse_term()?;
+        Ok(Expr::BinOp(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn parse_term(&mut self) -> Result<Expr, String> {
+        match self.bump() {
+            Some(Token::Str(s)) => Ok(Expr::Lit(Value::Str(s))),
+            Some(Token::Num(n)) => Ok(Expr::Lit(Value::Num(n))),
+            Some(Token::Ident(name)) => {
+                if name == "true" {
+                    return Ok(Expr::Lit(Value::Bool(true)));
+                }
+                if name == "false" {
+                    return Ok(Expr::Lit(Value::Bool(false)));
+                }
+                if self.peek() == Some(&Token::LParen) {
+                    self.bump();
+                    let args = self.parse_args()?;
+                    return Ok(Expr::Call(name, args));
+                }
+                Ok(Expr::Field(name))
+            }
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                match self.bump() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err("expected closing ')'".to_string()),
+                }
+            }
+            other => Err(format!("unexpected token {other:?}")),
+        }
+    }
+
+    fn parse_args(&mut self) -> Result<Vec<Expr>, String> {
+        let mut args = Vec::new();
+        if self.peek() == Some(&Token::RParen) {
+            self.bump();
+            return Ok(args);
+        }
+        loop {
+            args.push(self.parse_or()?);
+            match self.bump() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => break,
+                other => return Err(format!("expected ',' or ')', got {other:?}")),
+            }
+        }
+        Ok(args)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Str(s) => write!(f, "{s}"),
+            Value::Num(n) => write!(f, "{n}"),
+            Value::Bool(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::RiskCalculator;
+
+    #[test]
+    fn parses_and_evaluates_simple_guard() {
+        let guard = parse_guard("operation_type == \"file_delete\" && file_count > 10").unwrap();
+        let matching = RiskInput::new("file_delete").with_files(25);
+        let not_matching = RiskInput::new("file_delete").with_files(1);
+        assert!(guard.eval_bool(&matching));
+        assert!(!guard.eval_bool(&not_matching));
+    }
+
+    #[test]
+    fn supports_or_and_negation() {
+        let guard = parse_guard("!is_destructive || targets_production").unwrap();
+        let safe = RiskInput::new("refactor");
+        let destructive_prod = RiskInput::new("feature").destructive().production();
+        let destructive_only = RiskInput::new("feature").destructive();
+        assert!(guard.eval_bool(&safe));
+        assert!(guard.eval_bool(&destructive_prod));
+        assert!(!guard.eval_bool(&destructive_only));
+    }
+
+    #[test]
+    fn supports_starts_with_and_regex_match() {
+        let guard = parse_guard("starts_with(operation_type, \"file_\")").unwrap();
+        assert!(guard.eval_bool(&RiskInput::new("file_rename")));
+        assert!(!guard.eval_bool(&RiskInput::new("refactor")));
+
+        let guard = parse_guard("regex_match(operation_type, \"^file_(delete|rename)$\")").unwrap();
+        assert!(guard.eval_bool(&RiskInput::new("file_delete")));
+        assert!(!guard.eval_bool(&RiskInput::new("file_create")));
+    }
+
+    #[test]
+    fn invalid_guard_syntax_is_a_parse_error() {
+        assert!(parse_guard("file_count >").is_err());
+        assert!(parse_guard("(file_count > 1").is_err());
+    }
+
+    #[test]
+    fn loads_policy_document_with_partial_overrides() {
+        let yaml = r#"
+destructive_penalty: 50
+rules:
+  - name: bulk_delete
+    category: destructive
+    when: 'operation_type == "file_delete" && file_count > 10'
+    impact: 40
+    description: Bulk deletion across many files
+    recommendation: Split into smaller, reviewable deletions
+"#;
+        let calculator = RiskCalculator::from_policy_str(yaml).expect("valid policy");
+        assert_eq!(calculator.destructive_penalty, 50);
+        // Unset fields keep the built-in defaults.
+        assert_eq!(
+            calculator.production_penalty,
+            RiskCalculator::default().production_penalty
+        );
+
+        let input = RiskInput::new("file_delete").with_files(25);
+        let assessment = calculator.calculate(&input);
+        assert!(assessment.factors.iter().any(|f| f.name == "bulk_delete"));
+    }
+
+    #[test]
+    fn rejects_policy_with_invalid_guard() {
+        let yaml = r#"
+rules:
+  - name: broken
+    category: destructive
+    when: 'file_count >'
+    impact: 10
+"#;
+        let err = RiskCalculator::from_policy_str(yaml).expect_err("invalid guard");
+        assert!(matches!(err, RiskPolicyError::Guard { .. }));
+    }
+}
@@ -2,9 +2,13 @@
 //!
 //! Defines the rule system for policy evaluation.
 
+use std::collections::HashMap;
+
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use crate::verdict::{Violation, ViolationSeverity};
 use crate::risk::RiskLevel;
+use crate::expr;
 
 /// A single policy rule
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,16 +31,51 @@ pub struct PolicyRule {
     /// Whether this rule is enabled
     #[serde(default = "default_true")]
     pub enabled: bool,
-    
+
     /// Tags for categorization
     #[serde(default)]
     pub tags: Vec<String>,
+
+    /// Roles the evaluating [`RuleContext::actor`] must all hold for this
+    /// rule to pass. Empty means no role requirement.
+    #[serde(default)]
+    pub required_roles: Vec<String>,
+
+    /// Roles the evaluating [`RuleContext::actor`] must not hold for this
+    /// rule to pass. Empty means no restriction.
+    #[serde(default)]
+    pub forbidden_roles: Vec<String>,
+
+    /// Whether a violation of this rule actually blocks the operation
+    /// (`Enforce`, the default) or is only reported (`Audit`), following
+    /// Rudder's policy-mode/dry-run model -- lets a team observe a new
+    /// rule's impact before it starts blocking anything.
+    #[serde(default)]
+    pub enforcement: RuleMode,
+
+    /// Subjects this rule applies to: an actor id or a role name, matched
+    /// against [`RuleContext::effective_subjects`] (which expands roles
+    /// through [`RoleManager`] inheritance). Empty means the rule applies
+    /// to every subject, same as today.
+    #[serde(default)]
+    pub subjects: Vec<String>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Whether a [`PolicyRule`]'s violations block the operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleMode {
+    /// Violations produce a blocking [`Violation`] (subject to severity).
+    #[default]
+    Enforce,
+    /// Violations are still reported, but never block.
+    Audit,
+}
+
 impl PolicyRule {
     /// Create a new rule
     pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
@@ -48,20 +87,36 @@ impl PolicyRule {
             conditions: Vec::new(),
             enabled: true,
             tags: Vec::new(),
+            required_roles: Vec::new(),
+            forbidden_roles: Vec::new(),
+            enforcement: RuleMode::Enforce,
+            subjects: Vec::new(),
         }
     }
-    
+
     /// Set description
     pub fn with_description(mut self, desc: impl Into<String>) -> Self {
         self.description = desc.into();
         self
     }
-    
+
     /// Set severity
     pub fn with_severity(mut self, severity: RuleSeverity) -> Self {
         self.severity = severity;
         self
     }
+
+    /// Set enforcement mode.
+    pub fn with_enforcement(mut self, mode: RuleMode) -> Self {
+        self.enforcement = mode;
+        self
+    }
+
+    /// Evaluate and report this rule's violations without ever blocking.
+    pub fn audit_only(mut self) -> Self {
+        self.enforcement = RuleMode::Audit;
+        self
+    }
     
     /// Add a condition
     pub fn with_condition(mut self, condition: RuleCondition) -> Self {
@@ -74,7 +129,26 @@ impl PolicyRule {
         self.tags.push(tag.into());
         self
     }
-    
+
+    /// Require the evaluating actor to hold `role`
+    pub fn requires_role(mut self, role: impl Into<String>) -> Self {
+        self.required_roles.push(role.into());
+        self
+    }
+
+    /// Forbid the evaluating actor from holding `role`
+    pub fn forbids_role(mut self, role: impl Into<String>) -> Self {
+        self.forbidden_roles.push(role.into());
+        self
+    }
+
+    /// Scope this rule to only apply when the evaluating subject (an actor
+    /// id or a role, see [`Self::subjects`]) matches `subject`.
+    pub fn scoped_to_subject(mut self, subject: impl Into<String>) -> Self {
+        self.subjects.push(subject.into());
+        self
+    }
+
     /// Disable the rule
     pub fn disabled(mut self) -> Self {
         self.enabled = false;
@@ -83,30 +157,159 @@ impl PolicyRule {
     
     /// Evaluate the rule against a context
     pub fn evaluate(&self, context: &RuleContext) -> Option<Violation> {
-        if !self.enabled {
+        if !self.enabled || self.out_of_subject_scope(context) {
             return None;
         }
-        
+
+        let severity = match self.severity {
+            RuleSeverity::Info => ViolationSeverity::Info,
+            RuleSeverity::Warning => ViolationSeverity::Warning,
+            RuleSeverity::Error => ViolationSeverity::Error,
+            RuleSeverity::Critical => ViolationSeverity::Critical,
+        };
+
+        let blocking = self.enforcement == RuleMode::Enforce;
+
+        if let Some(reason) = self.role_violation_reason(context) {
+            return Some(
+                Violation::new(&self.id, &self.name, reason)
+                    .with_severity(severity)
+                    .with_blocking(blocking),
+            );
+        }
+
         for condition in &self.conditions {
             if !self.check_condition(condition, context) {
-                let severity = match self.severity {
-                    RuleSeverity::Info => ViolationSeverity::Info,
-                    RuleSeverity::Warning => ViolationSeverity::Warning,
-                    RuleSeverity::Error => ViolationSeverity::Error,
-                    RuleSeverity::Critical => ViolationSeverity::Critical,
-                };
-                
-                return Some(Violation::new(
-                    &self.id,
-                    &self.name,
-                    &self.description,
-                ).with_severity(severity));
+                return Some(
+                    Violation::new(&self.id, &self.name, &self.description)
+                        .with_severity(severity)
+                        .with_blocking(blocking),
+                );
             }
         }
-        
+
         None
     }
-    
+
+    /// Evaluate the rule against a context, keeping every condition's
+    /// outcome instead of stopping at the first failure. Use this over
+    /// [`Self::evaluate`] when the caller needs to show *why* a rule
+    /// blocked -- e.g. structured CI output -- rather than just whether it
+    /// did.
+    pub fn evaluate_detailed(&self, context: &RuleContext) -> RuleReport {
+        if !self.enabled || self.out_of_subject_scope(context) {
+            return RuleReport {
+                rule_id: self.id.clone(),
+                severity: self.severity,
+                enforcement: self.enforcement,
+                passed: true,
+                blocking: false,
+                conditions: Vec::new(),
+            };
+        }
+
+        if let Some(reason) = self.role_violation_reason(context) {
+            return RuleReport {
+                rule_id: self.id.clone(),
+                severity: self.severity,
+                enforcement: self.enforcement,
+                passed: false,
+                blocking: self.enforcement == RuleMode::Enforce,
+                conditions: vec![ConditionResult {
+                    condition_label: "actor role requirements".to_string(),
+                    passed: false,
+                    observed_value: None,
+                    expected: reason,
+                }],
+            };
+        }
+
+        let conditions: Vec<ConditionResult> = self
+            .conditions
+            .iter()
+            .map(|condition| self.condition_result(condition, context))
+            .collect();
+        let passed = conditions.iter().all(|c| c.passed);
+
+        RuleReport {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            enforcement: self.enforcement,
+            passed,
+            blocking: !passed && self.enforcement == RuleMode::Enforce,
+            conditions,
+        }
+    }
+
+    /// Build a [`ConditionResult`] trace entry for a single condition,
+    /// reusing [`Self::check_condition`] for the pass/fail verdict.
+    fn condition_result(&self, condition: &RuleCondition, context: &RuleContext) -> ConditionResult {
+        let passed = self.check_condition(condition, context);
+        let (condition_label, expected, observed_value) = describe_condition(condition, context);
+        ConditionResult {
+            condition_label,
+            passed,
+            observed_value,
+            expected,
+        }
+    }
+
+    /// Check `context.actor` against `required_roles`/`forbidden_roles`,
+    /// returning a description of the mismatch if either is violated. An
+    /// actor-less context (e.g. automated tooling with no role model) is
+    /// treated as holding no roles, so `required_roles` still blocks it.
+    fn role_violation_reason(&self, context: &RuleContext) -> Option<String> {
+        if self.required_roles.is_empty() && self.forbidden_roles.is_empty() {
+            return None;
+        }
+
+        let roles: &[String] = context
+            .actor
+            .as_ref()
+            .map(|actor| actor.roles.as_slice())
+            .unwrap_or(&[]);
+
+        let missing: Vec<&str> = self
+            .required_roles
+            .iter()
+            .map(String::as_str)
+            .filter(|required| !roles.iter().any(|role| role == required))
+            .collect();
+        if !missing.is_empty() {
+            return Some(format!(
+                "requires role(s) {:?}, actor is missing {:?}",
+                self.required_roles, missing
+            ));
+        }
+
+        if let Some(forbidden) = self
+            .forbidden_roles
+            .iter()
+            .find(|forbidden| roles.iter().any(|role| role == *forbidden))
+        {
+            return Some(format!(
+                "forbids role '{}', which the actor holds",
+                forbidden
+            ));
+        }
+
+        None
+    }
+
+    /// Is `context`'s evaluating subject outside this rule's
+    /// [`Self::subjects`] scope? Empty `subjects` means every subject is in
+    /// scope, matching today's behavior. Otherwise the rule only applies
+    /// when the actor's id or one of its (role-manager-expanded) roles
+    /// appears in `subjects`.
+    fn out_of_subject_scope(&self, context: &RuleContext) -> bool {
+        if self.subjects.is_empty() {
+            return false;
+        }
+
+        let effective = context.effective_subjects();
+        !self.subjects.iter().any(|subject| effective.iter().any(|e| e == subject))
+    }
+
     fn check_condition(&self, condition: &RuleCondition, context: &RuleContext) -> bool {
         match condition {
             RuleCondition::RiskLevel { max } => context.risk_level <= *max,
@@ -150,10 +353,240 @@ impl PolicyRule {
             RuleCondition::AffectsCriticalFiles { forbidden } => {
                 if *forbidden { !context.affects_critical_files } else { true }
             }
-            RuleCondition::Custom { predicate } => {
-                // Custom predicates are evaluated externally
-                predicate(context)
+            RuleCondition::PathMatches { globs, forbidden } => {
+                let any_match = globs
+                    .iter()
+                    .any(|glob| context.affected_paths.iter().any(|path| crate::constraints::matches_pattern(path, glob)));
+                if *forbidden { !any_match } else { any_match }
+            }
+            RuleCondition::OperationMatches { regex } => {
+                Regex::new(regex)
+                    .map(|re| re.is_match(&context.operation_type))
+                    .unwrap_or(false)
+            }
+            RuleCondition::Expr { expr } => {
+                // Re-parsed on every evaluation, same tradeoff as the regex
+                // conditions in `tdln_quality::rules` -- keeps this variant a
+                // plain serializable string instead of caching a compiled AST.
+                expr::parse(expr).map(|compiled| compiled.eval(context)).unwrap_or(false)
+            }
+            RuleCondition::All(conditions) => {
+                conditions.iter().all(|c| self.check_condition(c, context))
             }
+            RuleCondition::Any(conditions) => {
+                conditions.iter().any(|c| self.check_condition(c, context))
+            }
+            RuleCondition::Not(condition) => !self.check_condition(condition, context),
+        }
+    }
+}
+
+/// Outcome of one [`RuleCondition`] in a [`RuleReport`], for structured
+/// (e.g. CI-consumable) output. `condition_label` and `expected` describe
+/// the condition itself; `observed_value` is the runtime context value it
+/// was checked against, where there's a single one to show.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConditionResult {
+    pub condition_label: String,
+    pub passed: bool,
+    pub observed_value: Option<String>,
+    pub expected: String,
+}
+
+/// Full per-condition trace for one [`PolicyRule::evaluate_detailed`] call.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleReport {
+    pub rule_id: String,
+    pub severity: RuleSeverity,
+    pub enforcement: RuleMode,
+    pub passed: bool,
+    /// Whether this rule, if it failed, would actually block the
+    /// operation -- `false` for disabled rules, passing rules, and rules
+    /// under [`RuleMode::Audit`].
+    pub blocking: bool,
+    pub conditions: Vec<ConditionResult>,
+}
+
+/// Counts of failed (non-passing, enabled) rules by [`RuleSeverity`] in a
+/// [`PolicyReport`].
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SeverityCounts {
+    pub info: usize,
+    pub warning: usize,
+    pub error: usize,
+    pub critical: usize,
+}
+
+impl SeverityCounts {
+    fn record(&mut self, severity: RuleSeverity) {
+        match severity {
+            RuleSeverity::Info => self.info += 1,
+            RuleSeverity::Warning => self.warning += 1,
+            RuleSeverity::Error => self.error += 1,
+            RuleSeverity::Critical => self.critical += 1,
+        }
+    }
+}
+
+/// Structured, JSON-serializable report aggregating [`RuleReport`]s for a
+/// whole set of rules -- in the spirit of cloudformation-guard's
+/// machine-readable output, so CI can show every clause that contributed
+/// to a block rather than just the first one [`PolicyRule::evaluate`] hit.
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyReport {
+    pub passed: bool,
+    /// Whether any rule would actually block the operation -- `false` in
+    /// a rollout window where every blocking rule has been downgraded to
+    /// [`RuleMode::Audit`], even if `passed` is also `false`.
+    pub blocked: bool,
+    pub rules: Vec<RuleReport>,
+    pub counts_by_severity: SeverityCounts,
+}
+
+/// Run [`PolicyRule::evaluate_detailed`] across `rules`, aggregating the
+/// results into one [`PolicyReport`].
+pub fn evaluate_all_detailed(rules: &[PolicyRule], context: &RuleContext) -> PolicyReport {
+    let mut counts_by_severity = SeverityCounts::default();
+    let rules: Vec<RuleReport> = rules
+        .iter()
+        .map(|rule| {
+            let report = rule.evaluate_detailed(context);
+            if !report.passed {
+                counts_by_severity.record(report.severity);
+            }
+            report
+        })
+        .collect();
+    let passed = rules.iter().all(|r| r.passed);
+    let blocked = rules.iter().any(|r| r.blocking);
+
+    PolicyReport {
+        passed,
+        blocked,
+        rules,
+        counts_by_severity,
+    }
+}
+
+/// Extract the text `pattern` captures in `text`: the first capturing group
+/// if the pattern has one, otherwise the whole match. `None` if `pattern`
+/// fails to compile or doesn't match -- used to show *what* an
+/// [`RuleCondition::OperationMatches`] condition actually matched, not just
+/// whether it did.
+fn regex_capture(pattern: &str, text: &str) -> Option<String> {
+    let re = Regex::new(pattern).ok()?;
+    let captures = re.captures(text)?;
+    captures.get(1).or_else(|| captures.get(0)).map(|m| m.as_str().to_string())
+}
+
+/// Describe a condition for a [`ConditionResult`]: a human-readable label,
+/// the expected value/range, and the single observed runtime value where
+/// one applies (composite conditions have no single observed value).
+fn describe_condition(condition: &RuleCondition, context: &RuleContext) -> (String, String, Option<String>) {
+    match condition {
+        RuleCondition::RiskLevel { max } => (
+            "risk_level".to_string(),
+            format!("<= {:?}", max),
+            Some(format!("{:?}", context.risk_level)),
+        ),
+        RuleCondition::RiskLevelMin { min } => (
+            "risk_level".to_string(),
+            format!(">= {:?}", min),
+            Some(format!("{:?}", context.risk_level)),
+        ),
+        RuleCondition::FileCount { max } => (
+            "file_count".to_string(),
+            format!("<= {max}"),
+            Some(context.file_count.to_string()),
+        ),
+        RuleCondition::FileCountMin { min } => (
+            "file_count".to_string(),
+            format!(">= {min}"),
+            Some(context.file_count.to_string()),
+        ),
+        RuleCondition::LineCount { max } => (
+            "line_count".to_string(),
+            format!("<= {max}"),
+            Some(context.line_count.to_string()),
+        ),
+        RuleCondition::LineCountMin { min } => (
+            "line_count".to_string(),
+            format!(">= {min}"),
+            Some(context.line_count.to_string()),
+        ),
+        RuleCondition::IsDestructive { forbidden } => (
+            "is_destructive".to_string(),
+            if *forbidden { "false".to_string() } else { "any".to_string() },
+            Some(context.is_destructive.to_string()),
+        ),
+        RuleCondition::TargetsProduction { forbidden } => (
+            "targets_production".to_string(),
+            if *forbidden { "false".to_string() } else { "any".to_string() },
+            Some(context.targets_production.to_string()),
+        ),
+        RuleCondition::TestsPassed { required } => (
+            "tests_passed".to_string(),
+            if *required { "true".to_string() } else { "any".to_string() },
+            Some(format!("{:?}", context.tests_passed)),
+        ),
+        RuleCondition::LintPassed { required } => (
+            "lint_passed".to_string(),
+            if *required { "true".to_string() } else { "any".to_string() },
+            Some(format!("{:?}", context.lint_passed)),
+        ),
+        RuleCondition::OperationType { allowed } => (
+            "operation_type".to_string(),
+            format!("in {:?}", allowed),
+            Some(context.operation_type.clone()),
+        ),
+        RuleCondition::OperationTypeNot { forbidden } => (
+            "operation_type".to_string(),
+            format!("not in {:?}", forbidden),
+            Some(context.operation_type.clone()),
+        ),
+        RuleCondition::HasConfirmation { required } => (
+            "has_confirmation".to_string(),
+            if *required { "true".to_string() } else { "any".to_string() },
+            Some(context.has_confirmation.to_string()),
+        ),
+        RuleCondition::ModeIs { mode } => (
+            "mode".to_string(),
+            mode.clone(),
+            Some(context.mode.clone()),
+        ),
+        RuleCondition::AffectsCriticalFiles { forbidden } => (
+            "affects_critical_files".to_string(),
+            if *forbidden { "false".to_string() } else { "any".to_string() },
+            Some(context.affects_critical_files.to_string()),
+        ),
+        RuleCondition::PathMatches { globs, forbidden } => (
+            "path_matches".to_string(),
+            if *forbidden { format!("none match {:?}", globs) } else { format!("any match {:?}", globs) },
+            Some(format!("{:?}", context.affected_paths)),
+        ),
+        RuleCondition::OperationMatches { regex } => (
+            "operation_matches".to_string(),
+            regex.clone(),
+            regex_capture(regex, &context.operation_type),
+        ),
+        RuleCondition::Expr { expr } => ("expr".to_string(), expr.clone(), None),
+        RuleCondition::All(children) => {
+            let labels: Vec<String> = children
+                .iter()
+                .map(|c| describe_condition(c, context).0)
+                .collect();
+            (format!("all({})", labels.join(", ")), "all children pass".to_string(), None)
+        }
+        RuleCondition::Any(children) => {
+            let labels: Vec<String> = children
+                .iter()
+                .map(|c| describe_condition(c, context).0)
+                .collect();
+            (format!("any({})", labels.join(", ")), "at least one child passes".to_string(), None)
+        }
+        RuleCondition::Not(child) => {
+            let (label, _, _) = describe_condition(child, context);
+            (format!("not({label})"), "child fails".to_string(), None)
         }
     }
 }
@@ -208,8 +641,28 @@ pub enum RuleCondition {
     ModeIs { mode: String },
     /// Check if affects critical files
     AffectsCriticalFiles { forbidden: bool },
-    /// Custom predicate (for advanced rules)
-    Custom { predicate: fn(&RuleContext) -> bool },
+    /// At least one of `context.affected_paths` matches (or, if
+    /// `forbidden`, must not match) one of `globs`, checked with the same
+    /// matcher [`crate::constraints::matches_pattern`] uses for
+    /// layered-constraint path rules -- so e.g. `infra/**` can require
+    /// confirmation without duplicating `affects_critical_files`.
+    PathMatches { globs: Vec<String>, forbidden: bool },
+    /// `context.operation_type` matches this regex, checked anywhere in the
+    /// string and recompiled per evaluation -- same tradeoff as
+    /// [`crate::critical_paths::CriticalPathRule::matches`].
+    OperationMatches { regex: String },
+    /// Advanced condition expressed as a boolean expression over
+    /// [`RuleContext`] fields (see [`crate::expr`]), e.g.
+    /// `risk_level >= 2 && !has_confirmation`. Replaces the old
+    /// non-serializable `Custom { predicate: fn(&RuleContext) -> bool }`
+    /// escape hatch with a fully JSON-expressible one.
+    Expr { expr: String },
+    /// Passes iff every child condition passes.
+    All(Vec<RuleCondition>),
+    /// Passes iff at least one child condition passes.
+    Any(Vec<RuleCondition>),
+    /// Inverts a child condition.
+    Not(Box<RuleCondition>),
 }
 
 // Implement Serialize/Deserialize for RuleCondition
@@ -251,6 +704,35 @@ impl Serialize for RuleCondition {
                 map.serialize_entry("type", "operation_type")?;
                 map.serialize_entry("allowed", allowed)?;
             }
+            RuleCondition::PathMatches { globs, forbidden } => {
+                map.serialize_entry("type", "path_matches")?;
+                map.serialize_entry("globs", globs)?;
+                map.serialize_entry("forbidden", forbidden)?;
+            }
+            RuleCondition::OperationMatches { regex } => {
+                map.serialize_entry("type", "operation_matches")?;
+                map.serialize_entry("regex", regex)?;
+            }
+            RuleCondition::HasConfirmation { required } => {
+                map.serialize_entry("type", "has_confirmation")?;
+                map.serialize_entry("required", required)?;
+            }
+            RuleCondition::Expr { expr } => {
+                map.serialize_entry("type", "expr")?;
+                map.serialize_entry("expr", expr)?;
+            }
+            RuleCondition::All(conditions) => {
+                map.serialize_entry("type", "all")?;
+                map.serialize_entry("conditions", conditions)?;
+            }
+            RuleCondition::Any(conditions) => {
+                map.serialize_entry("type", "any")?;
+                map.serialize_entry("conditions", conditions)?;
+            }
+            RuleCondition::Not(condition) => {
+                map.serialize_entry("type", "not")?;
+                map.serialize_entry("condition", condition)?;
+            }
             _ => {
                 map.serialize_entry("type", "custom")?;
             }
@@ -286,7 +768,12 @@ impl<'de> Deserialize<'de> for RuleCondition {
                 let mut required: Option<bool> = None;
                 let mut allowed: Option<Vec<String>> = None;
                 let mut max_risk: Option<RiskLevel> = None;
-                
+                let mut expr_source: Option<String> = None;
+                let mut conditions: Option<Vec<RuleCondition>> = None;
+                let mut condition: Option<Box<RuleCondition>> = None;
+                let mut globs: Option<Vec<String>> = None;
+                let mut regex_source: Option<String> = None;
+
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
                         "type" => condition_type = Some(map.next_value()?),
@@ -303,6 +790,11 @@ impl<'de> Deserialize<'de> for RuleCondition {
                         "forbidden" => forbidden = Some(map.next_value()?),
                         "required" => required = Some(map.next_value()?),
                         "allowed" => allowed = Some(map.next_value()?),
+                        "expr" => expr_source = Some(map.next_value()?),
+                        "conditions" => conditions = Some(map.next_value()?),
+                        "condition" => condition = Some(map.next_value()?),
+                        "globs" => globs = Some(map.next_value()?),
+                        "regex" => regex_source = Some(map.next_value()?),
                         _ => { let _: serde_json::Value = map.next_value()?; }
                     }
                 }
@@ -331,9 +823,28 @@ impl<'de> Deserialize<'de> for RuleCondition {
                     "operation_type" => Ok(RuleCondition::OperationType {
                         allowed: allowed.unwrap_or_default(),
                     }),
+                    "expr" => Ok(RuleCondition::Expr {
+                        expr: expr_source.unwrap_or_default(),
+                    }),
+                    "path_matches" => Ok(RuleCondition::PathMatches {
+                        globs: globs.unwrap_or_default(),
+                        forbidden: forbidden.unwrap_or(true),
+                    }),
+                    "operation_matches" => Ok(RuleCondition::OperationMatches {
+                        regex: regex_source.unwrap_or_default(),
+                    }),
+                    "has_confirmation" => Ok(RuleCondition::HasConfirmation {
+                        required: required.unwrap_or(true),
+                    }),
+                    "all" => Ok(RuleCondition::All(conditions.unwrap_or_default())),
+                    "any" => Ok(RuleCondition::Any(conditions.unwrap_or_default())),
+                    "not" => Ok(RuleCondition::Not(
+                        condition.ok_or_else(|| de::Error::missing_field("condition"))?,
+                    )),
                     _ => Err(de::Error::unknown_variant(&ctype, &[
                         "risk_level", "file_count", "line_count", "is_destructive",
-                        "targets_production", "tests_passed", "operation_type"
+                        "targets_production", "tests_passed", "operation_type", "expr",
+                        "path_matches", "operation_matches", "has_confirmation", "all", "any", "not",
                     ])),
                 }
             }
@@ -343,6 +854,98 @@ impl<'de> Deserialize<'de> for RuleCondition {
     }
 }
 
+/// Resolves transitive role membership, mirroring Casbin's RBAC role
+/// manager (a `g` policy): register that `release-engineer` inherits
+/// `developer`, and [`Self::expand`] turns a held role list into every role
+/// it grants, directly or transitively.
+#[derive(Debug, Clone, Default)]
+pub struct RoleManager {
+    inherits: HashMap<String, Vec<String>>,
+}
+
+impl RoleManager {
+    /// A role manager with no inheritance registered.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grant everything `parent` grants to `role` as well.
+    pub fn with_inheritance(mut self, role: impl Into<String>, parent: impl Into<String>) -> Self {
+        self.inherits.entry(role.into()).or_default().push(parent.into());
+        self
+    }
+
+    /// Expand `roles` to include every role transitively inherited,
+    /// deduplicated. The input roles themselves are always included.
+    pub fn expand(&self, roles: &[String]) -> Vec<String> {
+        let mut effective: Vec<String> = Vec::new();
+        let mut queue: Vec<String> = roles.to_vec();
+
+        while let Some(role) = queue.pop() {
+            if effective.contains(&role) {
+                continue;
+            }
+            effective.push(role.clone());
+            if let Some(parents) = self.inherits.get(&role) {
+                queue.extend(parents.iter().cloned());
+            }
+        }
+
+        effective
+    }
+}
+
+/// Who is requesting an operation: an identity plus the roles and free-form
+/// attributes that role-aware [`PolicyRule`]s and [`crate::policy_set::PolicySet`]
+/// capability grants can key off of.
+///
+/// Distinct from [`crate::authorizer::Principal`], which pairs an identity
+/// with a single risk-ceiling role for [`crate::authorizer::Authorizer`];
+/// an `Actor` can hold several roles at once (e.g. `senior` and `on-call`)
+/// and carries arbitrary attributes for rules that need more than a role
+/// name, so the same policy set can behave differently for an autonomous
+/// agent vs. a human operator without duplicating whole modes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Actor {
+    /// Identity of the caller (e.g. username or account id).
+    pub id: String,
+    /// Roles held by the caller, checked against `required_roles` /
+    /// `forbidden_roles` on [`PolicyRule`] and against
+    /// [`crate::policy_set::PolicySet`]'s capability grant table.
+    pub roles: Vec<String>,
+    /// Free-form attributes (e.g. team, clearance) for rules to inspect.
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+}
+
+impl Actor {
+    /// Create a new actor with no roles or attributes.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            roles: Vec::new(),
+            attributes: HashMap::new(),
+        }
+    }
+
+    /// Grant this actor a role.
+    pub fn with_role(mut self, role: impl Into<String>) -> Self {
+        self.roles.push(role.into());
+        self
+    }
+
+    /// Attach a free-form attribute.
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Does this actor hold `role`?
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+}
+
 /// Context for rule evaluation
 #[derive(Debug, Clone, Default)]
 pub struct RuleContext {
@@ -357,6 +960,18 @@ pub struct RuleContext {
     pub has_confirmation: bool,
     pub mode: String,
     pub affects_critical_files: bool,
+    /// Paths affected by the operation, forwarded to
+    /// [`crate::risk::RiskInput::affected_paths`] when this context is used
+    /// to drive a risk assessment alongside rule evaluation.
+    pub affected_paths: Vec<String>,
+    /// Who is requesting the operation, if the caller supplied one. `None`
+    /// behaves as an actor with no roles: `required_roles` still blocks,
+    /// `forbidden_roles` never does.
+    pub actor: Option<Actor>,
+    /// Role hierarchy consulted by [`Self::effective_subjects`] to expand
+    /// `actor`'s roles before matching them against [`PolicyRule::subjects`].
+    /// `None` leaves the actor's roles unexpanded.
+    pub role_manager: Option<RoleManager>,
 }
 
 impl RuleContext {
@@ -416,6 +1031,47 @@ impl RuleContext {
         self.affects_critical_files = true;
         self
     }
+
+    /// Record the paths this operation affects, for the risk assessment
+    /// built from this context (see [`crate::risk::RiskInput::affected_paths`]).
+    pub fn with_affected_paths<I, S>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.affected_paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set who is requesting the operation (see [`Actor`]).
+    pub fn with_actor(mut self, actor: Actor) -> Self {
+        self.actor = Some(actor);
+        self
+    }
+
+    /// Set the role hierarchy used to expand `actor`'s roles for
+    /// [`PolicyRule::subjects`] scoping (see [`RoleManager`]).
+    pub fn with_role_manager(mut self, role_manager: RoleManager) -> Self {
+        self.role_manager = Some(role_manager);
+        self
+    }
+
+    /// Every identity this context's actor can be matched against for
+    /// [`PolicyRule::subjects`] scoping: the actor's own id plus every role
+    /// it holds, transitively expanded through `role_manager` inheritance
+    /// when one is set. Empty for an actor-less context.
+    pub fn effective_subjects(&self) -> Vec<String> {
+        let Some(actor) = &self.actor else {
+            return Vec::new();
+        };
+
+        let mut subjects = match &self.role_manager {
+            Some(role_manager) => role_manager.expand(&actor.roles),
+            None => actor.roles.clone(),
+        };
+        subjects.push(actor.id.clone());
+        subjects
+    }
 }
 
 /// Predefined policy rules
@@ -521,5 +1177,355 @@ mod tests {
         assert!(!rules.is_empty());
         assert!(rules.iter().any(|r| r.id == "max_files_mechanic"));
     }
+
+    #[test]
+    fn test_required_role_blocks_when_actor_lacks_it() {
+        let rule = PolicyRule::new("prod_deploy", "Production Deploy")
+            .with_description("Only release engineers may deploy")
+            .requires_role("release-engineer");
+
+        let no_actor = RuleContext::new("deploy");
+        assert!(rule.evaluate(&no_actor).is_some());
+
+        let wrong_role = RuleContext::new("deploy").with_actor(Actor::new("alice").with_role("junior"));
+        assert!(rule.evaluate(&wrong_role).is_some());
+
+        let right_role =
+            RuleContext::new("deploy").with_actor(Actor::new("bob").with_role("release-engineer"));
+        assert!(rule.evaluate(&right_role).is_none());
+    }
+
+    #[test]
+    fn test_forbidden_role_blocks_when_actor_holds_it() {
+        let rule = PolicyRule::new("no_interns_on_prod", "No Interns On Production")
+            .forbids_role("intern");
+
+        let intern = RuleContext::new("deploy").with_actor(Actor::new("carol").with_role("intern"));
+        assert!(rule.evaluate(&intern).is_some());
+
+        let senior = RuleContext::new("deploy").with_actor(Actor::new("dan").with_role("senior"));
+        assert!(rule.evaluate(&senior).is_none());
+    }
+
+    #[test]
+    fn test_expr_condition() {
+        let rule = PolicyRule::new("expr_rule", "Expr Rule")
+            .with_condition(RuleCondition::Expr {
+                expr: "risk_level < 2 || has_confirmation".to_string(),
+            });
+
+        let unconfirmed_high_risk = RuleContext::new("deploy").with_risk(RiskLevel::High);
+        let confirmed_high_risk = unconfirmed_high_risk.clone().confirmed();
+
+        assert!(rule.evaluate(&unconfirmed_high_risk).is_some());
+        assert!(rule.evaluate(&confirmed_high_risk).is_none());
+    }
+
+    #[test]
+    fn test_expr_condition_round_trips_through_json() {
+        let condition = RuleCondition::Expr {
+            expr: "file_count > 5".to_string(),
+        };
+
+        let json = serde_json::to_string(&condition).unwrap();
+        let restored: RuleCondition = serde_json::from_str(&json).unwrap();
+
+        assert!(matches!(restored, RuleCondition::Expr { expr } if expr == "file_count > 5"));
+    }
+
+    #[test]
+    fn test_all_condition_requires_every_child_to_pass() {
+        let rule = PolicyRule::new("destructive_prod_or_critical", "Destructive Prod Or Critical")
+            .with_condition(RuleCondition::All(vec![
+                RuleCondition::IsDestructive { forbidden: true },
+            ]));
+
+        let destructive = RuleContext::new("deploy").destructive();
+        let safe = RuleContext::new("deploy");
+
+        assert!(rule.evaluate(&destructive).is_some());
+        assert!(rule.evaluate(&safe).is_none());
+    }
+
+    #[test]
+    fn test_any_condition_passes_if_one_child_passes() {
+        let rule = PolicyRule::new("needs_review", "Needs Review").with_condition(
+            RuleCondition::Any(vec![
+                RuleCondition::TargetsProduction { forbidden: true },
+                RuleCondition::AffectsCriticalFiles { forbidden: true },
+            ]),
+        );
+
+        let prod_only = RuleContext::new("deploy").production();
+        let critical_only = RuleContext::new("deploy").critical_files();
+        let neither = RuleContext::new("deploy");
+        let both = RuleContext::new("deploy").production().critical_files();
+
+        assert!(rule.evaluate(&prod_only).is_none());
+        assert!(rule.evaluate(&critical_only).is_none());
+        assert!(rule.evaluate(&neither).is_none());
+        assert!(rule.evaluate(&both).is_some());
+    }
+
+    #[test]
+    fn test_not_condition_inverts_its_child() {
+        let rule = PolicyRule::new("requires_confirmation", "Requires Confirmation")
+            .with_condition(RuleCondition::Not(Box::new(RuleCondition::HasConfirmation {
+                required: true,
+            })));
+
+        let confirmed = RuleContext::new("deploy").confirmed();
+        let unconfirmed = RuleContext::new("deploy");
+
+        assert!(rule.evaluate(&confirmed).is_some());
+        assert!(rule.evaluate(&unconfirmed).is_none());
+    }
+
+    #[test]
+    fn test_nested_composite_conditions_round_trip_through_json() {
+        let condition = RuleCondition::All(vec![
+            RuleCondition::IsDestructive { forbidden: true },
+            RuleCondition::Any(vec![
+                RuleCondition::TargetsProduction { forbidden: true },
+                RuleCondition::Not(Box::new(RuleCondition::HasConfirmation { required: true })),
+            ]),
+        ]);
+
+        let json = serde_json::to_string(&condition).unwrap();
+        let restored: RuleCondition = serde_json::from_str(&json).unwrap();
+
+        assert!(matches!(restored, RuleCondition::All(children) if children.len() == 2));
+    }
+
+    #[test]
+    fn test_evaluate_detailed_reports_every_condition() {
+        let rule = PolicyRule::new("test_rule", "Test Rule")
+            .with_condition(RuleCondition::FileCount { max: 5 })
+            .with_condition(RuleCondition::LineCount { max: 200 });
+
+        let report = rule.evaluate_detailed(&RuleContext::new("bug_fix").with_files(10).with_lines(50));
+
+        assert!(!report.passed);
+        assert_eq!(report.conditions.len(), 2);
+        assert!(!report.conditions[0].passed);
+        assert_eq!(report.conditions[0].observed_value.as_deref(), Some("10"));
+        assert!(report.conditions[1].passed);
+    }
+
+    #[test]
+    fn test_evaluate_detailed_stops_at_role_violation() {
+        let rule = PolicyRule::new("prod_deploy", "Production Deploy").requires_role("release-engineer");
+
+        let report = rule.evaluate_detailed(&RuleContext::new("deploy"));
+        assert!(!report.passed);
+        assert_eq!(report.conditions.len(), 1);
+    }
+
+    #[test]
+    fn test_evaluate_all_detailed_counts_by_severity() {
+        let rules = vec![
+            PolicyRule::new("max_files", "Max Files")
+                .with_severity(RuleSeverity::Error)
+                .with_condition(RuleCondition::FileCount { max: 1 }),
+            PolicyRule::new("max_lines", "Max Lines")
+                .with_severity(RuleSeverity::Critical)
+                .with_condition(RuleCondition::LineCount { max: 1 }),
+        ];
+
+        let report = evaluate_all_detailed(&rules, &RuleContext::new("bug_fix").with_files(5).with_lines(5));
+
+        assert!(!report.passed);
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.counts_by_severity.error, 1);
+        assert_eq!(report.counts_by_severity.critical, 1);
+    }
+
+    #[test]
+    fn test_policy_report_serializes_to_json() {
+        let rules = vec![PolicyRule::new("max_files", "Max Files")
+            .with_condition(RuleCondition::FileCount { max: 1 })];
+
+        let report = evaluate_all_detailed(&rules, &RuleContext::new("bug_fix").with_files(5));
+        let json = serde_json::to_string(&report).unwrap();
+
+        assert!(json.contains("\"rule_id\":\"max_files\""));
+        assert!(json.contains("\"counts_by_severity\""));
+    }
+
+    #[test]
+    fn test_actor_has_role() {
+        let actor = Actor::new("erin").with_role("senior").with_role("on-call");
+        assert!(actor.has_role("senior"));
+        assert!(!actor.has_role("intern"));
+    }
+
+    #[test]
+    fn test_audit_only_rule_reports_but_does_not_block() {
+        let rule = PolicyRule::new("max_files", "Max Files")
+            .with_severity(RuleSeverity::Critical)
+            .with_condition(RuleCondition::FileCount { max: 1 })
+            .audit_only();
+
+        let violation = rule.evaluate(&RuleContext::new("bug_fix").with_files(5)).unwrap();
+        assert!(!violation.blocking);
+        assert_eq!(violation.severity, ViolationSeverity::Critical);
+    }
+
+    #[test]
+    fn test_enforce_rule_blocks_by_default() {
+        let rule = PolicyRule::new("max_files", "Max Files")
+            .with_condition(RuleCondition::FileCount { max: 1 });
+
+        let violation = rule.evaluate(&RuleContext::new("bug_fix").with_files(5)).unwrap();
+        assert!(violation.blocking);
+    }
+
+    #[test]
+    fn test_evaluate_detailed_reflects_audit_mode_blocking() {
+        let rule = PolicyRule::new("max_files", "Max Files")
+            .with_condition(RuleCondition::FileCount { max: 1 })
+            .audit_only();
+
+        let report = rule.evaluate_detailed(&RuleContext::new("bug_fix").with_files(5));
+        assert!(!report.passed);
+        assert!(!report.blocking);
+        assert_eq!(report.enforcement, RuleMode::Audit);
+    }
+
+    #[test]
+    fn test_evaluate_all_detailed_not_blocked_when_only_audit_rules_fail() {
+        let rules = vec![PolicyRule::new("max_files", "Max Files")
+            .with_severity(RuleSeverity::Error)
+            .with_condition(RuleCondition::FileCount { max: 1 })
+            .audit_only()];
+
+        let report = evaluate_all_detailed(&rules, &RuleContext::new("bug_fix").with_files(5));
+        assert!(!report.passed);
+        assert!(!report.blocked);
+    }
+
+    #[test]
+    fn test_role_manager_expands_transitive_inheritance() {
+        let roles = RoleManager::new().with_inheritance("release-engineer", "developer");
+
+        let effective = roles.expand(&["release-engineer".to_string()]);
+        assert!(effective.contains(&"release-engineer".to_string()));
+        assert!(effective.contains(&"developer".to_string()));
+    }
+
+    #[test]
+    fn test_rule_scoped_to_subject_skips_non_matching_actors() {
+        let rule = PolicyRule::new("junior_file_cap", "Junior File Cap")
+            .with_condition(RuleCondition::FileCount { max: 1 })
+            .scoped_to_subject("junior");
+
+        let context = RuleContext::new("bug_fix")
+            .with_files(5)
+            .with_actor(Actor::new("erin").with_role("senior"));
+
+        assert!(rule.evaluate(&context).is_none());
+    }
+
+    #[test]
+    fn test_rule_scoped_to_subject_matches_inherited_role() {
+        let rule = PolicyRule::new("dev_file_cap", "Developer File Cap")
+            .with_condition(RuleCondition::FileCount { max: 1 })
+            .scoped_to_subject("developer");
+
+        let role_manager = RoleManager::new().with_inheritance("release-engineer", "developer");
+        let context = RuleContext::new("bug_fix")
+            .with_files(5)
+            .with_actor(Actor::new("sam").with_role("release-engineer"))
+            .with_role_manager(role_manager);
+
+        assert!(rule.evaluate(&context).is_some());
+    }
+
+    #[test]
+    fn test_rule_scoped_to_subject_matches_actor_id_directly() {
+        let rule = PolicyRule::new("pinned_actor", "Pinned Actor")
+            .with_condition(RuleCondition::FileCount { max: 1 })
+            .scoped_to_subject("erin");
+
+        let context = RuleContext::new("bug_fix")
+            .with_files(5)
+            .with_actor(Actor::new("erin"));
+
+        assert!(rule.evaluate(&context).is_some());
+    }
+
+    #[test]
+    fn test_path_matches_forbidden_blocks_matching_paths() {
+        let rule = PolicyRule::new("no_infra_changes", "No Infra Changes").with_condition(
+            RuleCondition::PathMatches {
+                globs: vec!["infra/**".to_string()],
+                forbidden: true,
+            },
+        );
+
+        let touches_infra = RuleContext::new("deploy").with_affected_paths(["infra/network.tf"]);
+        let elsewhere = RuleContext::new("deploy").with_affected_paths(["src/main.rs"]);
+
+        assert!(rule.evaluate(&touches_infra).is_some());
+        assert!(rule.evaluate(&elsewhere).is_none());
+    }
+
+    #[test]
+    fn test_path_matches_requires_confirmation_for_infra_changes() {
+        let rule = PolicyRule::new("infra_needs_confirmation", "Infra Needs Confirmation")
+            .with_condition(RuleCondition::Any(vec![
+                RuleCondition::Not(Box::new(RuleCondition::PathMatches {
+                    globs: vec!["infra/**".to_string()],
+                    forbidden: false,
+                })),
+                RuleCondition::HasConfirmation { required: true },
+            ]));
+
+        let unconfirmed_infra = RuleContext::new("deploy").with_affected_paths(["infra/network.tf"]);
+        let confirmed_infra = RuleContext::new("deploy")
+            .with_affected_paths(["infra/network.tf"])
+            .confirmed();
+        let unconfirmed_app_code = RuleContext::new("deploy").with_affected_paths(["src/main.rs"]);
+
+        assert!(rule.evaluate(&unconfirmed_infra).is_some());
+        assert!(rule.evaluate(&confirmed_infra).is_none());
+        assert!(rule.evaluate(&unconfirmed_app_code).is_none());
+    }
+
+    #[test]
+    fn test_operation_matches_regex() {
+        let rule = PolicyRule::new("no_force_push", "No Force Push").with_condition(
+            RuleCondition::Not(Box::new(RuleCondition::OperationMatches {
+                regex: r"^git_push_force".to_string(),
+            })),
+        );
+
+        assert!(rule.evaluate(&RuleContext::new("git_push_force_origin")).is_some());
+        assert!(rule.evaluate(&RuleContext::new("git_push")).is_none());
+    }
+
+    #[test]
+    fn test_path_and_operation_match_conditions_round_trip_through_json() {
+        let condition = RuleCondition::All(vec![
+            RuleCondition::PathMatches {
+                globs: vec!["infra/**".to_string(), "**/*.tf".to_string()],
+                forbidden: true,
+            },
+            RuleCondition::OperationMatches {
+                regex: "^deploy_".to_string(),
+            },
+        ]);
+
+        let json = serde_json::to_string(&condition).unwrap();
+        let parsed: RuleCondition = serde_json::from_str(&json).unwrap();
+
+        match parsed {
+            RuleCondition::All(children) => {
+                assert!(matches!(&children[0], RuleCondition::PathMatches { forbidden: true, .. }));
+                assert!(matches!(&children[1], RuleCondition::OperationMatches { .. }));
+            }
+            _ => panic!("expected an All condition"),
+        }
+    }
 }
 
@@ -0,0 +1,282 @@
+//! Declarative rule language compiling straight into [`PolicyRule`], so
+//! operators can author rules like `rule::default_rules()`'s without
+//! recompiling.
+//!
+//! Each clause is one line of the form:
+//!
+//! ```text
+//! <verdict> "<name>" when <condition>
+//! ```
+//!
+//! `verdict` is `warn` or `block`; `name` becomes both the compiled rule's
+//! `id` and (on a violation) `Violation::rule_id`; `condition` is the same
+//! boolean expression language [`crate::expr`] already compiles for
+//! [`RuleCondition::Expr`] (`==`, `!=`, `<`, `<=`, `>`, `>=`, `in`,
+//! `contains`, `matches`, `glob`, `regex_replace`, `any`/`all`, `&&`, `||`,
+//! `!`, parentheses) over [`RuleContext`] fields, e.g.:
+//!
+//! ```text
+//! block "no_prod_destructive" when is_destructive && targets_production
+//! warn "large_change" when file_count > 10 && !tests_passed
+//! block "no_rewrites_in_mechanic" when mode == "mechanic" && operation_type contains "rewrite"
+//! ```
+//!
+//! A clause's condition describes what counts as a *violation* -- the
+//! mirror image of a [`PolicyRule`]'s own conditions, which describe what
+//! must hold to pass -- so each compiled rule wraps it in
+//! [`RuleCondition::Not`]. Unlike calling [`expr::parse`] directly, a
+//! condition naming a field outside [`KNOWN_FIELDS`], or a `matches`/
+//! `regex_replace` pattern that fails to compile, is rejected here at
+//! compile time instead of silently evaluating to `false` (or erroring) on
+//! every evaluation. Blank lines and `#`-prefixed comments are skipped, and
+//! clauses compile in source order, so [`PolicySet`](crate::policy_set::PolicySet)
+//! evaluates them in that same order and `Verdict::combine` always picks
+//! among violations deterministically.
+//!
+//! See also [`crate::policy_dsl`], an older, already-shipped clause
+//! language (`<severity> "<name>": <condition>`, `and`/`or`/`not`
+//! keywords) that compiles straight to [`Violation`](crate::verdict::Violation)s
+//! rather than [`PolicyRule`]s; this module targets the common case of
+//! wanting rules that slot into a [`PolicySet`](crate::policy_set::PolicySet)'s
+//! `rules` list alongside hand-written ones.
+
+use crate::expr::{self, Expr, ValueExpr};
+use crate::rule::{PolicyRule, RuleCondition, RuleSeverity};
+
+/// Fields [`crate::expr`] resolves at evaluation time (see its private
+/// `field_value`). A condition naming anything else is rejected here at
+/// compile time, rather than silently matching `false` at every
+/// evaluation the way a bare [`expr::parse`] call would. `item` is not
+/// listed here -- it's only valid inside an `any`/`all` predicate, checked
+/// separately.
+const KNOWN_FIELDS: &[&str] = &[
+    "operation_type",
+    "mode",
+    "risk_level",
+    "file_count",
+    "line_count",
+    "is_destructive",
+    "targets_production",
+    "tests_passed",
+    "lint_passed",
+    "has_confirmation",
+    "affects_critical_files",
+    "files",
+    "affected_paths",
+];
+
+/// Error compiling a [`rule_dsl`](self) document.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("line {line}: {message}")]
+pub struct RuleDslError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Compile a full document: one clause per non-blank, non-comment line, in
+/// source order.
+pub fn parse_source(source: &str) -> Result<Vec<PolicyRule>, RuleDslError> {
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                None
+            } else {
+                Some((i + 1, trimmed))
+            }
+        })
+        .map(|(line, clause)| compile_clause(clause).map_err(|message| RuleDslError { line, message }))
+        .collect()
+}
+
+fn compile_clause(line: &str) -> Result<PolicyRule, String> {
+    let (verdict_kw, rest) = split_first_word(line).ok_or_else(|| "expected a verdict keyword".to_string())?;
+    let severity = match verdict_kw {
+        "warn" => RuleSeverity::Warning,
+        "block" => RuleSeverity::Error,
+        other => return Err(format!("unknown verdict '{other}', expected 'warn' or 'block'")),
+    };
+
+    let (name, rest) = parse_quoted_name(rest)?;
+
+    let condition_src = rest
+        .trim_start()
+        .strip_prefix("when")
+        .ok_or_else(|| "expected 'when' before the condition".to_string())?
+        .trim();
+    if condition_src.is_empty() {
+        return Err("expected a condition after 'when'".to_string());
+    }
+
+    let condition = expr::parse(condition_src).map_err(|e| e.0)?;
+    validate_fields(&condition, false)?;
+    expr::validate_patterns(&condition)?;
+
+    let rule_id = name.clone();
+    Ok(PolicyRule::new(rule_id, name.clone())
+        .with_description(format!("'{name}' matched: {condition_src}"))
+        .with_severity(severity)
+        .with_condition(RuleCondition::Not(Box::new(RuleCondition::Expr { expr: condition_src.to_string() }))))
+}
+
+/// Split off the clause's leading whitespace-delimited word (its verdict
+/// keyword), returning it and the unconsumed remainder.
+fn split_first_word(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    match trimmed.find(char::is_whitespace) {
+        Some(end) => Some((&trimmed[..end], &trimmed[end..])),
+        None => None,
+    }
+}
+
+/// Parse a `"quoted name"` from the start of `rest`, returning the
+/// unquoted name and what follows the closing quote.
+fn parse_quoted_name(rest: &str) -> Result<(String, &str), String> {
+    let rest = rest.trim_start();
+    if !rest.starts_with('"') {
+        return Err("expected a quoted rule name".to_string());
+    }
+    match rest[1..].find('"') {
+        Some(end) => Ok((rest[1..1 + end].to_string(), &rest[2 + end..])),
+        None => Err("unterminated quoted rule name".to_string()),
+    }
+}
+
+/// Walk a parsed condition, rejecting any [`Expr::Compare`] or
+/// [`Expr::Quantifier`] that names a field outside [`KNOWN_FIELDS`].
+/// `allow_item` permits the pseudo-field `item`, valid only inside an
+/// `any`/`all` predicate.
+fn validate_fields(condition: &Expr, allow_item: bool) -> Result<(), String> {
+    match condition {
+        Expr::And(l, r) | Expr::Or(l, r) => {
+            validate_fields(l, allow_item)?;
+            validate_fields(r, allow_item)
+        }
+        Expr::Not(inner) => validate_fields(inner, allow_item),
+        Expr::Compare(value, _, _) => validate_value_field(value, allow_item),
+        Expr::Quantifier { list_field, predicate, .. } => {
+            if !KNOWN_FIELDS.contains(&list_field.as_str()) {
+                return Err(format!("unknown field '{list_field}'"));
+            }
+            validate_fields(predicate, true)
+        }
+    }
+}
+
+fn validate_value_field(value: &ValueExpr, allow_item: bool) -> Result<(), String> {
+    match value {
+        ValueExpr::Field(name) if allow_item && name == "item" => Ok(()),
+        ValueExpr::Field(name) => {
+            if KNOWN_FIELDS.contains(&name.as_str()) {
+                Ok(())
+            } else {
+                Err(format!("unknown field '{name}'"))
+            }
+        }
+        ValueExpr::RegexReplace { inner, .. } => validate_value_field(inner, allow_item),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::RuleContext;
+
+    #[test]
+    fn compiles_a_block_clause_into_a_blocking_rule() {
+        let rules = parse_source(r#"block "no_prod_destructive" when is_destructive && targets_production"#).unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].id, "no_prod_destructive");
+        assert_eq!(rules[0].severity, RuleSeverity::Error);
+
+        let violating = RuleContext::new("deploy").destructive().production();
+        let clean = RuleContext::new("deploy");
+        assert!(rules[0].evaluate(&violating).is_some());
+        assert!(rules[0].evaluate(&clean).is_none());
+    }
+
+    #[test]
+    fn compiles_a_warn_clause_into_a_non_blocking_rule() {
+        let rules = parse_source(r#"warn "large_change" when file_count > 10"#).unwrap();
+        assert_eq!(rules[0].severity, RuleSeverity::Warning);
+        assert!(!rules[0].severity.is_blocking());
+    }
+
+    #[test]
+    fn skips_blank_lines_and_comments() {
+        let rules = parse_source(
+            "\n# a comment\nwarn \"large_change\" when file_count > 10\n\n# another\nblock \"no_destructive\" when is_destructive\n",
+        )
+        .unwrap();
+        assert_eq!(rules.len(), 2);
+    }
+
+    #[test]
+    fn preserves_source_order() {
+        let rules = parse_source(
+            "warn \"a\" when file_count > 1\nblock \"b\" when is_destructive\nwarn \"c\" when line_count > 1\n",
+        )
+        .unwrap();
+        assert_eq!(rules.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn rejects_an_unknown_verdict_keyword() {
+        let err = parse_source(r#"allow "oops" when is_destructive"#).unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("unknown verdict"));
+    }
+
+    #[test]
+    fn rejects_an_unknown_field_instead_of_silently_never_matching() {
+        let err = parse_source(r#"block "typo" when file_cnt > 10"#).unwrap_err();
+        assert!(err.message.contains("unknown field 'file_cnt'"));
+    }
+
+    #[test]
+    fn rejects_a_missing_when_keyword() {
+        let err = parse_source(r#"block "no_when" file_count > 10"#).unwrap_err();
+        assert!(err.message.contains("'when'"));
+    }
+
+    #[test]
+    fn reports_the_failing_line_number_in_a_multi_clause_document() {
+        let err =
+            parse_source("warn \"ok\" when file_count > 1\nblock \"bad\" when nonexistent_field == 1\n").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn compiles_a_clause_using_glob_over_the_files_list() {
+        let rules = parse_source(r#"block "no_infra_touch" when glob(files, "infra/**")"#).unwrap();
+
+        let touches_infra = RuleContext::new("deploy").with_affected_paths(["infra/network.tf"]);
+        let elsewhere = RuleContext::new("deploy").with_affected_paths(["src/main.rs"]);
+        assert!(rules[0].evaluate(&touches_infra).is_some());
+        assert!(rules[0].evaluate(&elsewhere).is_none());
+    }
+
+    #[test]
+    fn compiles_a_clause_using_an_any_quantifier() {
+        let rules = parse_source(r#"block "no_secrets" when any(files, item matches "\.secret$")"#).unwrap();
+
+        let leaks = RuleContext::new("deploy").with_affected_paths(["keys/.secret"]);
+        let clean = RuleContext::new("deploy").with_affected_paths(["src/main.rs"]);
+        assert!(rules[0].evaluate(&leaks).is_some());
+        assert!(rules[0].evaluate(&clean).is_none());
+    }
+
+    #[test]
+    fn rejects_an_unknown_list_field_in_a_quantifier() {
+        let err = parse_source(r#"block "typo" when any(filess, item matches "x")"#).unwrap_err();
+        assert!(err.message.contains("unknown field 'filess'"));
+    }
+
+    #[test]
+    fn rejects_an_invalid_regex_pattern_at_compile_time() {
+        let err = parse_source(r#"block "bad_regex" when operation_type matches "(""#).unwrap_err();
+        assert!(err.message.contains("invalid regex pattern"));
+    }
+}
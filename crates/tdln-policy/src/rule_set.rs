@@ -0,0 +1,425 @@
+//! Hot-reloadable, evaluation-cached [`PolicyRule`] sets.
+//!
+//! Borrows Casbin's watcher-and-cache design: [`RuleSet`] loads its rules
+//! from a JSON or TOML file (the same JSON/TOML dispatch-by-extension
+//! [`crate::config::ConstraintsDocument`] uses, with TOML as the only
+//! non-JSON format), holds them behind an `Arc`
+//! that [`RuleSet::reload`] swaps atomically, and caches
+//! [`PolicyReport`]s keyed on the [`RuleContext`] fields a rule can
+//! actually see, for a configurable TTL -- so a burst of identical
+//! evaluations (e.g. the same operation re-checked by several callers)
+//! skips re-running every condition.
+//!
+//! [`RuleSet::watch`] polls the source file for changes the same way
+//! [`crate::watch::watch_constraints`] does (debounced mtime polling, no
+//! background thread spawned by this crate), reloading and invalidating
+//! the cache when it settles. [`RuleSet::register_watcher`] lets a caller
+//! hook that reload to invalidate its own downstream state (e.g. a
+//! rendered policy summary).
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::rule::{evaluate_all_detailed, PolicyReport, PolicyRule, RuleContext, RuleMode, RuleSeverity};
+use crate::watch::DEFAULT_DEBOUNCE;
+
+/// Default time a cached [`PolicyReport`] stays valid for a given context.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Poll interval between mtime checks in [`RuleSet::watch`].
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Error loading, reloading, or watching a [`RuleSet`]'s source file.
+#[derive(Debug, thiserror::Error)]
+pub enum RuleSetError {
+    #[error("failed to read rule set file '{0}': {1}")]
+    Io(String, String),
+    #[error("failed to parse rule set file '{0}': {1}")]
+    Parse(String, String),
+    #[error("rule set has no source file to watch")]
+    NoSource,
+}
+
+struct CachedEvaluation {
+    report: PolicyReport,
+    computed_at: Instant,
+}
+
+/// A [`PolicyRule`] set that can be loaded from disk, hot-reloaded, and
+/// evaluated through a TTL cache.
+pub struct RuleSet {
+    rules: RwLock<Arc<Vec<PolicyRule>>>,
+    source: Option<PathBuf>,
+    cache: Mutex<HashMap<u64, CachedEvaluation>>,
+    cache_ttl: Duration,
+    watchers: Mutex<Vec<Box<dyn Fn() + Send + Sync>>>,
+    /// Global rollout-window override: while set, [`Self::evaluate`]
+    /// downgrades every `Error`/`Critical` rule to [`RuleMode::Audit`]
+    /// regardless of its own configured `enforcement`, so operators can
+    /// observe a new rule set's impact before it starts blocking anything.
+    rollout_audit_override: AtomicBool,
+}
+
+impl RuleSet {
+    /// Build an in-memory rule set with no source file (so [`Self::reload`]
+    /// and [`Self::watch`] are no-ops / errors).
+    pub fn new(rules: Vec<PolicyRule>) -> Self {
+        Self {
+            rules: RwLock::new(Arc::new(rules)),
+            source: None,
+            cache: Mutex::new(HashMap::new()),
+            cache_ttl: DEFAULT_CACHE_TTL,
+            watchers: Mutex::new(Vec::new()),
+            rollout_audit_override: AtomicBool::new(false),
+        }
+    }
+
+    /// Load rules from `path`, dispatching on its extension the same way
+    /// [`crate::config::ConstraintsDocument::load`] does: `.json` is parsed
+    /// as JSON, anything else as TOML.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RuleSetError> {
+        let path = path.as_ref();
+        let rules = read_rules(path)?;
+        Ok(Self {
+            rules: RwLock::new(Arc::new(rules)),
+            source: Some(path.to_path_buf()),
+            cache: Mutex::new(HashMap::new()),
+            cache_ttl: DEFAULT_CACHE_TTL,
+            watchers: Mutex::new(Vec::new()),
+            rollout_audit_override: AtomicBool::new(false),
+        })
+    }
+
+    /// Override the evaluation cache's TTL (default [`DEFAULT_CACHE_TTL`]).
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// A snapshot of the currently loaded rules, as configured -- does not
+    /// reflect an active [`Self::begin_rollout_window`] downgrade, which is
+    /// applied only inside [`Self::evaluate`].
+    pub fn rules(&self) -> Arc<Vec<PolicyRule>> {
+        self.rules.read().unwrap().clone()
+    }
+
+    /// Begin a rollout window: until [`Self::end_rollout_window`] is
+    /// called, [`Self::evaluate`] treats every `Error`/`Critical` rule as
+    /// though its `enforcement` were [`RuleMode::Audit`], regardless of its
+    /// own configured mode, so teams can watch a new or changed rule set's
+    /// impact before it starts blocking operations. Clears the cache so no
+    /// stale, pre-window blocking decision survives the switch.
+    pub fn begin_rollout_window(&self) {
+        self.rollout_audit_override.store(true, Ordering::SeqCst);
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// End a rollout window started by [`Self::begin_rollout_window`],
+    /// restoring every rule's own configured [`RuleMode`].
+    pub fn end_rollout_window(&self) {
+        self.rollout_audit_override.store(false, Ordering::SeqCst);
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Whether a rollout window started by [`Self::begin_rollout_window`]
+    /// is currently active.
+    pub fn in_rollout_window(&self) -> bool {
+        self.rollout_audit_override.load(Ordering::SeqCst)
+    }
+
+    /// Evaluate `context` against the current rules, consulting the cache
+    /// first. A cache hit skips re-running every [`PolicyRule`]'s
+    /// conditions entirely.
+    pub fn evaluate(&self, context: &RuleContext) -> PolicyReport {
+        let key = hash_context(context);
+
+        if let Some(cached) = self.cache.lock().unwrap().get(&key) {
+            if cached.computed_at.elapsed() < self.cache_ttl {
+                return cached.report.clone();
+            }
+        }
+
+        let rules = self.rules.read().unwrap().clone();
+        let report = if self.in_rollout_window() {
+            let downgraded: Vec<PolicyRule> = rules.iter().cloned().map(downgrade_to_audit).collect();
+            evaluate_all_detailed(&downgraded, context)
+        } else {
+            evaluate_all_detailed(rules.as_slice(), context)
+        };
+
+        self.cache.lock().unwrap().insert(
+            key,
+            CachedEvaluation {
+                report: report.clone(),
+                computed_at: Instant::now(),
+            },
+        );
+
+        report
+    }
+
+    /// Re-read and re-parse the source file, swapping the loaded rules
+    /// behind the `Arc` and clearing the evaluation cache. A no-op if this
+    /// set wasn't built via [`Self::load`].
+    pub fn reload(&self) -> Result<(), RuleSetError> {
+        let Some(source) = &self.source else {
+            return Ok(());
+        };
+
+        let rules = read_rules(source)?;
+        *self.rules.write().unwrap() = Arc::new(rules);
+        self.cache.lock().unwrap().clear();
+
+        for watcher in self.watchers.lock().unwrap().iter() {
+            watcher();
+        }
+
+        Ok(())
+    }
+
+    /// Register a callback invoked after every successful [`Self::reload`]
+    /// (including ones triggered by [`Self::watch`]), for callers that
+    /// need to invalidate their own downstream state when the rules change.
+    pub fn register_watcher(&self, callback: impl Fn() + Send + Sync + 'static) {
+        self.watchers.lock().unwrap().push(Box::new(callback));
+    }
+
+    /// Poll the source file for changes and [`Self::reload`] once the tree
+    /// settles, debouncing bursts the same way
+    /// [`crate::watch::watch_constraints`] does. Runs forever; requires
+    /// this set to have been built via [`Self::load`].
+    pub fn watch(&self) -> Result<(), RuleSetError> {
+        self.watch_until(|| false)
+    }
+
+    /// Like [`Self::watch`], but stops once `should_stop` returns `true`
+    /// (checked once per poll) -- the seam this module's tests use to
+    /// bound an otherwise infinite loop.
+    fn watch_until(&self, mut should_stop: impl FnMut() -> bool) -> Result<(), RuleSetError> {
+        let source = self.source.clone().ok_or(RuleSetError::NoSource)?;
+        let mut last = mtime(&source)?;
+
+        loop {
+            if should_stop() {
+                return Ok(());
+            }
+            std::thread::sleep(POLL_INTERVAL);
+
+            let current = mtime(&source)?;
+            if current == last {
+                continue;
+            }
+            last = debounce_until_quiet(&source, current)?;
+            self.reload()?;
+        }
+    }
+}
+
+/// Downgrade `rule` to [`RuleMode::Audit`] if it's `Error` or `Critical`
+/// severity, for the duration of a [`RuleSet::begin_rollout_window`].
+fn downgrade_to_audit(rule: PolicyRule) -> PolicyRule {
+    match rule.severity {
+        RuleSeverity::Error | RuleSeverity::Critical => rule.with_enforcement(RuleMode::Audit),
+        RuleSeverity::Info | RuleSeverity::Warning => rule,
+    }
+}
+
+fn read_rules(path: &Path) -> Result<Vec<PolicyRule>, RuleSetError> {
+    let src = std::fs::read_to_string(path)
+        .map_err(|e| RuleSetError::Io(path.display().to_string(), e.to_string()))?;
+    let is_json = path.extension().and_then(|e| e.to_str()) == Some("json");
+    if is_json {
+        serde_json::from_str(&src).map_err(|e| RuleSetError::Parse(path.display().to_string(), e.to_string()))
+    } else {
+        toml::from_str(&src).map_err(|e| RuleSetError::Parse(path.display().to_string(), e.to_string()))
+    }
+}
+
+fn mtime(path: &Path) -> Result<SystemTime, RuleSetError> {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map_err(|e| RuleSetError::Io(path.display().to_string(), e.to_string()))
+}
+
+fn debounce_until_quiet(path: &Path, mut last: SystemTime) -> Result<SystemTime, RuleSetError> {
+    let mut stable_since = Instant::now();
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let current = mtime(path)?;
+        if current != last {
+            last = current;
+            stable_since = Instant::now();
+        } else if stable_since.elapsed() >= DEFAULT_DEBOUNCE {
+            return Ok(last);
+        }
+    }
+}
+
+/// Hash the [`RuleContext`] fields any [`crate::rule::RuleCondition`] can
+/// actually observe, so two contexts that would evaluate identically share
+/// a cache entry. Attribute/role ordering is normalized first so two
+/// [`crate::rule::Actor`]s built with the same roles in a different order
+/// still hash the same.
+fn hash_context(context: &RuleContext) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+
+    let mut hasher = DefaultHasher::new();
+    context.operation_type.hash(&mut hasher);
+    context.risk_level.hash(&mut hasher);
+    context.file_count.hash(&mut hasher);
+    context.line_count.hash(&mut hasher);
+    context.is_destructive.hash(&mut hasher);
+    context.targets_production.hash(&mut hasher);
+    context.tests_passed.hash(&mut hasher);
+    context.lint_passed.hash(&mut hasher);
+    context.has_confirmation.hash(&mut hasher);
+    context.mode.hash(&mut hasher);
+    context.affects_critical_files.hash(&mut hasher);
+    context.affected_paths.hash(&mut hasher);
+
+    if let Some(actor) = &context.actor {
+        actor.id.hash(&mut hasher);
+        let mut roles = actor.roles.clone();
+        roles.sort();
+        roles.hash(&mut hasher);
+        let mut attributes: Vec<(&String, &String)> = actor.attributes.iter().collect();
+        attributes.sort();
+        attributes.hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::{RuleCondition, RuleSeverity};
+
+    fn fixture_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("tdln_policy_rule_set_{}_{}.json", name, std::process::id()))
+    }
+
+    fn rule_json(max_files: usize) -> String {
+        format!(
+            r#"[{{"id":"max_files","name":"Max Files","description":"","severity":"error","conditions":[{{"type":"file_count","max":{max_files}}}],"enabled":true,"tags":[],"required_roles":[],"forbidden_roles":[]}}]"#
+        )
+    }
+
+    #[test]
+    fn evaluate_consults_cache_on_repeat_contexts() {
+        let set = RuleSet::new(vec![PolicyRule::new("max_files", "Max Files")
+            .with_severity(RuleSeverity::Error)
+            .with_condition(RuleCondition::FileCount { max: 5 })]);
+
+        let ctx = RuleContext::new("bug_fix").with_files(10);
+        let first = set.evaluate(&ctx);
+        let second = set.evaluate(&ctx);
+
+        assert!(!first.passed);
+        assert_eq!(first.rules.len(), second.rules.len());
+        assert_eq!(first.passed, second.passed);
+    }
+
+    #[test]
+    fn expired_cache_entries_are_recomputed() {
+        let set = RuleSet::new(vec![PolicyRule::new("max_files", "Max Files")
+            .with_condition(RuleCondition::FileCount { max: 5 })])
+        .with_cache_ttl(Duration::from_millis(1));
+
+        let ctx = RuleContext::new("bug_fix").with_files(10);
+        set.evaluate(&ctx);
+        std::thread::sleep(Duration::from_millis(5));
+        let report = set.evaluate(&ctx);
+
+        assert!(!report.passed);
+    }
+
+    #[test]
+    fn reload_swaps_rules_and_clears_the_cache() {
+        let path = fixture_path("reload");
+        std::fs::write(&path, rule_json(5)).unwrap();
+
+        let set = RuleSet::load(&path).unwrap();
+        let loose_ctx = RuleContext::new("bug_fix").with_files(10);
+        assert!(!set.evaluate(&loose_ctx).passed);
+
+        std::fs::write(&path, rule_json(20)).unwrap();
+        set.reload().unwrap();
+        assert!(set.evaluate(&loose_ctx).passed);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn register_watcher_fires_on_reload() {
+        let path = fixture_path("watcher_hook");
+        std::fs::write(&path, rule_json(5)).unwrap();
+
+        let set = RuleSet::load(&path).unwrap();
+        let fired = Arc::new(Mutex::new(false));
+        let fired_in_callback = fired.clone();
+        set.register_watcher(move || *fired_in_callback.lock().unwrap() = true);
+
+        set.reload().unwrap();
+        assert!(*fired.lock().unwrap());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn watch_reloads_after_the_source_file_changes() {
+        let path = fixture_path("watch");
+        std::fs::write(&path, rule_json(5)).unwrap();
+
+        let set = RuleSet::load(&path).unwrap();
+        let ctx = RuleContext::new("bug_fix").with_files(10);
+        assert!(!set.evaluate(&ctx).passed);
+
+        let path_for_edit = path.clone();
+        let mut edited = false;
+        set.watch_until(move || {
+            if !edited {
+                std::thread::sleep(Duration::from_millis(50));
+                std::fs::write(&path_for_edit, rule_json(20)).unwrap();
+                edited = true;
+                false
+            } else {
+                true
+            }
+        })
+        .unwrap();
+
+        assert!(set.evaluate(&ctx).passed);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn watch_without_a_source_errors() {
+        let set = RuleSet::new(vec![]);
+        assert!(matches!(set.watch(), Err(RuleSetError::NoSource)));
+    }
+
+    #[test]
+    fn rollout_window_downgrades_error_rules_to_non_blocking() {
+        let set = RuleSet::new(vec![PolicyRule::new("max_files", "Max Files")
+            .with_severity(RuleSeverity::Error)
+            .with_condition(RuleCondition::FileCount { max: 5 })]);
+
+        let ctx = RuleContext::new("bug_fix").with_files(10);
+        assert!(set.evaluate(&ctx).blocked);
+
+        set.begin_rollout_window();
+        assert!(set.in_rollout_window());
+        let report = set.evaluate(&ctx);
+        assert!(!report.passed);
+        assert!(!report.blocked);
+
+        set.end_rollout_window();
+        assert!(!set.in_rollout_window());
+        assert!(set.evaluate(&ctx).blocked);
+    }
+}
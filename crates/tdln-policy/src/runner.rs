@@ -0,0 +1,394 @@
+//! Test and lint execution, producing a populated [`OperationMetrics`].
+//!
+//! Without this module, `OperationMetrics.tests_passed`/`lint_passed`/
+//! `time_ms` only ever hold whatever the caller already knew, so
+//! `must_pass_tests`/`must_pass_lint` constraints can only check values
+//! computed elsewhere. [`TestRunner`] closes that gap: it discovers target
+//! files under a root directory with the [`crate::constraints`] glob engine,
+//! runs a configured test (and/or lint) command against each one concurrently
+//! across a bounded worker pool, and folds the exit statuses into metrics
+//! ready for [`crate::constraints::validate_constraints`].
+//!
+//! Unit order is shuffled with a seeded PRNG before dispatch, mirroring
+//! [`tdln_stages::Pipeline::with_shuffle_seed`]: a fixed `seed` reproduces the
+//! same order every run, so ordering-dependent flakiness shows up
+//! deterministically instead of depending on directory-walk order.
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Instant;
+
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use walkdir::WalkDir;
+
+use crate::constraints::{matches_pattern, OperationMetrics};
+
+/// Configuration for a [`TestRunner`] execution.
+#[derive(Debug, Clone)]
+pub struct RunnerConfig {
+    /// Directory to discover target files under.
+    pub root: PathBuf,
+    /// Glob patterns (see [`crate::constraints`]'s glob engine) a file must
+    /// match to be included. Empty means "every file".
+    pub include: Vec<String>,
+    /// Glob patterns that exclude an otherwise-included file.
+    pub exclude: Vec<String>,
+    /// Command to run once per target file to check tests, e.g.
+    /// `["cargo", "test", "--"]` -- the file path is appended as the final
+    /// argument. `None` skips test execution entirely.
+    pub test_command: Option<Vec<String>>,
+    /// Command to run once per target file to check lint, analogous to
+    /// `test_command`.
+    pub lint_command: Option<Vec<String>>,
+    /// Maximum number of units run concurrently.
+    pub parallelism: usize,
+    /// Seed for the deterministic unit-order shuffle. `None` runs units in
+    /// discovery order.
+    pub seed: Option<u64>,
+}
+
+impl RunnerConfig {
+    /// Create a config with no commands configured and unbounded discovery
+    /// (every file under `root` is a candidate unit).
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            test_command: None,
+            lint_command: None,
+            parallelism: 4,
+            seed: None,
+        }
+    }
+
+    /// Restrict discovery to files matching at least one of `patterns`.
+    pub fn with_include(mut self, patterns: Vec<String>) -> Self {
+        self.include = patterns;
+        self
+    }
+
+    /// Drop files matching any of `patterns`, even if they matched `include`.
+    pub fn with_exclude(mut self, patterns: Vec<String>) -> Self {
+        self.exclude = patterns;
+        self
+    }
+
+    /// Set the test command. The target file is appended as the last arg.
+    pub fn with_test_command(mut self, command: Vec<String>) -> Self {
+        self.test_command = Some(command);
+        self
+    }
+
+    /// Set the lint command. The target file is appended as the last arg.
+    pub fn with_lint_command(mut self, command: Vec<String>) -> Self {
+        self.lint_command = Some(command);
+        self
+    }
+
+    /// Bound how many units run at once. Clamped to at least 1.
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Seed the deterministic unit-order shuffle.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+/// Outcome of running one command against one unit (a discovered file).
+#[derive(Debug, Clone)]
+pub struct UnitFailure {
+    /// The file the unit ran against.
+    pub unit: String,
+    /// Captured stdout+stderr (or the spawn error) from the failing run.
+    pub output: String,
+}
+
+/// Aggregate result of running a command across every discovered unit.
+#[derive(Debug, Clone, Default)]
+pub struct RunSummary {
+    /// Number of units run.
+    pub total: usize,
+    /// Number of units that exited successfully.
+    pub passed: usize,
+    /// Number of units that exited non-zero (or failed to spawn).
+    pub failed: usize,
+    /// The first failure encountered, in unit order -- the one
+    /// `generate_remediation` should point at.
+    pub first_failure: Option<UnitFailure>,
+}
+
+impl RunSummary {
+    fn record(&mut self, unit: &str, outcome: Result<(bool, String), String>) {
+        self.total += 1;
+        let (passed, output) = match outcome {
+            Ok((passed, output)) => (passed, output),
+            Err(spawn_error) => (false, spawn_error),
+        };
+
+        if passed {
+            self.passed += 1;
+        } else {
+            self.failed += 1;
+            if self.first_failure.is_none() {
+                self.first_failure = Some(UnitFailure {
+                    unit: unit.to_string(),
+                    output,
+                });
+            }
+        }
+    }
+
+    /// `true` if every unit passed (including the vacuous case of no units).
+    pub fn all_passed(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// Error discovering target files for a run.
+#[derive(Debug, thiserror::Error)]
+pub enum RunnerError {
+    #[error("failed to scan '{0}': {1}")]
+    Scan(String, String),
+}
+
+/// Executes configured test/lint commands over discovered files and builds
+/// a populated [`OperationMetrics`].
+pub struct TestRunner {
+    config: RunnerConfig,
+}
+
+impl TestRunner {
+    pub fn new(config: RunnerConfig) -> Self {
+        Self { config }
+    }
+
+    /// The root directory this runner discovers/runs units under.
+    pub fn root(&self) -> &std::path::Path {
+        &self.config.root
+    }
+
+    /// Walk [`RunnerConfig::root`] and return every file matching `include`
+    /// (or every file, if `include` is empty) that doesn't match `exclude`,
+    /// as paths relative to `root`. Public so callers that need the target
+    /// list without actually running commands (e.g. [`crate::watch`]'s
+    /// cheap mtime polling) can reuse the same discovery/glob logic.
+    pub fn discover_files(&self) -> Result<Vec<String>, RunnerError> {
+        self.discover()
+    }
+
+    fn discover(&self) -> Result<Vec<String>, RunnerError> {
+        let mut files = Vec::new();
+
+        for entry in WalkDir::new(&self.config.root) {
+            let entry = entry.map_err(|e| {
+                RunnerError::Scan(self.config.root.display().to_string(), e.to_string())
+            })?;
+
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let relative = entry
+                .path()
+                .strip_prefix(&self.config.root)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+
+            let included = self.config.include.is_empty()
+                || self.config.include.iter().any(|p| matches_pattern(&relative, p));
+            let excluded = self.config.exclude.iter().any(|p| matches_pattern(&relative, p));
+
+            if included && !excluded {
+                files.push(relative);
+            }
+        }
+
+        if let Some(seed) = self.config.seed {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            files.shuffle(&mut rng);
+        } else {
+            files.sort();
+        }
+
+        Ok(files)
+    }
+
+    /// Run `command` once per file in `units`, appending the file path as
+    /// the last argument, across a worker pool bounded by `parallelism`.
+    fn run_command(&self, command: &[String], units: &[String]) -> RunSummary {
+        let mut summary = RunSummary::default();
+        let (program, base_args) = match command.split_first() {
+            Some((program, rest)) => (program, rest),
+            None => return summary,
+        };
+
+        for chunk in units.chunks(self.config.parallelism.max(1)) {
+            let results: Vec<(String, Result<(bool, String), String>)> = std::thread::scope(|scope| {
+                let handles: Vec<_> = chunk
+                    .iter()
+                    .map(|unit| {
+                        scope.spawn(move || (unit.clone(), run_unit(program, base_args, unit, &self.config.root)))
+                    })
+                    .collect();
+                handles.into_iter().map(|h| h.join().expect("runner unit panicked")).collect()
+            });
+
+            for (unit, outcome) in results {
+                summary.record(&unit, outcome);
+            }
+        }
+
+        summary
+    }
+
+    /// Discover target files, run the configured test/lint commands, and
+    /// fold the results into an [`OperationMetrics`] ready for
+    /// [`crate::constraints::validate_constraints`].
+    pub fn run(&self) -> Result<OperationMetrics, RunnerError> {
+        let started = Instant::now();
+        let files = self.discover()?;
+
+        let test_results = self.config.test_command.as_ref().map(|cmd| self.run_command(cmd, &files));
+        let lint_results = self.config.lint_command.as_ref().map(|cmd| self.run_command(cmd, &files));
+
+        let mut metrics = OperationMetrics::new().with_files(files.len(), files);
+        metrics.time_ms = started.elapsed().as_millis() as u64;
+        if let Some(results) = &test_results {
+            metrics.tests_passed = Some(results.all_passed());
+        }
+        if let Some(results) = &lint_results {
+            metrics.lint_passed = Some(results.all_passed());
+        }
+        metrics.test_results = test_results;
+        metrics.lint_results = lint_results;
+
+        Ok(metrics)
+    }
+}
+
+/// Run `program base_args... unit` from `root` and classify the outcome.
+fn run_unit(
+    program: &str,
+    base_args: &[String],
+    unit: &str,
+    root: &PathBuf,
+) -> Result<(bool, String), String> {
+    let output = Command::new(program)
+        .args(base_args)
+        .arg(unit)
+        .current_dir(root)
+        .output()
+        .map_err(|e| format!("failed to run '{} {} {}': {}", program, base_args.join(" "), unit, e))?;
+
+    let mut combined = String::from_utf8_lossy(&output.stdout).into_owned();
+    combined.push_str(&String::from_utf8_lossy(&output.stderr));
+
+    Ok((output.status.success(), combined))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("tdln_policy_runner_{}_{}", name, std::process::id()))
+    }
+
+    fn write_fixture_files(root: &PathBuf, files: &[(&str, &str)]) {
+        fs::create_dir_all(root).unwrap();
+        for (relative, contents) in files {
+            let path = root.join(relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).unwrap();
+            }
+            fs::write(path, contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn discover_filters_by_include_and_exclude_globs() {
+        let root = fixture_dir("discover");
+        write_fixture_files(
+            &root,
+            &[
+                ("src/lib.rs", ""),
+                ("src/main.rs", ""),
+                ("src/generated.rs", ""),
+                ("README.md", ""),
+            ],
+        );
+
+        let runner = TestRunner::new(
+            RunnerConfig::new(&root)
+                .with_include(vec!["src/*.rs".to_string()])
+                .with_exclude(vec!["*generated*".to_string()]),
+        );
+
+        let files = runner.discover().unwrap();
+        assert_eq!(files, vec!["src/lib.rs".to_string(), "src/main.rs".to_string()]);
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn discover_with_seed_is_a_reproducible_shuffle() {
+        let root = fixture_dir("shuffle");
+        write_fixture_files(&root, &[("a.rs", ""), ("b.rs", ""), ("c.rs", ""), ("d.rs", "")]);
+
+        let first = TestRunner::new(RunnerConfig::new(&root).with_seed(7)).discover().unwrap();
+        let second = TestRunner::new(RunnerConfig::new(&root).with_seed(7)).discover().unwrap();
+        let mut sorted = first.clone();
+        sorted.sort();
+
+        assert_eq!(first, second);
+        assert_eq!(sorted, vec!["a.rs".to_string(), "b.rs".to_string(), "c.rs".to_string(), "d.rs".to_string()]);
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn run_reports_pass_and_first_failure_for_a_failing_unit() {
+        let root = fixture_dir("run");
+        write_fixture_files(&root, &[("pass.txt", "ok"), ("fail.txt", "bad")]);
+
+        let runner = TestRunner::new(
+            RunnerConfig::new(&root)
+                .with_include(vec!["*.txt".to_string()])
+                .with_test_command(vec!["sh".to_string(), "-c".to_string(), "grep -q ok \"$0\"".to_string()])
+                .with_seed(1),
+        );
+
+        let metrics = runner.run().unwrap();
+        assert_eq!(metrics.tests_passed, Some(false));
+
+        let results = metrics.test_results.expect("test_results should be populated");
+        assert_eq!(results.total, 2);
+        assert_eq!(results.passed, 1);
+        assert_eq!(results.failed, 1);
+        assert_eq!(results.first_failure.unwrap().unit, "fail.txt");
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn run_with_no_commands_leaves_pass_fail_unset() {
+        let root = fixture_dir("no_commands");
+        write_fixture_files(&root, &[("a.rs", "")]);
+
+        let metrics = TestRunner::new(RunnerConfig::new(&root)).run().unwrap();
+        assert_eq!(metrics.tests_passed, None);
+        assert_eq!(metrics.lint_passed, None);
+        assert_eq!(metrics.file_count, 1);
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -141,6 +141,22 @@ pub struct Violation {
     /// Additional context
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<serde_json::Value>,
+    /// Suggested corrective action, shown to operators alongside the verdict.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+    /// Machine-readable remediation hint (e.g. a command or patch id) for
+    /// tooling that wants to act on the violation without parsing `remediation`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+    /// Whether this violation should actually block the operation. `false`
+    /// for rules evaluated under [`crate::rule::RuleMode::Audit`] -- the
+    /// violation is still reported, just never enforced.
+    #[serde(default = "default_blocking")]
+    pub blocking: bool,
+}
+
+fn default_blocking() -> bool {
+    true
 }
 
 impl Violation {
@@ -157,26 +173,48 @@ impl Violation {
             severity: ViolationSeverity::Error,
             location: None,
             context: None,
+            remediation: None,
+            fix: None,
+            blocking: true,
         }
     }
-    
+
     /// Set the severity
     pub fn with_severity(mut self, severity: ViolationSeverity) -> Self {
         self.severity = severity;
         self
     }
-    
+
+    /// Set whether this violation should actually block (see
+    /// [`crate::rule::RuleMode::Audit`]).
+    pub fn with_blocking(mut self, blocking: bool) -> Self {
+        self.blocking = blocking;
+        self
+    }
+
     /// Set the location
     pub fn with_location(mut self, location: impl Into<String>) -> Self {
         self.location = Some(location.into());
         self
     }
-    
+
     /// Set additional context
     pub fn with_context(mut self, context: serde_json::Value) -> Self {
         self.context = Some(context);
         self
     }
+
+    /// Attach a suggested corrective action
+    pub fn with_remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+
+    /// Attach a machine-readable remediation hint
+    pub fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
 }
 
 /// Severity of a violation
@@ -0,0 +1,324 @@
+//! Watch mode for continuous constraint re-validation.
+//!
+//! A one-shot [`validate_constraints`] call only reflects the source tree at
+//! the instant it's invoked, so an agent iterating on a change has to
+//! re-invoke it by hand after every edit to see whether a violation cleared.
+//! [`watch_constraints`] polls the affected source tree instead, debouncing
+//! bursts of edits (format-on-save, a multi-file rewrite) into one
+//! re-validation, and hands every fresh [`Verdict`] to a callback --
+//! mirroring [`tdln_stages::Pipeline::watch`]'s poll-based design, but
+//! feeding [`crate::runner::TestRunner`] instead of a pipeline stage so the
+//! re-validation also re-runs tests/lint when the caller configured them.
+//!
+//! Each watched path is canonicalized to an absolute path before the loop
+//! starts, so the watch keeps resolving the right files even if the
+//! process's current directory changes mid-session. File-change relevance
+//! is decided with the same glob engine [`crate::constraints`] uses for
+//! `forbidden_patterns`/`allowed_paths`: build artifacts, VCS internals, and
+//! installed packages under a watched root never trigger a re-run, even
+//! though they live inside it.
+//!
+//! Because each cycle's test/lint run is synchronous, there's no separate
+//! "in-flight run" to cancel -- the debounce *is* the restart: a run never
+//! starts until the tree has been quiet for [`DEFAULT_DEBOUNCE`], so a burst
+//! of edits during what would have been a run instead delays it until
+//! things settle, and the eventual run reflects the final state rather than
+//! whatever was on disk when the burst began.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::constraints::{validate_constraints, Constraints, OperationMetrics};
+use crate::runner::{RunSummary, RunnerConfig, RunnerError, TestRunner};
+use crate::verdict::Verdict;
+
+/// How long the watched tree must stop changing before a cycle re-runs.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Poll interval between mtime checks.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Directories whose churn should never trigger a re-validation, even
+/// though they live under a watched root.
+const IGNORED_PATTERNS: &[&str] = &[
+    ".git/**",
+    "target/**",
+    "node_modules/**",
+    "**/.git/**",
+    "**/target/**",
+    "**/node_modules/**",
+];
+
+/// Error setting up or running a constraint watch loop.
+#[derive(Debug, thiserror::Error)]
+pub enum WatchError {
+    #[error("no paths given to watch")]
+    NoPaths,
+    #[error("failed to resolve watched path '{0}': {1}")]
+    Resolve(String, String),
+    #[error("failed to scan watched path '{0}': {1}")]
+    Scan(String, String),
+    #[error("test/lint run failed: {0}")]
+    Runner(#[from] RunnerError),
+}
+
+/// Watch `paths` (each a file or a directory) for changes and re-run
+/// [`validate_constraints`] -- fed by a [`TestRunner`] per watched root, so
+/// tests/lint re-run too -- every time the tracked tree settles after an
+/// edit, calling `on_verdict` with each fresh [`Verdict`].
+///
+/// Runs forever; pair with a process signal handler to stop it, or see this
+/// module's tests for a bounded variant used in-crate.
+pub fn watch_constraints(
+    constraints: &Constraints,
+    paths: &[PathBuf],
+    on_verdict: impl FnMut(Verdict),
+) -> Result<(), WatchError> {
+    watch_until(constraints, paths, on_verdict, || false)
+}
+
+/// Like [`watch_constraints`], but stops once `should_stop` returns `true`
+/// (checked once per cycle) -- the seam tests use to bound an otherwise
+/// infinite loop.
+fn watch_until(
+    constraints: &Constraints,
+    paths: &[PathBuf],
+    mut on_verdict: impl FnMut(Verdict),
+    mut should_stop: impl FnMut() -> bool,
+) -> Result<(), WatchError> {
+    if paths.is_empty() {
+        return Err(WatchError::NoPaths);
+    }
+
+    let runners = build_runners(paths)?;
+    let mut mtimes = snapshot_all(&runners)?;
+
+    on_verdict(validate_constraints(constraints, &run_all(&runners)?));
+
+    loop {
+        if should_stop() {
+            return Ok(());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+
+        let current = snapshot_all(&runners)?;
+        if current == mtimes {
+            continue;
+        }
+        mtimes = debounce_until_quiet(&runners, current)?;
+
+        on_verdict(validate_constraints(constraints, &run_all(&runners)?));
+    }
+}
+
+/// Build one [`TestRunner`] per watched path: directories are watched as
+/// their own root, files as their parent directory's root, both
+/// canonicalized so a later `cwd` change can't break resolution.
+fn build_runners(paths: &[PathBuf]) -> Result<Vec<TestRunner>, WatchError> {
+    paths
+        .iter()
+        .map(|path| {
+            let canonical = path
+                .canonicalize()
+                .map_err(|e| WatchError::Resolve(path.display().to_string(), e.to_string()))?;
+            let root = if canonical.is_dir() {
+                canonical
+            } else {
+                canonical.parent().map(Path::to_path_buf).unwrap_or(canonical)
+            };
+
+            Ok(TestRunner::new(RunnerConfig::new(root).with_exclude(
+                IGNORED_PATTERNS.iter().map(|p| p.to_string()).collect(),
+            )))
+        })
+        .collect()
+}
+
+/// One discovered file's mtime, tagged with which runner's root it came
+/// from (two runners could otherwise report the same relative path).
+type MtimeSnapshot = Vec<(usize, String, SystemTime)>;
+
+fn snapshot_all(runners: &[TestRunner]) -> Result<MtimeSnapshot, WatchError> {
+    let mut snapshot = Vec::new();
+    for (i, runner) in runners.iter().enumerate() {
+        for file in runner.discover_files()? {
+            let full = runner.root().join(&file);
+            let modified = fs::metadata(&full)
+                .and_then(|m| m.modified())
+                .map_err(|e| WatchError::Scan(full.display().to_string(), e.to_string()))?;
+            snapshot.push((i, file, modified));
+        }
+    }
+    Ok(snapshot)
+}
+
+/// Poll `runners`' discovered files until their mtimes stop changing for
+/// [`DEFAULT_DEBOUNCE`], returning the final settled snapshot.
+fn debounce_until_quiet(
+    runners: &[TestRunner],
+    mut last: MtimeSnapshot,
+) -> Result<MtimeSnapshot, WatchError> {
+    let mut stable_since = Instant::now();
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let next = snapshot_all(runners)?;
+        if next != last {
+            last = next;
+            stable_since = Instant::now();
+        } else if stable_since.elapsed() >= DEFAULT_DEBOUNCE {
+            return Ok(last);
+        }
+    }
+}
+
+/// Run every watched root's [`TestRunner`] and fold the results into one
+/// combined [`OperationMetrics`].
+fn run_all(runners: &[TestRunner]) -> Result<OperationMetrics, WatchError> {
+    let mut combined = OperationMetrics::new();
+    let mut affected_files = Vec::new();
+    let mut line_count = 0usize;
+
+    for runner in runners {
+        let part = runner.run()?;
+        for file in &part.affected_files {
+            // `TestRunner` is command-execution-focused and doesn't count
+            // lines; watch mode needs a real number to check `max_lines`
+            // against, so it reads the (already-discovered) files itself.
+            // Binary/unreadable files just don't contribute -- not fatal.
+            if let Ok(contents) = fs::read_to_string(runner.root().join(file)) {
+                line_count += contents.lines().count();
+            }
+        }
+        affected_files.extend(part.affected_files);
+        combined.time_ms = combined.time_ms.max(part.time_ms);
+        combined.tests_passed = combine_passed(combined.tests_passed, part.tests_passed);
+        combined.lint_passed = combine_passed(combined.lint_passed, part.lint_passed);
+        combined.test_results = merge_run_summary(combined.test_results, part.test_results);
+        combined.lint_results = merge_run_summary(combined.lint_results, part.lint_results);
+    }
+
+    combined.file_count = affected_files.len();
+    combined.affected_files = affected_files;
+    combined.line_count = line_count;
+    Ok(combined)
+}
+
+fn combine_passed(acc: Option<bool>, next: Option<bool>) -> Option<bool> {
+    match (acc, next) {
+        (None, x) | (x, None) => x,
+        (Some(a), Some(b)) => Some(a && b),
+    }
+}
+
+fn merge_run_summary(acc: Option<RunSummary>, next: Option<RunSummary>) -> Option<RunSummary> {
+    match (acc, next) {
+        (None, x) | (x, None) => x,
+        (Some(mut a), Some(b)) => {
+            a.total += b.total;
+            a.passed += b.passed;
+            a.failed += b.failed;
+            if a.first_failure.is_none() {
+                a.first_failure = b.first_failure;
+            }
+            Some(a)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("tdln_policy_watch_{}_{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn watch_runs_once_and_reflects_the_initial_tree() {
+        let root = fixture_dir("static");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("a.rs"), "one\ntwo\nthree\n").unwrap();
+
+        let mut verdicts = Vec::new();
+        watch_until(
+            &Constraints::mechanic_mode(),
+            std::slice::from_ref(&root),
+            |v| verdicts.push(v),
+            || true,
+        )
+        .unwrap();
+
+        assert_eq!(verdicts.len(), 1);
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn watch_reruns_after_a_tracked_file_changes() {
+        let root = fixture_dir("changing");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("a.rs"), "one\n".repeat(1)).unwrap();
+
+        let root_for_edit = root.clone();
+        let mut edited = false;
+        let mut verdicts = Vec::new();
+
+        watch_until(
+            &Constraints::mechanic_mode().merge(&Constraints { max_lines: Some(3), ..Default::default() }),
+            std::slice::from_ref(&root),
+            |v| verdicts.push(v),
+            move || {
+                if !edited {
+                    thread::sleep(Duration::from_millis(50));
+                    fs::write(root_for_edit.join("a.rs"), "one\ntwo\nthree\nfour\n").unwrap();
+                    edited = true;
+                    false
+                } else {
+                    true
+                }
+            },
+        )
+        .unwrap();
+
+        assert_eq!(verdicts.len(), 2);
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn watch_ignores_changes_under_ignored_directories() {
+        let root = fixture_dir("ignored");
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(root.join("a.rs"), "fine\n").unwrap();
+
+        let mut verdicts = Vec::new();
+        let root_for_edit = root.clone();
+        let mut ticks = 0;
+
+        watch_until(
+            &Constraints::mechanic_mode(),
+            std::slice::from_ref(&root),
+            |v| verdicts.push(v),
+            move || {
+                ticks += 1;
+                if ticks == 1 {
+                    // Churn inside `target/`, which should never be tracked.
+                    fs::write(root_for_edit.join("target").join("build.log"), "noise").unwrap();
+                }
+                ticks > 3
+            },
+        )
+        .unwrap();
+
+        // Only the initial run fires; ignored-directory churn never settles
+        // into a second one.
+        assert_eq!(verdicts.len(), 1);
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn watch_errors_on_an_empty_path_list() {
+        let result = watch_until(&Constraints::mechanic_mode(), &[], |_: Verdict| {}, || true);
+        assert!(matches!(result, Err(WatchError::NoPaths)));
+    }
+}
@@ -0,0 +1,104 @@
+//! Hardware-normalized performance baselines.
+//!
+//! The same agent run looks "slow" on a weak CI box and "fast" on a beefy
+//! dev machine, so raw `duration_ms`/`cpu_ms` figures aren't comparable
+//! across hosts. [`SystemBenchmark::probe`] measures the host once (e.g. at
+//! process startup) against fixed reference constants -- a CPU score from a
+//! timed fixed-iteration hashing loop, a memory score from a large buffer
+//! fill+copy, and a disk score from a timed sequential write+fsync of a
+//! fixed-size temp file -- so [`crate::metrics::PerformanceMetrics::normalized`]
+//! can scale a run's figures onto the reference machine's timescale.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+/// Time the three probes took on the reference machine this crate's scores
+/// are expressed against -- a modest single-core CI runner. Recalibrate
+/// these if the reference host changes; every existing `SystemBenchmark`
+/// stays comparable as long as the constants (and probe workloads) don't.
+const REFERENCE_CPU_MS: f64 = 40.0;
+const REFERENCE_MEMORY_MS: f64 = 25.0;
+const REFERENCE_DISK_MS: f64 = 15.0;
+
+const CPU_PROBE_ITERATIONS: u64 = 2_000_000;
+const MEMORY_PROBE_BYTES: usize = 64 * 1024 * 1024;
+const DISK_PROBE_BYTES: usize = 8 * 1024 * 1024;
+
+/// A one-time measurement of how this host's CPU, memory bandwidth, and
+/// disk compare to [`REFERENCE_CPU_MS`]/[`REFERENCE_MEMORY_MS`]/[`REFERENCE_DISK_MS`].
+/// Each ratio is `measured / reference`: `> 1.0` means this host is slower
+/// than the reference, `< 1.0` means faster.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SystemBenchmark {
+    pub cpu_ratio: f64,
+    pub memory_ratio: f64,
+    pub disk_ratio: f64,
+}
+
+impl SystemBenchmark {
+    /// Run all three probes against the live host.
+    pub fn probe() -> Self {
+        Self {
+            cpu_ratio: cpu_probe_ms() / REFERENCE_CPU_MS,
+            memory_ratio: memory_probe_ms() / REFERENCE_MEMORY_MS,
+            disk_ratio: disk_probe_ms() / REFERENCE_DISK_MS,
+        }
+    }
+}
+
+/// Hash a fixed number of integers with `DefaultHasher`, timed -- a CPU-bound
+/// workload with no I/O or allocation in its hot loop.
+fn cpu_probe_ms() -> f64 {
+    let start = Instant::now();
+    let mut hasher = DefaultHasher::new();
+    for i in 0..CPU_PROBE_ITERATIONS {
+        i.hash(&mut hasher);
+    }
+    std::hint::black_box(hasher.finish());
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// Fill and clone a fixed-size buffer, timed -- exercises memory bandwidth
+/// rather than CPU or I/O.
+fn memory_probe_ms() -> f64 {
+    let start = Instant::now();
+    let mut buf = vec![0u8; MEMORY_PROBE_BYTES];
+    buf.fill(0xAA);
+    let copy = buf.clone();
+    std::hint::black_box(copy);
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// Sequentially write and fsync a fixed-size temp file, timed. The file is
+/// removed again once timed, regardless of whether the write succeeded.
+fn disk_probe_ms() -> f64 {
+    let path = std::env::temp_dir().join(format!("tdln-quality-benchmark-{}.tmp", std::process::id()));
+
+    let start = Instant::now();
+    if let Ok(mut file) = std::fs::File::create(&path) {
+        let buf = vec![0u8; DISK_PROBE_BYTES];
+        let _ = file.write_all(&buf);
+        let _ = file.sync_all();
+    }
+    let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let _ = std::fs::remove_file(&path);
+    elapsed_ms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_reports_positive_ratios() {
+        let bench = SystemBenchmark::probe();
+        assert!(bench.cpu_ratio > 0.0);
+        assert!(bench.memory_ratio > 0.0);
+        assert!(bench.disk_ratio > 0.0);
+    }
+}
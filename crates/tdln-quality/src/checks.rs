@@ -3,7 +3,10 @@
 //! Provides additional quality validation beyond basic metrics.
 
 use serde::{Deserialize, Serialize};
-use crate::gate::{Check, CheckStatus};
+use crate::complexity::{analyze_rust_complexity, analyze_rust_doc_ratio};
+use crate::gate::{limit_exceeded_check, Check, CheckStatus, EvalLimits, EvalTracker, JobResult, LimitKind};
+use crate::guard::{FileReport, GuardParseError, GuardRuleSet};
+use crate::rules::{CompiledRules, RuleParseError};
 
 /// Code quality checker
 pub struct CodeQualityChecker {
@@ -15,6 +18,16 @@ pub struct CodeQualityChecker {
     pub max_complexity: Option<u32>,
     /// Minimum documentation ratio
     pub min_doc_ratio: Option<f32>,
+    /// Rules compiled from a policy-as-code DSL, evaluated alongside the
+    /// hardcoded pattern lists above. Empty unless built via [`Self::from_rules`].
+    pub rules: CompiledRules,
+    /// Stateful, function-based rules (see [`crate::guard`]), evaluated
+    /// against both the file content and the job's `JobResult`. Empty
+    /// unless built via [`Self::from_guard_rules`].
+    pub guard_rules: GuardRuleSet,
+    /// Resource limits [`Self::check_code`] enforces across its pattern and
+    /// rule scans -- see [`EvalLimits`].
+    pub limits: EvalLimits,
 }
 
 impl Default for CodeQualityChecker {
@@ -32,6 +45,9 @@ impl Default for CodeQualityChecker {
             required_patterns: Vec::new(),
             max_complexity: Some(20),
             min_doc_ratio: Some(0.1),
+            rules: CompiledRules::default(),
+            guard_rules: GuardRuleSet::default(),
+            limits: EvalLimits::default(),
         }
     }
 }
@@ -62,75 +78,197 @@ impl CodeQualityChecker {
             ],
             max_complexity: Some(15),
             min_doc_ratio: Some(0.15),
+            rules: CompiledRules::default(),
+            guard_rules: GuardRuleSet::default(),
+            limits: EvalLimits::default(),
         }
     }
-    
-    /// Check code content
+
+    /// Build a checker whose checks come entirely from a policy-as-code rule
+    /// DSL (see [`crate::rules`]) instead of the hardcoded pattern lists.
+    pub fn from_rules(src: &str) -> Result<Self, RuleParseError> {
+        Ok(Self {
+            forbidden_patterns: Vec::new(),
+            required_patterns: Vec::new(),
+            max_complexity: None,
+            min_doc_ratio: None,
+            rules: CompiledRules::from_toml(src)?,
+            guard_rules: GuardRuleSet::default(),
+            limits: EvalLimits::default(),
+        })
+    }
+
+    /// Build a checker whose checks come entirely from a stateful,
+    /// function-based guard rule document (see [`crate::guard`]) instead of
+    /// the hardcoded pattern lists.
+    pub fn from_guard_rules(src: &str) -> Result<Self, GuardParseError> {
+        Ok(Self {
+            forbidden_patterns: Vec::new(),
+            required_patterns: Vec::new(),
+            max_complexity: None,
+            min_doc_ratio: None,
+            rules: CompiledRules::default(),
+            guard_rules: GuardRuleSet::from_toml(src)?,
+            limits: EvalLimits::default(),
+        })
+    }
+
+    /// Bound the worst-case cost of [`Self::check_code`] -- use when
+    /// `rules`/`guard_rules` come from an untrusted rule document.
+    pub fn with_limits(mut self, limits: EvalLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Check code content against a `JobResult`, additionally running
+    /// [`Self::guard_rules`] (which can see the job's output, not just the
+    /// file content) and rolling both into one [`FileReport`] -- so the same
+    /// structured output can drive a `QualityGate` verdict (via
+    /// [`FileReport::checks`]) and machine-readable CI output (via
+    /// [`FileReport::clauses`]).
+    pub fn check_file(&self, content: &str, filename: &str, job: &JobResult) -> FileReport {
+        let mut report = self.guard_rules.evaluate(content, filename, job);
+        report.checks.splice(0..0, self.check_code(content, filename));
+        report
+    }
+
+    /// Check code content. Enforces `self.limits` across every pattern and
+    /// rule scanned below: on breach, scanning stops early and the last
+    /// check is a single `CheckStatus::LimitExceeded` naming which limit
+    /// tripped, rather than hanging on a pathological rule set.
     pub fn check_code(&self, content: &str, filename: &str) -> Vec<Check> {
         let mut checks = Vec::new();
-        
-        // Check forbidden patterns
-        for pattern in &self.forbidden_patterns {
-            if content.contains(&pattern.pattern) {
-                let status = match pattern.severity {
-                    PatternSeverity::Error => CheckStatus::Fail,
-                    PatternSeverity::Warning => CheckStatus::Warn,
-                    PatternSeverity::Info => CheckStatus::Ok,
+
+        if content.len() > self.limits.max_file_bytes {
+            checks.push(limit_exceeded_check(LimitKind::FileBytes));
+            return checks;
+        }
+
+        let mut tracker = EvalTracker::new(self.limits);
+
+        let tripped = 'scan: {
+            // Check forbidden patterns
+            for pattern in &self.forbidden_patterns {
+                if let Some(kind) = tracker.tick() {
+                    break 'scan Some(kind);
+                }
+                if content.contains(&pattern.pattern) {
+                    let status = match pattern.severity {
+                        PatternSeverity::Error => CheckStatus::Fail,
+                        PatternSeverity::Warning => CheckStatus::Warn,
+                        PatternSeverity::Info => CheckStatus::Ok,
+                    };
+
+                    if let Some(kind) = tracker.record_matches(1) {
+                        break 'scan Some(kind);
+                    }
+                    checks.push(Check {
+                        name: format!("forbidden_{}", pattern.pattern.to_lowercase().replace(' ', "_")),
+                        status,
+                        message: format!("Found '{}': {}", pattern.pattern, pattern.reason),
+                        impact: pattern.impact,
+                        remediation: None,
+                    });
+                }
+            }
+
+            // Check required patterns
+            for pattern in &self.required_patterns {
+                if let Some(kind) = tracker.tick() {
+                    break 'scan Some(kind);
+                }
+                let should_check = match &pattern.scope {
+                    PatternScope::All => true,
+                    PatternScope::TestFiles => filename.contains("test"),
+                    PatternScope::SourceFiles => !filename.contains("test"),
                 };
-                
-                checks.push(Check {
-                    name: format!("forbidden_{}", pattern.pattern.to_lowercase().replace(' ', "_")),
-                    status,
-                    message: format!("Found '{}': {}", pattern.pattern, pattern.reason),
-                    impact: pattern.impact,
-                });
+
+                if should_check && !content.contains(&pattern.pattern) {
+                    if let Some(kind) = tracker.record_matches(1) {
+                        break 'scan Some(kind);
+                    }
+                    checks.push(Check {
+                        name: format!("required_{}", pattern.pattern.to_lowercase().replace(' ', "_")),
+                        status: CheckStatus::Warn,
+                        message: format!("Missing '{}': {}", pattern.pattern, pattern.reason),
+                        impact: pattern.impact,
+                        remediation: None,
+                    });
+                }
             }
-        }
-        
-        // Check required patterns
-        for pattern in &self.required_patterns {
-            let should_check = match &pattern.scope {
-                PatternScope::All => true,
-                PatternScope::TestFiles => filename.contains("test"),
-                PatternScope::SourceFiles => !filename.contains("test"),
-            };
-            
-            if should_check && !content.contains(&pattern.pattern) {
-                checks.push(Check {
-                    name: format!("required_{}", pattern.pattern.to_lowercase().replace(' ', "_")),
-                    status: CheckStatus::Warn,
-                    message: format!("Missing '{}': {}", pattern.pattern, pattern.reason),
-                    impact: pattern.impact,
-                });
+
+            if let Some(kind) = tracker.tick() {
+                break 'scan Some(kind);
             }
-        }
-        
-        // Check complexity (simple heuristic: count control flow keywords)
-        if let Some(max) = self.max_complexity {
-            let complexity = estimate_complexity(content);
-            if complexity > max {
-                checks.push(Check {
-                    name: "complexity".to_string(),
-                    status: CheckStatus::Warn,
-                    message: format!("Estimated complexity {} exceeds maximum {}", complexity, max),
-                    impact: -10,
-                });
+
+            // Check complexity. For Rust files, this is a per-function cyclomatic
+            // complexity computed over the real AST; other files (or Rust source
+            // that fails to parse) fall back to the substring heuristic.
+            if let Some(max) = self.max_complexity {
+                if filename.ends_with(".rs") {
+                    match analyze_rust_complexity(content) {
+                        Some(functions) => {
+                            for f in functions.iter().filter(|f| f.complexity > max) {
+                                checks.push(Check {
+                                    name: format!("complexity_{}", f.name),
+                                    status: CheckStatus::Warn,
+                                    message: format!(
+                                        "Function '{}' (lines {}-{}) has complexity {} exceeding maximum {}",
+                                        f.name, f.line_start, f.line_end, f.complexity, max
+                                    ),
+                                    impact: -10,
+                                    remediation: None,
+                                });
+                            }
+                        }
+                        None => push_heuristic_complexity_check(&mut checks, content, max),
+                    }
+                } else {
+                    push_heuristic_complexity_check(&mut checks, content, max);
+                }
             }
-        }
-        
-        // Check documentation ratio
-        if let Some(min_ratio) = self.min_doc_ratio {
-            let ratio = estimate_doc_ratio(content);
-            if ratio < min_ratio {
-                checks.push(Check {
-                    name: "documentation".to_string(),
-                    status: CheckStatus::Warn,
-                    message: format!("Documentation ratio {:.1}% below minimum {:.1}%", ratio * 100.0, min_ratio * 100.0),
-                    impact: -5,
-                });
+
+            if let Some(kind) = tracker.tick() {
+                break 'scan Some(kind);
             }
+
+            // Check documentation ratio, preferring an AST-based count of
+            // documented vs. total public items for Rust files.
+            if let Some(min_ratio) = self.min_doc_ratio {
+                let ratio = if filename.ends_with(".rs") {
+                    analyze_rust_doc_ratio(content).unwrap_or_else(|| estimate_doc_ratio(content))
+                } else {
+                    estimate_doc_ratio(content)
+                };
+                if ratio < min_ratio {
+                    checks.push(Check {
+                        name: "documentation".to_string(),
+                        status: CheckStatus::Warn,
+                        message: format!("Documentation ratio {:.1}% below minimum {:.1}%", ratio * 100.0, min_ratio * 100.0),
+                        impact: -5,
+                        remediation: None,
+                    });
+                }
+            }
+
+            if let Some(kind) = tracker.tick() {
+                break 'scan Some(kind);
+            }
+
+            // Check DSL rules (policy-as-code, see `CodeQualityChecker::from_rules`)
+            let dsl_checks = self.rules.check(content, filename);
+            if let Some(kind) = tracker.record_matches(dsl_checks.len()) {
+                break 'scan Some(kind);
+            }
+            checks.extend(dsl_checks);
+
+            None
+        };
+
+        if let Some(kind) = tripped {
+            checks.push(limit_exceeded_check(kind));
         }
-        
+
         checks
     }
 }
@@ -216,6 +354,21 @@ pub enum PatternScope {
     SourceFiles,
 }
 
+/// Fallback complexity check for non-Rust files (or unparseable Rust), using
+/// the file-wide substring heuristic instead of a per-function AST count.
+fn push_heuristic_complexity_check(checks: &mut Vec<Check>, content: &str, max: u32) {
+    let complexity = estimate_complexity(content);
+    if complexity > max {
+        checks.push(Check {
+            name: "complexity".to_string(),
+            status: CheckStatus::Warn,
+            message: format!("Estimated complexity {} exceeds maximum {}", complexity, max),
+            impact: -10,
+            remediation: None,
+        });
+    }
+}
+
 /// Estimate code complexity (simple heuristic)
 fn estimate_complexity(content: &str) -> u32 {
     let keywords = [
@@ -291,6 +444,7 @@ impl OutputQualityChecker {
                 status: CheckStatus::Fail,
                 message: format!("Output too short: {} chars (min: {})", output.len(), self.min_length),
                 impact: -20,
+                remediation: None,
             });
         }
         
@@ -301,6 +455,7 @@ impl OutputQualityChecker {
                     status: CheckStatus::Warn,
                     message: format!("Output too long: {} chars (max: {})", output.len(), max),
                     impact: -5,
+                    remediation: None,
                 });
             }
         }
@@ -313,6 +468,7 @@ impl OutputQualityChecker {
                     status: CheckStatus::Warn,
                     message: format!("Missing required section: {}", section),
                     impact: -10,
+                    remediation: None,
                 });
             }
         }
@@ -325,6 +481,7 @@ impl OutputQualityChecker {
                     status: CheckStatus::Warn,
                     message: format!("Contains placeholder/forbidden text: {}", forbidden),
                     impact: -5,
+                    remediation: None,
                 });
             }
         }
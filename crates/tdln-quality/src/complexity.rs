@@ -0,0 +1,208 @@
+//! AST-based complexity and documentation metrics for Rust source.
+//!
+//! Supersedes the substring-counting `estimate_complexity`/`estimate_doc_ratio`
+//! heuristics in [`crate::checks`] for `.rs` files: functions are located and
+//! walked with `syn`, so only real decision points in the AST count toward
+//! complexity, and it's reported *per function with span* rather than as one
+//! file-wide number. Non-Rust files keep using the old heuristics.
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+
+/// Cyclomatic complexity for a single function, with its source span (1-based lines).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionComplexity {
+    pub name: String,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub complexity: u32,
+}
+
+/// Parse `source` as a Rust file and compute per-function cyclomatic complexity.
+///
+/// Returns `None` if `source` does not parse as a valid Rust file, so callers
+/// can fall back to the substring heuristic.
+pub fn analyze_rust_complexity(source: &str) -> Option<Vec<FunctionComplexity>> {
+    let file = syn::parse_file(source).ok()?;
+    let mut visitor = FnVisitor::default();
+    visitor.visit_file(&file);
+    Some(visitor.functions)
+}
+
+/// Ratio of public items (`fn`, `struct`, `enum`, `trait`) that carry a doc
+/// comment, over the total number of public items. `None` if `source` fails to parse.
+pub fn analyze_rust_doc_ratio(source: &str) -> Option<f32> {
+    let file = syn::parse_file(source).ok()?;
+    let mut total = 0u32;
+    let mut documented = 0u32;
+
+    for item in &file.items {
+        if let Some((vis, attrs)) = public_item_parts(item) {
+            if matches!(vis, syn::Visibility::Public(_)) {
+                total += 1;
+                if has_doc(attrs) {
+                    documented += 1;
+                }
+            }
+        }
+    }
+
+    Some(if total == 0 { 1.0 } else { documented as f32 / total as f32 })
+}
+
+#[derive(Default)]
+struct FnVisitor {
+    functions: Vec<FunctionComplexity>,
+}
+
+impl FnVisitor {
+    fn record(&mut self, name: String, span: proc_macro2::Span, block: &syn::Block) {
+        let mut counter = ComplexityCounter::default();
+        counter.visit_block(block);
+        let start = span.start();
+        let end = span.end();
+        self.functions.push(FunctionComplexity {
+            name,
+            line_start: start.line,
+            line_end: end.line,
+            complexity: 1 + counter.decision_points,
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for FnVisitor {
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        self.record(node.sig.ident.to_string(), node.span(), &node.block);
+        visit::visit_item_fn(self, node);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.record(node.sig.ident.to_string(), node.span(), &node.block);
+        visit::visit_impl_item_fn(self, node);
+    }
+}
+
+/// Walks one function body, counting decision points:
+/// `if`/`while`/`for`/`loop`/`?`, match arms beyond the first, and `&&`/`||`.
+#[derive(Default)]
+struct ComplexityCounter {
+    decision_points: u32,
+}
+
+impl<'ast> Visit<'ast> for ComplexityCounter {
+    fn visit_expr_if(&mut self, node: &'ast syn::ExprIf) {
+        self.decision_points += 1;
+        visit::visit_expr_if(self, node);
+    }
+
+    fn visit_expr_while(&mut self, node: &'ast syn::ExprWhile) {
+        self.decision_points += 1;
+        visit::visit_expr_while(self, node);
+    }
+
+    fn visit_expr_for_loop(&mut self, node: &'ast syn::ExprForLoop) {
+        self.decision_points += 1;
+        visit::visit_expr_for_loop(self, node);
+    }
+
+    fn visit_expr_loop(&mut self, node: &'ast syn::ExprLoop) {
+        self.decision_points += 1;
+        visit::visit_expr_loop(self, node);
+    }
+
+    fn visit_expr_match(&mut self, node: &'ast syn::ExprMatch) {
+        self.decision_points += node.arms.len().saturating_sub(1) as u32;
+        visit::visit_expr_match(self, node);
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast syn::ExprBinary) {
+        if matches!(node.op, syn::BinOp::And(_) | syn::BinOp::Or(_)) {
+            self.decision_points += 1;
+        }
+        visit::visit_expr_binary(self, node);
+    }
+
+    fn visit_expr_try(&mut self, node: &'ast syn::ExprTry) {
+        self.decision_points += 1;
+        visit::visit_expr_try(self, node);
+    }
+
+    // Nested `fn` items get their own complexity entry via `FnVisitor`;
+    // don't fold their decision points into the enclosing function.
+    fn visit_item_fn(&mut self, _node: &'ast syn::ItemFn) {}
+}
+
+fn public_item_parts(item: &syn::Item) -> Option<(&syn::Visibility, &[syn::Attribute])> {
+    match item {
+        syn::Item::Fn(f) => Some((&f.vis, &f.attrs)),
+        syn::Item::Struct(s) => Some((&s.vis, &s.attrs)),
+        syn::Item::Enum(e) => Some((&e.vis, &e.attrs)),
+        syn::Item::Trait(t) => Some((&t.vis, &t.attrs)),
+        _ => None,
+    }
+}
+
+fn has_doc(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|a| a.path().is_ident("doc"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_decision_points() {
+        let src = r#"
+            fn risky(x: i32, y: i32) -> i32 {
+                if x > 0 && y > 0 {
+                    for i in 0..x {
+                        if i == y { return i; }
+                    }
+                }
+                match x {
+                    0 => 0,
+                    1 => 1,
+                    _ => -1,
+                }
+            }
+        "#;
+        let fns = analyze_rust_complexity(src).unwrap();
+        assert_eq!(fns.len(), 1);
+        assert_eq!(fns[0].name, "risky");
+        // base 1 + if(1) + &&(1) + for(1) + if(1) + match arms beyond first(2) = 7
+        assert_eq!(fns[0].complexity, 7);
+    }
+
+    #[test]
+    fn nested_fn_counted_separately() {
+        let src = r#"
+            fn outer() {
+                fn inner() {
+                    if true {}
+                }
+            }
+        "#;
+        let fns = analyze_rust_complexity(src).unwrap();
+        assert_eq!(fns.len(), 2);
+        let outer = fns.iter().find(|f| f.name == "outer").unwrap();
+        assert_eq!(outer.complexity, 1);
+        let inner = fns.iter().find(|f| f.name == "inner").unwrap();
+        assert_eq!(inner.complexity, 2);
+    }
+
+    #[test]
+    fn doc_ratio_counts_public_items() {
+        let src = r#"
+            /// documented
+            pub fn a() {}
+            pub fn b() {}
+            fn private_c() {}
+        "#;
+        let ratio = analyze_rust_doc_ratio(src).unwrap();
+        assert_eq!(ratio, 0.5);
+    }
+
+    #[test]
+    fn invalid_rust_returns_none() {
+        assert!(analyze_rust_complexity("not valid rust {{{").is_none());
+    }
+}
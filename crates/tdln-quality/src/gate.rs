@@ -11,19 +11,22 @@ use serde::{Deserialize, Serialize};
 pub struct JobResult {
     /// Test results
     pub tests: Option<TestResults>,
-    
+
     /// Lint results
     pub lint: Option<LintResults>,
-    
+
+    /// Formatter check results
+    pub fmt: Option<FmtResults>,
+
     /// Code changes
     pub changes: Option<ChangeStats>,
-    
+
     /// Budget usage
     pub budget: Option<BudgetUsage>,
-    
+
     /// Output text (for output quality checks)
     pub output: Option<String>,
-    
+
     /// Citations provided
     pub citations: Vec<String>,
 }
@@ -42,6 +45,16 @@ pub struct LintResults {
     pub warnings: u32,
 }
 
+/// Output of a formatter check-mode run, e.g. `cargo fmt --all -- --check`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FmtResults {
+    pub files_unformatted: u32,
+    /// The formatter's suggested diff, when captured -- surfaced verbatim
+    /// in the `fmt_clean` check message since it's the actionable output.
+    #[serde(default)]
+    pub diff: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChangeStats {
     pub files_changed: u32,
@@ -63,6 +76,41 @@ pub struct Check {
     pub status: CheckStatus,
     pub message: String,
     pub impact: i32, // Score impact (negative)
+    /// A machine-actionable hint for clearing this check, when we know one
+    /// -- see [`Remediation`]. `None` for checks that passed, or that don't
+    /// have an obvious automatic fix.
+    #[serde(default)]
+    pub remediation: Option<Remediation>,
+}
+
+/// A concrete, machine-actionable fix suggestion attached to a failing or
+/// warning [`Check`] -- mirrors the `Fixer` edit an rslint `Rule` can attach
+/// to its diagnostic. Lets an agent consuming a [`QualityVerdict`] attempt a
+/// fix and retry instead of re-parsing `Check::message`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Remediation {
+    pub kind: RemediationKind,
+    pub message: String,
+    /// A shell command that would address the failure, e.g. `cargo fmt`.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// A `QualityProfile` field name and the value that would clear this
+    /// check by raising the limit, e.g. `("max_files", "10")`.
+    #[serde(default)]
+    pub profile_override: Option<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemediationKind {
+    /// Run a shell command (formatter, linter --fix, etc).
+    RunCommand,
+    /// Raise a `QualityProfile` limit to accommodate the result.
+    RaiseLimit,
+    /// Split the change into smaller, separately-gated pieces.
+    SplitChange,
+    /// Attach a citation/source for an unsupported claim.
+    AddCitation,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -70,6 +118,122 @@ pub enum CheckStatus {
     Ok,
     Warn,
     Fail,
+    /// An [`EvalLimits`] budget was exceeded before evaluation could finish
+    /// running every check/rule. Always treated as blocking, since the
+    /// checks that did run are an incomplete picture of the result.
+    LimitExceeded,
+}
+
+/// Resource limits for one quality evaluation pass -- borrows biscuit's
+/// executor `Limits` idea so a pathological rule set (huge files,
+/// catastrophic-backtracking regex, thousands of rules) degrades into a
+/// reported [`CheckStatus::LimitExceeded`] check instead of hanging.
+/// [`QualityGate::evaluate`] and [`crate::checks::CodeQualityChecker::check_code`]
+/// each track usage against their own `EvalLimits` across every check/rule
+/// they run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvalLimits {
+    /// Maximum number of checks/rules evaluated in one pass.
+    pub max_iterations: usize,
+    /// Maximum cumulative pattern/check matches counted in one pass.
+    pub max_matches: usize,
+    /// Files larger than this are rejected before any rule runs.
+    pub max_file_bytes: usize,
+    /// Wall-clock budget for one pass.
+    pub timeout: std::time::Duration,
+}
+
+impl Default for EvalLimits {
+    /// Generous enough that every built-in check and existing test still
+    /// passes; only a deliberately pathological rule set should trip these.
+    fn default() -> Self {
+        Self {
+            max_iterations: 10_000,
+            max_matches: 10_000,
+            max_file_bytes: 16 * 1024 * 1024,
+            timeout: std::time::Duration::from_secs(5),
+        }
+    }
+}
+
+/// Which `EvalLimits` field a breach tripped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    Iterations,
+    Matches,
+    FileBytes,
+    Timeout,
+}
+
+impl std::fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            LimitKind::Iterations => "maximum iteration count",
+            LimitKind::Matches => "maximum match count",
+            LimitKind::FileBytes => "maximum file size",
+            LimitKind::Timeout => "evaluation timeout",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Evaluation couldn't run its rule set to completion under its `EvalLimits`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("quality evaluation exceeded its {0}")]
+pub struct QualityError(pub LimitKind);
+
+/// Tracks cumulative iteration count, match count, and elapsed time against
+/// an [`EvalLimits`] budget across every check/rule in one evaluation pass.
+#[derive(Debug)]
+pub struct EvalTracker {
+    limits: EvalLimits,
+    start: std::time::Instant,
+    iterations: usize,
+    matches: usize,
+}
+
+impl EvalTracker {
+    pub fn new(limits: EvalLimits) -> Self {
+        Self {
+            limits,
+            start: std::time::Instant::now(),
+            iterations: 0,
+            matches: 0,
+        }
+    }
+
+    /// Record one check/rule having run. Returns the tripped limit, if any.
+    pub fn tick(&mut self) -> Option<LimitKind> {
+        self.iterations += 1;
+        if self.iterations > self.limits.max_iterations {
+            return Some(LimitKind::Iterations);
+        }
+        if self.start.elapsed() > self.limits.timeout {
+            return Some(LimitKind::Timeout);
+        }
+        None
+    }
+
+    /// Record `n` additional matches found. Returns the tripped limit, if any.
+    pub fn record_matches(&mut self, n: usize) -> Option<LimitKind> {
+        self.matches += n;
+        if self.matches > self.limits.max_matches {
+            return Some(LimitKind::Matches);
+        }
+        None
+    }
+}
+
+/// Build the `Check` an `EvalLimits` breach produces: always `LimitExceeded`,
+/// weighted heavily enough to force a `BLOCK` verdict on its own.
+pub fn limit_exceeded_check(kind: LimitKind) -> Check {
+    Check {
+        name: "eval_limits".to_string(),
+        status: CheckStatus::LimitExceeded,
+        message: format!("Evaluation aborted: exceeded {kind}"),
+        impact: -100,
+        remediation: None,
+    }
 }
 
 /// Overall quality verdict
@@ -77,313 +241,745 @@ pub enum CheckStatus {
 pub struct QualityVerdict {
     /// Overall verdict
     pub verdict: String, // "OK" | "WARN" | "BLOCK"
-    
+
     /// Numeric score (0-100)
     pub score: u32,
-    
+
     /// Individual check results
     pub checks: Vec<Check>,
-    
+
     /// Profile used for evaluation
     pub profile: String,
-    
+
     /// Summary message
     pub summary: String,
+
+    /// Nagios-style performance data points -- see [`Self::to_nagios`].
+    pub perfdata: Vec<PerfDatum>,
 }
 
-/// Quality gate that evaluates job results
-pub struct QualityGate {
-    profile: QualityProfile,
+/// One Nagios plugin perfdata point: `label=value;warn;crit`. `warn`/`crit`
+/// are empty strings when the profile has no corresponding limit, which is
+/// valid Nagios perfdata syntax.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerfDatum {
+    pub label: String,
+    pub value: String,
+    pub warn: String,
+    pub crit: String,
 }
 
-impl QualityGate {
-    /// Create a new quality gate with the given profile
-    pub fn new(profile: QualityProfile) -> Self {
-        Self { profile }
+impl std::fmt::Display for PerfDatum {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}={};{};{}",
+            self.label, self.value, self.warn, self.crit
+        )
     }
+}
 
-    /// Create a quality gate for a specific mode
-    pub fn for_mode(mode: &str) -> Self {
-        Self::new(QualityProfile::for_mode(mode))
+impl QualityVerdict {
+    /// Render as a Nagios plugin status line (status code, status text) so
+    /// CI monitors and dashboards that speak the Nagios plugin protocol can
+    /// consume the gate directly instead of only JSON. The exit code
+    /// follows the standard convention: `0` OK, `1` WARN, `2` BLOCK, `3`
+    /// UNKNOWN for any verdict string this crate didn't itself produce.
+    pub fn to_nagios(&self) -> (i32, String) {
+        let code = match self.verdict.as_str() {
+            "OK" => 0,
+            "WARN" => 1,
+            "BLOCK" => 2,
+            _ => 3,
+        };
+        let status = match code {
+            0 => "OK",
+            1 => "WARN",
+            2 => "BLOCK",
+            _ => "UNKNOWN",
+        };
+
+        let perf = self
+            .perfdata
+            .iter()
+            .map(PerfDatum::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let line = format!("QUALITY {} - {} | {}", status, self.summary, perf);
+
+        (code, line)
     }
+}
 
-    /// Evaluate a job result against the quality profile
-    pub fn evaluate(&self, result: &JobResult) -> QualityVerdict {
-        let mut checks = Vec::new();
-        let mut score = 100i32;
-
-        // === Test Checks ===
-        if self.profile.require_tests {
-            if let Some(tests) = &result.tests {
-                if tests.failed > self.profile.max_test_failures {
-                    checks.push(Check {
-                        name: "tests_pass".to_string(),
-                        status: CheckStatus::Fail,
-                        message: format!(
-                            "{} tests failed (max allowed: {})",
-                            tests.failed, self.profile.max_test_failures
-                        ),
-                        impact: -30,
-                    });
-                    score -= 30;
-                } else {
-                    checks.push(Check {
-                        name: "tests_pass".to_string(),
-                        status: CheckStatus::Ok,
-                        message: format!("{} passed, {} failed", tests.passed, tests.failed),
-                        impact: 0,
-                    });
-                }
+/// One quality dimension `QualityGate` evaluates -- the same shape as an
+/// rslint `Rule`: given the job's result and the active profile, inspect
+/// whatever it cares about and optionally emit a [`Check`]. `None` means
+/// the dimension doesn't apply (e.g. there's no budget to check against).
+/// Every built-in check below (tests, lint, changes, budget, output,
+/// citations) implements this trait; [`QualityGate::register`] lets a
+/// consumer add project-specific checks (e.g. "no TODO left in diff")
+/// without forking the crate.
+pub trait QualityCheck: Send + Sync {
+    /// Identifies this check for logging/debugging -- independent of the
+    /// `Check::name` it emits, which is what shows up in the verdict.
+    fn name(&self) -> &str;
 
-                // Coverage check
-                if let Some(coverage) = tests.coverage {
-                    if coverage < self.profile.min_coverage {
-                        checks.push(Check {
-                            name: "test_coverage".to_string(),
-                            status: CheckStatus::Warn,
-                            message: format!(
-                                "Coverage {:.1}% below minimum {:.1}%",
-                                coverage * 100.0,
-                                self.profile.min_coverage * 100.0
-                            ),
-                            impact: -10,
-                        });
-                        score -= 10;
-                    } else {
-                        checks.push(Check {
-                            name: "test_coverage".to_string(),
-                            status: CheckStatus::Ok,
-                            message: format!("Coverage: {:.1}%", coverage * 100.0),
-                            impact: 0,
-                        });
-                    }
-                }
-            } else {
-                checks.push(Check {
-                    name: "tests_pass".to_string(),
-                    status: CheckStatus::Warn,
-                    message: "No test results provided".to_string(),
-                    impact: -15,
-                });
-                score -= 15;
-            }
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check>;
+}
+
+struct TestsPassCheck;
+
+impl QualityCheck for TestsPassCheck {
+    fn name(&self) -> &str {
+        "tests_pass"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        if !profile.require_tests {
+            return None;
         }
 
-        // === Lint Checks ===
-        if self.profile.require_lint {
-            if let Some(lint) = &result.lint {
-                if lint.errors > self.profile.max_lint_errors {
-                    checks.push(Check {
-                        name: "lint_clean".to_string(),
-                        status: CheckStatus::Fail,
-                        message: format!(
-                            "{} lint errors (max allowed: {})",
-                            lint.errors, self.profile.max_lint_errors
-                        ),
-                        impact: -20,
-                    });
-                    score -= 20;
-                } else if lint.warnings > self.profile.max_lint_warnings {
-                    checks.push(Check {
-                        name: "lint_clean".to_string(),
-                        status: CheckStatus::Warn,
-                        message: format!(
-                            "{} lint warnings (max allowed: {})",
-                            lint.warnings, self.profile.max_lint_warnings
-                        ),
-                        impact: -5,
-                    });
-                    score -= 5;
-                } else {
-                    checks.push(Check {
-                        name: "lint_clean".to_string(),
-                        status: CheckStatus::Ok,
-                        message: format!("{} errors, {} warnings", lint.errors, lint.warnings),
-                        impact: 0,
-                    });
-                }
-            }
+        match &result.tests {
+            Some(tests) if tests.failed > profile.max_test_failures => Some(Check {
+                name: "tests_pass".to_string(),
+                status: CheckStatus::Fail,
+                message: format!(
+                    "{} tests failed (max allowed: {})",
+                    tests.failed, profile.max_test_failures
+                ),
+                impact: profile.weight("tests_pass"),
+                remediation: None,
+            }),
+            Some(tests) => Some(Check {
+                name: "tests_pass".to_string(),
+                status: CheckStatus::Ok,
+                message: format!("{} passed, {} failed", tests.passed, tests.failed),
+                impact: 0,
+                remediation: None,
+            }),
+            None => Some(Check {
+                name: "tests_pass".to_string(),
+                status: CheckStatus::Warn,
+                message: "No test results provided".to_string(),
+                impact: -15,
+                remediation: None,
+            }),
         }
+    }
+}
 
-        // === Change Limit Checks ===
-        if let Some(changes) = &result.changes {
-            // File limit
-            if let Some(max_files) = self.profile.max_files {
-                if changes.files_changed > max_files {
-                    checks.push(Check {
-                        name: "file_limit".to_string(),
-                        status: CheckStatus::Fail,
-                        message: format!(
-                            "{} files changed (max allowed: {})",
-                            changes.files_changed, max_files
-                        ),
-                        impact: -25,
-                    });
-                    score -= 25;
-                } else {
-                    checks.push(Check {
-                        name: "file_limit".to_string(),
-                        status: CheckStatus::Ok,
-                        message: format!("{}/{} files", changes.files_changed, max_files),
-                        impact: 0,
-                    });
-                }
-            }
+struct TestCoverageCheck;
 
-            // Line limit
-            if let Some(max_lines) = self.profile.max_lines {
-                let total_lines = changes.lines_added + changes.lines_removed;
-                if total_lines > max_lines {
-                    checks.push(Check {
-                        name: "line_limit".to_string(),
-                        status: CheckStatus::Fail,
-                        message: format!(
-                            "{} lines changed (max allowed: {})",
-                            total_lines, max_lines
-                        ),
-                        impact: -25,
-                    });
-                    score -= 25;
-                } else {
-                    checks.push(Check {
-                        name: "line_limit".to_string(),
-                        status: CheckStatus::Ok,
-                        message: format!("{}/{} lines", total_lines, max_lines),
-                        impact: 0,
-                    });
-                }
-            }
+impl QualityCheck for TestCoverageCheck {
+    fn name(&self) -> &str {
+        "test_coverage"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        if !profile.require_tests {
+            return None;
         }
+        let coverage = result.tests.as_ref()?.coverage?;
 
-        // === Budget Checks ===
-        if let Some(budget) = &result.budget {
-            // Step limit
-            if budget.steps_used > self.profile.max_steps {
-                checks.push(Check {
-                    name: "step_budget".to_string(),
-                    status: CheckStatus::Warn,
-                    message: format!(
-                        "{} steps used (limit: {})",
-                        budget.steps_used, self.profile.max_steps
-                    ),
-                    impact: -10,
-                });
-                score -= 10;
-            }
+        if coverage < profile.min_coverage {
+            Some(Check {
+                name: "test_coverage".to_string(),
+                status: CheckStatus::Warn,
+                message: format!(
+                    "Coverage {:.1}% below minimum {:.1}%",
+                    coverage * 100.0,
+                    profile.min_coverage * 100.0
+                ),
+                impact: profile.weight("test_coverage"),
+                remediation: None,
+            })
+        } else {
+            Some(Check {
+                name: "test_coverage".to_string(),
+                status: CheckStatus::Ok,
+                message: format!("Coverage: {:.1}%", coverage * 100.0),
+                impact: 0,
+                remediation: None,
+            })
+        }
+    }
+}
 
-            // Token limit
-            if budget.tokens_used > self.profile.max_tokens {
-                checks.push(Check {
-                    name: "token_budget".to_string(),
-                    status: CheckStatus::Warn,
-                    message: format!(
-                        "{} tokens used (limit: {})",
-                        budget.tokens_used, self.profile.max_tokens
-                    ),
-                    impact: -10,
-                });
-                score -= 10;
-            }
+struct LintCleanCheck;
 
-            // Time limit
-            if budget.time_ms > self.profile.max_time_ms {
-                checks.push(Check {
-                    name: "time_budget".to_string(),
-                    status: CheckStatus::Warn,
-                    message: format!(
-                        "{}ms elapsed (limit: {}ms)",
-                        budget.time_ms, self.profile.max_time_ms
-                    ),
-                    impact: -10,
-                });
-                score -= 10;
-            }
+impl QualityCheck for LintCleanCheck {
+    fn name(&self) -> &str {
+        "lint_clean"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        if !profile.require_lint {
+            return None;
         }
+        let lint = result.lint.as_ref()?;
 
-        // === Output Quality Checks ===
-        if let Some(output) = &result.output {
-            // Minimum length
-            if output.len() < self.profile.min_text_chars {
-                checks.push(Check {
-                    name: "output_length".to_string(),
-                    status: CheckStatus::Warn,
-                    message: format!(
-                        "Output too short: {} chars (min: {})",
-                        output.len(),
-                        self.profile.min_text_chars
-                    ),
-                    impact: -5,
-                });
-                score -= 5;
-            }
+        if lint.errors > profile.max_lint_errors {
+            Some(Check {
+                name: "lint_clean".to_string(),
+                status: CheckStatus::Fail,
+                message: format!(
+                    "{} lint errors (max allowed: {})",
+                    lint.errors, profile.max_lint_errors
+                ),
+                impact: profile.weight("lint_errors"),
+                remediation: Some(Remediation {
+                    kind: RemediationKind::RunCommand,
+                    message: "Run the linter's autofix mode and re-check".to_string(),
+                    command: Some("cargo clippy --fix --allow-dirty".to_string()),
+                    profile_override: None,
+                }),
+            })
+        } else if lint.warnings > profile.max_lint_warnings {
+            Some(Check {
+                name: "lint_clean".to_string(),
+                status: CheckStatus::Warn,
+                message: format!(
+                    "{} lint warnings (max allowed: {})",
+                    lint.warnings, profile.max_lint_warnings
+                ),
+                impact: profile.weight("lint_warnings"),
+                remediation: Some(Remediation {
+                    kind: RemediationKind::RunCommand,
+                    message: "Run the linter's autofix mode and re-check".to_string(),
+                    command: Some("cargo clippy --fix --allow-dirty".to_string()),
+                    profile_override: None,
+                }),
+            })
+        } else {
+            Some(Check {
+                name: "lint_clean".to_string(),
+                status: CheckStatus::Ok,
+                message: format!("{} errors, {} warnings", lint.errors, lint.warnings),
+                impact: 0,
+                remediation: None,
+            })
+        }
+    }
+}
 
-            // Forbidden tokens
-            for token in &self.profile.forbidden_tokens {
-                if output.contains(token) {
-                    checks.push(Check {
-                        name: "forbidden_token".to_string(),
-                        status: CheckStatus::Warn,
-                        message: format!("Output contains forbidden token: {}", token),
-                        impact: -5,
-                    });
-                    score -= 5;
-                }
-            }
+struct FmtCleanCheck;
+
+impl QualityCheck for FmtCleanCheck {
+    fn name(&self) -> &str {
+        "fmt_clean"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        if !profile.require_fmt {
+            return None;
+        }
+        let fmt = result.fmt.as_ref()?;
+
+        if fmt.files_unformatted > 0 {
+            let message = match &fmt.diff {
+                Some(diff) => format!(
+                    "{} file(s) not canonically formatted:\n{}",
+                    fmt.files_unformatted, diff
+                ),
+                None => format!("{} file(s) not canonically formatted", fmt.files_unformatted),
+            };
+            Some(Check {
+                name: "fmt_clean".to_string(),
+                status: CheckStatus::Fail,
+                message,
+                impact: profile.weight("fmt_clean"),
+                remediation: Some(Remediation {
+                    kind: RemediationKind::RunCommand,
+                    message: "Run the formatter and re-check".to_string(),
+                    command: Some("cargo fmt --all".to_string()),
+                    profile_override: None,
+                }),
+            })
+        } else {
+            Some(Check {
+                name: "fmt_clean".to_string(),
+                status: CheckStatus::Ok,
+                message: "All files canonically formatted".to_string(),
+                impact: 0,
+                remediation: None,
+            })
+        }
+    }
+}
+
+struct FileLimitCheck;
+
+impl QualityCheck for FileLimitCheck {
+    fn name(&self) -> &str {
+        "file_limit"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        let changes = result.changes.as_ref()?;
+        let max_files = profile.max_files?;
+
+        if changes.files_changed > max_files {
+            Some(Check {
+                name: "file_limit".to_string(),
+                status: CheckStatus::Fail,
+                message: format!(
+                    "{} files changed (max allowed: {})",
+                    changes.files_changed, max_files
+                ),
+                impact: profile.weight("file_limit"),
+                remediation: Some(Remediation {
+                    kind: RemediationKind::SplitChange,
+                    message: "Split the change into smaller, separately-gated PRs".to_string(),
+                    command: None,
+                    profile_override: Some((
+                        "max_files".to_string(),
+                        changes.files_changed.to_string(),
+                    )),
+                }),
+            })
+        } else {
+            Some(Check {
+                name: "file_limit".to_string(),
+                status: CheckStatus::Ok,
+                message: format!("{}/{} files", changes.files_changed, max_files),
+                impact: 0,
+                remediation: None,
+            })
+        }
+    }
+}
+
+struct LineLimitCheck;
+
+impl QualityCheck for LineLimitCheck {
+    fn name(&self) -> &str {
+        "line_limit"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        let changes = result.changes.as_ref()?;
+        let max_lines = profile.max_lines?;
+        let total_lines = changes.lines_added + changes.lines_removed;
+
+        if total_lines > max_lines {
+            Some(Check {
+                name: "line_limit".to_string(),
+                status: CheckStatus::Fail,
+                message: format!("{} lines changed (max allowed: {})", total_lines, max_lines),
+                impact: profile.weight("line_limit"),
+                remediation: Some(Remediation {
+                    kind: RemediationKind::SplitChange,
+                    message: "Split the change into smaller, separately-gated PRs".to_string(),
+                    command: None,
+                    profile_override: Some(("max_lines".to_string(), total_lines.to_string())),
+                }),
+            })
+        } else {
+            Some(Check {
+                name: "line_limit".to_string(),
+                status: CheckStatus::Ok,
+                message: format!("{}/{} lines", total_lines, max_lines),
+                impact: 0,
+                remediation: None,
+            })
+        }
+    }
+}
+
+struct StepBudgetCheck;
+
+impl QualityCheck for StepBudgetCheck {
+    fn name(&self) -> &str {
+        "step_budget"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        let budget = result.budget.as_ref()?;
+        if budget.steps_used > profile.max_steps {
+            Some(Check {
+                name: "step_budget".to_string(),
+                status: CheckStatus::Warn,
+                message: format!(
+                    "{} steps used (limit: {})",
+                    budget.steps_used, profile.max_steps
+                ),
+                impact: profile.weight("step_budget"),
+                remediation: None,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+struct TokenBudgetCheck;
+
+impl QualityCheck for TokenBudgetCheck {
+    fn name(&self) -> &str {
+        "token_budget"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        let budget = result.budget.as_ref()?;
+        if budget.tokens_used > profile.max_tokens {
+            Some(Check {
+                name: "token_budget".to_string(),
+                status: CheckStatus::Warn,
+                message: format!(
+                    "{} tokens used (limit: {})",
+                    budget.tokens_used, profile.max_tokens
+                ),
+                impact: profile.weight("token_budget"),
+                remediation: None,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+struct TimeBudgetCheck;
+
+impl QualityCheck for TimeBudgetCheck {
+    fn name(&self) -> &str {
+        "time_budget"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        let budget = result.budget.as_ref()?;
+        if budget.time_ms > profile.max_time_ms {
+            Some(Check {
+                name: "time_budget".to_string(),
+                status: CheckStatus::Warn,
+                message: format!(
+                    "{}ms elapsed (limit: {}ms)",
+                    budget.time_ms, profile.max_time_ms
+                ),
+                impact: profile.weight("time_budget"),
+                remediation: None,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+struct OutputLengthCheck;
+
+impl QualityCheck for OutputLengthCheck {
+    fn name(&self) -> &str {
+        "output_length"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        let output = result.output.as_ref()?;
+        if output.len() < profile.min_text_chars {
+            Some(Check {
+                name: "output_length".to_string(),
+                status: CheckStatus::Warn,
+                message: format!(
+                    "Output too short: {} chars (min: {})",
+                    output.len(),
+                    profile.min_text_chars
+                ),
+                impact: profile.weight("output_length"),
+                remediation: None,
+            })
+        } else {
+            None
         }
+    }
+}
+
+struct ForbiddenTokenCheck;
 
-        // === Citation Checks ===
-        if self.profile.require_citations && result.citations.is_empty() {
-            checks.push(Check {
+impl QualityCheck for ForbiddenTokenCheck {
+    fn name(&self) -> &str {
+        "forbidden_token"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        let output = result.output.as_ref()?;
+        let found: Vec<&str> = profile
+            .forbidden_tokens
+            .iter()
+            .filter(|token| output.contains(token.as_str()))
+            .map(String::as_str)
+            .collect();
+
+        if found.is_empty() {
+            return None;
+        }
+
+        Some(Check {
+            name: "forbidden_token".to_string(),
+            status: CheckStatus::Warn,
+            message: format!("Output contains forbidden token(s): {}", found.join(", ")),
+            impact: profile.weight("forbidden_token") * found.len() as i32,
+            remediation: None,
+        })
+    }
+}
+
+struct CitationsCheck;
+
+impl QualityCheck for CitationsCheck {
+    fn name(&self) -> &str {
+        "citations"
+    }
+
+    fn evaluate(&self, result: &JobResult, profile: &QualityProfile) -> Option<Check> {
+        if profile.require_citations && result.citations.is_empty() {
+            Some(Check {
                 name: "citations".to_string(),
                 status: CheckStatus::Warn,
                 message: "No citations provided".to_string(),
-                impact: -10,
-            });
-            score -= 10;
+                impact: profile.weight("citations"),
+                remediation: Some(Remediation {
+                    kind: RemediationKind::AddCitation,
+                    message: "Attach a source for the claims made in the output".to_string(),
+                    command: None,
+                    profile_override: None,
+                }),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+fn built_in_checks() -> Vec<Box<dyn QualityCheck>> {
+    vec![
+        Box::new(TestsPassCheck),
+        Box::new(TestCoverageCheck),
+        Box::new(LintCleanCheck),
+        Box::new(FmtCleanCheck),
+        Box::new(FileLimitCheck),
+        Box::new(LineLimitCheck),
+        Box::new(StepBudgetCheck),
+        Box::new(TokenBudgetCheck),
+        Box::new(TimeBudgetCheck),
+        Box::new(OutputLengthCheck),
+        Box::new(ForbiddenTokenCheck),
+        Box::new(CitationsCheck),
+    ]
+}
+
+/// Quality gate that evaluates job results
+pub struct QualityGate {
+    profile: QualityProfile,
+    checks: Vec<Box<dyn QualityCheck>>,
+    limits: EvalLimits,
+}
+
+impl QualityGate {
+    /// Create a new quality gate with the given profile
+    pub fn new(profile: QualityProfile) -> Self {
+        Self {
+            profile,
+            checks: built_in_checks(),
+            limits: EvalLimits::default(),
+        }
+    }
+
+    /// Create a quality gate for a specific mode
+    pub fn for_mode(mode: &str) -> Self {
+        Self::new(QualityProfile::for_mode(mode))
+    }
+
+    /// Add a check run alongside the built-ins on every [`Self::evaluate`]
+    /// call, e.g. a project-specific "no TODO left in diff" rule.
+    pub fn register(&mut self, check: Box<dyn QualityCheck>) {
+        self.checks.push(check);
+    }
+
+    /// Bound the worst-case cost of [`Self::evaluate`] -- use when some of
+    /// the registered checks come from an untrusted rule set.
+    pub fn with_limits(mut self, limits: EvalLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Evaluate a job result against the quality profile
+    pub fn evaluate(&self, result: &JobResult) -> QualityVerdict {
+        let mut tracker = EvalTracker::new(self.limits);
+        let mut checks: Vec<Check> = Vec::new();
+
+        for check in &self.checks {
+            if let Some(kind) = tracker.tick() {
+                checks.push(limit_exceeded_check(kind));
+                break;
+            }
+            if let Some(c) = check.evaluate(result, &self.profile) {
+                if let Some(kind) = tracker.record_matches(1) {
+                    checks.push(c);
+                    checks.push(limit_exceeded_check(kind));
+                    break;
+                }
+                checks.push(c);
+            }
         }
 
         // Clamp score
-        score = score.max(0);
+        let score = (100 + checks.iter().map(|c| c.impact).sum::<i32>()).max(0) as u32;
 
-        // Determine overall verdict
-        let has_fail = checks.iter().any(|c| c.status == CheckStatus::Fail);
-        let has_warn = checks.iter().any(|c| c.status == CheckStatus::Warn);
+        // A check only forces BLOCK on its own if the profile hasn't
+        // demoted it to non-blocking; it still contributes its score
+        // penalty either way. An exceeded limit always blocks.
+        let blocking_fail = checks.iter().any(|c| {
+            c.status == CheckStatus::LimitExceeded
+                || (c.status == CheckStatus::Fail && self.profile.blocks_on_fail(&c.name))
+        });
+        // A non-blocking Fail still degrades the verdict to WARN rather
+        // than disappearing entirely.
+        let has_warn = checks.iter().any(|c| {
+            c.status == CheckStatus::Warn
+                || (c.status == CheckStatus::Fail && !self.profile.blocks_on_fail(&c.name))
+        });
 
-        let verdict = if has_fail {
+        let verdict = if blocking_fail || score < self.profile.block_below {
             "BLOCK"
-        } else if has_warn {
+        } else if has_warn || score < self.profile.warn_below {
             "WARN"
         } else {
             "OK"
         };
 
         // Generate summary
-        let summary = if has_fail {
+        let summary = if verdict == "BLOCK" {
             let fails: Vec<&str> = checks
                 .iter()
-                .filter(|c| c.status == CheckStatus::Fail)
+                .filter(|c| {
+                    c.status == CheckStatus::LimitExceeded
+                        || (c.status == CheckStatus::Fail && self.profile.blocks_on_fail(&c.name))
+                })
                 .map(|c| c.name.as_str())
                 .collect();
-            format!("Blocked: {}", fails.join(", "))
-        } else if has_warn {
+            if fails.is_empty() {
+                format!(
+                    "Blocked: score {} below threshold {}",
+                    score, self.profile.block_below
+                )
+            } else {
+                format!("Blocked: {}", fails.join(", "))
+            }
+        } else if verdict == "WARN" {
             let warns: Vec<&str> = checks
                 .iter()
-                .filter(|c| c.status == CheckStatus::Warn)
+                .filter(|c| {
+                    c.status == CheckStatus::Warn
+                        || (c.status == CheckStatus::Fail && !self.profile.blocks_on_fail(&c.name))
+                })
                 .map(|c| c.name.as_str())
                 .collect();
-            format!("Passed with warnings: {}", warns.join(", "))
+            if warns.is_empty() {
+                format!(
+                    "Passed with warnings: score {} below threshold {}",
+                    score, self.profile.warn_below
+                )
+            } else {
+                format!("Passed with warnings: {}", warns.join(", "))
+            }
         } else {
             "All checks passed".to_string()
         };
 
         QualityVerdict {
             verdict: verdict.to_string(),
-            score: score as u32,
+            score,
             checks,
             profile: self.profile.name.clone(),
             summary,
+            perfdata: perfdata(result, &self.profile, score),
+        }
+    }
+}
+
+/// Build the Nagios perfdata points for `result`/`profile`/`score`, drawing
+/// each point's warn/crit threshold from the matching `QualityProfile`
+/// limit. A field is omitted entirely when `result` doesn't carry it (e.g.
+/// no budget usage reported); a threshold is an empty string when the
+/// profile places no limit on it (e.g. genius mode's unlimited file count).
+fn perfdata(result: &JobResult, profile: &QualityProfile, score: u32) -> Vec<PerfDatum> {
+    let mut points = Vec::new();
+
+    if let Some(tests) = &result.tests {
+        points.push(PerfDatum {
+            label: "tests_failed".to_string(),
+            value: tests.failed.to_string(),
+            warn: String::new(),
+            crit: profile.max_test_failures.to_string(),
+        });
+
+        if let Some(coverage) = tests.coverage {
+            points.push(PerfDatum {
+                label: "coverage".to_string(),
+                value: format!("{:.1}", coverage * 100.0),
+                warn: format!("{:.1}", profile.min_coverage * 100.0),
+                crit: String::new(),
+            });
         }
     }
+
+    if let Some(lint) = &result.lint {
+        points.push(PerfDatum {
+            label: "lint_errors".to_string(),
+            value: lint.errors.to_string(),
+            warn: String::new(),
+            crit: profile.max_lint_errors.to_string(),
+        });
+        points.push(PerfDatum {
+            label: "lint_warnings".to_string(),
+            value: lint.warnings.to_string(),
+            warn: profile.max_lint_warnings.to_string(),
+            crit: String::new(),
+        });
+    }
+
+    if let Some(changes) = &result.changes {
+        points.push(PerfDatum {
+            label: "files_changed".to_string(),
+            value: changes.files_changed.to_string(),
+            warn: String::new(),
+            crit: profile.max_files.map(|v| v.to_string()).unwrap_or_default(),
+        });
+        points.push(PerfDatum {
+            label: "total_lines".to_string(),
+            value: (changes.lines_added + changes.lines_removed).to_string(),
+            warn: String::new(),
+            crit: profile.max_lines.map(|v| v.to_string()).unwrap_or_default(),
+        });
+    }
+
+    if let Some(budget) = &result.budget {
+        points.push(PerfDatum {
+            label: "steps_used".to_string(),
+            value: budget.steps_used.to_string(),
+            warn: profile.max_steps.to_string(),
+            crit: String::new(),
+        });
+        points.push(PerfDatum {
+            label: "tokens_used".to_string(),
+            value: budget.tokens_used.to_string(),
+            warn: profile.max_tokens.to_string(),
+            crit: String::new(),
+        });
+        points.push(PerfDatum {
+            label: "time_ms".to_string(),
+            value: budget.time_ms.to_string(),
+            warn: profile.max_time_ms.to_string(),
+            crit: String::new(),
+        });
+    }
+
+    points.push(PerfDatum {
+        label: "score".to_string(),
+        value: score.to_string(),
+        warn: String::new(),
+        crit: String::new(),
+    });
+
+    points
 }
 
 impl Default for QualityGate {
@@ -399,7 +995,7 @@ mod tests {
     #[test]
     fn test_passing_job() {
         let gate = QualityGate::for_mode("mechanic");
-        
+
         let result = JobResult {
             tests: Some(TestResults {
                 passed: 10,
@@ -411,6 +1007,7 @@ mod tests {
                 errors: 0,
                 warnings: 2,
             }),
+            fmt: None,
             changes: Some(ChangeStats {
                 files_changed: 2,
                 lines_added: 50,
@@ -420,7 +1017,7 @@ mod tests {
             output: Some("Task completed successfully. All checks passed.".to_string()),
             citations: vec!["cite:0".to_string()],
         };
-        
+
         let verdict = gate.evaluate(&result);
         assert_eq!(verdict.verdict, "OK");
     }
@@ -428,7 +1025,7 @@ mod tests {
     #[test]
     fn test_failing_tests() {
         let gate = QualityGate::for_mode("mechanic");
-        
+
         let result = JobResult {
             tests: Some(TestResults {
                 passed: 8,
@@ -437,12 +1034,13 @@ mod tests {
                 coverage: None,
             }),
             lint: None,
+            fmt: None,
             changes: None,
             budget: None,
             output: None,
             citations: vec![],
         };
-        
+
         let verdict = gate.evaluate(&result);
         assert_eq!(verdict.verdict, "BLOCK");
     }
@@ -450,7 +1048,7 @@ mod tests {
     #[test]
     fn test_exceeding_file_limit() {
         let gate = QualityGate::for_mode("mechanic");
-        
+
         let result = JobResult {
             tests: Some(TestResults {
                 passed: 10,
@@ -462,6 +1060,7 @@ mod tests {
                 errors: 0,
                 warnings: 0,
             }),
+            fmt: None,
             changes: Some(ChangeStats {
                 files_changed: 10,
                 lines_added: 100,
@@ -471,9 +1070,300 @@ mod tests {
             output: None,
             citations: vec![],
         };
-        
+
         let verdict = gate.evaluate(&result);
         assert_eq!(verdict.verdict, "BLOCK");
         assert!(verdict.checks.iter().any(|c| c.name == "file_limit"));
     }
+
+    struct NoTodoCheck;
+
+    impl QualityCheck for NoTodoCheck {
+        fn name(&self) -> &str {
+            "no_todo"
+        }
+
+        fn evaluate(&self, result: &JobResult, _profile: &QualityProfile) -> Option<Check> {
+            let output = result.output.as_ref()?;
+            if output.contains("TODO") {
+                Some(Check {
+                    name: "no_todo".to_string(),
+                    status: CheckStatus::Fail,
+                    message: "Output references an unresolved TODO".to_string(),
+                    impact: -10,
+                    remediation: None,
+                })
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_registered_check_runs_alongside_built_ins() {
+        let mut gate = QualityGate::for_mode("genius");
+        gate.register(Box::new(NoTodoCheck));
+
+        let result = JobResult {
+            tests: Some(TestResults {
+                passed: 1,
+                failed: 0,
+                skipped: 0,
+                coverage: None,
+            }),
+            lint: None,
+            fmt: None,
+            changes: None,
+            budget: None,
+            output: Some("Task completed successfully, but left a TODO.".to_string()),
+            citations: vec!["cite:0".to_string()],
+        };
+
+        let verdict = gate.evaluate(&result);
+        assert_eq!(verdict.verdict, "BLOCK");
+        assert!(verdict.checks.iter().any(|c| c.name == "no_todo"));
+    }
+
+    #[test]
+    fn test_to_nagios_maps_verdict_to_exit_code_and_perfdata() {
+        let gate = QualityGate::for_mode("mechanic");
+        let result = JobResult {
+            tests: Some(TestResults {
+                passed: 8,
+                failed: 2,
+                skipped: 0,
+                coverage: Some(0.5),
+            }),
+            lint: Some(LintResults {
+                errors: 0,
+                warnings: 0,
+            }),
+            fmt: None,
+            changes: None,
+            budget: None,
+            output: None,
+            citations: vec!["cite:0".to_string()],
+        };
+
+        let verdict = gate.evaluate(&result);
+        let (code, line) = verdict.to_nagios();
+
+        assert_eq!(code, 2);
+        assert!(line.starts_with("QUALITY BLOCK -"));
+        assert!(line.contains("tests_failed=2;;0"));
+        assert!(line.contains("coverage=50.0;80.0;"));
+        assert!(line.contains('|'));
+    }
+
+    #[test]
+    fn test_failing_file_limit_suggests_split_or_profile_override() {
+        let gate = QualityGate::for_mode("mechanic");
+
+        let result = JobResult {
+            tests: Some(TestResults {
+                passed: 10,
+                failed: 0,
+                skipped: 0,
+                coverage: None,
+            }),
+            lint: None,
+            fmt: None,
+            changes: Some(ChangeStats {
+                files_changed: 10,
+                lines_added: 100,
+                lines_removed: 50,
+            }),
+            budget: None,
+            output: None,
+            citations: vec![],
+        };
+
+        let verdict = gate.evaluate(&result);
+        let check = verdict
+            .checks
+            .iter()
+            .find(|c| c.name == "file_limit")
+            .expect("file_limit check present");
+        let remediation = check.remediation.as_ref().expect("remediation attached");
+        assert_eq!(remediation.kind, RemediationKind::SplitChange);
+        assert_eq!(
+            remediation.profile_override,
+            Some(("max_files".to_string(), "10".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_missing_citations_suggests_add_citation() {
+        let gate = QualityGate::for_mode("genius");
+
+        let result = JobResult {
+            tests: None,
+            lint: None,
+            fmt: None,
+            changes: None,
+            budget: None,
+            output: None,
+            citations: vec![],
+        };
+
+        let verdict = gate.evaluate(&result);
+        let check = verdict
+            .checks
+            .iter()
+            .find(|c| c.name == "citations")
+            .expect("citations check present");
+        let remediation = check.remediation.as_ref().expect("remediation attached");
+        assert_eq!(remediation.kind, RemediationKind::AddCitation);
+    }
+
+    #[test]
+    fn test_non_blocking_check_downgrades_fail_to_warn() {
+        let mut profile = QualityProfile::mechanic();
+        profile.non_blocking_checks.insert("lint_clean".to_string());
+        let gate = QualityGate::new(profile);
+
+        let result = JobResult {
+            tests: Some(TestResults {
+                passed: 10,
+                failed: 0,
+                skipped: 0,
+                coverage: Some(0.9),
+            }),
+            lint: Some(LintResults {
+                errors: 5,
+                warnings: 0,
+            }),
+            fmt: None,
+            changes: None,
+            budget: None,
+            output: None,
+            citations: vec!["cite:0".to_string()],
+        };
+
+        let verdict = gate.evaluate(&result);
+        assert_eq!(verdict.verdict, "WARN");
+        assert!(verdict
+            .checks
+            .iter()
+            .any(|c| c.name == "lint_clean" && c.status == CheckStatus::Fail));
+    }
+
+    #[test]
+    fn test_warnings_alone_can_block_via_score_threshold() {
+        let mut profile = QualityProfile::genius();
+        profile.block_below = 95;
+        let gate = QualityGate::new(profile);
+
+        let result = JobResult {
+            tests: Some(TestResults {
+                passed: 10,
+                failed: 0,
+                skipped: 0,
+                coverage: Some(0.3),
+            }),
+            lint: None,
+            fmt: None,
+            changes: None,
+            budget: None,
+            output: None,
+            citations: vec!["cite:0".to_string()],
+        };
+
+        let verdict = gate.evaluate(&result);
+        assert!(!verdict.checks.iter().any(|c| c.status == CheckStatus::Fail));
+        assert_eq!(verdict.verdict, "BLOCK");
+    }
+
+    #[test]
+    fn test_custom_weights_change_score_impact() {
+        let mut profile = QualityProfile::mechanic();
+        profile
+            .check_weights
+            .insert("lint_warnings".to_string(), -1);
+        let gate = QualityGate::new(profile);
+
+        let result = JobResult {
+            tests: Some(TestResults {
+                passed: 10,
+                failed: 0,
+                skipped: 0,
+                coverage: Some(0.9),
+            }),
+            lint: Some(LintResults {
+                errors: 0,
+                warnings: 11,
+            }),
+            fmt: None,
+            changes: None,
+            budget: None,
+            output: None,
+            citations: vec!["cite:0".to_string()],
+        };
+
+        let verdict = gate.evaluate(&result);
+        assert_eq!(verdict.score, 99);
+    }
+
+    #[test]
+    fn test_unformatted_files_block_with_diff_in_message() {
+        let gate = QualityGate::for_mode("mechanic");
+
+        let result = JobResult {
+            tests: Some(TestResults {
+                passed: 10,
+                failed: 0,
+                skipped: 0,
+                coverage: Some(0.9),
+            }),
+            lint: None,
+            fmt: Some(FmtResults {
+                files_unformatted: 1,
+                diff: Some("-fn foo(){}\n+fn foo() {}".to_string()),
+            }),
+            changes: None,
+            budget: None,
+            output: None,
+            citations: vec!["cite:0".to_string()],
+        };
+
+        let verdict = gate.evaluate(&result);
+        assert_eq!(verdict.verdict, "BLOCK");
+        let check = verdict
+            .checks
+            .iter()
+            .find(|c| c.name == "fmt_clean")
+            .expect("fmt_clean check present");
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.message.contains("-fn foo(){}"));
+        assert_eq!(
+            check.remediation.as_ref().unwrap().command.as_deref(),
+            Some("cargo fmt --all")
+        );
+    }
+
+    #[test]
+    fn test_fmt_check_skipped_when_not_required() {
+        let gate = QualityGate::for_mode("genius");
+
+        let result = JobResult {
+            tests: Some(TestResults {
+                passed: 1,
+                failed: 0,
+                skipped: 0,
+                coverage: None,
+            }),
+            lint: None,
+            fmt: Some(FmtResults {
+                files_unformatted: 3,
+                diff: None,
+            }),
+            changes: None,
+            budget: None,
+            output: None,
+            citations: vec!["cite:0".to_string()],
+        };
+
+        let verdict = gate.evaluate(&result);
+        assert!(!verdict.checks.iter().any(|c| c.name == "fmt_clean"));
+    }
 }
@@ -0,0 +1,450 @@
+//! Function-based, stateful quality rules, in the spirit of
+//! cloudformation-guard: rather than a single match expression per rule
+//! (see [`crate::rules`]), a [`GuardRule`] is a sequence of named clauses,
+//! each calling a built-in function and binding its result so later clauses
+//! in the same rule can reference it -- giving stateful resolution within
+//! one evaluation pass, and a trace of every captured value for CI tooling.
+//!
+//! ```toml
+//! [[guard_rule]]
+//! name = "unwrap_density"
+//! when = "source_files"
+//!
+//! [[guard_rule.clause]]
+//! name = "unwrap_count"
+//! function = { count = { field = "content", pattern = "unwrap\\(\\)" } }
+//! assert = { lte = 2.0 }
+//! severity = "error"
+//! message = "too many unwrap() calls in one file"
+//! ```
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::gate::{Check, CheckStatus, JobResult};
+use crate::rules::{Scope, Severity};
+
+/// A value produced by a [`GuardFunc`] call, either fed into a later clause
+/// or reported in a [`ClauseOutcome`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuardValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    /// The referenced field or an earlier clause's output wasn't available
+    /// (missing `JobResult` data, an unparsable regex, invalid JSON, ...).
+    Missing,
+}
+
+impl std::fmt::Display for GuardValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuardValue::Text(s) => write!(f, "{s}"),
+            GuardValue::Number(n) => write!(f, "{n}"),
+            GuardValue::Bool(b) => write!(f, "{b}"),
+            GuardValue::Missing => write!(f, "<missing>"),
+        }
+    }
+}
+
+/// Built-in functions a clause can call, each taking a `field` naming what
+/// to read: `"content"` (the file content), `"output"` (`JobResult.output`),
+/// or the name of an earlier clause in the same rule.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardFunc {
+    /// `regex_replace(field, pattern, replacement)`
+    RegexReplace {
+        field: String,
+        pattern: String,
+        replacement: String,
+    },
+    /// `count(field, pattern)` -- number of non-overlapping regex matches.
+    Count { field: String, pattern: String },
+    /// `json_path(field, path)` -- dotted lookup (e.g. `$.foo.bar`) into
+    /// `field` parsed as JSON.
+    JsonPath { field: String, path: String },
+}
+
+/// A predicate tested against a clause's [`GuardValue`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardAssert {
+    Eq(AssertLiteral),
+    Gt(f64),
+    Gte(f64),
+    Lt(f64),
+    Lte(f64),
+    Contains(String),
+}
+
+/// `GuardValue` isn't itself deserializable (it also models derived state
+/// like `Missing`), so `assert = { eq = ... }` deserializes into this
+/// narrower literal shape instead.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum AssertLiteral {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+}
+
+impl GuardAssert {
+    fn check(&self, value: &GuardValue) -> bool {
+        match (self, value) {
+            (GuardAssert::Eq(AssertLiteral::Text(s)), GuardValue::Text(t)) => s == t,
+            (GuardAssert::Eq(AssertLiteral::Number(n)), GuardValue::Number(v)) => n == v,
+            (GuardAssert::Eq(AssertLiteral::Bool(b)), GuardValue::Bool(v)) => b == v,
+            (GuardAssert::Gt(n), GuardValue::Number(v)) => v > n,
+            (GuardAssert::Gte(n), GuardValue::Number(v)) => v >= n,
+            (GuardAssert::Lt(n), GuardValue::Number(v)) => v < n,
+            (GuardAssert::Lte(n), GuardValue::Number(v)) => v <= n,
+            (GuardAssert::Contains(s), GuardValue::Text(t)) => t.contains(s.as_str()),
+            _ => false,
+        }
+    }
+}
+
+/// One named step of a [`GuardRule`]: calls `function`, binds its result
+/// under `name` for later clauses, and optionally asserts on it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuardClauseDef {
+    pub name: String,
+    pub function: GuardFunc,
+    #[serde(default)]
+    pub assert: Option<GuardAssert>,
+    pub message: Option<String>,
+    #[serde(default)]
+    pub severity: Severity,
+    #[serde(default = "default_impact")]
+    pub impact: i32,
+}
+
+fn default_impact() -> i32 {
+    -10
+}
+
+/// One compiled rule, as deserialized straight from TOML.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuardRuleDef {
+    pub name: String,
+    #[serde(default)]
+    pub when: Scope,
+    #[serde(default)]
+    pub clause: Vec<GuardClauseDef>,
+}
+
+/// Top-level TOML document: a list of rules under `[[guard_rule]]`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GuardRuleFile {
+    #[serde(default)]
+    pub guard_rule: Vec<GuardRuleDef>,
+}
+
+/// The outcome of one clause, kept around (beyond the `Check` it may have
+/// produced) so a `CombinedReport` can show every captured value, not just
+/// the ones that failed.
+#[derive(Debug, Clone)]
+pub struct ClauseOutcome {
+    pub rule: String,
+    pub clause: String,
+    pub status: CheckStatus,
+    pub value: GuardValue,
+}
+
+/// A rule compiled from a [`GuardRuleDef`], ready to evaluate against a
+/// file's content and the `JobResult` it was produced from.
+#[derive(Debug, Clone)]
+pub struct GuardRule {
+    def: GuardRuleDef,
+}
+
+impl GuardRule {
+    fn evaluate(&self, content: &str, filename: &str, job: &JobResult) -> (Vec<ClauseOutcome>, Vec<Check>) {
+        let mut outcomes = Vec::new();
+        let mut checks = Vec::new();
+
+        if !self.def.when.applies_to(filename) {
+            return (outcomes, checks);
+        }
+
+        let mut bound: HashMap<String, GuardValue> = HashMap::new();
+        for clause in &self.def.clause {
+            let value = eval_function(&clause.function, content, job, &bound);
+            bound.insert(clause.name.clone(), value.clone());
+
+            let status = match &clause.assert {
+                Some(assert) if !assert.check(&value) => clause.severity.status(),
+                _ => CheckStatus::Ok,
+            };
+
+            if status != CheckStatus::Ok {
+                let message = clause.message.clone().unwrap_or_else(|| {
+                    format!(
+                        "Clause '{}' of rule '{}' failed with value '{}'",
+                        clause.name, self.def.name, value
+                    )
+                });
+                checks.push(Check {
+                    name: format!("{}.{}", self.def.name, clause.name),
+                    status,
+                    message,
+                    impact: clause.impact,
+                    remediation: None,
+                });
+            }
+
+            outcomes.push(ClauseOutcome {
+                rule: self.def.name.clone(),
+                clause: clause.name.clone(),
+                status,
+                value,
+            });
+        }
+
+        (outcomes, checks)
+    }
+}
+
+fn eval_function(func: &GuardFunc, content: &str, job: &JobResult, bound: &HashMap<String, GuardValue>) -> GuardValue {
+    match func {
+        GuardFunc::RegexReplace { field, pattern, replacement } => match resolve_field(field, content, job, bound) {
+            GuardValue::Text(text) => regex::Regex::new(pattern)
+                .map(|re| GuardValue::Text(re.replace_all(&text, replacement.as_str()).into_owned()))
+                .unwrap_or(GuardValue::Missing),
+            _ => GuardValue::Missing,
+        },
+        GuardFunc::Count { field, pattern } => match resolve_field(field, content, job, bound) {
+            GuardValue::Text(text) => regex::Regex::new(pattern)
+                .map(|re| GuardValue::Number(re.find_iter(&text).count() as f64))
+                .unwrap_or(GuardValue::Missing),
+            _ => GuardValue::Missing,
+        },
+        GuardFunc::JsonPath { field, path } => match resolve_field(field, content, job, bound) {
+            GuardValue::Text(text) => serde_json::from_str::<serde_json::Value>(&text)
+                .ok()
+                .and_then(|v| json_path_lookup(&v, path))
+                .unwrap_or(GuardValue::Missing),
+            _ => GuardValue::Missing,
+        },
+    }
+}
+
+/// Resolve a clause's `field` argument: the raw file `content`, the job's
+/// `output`, or a value an earlier clause in the same rule bound.
+fn resolve_field(field: &str, content: &str, job: &JobResult, bound: &HashMap<String, GuardValue>) -> GuardValue {
+    match field {
+        "content" => GuardValue::Text(content.to_string()),
+        "output" => job.output.clone().map(GuardValue::Text).unwrap_or(GuardValue::Missing),
+        name => bound.get(name).cloned().unwrap_or(GuardValue::Missing),
+    }
+}
+
+fn json_path_lookup(value: &serde_json::Value, path: &str) -> Option<GuardValue> {
+    let mut cur = value;
+    for segment in path.trim_start_matches('$').split('.').filter(|s| !s.is_empty()) {
+        cur = cur.get(segment)?;
+    }
+    Some(match cur {
+        serde_json::Value::String(s) => GuardValue::Text(s.clone()),
+        serde_json::Value::Number(n) => GuardValue::Number(n.as_f64().unwrap_or(0.0)),
+        serde_json::Value::Bool(b) => GuardValue::Bool(*b),
+        other => GuardValue::Text(other.to_string()),
+    })
+}
+
+/// A compiled set of guard rules, parsed once and evaluated against many files.
+#[derive(Debug, Clone, Default)]
+pub struct GuardRuleSet {
+    rules: Vec<GuardRule>,
+}
+
+impl GuardRuleSet {
+    /// Parse a TOML guard-rule document into a compiled rule set.
+    pub fn from_toml(src: &str) -> Result<Self, GuardParseError> {
+        let file: GuardRuleFile = toml::from_str(src).map_err(|e| GuardParseError(e.to_string()))?;
+        Ok(Self {
+            rules: file.guard_rule.into_iter().map(|def| GuardRule { def }).collect(),
+        })
+    }
+
+    /// Evaluate every rule against one file, producing its full clause
+    /// trace alongside the `Check`s any failed assertion produced.
+    pub fn evaluate(&self, content: &str, filename: &str, job: &JobResult) -> FileReport {
+        let mut clauses = Vec::new();
+        let mut checks = Vec::new();
+
+        for rule in &self.rules {
+            let (rule_clauses, rule_checks) = rule.evaluate(content, filename, job);
+            clauses.extend(rule_clauses);
+            checks.extend(rule_checks);
+        }
+
+        FileReport {
+            filename: filename.to_string(),
+            clauses,
+            checks,
+        }
+    }
+}
+
+/// Error parsing a guard rule document.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("failed to parse guard rules: {0}")]
+pub struct GuardParseError(String);
+
+/// One file's guard-rule evaluation: every clause's outcome (for CI/
+/// introspection tooling) plus the subset that became `Check`s (for
+/// feeding a `QualityGate` verdict).
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    pub filename: String,
+    pub clauses: Vec<ClauseOutcome>,
+    pub checks: Vec<Check>,
+}
+
+/// Per-file guard reports rolled up into one combined report, mirroring
+/// [`crate::rules::combine`] but keyed by the richer [`FileReport`].
+#[derive(Debug, Clone)]
+pub struct CombinedReport {
+    pub files: Vec<FileReport>,
+    /// `true` if no file produced a `Fail` check.
+    pub passed: bool,
+    /// Sum of every check's impact across every file.
+    pub total_impact: i32,
+}
+
+/// Merge per-file [`FileReport`]s into one combined report.
+pub fn combine(reports: impl IntoIterator<Item = FileReport>) -> CombinedReport {
+    let mut files = Vec::new();
+    let mut total_impact = 0;
+    let mut passed = true;
+
+    for report in reports {
+        total_impact += report.checks.iter().map(|c| c.impact).sum::<i32>();
+        if report.checks.iter().any(|c| c.status == CheckStatus::Fail) {
+            passed = false;
+        }
+        files.push(report);
+    }
+
+    CombinedReport {
+        files,
+        passed,
+        total_impact,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job_with_output(output: &str) -> JobResult {
+        JobResult {
+            tests: None,
+            lint: None,
+            fmt: None,
+            changes: None,
+            budget: None,
+            output: Some(output.to_string()),
+            citations: Vec::new(),
+        }
+    }
+
+    const RULES: &str = r##"
+        [[guard_rule]]
+        name = "unwrap_density"
+        when = "source_files"
+
+        [[guard_rule.clause]]
+        name = "unwrap_count"
+        function = { count = { field = "content", pattern = "unwrap\\(\\)" } }
+        assert = { lte = 1.0 }
+        severity = "error"
+        message = "too many unwrap() calls"
+    "##;
+
+    #[test]
+    fn count_clause_binds_and_asserts() {
+        let rules = GuardRuleSet::from_toml(RULES).unwrap();
+        let job = job_with_output("");
+
+        let report = rules.evaluate("x.unwrap();", "lib.rs", &job);
+        assert!(report.checks.is_empty());
+        assert_eq!(report.clauses[0].value, GuardValue::Number(1.0));
+
+        let report = rules.evaluate("x.unwrap(); y.unwrap();", "lib.rs", &job);
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(report.checks[0].name, "unwrap_density.unwrap_count");
+    }
+
+    #[test]
+    fn scope_restricts_rule_to_source_files() {
+        let rules = GuardRuleSet::from_toml(RULES).unwrap();
+        let job = job_with_output("");
+        let report = rules.evaluate("x.unwrap(); y.unwrap();", "lib_test.rs", &job);
+        assert!(report.checks.is_empty());
+        assert!(report.clauses.is_empty());
+    }
+
+    #[test]
+    fn clause_can_depend_on_an_earlier_clause_in_the_same_rule() {
+        let rules = r##"
+            [[guard_rule]]
+            name = "redacted_unwrap_count"
+
+            [[guard_rule.clause]]
+            name = "scrubbed"
+            function = { regex_replace = { field = "content", pattern = "TODO", replacement = "" } }
+
+            [[guard_rule.clause]]
+            name = "remaining_unwraps"
+            function = { count = { field = "scrubbed", pattern = "unwrap\\(\\)" } }
+            assert = { eq = 0.0 }
+        "##;
+        let rules = GuardRuleSet::from_toml(rules).unwrap();
+        let job = job_with_output("");
+
+        let report = rules.evaluate("TODO x.unwrap();", "lib.rs", &job);
+        assert_eq!(report.clauses[0].value, GuardValue::Text(" x.unwrap();".to_string()));
+        assert_eq!(report.clauses[1].value, GuardValue::Number(1.0));
+        assert_eq!(report.checks.len(), 1);
+    }
+
+    #[test]
+    fn json_path_reads_the_job_output() {
+        let rules = r##"
+            [[guard_rule]]
+            name = "coverage_floor"
+
+            [[guard_rule.clause]]
+            name = "coverage"
+            function = { json_path = { field = "output", path = "$.coverage" } }
+            assert = { gte = 0.8 }
+        "##;
+        let rules = GuardRuleSet::from_toml(rules).unwrap();
+
+        let job = job_with_output(r#"{"coverage": 0.5}"#);
+        let report = rules.evaluate("", "lib.rs", &job);
+        assert_eq!(report.clauses[0].value, GuardValue::Number(0.5));
+        assert_eq!(report.checks.len(), 1);
+
+        let job = job_with_output(r#"{"coverage": 0.9}"#);
+        let report = rules.evaluate("", "lib.rs", &job);
+        assert!(report.checks.is_empty());
+    }
+
+    #[test]
+    fn combine_merges_per_file_reports() {
+        let rules = GuardRuleSet::from_toml(RULES).unwrap();
+        let job = job_with_output("");
+
+        let a = rules.evaluate("x.unwrap();", "a.rs", &job);
+        let b = rules.evaluate("x.unwrap(); y.unwrap();", "b.rs", &job);
+
+        let report = combine(vec![a, b]);
+        assert!(!report.passed);
+        assert_eq!(report.files.len(), 2);
+        assert_eq!(report.total_impact, -10);
+    }
+}
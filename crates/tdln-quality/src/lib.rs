@@ -25,6 +25,7 @@
 //!         coverage: Some(0.9),
 //!     }),
 //!     lint: Some(LintResults { errors: 0, warnings: 2 }),
+//!     fmt: None,
 //!     changes: None,
 //!     budget: None,
 //!     output: Some("Task completed successfully".to_string()),
@@ -72,18 +73,30 @@
 //! println!("Trend: {:?}", summary.trend);
 //! ```
 
+pub mod benchmark;
 pub mod checks;
+pub mod complexity;
 pub mod gate;
+pub mod guard;
 pub mod metrics;
 pub mod profile;
+pub mod rules;
 
 pub use gate::{
-    QualityGate, QualityVerdict, JobResult, TestResults, LintResults,
-    ChangeStats, BudgetUsage, Check, CheckStatus,
+    QualityGate, QualityVerdict, JobResult, TestResults, LintResults, FmtResults,
+    ChangeStats, BudgetUsage, Check, CheckStatus, QualityCheck, PerfDatum,
+    Remediation, RemediationKind, EvalLimits, EvalTracker, LimitKind, QualityError,
 };
-pub use profile::QualityProfile;
+pub use profile::{QualityProfile, ValidationReport, Violation};
 pub use checks::{CodeQualityChecker, OutputQualityChecker, ForbiddenPattern, RequiredPattern};
-pub use metrics::{QualityMetrics, TestMetrics, CodeMetrics, PerformanceMetrics, MetricsAggregator, MetricsTrend};
+pub use complexity::{analyze_rust_complexity, analyze_rust_doc_ratio, FunctionComplexity};
+pub use metrics::{QualityMetrics, TestMetrics, CodeMetrics, PerformanceMetrics, MetricsAggregator, MetricsTrend, NormalizedPerformance, TestHistory, MetricField};
+pub use benchmark::SystemBenchmark;
+pub use rules::{combine, CombinedReport, CompiledRules, MatchExpr, Rule, RuleParseError, Scope, Severity};
+pub use guard::{
+    combine as combine_guard_reports, ClauseOutcome, CombinedReport as GuardCombinedReport, FileReport,
+    GuardAssert, GuardFunc, GuardParseError, GuardRule, GuardRuleSet, GuardValue,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -132,6 +145,7 @@ pub fn job_result_from_metrics(metrics: &metrics::QualityMetrics) -> JobResult {
             errors: metrics.code.lint_errors,
             warnings: metrics.code.lint_warnings,
         }),
+        fmt: None,
         changes: Some(ChangeStats {
             files_changed: metrics.code.files_changed,
             lines_added: metrics.code.lines_added,
@@ -161,6 +175,7 @@ mod tests {
                 coverage: Some(0.85),
             }),
             lint: Some(LintResults { errors: 0, warnings: 0 }),
+            fmt: None,
             changes: Some(ChangeStats {
                 files_changed: 2,
                 lines_added: 30,
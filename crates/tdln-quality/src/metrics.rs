@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::benchmark::SystemBenchmark;
+
 /// Collection of quality metrics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct QualityMetrics {
@@ -38,7 +40,14 @@ impl QualityMetrics {
             let pass_rate = self.tests.passed as f32 / self.tests.total as f32;
             score -= ((1.0 - pass_rate) * 40.0) as i32;
         }
-        
+
+        // Flaky-test contribution: a test that flips between pass and fail
+        // is a worse signal than one that simply fails, since it hides
+        // real regressions behind "just a flake, rerun it".
+        if self.tests.flaky > 0 {
+            score -= (self.tests.flaky.min(10) * 3) as i32;
+        }
+
         // Coverage contribution (20%)
         if let Some(coverage) = self.tests.coverage {
             if coverage < 0.8 {
@@ -195,6 +204,62 @@ impl PerformanceMetrics {
             self.duration_ms / self.steps_taken as u64
         }
     }
+
+    /// Scale `duration_ms`/`cpu_ms` onto `bench`'s reference-machine
+    /// timescale by dividing out its CPU ratio, so a run's performance
+    /// figures can be compared across hosts of different speeds instead of
+    /// only against this crate's fixed `60000ms`/`500MB` thresholds.
+    pub fn normalized(&self, bench: &SystemBenchmark) -> NormalizedPerformance {
+        NormalizedPerformance {
+            duration_ms: self.duration_ms as f64 / bench.cpu_ratio,
+            cpu_ms: self.cpu_ms as f64 / bench.cpu_ratio,
+        }
+    }
+}
+
+/// [`PerformanceMetrics::duration_ms`]/`cpu_ms`, rescaled onto a
+/// [`SystemBenchmark`]'s reference-machine timescale.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedPerformance {
+    pub duration_ms: f64,
+    pub cpu_ms: f64,
+}
+
+/// Per-test pass/fail state tracked across every sample a [`MetricsAggregator`]
+/// has seen outcomes for -- the same signal a CI system uses to decide a
+/// failure is "just a flake" worth retrying rather than a real regression.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TestHistory {
+    last_outcome: Option<bool>,
+    total_runs: u32,
+    flip_count: u32,
+}
+
+impl TestHistory {
+    fn record(&mut self, passed: bool) {
+        if let Some(last) = self.last_outcome {
+            if last != passed {
+                self.flip_count += 1;
+            }
+        }
+        self.last_outcome = Some(passed);
+        self.total_runs += 1;
+    }
+
+    /// Total times this test has been observed.
+    pub fn total_runs(&self) -> u32 {
+        self.total_runs
+    }
+
+    /// Number of times this test's outcome flipped relative to its previous run.
+    pub fn flip_count(&self) -> u32 {
+        self.flip_count
+    }
+
+    /// Whether this test has ever flipped between pass and fail.
+    pub fn is_flaky(&self) -> bool {
+        self.flip_count > 0
+    }
 }
 
 /// Metrics aggregator for tracking over time
@@ -202,6 +267,7 @@ impl PerformanceMetrics {
 pub struct MetricsAggregator {
     samples: Vec<QualityMetrics>,
     max_samples: usize,
+    test_history: HashMap<String, TestHistory>,
 }
 
 impl MetricsAggregator {
@@ -209,16 +275,56 @@ impl MetricsAggregator {
         Self {
             samples: Vec::new(),
             max_samples,
+            test_history: HashMap::new(),
         }
     }
-    
+
     pub fn add(&mut self, metrics: QualityMetrics) {
         self.samples.push(metrics);
         if self.samples.len() > self.max_samples {
             self.samples.remove(0);
         }
     }
-    
+
+    /// Record one sample's per-test pass/fail outcomes (test name ->
+    /// passed), updating each test's [`TestHistory`] and back-filling the
+    /// most recently [`Self::add`]ed sample's `TestMetrics::flaky` with the
+    /// number of tests that have ever flipped outcome. Call right after
+    /// `add` for the same sample; an aggregator that never calls this
+    /// simply reports no flaky tests.
+    pub fn record_test_outcomes<'a>(&mut self, outcomes: impl IntoIterator<Item = (&'a str, bool)>) {
+        for (name, passed) in outcomes {
+            self.test_history.entry(name.to_string()).or_default().record(passed);
+        }
+
+        let flaky_count = self.test_history.values().filter(|h| h.is_flaky()).count() as u32;
+        if let Some(last) = self.samples.last_mut() {
+            last.tests.flaky = flaky_count;
+        }
+    }
+
+    /// Names of every test that has flipped between pass and fail across
+    /// the outcomes recorded so far.
+    pub fn flaky_tests(&self) -> Vec<&str> {
+        self.test_history
+            .iter()
+            .filter(|(_, history)| history.is_flaky())
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Fraction of pass/fail transitions across every tracked test that were
+    /// actually a flip, i.e. `flips / transitions` where a test with `n`
+    /// runs has `n - 1` transitions. `0.0` if no test has more than one run.
+    pub fn flakiness_rate(&self) -> f32 {
+        let transitions: u32 = self.test_history.values().map(|h| h.total_runs.saturating_sub(1)).sum();
+        if transitions == 0 {
+            return 0.0;
+        }
+        let flips: u32 = self.test_history.values().map(|h| h.flip_count).sum();
+        flips as f32 / transitions as f32
+    }
+
     pub fn count(&self) -> usize {
         self.samples.len()
     }
@@ -253,37 +359,135 @@ impl MetricsAggregator {
         }
     }
     
+    /// Least-squares linear regression of `overall_score` against sample
+    /// index: `(slope, r_squared)`. `slope` is in score points per sample;
+    /// `r_squared` is how much of the score's variance the line explains,
+    /// `0.0` for fewer than two samples or a perfectly flat series.
+    pub fn score_regression(&self) -> (f32, f32) {
+        let ys: Vec<f32> = self.samples.iter().map(|m| m.overall_score() as f32).collect();
+        linear_regression(&ys)
+    }
+
+    /// Classifies the score's direction from [`Self::score_regression`]:
+    /// `Improving`/`Declining` only when the slope's magnitude exceeds
+    /// [`TREND_SLOPE_THRESHOLD`] score points per sample *and* `r_squared`
+    /// clears [`TREND_CONFIDENCE_FLOOR`] -- a noisy or inconclusive series
+    /// (few samples, a flat series, a one-off outlier) reports `Stable`
+    /// rather than over-calling a trend.
     pub fn trend(&self) -> MetricsTrend {
         if self.samples.len() < 2 {
             return MetricsTrend::Stable;
         }
-        
-        let half = self.samples.len() / 2;
-        let first_half: f32 = self.samples[..half].iter()
-            .map(|m| m.overall_score() as f32)
-            .sum::<f32>() / half as f32;
-        let second_half: f32 = self.samples[half..].iter()
-            .map(|m| m.overall_score() as f32)
-            .sum::<f32>() / (self.samples.len() - half) as f32;
-        
-        let diff = second_half - first_half;
-        
-        if diff > 5.0 {
+
+        let (slope, r_squared) = self.score_regression();
+        if r_squared < TREND_CONFIDENCE_FLOOR {
+            return MetricsTrend::Stable;
+        }
+
+        if slope > TREND_SLOPE_THRESHOLD {
             MetricsTrend::Improving
-        } else if diff < -5.0 {
+        } else if slope < -TREND_SLOPE_THRESHOLD {
             MetricsTrend::Declining
         } else {
             MetricsTrend::Stable
         }
     }
-    
+
+    /// The `p`th percentile (`0.0..=100.0`, e.g. `50.0`/`95.0`/`99.0`) of
+    /// `field` across every retained sample, via nearest-rank over the
+    /// sorted values. `None` if no sample carries that field (e.g.
+    /// `Coverage` when no sample ever reported coverage).
+    pub fn percentile(&self, field: MetricField, p: f32) -> Option<f32> {
+        let mut values: Vec<f32> = self.samples.iter().filter_map(|m| field.extract(m)).collect();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).expect("metric values are never NaN"));
+
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * (values.len() - 1) as f32).round() as usize;
+        Some(values[rank.min(values.len() - 1)])
+    }
+
     pub fn summary(&self) -> MetricsSummary {
+        let (slope, r_squared) = self.score_regression();
         MetricsSummary {
             sample_count: self.samples.len(),
             average_score: self.average_score(),
             average_pass_rate: self.average_pass_rate(),
             average_coverage: self.average_coverage(),
             trend: self.trend(),
+            score_slope: slope,
+            score_r_squared: r_squared,
+        }
+    }
+}
+
+/// Score-points-per-sample slope magnitude a series must clear before
+/// [`MetricsAggregator::trend`] calls it `Improving`/`Declining`.
+const TREND_SLOPE_THRESHOLD: f32 = 1.0;
+
+/// Minimum `r_squared` (how much of the score's variance the regression
+/// line explains) before [`MetricsAggregator::trend`] trusts the slope at
+/// all, rather than reporting `Stable` for a noisy or inconclusive series.
+const TREND_CONFIDENCE_FLOOR: f32 = 0.3;
+
+/// Least-squares fit of `ys` against `0..ys.len()`: `(slope, r_squared)`.
+/// `(0.0, 0.0)` for fewer than two points or a zero-variance `x` series
+/// (never happens here since `x` is always `0..n`, but guards division by
+/// zero defensively).
+fn linear_regression(ys: &[f32]) -> (f32, f32) {
+    let n = ys.len();
+    if n < 2 {
+        return (0.0, 0.0);
+    }
+
+    let x_mean = (n - 1) as f32 / 2.0;
+    let y_mean = ys.iter().sum::<f32>() / n as f32;
+
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for (i, &y) in ys.iter().enumerate() {
+        let dx = i as f32 - x_mean;
+        numerator += dx * (y - y_mean);
+        denominator += dx * dx;
+    }
+    if denominator == 0.0 {
+        return (0.0, 0.0);
+    }
+    let slope = numerator / denominator;
+
+    let mut ss_res = 0.0;
+    let mut ss_tot = 0.0;
+    for (i, &y) in ys.iter().enumerate() {
+        let y_hat = y_mean + slope * (i as f32 - x_mean);
+        ss_res += (y - y_hat).powi(2);
+        ss_tot += (y - y_mean).powi(2);
+    }
+    let r_squared = if ss_tot == 0.0 { 0.0 } else { 1.0 - ss_res / ss_tot };
+
+    (slope, r_squared)
+}
+
+/// A metric [`MetricsAggregator::percentile`] can extract from one sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricField {
+    /// [`QualityMetrics::overall_score`].
+    Score,
+    /// [`TestMetrics::coverage`], when the sample reported one.
+    Coverage,
+    /// [`PerformanceMetrics::duration_ms`].
+    DurationMs,
+    /// [`PerformanceMetrics::tokens_used`].
+    TokensUsed,
+}
+
+impl MetricField {
+    fn extract(self, metrics: &QualityMetrics) -> Option<f32> {
+        match self {
+            MetricField::Score => Some(metrics.overall_score() as f32),
+            MetricField::Coverage => metrics.tests.coverage,
+            MetricField::DurationMs => Some(metrics.performance.duration_ms as f32),
+            MetricField::TokensUsed => Some(metrics.performance.tokens_used as f32),
         }
     }
 }
@@ -305,6 +509,10 @@ pub struct MetricsSummary {
     pub average_pass_rate: f32,
     pub average_coverage: Option<f32>,
     pub trend: MetricsTrend,
+    /// [`MetricsAggregator::score_regression`]'s slope, in score points per sample.
+    pub score_slope: f32,
+    /// [`MetricsAggregator::score_regression`]'s `r_squared`.
+    pub score_r_squared: f32,
 }
 
 #[cfg(test)]
@@ -395,5 +603,126 @@ mod tests {
         
         assert_eq!(agg.trend(), MetricsTrend::Declining);
     }
+
+    #[test]
+    fn test_normalized_scales_out_a_slower_hosts_cpu_ratio() {
+        let perf = PerformanceMetrics {
+            duration_ms: 1000,
+            cpu_ms: 800,
+            ..Default::default()
+        };
+        let bench = SystemBenchmark {
+            cpu_ratio: 2.0,
+            memory_ratio: 1.0,
+            disk_ratio: 1.0,
+        };
+
+        let normalized = perf.normalized(&bench);
+        assert_eq!(normalized.duration_ms, 500.0);
+        assert_eq!(normalized.cpu_ms, 400.0);
+    }
+
+    #[test]
+    fn test_flaky_test_detected_after_outcome_flips() {
+        let mut agg = MetricsAggregator::new(100);
+
+        agg.add(QualityMetrics::new());
+        agg.record_test_outcomes([("test_a", true), ("test_b", true)]);
+
+        agg.add(QualityMetrics::new());
+        agg.record_test_outcomes([("test_a", false), ("test_b", true)]);
+
+        assert_eq!(agg.flaky_tests(), vec!["test_a"]);
+        assert!(agg.flakiness_rate() > 0.0);
+    }
+
+    #[test]
+    fn test_flaky_count_backfilled_onto_latest_sample() {
+        let mut agg = MetricsAggregator::new(100);
+
+        agg.add(QualityMetrics::new());
+        agg.record_test_outcomes([("test_a", true)]);
+        agg.add(QualityMetrics::new());
+        agg.record_test_outcomes([("test_a", false)]);
+
+        assert_eq!(agg.samples.last().unwrap().tests.flaky, 1);
+    }
+
+    #[test]
+    fn test_stable_test_is_not_flaky() {
+        let mut agg = MetricsAggregator::new(100);
+
+        for _ in 0..5 {
+            agg.add(QualityMetrics::new());
+            agg.record_test_outcomes([("test_a", true)]);
+        }
+
+        assert!(agg.flaky_tests().is_empty());
+        assert_eq!(agg.flakiness_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_trend_stable_for_a_flat_series() {
+        let mut agg = MetricsAggregator::new(100);
+        for _ in 0..10 {
+            let mut m = QualityMetrics::new();
+            m.tests = TestMetrics { total: 10, passed: 10, failed: 0, ..Default::default() };
+            agg.add(m);
+        }
+
+        assert_eq!(agg.trend(), MetricsTrend::Stable);
+        let (slope, r_squared) = agg.score_regression();
+        assert_eq!(slope, 0.0);
+        assert_eq!(r_squared, 0.0);
+    }
+
+    #[test]
+    fn test_trend_improving_for_a_rising_series() {
+        let mut agg = MetricsAggregator::new(100);
+        for i in 0..10u32 {
+            let mut m = QualityMetrics::new();
+            m.tests = TestMetrics { total: 10, passed: i.min(5), failed: 10 - i.min(5), ..Default::default() };
+            agg.add(m);
+        }
+
+        assert_eq!(agg.trend(), MetricsTrend::Improving);
+    }
+
+    #[test]
+    fn test_percentile_score_across_samples() {
+        let mut agg = MetricsAggregator::new(100);
+        for passed in [10u32, 8, 6, 4, 2] {
+            let mut m = QualityMetrics::new();
+            m.tests = TestMetrics { total: 10, passed, failed: 10 - passed, ..Default::default() };
+            agg.add(m);
+        }
+
+        let p50 = agg.percentile(MetricField::Score, 50.0).unwrap();
+        let p100 = agg.percentile(MetricField::Score, 100.0).unwrap();
+        assert_eq!(p100, 100.0);
+        assert!(p50 <= p100);
+    }
+
+    #[test]
+    fn test_percentile_none_when_field_never_reported() {
+        let mut agg = MetricsAggregator::new(100);
+        agg.add(QualityMetrics::new());
+        assert_eq!(agg.percentile(MetricField::Coverage, 50.0), None);
+    }
+
+    #[test]
+    fn test_summary_carries_slope_and_r_squared() {
+        let mut agg = MetricsAggregator::new(100);
+        for i in 0..10u32 {
+            let mut m = QualityMetrics::new();
+            m.tests = TestMetrics { total: 10, passed: 10 - i.min(5), failed: i.min(5), ..Default::default() };
+            agg.add(m);
+        }
+
+        let summary = agg.summary();
+        assert_eq!(summary.trend, MetricsTrend::Declining);
+        assert!(summary.score_slope < 0.0);
+        assert!(summary.score_r_squared > TREND_CONFIDENCE_FLOOR);
+    }
 }
 
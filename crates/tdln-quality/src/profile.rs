@@ -3,69 +3,127 @@
 //! Defines constraints and thresholds for mechanic vs genius mode.
 
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::metrics::QualityMetrics;
+use crate::rules::Severity;
 
 /// Quality profile defining constraints and thresholds
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QualityProfile {
     /// Profile name (e.g., "mechanic@1.0", "genius@1.0")
     pub name: String,
-    
+
     /// Operating mode
     pub mode: String,
-    
+
     // === Test Requirements ===
-    
     /// Whether tests must pass
     pub require_tests: bool,
-    
+
     /// Maximum allowed test failures (0 for mechanic)
     pub max_test_failures: u32,
-    
+
     // === Lint Requirements ===
-    
     /// Whether lint must pass
     pub require_lint: bool,
-    
+
     /// Maximum allowed lint errors
     pub max_lint_errors: u32,
-    
+
     /// Maximum allowed lint warnings
     pub max_lint_warnings: u32,
-    
+
+    // === Formatting Requirements ===
+    /// Whether output must be canonically formatted (e.g. `cargo fmt
+    /// --all -- --check` clean). Defaults to `false` so profiles
+    /// serialized before this field existed keep evaluating unchanged.
+    #[serde(default)]
+    pub require_fmt: bool,
+
     // === Change Limits ===
-    
     /// Maximum files that can be changed
     pub max_files: Option<u32>,
-    
+
     /// Maximum lines that can be changed
     pub max_lines: Option<u32>,
-    
+
     // === Coverage Requirements ===
-    
     /// Minimum test coverage (0.0 to 1.0)
     pub min_coverage: f32,
-    
+
     /// Require citations for claims
     pub require_citations: bool,
-    
+
     // === Output Requirements ===
-    
     /// Minimum output text length
     pub min_text_chars: usize,
-    
+
     /// Forbidden tokens in output
     pub forbidden_tokens: Vec<String>,
-    
+
     // === Budget Limits ===
-    
     /// Maximum steps allowed
     pub max_steps: u32,
-    
+
     /// Maximum tokens allowed
     pub max_tokens: u32,
-    
+
     /// Maximum time in milliseconds
     pub max_time_ms: u64,
+
+    // === Scoring ===
+    /// Score penalty applied per failing/warning check, keyed by a
+    /// descriptive weight name (`"tests_pass"`, `"lint_errors"`, ...) --
+    /// see [`Self::weight`]. Falls back to [`default_check_weights`] for
+    /// any name a profile doesn't override, so a partial YAML profile only
+    /// needs to list the weights it wants to change.
+    #[serde(default = "default_check_weights")]
+    pub check_weights: HashMap<String, i32>,
+
+    /// [`crate::gate::Check::name`]s that should never force a BLOCK
+    /// verdict on their own, even when they report
+    /// [`crate::gate::CheckStatus::Fail`] -- e.g. a profile that treats
+    /// lint as advisory. The check still reports `Fail` and its score
+    /// penalty still applies.
+    #[serde(default)]
+    pub non_blocking_checks: HashSet<String>,
+
+    /// Score below which the verdict is BLOCK, independent of any single
+    /// check's status -- lets warnings alone pile up into a block. `0`
+    /// (the default) disables this, since the clamped score never drops
+    /// below it.
+    #[serde(default)]
+    pub block_below: u32,
+
+    /// Score below which the verdict is WARN, independent of any single
+    /// check's status. `0` (the default) disables this.
+    #[serde(default)]
+    pub warn_below: u32,
+}
+
+/// The weights `QualityGate`'s built-in checks used before they became
+/// profile-driven -- kept as the default so existing profiles (and any
+/// YAML that doesn't set `check_weights`) see unchanged scoring.
+pub fn default_check_weights() -> HashMap<String, i32> {
+    [
+        ("tests_pass", -30),
+        ("test_coverage", -10),
+        ("lint_errors", -20),
+        ("lint_warnings", -5),
+        ("fmt_clean", -15),
+        ("file_limit", -25),
+        ("line_limit", -25),
+        ("step_budget", -10),
+        ("token_budget", -10),
+        ("time_budget", -10),
+        ("output_length", -5),
+        ("forbidden_token", -5),
+        ("citations", -10),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v))
+    .collect()
 }
 
 impl QualityProfile {
@@ -79,6 +137,7 @@ impl QualityProfile {
             require_lint: true,
             max_lint_errors: 0,
             max_lint_warnings: 10,
+            require_fmt: true,
             max_files: Some(5),
             max_lines: Some(200),
             min_coverage: 0.8,
@@ -88,6 +147,10 @@ impl QualityProfile {
             max_steps: 20,
             max_tokens: 50_000,
             max_time_ms: 60_000,
+            check_weights: default_check_weights(),
+            non_blocking_checks: HashSet::new(),
+            block_below: 0,
+            warn_below: 0,
         }
     }
 
@@ -97,27 +160,193 @@ impl QualityProfile {
             name: "genius@1.0".to_string(),
             mode: "genius".to_string(),
             require_tests: true,
-            max_test_failures: 0,  // Still require passing tests
+            max_test_failures: 0, // Still require passing tests
             require_lint: true,
-            max_lint_errors: 5,    // Allow some errors
+            max_lint_errors: 5, // Allow some errors
             max_lint_warnings: 50,
-            max_files: None,       // No file limit
-            max_lines: None,       // No line limit
-            min_coverage: 0.6,     // Lower coverage requirement
+            require_fmt: false, // Style isn't gated in genius mode
+            max_files: None,   // No file limit
+            max_lines: None,   // No line limit
+            min_coverage: 0.6, // Lower coverage requirement
             require_citations: true,
             min_text_chars: 30,
             forbidden_tokens: vec!["???".to_string()],
             max_steps: 100,
             max_tokens: 200_000,
             max_time_ms: 300_000,
+            check_weights: default_check_weights(),
+            non_blocking_checks: HashSet::new(),
+            block_below: 0,
+            warn_below: 0,
         }
     }
 
+    /// Score penalty configured for `weight_name`, falling back to `0` if
+    /// neither this profile nor [`default_check_weights`] mentions it --
+    /// e.g. a custom check registered via `QualityGate::register` that
+    /// the profile predates.
+    pub fn weight(&self, weight_name: &str) -> i32 {
+        self.check_weights
+            .get(weight_name)
+            .copied()
+            .or_else(|| default_check_weights().get(weight_name).copied())
+            .unwrap_or(0)
+    }
+
+    /// Whether a `Fail` status from the check named `check_name` should by
+    /// itself force a BLOCK verdict, as opposed to just weighing down the
+    /// score.
+    pub fn blocks_on_fail(&self, check_name: &str) -> bool {
+        !self.non_blocking_checks.contains(check_name)
+    }
+
     /// Load profile from YAML
     pub fn from_yaml(yaml: &str) -> Result<Self, String> {
         serde_yaml::from_str(yaml).map_err(|e| e.to_string())
     }
 
+    /// Check `metrics` (and, if given, the run's `output_text`) against
+    /// every threshold this profile declares, e.g. `max_test_failures`,
+    /// `min_coverage`, `max_files`/`max_lines`, `forbidden_tokens`, and the
+    /// step/token/time budgets. Unlike [`QualityMetrics::overall_score`]'s
+    /// hardcoded 0-100 score, this reports exactly which constraints were
+    /// breached, so a caller can reject a run (e.g. `no_truth_no_output` in
+    /// mechanic mode) the moment [`ValidationReport::passed`] is `false`
+    /// instead of reasoning about an opaque number.
+    pub fn validate(&self, metrics: &QualityMetrics, output_text: Option<&str>) -> ValidationReport {
+        let mut violations = Vec::new();
+
+        if self.require_tests && metrics.tests.failed > self.max_test_failures {
+            violations.push(Violation {
+                rule: "max_test_failures".to_string(),
+                severity: Severity::Error,
+                expected: format!("<= {}", self.max_test_failures),
+                actual: metrics.tests.failed.to_string(),
+                message: format!(
+                    "{} test(s) failed (max allowed: {})",
+                    metrics.tests.failed, self.max_test_failures
+                ),
+            });
+        }
+
+        if self.require_lint && metrics.code.lint_errors > self.max_lint_errors {
+            violations.push(Violation {
+                rule: "max_lint_errors".to_string(),
+                severity: Severity::Error,
+                expected: format!("<= {}", self.max_lint_errors),
+                actual: metrics.code.lint_errors.to_string(),
+                message: format!(
+                    "{} lint error(s) (max allowed: {})",
+                    metrics.code.lint_errors, self.max_lint_errors
+                ),
+            });
+        }
+
+        if let Some(coverage) = metrics.tests.coverage {
+            if coverage < self.min_coverage {
+                violations.push(Violation {
+                    rule: "min_coverage".to_string(),
+                    severity: Severity::Warning,
+                    expected: format!(">= {:.1}%", self.min_coverage * 100.0),
+                    actual: format!("{:.1}%", coverage * 100.0),
+                    message: format!(
+                        "Coverage {:.1}% below minimum {:.1}%",
+                        coverage * 100.0,
+                        self.min_coverage * 100.0
+                    ),
+                });
+            }
+        }
+
+        if let Some(max_files) = self.max_files {
+            if metrics.code.files_changed > max_files {
+                violations.push(Violation {
+                    rule: "max_files".to_string(),
+                    severity: Severity::Error,
+                    expected: format!("<= {max_files}"),
+                    actual: metrics.code.files_changed.to_string(),
+                    message: format!(
+                        "{} file(s) changed (max allowed: {})",
+                        metrics.code.files_changed, max_files
+                    ),
+                });
+            }
+        }
+
+        if let Some(max_lines) = self.max_lines {
+            let total_lines = metrics.code.total_lines_changed();
+            if total_lines > max_lines {
+                violations.push(Violation {
+                    rule: "max_lines".to_string(),
+                    severity: Severity::Error,
+                    expected: format!("<= {max_lines}"),
+                    actual: total_lines.to_string(),
+                    message: format!("{total_lines} line(s) changed (max allowed: {max_lines})"),
+                });
+            }
+        }
+
+        if let Some(output) = output_text {
+            let found: Vec<&str> = self
+                .forbidden_tokens
+                .iter()
+                .filter(|token| output.contains(token.as_str()))
+                .map(String::as_str)
+                .collect();
+            if !found.is_empty() {
+                violations.push(Violation {
+                    rule: "forbidden_tokens".to_string(),
+                    severity: Severity::Error,
+                    expected: "none of the forbidden tokens present".to_string(),
+                    actual: found.join(", "),
+                    message: format!("Output contains forbidden token(s): {}", found.join(", ")),
+                });
+            }
+        }
+
+        if metrics.performance.steps_taken > self.max_steps {
+            violations.push(Violation {
+                rule: "max_steps".to_string(),
+                severity: Severity::Warning,
+                expected: format!("<= {}", self.max_steps),
+                actual: metrics.performance.steps_taken.to_string(),
+                message: format!(
+                    "{} step(s) used (limit: {})",
+                    metrics.performance.steps_taken, self.max_steps
+                ),
+            });
+        }
+
+        if metrics.performance.tokens_used > self.max_tokens {
+            violations.push(Violation {
+                rule: "max_tokens".to_string(),
+                severity: Severity::Warning,
+                expected: format!("<= {}", self.max_tokens),
+                actual: metrics.performance.tokens_used.to_string(),
+                message: format!(
+                    "{} token(s) used (limit: {})",
+                    metrics.performance.tokens_used, self.max_tokens
+                ),
+            });
+        }
+
+        if metrics.performance.duration_ms > self.max_time_ms {
+            violations.push(Violation {
+                rule: "max_time_ms".to_string(),
+                severity: Severity::Warning,
+                expected: format!("<= {}ms", self.max_time_ms),
+                actual: format!("{}ms", metrics.performance.duration_ms),
+                message: format!(
+                    "{}ms elapsed (limit: {}ms)",
+                    metrics.performance.duration_ms, self.max_time_ms
+                ),
+            });
+        }
+
+        let passed = !violations.iter().any(|v| v.severity == Severity::Error);
+        ValidationReport { violations, passed }
+    }
+
     /// Get profile by mode name
     pub fn for_mode(mode: &str) -> Self {
         match mode {
@@ -134,6 +363,30 @@ impl Default for QualityProfile {
     }
 }
 
+/// One profile constraint that [`QualityMetrics`]/`output_text` failed to
+/// satisfy, as reported by [`QualityProfile::validate`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Violation {
+    /// Name of the violated constraint, e.g. `"max_test_failures"`.
+    pub rule: String,
+    pub severity: Severity,
+    /// The profile's configured limit, rendered for display.
+    pub expected: String,
+    /// What `metrics`/`output_text` actually measured.
+    pub actual: String,
+    pub message: String,
+}
+
+/// The result of [`QualityProfile::validate`]: every constraint that was
+/// breached, plus a top-level pass/fail gate driven by `Severity::Error`
+/// violations (a `Severity::Warning` violation, e.g. coverage dipping below
+/// `min_coverage`, is reported but doesn't flip `passed` to `false`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub violations: Vec<Violation>,
+    pub passed: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,4 +406,132 @@ mod tests {
         assert_eq!(profile.max_lines, None);
         assert!(profile.require_tests);
     }
+
+    #[test]
+    fn test_default_weights_match_legacy_impacts() {
+        let profile = QualityProfile::mechanic();
+        assert_eq!(profile.weight("tests_pass"), -30);
+        assert_eq!(profile.weight("file_limit"), -25);
+        assert_eq!(profile.weight("unknown_check"), 0);
+    }
+
+    #[test]
+    fn test_score_thresholds_default_to_disabled() {
+        let profile = QualityProfile::mechanic();
+        assert_eq!(profile.block_below, 0);
+        assert_eq!(profile.warn_below, 0);
+    }
+
+    #[test]
+    fn test_non_blocking_check_override() {
+        let mut profile = QualityProfile::mechanic();
+        assert!(profile.blocks_on_fail("lint_clean"));
+        profile.non_blocking_checks.insert("lint_clean".to_string());
+        assert!(!profile.blocks_on_fail("lint_clean"));
+    }
+
+    #[test]
+    fn test_from_yaml_without_scoring_fields_uses_defaults() {
+        let yaml = r#"
+name: custom@1.0
+mode: custom
+require_tests: true
+max_test_failures: 0
+require_lint: false
+max_lint_errors: 0
+max_lint_warnings: 0
+max_files: null
+max_lines: null
+min_coverage: 0.0
+require_citations: false
+min_text_chars: 0
+forbidden_tokens: []
+max_steps: 1
+max_tokens: 1
+max_time_ms: 1
+"#;
+        let profile = QualityProfile::from_yaml(yaml).expect("valid profile");
+        assert_eq!(profile.weight("tests_pass"), -30);
+        assert_eq!(profile.block_below, 0);
+        assert!(profile.non_blocking_checks.is_empty());
+        assert!(!profile.require_fmt);
+    }
+
+    #[test]
+    fn test_require_fmt_defaults_differ_by_mode() {
+        assert!(QualityProfile::mechanic().require_fmt);
+        assert!(!QualityProfile::genius().require_fmt);
+    }
+
+    fn passing_metrics() -> QualityMetrics {
+        let mut metrics = QualityMetrics::new();
+        metrics.tests.total = 10;
+        metrics.tests.passed = 10;
+        metrics.tests.failed = 0;
+        metrics.tests.coverage = Some(0.9);
+        metrics.code.files_changed = 2;
+        metrics.code.lines_added = 50;
+        metrics
+    }
+
+    #[test]
+    fn test_validate_passes_clean_metrics() {
+        let report = QualityProfile::mechanic().validate(&passing_metrics(), Some("all good"));
+        assert!(report.passed);
+        assert!(report.violations.is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_test_failures_as_blocking() {
+        let mut metrics = passing_metrics();
+        metrics.tests.failed = 2;
+
+        let report = QualityProfile::mechanic().validate(&metrics, None);
+        assert!(!report.passed);
+        let violation = report.violations.iter().find(|v| v.rule == "max_test_failures").unwrap();
+        assert_eq!(violation.severity, Severity::Error);
+        assert_eq!(violation.actual, "2");
+    }
+
+    #[test]
+    fn test_validate_coverage_below_minimum_warns_without_blocking() {
+        let mut metrics = passing_metrics();
+        metrics.tests.coverage = Some(0.5);
+
+        let report = QualityProfile::mechanic().validate(&metrics, None);
+        assert!(report.passed);
+        let violation = report.violations.iter().find(|v| v.rule == "min_coverage").unwrap();
+        assert_eq!(violation.severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_validate_exceeding_file_and_line_limits_blocks() {
+        let mut metrics = passing_metrics();
+        metrics.code.files_changed = 10;
+        metrics.code.lines_added = 1000;
+
+        let report = QualityProfile::mechanic().validate(&metrics, None);
+        assert!(!report.passed);
+        assert!(report.violations.iter().any(|v| v.rule == "max_files"));
+        assert!(report.violations.iter().any(|v| v.rule == "max_lines"));
+    }
+
+    #[test]
+    fn test_validate_forbidden_token_in_output_blocks() {
+        let metrics = passing_metrics();
+        let report = QualityProfile::mechanic().validate(&metrics, Some("still has a FIXME in it"));
+        assert!(!report.passed);
+        assert!(report.violations.iter().any(|v| v.rule == "forbidden_tokens"));
+    }
+
+    #[test]
+    fn test_validate_budget_overage_warns_without_blocking() {
+        let mut metrics = passing_metrics();
+        metrics.performance.steps_taken = 999;
+
+        let report = QualityProfile::mechanic().validate(&metrics, None);
+        assert!(report.passed);
+        let violation = report.violations.iter().find(|v| v.rule == "max_steps").unwrap();
+        assert_eq!(violation.severity, Severity::Warning);
+    }
 }
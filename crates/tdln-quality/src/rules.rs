@@ -0,0 +1,311 @@
+//! Declarative rule DSL for code quality checks.
+//!
+//! `CodeQualityChecker` used to hardcode its `forbidden_patterns` and
+//! `required_patterns`, so a project that wanted a different gate had to
+//! recompile. This module parses a small TOML rule language into compiled
+//! [`Rule`]s so the same checker can be driven by a project-local file.
+//!
+//! ```toml
+//! [[rule]]
+//! name = "no_unwrap_without_test"
+//! when = "source_files"
+//! match = { contains = "unwrap()" }
+//! requires_nearby = "#[test]"
+//! severity = "error"
+//! impact = -15
+//! message = "unwrap() must be covered by a nearby #[test]"
+//!
+//! [[rule]]
+//! name = "no_todo"
+//! match = { any = [ { contains = "TODO" }, { contains = "FIXME" } ] }
+//! ```
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::gate::{Check, CheckStatus};
+
+/// Top-level TOML document: a list of rules under `[[rule]]`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RuleFile {
+    #[serde(default)]
+    pub rule: Vec<RuleDef>,
+}
+
+/// One compiled rule, as deserialized straight from TOML.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuleDef {
+    pub name: String,
+    #[serde(default)]
+    pub when: Scope,
+    #[serde(rename = "match")]
+    pub matcher: MatchExpr,
+    pub message: Option<String>,
+    #[serde(default = "default_impact")]
+    pub impact: i32,
+    #[serde(default)]
+    pub severity: Severity,
+    /// Stateful clause: every match of `matcher` must have this pattern
+    /// elsewhere in the same file, e.g. every `pub fn ... unwrap()` needs a
+    /// nearby `#[test]`.
+    #[serde(default)]
+    pub requires_nearby: Option<String>,
+    /// `regex_replace(pattern, repl)` applied to the matched content before
+    /// it is reported, letting a rule redact or normalize what it captured.
+    #[serde(default)]
+    pub regex_replace: Option<RegexReplace>,
+}
+
+fn default_impact() -> i32 {
+    -10
+}
+
+/// Which files a rule applies to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    #[default]
+    All,
+    TestFiles,
+    SourceFiles,
+}
+
+impl Scope {
+    fn applies_to(self, filename: &str) -> bool {
+        match self {
+            Scope::All => true,
+            Scope::TestFiles => filename.contains("test"),
+            Scope::SourceFiles => !filename.contains("test"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    #[default]
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn status(self) -> CheckStatus {
+        match self {
+            Severity::Info => CheckStatus::Ok,
+            Severity::Warning => CheckStatus::Warn,
+            Severity::Error => CheckStatus::Fail,
+        }
+    }
+}
+
+/// Boolean combinator tree over match predicates.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum MatchExpr {
+    Contains { contains: String },
+    Regex { regex: String },
+    All { all: Vec<MatchExpr> },
+    Any { any: Vec<MatchExpr> },
+    Not { not: Box<MatchExpr> },
+}
+
+impl MatchExpr {
+    fn eval(&self, content: &str) -> bool {
+        match self {
+            MatchExpr::Contains { contains } => content.contains(contains.as_str()),
+            MatchExpr::Regex { regex } => regex::Regex::new(regex).map(|re| re.is_match(content)).unwrap_or(false),
+            MatchExpr::All { all } => all.iter().all(|m| m.eval(content)),
+            MatchExpr::Any { any } => any.iter().any(|m| m.eval(content)),
+            MatchExpr::Not { not } => !not.eval(content),
+        }
+    }
+
+    /// The literal or pattern this expression ultimately tests for, used to
+    /// label checks and to feed `regex_replace`.
+    fn primary_text(&self) -> &str {
+        match self {
+            MatchExpr::Contains { contains } => contains,
+            MatchExpr::Regex { regex } => regex,
+            MatchExpr::All { all } => all.first().map(MatchExpr::primary_text).unwrap_or(""),
+            MatchExpr::Any { any } => any.first().map(MatchExpr::primary_text).unwrap_or(""),
+            MatchExpr::Not { not } => not.primary_text(),
+        }
+    }
+}
+
+/// A `regex_replace(pattern, repl)` post-processing step.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegexReplace {
+    pub pattern: String,
+    pub repl: String,
+}
+
+impl RegexReplace {
+    fn apply(&self, text: &str) -> String {
+        regex::Regex::new(&self.pattern)
+            .map(|re| re.replace_all(text, self.repl.as_str()).into_owned())
+            .unwrap_or_else(|_| text.to_string())
+    }
+}
+
+/// A rule compiled from a [`RuleDef`], ready to evaluate against file content.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    def: RuleDef,
+}
+
+impl Rule {
+    fn evaluate(&self, content: &str, filename: &str) -> Option<Check> {
+        if !self.def.when.applies_to(filename) {
+            return None;
+        }
+        if !self.def.matcher.eval(content) {
+            return None;
+        }
+        if let Some(nearby) = &self.def.requires_nearby {
+            if content.contains(nearby.as_str()) {
+                return None;
+            }
+        }
+
+        let matched = self.def.matcher.primary_text();
+        let matched = match &self.def.regex_replace {
+            Some(rr) => rr.apply(matched),
+            None => matched.to_string(),
+        };
+
+        let message = self
+            .def
+            .message
+            .clone()
+            .unwrap_or_else(|| format!("Rule '{}' matched '{}'", self.def.name, matched));
+
+        Some(Check {
+            name: self.def.name.clone(),
+            status: self.def.severity.status(),
+            message,
+            impact: self.def.impact,
+            remediation: None,
+        })
+    }
+}
+
+/// A compiled set of rules, parsed once and evaluated against many files.
+#[derive(Debug, Clone, Default)]
+pub struct CompiledRules {
+    rules: Vec<Rule>,
+}
+
+impl CompiledRules {
+    /// Parse a TOML rule document into a compiled rule set.
+    pub fn from_toml(src: &str) -> Result<Self, RuleParseError> {
+        let file: RuleFile = toml::from_str(src).map_err(|e| RuleParseError(e.to_string()))?;
+        Ok(Self {
+            rules: file.rule.into_iter().map(|def| Rule { def }).collect(),
+        })
+    }
+
+    /// Evaluate every rule against a single file's content.
+    pub fn check(&self, content: &str, filename: &str) -> Vec<Check> {
+        self.rules.iter().filter_map(|rule| rule.evaluate(content, filename)).collect()
+    }
+}
+
+/// Error parsing a rule DSL document.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("failed to parse quality rules: {0}")]
+pub struct RuleParseError(String);
+
+/// Per-file checks rolled up into a single report, mirroring how multiple
+/// source files get combined into one quality verdict elsewhere in this crate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombinedReport {
+    /// Checks keyed by filename.
+    pub files: HashMap<String, Vec<Check>>,
+    /// `true` if no file produced a `Fail` check.
+    pub passed: bool,
+    /// Sum of every check's impact across every file.
+    pub total_impact: i32,
+}
+
+/// Merge per-file `Vec<Check>` results into one combined report.
+pub fn combine<'a>(per_file: impl IntoIterator<Item = (&'a str, Vec<Check>)>) -> CombinedReport {
+    let mut files = HashMap::new();
+    let mut total_impact = 0;
+    let mut passed = true;
+
+    for (filename, checks) in per_file {
+        total_impact += checks.iter().map(|c| c.impact).sum::<i32>();
+        if checks.iter().any(|c| c.status == CheckStatus::Fail) {
+            passed = false;
+        }
+        files.insert(filename.to_string(), checks);
+    }
+
+    CombinedReport {
+        files,
+        passed,
+        total_impact,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r##"
+        [[rule]]
+        name = "no_todo"
+        match = { contains = "TODO" }
+        severity = "error"
+        impact = -15
+
+        [[rule]]
+        name = "unwrap_needs_test"
+        when = "source_files"
+        match = { contains = "unwrap()" }
+        requires_nearby = "#[test]"
+        severity = "error"
+        impact = -20
+    "##;
+
+    #[test]
+    fn parses_and_matches_simple_rule() {
+        let rules = CompiledRules::from_toml(RULES).unwrap();
+        let checks = rules.check("// TODO: fix this", "main.rs");
+        assert_eq!(checks.len(), 1); // TODO matches; unwrap() does not appear so it's skipped
+        assert_eq!(checks[0].name, "no_todo");
+    }
+
+    #[test]
+    fn stateful_rule_requires_nearby_pattern() {
+        let rules = CompiledRules::from_toml(RULES).unwrap();
+
+        let without_test = "pub fn risky() { x.unwrap() }";
+        let checks = rules.check(without_test, "lib.rs");
+        assert!(checks.iter().any(|c| c.name == "unwrap_needs_test"));
+
+        let with_test = "pub fn risky() { x.unwrap() } #[test] fn risky_test() {}";
+        let checks = rules.check(with_test, "lib.rs");
+        assert!(!checks.iter().any(|c| c.name == "unwrap_needs_test"));
+    }
+
+    #[test]
+    fn scope_restricts_rule_to_source_files() {
+        let rules = CompiledRules::from_toml(RULES).unwrap();
+        let checks = rules.check("x.unwrap()", "lib_test.rs");
+        assert!(!checks.iter().any(|c| c.name == "unwrap_needs_test"));
+    }
+
+    #[test]
+    fn combine_merges_per_file_checks() {
+        let report = combine(vec![
+            ("a.rs", vec![Check { name: "x".into(), status: CheckStatus::Ok, message: "ok".into(), impact: 0, remediation: None }]),
+            ("b.rs", vec![Check { name: "y".into(), status: CheckStatus::Fail, message: "bad".into(), impact: -30, remediation: None }]),
+        ]);
+
+        assert!(!report.passed);
+        assert_eq!(report.total_impact, -30);
+        assert_eq!(report.files.len(), 2);
+    }
+}
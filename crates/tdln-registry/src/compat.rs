@@ -1,12 +1,20 @@
 //! Compatibility Matrix
+use crate::semver::{Version, VersionReq};
+use crate::RegistryEntry;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompatMatrix {
     pub gin_gout_pairs: HashMap<String, Vec<String>>,
     pub policy_spec_pairs: HashMap<String, Vec<String>>,
     pub pack_policy_pairs: HashMap<String, Vec<String>>,
+    /// Registered entries keyed by id, used by [`CompatMatrix::is_compatible`]
+    /// and [`CompatMatrix::resolve`] to check a `compatible_with` semver
+    /// range against another entry's actual version.
+    #[serde(default)]
+    pub entries: HashMap<String, RegistryEntry>,
 }
 
 impl CompatMatrix {
@@ -15,13 +23,411 @@ impl CompatMatrix {
             gin_gout_pairs: HashMap::new(),
             policy_spec_pairs: HashMap::new(),
             pack_policy_pairs: HashMap::new(),
+            entries: HashMap::new(),
         }
     }
 
+    /// Register (or replace) an entry so [`CompatMatrix::is_compatible`],
+    /// [`CompatMatrix::resolve`], and [`CompatMatrix::validate`] can see it.
+    pub fn register(&mut self, entry: RegistryEntry) {
+        self.entries.insert(entry.id.clone(), entry);
+    }
+
+    /// Whether `id_a`'s declared `compatible_with` range for `id_b` is
+    /// satisfied by `id_b`'s actual registered semver. `false` if either id
+    /// is unregistered, `id_a` declares no range for `id_b`, or either
+    /// version/range fails to parse -- same "no match, no claim" convention
+    /// as [`CompatMatrix::is_compatible_gin_gout`].
+    pub fn is_compatible(&self, id_a: &str, id_b: &str) -> bool {
+        let Some(a) = self.entries.get(id_a) else { return false };
+        let Some(b) = self.entries.get(id_b) else { return false };
+
+        a.compatible_with.iter().any(|(target, range)| {
+            target == id_b
+                && Version::parse(&b.semver)
+                    .ok()
+                    .zip(VersionReq::parse(range).ok())
+                    .is_some_and(|(version, req)| req.matches(&version))
+        })
+    }
+
+    /// For every id `id` declares a `compatible_with` range for, the
+    /// concrete registered version that satisfies it -- omitting entries
+    /// that are unregistered, fail to parse, or don't actually satisfy the
+    /// declared range. Call [`CompatMatrix::validate`] first if the caller
+    /// needs to know *why* an id was omitted rather than just that it was.
+    pub fn resolve(&self, id: &str) -> Vec<(String, String)> {
+        let Some(entry) = self.entries.get(id) else { return Vec::new() };
+
+        entry
+            .compatible_with
+            .iter()
+            .filter_map(|(target_id, range)| {
+                let target = self.entries.get(target_id)?;
+                let version = Version::parse(&target.semver).ok()?;
+                let req = VersionReq::parse(range).ok()?;
+                req.matches(&version).then(|| (target_id.clone(), target.semver.clone()))
+            })
+            .collect()
+    }
+
+    /// Check every registered entry's semver, every `compatible_with`
+    /// range (parses, targets an existing entry, and is actually satisfied
+    /// by that entry's version), and the `compatible_with` reference graph
+    /// for cycles -- so a grammar registry can refuse to load an
+    /// inconsistent compat set up front instead of discovering a broken
+    /// entry lazily through [`CompatMatrix::is_compatible`]/[`CompatMatrix::resolve`].
+    pub fn validate(&self) -> Result<(), CompatError> {
+        for entry in self.entries.values() {
+            Version::parse(&entry.semver)
+                .map_err(|e| CompatError::InvalidVersion { id: entry.id.clone(), source: e.to_string() })?;
+
+            for (target_id, range) in &entry.compatible_with {
+                let target = self
+                    .entries
+                    .get(target_id)
+                    .ok_or_else(|| CompatError::UnknownEntry(target_id.clone()))?;
+                let req = VersionReq::parse(range).map_err(|e| CompatError::InvalidRange {
+                    from: entry.id.clone(),
+                    to: target_id.clone(),
+                    range: range.clone(),
+                    source: e.to_string(),
+                })?;
+                let target_version = Version::parse(&target.semver)
+                    .map_err(|e| CompatError::InvalidVersion { id: target_id.clone(), source: e.to_string() })?;
+                if !req.matches(&target_version) {
+                    return Err(CompatError::Unsatisfiable {
+                        from: entry.id.clone(),
+                        to: target_id.clone(),
+                        range: range.clone(),
+                        actual: target.semver.clone(),
+                    });
+                }
+            }
+        }
+
+        detect_cycle(&self.entries)
+    }
+
     pub fn is_compatible_gin_gout(&self, gin: &str, gout: &str) -> bool {
         self.gin_gout_pairs
             .get(gin)
             .map(|v| v.contains(&gout.to_string()))
             .unwrap_or(false)
     }
+
+    /// Load a `CompatMatrix` from a YAML file, alongside
+    /// `response-templates.yaml` in `grammars/`.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read compat matrix file: {}", e))?;
+        serde_yaml::from_str(&content).map_err(|e| format!("Failed to parse compat matrix YAML: {}", e))
+    }
+
+    /// Walk the gin->gout, policy->spec, and pack->policy edges of
+    /// `config` against this matrix's three maps, collecting every
+    /// incompatible one rather than stopping at the first -- so a caller
+    /// gets "X is not compatible with Y; allowed: [...]" for every broken
+    /// edge in one pass instead of a bare `false`.
+    pub fn validate_pipeline(&self, config: &PipelineConfig) -> PipelineValidation {
+        let mut edges = Vec::new();
+
+        check_edge(&self.gin_gout_pairs, "gin->gout", &config.gin, &config.gout, &mut edges);
+        check_edge(&self.policy_spec_pairs, "policy->spec", &config.policy, &config.spec, &mut edges);
+        check_edge(&self.pack_policy_pairs, "pack->policy", &config.pack, &config.policy, &mut edges);
+
+        PipelineValidation { edges }
+    }
+
+    /// Compatible next-stage options for `value` in `stage`'s outgoing map
+    /// -- e.g. `suggest("gin", "promptspec.in.v1")` lists every `gout` it's
+    /// compatible with. An unknown `stage` name or a `value` absent from
+    /// that map both yield an empty list, same "no match, no suggestions"
+    /// convention as [`CompatMatrix::is_compatible_gin_gout`].
+    pub fn suggest(&self, stage: &str, value: &str) -> Vec<String> {
+        let map = match stage {
+            "gin" => &self.gin_gout_pairs,
+            "policy" => &self.policy_spec_pairs,
+            "pack" => &self.pack_policy_pairs,
+            _ => return Vec::new(),
+        };
+        map.get(value).cloned().unwrap_or_default()
+    }
+}
+
+impl Default for CompatMatrix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The concrete stage selections for one pipeline run -- checked pairwise
+/// against [`CompatMatrix`]'s three compatibility maps by
+/// [`CompatMatrix::validate_pipeline`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineConfig {
+    pub gin: String,
+    pub gout: String,
+    pub policy: String,
+    pub spec: String,
+    pub pack: String,
+}
+
+/// One incompatible adjacent pair found by [`CompatMatrix::validate_pipeline`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncompatibleEdge {
+    /// Name of the edge, e.g. `"gin->gout"`.
+    pub edge: String,
+    /// The stage value with no compatible counterpart among `allowed`.
+    pub from: String,
+    /// The incompatible counterpart the pipeline actually selected.
+    pub to: String,
+    /// Every counterpart that is compatible with `from`.
+    pub allowed: Vec<String>,
+}
+
+/// Result of [`CompatMatrix::validate_pipeline`]: empty `edges` means every
+/// adjacent pair in the pipeline is compatible.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineValidation {
+    pub edges: Vec<IncompatibleEdge>,
+}
+
+impl PipelineValidation {
+    pub fn is_valid(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+fn check_edge(
+    map: &HashMap<String, Vec<String>>,
+    edge: &str,
+    from: &str,
+    to: &str,
+    edges: &mut Vec<IncompatibleEdge>,
+) {
+    let allowed = map.get(from).cloned().unwrap_or_default();
+    if !allowed.iter().any(|a| a == to) {
+        edges.push(IncompatibleEdge {
+            edge: edge.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            allowed,
+        });
+    }
+}
+
+/// Errors [`CompatMatrix::validate`] reports for a registered entry set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompatError {
+    /// A `compatible_with` entry names an id with no registered entry.
+    UnknownEntry(String),
+    /// An entry's own `semver` failed to parse.
+    InvalidVersion { id: String, source: String },
+    /// A `compatible_with` range failed to parse.
+    InvalidRange { from: String, to: String, range: String, source: String },
+    /// `to`'s registered version doesn't satisfy the range `from` declares for it.
+    Unsatisfiable { from: String, to: String, range: String, actual: String },
+    /// The `compatible_with` reference graph loops back on itself; `path`
+    /// is the cycle, starting and ending at the same id.
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for CompatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompatError::UnknownEntry(id) => write!(f, "compatible_with references unregistered entry {id:?}"),
+            CompatError::InvalidVersion { id, source } => write!(f, "entry {id:?} has an invalid semver: {source}"),
+            CompatError::InvalidRange { from, to, range, source } => {
+                write!(f, "{from:?}'s compatible_with range {range:?} for {to:?} is invalid: {source}")
+            }
+            CompatError::Unsatisfiable { from, to, range, actual } => write!(
+                f,
+                "{from:?} requires {to:?} to satisfy {range:?}, but its registered version is {actual:?}"
+            ),
+            CompatError::Cycle(path) => write!(f, "compatible_with cycle: {}", path.join(" -> ")),
+        }
+    }
+}
+
+impl std::error::Error for CompatError {}
+
+/// Depth-first cycle detection over the `compatible_with` reference graph:
+/// an id found already on the current DFS stack closes a cycle back to
+/// itself.
+fn detect_cycle(entries: &HashMap<String, RegistryEntry>) -> Result<(), CompatError> {
+    let mut visited = HashSet::new();
+    let mut stack = Vec::new();
+
+    for id in entries.keys() {
+        if !visited.contains(id) {
+            visit(id, entries, &mut visited, &mut stack)?;
+        }
+    }
+    Ok(())
+}
+
+fn visit(
+    id: &str,
+    entries: &HashMap<String, RegistryEntry>,
+    visited: &mut HashSet<String>,
+    stack: &mut Vec<String>,
+) -> Result<(), CompatError> {
+    if let Some(start) = stack.iter().position(|s| s == id) {
+        let mut cycle = stack[start..].to_vec();
+        cycle.push(id.to_string());
+        return Err(CompatError::Cycle(cycle));
+    }
+    if visited.contains(id) {
+        return Ok(());
+    }
+
+    stack.push(id.to_string());
+    if let Some(entry) = entries.get(id) {
+        for (target_id, _) in &entry.compatible_with {
+            visit(target_id, entries, visited, stack)?;
+        }
+    }
+    stack.pop();
+    visited.insert(id.to_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix() -> CompatMatrix {
+        let mut matrix = CompatMatrix::new();
+        matrix
+            .gin_gout_pairs
+            .insert("promptspec.in.v1".to_string(), vec!["korean.out.v1".to_string()]);
+        matrix
+            .policy_spec_pairs
+            .insert("strict.safety.v1".to_string(), vec!["ko.answer.v1".to_string()]);
+        matrix
+            .pack_policy_pairs
+            .insert("cpic.2025.10".to_string(), vec!["strict.safety.v1".to_string()]);
+        matrix
+    }
+
+    fn config() -> PipelineConfig {
+        PipelineConfig {
+            gin: "promptspec.in.v1".to_string(),
+            gout: "korean.out.v1".to_string(),
+            policy: "strict.safety.v1".to_string(),
+            spec: "ko.answer.v1".to_string(),
+            pack: "cpic.2025.10".to_string(),
+        }
+    }
+
+    #[test]
+    fn validate_pipeline_passes_a_fully_compatible_chain() {
+        let validation = matrix().validate_pipeline(&config());
+        assert!(validation.is_valid());
+    }
+
+    #[test]
+    fn validate_pipeline_reports_every_incompatible_edge() {
+        let mut broken = config();
+        broken.gout = "generic.out.v1".to_string();
+        broken.spec = "en.answer.v1".to_string();
+
+        let validation = matrix().validate_pipeline(&broken);
+        assert_eq!(validation.edges.len(), 2);
+
+        let gin_gout = validation.edges.iter().find(|e| e.edge == "gin->gout").unwrap();
+        assert_eq!(gin_gout.from, "promptspec.in.v1");
+        assert_eq!(gin_gout.to, "generic.out.v1");
+        assert_eq!(gin_gout.allowed, vec!["korean.out.v1".to_string()]);
+
+        let policy_spec = validation.edges.iter().find(|e| e.edge == "policy->spec").unwrap();
+        assert_eq!(policy_spec.to, "en.answer.v1");
+    }
+
+    #[test]
+    fn suggest_lists_compatible_counterparts() {
+        let matrix = matrix();
+        assert_eq!(matrix.suggest("gin", "promptspec.in.v1"), vec!["korean.out.v1".to_string()]);
+        assert_eq!(matrix.suggest("gin", "unknown"), Vec::<String>::new());
+        assert_eq!(matrix.suggest("not_a_stage", "promptspec.in.v1"), Vec::<String>::new());
+    }
+
+    fn entry(id: &str, semver: &str, compatible_with: &[(&str, &str)]) -> RegistryEntry {
+        RegistryEntry {
+            id: id.to_string(),
+            semver: semver.to_string(),
+            compatible_with: compatible_with.iter().map(|(id, range)| (id.to_string(), range.to_string())).collect(),
+        }
+    }
+
+    fn semver_matrix() -> CompatMatrix {
+        let mut matrix = CompatMatrix::new();
+        matrix.register(entry("pack-a", "1.2.0", &[("pack-b", "^1.0.0")]));
+        matrix.register(entry("pack-b", "1.4.0", &[]));
+        matrix
+    }
+
+    #[test]
+    fn is_compatible_checks_the_declared_range_against_the_actual_version() {
+        let matrix = semver_matrix();
+        assert!(matrix.is_compatible("pack-a", "pack-b"));
+        assert!(!matrix.is_compatible("pack-b", "pack-a")); // no range declared in this direction
+        assert!(!matrix.is_compatible("pack-a", "unknown"));
+    }
+
+    #[test]
+    fn resolve_returns_the_satisfying_version_for_every_declared_range() {
+        let matrix = semver_matrix();
+        assert_eq!(matrix.resolve("pack-a"), vec![("pack-b".to_string(), "1.4.0".to_string())]);
+        assert_eq!(matrix.resolve("pack-b"), Vec::<(String, String)>::new());
+        assert_eq!(matrix.resolve("unknown"), Vec::<(String, String)>::new());
+    }
+
+    #[test]
+    fn resolve_omits_a_target_whose_version_does_not_satisfy_the_range() {
+        let mut matrix = CompatMatrix::new();
+        matrix.register(entry("pack-a", "1.0.0", &[("pack-b", "^2.0.0")]));
+        matrix.register(entry("pack-b", "1.4.0", &[]));
+        assert!(matrix.resolve("pack-a").is_empty());
+    }
+
+    #[test]
+    fn validate_passes_a_consistent_registry() {
+        assert!(semver_matrix().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_reports_an_unknown_compatible_with_target() {
+        let mut matrix = CompatMatrix::new();
+        matrix.register(entry("pack-a", "1.0.0", &[("missing", "^1.0.0")]));
+        assert_eq!(matrix.validate(), Err(CompatError::UnknownEntry("missing".to_string())));
+    }
+
+    #[test]
+    fn validate_reports_an_unsatisfiable_range() {
+        let mut matrix = CompatMatrix::new();
+        matrix.register(entry("pack-a", "1.0.0", &[("pack-b", "^2.0.0")]));
+        matrix.register(entry("pack-b", "1.4.0", &[]));
+        match matrix.validate() {
+            Err(CompatError::Unsatisfiable { from, to, .. }) => {
+                assert_eq!(from, "pack-a");
+                assert_eq!(to, "pack-b");
+            }
+            other => panic!("expected Unsatisfiable, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn validate_reports_a_compatible_with_cycle() {
+        let mut matrix = CompatMatrix::new();
+        matrix.register(entry("pack-a", "1.0.0", &[("pack-b", "^1.0.0")]));
+        matrix.register(entry("pack-b", "1.0.0", &[("pack-a", "^1.0.0")]));
+        match matrix.validate() {
+            Err(CompatError::Cycle(path)) => {
+                assert_eq!(path.first(), path.last());
+            }
+            other => panic!("expected Cycle, got {other:?}"),
+        }
+    }
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 //! TDLN Registry: Compat Matrix e versionamento
 pub mod compat;
 pub mod grammar_registry;
+pub mod semver;
 
-pub use compat::CompatMatrix;
+pub use compat::{CompatError, CompatMatrix};
 pub use grammar_registry::GrammarRegistry;
 
 use serde::{Deserialize, Serialize};
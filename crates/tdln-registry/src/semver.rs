@@ -0,0 +1,294 @@
+//! Minimal Cargo-style semantic version parsing and range matching.
+//!
+//! Hand-rolls just enough of Cargo's comparator grammar (`^`, `~`, `>=`,
+//! `<`, `*`, and comma-separated intersections) to answer "does this
+//! concrete version satisfy this declared range" -- all [`crate::compat::CompatMatrix`]
+//! needs for [`crate::compat::CompatMatrix::is_compatible`] and
+//! [`crate::compat::CompatMatrix::resolve`].
+
+use std::fmt;
+
+/// A parsed `major.minor.patch` version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    /// Parse a strict `major.minor.patch` version (no pre-release or build
+    /// metadata -- the registry doesn't use either).
+    pub fn parse(s: &str) -> Result<Self, SemverError> {
+        let partial = PartialVersion::parse(s)?;
+        if partial.minor.is_none() || partial.patch.is_none() {
+            return Err(SemverError::InvalidVersion(s.to_string()));
+        }
+        Ok(partial.to_version())
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A version with some trailing components omitted, e.g. `1`, `1.2`, or
+/// `1.2.3` -- the form comparator bounds (`^1.2`, `~1`) are written in.
+/// Missing components default to `0` when filled into a concrete
+/// [`Version`], but are tracked separately here because *which* components
+/// were given changes where a caret/tilde range's upper bound falls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PartialVersion {
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+}
+
+impl PartialVersion {
+    fn parse(s: &str) -> Result<Self, SemverError> {
+        let mut parts = s.split('.');
+        let invalid = || SemverError::InvalidVersion(s.to_string());
+
+        let major = parts.next().ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+        let minor = match parts.next() {
+            Some(m) => Some(m.parse().map_err(|_| invalid())?),
+            None => None,
+        };
+        let patch = match parts.next() {
+            Some(p) => Some(p.parse().map_err(|_| invalid())?),
+            None => None,
+        };
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(PartialVersion { major, minor, patch })
+    }
+
+    fn to_version(self) -> Version {
+        Version { major: self.major, minor: self.minor.unwrap_or(0), patch: self.patch.unwrap_or(0) }
+    }
+}
+
+/// A single comparator term within a [`VersionReq`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Comparator {
+    Exact(Version),
+    Gte(Version),
+    Gt(Version),
+    Lte(Version),
+    Lt(Version),
+    /// `^1.2.3`-style: compatible up to (not including) the next change
+    /// that Cargo's caret rules consider breaking.
+    Caret(PartialVersion),
+    /// `~1.2.3`-style: compatible up to (not including) the next change at
+    /// the most specific component given.
+    Tilde(PartialVersion),
+    /// `*`, `1.*`, or `1.2.*`.
+    Wildcard { major: Option<u64>, minor: Option<u64> },
+}
+
+impl Comparator {
+    fn matches(&self, v: &Version) -> bool {
+        match self {
+            Comparator::Exact(req) => v == req,
+            Comparator::Gte(req) => v >= req,
+            Comparator::Gt(req) => v > req,
+            Comparator::Lte(req) => v <= req,
+            Comparator::Lt(req) => v < req,
+            Comparator::Caret(pv) => {
+                let (lower, upper) = caret_bounds(pv);
+                *v >= lower && *v < upper
+            }
+            Comparator::Tilde(pv) => {
+                let (lower, upper) = tilde_bounds(pv);
+                *v >= lower && *v < upper
+            }
+            Comparator::Wildcard { major, minor } => match (major, minor) {
+                (None, _) => true,
+                (Some(major), None) => v.major == *major,
+                (Some(major), Some(minor)) => v.major == *major && v.minor == *minor,
+            },
+        }
+    }
+}
+
+/// The `[lower, upper)` bounds of a caret range, per Cargo's table: the
+/// upper bound falls at the first component left-to-right (major, then
+/// minor, then patch) that is nonzero, bumped by one; an all-zero version
+/// only widens as far as the next major version.
+fn caret_bounds(pv: &PartialVersion) -> (Version, Version) {
+    let lower = pv.to_version();
+    let upper = if pv.major > 0 {
+        Version { major: pv.major + 1, minor: 0, patch: 0 }
+    } else if pv.minor.unwrap_or(0) > 0 {
+        Version { major: 0, minor: pv.minor.unwrap_or(0) + 1, patch: 0 }
+    } else if pv.minor.is_none() {
+        Version { major: 1, minor: 0, patch: 0 }
+    } else if pv.patch.unwrap_or(0) > 0 {
+        Version { major: 0, minor: 0, patch: pv.patch.unwrap_or(0) + 1 }
+    } else if pv.patch.is_none() {
+        Version { major: 0, minor: 1, patch: 0 }
+    } else {
+        Version { major: 0, minor: 0, patch: 1 }
+    };
+    (lower, upper)
+}
+
+/// The `[lower, upper)` bounds of a tilde range: the upper bound ignores
+/// the patch component entirely, landing one past whatever minor (or
+/// major, if minor was omitted) was given.
+fn tilde_bounds(pv: &PartialVersion) -> (Version, Version) {
+    let lower = pv.to_version();
+    let upper = match pv.minor {
+        Some(minor) => Version { major: pv.major, minor: minor + 1, patch: 0 },
+        None => Version { major: pv.major + 1, minor: 0, patch: 0 },
+    };
+    (lower, upper)
+}
+
+fn parse_comparator(term: &str) -> Result<Comparator, SemverError> {
+    let term = term.trim();
+    let invalid = || SemverError::InvalidRange(term.to_string());
+
+    if term == "*" {
+        return Ok(Comparator::Wildcard { major: None, minor: None });
+    }
+    if let Some(rest) = term.strip_prefix("^") {
+        return Ok(Comparator::Caret(PartialVersion::parse(rest)?));
+    }
+    if let Some(rest) = term.strip_prefix("~") {
+        return Ok(Comparator::Tilde(PartialVersion::parse(rest)?));
+    }
+    if let Some(rest) = term.strip_prefix(">=") {
+        return Ok(Comparator::Gte(PartialVersion::parse(rest)?.to_version()));
+    }
+    if let Some(rest) = term.strip_prefix("<=") {
+        return Ok(Comparator::Lte(PartialVersion::parse(rest)?.to_version()));
+    }
+    if let Some(rest) = term.strip_prefix(">") {
+        return Ok(Comparator::Gt(PartialVersion::parse(rest)?.to_version()));
+    }
+    if let Some(rest) = term.strip_prefix("<") {
+        return Ok(Comparator::Lt(PartialVersion::parse(rest)?.to_version()));
+    }
+    if let Some(rest) = term.strip_prefix("=") {
+        return Ok(Comparator::Exact(PartialVersion::parse(rest)?.to_version()));
+    }
+    if let Some(prefix) = term.strip_suffix(".*") {
+        let pv = PartialVersion::parse(prefix).map_err(|_| invalid())?;
+        return Ok(Comparator::Wildcard { major: Some(pv.major), minor: pv.minor });
+    }
+
+    // A bare version (no operator) defaults to caret, matching Cargo's
+    // behavior for plain `Cargo.toml` dependency version strings.
+    Ok(Comparator::Caret(PartialVersion::parse(term)?))
+}
+
+/// A version range: an intersection of comma-separated comparator terms,
+/// e.g. `">=1.2.0, <2.0.0"`. A version satisfies the range only if it
+/// satisfies every term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    pub fn parse(s: &str) -> Result<Self, SemverError> {
+        let comparators: Vec<Comparator> =
+            s.split(',').map(parse_comparator).collect::<Result<_, _>>()?;
+        if comparators.is_empty() {
+            return Err(SemverError::InvalidRange(s.to_string()));
+        }
+        Ok(VersionReq { comparators })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(version))
+    }
+}
+
+/// Errors from parsing a [`Version`] or [`VersionReq`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SemverError {
+    InvalidVersion(String),
+    InvalidRange(String),
+}
+
+impl fmt::Display for SemverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SemverError::InvalidVersion(v) => write!(f, "invalid version {v:?}"),
+            SemverError::InvalidRange(r) => write!(f, "invalid version range {r:?}"),
+        }
+    }
+}
+
+impl std::error::Error for SemverError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_strict_version() {
+        assert_eq!(Version::parse("1.2.3").unwrap(), Version { major: 1, minor: 2, patch: 3 });
+        assert!(Version::parse("1.2").is_err());
+        assert!(Version::parse("not-a-version").is_err());
+    }
+
+    #[test]
+    fn caret_range_allows_patch_and_minor_bumps_but_not_major() {
+        let req = VersionReq::parse("^1.2.3").unwrap();
+        assert!(req.matches(&Version::parse("1.2.3").unwrap()));
+        assert!(req.matches(&Version::parse("1.9.0").unwrap()));
+        assert!(!req.matches(&Version::parse("1.2.2").unwrap()));
+        assert!(!req.matches(&Version::parse("2.0.0").unwrap()));
+    }
+
+    #[test]
+    fn caret_range_on_a_pre_1_0_minor_only_allows_patch_bumps() {
+        let req = VersionReq::parse("^0.2.3").unwrap();
+        assert!(req.matches(&Version::parse("0.2.9").unwrap()));
+        assert!(!req.matches(&Version::parse("0.3.0").unwrap()));
+    }
+
+    #[test]
+    fn tilde_range_allows_only_patch_bumps() {
+        let req = VersionReq::parse("~1.2.3").unwrap();
+        assert!(req.matches(&Version::parse("1.2.9").unwrap()));
+        assert!(!req.matches(&Version::parse("1.3.0").unwrap()));
+    }
+
+    #[test]
+    fn wildcard_range_pins_major_and_minor() {
+        let req = VersionReq::parse("1.2.*").unwrap();
+        assert!(req.matches(&Version::parse("1.2.0").unwrap()));
+        assert!(req.matches(&Version::parse("1.2.99").unwrap()));
+        assert!(!req.matches(&Version::parse("1.3.0").unwrap()));
+    }
+
+    #[test]
+    fn bare_star_matches_everything() {
+        let req = VersionReq::parse("*").unwrap();
+        assert!(req.matches(&Version::parse("0.0.1").unwrap()));
+        assert!(req.matches(&Version::parse("99.99.99").unwrap()));
+    }
+
+    #[test]
+    fn comma_separated_terms_intersect() {
+        let req = VersionReq::parse(">=1.2.0, <1.5.0").unwrap();
+        assert!(req.matches(&Version::parse("1.4.0").unwrap()));
+        assert!(!req.matches(&Version::parse("1.5.0").unwrap()));
+        assert!(!req.matches(&Version::parse("1.1.0").unwrap()));
+    }
+
+    #[test]
+    fn a_bare_version_defaults_to_caret() {
+        let req = VersionReq::parse("1.2.3").unwrap();
+        assert!(req.matches(&Version::parse("1.9.9").unwrap()));
+        assert!(!req.matches(&Version::parse("2.0.0").unwrap()));
+    }
+}
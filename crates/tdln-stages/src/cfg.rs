@@ -0,0 +1,403 @@
+//! A tiny boolean predicate language for [`crate::Pipeline::add_stage_if`],
+//! in the spirit of `cfg(...)` expression matching for platform targets: a
+//! guard like `"mode == mechanic && files_changed > 5"` is parsed once
+//! into an [`Expr`] and evaluated against the pipeline's flowing `Value`
+//! context before each guarded stage runs. That lets a single pipeline
+//! vary which stages execute by runtime mode/metrics instead of requiring
+//! a separate builder function (`standard_pipeline` vs `mechanic_pipeline`)
+//! per combination.
+
+use serde_json::Value;
+use std::fmt;
+
+/// A parsed guard expression. Build one with [`Expr::parse`], evaluate it
+/// against a context with [`Expr::eval`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    /// A bare key, true when the context value at `key` is present and
+    /// "truthy" (a non-`false` boolean, a non-zero number, or a
+    /// non-empty string other than `"false"`).
+    Truthy(String),
+    Compare(String, CompareOp, Literal),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+}
+
+/// An error parsing a guard expression, reported with the offending
+/// source so a bad guard string is easy to track back to its call site.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError(String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cfg expression error: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Expr {
+    /// Parse a guard expression, e.g. `"mode == mechanic && files_changed > 5"`.
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(ParseError(format!(
+                "unexpected trailing input starting at token {}",
+                parser.pos
+            )));
+        }
+        Ok(expr)
+    }
+
+    /// Evaluate this expression against `context`, an object mapping keys
+    /// to the values a guard can compare against. Looking up a key in a
+    /// non-object `context`, or a key the object doesn't have, always
+    /// evaluates as "missing" (falsy / never equal).
+    pub fn eval(&self, context: &Value) -> bool {
+        match self {
+            Expr::And(lhs, rhs) => lhs.eval(context) && rhs.eval(context),
+            Expr::Or(lhs, rhs) => lhs.eval(context) || rhs.eval(context),
+            Expr::Not(inner) => !inner.eval(context),
+            Expr::Truthy(key) => is_truthy(context.get(key)),
+            Expr::Compare(key, op, literal) => compare(context.get(key), *op, literal),
+        }
+    }
+}
+
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(Value::Number(n)) => n.as_f64().map(|f| f != 0.0).unwrap_or(false),
+        Some(Value::String(s)) => !s.is_empty() && s != "false",
+        Some(Value::Array(a)) => !a.is_empty(),
+        Some(Value::Object(o)) => !o.is_empty(),
+    }
+}
+
+fn compare(value: Option<&Value>, op: CompareOp, literal: &Literal) -> bool {
+    let ordering = match (value, literal) {
+        (Some(Value::Number(n)), Literal::Num(rhs)) => n.as_f64().and_then(|lhs| lhs.partial_cmp(rhs)),
+        (Some(Value::Bool(lhs)), Literal::Bool(rhs)) => Some(lhs.cmp(rhs)),
+        // Any other pairing (string vs. string, or a type mismatch like
+        // comparing a number field to a quoted literal) falls back to
+        // comparing the stringified forms -- good enough for the
+        // mode/flag-style guards this language targets.
+        (Some(v), rhs) => Some(value_as_str(v).cmp(&literal_as_str(rhs))),
+        (None, _) => None,
+    };
+
+    match (op, ordering) {
+        (CompareOp::Eq, ord) => ord == Some(std::cmp::Ordering::Equal),
+        (CompareOp::Ne, ord) => ord != Some(std::cmp::Ordering::Equal),
+        (CompareOp::Lt, Some(ord)) => ord == std::cmp::Ordering::Less,
+        (CompareOp::Le, Some(ord)) => ord != std::cmp::Ordering::Greater,
+        (CompareOp::Gt, Some(ord)) => ord == std::cmp::Ordering::Greater,
+        (CompareOp::Ge, Some(ord)) => ord != std::cmp::Ordering::Less,
+        (_, None) => false,
+    }
+}
+
+fn value_as_str(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn literal_as_str(literal: &Literal) -> String {
+    match literal {
+        Literal::Str(s) => s.clone(),
+        Literal::Num(n) if n.fract() == 0.0 => format!("{}", *n as i64),
+        Literal::Num(n) => n.to_string(),
+        Literal::Bool(b) => b.to_string(),
+    }
+}
+
+// ============================================================================
+// TOKENIZER
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    AndAnd,
+    OrOr,
+    Bang,
+    EqEq,
+    NotEq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    LParen,
+    RParen,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if c == '&' && chars.get(i + 1) == Some(&'&') {
+            tokens.push(Token::AndAnd);
+            i += 2;
+        } else if c == '|' && chars.get(i + 1) == Some(&'|') {
+            tokens.push(Token::OrOr);
+            i += 2;
+        } else if c == '=' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(Token::EqEq);
+            i += 2;
+        } else if c == '!' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(Token::NotEq);
+            i += 2;
+        } else if c == '!' {
+            tokens.push(Token::Bang);
+            i += 1;
+        } else if c == '>' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(Token::Ge);
+            i += 2;
+        } else if c == '<' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(Token::Le);
+            i += 2;
+        } else if c == '>' {
+            tokens.push(Token::Gt);
+            i += 1;
+        } else if c == '<' {
+            tokens.push(Token::Lt);
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let quote = c;
+            let mut value = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != quote {
+                value.push(chars[i]);
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(ParseError(format!("unterminated string starting at {quote}")));
+            }
+            i += 1; // closing quote
+            tokens.push(Token::Str(value));
+        } else if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let num = text
+                .parse::<f64>()
+                .map_err(|_| ParseError(format!("invalid number literal {text:?}")))?;
+            tokens.push(Token::Num(num));
+        } else if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.' || chars[i] == '-')
+            {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            return Err(ParseError(format!("unexpected character {c:?}")));
+        }
+    }
+
+    Ok(tokens)
+}
+
+// ============================================================================
+// RECURSIVE-DESCENT PARSER
+// ============================================================================
+//
+// Grammar (lowest to highest precedence):
+//   or_expr    := and_expr ( "||" and_expr )*
+//   and_expr   := unary ( "&&" unary )*
+//   unary      := "!" unary | comparison
+//   comparison := primary ( cmp_op primary )?
+//   primary    := "(" or_expr ")" | ident | literal
+//   cmp_op     := "==" | "!=" | "<" | "<=" | ">" | ">="
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        if tok.is_some() {
+            self.pos += 1;
+        }
+        tok
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_and()?;
+        while matches!(self.peek(), Some(Token::OrOr)) {
+            self.bump();
+            let rhs = self.parse_and()?;
+            expr = Expr::Or(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_unary()?;
+        while matches!(self.peek(), Some(Token::AndAnd)) {
+            self.bump();
+            let rhs = self.parse_unary()?;
+            expr = Expr::And(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        if matches!(self.peek(), Some(Token::Bang)) {
+            self.bump();
+            let inner = self.parse_unary()?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, ParseError> {
+        if matches!(self.peek(), Some(Token::LParen)) {
+            self.bump();
+            let expr = self.parse_or()?;
+            match self.bump() {
+                Some(Token::RParen) => return Ok(expr),
+                other => return Err(ParseError(format!("expected ')', found {other:?}"))),
+            }
+        }
+
+        let key = match self.bump() {
+            Some(Token::Ident(name)) => name,
+            other => return Err(ParseError(format!("expected an identifier, found {other:?}"))),
+        };
+
+        let op = match self.peek() {
+            Some(Token::EqEq) => Some(CompareOp::Eq),
+            Some(Token::NotEq) => Some(CompareOp::Ne),
+            Some(Token::Lt) => Some(CompareOp::Lt),
+            Some(Token::Le) => Some(CompareOp::Le),
+            Some(Token::Gt) => Some(CompareOp::Gt),
+            Some(Token::Ge) => Some(CompareOp::Ge),
+            _ => None,
+        };
+
+        let Some(op) = op else {
+            return Ok(Expr::Truthy(key));
+        };
+        self.bump();
+
+        let literal = match self.bump() {
+            Some(Token::Ident(word)) => match word.as_str() {
+                "true" => Literal::Bool(true),
+                "false" => Literal::Bool(false),
+                _ => Literal::Str(word),
+            },
+            Some(Token::Str(text)) => Literal::Str(text),
+            Some(Token::Num(n)) => Literal::Num(n),
+            other => return Err(ParseError(format!("expected a value after comparison operator, found {other:?}"))),
+        };
+
+        Ok(Expr::Compare(key, op, literal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn equality_on_strings() {
+        let expr = Expr::parse("mode == mechanic").unwrap();
+        assert!(expr.eval(&json!({ "mode": "mechanic" })));
+        assert!(!expr.eval(&json!({ "mode": "genius" })));
+    }
+
+    #[test]
+    fn inequality_and_and() {
+        let expr = Expr::parse("mode == mechanic && files_changed > 5").unwrap();
+        assert!(expr.eval(&json!({ "mode": "mechanic", "files_changed": 10 })));
+        assert!(!expr.eval(&json!({ "mode": "mechanic", "files_changed": 2 })));
+        assert!(!expr.eval(&json!({ "mode": "genius", "files_changed": 10 })));
+    }
+
+    #[test]
+    fn or_and_negation() {
+        let expr = Expr::parse("!(mode == mechanic) || files_changed >= 20").unwrap();
+        assert!(expr.eval(&json!({ "mode": "genius", "files_changed": 0 })));
+        assert!(expr.eval(&json!({ "mode": "mechanic", "files_changed": 20 })));
+        assert!(!expr.eval(&json!({ "mode": "mechanic", "files_changed": 1 })));
+    }
+
+    #[test]
+    fn bare_key_is_a_truthy_check() {
+        let expr = Expr::parse("require_tests").unwrap();
+        assert!(expr.eval(&json!({ "require_tests": true })));
+        assert!(!expr.eval(&json!({ "require_tests": false })));
+        assert!(!expr.eval(&json!({})));
+    }
+
+    #[test]
+    fn missing_key_is_never_equal() {
+        let expr = Expr::parse("mode == mechanic").unwrap();
+        assert!(!expr.eval(&json!({})));
+    }
+
+    #[test]
+    fn not_equal_operator() {
+        let expr = Expr::parse("mode != mechanic").unwrap();
+        assert!(expr.eval(&json!({ "mode": "genius" })));
+        assert!(!expr.eval(&json!({ "mode": "mechanic" })));
+    }
+
+    #[test]
+    fn rejects_trailing_garbage() {
+        assert!(Expr::parse("mode == mechanic )").is_err());
+    }
+
+    #[test]
+    fn rejects_unterminated_string() {
+        assert!(Expr::parse("mode == \"mechanic").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Structured, span-annotated stage errors and policy violations.
+//!
+//! `PolicyStage` used to push violations as formatted `String`s and
+//! [`SimpleStage::execute`](crate::SimpleStage::execute) returned
+//! `Box<dyn Error>`, so downstream stages and renderers had no way to
+//! reason about what failed or where -- only a sentence meant for a
+//! terminal. [`Violation`] and [`StageError`] give that structure back,
+//! and [`SourceSpan`] anchors a violation or matched slot to a byte range
+//! into the original natural-language input the way a compiler anchors a
+//! diagnostic to `Location { line, col }`; `render_markdown`/`render_plain`
+//! use it to draw a caret under the offending text instead of just naming it.
+
+use serde::{Deserialize, Serialize};
+
+/// A byte range into the original natural-language input. Intents here are
+/// single-line asks, so a `[start, end)` byte range is enough to draw a
+/// caret -- no need for the `line`/`col` pair a multi-line compiler
+/// diagnostic would carry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl SourceSpan {
+    pub fn new(start: usize, end: usize) -> Self {
+        SourceSpan { start, end }
+    }
+
+    /// Find the first occurrence of `needle` in `haystack` and span it.
+    /// Returns `None` if `needle` doesn't occur, so callers fall back to an
+    /// unanchored diagnostic rather than guessing a span.
+    pub fn find(haystack: &str, needle: &str) -> Option<Self> {
+        haystack.find(needle).map(|start| SourceSpan::new(start, start + needle.len()))
+    }
+}
+
+/// What kind of policy constraint a [`Violation`] reports, with the
+/// `expected`/`found` values that triggered it -- the same shape rustc
+/// uses for "expected X, found Y" diagnostics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ViolationKind {
+    MaxFilesExceeded { found: usize, expected: usize },
+    MaxLinesExceeded { found: usize, expected: usize },
+    MissingRequiredTests,
+}
+
+impl ViolationKind {
+    /// Render the same sentence the old `format!`-built strings used, so
+    /// existing consumers of the message text see no change.
+    pub fn message(&self) -> String {
+        match self {
+            Self::MaxFilesExceeded { found, expected } => {
+                format!("Files changed ({found}) exceeds max ({expected})")
+            }
+            Self::MaxLinesExceeded { found, expected } => {
+                format!("Lines changed ({found}) exceeds max ({expected})")
+            }
+            Self::MissingRequiredTests => "Required tests were not included".to_string(),
+        }
+    }
+}
+
+/// A single policy violation, optionally anchored to the part of the
+/// original input that caused it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Violation {
+    #[serde(flatten)]
+    pub kind: ViolationKind,
+    pub location: Option<SourceSpan>,
+}
+
+impl Violation {
+    pub fn new(kind: ViolationKind) -> Self {
+        Violation { kind, location: None }
+    }
+
+    pub fn at(kind: ViolationKind, location: SourceSpan) -> Self {
+        Violation { kind, location: Some(location) }
+    }
+
+    pub fn message(&self) -> String {
+        self.kind.message()
+    }
+}
+
+/// Errors a [`SimpleStage`](crate::SimpleStage) can fail with. Mirrors
+/// `tdln_core::StageError`'s shape (a small enum with a `Display`/`Error`
+/// impl) for this crate's simplified stage trait.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StageError {
+    /// One or more policy violations blocked execution outright (as
+    /// opposed to `PolicyStage`, which reports violations in its output
+    /// and lets the pipeline continue).
+    PolicyViolation(Vec<Violation>),
+    /// Any other stage-specific failure.
+    ExecutionFailed(String),
+}
+
+impl std::fmt::Display for StageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::PolicyViolation(violations) => {
+                write!(f, "POLICY/VIOLATION: ")?;
+                let messages: Vec<String> = violations.iter().map(Violation::message).collect();
+                write!(f, "{}", messages.join("; "))
+            }
+            Self::ExecutionFailed(msg) => write!(f, "STAGE/EXEC: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for StageError {}
+
+impl From<&str> for StageError {
+    fn from(msg: &str) -> Self {
+        StageError::ExecutionFailed(msg.to_string())
+    }
+}
+
+impl From<String> for StageError {
+    fn from(msg: String) -> Self {
+        StageError::ExecutionFailed(msg)
+    }
+}
+
+impl From<serde_json::Error> for StageError {
+    fn from(err: serde_json::Error) -> Self {
+        StageError::ExecutionFailed(err.to_string())
+    }
+}
+
+/// Render a violation as a compiler-style diagnostic: the message, plus --
+/// when `location` is set -- the offending line from `text` with a caret
+/// underneath it. Falls back to just the message when there's no span, or
+/// when the span doesn't land inside `text`.
+pub fn render_violation_diagnostic(violation: &Violation, text: &str) -> String {
+    let Some(location) = violation.location else {
+        return violation.message();
+    };
+    if location.end > text.len() || location.start > location.end {
+        return violation.message();
+    }
+
+    let caret_line = " ".repeat(location.start) + &"^".repeat((location.end - location.start).max(1));
+    format!("{}\n  {}\n  {}", violation.message(), text, caret_line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn violation_message_matches_legacy_wording() {
+        let v = Violation::new(ViolationKind::MaxFilesExceeded { found: 10, expected: 5 });
+        assert_eq!(v.message(), "Files changed (10) exceeds max (5)");
+    }
+
+    #[test]
+    fn source_span_find_locates_the_needle() {
+        let span = SourceSpan::find("fix bug in auth.ts", "bug").unwrap();
+        assert_eq!(span, SourceSpan::new(4, 7));
+    }
+
+    #[test]
+    fn source_span_find_is_none_when_absent() {
+        assert!(SourceSpan::find("fix bug in auth.ts", "xyz").is_none());
+    }
+
+    #[test]
+    fn render_violation_diagnostic_draws_a_caret_when_located() {
+        let v = Violation::at(
+            ViolationKind::MaxFilesExceeded { found: 10, expected: 5 },
+            SourceSpan::new(4, 7),
+        );
+        let rendered = render_violation_diagnostic(&v, "fix bug in auth.ts");
+        assert!(rendered.contains("fix bug in auth.ts"));
+        assert!(rendered.contains("    ^^^"));
+    }
+
+    #[test]
+    fn render_violation_diagnostic_falls_back_without_location() {
+        let v = Violation::new(ViolationKind::MissingRequiredTests);
+        assert_eq!(render_violation_diagnostic(&v, "fix bug in auth.ts"), v.message());
+    }
+}
@@ -12,15 +12,26 @@
 //!       LogLine    Validated  Scored    Rendered
 //! ```
 
+pub mod cfg;
+mod diagnostics;
 mod parse_promptspec;
+mod policy_gate;
 mod policy_pass;
 mod render_generic;
+mod watch;
 
+pub use diagnostics::{render_violation_diagnostic, SourceSpan, StageError, Violation, ViolationKind};
 pub use parse_promptspec::ParsePromptspecStage;
+pub use policy_gate::PolicyGateStage;
 pub use policy_pass::PolicyPassStage;
 pub use render_generic::RenderGenericStage;
+pub use watch::{WatchSnapshot, DEFAULT_DEBOUNCE};
 
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::error::Error;
 
 // ============================================================================
@@ -32,49 +43,228 @@ use std::error::Error;
 pub trait SimpleStage: Send + Sync {
     /// Stage name
     fn name(&self) -> &str;
-    
+
     /// Execute the stage
-    fn execute(&self, input: Value) -> Result<Value, Box<dyn Error>>;
+    fn execute(&self, input: Value) -> Result<Value, StageError>;
+}
+
+/// A node in a [`Pipeline`]'s dependency graph: a stage plus the names of
+/// the nodes it reads from. See [`Pipeline::add_node`] and
+/// [`Pipeline::run_dag`].
+struct Node {
+    name: String,
+    stage: Box<dyn SimpleStage>,
+    depends_on: Vec<String>,
+}
+
+/// A stage in the linear `stages` chain plus an optional [`cfg::Expr`]
+/// guard: when present, [`Pipeline::run`] evaluates it against the
+/// flowing context and skips the stage entirely (passing the current
+/// value through unchanged) when it's false. See [`Pipeline::add_stage_if`].
+struct GuardedStage {
+    guard: Option<cfg::Expr>,
+    stage: Box<dyn SimpleStage>,
 }
 
 /// Pipeline orchestrator
 pub struct Pipeline {
-    stages: Vec<Box<dyn SimpleStage>>,
+    stages: Vec<GuardedStage>,
+    nodes: Vec<Node>,
+    shuffle_seed: Option<u64>,
 }
 
 impl Pipeline {
     /// Create an empty pipeline
     pub fn new() -> Self {
-        Pipeline { stages: Vec::new() }
+        Pipeline { stages: Vec::new(), nodes: Vec::new(), shuffle_seed: None }
     }
-    
-    /// Add a stage to the pipeline
+
+    /// Add a stage to the pipeline, always run.
     pub fn add_stage(mut self, stage: Box<dyn SimpleStage>) -> Self {
-        self.stages.push(stage);
+        self.stages.push(GuardedStage { guard: None, stage });
         self
     }
-    
+
+    /// Add a stage guarded by a [`cfg`]-style boolean expression over the
+    /// pipeline's flowing context, e.g. `"mode == mechanic && files_changed > 5"`.
+    /// [`Pipeline::run`] evaluates `guard` against the current value before
+    /// calling the stage, skipping it (context passes through unchanged)
+    /// when the guard is false. This is what lets one pipeline's
+    /// `PolicyStage` variant and extra stages switch on runtime mode and
+    /// metrics instead of requiring a separate builder function per
+    /// combination (see [`adaptive_pipeline`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `guard` fails to parse -- guards are written once at
+    /// pipeline-construction time, so a bad expression is a programmer
+    /// error, not a runtime condition to handle.
+    pub fn add_stage_if(mut self, guard: &str, stage: Box<dyn SimpleStage>) -> Self {
+        let expr = cfg::Expr::parse(guard).unwrap_or_else(|e| panic!("invalid stage guard {guard:?}: {e}"));
+        self.stages.push(GuardedStage { guard: Some(expr), stage });
+        self
+    }
+
     /// Run the pipeline
     pub fn run(&self, input: Value) -> Result<Value, Box<dyn Error>> {
         let mut current = input;
-        
-        for stage in &self.stages {
-            println!("[Pipeline] Running stage: {}", stage.name());
-            current = stage.execute(current)?;
+
+        for guarded in &self.stages {
+            if let Some(guard) = &guarded.guard {
+                if !guard.eval(&current) {
+                    continue;
+                }
+            }
+            println!("[Pipeline] Running stage: {}", guarded.stage.name());
+            current = guarded.stage.execute(current)?;
         }
-        
+
         Ok(current)
     }
-    
+
     /// Get stage count
     pub fn len(&self) -> usize {
         self.stages.len()
     }
-    
+
     /// Check if pipeline is empty
     pub fn is_empty(&self) -> bool {
         self.stages.is_empty()
     }
+
+    /// Add a named node to the pipeline's dependency graph, run separately
+    /// from the linear `stages` chain via [`Pipeline::run_dag`]. `name`
+    /// must be unique and `depends_on` must name other nodes already or
+    /// later added to this pipeline.
+    pub fn add_node(mut self, name: &str, stage: Box<dyn SimpleStage>, depends_on: &[&str]) -> Self {
+        self.nodes.push(Node {
+            name: name.to_string(),
+            stage,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+        });
+        self
+    }
+
+    /// Shuffle the execution order of mutually-independent nodes within
+    /// each level of [`Pipeline::run_dag`], seeded by `seed` so the order
+    /// is reproducible across runs. Mirrors how test runners shuffle
+    /// independent tests to surface ordering bugs instead of hiding them
+    /// behind whatever order happened to run first.
+    pub fn with_shuffle_seed(mut self, seed: u64) -> Self {
+        self.shuffle_seed = Some(seed);
+        self
+    }
+
+    /// Run the node graph added via [`Pipeline::add_node`] instead of the
+    /// linear `stages` chain.
+    ///
+    /// Nodes are grouped into dependency levels (Kahn's algorithm); within
+    /// a level every node is independent of every other, so they execute
+    /// concurrently on their own thread via `std::thread::scope`, joining
+    /// into a shared context keyed by node name. Each node's `execute`
+    /// input is a JSON object holding the pipeline's original `input` plus
+    /// one key per declared dependency, populated with that dependency's
+    /// output. The first error encountered (by node order within its
+    /// level) aborts the run -- later levels never start.
+    pub fn run_dag(&self, input: Value) -> Result<HashMap<String, Value>, Box<dyn Error>> {
+        let levels = self.toposort_levels()?;
+        let mut context: HashMap<String, Value> = HashMap::new();
+        context.insert("input".to_string(), input);
+
+        for (level_index, mut ready) in levels.into_iter().enumerate() {
+            if let Some(seed) = self.shuffle_seed {
+                let mut rng = SmallRng::seed_from_u64(seed ^ level_index as u64);
+                ready.shuffle(&mut rng);
+            }
+
+            let snapshot = &context;
+            let results: Vec<(String, Result<Value, String>)> = std::thread::scope(|scope| {
+                let handles: Vec<_> = ready
+                    .iter()
+                    .map(|&idx| {
+                        let node = &self.nodes[idx];
+                        let node_input = build_node_input(snapshot, node);
+                        scope.spawn(move || {
+                            println!("[Pipeline] Running node: {}", node.name);
+                            (node.name.clone(), node.stage.execute(node_input).map_err(|e| e.to_string()))
+                        })
+                    })
+                    .collect();
+                handles.into_iter().map(|h| h.join().expect("pipeline node panicked")).collect()
+            });
+
+            for (name, result) in results {
+                context.insert(name, result?);
+            }
+        }
+
+        Ok(context)
+    }
+
+    /// Group `nodes` into dependency levels via Kahn's algorithm: level 0
+    /// holds every node with no dependencies, level 1 holds every node
+    /// whose dependencies are all in level 0, and so on. Errors if a
+    /// node depends on an unknown name or the graph has a cycle.
+    fn toposort_levels(&self) -> Result<Vec<Vec<usize>>, Box<dyn Error>> {
+        let index_by_name: HashMap<&str, usize> =
+            self.nodes.iter().enumerate().map(|(i, n)| (n.name.as_str(), i)).collect();
+
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); self.nodes.len()];
+        let mut remaining: Vec<usize> = vec![0; self.nodes.len()];
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            for dep in &node.depends_on {
+                let &dep_idx = index_by_name
+                    .get(dep.as_str())
+                    .ok_or_else(|| format!("node '{}' depends on unknown node '{}'", node.name, dep))?;
+                dependents[dep_idx].push(i);
+                remaining[i] += 1;
+            }
+        }
+
+        let mut done = vec![false; self.nodes.len()];
+        let mut levels = Vec::new();
+        let mut processed = 0;
+
+        loop {
+            let ready: Vec<usize> = (0..self.nodes.len()).filter(|&i| !done[i] && remaining[i] == 0).collect();
+            if ready.is_empty() {
+                break;
+            }
+            for &i in &ready {
+                done[i] = true;
+                processed += 1;
+            }
+            for &i in &ready {
+                for &dependent in &dependents[i] {
+                    remaining[dependent] -= 1;
+                }
+            }
+            levels.push(ready);
+        }
+
+        if processed != self.nodes.len() {
+            return Err("pipeline dependency graph has a cycle".into());
+        }
+
+        Ok(levels)
+    }
+}
+
+/// Build a node's input: the pipeline's original input under `"input"`,
+/// plus one key per dependency the node declared, holding that
+/// dependency's already-computed output.
+fn build_node_input(context: &HashMap<String, Value>, node: &Node) -> Value {
+    let mut fields = serde_json::Map::new();
+    if let Some(input) = context.get("input") {
+        fields.insert("input".to_string(), input.clone());
+    }
+    for dep in &node.depends_on {
+        if let Some(value) = context.get(dep) {
+            fields.insert(dep.clone(), value.clone());
+        }
+    }
+    Value::Object(fields)
 }
 
 impl Default for Pipeline {
@@ -95,32 +285,51 @@ impl SimpleStage for TdlnInStage {
         "tdln-in"
     }
     
-    fn execute(&self, input: Value) -> Result<Value, Box<dyn Error>> {
+    fn execute(&self, input: Value) -> Result<Value, StageError> {
         // Extract text from input
         let text = input.get("text")
             .and_then(|v| v.as_str())
             .unwrap_or("");
-        
+
         // Simple intent classification (placeholder)
-        let intent = if text.contains("fix") || text.contains("bug") {
-            "bug_fix"
-        } else if text.contains("add") || text.contains("implement") {
-            "feature"
-        } else if text.contains("refactor") {
-            "refactor"
+        let (intent, keyword) = if let Some(kw) = ["fix", "bug"].iter().find(|kw| text.contains(**kw)) {
+            ("bug_fix", Some(*kw))
+        } else if let Some(kw) = ["add", "implement"].iter().find(|kw| text.contains(**kw)) {
+            ("feature", Some(*kw))
+        } else if let Some(kw) = ["refactor"].iter().find(|kw| text.contains(**kw)) {
+            ("refactor", Some(*kw))
         } else {
-            "unknown"
+            ("unknown", None)
         };
-        
-        Ok(serde_json::json!({
+
+        // Attach the source span of the keyword that drove the
+        // classification, so downstream renderers can point at it the way
+        // a grammar match would point at its matched slot.
+        let location = keyword.and_then(|kw| SourceSpan::find(text, kw));
+
+        let mut output = serde_json::json!({
             "verdict": "Match",
             "span": {
                 "name": intent,
                 "text": text,
+                "location": location,
             },
             "confidence": 0.9,
             "original": input,
-        }))
+        });
+
+        // Forward the fields downstream `cfg` guards and `PolicyStage`
+        // care about to the top level of this stage's own output, so a
+        // guard like `add_stage_if("mode == mechanic", ...)` or
+        // `PolicyStage` reading `files_changed` still sees them after
+        // they'd otherwise be buried under `original`.
+        for key in ["mode", "files_changed", "lines_changed"] {
+            if let Some(value) = output["original"].get(key).cloned() {
+                output[key] = value;
+            }
+        }
+
+        Ok(output)
     }
 }
 
@@ -164,36 +373,36 @@ impl SimpleStage for PolicyStage {
         "policy"
     }
     
-    fn execute(&self, input: Value) -> Result<Value, Box<dyn Error>> {
+    fn execute(&self, input: Value) -> Result<Value, StageError> {
         // Extract metrics from input
         let files_changed = input.get("files_changed")
             .and_then(|v| v.as_u64())
             .unwrap_or(0) as usize;
-        
+
         let lines_changed = input.get("lines_changed")
             .and_then(|v| v.as_u64())
             .unwrap_or(0) as usize;
-        
+
         // Validate against constraints
-        let mut violations: Vec<String> = Vec::new();
-        
+        let mut violations: Vec<Violation> = Vec::new();
+
         if files_changed > self.max_files {
-            violations.push(format!(
-                "Files changed ({}) exceeds max ({})",
-                files_changed, self.max_files
-            ));
+            violations.push(Violation::new(ViolationKind::MaxFilesExceeded {
+                found: files_changed,
+                expected: self.max_files,
+            }));
         }
-        
+
         if lines_changed > self.max_lines {
-            violations.push(format!(
-                "Lines changed ({}) exceeds max ({})",
-                lines_changed, self.max_lines
-            ));
+            violations.push(Violation::new(ViolationKind::MaxLinesExceeded {
+                found: lines_changed,
+                expected: self.max_lines,
+            }));
         }
-        
+
         let passed = violations.is_empty();
-        
-        Ok(serde_json::json!({
+
+        let mut output = serde_json::json!({
             "passed": passed,
             "violations": violations,
             "constraints": {
@@ -202,7 +411,19 @@ impl SimpleStage for PolicyStage {
                 "require_tests": self.require_tests,
             },
             "input": input,
-        }))
+        });
+
+        // Forward the same context fields `TdlnInStage` forwards, so a
+        // `cfg` guard on a stage downstream of `PolicyStage` still sees
+        // `mode`/`files_changed`/`lines_changed` at the top level instead
+        // of buried under `input`.
+        for key in ["mode", "files_changed", "lines_changed"] {
+            if let Some(value) = output["input"].get(key).cloned() {
+                output[key] = value;
+            }
+        }
+
+        Ok(output)
     }
 }
 
@@ -214,7 +435,7 @@ impl SimpleStage for QualityStage {
         "quality"
     }
     
-    fn execute(&self, input: Value) -> Result<Value, Box<dyn Error>> {
+    fn execute(&self, input: Value) -> Result<Value, StageError> {
         // Extract evaluation if present
         let correctness = input.get("correctness")
             .and_then(|v| v.as_f64())
@@ -235,8 +456,8 @@ impl SimpleStage for QualityStage {
         let overall = (correctness + efficiency + honesty + safety) / 4.0;
         
         let quality_gate = overall >= 0.7;
-        
-        Ok(serde_json::json!({
+
+        let mut output = serde_json::json!({
             "scores": {
                 "correctness": correctness,
                 "efficiency": efficiency,
@@ -246,7 +467,18 @@ impl SimpleStage for QualityStage {
             },
             "quality_gate_passed": quality_gate,
             "input": input,
-        }))
+        });
+
+        // Same forwarding `PolicyStage`/`TdlnInStage` do, so a `cfg` guard
+        // on a stage after this one (e.g. an extra review pass gated on
+        // `files_changed`) still sees the metric fields at the top level.
+        for key in ["mode", "files_changed", "lines_changed"] {
+            if let Some(value) = output["input"].get(key).cloned() {
+                output[key] = value;
+            }
+        }
+
+        Ok(output)
     }
 }
 
@@ -289,7 +521,7 @@ impl SimpleStage for TdlnOutStage {
         "tdln-out"
     }
     
-    fn execute(&self, input: Value) -> Result<Value, Box<dyn Error>> {
+    fn execute(&self, input: Value) -> Result<Value, StageError> {
         let rendered = match self.format {
             OutputFormat::Json => serde_json::to_string_pretty(&input)?,
             OutputFormat::Markdown => render_markdown(&input),
@@ -310,27 +542,80 @@ impl SimpleStage for TdlnOutStage {
 
 fn render_markdown(value: &Value) -> String {
     let mut output = String::new();
-    
-    if let Some(span) = value.get("span") {
+
+    if let Some(span) = find_nested(value, "span") {
         output.push_str(&format!("## {}\n\n", span.get("name").and_then(|v| v.as_str()).unwrap_or("Result")));
         if let Some(text) = span.get("text").and_then(|v| v.as_str()) {
             output.push_str(&format!("{}\n\n", text));
         }
     }
-    
-    if let Some(scores) = value.get("scores") {
+
+    if let Some(scores) = find_nested(value, "scores") {
         output.push_str("### Quality Scores\n\n");
         output.push_str(&format!("- Correctness: {:.0}%\n", scores.get("correctness").and_then(|v| v.as_f64()).unwrap_or(0.0) * 100.0));
         output.push_str(&format!("- Efficiency: {:.0}%\n", scores.get("efficiency").and_then(|v| v.as_f64()).unwrap_or(0.0) * 100.0));
         output.push_str(&format!("- Honesty: {:.0}%\n", scores.get("honesty").and_then(|v| v.as_f64()).unwrap_or(0.0) * 100.0));
         output.push_str(&format!("- Safety: {:.0}%\n", scores.get("safety").and_then(|v| v.as_f64()).unwrap_or(0.0) * 100.0));
     }
-    
+
+    if let Some(diagnostics) = render_violations(value, "```\n", "\n```\n\n") {
+        output.push_str("### Policy Violations\n\n");
+        output.push_str(&diagnostics);
+    }
+
     output
 }
 
 fn render_plain(value: &Value) -> String {
-    serde_json::to_string(value).unwrap_or_default()
+    let mut output = serde_json::to_string(value).unwrap_or_default();
+
+    if let Some(diagnostics) = render_violations(value, "", "\n") {
+        output.push('\n');
+        output.push_str(&diagnostics);
+    }
+
+    output
+}
+
+/// Render every [`Violation`] found nested in `value` as a compiler-style
+/// diagnostic (see [`render_violation_diagnostic`]), anchored to whatever
+/// natural-language `text` is also nested in `value`. Each rendered
+/// diagnostic is wrapped in `prefix`/`suffix` so markdown can fence it as a
+/// code block while plain text leaves it bare. Returns `None` when `value`
+/// carries no (or no non-empty) `violations` array.
+fn render_violations(value: &Value, prefix: &str, suffix: &str) -> Option<String> {
+    let violations = find_nested(value, "violations")?.as_array()?;
+    if violations.is_empty() {
+        return None;
+    }
+
+    let text = find_nested(value, "text").and_then(|v| v.as_str()).unwrap_or("");
+
+    let mut output = String::new();
+    for raw in violations {
+        match serde_json::from_value::<Violation>(raw.clone()) {
+            Ok(violation) => {
+                output.push_str(prefix);
+                output.push_str(&render_violation_diagnostic(&violation, text));
+                output.push_str(suffix);
+            }
+            Err(_) => output.push_str(&format!("- {}\n", raw)),
+        }
+    }
+    Some(output)
+}
+
+/// Depth-first search for the first object field named `key` anywhere in
+/// `value`. Stages nest each earlier stage's output under its own (see
+/// `PolicyStage`/`QualityStage`), so by the time `TdlnOutStage` renders the
+/// final value, a field set early in the chain -- `span`, `text`,
+/// `violations` -- is buried a level or two deep rather than sitting at
+/// the top.
+fn find_nested<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => map.get(key).or_else(|| map.values().find_map(|v| find_nested(v, key))),
+        _ => None,
+    }
 }
 
 // ============================================================================
@@ -364,6 +649,26 @@ pub fn genius_pipeline() -> Pipeline {
         .add_stage(Box::new(TdlnOutStage::default()))
 }
 
+/// A single pipeline whose `PolicyStage` variant and extra quality pass
+/// switch on the runtime `mode`/`files_changed` context instead of
+/// requiring a separate builder per combination -- what
+/// [`standard_pipeline`]/[`mechanic_pipeline`]/[`genius_pipeline`] above
+/// would collapse into if `cfg`-style guards existed from the start. The
+/// three `PolicyStage` guards partition on `mode` (unset or anything
+/// other than `"mechanic"`/`"genius"` falls through to the default), and
+/// a change large enough to warrant a second look gets a second
+/// `QualityStage` pass.
+pub fn adaptive_pipeline() -> Pipeline {
+    Pipeline::new()
+        .add_stage(Box::new(TdlnInStage))
+        .add_stage_if("mode == mechanic", Box::new(PolicyStage::mechanic_mode()))
+        .add_stage_if("mode == genius", Box::new(PolicyStage::genius_mode()))
+        .add_stage_if("mode != mechanic && mode != genius", Box::new(PolicyStage::default()))
+        .add_stage(Box::new(QualityStage))
+        .add_stage_if("files_changed > 10", Box::new(QualityStage))
+        .add_stage(Box::new(TdlnOutStage::default()))
+}
+
 /// Convenience helper to load the default trio of stages in the order
 /// `parse → policy → render`.
 pub fn default_stages() -> Vec<Box<dyn tdln_core::Stage>> {
@@ -433,7 +738,99 @@ mod tests {
         assert_eq!(output.get("passed").unwrap(), false);
         assert!(!output.get("violations").unwrap().as_array().unwrap().is_empty());
     }
-    
+
+    #[test]
+    fn test_policy_stage_emits_structured_violations() {
+        let stage = PolicyStage::mechanic_mode();
+
+        let input = json!({
+            "files_changed": 10,
+            "lines_changed": 500,
+        });
+
+        let output = stage.execute(input).unwrap();
+        let violations: Vec<Violation> =
+            serde_json::from_value(output.get("violations").unwrap().clone()).unwrap();
+
+        assert_eq!(
+            violations,
+            vec![
+                Violation::new(ViolationKind::MaxFilesExceeded { found: 10, expected: 5 }),
+                Violation::new(ViolationKind::MaxLinesExceeded { found: 500, expected: 200 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_markdown_renders_violations_as_diagnostics() {
+        let value = json!({
+            "text": "fix bug in auth.ts",
+            "violations": [
+                Violation::at(
+                    ViolationKind::MaxFilesExceeded { found: 10, expected: 5 },
+                    SourceSpan::new(4, 7),
+                ),
+            ],
+        });
+
+        let rendered = render_markdown(&value);
+        assert!(rendered.contains("### Policy Violations"));
+        assert!(rendered.contains("Files changed (10) exceeds max (5)"));
+        assert!(rendered.contains("^^^"));
+    }
+
+    #[test]
+    fn render_markdown_omits_violations_section_when_none() {
+        let value = json!({ "violations": [] });
+        assert!(!render_markdown(&value).contains("Policy Violations"));
+    }
+
+    #[test]
+    fn add_stage_if_skips_the_stage_when_the_guard_is_false() {
+        let pipeline = Pipeline::new()
+            .add_stage_if("mode == mechanic", Box::new(EchoStage { name: "guarded".to_string() }));
+
+        let output = pipeline.run(json!({ "mode": "genius" })).unwrap();
+        assert_eq!(output, json!({ "mode": "genius" }));
+    }
+
+    #[test]
+    fn add_stage_if_runs_the_stage_when_the_guard_is_true() {
+        let pipeline = Pipeline::new()
+            .add_stage_if("mode == mechanic", Box::new(EchoStage { name: "guarded".to_string() }));
+
+        let output = pipeline.run(json!({ "mode": "mechanic" })).unwrap();
+        assert_eq!(output["ran"], "guarded");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid stage guard")]
+    fn add_stage_if_panics_on_an_unparsable_guard() {
+        Pipeline::new().add_stage_if("mode ==", Box::new(EchoStage { name: "guarded".to_string() }));
+    }
+
+    #[test]
+    fn adaptive_pipeline_selects_the_policy_variant_matching_mode() {
+        let pipeline = adaptive_pipeline();
+
+        let output = pipeline
+            .run(json!({ "text": "fix bug in auth.ts", "mode": "mechanic", "files_changed": 10, "lines_changed": 500 }))
+            .unwrap();
+
+        assert_eq!(find_nested(&output, "passed").unwrap(), false);
+    }
+
+    #[test]
+    fn adaptive_pipeline_runs_an_extra_quality_pass_for_large_changes() {
+        let pipeline = adaptive_pipeline();
+
+        let output = pipeline
+            .run(json!({ "text": "fix bug in auth.ts", "files_changed": 20, "lines_changed": 50 }))
+            .unwrap();
+
+        assert!(output.get("rendered").is_some());
+    }
+
     #[test]
     fn test_quality_stage() {
         let stage = QualityStage;
@@ -451,4 +848,81 @@ mod tests {
         assert!(scores.get("overall").unwrap().as_f64().unwrap() > 0.8);
         assert_eq!(output.get("quality_gate_passed").unwrap(), true);
     }
+
+    struct EchoStage {
+        name: String,
+    }
+
+    impl SimpleStage for EchoStage {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn execute(&self, input: Value) -> Result<Value, StageError> {
+            Ok(json!({ "ran": self.name.clone(), "saw": input }))
+        }
+    }
+
+    struct FailingStage;
+
+    impl SimpleStage for FailingStage {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn execute(&self, _input: Value) -> Result<Value, StageError> {
+            Err("boom".into())
+        }
+    }
+
+    #[test]
+    fn test_run_dag_joins_independent_nodes_before_a_dependent_one() {
+        let pipeline = Pipeline::new()
+            .add_node("a", Box::new(EchoStage { name: "a".to_string() }), &[])
+            .add_node("b", Box::new(EchoStage { name: "b".to_string() }), &[])
+            .add_node("joined", Box::new(EchoStage { name: "joined".to_string() }), &["a", "b"]);
+
+        let context = pipeline.run_dag(json!({ "text": "hello" })).unwrap();
+
+        assert_eq!(context["a"]["ran"], "a");
+        assert_eq!(context["b"]["ran"], "b");
+        let joined_saw = &context["joined"]["saw"];
+        assert_eq!(joined_saw["a"]["ran"], "a");
+        assert_eq!(joined_saw["b"]["ran"], "b");
+        assert_eq!(joined_saw["input"]["text"], "hello");
+    }
+
+    #[test]
+    fn test_run_dag_fails_fast_on_a_node_error() {
+        let pipeline = Pipeline::new()
+            .add_node("ok", Box::new(EchoStage { name: "ok".to_string() }), &[])
+            .add_node("bad", Box::new(FailingStage), &[]);
+
+        assert!(pipeline.run_dag(json!({})).is_err());
+    }
+
+    #[test]
+    fn test_run_dag_rejects_unknown_dependency() {
+        let pipeline = Pipeline::new().add_node("a", Box::new(EchoStage { name: "a".to_string() }), &["missing"]);
+
+        assert!(pipeline.run_dag(json!({})).is_err());
+    }
+
+    #[test]
+    fn test_run_dag_with_shuffle_seed_is_reproducible() {
+        let build = || {
+            Pipeline::new()
+                .add_node("a", Box::new(EchoStage { name: "a".to_string() }), &[])
+                .add_node("b", Box::new(EchoStage { name: "b".to_string() }), &[])
+                .add_node("c", Box::new(EchoStage { name: "c".to_string() }), &[])
+                .with_shuffle_seed(42)
+        };
+
+        let first = build().run_dag(json!({})).unwrap();
+        let second = build().run_dag(json!({})).unwrap();
+
+        assert_eq!(first["a"], second["a"]);
+        assert_eq!(first["b"], second["b"]);
+        assert_eq!(first["c"], second["c"]);
+    }
 }
@@ -0,0 +1,270 @@
+//! Policy gate as a first-class pipeline stage.
+//!
+//! `PolicySet`/`FullEvaluation` (see `tdln_policy`) otherwise live entirely
+//! outside the compile pipeline. `PolicyGateStage` wraps a `PolicySet` so a
+//! policy decision is a hashable, replayable step like any other: its input
+//! and output both flow through `PipelineRunner`, and its `Stage::verdict`
+//! override feeds the evaluation's final verdict back into the run's
+//! `StageProof`.
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use tdln_core::data_model::QualityProof;
+use tdln_core::{Stage, StageError};
+use tdln_policy::{Actor, FullEvaluation, OperationMetrics, PolicySet, RuleContext, VerdictSeverity};
+
+static IN_SCHEMA: Lazy<Vec<u8>> = Lazy::new(|| include_bytes!("../schemas/policy_gate.in.json").to_vec());
+static OUT_SCHEMA: Lazy<Vec<u8>> = Lazy::new(|| include_bytes!("../schemas/policy_gate.out.json").to_vec());
+
+/// JSON input for [`PolicyGateStage`]: the `RuleContext`/`OperationMetrics`
+/// fields needed to run a `PolicySet::evaluate`, flattened into one
+/// document since neither source type implements `Deserialize`.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PolicyGateInput {
+    operation_type: String,
+    #[serde(default)]
+    mode: String,
+    #[serde(default)]
+    file_count: usize,
+    #[serde(default)]
+    line_count: usize,
+    #[serde(default)]
+    is_destructive: bool,
+    #[serde(default)]
+    targets_production: bool,
+    #[serde(default)]
+    tests_passed: Option<bool>,
+    #[serde(default)]
+    lint_passed: Option<bool>,
+    #[serde(default)]
+    has_confirmation: bool,
+    #[serde(default)]
+    affected_paths: Vec<String>,
+    #[serde(default)]
+    affected_files: Vec<String>,
+    #[serde(default)]
+    actor_id: Option<String>,
+    #[serde(default)]
+    actor_roles: Vec<String>,
+    #[serde(default)]
+    steps_used: u32,
+    #[serde(default)]
+    tokens_used: u32,
+    #[serde(default)]
+    time_ms: u64,
+    #[serde(default)]
+    reviewer_count: u32,
+}
+
+impl PolicyGateInput {
+    fn context(&self) -> RuleContext {
+        let mut ctx = RuleContext::new(self.operation_type.clone())
+            .with_files(self.file_count)
+            .with_lines(self.line_count)
+            .mode(self.mode.clone())
+            .with_affected_paths(self.affected_paths.clone());
+
+        if self.is_destructive {
+            ctx = ctx.destructive();
+        }
+        if self.targets_production {
+            ctx = ctx.production();
+        }
+        if let Some(passed) = self.tests_passed {
+            ctx = ctx.tests(passed);
+        }
+        if let Some(passed) = self.lint_passed {
+            ctx = ctx.lint(passed);
+        }
+        if self.has_confirmation {
+            ctx = ctx.confirmed();
+        }
+        if let Some(id) = &self.actor_id {
+            let mut actor = Actor::new(id.clone());
+            for role in &self.actor_roles {
+                actor = actor.with_role(role.clone());
+            }
+            ctx = ctx.with_actor(actor);
+        }
+        ctx
+    }
+
+    fn metrics(&self) -> OperationMetrics {
+        let mut metrics = OperationMetrics::new()
+            .with_files(self.file_count, self.affected_files.clone())
+            .with_lines(self.line_count)
+            .with_steps(self.steps_used)
+            .with_tokens(self.tokens_used)
+            .with_time(self.time_ms)
+            .operation(self.operation_type.clone());
+
+        if self.targets_production {
+            metrics = metrics.production();
+        }
+        if self.has_confirmation {
+            metrics = metrics.confirmed();
+        }
+        if let Some(passed) = self.tests_passed {
+            metrics = metrics.with_tests(passed);
+        }
+        if let Some(passed) = self.lint_passed {
+            metrics = metrics.with_lint(passed);
+        }
+        metrics.reviewer_count = self.reviewer_count;
+        metrics
+    }
+}
+
+/// Runs a `PolicySet` evaluation as a pipeline stage. `fail_fast` is
+/// whatever the wrapped `PolicySet` was built with -- `evaluate` already
+/// short-circuits rule evaluation on the first blocking violation when it's
+/// set, so the stage just forwards the policy's own setting.
+pub struct PolicyGateStage {
+    policy: PolicySet,
+}
+
+impl PolicyGateStage {
+    /// Wrap `policy` as a pipeline stage.
+    pub fn new(policy: PolicySet) -> Self {
+        Self { policy }
+    }
+
+    /// Map a `FullEvaluation`'s final verdict into this stage's
+    /// `StageProof::verdict` convention.
+    pub fn verdict_label(evaluation: &FullEvaluation) -> &'static str {
+        match evaluation.final_verdict.severity() {
+            VerdictSeverity::Allow => "OK",
+            VerdictSeverity::Warn => "WARN",
+            VerdictSeverity::Block => "BLOCK",
+        }
+    }
+
+    /// Build the `QualityProof` this evaluation contributes to the
+    /// pipeline's overall `Proof`: the risk score, and one check name per
+    /// distinct rule/constraint violation raised.
+    pub fn quality_proof(evaluation: &FullEvaluation) -> QualityProof {
+        let mut checks: Vec<String> =
+            evaluation.all_violations().iter().map(|v| v.rule_id.clone()).collect();
+        checks.sort();
+        checks.dedup();
+
+        QualityProof {
+            profile: evaluation.policy_id.clone(),
+            score: evaluation.risk_assessment.score,
+            status: Self::verdict_label(evaluation).to_string(),
+            checks,
+        }
+    }
+}
+
+impl Stage for PolicyGateStage {
+    fn id(&self) -> &'static str {
+        "policy.gate.v1"
+    }
+
+    fn in_schema(&self) -> &'static [u8] {
+        &IN_SCHEMA
+    }
+
+    fn out_schema(&self) -> &'static [u8] {
+        &OUT_SCHEMA
+    }
+
+    fn verdict(&self, output: &[u8]) -> Option<String> {
+        serde_json::from_slice::<FullEvaluation>(output)
+            .ok()
+            .map(|evaluation| Self::verdict_label(&evaluation).to_string())
+    }
+
+    fn run(
+        &self,
+        input: &[u8],
+        _ctx: &tdln_core::ExecutionContext,
+    ) -> Result<Vec<u8>, StageError> {
+        let parsed: PolicyGateInput =
+            serde_json::from_slice(input).map_err(|e| StageError::ValidationFailed(e.to_string()))?;
+
+        let evaluation = self.policy.evaluate(&parsed.context(), &parsed.metrics());
+
+        serde_json::to_vec(&evaluation).map_err(|e| StageError::ExecutionFailed(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdln_core::ExecutionContext;
+
+    fn ctx() -> ExecutionContext {
+        ExecutionContext::new("t".to_string(), "dev".to_string())
+    }
+
+    #[test]
+    fn allowed_operation_round_trips_as_ok() {
+        let stage = PolicyGateStage::new(PolicySet::mechanic());
+        let input = serde_json::json!({
+            "operation_type": "bug_fix",
+            "mode": "mechanic",
+            "file_count": 2,
+            "line_count": 50,
+            "tests_passed": true,
+            "lint_passed": true,
+        });
+
+        let output = stage.run(&serde_json::to_vec(&input).unwrap(), &ctx()).unwrap();
+        let evaluation: FullEvaluation = serde_json::from_slice(&output).unwrap();
+
+        assert!(evaluation.is_allowed());
+        assert_eq!(stage.verdict(&output).as_deref(), Some("OK"));
+    }
+
+    #[test]
+    fn blocked_operation_surfaces_block_verdict_and_checks() {
+        let stage = PolicyGateStage::new(PolicySet::mechanic());
+        let input = serde_json::json!({
+            "operation_type": "feature",
+            "mode": "mechanic",
+            "file_count": 20,
+            "line_count": 500,
+        });
+
+        let output = stage.run(&serde_json::to_vec(&input).unwrap(), &ctx()).unwrap();
+        let evaluation: FullEvaluation = serde_json::from_slice(&output).unwrap();
+
+        assert!(evaluation.is_blocked());
+        assert_eq!(stage.verdict(&output).as_deref(), Some("BLOCK"));
+
+        let quality = PolicyGateStage::quality_proof(&evaluation);
+        assert!(!quality.checks.is_empty());
+        assert_eq!(quality.status, "BLOCK");
+    }
+
+    #[test]
+    fn role_capability_reaches_the_stage_through_the_actor_fields() {
+        let policy = PolicySet::new("scoped@1.0", "Scoped Policy")
+            .with_constraints(tdln_policy::Constraints {
+                max_files: Some(5),
+                ..Default::default()
+            })
+            .with_role_capability(
+                "senior",
+                tdln_policy::Constraints {
+                    max_files: Some(20),
+                    ..Default::default()
+                },
+            );
+        let stage = PolicyGateStage::new(policy);
+
+        let input = serde_json::json!({
+            "operation_type": "feature",
+            "file_count": 10,
+            "actor_id": "alice",
+            "actor_roles": ["senior"],
+        });
+
+        let output = stage.run(&serde_json::to_vec(&input).unwrap(), &ctx()).unwrap();
+        let evaluation: FullEvaluation = serde_json::from_slice(&output).unwrap();
+
+        assert!(evaluation.is_allowed());
+    }
+}
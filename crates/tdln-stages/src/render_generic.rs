@@ -1,10 +1,36 @@
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use tdln_core::{Stage, StageError};
+use tdln_policy::Violation;
 
 static IN_SCHEMA: Lazy<Vec<u8>> = Lazy::new(|| include_bytes!("../schemas/policy.out.json").to_vec());
 static OUT_SCHEMA: Lazy<Vec<u8>> = Lazy::new(|| include_bytes!("../schemas/render.out.json").to_vec());
 
+/// How a [`RenderGenericStage`] verdict is turned into `message`.
+///
+/// Chosen per-call via [`RenderInput::reporter_format`] or, failing that,
+/// the `"reporter_format"` key in [`tdln_core::ExecutionContext::metadata`]
+/// -- the same fallback-to-context pattern other stages use for a setting
+/// that's usually fixed for a whole pipeline run rather than one input.
+/// Defaults to [`ReporterFormat::Pretty`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReporterFormat {
+    /// One character per violation/warning, for a fast-scrolling terminal.
+    Dot,
+    /// A human-readable report: each violation's rule, severity, location
+    /// and remediation, one per line.
+    Pretty,
+    /// The verdict as machine-readable JSON, for a CI gate to parse.
+    Json,
+}
+
+impl Default for ReporterFormat {
+    fn default() -> Self {
+        ReporterFormat::Pretty
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct RenderInput {
     allowed: bool,
@@ -12,6 +38,12 @@ struct RenderInput {
     normalized_goal: String,
     mode: String,
     constraints: Vec<String>,
+    #[serde(default)]
+    violations: Vec<Violation>,
+    #[serde(default)]
+    warnings: Vec<String>,
+    #[serde(default)]
+    reporter_format: Option<ReporterFormat>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +51,8 @@ struct RenderOutput {
     message: String,
     mode: String,
     constraints: Vec<String>,
+    violations: Vec<Violation>,
+    warnings: Vec<String>,
 }
 
 #[derive(Default)]
@@ -40,32 +74,107 @@ impl Stage for RenderGenericStage {
     fn run(
         &self,
         input: &[u8],
-        _ctx: &tdln_core::ExecutionContext,
+        ctx: &tdln_core::ExecutionContext,
     ) -> Result<Vec<u8>, StageError> {
         let parsed: RenderInput =
             serde_json::from_slice(input).map_err(|e| StageError::ValidationFailed(e.to_string()))?;
 
-        let message = if parsed.allowed {
-            format!(
-                "Planned '{}' in mode {} with {} constraints.",
-                parsed.normalized_goal,
-                parsed.mode,
-                parsed.constraints.len()
-            )
-        } else {
-            format!(
-                "Task '{}' blocked by policy: {}",
-                parsed.normalized_goal,
-                parsed.reason.unwrap_or_else(|| "unspecified".to_string())
-            )
-        };
+        let format = parsed.reporter_format.unwrap_or_else(|| format_from_context(ctx));
+        let message = render_report(format, &parsed);
 
         let output = RenderOutput {
             message,
             mode: parsed.mode,
             constraints: parsed.constraints,
+            violations: parsed.violations,
+            warnings: parsed.warnings,
         };
 
         serde_json::to_vec(&output).map_err(|e| StageError::ExecutionFailed(e.to_string()))
     }
 }
+
+/// Read `"reporter_format"` out of the context's free-form metadata bag. An
+/// unset or unrecognized value falls back to [`ReporterFormat::default`]
+/// rather than failing the stage -- the format only changes presentation,
+/// never whether the operation is allowed.
+fn format_from_context(ctx: &tdln_core::ExecutionContext) -> ReporterFormat {
+    match ctx.metadata.get("reporter_format").and_then(|v| v.as_str()) {
+        Some("dot") => ReporterFormat::Dot,
+        Some("json") => ReporterFormat::Json,
+        Some("pretty") => ReporterFormat::Pretty,
+        _ => ReporterFormat::default(),
+    }
+}
+
+fn render_report(format: ReporterFormat, input: &RenderInput) -> String {
+    match format {
+        ReporterFormat::Dot => render_dot(input),
+        ReporterFormat::Pretty => render_pretty(input),
+        ReporterFormat::Json => render_json(input),
+    }
+}
+
+/// One character per violation (`F`) and warning (`W`), or a single `.` when
+/// there's neither -- mirrors a test runner's dot-progress output, so a long
+/// batch of operations scrolls by as a compact pass/fail stream.
+fn render_dot(input: &RenderInput) -> String {
+    if input.violations.is_empty() && input.warnings.is_empty() {
+        return ".".to_string();
+    }
+    let mut dots = "F".repeat(input.violations.len());
+    dots.push_str(&"W".repeat(input.warnings.len()));
+    dots
+}
+
+/// The original one-sentence summary, followed by each violation's
+/// `rule_id`, `rule_name`, severity, location and remediation, then any
+/// plain warning strings -- the full detail `validate_constraints` computed
+/// but the old renderer dropped.
+fn render_pretty(input: &RenderInput) -> String {
+    let mut out = if input.allowed {
+        format!(
+            "Planned '{}' in mode {} with {} constraints.\n",
+            input.normalized_goal,
+            input.mode,
+            input.constraints.len()
+        )
+    } else {
+        format!(
+            "Task '{}' blocked by policy: {}\n",
+            input.normalized_goal,
+            input.reason.as_deref().unwrap_or("unspecified")
+        )
+    };
+
+    for violation in &input.violations {
+        out.push_str(&format!("  [{}] {} ({})", violation.rule_id, violation.rule_name, violation.severity));
+        if let Some(location) = &violation.location {
+            out.push_str(&format!(" at {}", location));
+        }
+        out.push('\n');
+        out.push_str(&format!("    {}\n", violation.description));
+        if let Some(remediation) = &violation.remediation {
+            out.push_str(&format!("    fix: {}\n", remediation));
+        }
+    }
+
+    for warning in &input.warnings {
+        out.push_str(&format!("  warning: {}\n", warning));
+    }
+
+    out
+}
+
+/// The verdict and its full violation/warning detail as a single JSON
+/// object, for a CI gate to parse instead of scraping `pretty` text.
+fn render_json(input: &RenderInput) -> String {
+    serde_json::json!({
+        "allowed": input.allowed,
+        "mode": input.mode,
+        "constraints": input.constraints,
+        "violations": input.violations,
+        "warnings": input.warnings,
+    })
+    .to_string()
+}
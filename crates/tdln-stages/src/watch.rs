@@ -0,0 +1,318 @@
+//! Watch mode for [`Pipeline`]: re-run on grammar or input fixture changes.
+//!
+//! This is the same ergonomic win a `--watch` subcommand gives test
+//! runners: grammar authors editing `coding-intents.yaml` get immediate
+//! round-trip feedback instead of re-invoking the pipeline by hand after
+//! every edit. File-watching here is deliberately poll-based rather than
+//! OS-notify-based -- this crate has no `notify` dependency, and a grammar
+//! file is edited by a human, not machine-written at microsecond rates, so
+//! sub-second polling is indistinguishable from instant.
+
+use crate::Pipeline;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long [`Pipeline::watch`] waits for the watched paths to stop
+/// changing before it re-runs, so a burst of saves from an editor
+/// (format-on-save, etc.) triggers one re-run instead of one per write.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Poll interval between mtime checks.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// One rolled-up observation of a watched re-run: the rendered output plus
+/// whatever confidence/merkle-root the pipeline's output happened to carry,
+/// so an operator editing a grammar can see drift across edits without
+/// re-deriving it from the raw JSON each time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchSnapshot {
+    pub rendered: String,
+    pub confidence: Option<f64>,
+    pub merkle_root: Option<String>,
+}
+
+impl Pipeline {
+    /// Watch `paths` (the grammar YAML plus any input fixture files) for
+    /// changes, re-running `self` against `input_provider()`'s current
+    /// output each time one changes and printing a diff of the rendered
+    /// output against the previous run. Keeps the last `history`
+    /// [`WatchSnapshot`]s (oldest first) so confidence/merkle-root drift
+    /// across several edits stays visible after the terminal scrolls.
+    ///
+    /// Runs an initial pass immediately, then loops until `should_stop`
+    /// returns `true` -- pass `|| false` to watch forever (e.g. under
+    /// Ctrl-C) or a counter/deadline closure in tests.
+    pub fn watch(
+        &self,
+        paths: &[PathBuf],
+        history: usize,
+        mut input_provider: impl FnMut() -> Value,
+        mut should_stop: impl FnMut() -> bool,
+    ) -> Result<Vec<WatchSnapshot>, Box<dyn Error>> {
+        let history = history.max(1);
+        let mut snapshots: VecDeque<WatchSnapshot> = VecDeque::with_capacity(history);
+        let mut mtimes = snapshot_mtimes(paths)?;
+
+        let first = self.run_and_snapshot(input_provider())?;
+        print_drift(&first, snapshots.back());
+        push_bounded(&mut snapshots, first, history);
+
+        while !should_stop() {
+            std::thread::sleep(POLL_INTERVAL);
+
+            let current = snapshot_mtimes(paths)?;
+            if current == mtimes {
+                continue;
+            }
+            mtimes = debounce_until_quiet(paths, current)?;
+
+            let snapshot = self.run_and_snapshot(input_provider())?;
+            print_drift(&snapshot, snapshots.back());
+            push_bounded(&mut snapshots, snapshot, history);
+        }
+
+        Ok(snapshots.into_iter().collect())
+    }
+
+    fn run_and_snapshot(&self, input: Value) -> Result<WatchSnapshot, Box<dyn Error>> {
+        let output = self.run(input)?;
+
+        let rendered = output
+            .get("rendered")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| output.to_string());
+        let confidence = find_f64(&output, "confidence");
+        let merkle_root = find_str(&output, "merkle_root");
+
+        Ok(WatchSnapshot { rendered, confidence, merkle_root })
+    }
+}
+
+/// Poll `paths` until their mtimes stop changing for [`DEFAULT_DEBOUNCE`],
+/// returning the final settled snapshot.
+fn debounce_until_quiet(
+    paths: &[PathBuf],
+    mut last: Vec<(PathBuf, SystemTime)>,
+) -> Result<Vec<(PathBuf, SystemTime)>, Box<dyn Error>> {
+    let mut stable_since = Instant::now();
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        let next = snapshot_mtimes(paths)?;
+        if next != last {
+            last = next;
+            stable_since = Instant::now();
+        } else if stable_since.elapsed() >= DEFAULT_DEBOUNCE {
+            return Ok(last);
+        }
+    }
+}
+
+fn snapshot_mtimes(paths: &[PathBuf]) -> Result<Vec<(PathBuf, SystemTime)>, Box<dyn Error>> {
+    paths
+        .iter()
+        .map(|p| Ok((p.clone(), fs::metadata(p)?.modified()?)))
+        .collect()
+}
+
+fn push_bounded(snapshots: &mut VecDeque<WatchSnapshot>, snapshot: WatchSnapshot, cap: usize) {
+    if snapshots.len() == cap {
+        snapshots.pop_front();
+    }
+    snapshots.push_back(snapshot);
+}
+
+/// Depth-first search for the first `key` anywhere in `value`, as an `f64`.
+/// The pipeline's stages nest each stage's input under its own output
+/// (see [`crate::QualityStage`]/[`crate::TdlnOutStage`]), so a field like
+/// `confidence` or `merkle_root` set early in the chain is buried a few
+/// levels deep by the time `watch` inspects the final output.
+fn find_f64(value: &Value, key: &str) -> Option<f64> {
+    find_field(value, key).and_then(|v| v.as_f64())
+}
+
+fn find_str(value: &Value, key: &str) -> Option<String> {
+    find_field(value, key).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+fn find_field<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => {
+            if let Some(found) = map.get(key) {
+                return Some(found);
+            }
+            map.values().find_map(|v| find_field(v, key))
+        }
+        _ => None,
+    }
+}
+
+/// Print a minimal line-based diff between the previous and current
+/// rendered output, plus the confidence/merkle-root the new run carries --
+/// enough for an operator to see what a grammar edit changed without
+/// reaching for a full diff tool.
+fn print_drift(snapshot: &WatchSnapshot, previous: Option<&WatchSnapshot>) {
+    match previous {
+        None => println!(
+            "[watch] initial run (confidence={:?}, merkle_root={:?})",
+            snapshot.confidence, snapshot.merkle_root
+        ),
+        Some(prev) if prev.rendered == snapshot.rendered => println!(
+            "[watch] re-ran, output unchanged (confidence={:?}, merkle_root={:?})",
+            snapshot.confidence, snapshot.merkle_root
+        ),
+        Some(prev) => {
+            println!(
+                "[watch] output changed (confidence={:?} -> {:?}, merkle_root={:?} -> {:?}):",
+                prev.confidence, snapshot.confidence, prev.merkle_root, snapshot.merkle_root
+            );
+            for line in diff_lines(&prev.rendered, &snapshot.rendered) {
+                println!("{line}");
+            }
+        }
+    }
+}
+
+/// A minimal line-oriented diff: lines only in `before` prefixed `-`, lines
+/// only in `after` prefixed `+`. Not an LCS diff -- good enough for the
+/// short rendered fixtures this mode targets, and keeps watch mode
+/// dependency-free.
+fn diff_lines(before: &str, after: &str) -> Vec<String> {
+    let before_lines: Vec<&str> = before.lines().collect();
+    let after_lines: Vec<&str> = after.lines().collect();
+
+    let mut output = Vec::new();
+    for line in &before_lines {
+        if !after_lines.contains(line) {
+            output.push(format!("-{line}"));
+        }
+    }
+    for line in &after_lines {
+        if !before_lines.contains(line) {
+            output.push(format!("+{line}"));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PolicyStage, QualityStage, TdlnInStage, TdlnOutStage};
+    use serde_json::json;
+    use std::thread;
+
+    fn fixture_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("tdln_stages_watch_{}_{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn watch_runs_once_with_no_changes_when_told_to_stop_immediately() {
+        let path = fixture_path("static");
+        fs::write(&path, "fix bug in auth.ts").unwrap();
+
+        let pipeline = Pipeline::new()
+            .add_stage(Box::new(TdlnInStage))
+            .add_stage(Box::new(PolicyStage::default()))
+            .add_stage(Box::new(QualityStage))
+            .add_stage(Box::new(TdlnOutStage::default()));
+
+        let snapshots = pipeline
+            .watch(std::slice::from_ref(&path), 5, || json!({ "text": "fix bug in auth.ts" }), || true)
+            .unwrap();
+
+        assert_eq!(snapshots.len(), 1);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn watch_reruns_and_reports_drift_after_a_file_changes() {
+        let path = fixture_path("changing");
+        fs::write(&path, "fix bug in auth.ts").unwrap();
+
+        let pipeline = Pipeline::new()
+            .add_stage(Box::new(TdlnInStage))
+            .add_stage(Box::new(PolicyStage::default()))
+            .add_stage(Box::new(QualityStage))
+            .add_stage(Box::new(TdlnOutStage::default()));
+
+        let mut calls = 0;
+        let path_for_edit = path.clone();
+        let snapshots = pipeline
+            .watch(
+                std::slice::from_ref(&path),
+                2,
+                move || {
+                    calls += 1;
+                    if calls == 1 {
+                        json!({ "text": "fix bug in auth.ts" })
+                    } else {
+                        json!({ "text": "add feature to auth.ts" })
+                    }
+                },
+                {
+                    let path_for_edit = path_for_edit.clone();
+                    let mut edited = false;
+                    move || {
+                        if !edited {
+                            thread::sleep(Duration::from_millis(50));
+                            fs::write(&path_for_edit, "add feature to auth.ts").unwrap();
+                            edited = true;
+                            false
+                        } else {
+                            true
+                        }
+                    }
+                },
+            )
+            .unwrap();
+
+        assert_eq!(snapshots.len(), 2);
+        assert_ne!(snapshots[0].rendered, snapshots[1].rendered);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn watch_history_is_bounded() {
+        let path = fixture_path("bounded");
+        fs::write(&path, "v0").unwrap();
+
+        let pipeline = Pipeline::new()
+            .add_stage(Box::new(TdlnInStage))
+            .add_stage(Box::new(TdlnOutStage::default()));
+
+        let mut calls = 0u32;
+        let path_for_edit = path.clone();
+        let snapshots = pipeline
+            .watch(
+                std::slice::from_ref(&path),
+                1,
+                move || {
+                    calls += 1;
+                    json!({ "text": format!("fix bug {calls}") })
+                },
+                {
+                    let path_for_edit = path_for_edit.clone();
+                    let mut edits = 0u32;
+                    move || {
+                        if edits < 2 {
+                            thread::sleep(Duration::from_millis(50));
+                            edits += 1;
+                            fs::write(&path_for_edit, format!("v{edits}")).unwrap();
+                            false
+                        } else {
+                            true
+                        }
+                    }
+                },
+            )
+            .unwrap();
+
+        assert_eq!(snapshots.len(), 1);
+        fs::remove_file(&path).ok();
+    }
+}
@@ -1,5 +1,6 @@
 //! Citation anchoring
 use serde::{Deserialize, Serialize};
+use tdln_core::provenance::{ContentHash, MerkleRoot, Side};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Citation {
@@ -23,4 +24,134 @@ impl Citation {
     pub fn validate_quote_length(&self) -> bool {
         self.quote.split_whitespace().count() <= 25
     }
+}
+
+/// A deterministic, verifiable collection of [`Citation`]s: sorted by
+/// `(source_id, location)` so the root doesn't depend on ingestion order,
+/// then committed to a Merkle root over blake3 leaf hashes via the shared
+/// [`tdln_core::provenance`] primitives -- the same tree construction both
+/// `TruthPack` types already use, rather than a third parallel
+/// implementation. Lets a downstream auditor confirm a quoted source was
+/// part of a given agent output ([`CitationSet::proof`]/[`verify`]) without
+/// re-rendering it.
+#[derive(Debug, Clone, Default)]
+pub struct CitationSet {
+    citations: Vec<Citation>,
+}
+
+impl CitationSet {
+    /// Ingest `citations`, sorted deterministically by `(source_id,
+    /// location)`. Rejects the whole batch -- rather than silently
+    /// dropping the offender -- if any citation's quote fails
+    /// [`Citation::validate_quote_length`].
+    pub fn build(mut citations: Vec<Citation>) -> Result<Self, String> {
+        if let Some(bad) = citations.iter().find(|c| !c.validate_quote_length()) {
+            return Err(format!(
+                "citation {}#{} exceeds the 25-word quote limit",
+                bad.source_id, bad.location
+            ));
+        }
+
+        citations.sort_by(|a, b| (&a.source_id, &a.location).cmp(&(&b.source_id, &b.location)));
+        Ok(Self { citations })
+    }
+
+    fn leaf_hashes(&self) -> Vec<ContentHash> {
+        self.citations.iter().map(leaf_hash).collect()
+    }
+
+    /// The Merkle root committing to every ingested citation.
+    pub fn merkle_root(&self) -> MerkleRoot {
+        MerkleRoot::from_leaves(self.leaf_hashes())
+    }
+
+    /// Build an inclusion proof that `citation` belongs to this set --
+    /// `None` if it isn't one of the citations [`CitationSet::build`]
+    /// ingested.
+    pub fn proof(&self, citation: &Citation) -> Option<Vec<(ContentHash, Side)>> {
+        let index = self.citations.iter().position(|c| {
+            c.source_id == citation.source_id && c.location == citation.location && c.quote == citation.quote
+        })?;
+        Some(MerkleRoot::prove(&self.leaf_hashes(), index))
+    }
+}
+
+/// Verify that `leaf` belongs under `root`, given an inclusion proof from
+/// [`CitationSet::proof`] -- doesn't need the whole [`CitationSet`], so an
+/// auditor can confirm a quoted source was part of a given output without
+/// re-rendering it.
+pub fn verify(leaf: &Citation, proof: &[(ContentHash, Side)], root: &MerkleRoot) -> bool {
+    MerkleRoot::verify(&leaf_hash(leaf), proof, root)
+}
+
+fn leaf_hash(citation: &Citation) -> ContentHash {
+    let preimage =
+        format!("{}:{}:{}:{}", citation.source_id, citation.location, citation.quote, citation.hash);
+    ContentHash::of(preimage.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn citation(source_id: &str, location: &str, quote: &str) -> Citation {
+        Citation::new(source_id.to_string(), location.to_string(), quote.to_string())
+    }
+
+    #[test]
+    fn merkle_root_is_independent_of_ingestion_order() {
+        let forward = CitationSet::build(vec![
+            citation("doc-a", "p1", "quote one"),
+            citation("doc-b", "p2", "quote two"),
+        ])
+        .unwrap();
+        let reversed = CitationSet::build(vec![
+            citation("doc-b", "p2", "quote two"),
+            citation("doc-a", "p1", "quote one"),
+        ])
+        .unwrap();
+
+        assert_eq!(forward.merkle_root(), reversed.merkle_root());
+    }
+
+    #[test]
+    fn build_rejects_a_quote_over_the_word_limit() {
+        let long_quote = (0..26).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let result = CitationSet::build(vec![citation("doc-a", "p1", &long_quote)]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn proof_and_verify_round_trip_for_every_citation() {
+        let citations = vec![
+            citation("doc-a", "p1", "quote one"),
+            citation("doc-b", "p2", "quote two"),
+            citation("doc-c", "p3", "quote three"),
+        ];
+        let set = CitationSet::build(citations.clone()).unwrap();
+        let root = set.merkle_root();
+
+        for c in &citations {
+            let proof = set.proof(c).unwrap();
+            assert!(verify(c, &proof, &root));
+        }
+    }
+
+    #[test]
+    fn proof_is_none_for_a_citation_never_ingested() {
+        let set = CitationSet::build(vec![citation("doc-a", "p1", "quote one")]).unwrap();
+        let stranger = citation("doc-z", "p9", "never seen");
+        assert!(set.proof(&stranger).is_none());
+    }
+
+    #[test]
+    fn verify_rejects_a_tampered_quote() {
+        let citations = vec![citation("doc-a", "p1", "quote one"), citation("doc-b", "p2", "quote two")];
+        let set = CitationSet::build(citations.clone()).unwrap();
+        let root = set.merkle_root();
+        let proof = set.proof(&citations[0]).unwrap();
+
+        let tampered = citation("doc-a", "p1", "quote ONE TAMPERED");
+        assert!(!verify(&tampered, &proof, &root));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,346 @@
+//! UCAN-style capability delegation for TruthPack seals.
+//!
+//! A seal is anchored by a chain of tokens: an issuer key grants a capability
+//! (e.g. `{ with: "truthpack:us", can: "seal" }`) to an audience key, and each
+//! token is signed (Ed25519) over a payload that references the content
+//! address (`parent_cid`) of the token before it. Verifying a seal means
+//! walking the chain from its trusted root down to the key that actually
+//! signed the pack, checking signatures, link continuity, and that every
+//! hop only narrows (never widens) the capability it was handed.
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::pack::TruthPack;
+
+/// A capability grant: a resource pattern (`with`) and an action (`can`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capability {
+    pub with: String,
+    pub can: String,
+}
+
+impl Capability {
+    pub fn new(with: impl Into<String>, can: impl Into<String>) -> Self {
+        Self {
+            with: with.into(),
+            can: can.into(),
+        }
+    }
+
+    /// `true` if `self` is equal to or strictly narrower than `parent`.
+    ///
+    /// The action must match exactly; the resource may only be refined by
+    /// appending a `:`-separated segment (`truthpack:us` → `truthpack:us:ca`),
+    /// never broadened or swapped for an unrelated resource.
+    fn attenuates(&self, parent: &Capability) -> bool {
+        self.can == parent.can
+            && (self.with == parent.with || self.with.starts_with(&format!("{}:", parent.with)))
+    }
+}
+
+/// One link in a delegation chain: `issuer` grants `capability` to `audience`,
+/// signing over the link and the content address of its parent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationToken {
+    /// Hex-encoded Ed25519 public key of the granting party.
+    pub issuer: String,
+    /// Hex-encoded Ed25519 public key of the party receiving the capability.
+    pub audience: String,
+    pub capability: Capability,
+    /// Content address (blake3 hex) of the parent token, or `None` for the root.
+    pub parent_cid: Option<String>,
+    /// Hex-encoded Ed25519 signature over this token's signing payload.
+    pub signature: String,
+}
+
+impl DelegationToken {
+    fn signing_payload(issuer: &str, audience: &str, capability: &Capability, parent_cid: Option<&str>) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(issuer.as_bytes());
+        buf.extend_from_slice(audience.as_bytes());
+        buf.extend_from_slice(capability.with.as_bytes());
+        buf.extend_from_slice(capability.can.as_bytes());
+        buf.extend_from_slice(parent_cid.unwrap_or("").as_bytes());
+        buf
+    }
+
+    /// Content address of this token: `blake3(signing payload ‖ signature)` as hex.
+    pub fn cid(&self) -> String {
+        let mut buf = Self::signing_payload(
+            &self.issuer,
+            &self.audience,
+            &self.capability,
+            self.parent_cid.as_deref(),
+        );
+        buf.extend_from_slice(self.signature.as_bytes());
+        encode_hex(blake3::hash(&buf).as_bytes())
+    }
+}
+
+/// A chain of delegations, ordered from the trusted root to the final
+/// audience key, plus the top-level signature it authorizes over the pack's
+/// Merkle root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Seal {
+    /// Hex-encoded Ed25519 signature over `pack.merkle_root`, made by the
+    /// holder of the chain's final `audience` key.
+    pub root_signature: String,
+    pub chain: Vec<DelegationToken>,
+}
+
+/// Reasons a seal can fail to verify, kept distinct so callers can tell a
+/// forged signature apart from a merely misconfigured or over-broad chain.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SealError {
+    #[error("bad signature: {0}")]
+    BadSignature(String),
+    #[error("broken delegation chain: {0}")]
+    BrokenChain(String),
+    #[error("capability widened instead of attenuated at hop {0}")]
+    OverBroadAttenuation(usize),
+    #[error("chain root is not a trusted key")]
+    UntrustedRoot,
+    #[error("delegated capability does not authorize this pack: {0}")]
+    CapabilityMismatch(String),
+}
+
+/// Issue one delegation token, signing it with the issuer's key.
+pub fn issue_token(
+    issuer_key: &SigningKey,
+    audience: &VerifyingKey,
+    capability: Capability,
+    parent: Option<&DelegationToken>,
+) -> DelegationToken {
+    let issuer = encode_hex(issuer_key.verifying_key().as_bytes());
+    let audience = encode_hex(audience.as_bytes());
+    let parent_cid = parent.map(|p| p.cid());
+
+    let payload = DelegationToken::signing_payload(&issuer, &audience, &capability, parent_cid.as_deref());
+    let signature = encode_hex(&issuer_key.sign(&payload).to_bytes());
+
+    DelegationToken {
+        issuer,
+        audience,
+        capability,
+        parent_cid,
+        signature,
+    }
+}
+
+/// Sign a pack's Merkle root with `signing_key`, anchoring it to `chain`.
+///
+/// `signing_key` must hold the private half of the final token's `audience`
+/// key (or be the sole root authority if `chain` is empty and the key is
+/// itself trusted).
+pub fn sign_pack(pack: &TruthPack, chain: Vec<DelegationToken>, signing_key: &SigningKey) -> Result<Seal, SealError> {
+    if let Some(last) = chain.last() {
+        let expected = encode_hex(signing_key.verifying_key().as_bytes());
+        if last.audience != expected {
+            return Err(SealError::BrokenChain(
+                "signing key does not match the chain's final audience".to_string(),
+            ));
+        }
+    }
+
+    let root_signature = encode_hex(&signing_key.sign(pack.merkle_root.as_bytes()).to_bytes());
+    Ok(Seal { root_signature, chain })
+}
+
+/// Verify that `seal` authorizes `pack`, walking its delegation chain down
+/// to the key that signed the Merkle root, and that the chain's terminal
+/// capability actually covers `truthpack:{pack.jurisdiction}`/`seal` --
+/// otherwise a chain could be internally well-formed and trusted-rooted yet
+/// delegate authority over an unrelated resource entirely.
+pub fn verify_seal(pack: &TruthPack, seal: &Seal, trusted_roots: &[VerifyingKey]) -> Result<(), SealError> {
+    for (i, token) in seal.chain.iter().enumerate() {
+        let issuer_vk = parse_verifying_key(&token.issuer)?;
+        let payload = DelegationToken::signing_payload(
+            &token.issuer,
+            &token.audience,
+            &token.capability,
+            token.parent_cid.as_deref(),
+        );
+        verify_signature(&issuer_vk, &payload, &token.signature)?;
+
+        if i == 0 {
+            if !trusted_roots.contains(&issuer_vk) {
+                return Err(SealError::UntrustedRoot);
+            }
+        } else {
+            let parent = &seal.chain[i - 1];
+            if token.parent_cid.as_deref() != Some(parent.cid()).as_deref() {
+                return Err(SealError::BrokenChain(format!("hop {} does not reference its parent", i)));
+            }
+            if token.issuer != parent.audience {
+                return Err(SealError::BrokenChain(format!(
+                    "hop {} issuer does not match parent audience",
+                    i
+                )));
+            }
+            if !token.capability.attenuates(&parent.capability) {
+                return Err(SealError::OverBroadAttenuation(i));
+            }
+        }
+    }
+
+    let signer_vk = match seal.chain.last() {
+        Some(last) => {
+            let required = Capability::new(format!("truthpack:{}", pack.jurisdiction), "seal");
+            if !required.attenuates(&last.capability) {
+                return Err(SealError::CapabilityMismatch(format!(
+                    "terminal capability {{ with: {:?}, can: {:?} }} does not cover seal on {:?}",
+                    last.capability.with, last.capability.can, required.with
+                )));
+            }
+            parse_verifying_key(&last.audience)?
+        }
+        None => {
+            // No delegation at all: the root signature must itself come
+            // directly from one of the trusted roots.
+            return trusted_roots
+                .iter()
+                .find(|root| verify_signature(root, pack.merkle_root.as_bytes(), &seal.root_signature).is_ok())
+                .map(|_| ())
+                .ok_or(SealError::UntrustedRoot);
+        }
+    };
+
+    verify_signature(&signer_vk, pack.merkle_root.as_bytes(), &seal.root_signature)
+}
+
+fn parse_verifying_key(hex_key: &str) -> Result<VerifyingKey, SealError> {
+    let bytes = decode_hex_32(hex_key).ok_or_else(|| SealError::BadSignature("malformed public key".to_string()))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| SealError::BadSignature(e.to_string()))
+}
+
+fn verify_signature(key: &VerifyingKey, payload: &[u8], signature_hex: &str) -> Result<(), SealError> {
+    let bytes = decode_hex_64(signature_hex).ok_or_else(|| SealError::BadSignature("malformed signature".to_string()))?;
+    let signature = Signature::from_bytes(&bytes);
+    key.verify(payload, &signature)
+        .map_err(|e| SealError::BadSignature(e.to_string()))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex_32(s: &str) -> Option<[u8; 32]> {
+    let v = decode_hex(s)?;
+    v.try_into().ok()
+}
+
+fn decode_hex_64(s: &str) -> Option<[u8; 64]> {
+    let v = decode_hex(s)?;
+    v.try_into().ok()
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    fn key_from_seed(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn sample_pack() -> TruthPack {
+        let mut pack = TruthPack::new("pack-1".to_string(), "1.0.0".to_string(), "us".to_string());
+        pack.compute_merkle_root();
+        pack
+    }
+
+    #[test]
+    fn root_can_seal_directly() {
+        let root = key_from_seed(1);
+        let pack = sample_pack();
+        let seal = sign_pack(&pack, Vec::new(), &root).unwrap();
+        assert!(verify_seal(&pack, &seal, &[root.verifying_key()]).is_ok());
+    }
+
+    #[test]
+    fn delegated_chain_verifies() {
+        let root = key_from_seed(1);
+        let mid = key_from_seed(2);
+        let leaf = key_from_seed(3);
+
+        let token1 = issue_token(&root, &mid.verifying_key(), Capability::new("truthpack:us", "seal"), None);
+        let token2 = issue_token(
+            &mid,
+            &leaf.verifying_key(),
+            Capability::new("truthpack:us:ca", "seal"),
+            Some(&token1),
+        );
+
+        let mut pack = TruthPack::new("pack-1".to_string(), "1.0.0".to_string(), "us:ca".to_string());
+        pack.compute_merkle_root();
+        let seal = sign_pack(&pack, vec![token1, token2], &leaf).unwrap();
+        assert!(verify_seal(&pack, &seal, &[root.verifying_key()]).is_ok());
+    }
+
+    #[test]
+    fn a_capability_for_an_unrelated_resource_does_not_authorize_sealing() {
+        let root = key_from_seed(1);
+        let leaf = key_from_seed(2);
+
+        let token = issue_token(&root, &leaf.verifying_key(), Capability::new("weather:oslo", "read"), None);
+
+        let pack = sample_pack();
+        let seal = sign_pack(&pack, vec![token], &leaf).unwrap();
+
+        assert!(matches!(
+            verify_seal(&pack, &seal, &[root.verifying_key()]),
+            Err(SealError::CapabilityMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn untrusted_root_is_rejected() {
+        let root = key_from_seed(1);
+        let other_root = key_from_seed(9);
+        let pack = sample_pack();
+        let seal = sign_pack(&pack, Vec::new(), &root).unwrap();
+        assert_eq!(
+            verify_seal(&pack, &seal, &[other_root.verifying_key()]),
+            Err(SealError::UntrustedRoot)
+        );
+    }
+
+    #[test]
+    fn over_broad_attenuation_is_rejected() {
+        let root = key_from_seed(1);
+        let mid = key_from_seed(2);
+        let leaf = key_from_seed(3);
+
+        let token1 = issue_token(&root, &mid.verifying_key(), Capability::new("truthpack:us", "seal"), None);
+        // `mid` legitimately delegates, but widens the resource instead of narrowing it.
+        let widened = issue_token(&mid, &leaf.verifying_key(), Capability::new("truthpack", "seal"), Some(&token1));
+
+        let pack = sample_pack();
+        let seal = sign_pack(&pack, vec![token1, widened], &leaf).unwrap();
+
+        assert_eq!(verify_seal(&pack, &seal, &[root.verifying_key()]), Err(SealError::OverBroadAttenuation(1)));
+    }
+
+    #[test]
+    fn tampered_signature_is_rejected() {
+        let root = key_from_seed(1);
+        let pack = sample_pack();
+        let mut seal = sign_pack(&pack, Vec::new(), &root).unwrap();
+        seal.root_signature = "00".repeat(64);
+        assert_eq!(
+            verify_seal(&pack, &seal, &[root.verifying_key()]),
+            Err(SealError::UntrustedRoot)
+        );
+    }
+}
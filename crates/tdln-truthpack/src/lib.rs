@@ -2,10 +2,12 @@
 pub mod pack;
 pub mod manifest;
 pub mod citation;
+pub mod delegation;
 
 pub use pack::TruthPack;
 pub use manifest::Manifest;
-pub use citation::Citation;
+pub use citation::{Citation, CitationSet};
+pub use delegation::{sign_pack, verify_seal, Capability, DelegationToken, Seal, SealError};
 
 use serde::{Deserialize, Serialize};
 
@@ -1,6 +1,9 @@
-//! TruthPack implementation
+//! TruthPack implementation: a jurisdiction/legal provenance pack whose
+//! sources, tables, and citations are committed to a single Merkle root
+//! built from the shared [`tdln_core::provenance`] primitives.
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use tdln_core::provenance::{ContentHash, MerkleRoot, Provenance, ProvenanceCitation};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TruthPack {
@@ -48,8 +51,130 @@ impl TruthPack {
         }
     }
 
+    /// Recompute `merkle_root` from the current `sources`/`tables`/`citations`,
+    /// one leaf per entry. Entries are sorted by a stable key first so the
+    /// root doesn't depend on `HashMap` iteration order.
     pub fn compute_merkle_root(&mut self) {
-        let data = serde_json::to_string(&self).unwrap_or_default();
-        self.merkle_root = format!("0x{}", blake3::hash(data.as_bytes()));
+        self.merkle_root = self.leaf_root().to_string();
+    }
+
+    /// Whether `merkle_root` actually matches the pack's current content.
+    pub fn verify(&self) -> bool {
+        self.merkle_root == self.leaf_root().to_string()
+    }
+
+    fn leaf_root(&self) -> MerkleRoot {
+        let mut leaves: Vec<(String, ContentHash)> = Vec::new();
+
+        for (key, source) in &self.sources {
+            let preimage = format!("source:{}:{}:{}:{}", key, source.content_hash, source.url, source.mime);
+            leaves.push((format!("source:{key}"), ContentHash::of(preimage.as_bytes())));
+        }
+        for (key, table) in &self.tables {
+            let rows = serde_json::to_string(&table.rows).unwrap_or_default();
+            let preimage = format!("table:{}:{}:{}:{}", key, table.source_id, table.location, rows);
+            leaves.push((format!("table:{key}"), ContentHash::of(preimage.as_bytes())));
+        }
+        for (index, citation) in self.citations.iter().enumerate() {
+            let preimage =
+                format!("citation:{}:{}:{}:{}", citation.source_id, citation.location, citation.quote, citation.hash);
+            leaves.push((format!("citation:{index}:{}", citation.source_id), ContentHash::of(preimage.as_bytes())));
+        }
+
+        leaves.sort_by(|a, b| a.0.cmp(&b.0));
+        MerkleRoot::from_leaves(leaves.into_iter().map(|(_, hash)| hash).collect())
+    }
+}
+
+impl Provenance for TruthPack {
+    fn merkle_root(&self) -> MerkleRoot {
+        ContentHash::parse(&self.merkle_root).map(MerkleRoot::new).unwrap_or_else(|| self.leaf_root())
+    }
+
+    fn verify(&self) -> bool {
+        TruthPack::verify(self)
+    }
+
+    fn citations(&self) -> Vec<ProvenanceCitation> {
+        self.citations
+            .iter()
+            .map(|c| ProvenanceCitation {
+                source_id: c.source_id.clone(),
+                location: c.location.clone(),
+                quote: c.quote.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pack_with_one_citation(quote: &str) -> TruthPack {
+        let mut pack = TruthPack::new("pack-1".to_string(), "1.0.0".to_string(), "US-CA".to_string());
+        pack.citations.push(PackCitation {
+            source_id: "doc-a".to_string(),
+            location: "p1".to_string(),
+            quote: quote.to_string(),
+            hash: format!("blake3:{}", blake3::hash(quote.as_bytes()).to_hex()),
+        });
+        pack.compute_merkle_root();
+        pack
+    }
+
+    #[test]
+    fn compute_merkle_root_is_independent_of_map_insertion_order() {
+        let mut forward = TruthPack::new("p".to_string(), "1.0.0".to_string(), "US-CA".to_string());
+        forward.sources.insert(
+            "a".to_string(),
+            SourceContent { content_hash: "h1".to_string(), url: "u1".to_string(), mime: "text/plain".to_string() },
+        );
+        forward.sources.insert(
+            "b".to_string(),
+            SourceContent { content_hash: "h2".to_string(), url: "u2".to_string(), mime: "text/plain".to_string() },
+        );
+        forward.compute_merkle_root();
+
+        let mut reversed = TruthPack::new("p".to_string(), "1.0.0".to_string(), "US-CA".to_string());
+        reversed.sources.insert(
+            "b".to_string(),
+            SourceContent { content_hash: "h2".to_string(), url: "u2".to_string(), mime: "text/plain".to_string() },
+        );
+        reversed.sources.insert(
+            "a".to_string(),
+            SourceContent { content_hash: "h1".to_string(), url: "u1".to_string(), mime: "text/plain".to_string() },
+        );
+        reversed.compute_merkle_root();
+
+        assert_eq!(forward.merkle_root, reversed.merkle_root);
+    }
+
+    #[test]
+    fn verify_passes_for_a_freshly_computed_root() {
+        let pack = pack_with_one_citation("quote a");
+        assert!(pack.verify());
+    }
+
+    #[test]
+    fn verify_fails_once_content_changes_without_recomputing() {
+        let mut pack = pack_with_one_citation("quote a");
+        pack.citations[0].quote = "a different quote entirely".to_string();
+        assert!(!pack.verify());
+    }
+
+    #[test]
+    fn provenance_citations_mirrors_pack_citations() {
+        let pack = pack_with_one_citation("quote a");
+        let citations = Provenance::citations(&pack);
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0].source_id, "doc-a");
+        assert_eq!(citations[0].quote, "quote a");
+    }
+
+    #[test]
+    fn provenance_merkle_root_matches_the_stored_field() {
+        let pack = pack_with_one_citation("quote a");
+        assert_eq!(Provenance::merkle_root(&pack).to_string(), pack.merkle_root);
     }
 }
\ No newline at end of file